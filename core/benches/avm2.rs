@@ -0,0 +1,37 @@
+//! Benchmarks of the AVM2 interpreter, running SWFs that do heavy work on
+//! every frame.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::{Player, PlayerBuilder};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Load one of the SWFs in `benches/swfs` and run its first frame.
+fn load_player(name: &str) -> Arc<Mutex<Player>> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("benches/swfs")
+        .join(name)
+        .join("test.swf");
+    let movie = SwfMovie::from_path(path, None).expect("Benchmark SWF should load");
+    let player = PlayerBuilder::new().with_movie(movie).build();
+    player.lock().unwrap().run_frame();
+    player
+}
+
+fn interpreter(c: &mut Criterion) {
+    let player = load_player("avm2_interpreter");
+    c.bench_function("avm2_interpreter", |b| {
+        b.iter(|| player.lock().unwrap().run_frame())
+    });
+}
+
+fn property_access(c: &mut Criterion) {
+    let player = load_player("avm2_property_access");
+    c.bench_function("avm2_property_access", |b| {
+        b.iter(|| player.lock().unwrap().run_frame())
+    });
+}
+
+criterion_group!(benches, interpreter, property_access);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	// Runs an arithmetic- and branch-heavy loop every frame, to measure the
+	// overhead of executing AVM2 instructions.
+	public class Test extends MovieClip {
+		public var result:Number = 0;
+
+		public function Test() {
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			var total:Number = 0;
+			for (var i:int = 0; i < 100000; i++) {
+				switch (i % 4) {
+					case 0:
+						total += step(i);
+						break;
+					case 1:
+						total -= i * 0.5;
+						break;
+					default:
+						if (i & 8) {
+							total += 1;
+						} else {
+							total *= 0.999;
+						}
+				}
+			}
+			result = total;
+		}
+
+		private function step(value:int):Number {
+			return value / 3 + (value >> 2);
+		}
+	}
+}
@@ -0,0 +1,58 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	// Reads, writes and calls properties of untyped objects every frame, to
+	// measure the cost of property lookups.
+	public class Test extends MovieClip {
+		public var result:Number = 0;
+
+		private var points:Array = [];
+
+		public function Test() {
+			for (var i:int = 0; i < 100; i++) {
+				points.push(i % 2 == 0 ? new Point2(i) : new Point3(i));
+			}
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			var total:Number = 0;
+			for (var round:int = 0; round < 500; round++) {
+				for (var i:int = 0; i < points.length; i++) {
+					var point:* = points[i];
+					point.x = point.x + 1;
+					total += point.y + point.length();
+				}
+			}
+			result = total;
+		}
+	}
+}
+
+class Point2 {
+	public var x:Number;
+	public var y:Number;
+
+	public function Point2(value:Number) {
+		x = value;
+		y = value * 2;
+	}
+
+	public function length():Number {
+		return Math.sqrt(x * x + y * y);
+	}
+}
+
+class Point3 extends Point2 {
+	public var z:Number;
+
+	public function Point3(value:Number) {
+		super(value);
+		z = value * 3;
+	}
+
+	override public function length():Number {
+		return Math.sqrt(x * x + y * y + z * z);
+	}
+}
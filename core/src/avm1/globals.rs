@@ -4,7 +4,9 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, TObject, Value};
-use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{
+    DisplayObject, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
+};
 use crate::string::{AvmString, WStr, WString};
 use gc_arena::Collect;
 use gc_arena::MutationContext;
@@ -42,6 +44,8 @@ mod matrix;
 pub(crate) mod mouse;
 pub(crate) mod movie_clip;
 mod movie_clip_loader;
+mod net_connection;
+mod net_stream;
 pub(crate) mod number;
 mod object;
 mod point;
@@ -51,6 +55,7 @@ pub(crate) mod shared_object;
 pub(crate) mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -61,6 +66,7 @@ mod transform;
 mod video;
 mod xml;
 mod xml_node;
+mod xml_socket;
 
 const GLOBAL_DECLS: &[Declaration] = declare_properties! {
     "trace" => method(trace; DONT_ENUM);
@@ -557,6 +563,10 @@ pub fn create_globals<'gc>(
     let load_vars_proto = load_vars::create_proto(gc_context, object_proto, function_proto);
     let local_connection_proto =
         local_connection::create_proto(gc_context, object_proto, function_proto);
+    let net_connection_proto =
+        net_connection::create_proto(gc_context, object_proto, function_proto);
+    let net_stream_proto = net_stream::create_proto(gc_context, object_proto, function_proto);
+    let xml_socket_proto = xml_socket::create_proto(gc_context, object_proto, function_proto);
     let matrix_proto = matrix::create_proto(gc_context, object_proto, function_proto);
     let point_proto = point::create_proto(gc_context, object_proto, function_proto);
     let rectangle_proto = rectangle::create_proto(gc_context, object_proto, function_proto);
@@ -636,6 +646,27 @@ pub fn create_globals<'gc>(
         function_proto,
         local_connection_proto,
     );
+    let net_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(net_connection::constructor),
+        constructor_to_fn!(net_connection::constructor),
+        function_proto,
+        net_connection_proto,
+    );
+    let net_stream = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(net_stream::constructor),
+        constructor_to_fn!(net_stream::constructor),
+        function_proto,
+        net_stream_proto,
+    );
+    let xml_socket = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(xml_socket::constructor),
+        constructor_to_fn!(xml_socket::constructor),
+        function_proto,
+        xml_socket_proto,
+    );
     let movie_clip = FunctionObject::constructor(
         gc_context,
         Executable::Native(movie_clip::constructor),
@@ -658,6 +689,13 @@ pub fn create_globals<'gc>(
         function_proto,
         text_field_proto,
     );
+    let style_sheet = style_sheet::create_constructor(gc_context, object_proto, function_proto);
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let text_format = FunctionObject::constructor(
         gc_context,
         Executable::Native(text_format::constructor),
@@ -944,6 +982,18 @@ pub fn create_globals<'gc>(
         local_connection.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "NetConnection",
+        net_connection.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(
+        gc_context,
+        "NetStream",
+        net_stream.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(
         gc_context,
         "MovieClip",
@@ -971,6 +1021,12 @@ pub fn create_globals<'gc>(
     );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "XML", xml.into(), Attribute::DONT_ENUM);
+    globals.define_value(
+        gc_context,
+        "XMLSocket",
+        xml_socket.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "String", string.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "Number", number.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "Boolean", boolean.into(), Attribute::DONT_ENUM);
@@ -1192,6 +1248,88 @@ fn get_depth<'gc>(
     Ok(Value::Undefined)
 }
 
+fn tab_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(interactive) = this.as_display_object().and_then(|o| o.as_interactive()) {
+        if let Some(tab_enabled) = interactive.tab_enabled_override() {
+            return Ok(tab_enabled.into());
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+fn set_tab_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(interactive) = this.as_display_object().and_then(|o| o.as_interactive()) {
+        let tab_enabled = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined => None,
+            value => Some(value.as_bool(activation.swf_version())),
+        };
+        interactive.set_tab_enabled_override(activation.context.gc_context, tab_enabled);
+    }
+    Ok(Value::Undefined)
+}
+
+fn tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(interactive) = this.as_display_object().and_then(|o| o.as_interactive()) {
+        if let Some(tab_index) = interactive.tab_index() {
+            return Ok(tab_index.into());
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(interactive) = this.as_display_object().and_then(|o| o.as_interactive()) {
+        let tab_index = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_i32(activation)?),
+        };
+        interactive.set_tab_index(activation.context.gc_context, tab_index);
+    }
+    Ok(Value::Undefined)
+}
+
+fn tab_children<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(interactive) = this.as_display_object().and_then(|o| o.as_interactive()) {
+        return Ok(interactive.tab_children().into());
+    }
+    Ok(Value::Undefined)
+}
+
+fn set_tab_children<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(interactive) = this.as_display_object().and_then(|o| o.as_interactive()) {
+        let tab_children = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .as_bool(activation.swf_version());
+        interactive.set_tab_children(activation.context.gc_context, tab_children);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn remove_display_object<'gc>(this: DisplayObject<'gc>, activation: &mut Activation<'_, 'gc>) {
     let depth = this.depth().wrapping_sub(0);
     // Can only remove positive depths (when offset by the AVM depth bias).
@@ -6,7 +6,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
 use crate::string::{AvmString, WStr};
 use gc_arena::{Collect, GcCell, MutationContext};
-use swf::Color;
+use swf::{BevelFilter, BevelFilterFlags, Color, Fixed16, Fixed8};
 
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(no_drop)]
@@ -75,6 +75,28 @@ impl BevelFilterObject {
         bevel_filter.set_knockout(activation, args.get(11))?;
         Ok(bevel_filter)
     }
+
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> BevelFilter {
+        let mut flags = BevelFilterFlags::COMPOSITE_SOURCE;
+        match self.type_ {
+            BevelFilterType::Inner => flags |= BevelFilterFlags::INNER_SHADOW,
+            BevelFilterType::Outer => {}
+            BevelFilterType::Full => flags |= BevelFilterFlags::ON_TOP,
+        }
+        flags.set(BevelFilterFlags::KNOCKOUT, self.knockout);
+        flags |= BevelFilterFlags::from_passes(self.quality as u8);
+        BevelFilter {
+            shadow_color: self.shadow,
+            highlight_color: self.highlight,
+            blur_x: Fixed16::from_f64(self.blur_x),
+            blur_y: Fixed16::from_f64(self.blur_y),
+            angle: Fixed16::from_f64(self.angle),
+            distance: Fixed16::from_f64(self.distance),
+            strength: Fixed8::from_f64(f64::from(self.strength) / 256.0),
+            flags,
+        }
+    }
 }
 
 impl Default for BevelFilterObject {
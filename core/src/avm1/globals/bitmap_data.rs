@@ -4,6 +4,7 @@ use super::matrix::object_to_matrix;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::globals::color_transform::ColorTransformObject;
 use crate::avm1::object::bitmap_data::BitmapDataObject;
+use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, TObject, Value};
 use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color};
@@ -14,6 +15,7 @@ use crate::display_object::TDisplayObject;
 use crate::swf::BlendMode;
 use crate::{avm1_stub, avm_error};
 use gc_arena::{GcCell, MutationContext};
+use ruffle_render::filters::Filter;
 use ruffle_render::transform::Transform;
 use std::str::FromStr;
 
@@ -580,12 +582,124 @@ pub fn draw<'gc>(
     Ok((-1).into())
 }
 
+/// Converts an AVM1 filter object into a filter that can be passed to the renderer.
+fn filter_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    filter_object: Object<'gc>,
+) -> Option<Filter> {
+    let filter = match filter_object.native() {
+        NativeObject::BlurFilter(filter) => Filter::BlurFilter(filter.read().filter()),
+        NativeObject::BevelFilter(filter) => Filter::BevelFilter(filter.read().filter()),
+        _ => {
+            if let Some(filter) = filter_object.as_glow_filter_object() {
+                Filter::GlowFilter(filter.filter())
+            } else if let Some(filter) = filter_object.as_drop_shadow_filter_object() {
+                Filter::DropShadowFilter(filter.filter())
+            } else if let Some(filter) = filter_object.as_color_matrix_filter_object() {
+                Filter::ColorMatrixFilter(filter.filter())
+            } else if let Some(filter) = filter_object.as_gradient_glow_filter_object() {
+                Filter::GradientGlowFilter(filter.filter())
+            } else if let Some(filter) = filter_object.as_gradient_bevel_filter_object() {
+                Filter::GradientBevelFilter(filter.filter())
+            } else if let Some(filter) = filter_object.as_convolution_filter_object() {
+                Filter::ConvolutionFilter(filter.filter())
+            } else if let Some(filter) = filter_object.as_displacement_map_filter_object() {
+                Filter::DisplacementMapFilter(filter.filter(&mut activation.context))
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(filter)
+}
+
 pub fn apply_filter<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "BitmapData", "applyFilter");
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        if !bitmap_data.disposed() {
+            let source_bitmap = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let source_rect = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let src_min_x = source_rect
+                .get("x", activation)?
+                .coerce_to_f64(activation)? as u32;
+            let src_min_y = source_rect
+                .get("y", activation)?
+                .coerce_to_f64(activation)? as u32;
+            let src_width = source_rect
+                .get("width", activation)?
+                .coerce_to_f64(activation)? as u32;
+            let src_height = source_rect
+                .get("height", activation)?
+                .coerce_to_f64(activation)? as u32;
+
+            let dest_point = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let dest_x = dest_point.get("x", activation)?.coerce_to_f64(activation)? as u32;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_f64(activation)? as u32;
+
+            let filter_object = args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let Some(filter) = filter_from_object(activation, filter_object) else {
+                avm1_stub!(
+                    activation,
+                    "BitmapData",
+                    "applyFilter",
+                    "with this filter type"
+                );
+                return Ok((-1).into());
+            };
+
+            if let Some(src_bitmap) = source_bitmap.as_bitmap_data_object() {
+                if !src_bitmap.disposed() {
+                    let mut src_bitmap_data = src_bitmap
+                        .bitmap_data()
+                        .write(activation.context.gc_context);
+                    let Some(source_handle) =
+                        src_bitmap_data.bitmap_handle(activation.context.renderer)
+                    else {
+                        tracing::warn!(
+                            "Ignoring BitmapData.applyFilter() with an undrawable source"
+                        );
+                        return Ok((-1).into());
+                    };
+                    src_bitmap_data.update_dirty_texture(activation.context.renderer);
+                    drop(src_bitmap_data);
+
+                    let dest_bitmap_data = bitmap_data
+                        .bitmap_data_wrapper()
+                        .overwrite_cpu_pixels_from_gpu(&mut activation.context);
+                    dest_bitmap_data
+                        .write(activation.context.gc_context)
+                        .apply_filter(
+                            &mut activation.context,
+                            source_handle,
+                            (src_min_x, src_min_y),
+                            (src_width, src_height),
+                            (dest_x, dest_y),
+                            filter,
+                        );
+                    return Ok(0.into());
+                }
+            }
+        }
+    }
+
     Ok((-1).into())
 }
 
@@ -5,6 +5,7 @@ use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::{BlurFilter, BlurFilterFlags, Fixed16};
 
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -14,6 +15,17 @@ pub struct BlurFilterObject {
     quality: i32,
 }
 
+impl BlurFilterObject {
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> BlurFilter {
+        BlurFilter {
+            blur_x: Fixed16::from_f64(self.blur_x),
+            blur_y: Fixed16::from_f64(self.blur_y),
+            flags: BlurFilterFlags::from_passes(self.quality as u8),
+        }
+    }
+}
+
 macro_rules! blur_filter_method {
     ($index:literal) => {
         |activation, this, args| method(activation, this, args, $index)
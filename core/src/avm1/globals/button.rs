@@ -42,6 +42,8 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "getDepth" => method(globals::get_depth; DONT_ENUM | DONT_DELETE | READ_ONLY | VERSION_6);
     "useHandCursor" => property(button_getter!(use_hand_cursor), button_setter!(set_use_hand_cursor));
     "blendMode" => property(button_getter!(blend_mode), button_setter!(set_blend_mode); DONT_DELETE | DONT_ENUM);
+    "tabEnabled" => property(globals::tab_enabled, globals::set_tab_enabled; DONT_DELETE | DONT_ENUM | VERSION_6);
+    "tabIndex" => property(globals::tab_index, globals::set_tab_index; DONT_DELETE | DONT_ENUM | VERSION_6);
 };
 
 pub fn create_proto<'gc>(
@@ -1,14 +1,13 @@
 //! AVM1 LoadVars object
-//! TODO: bytesLoaded, bytesTotal, contentType, addRequestHeader
+//! TODO: bytesLoaded, bytesTotal, contentType
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::ExecutionReason;
 use crate::avm1::property::Attribute;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, TObject, Value};
-use crate::avm1_stub;
-use crate::backend::navigator::{NavigationMethod, Request};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
+use crate::backend::navigator::{is_request_header_allowed, NavigationMethod, Request};
 use crate::string::AvmString;
 use gc_arena::MutationContext;
 
@@ -46,15 +45,76 @@ pub fn create_proto<'gc>(
     object.into()
 }
 
+/// Implements `LoadVars.addRequestHeader`, which takes either a header name
+/// and value, or an array of alternating header names and values.
 fn add_request_header<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "LoadVars", "addRequestHeader");
+    let new_headers: Vec<Value<'gc>> = match args {
+        [Value::Object(headers), ..] => {
+            let length = headers.length(activation)?;
+            (0..length)
+                .map(|i| headers.get_element(activation, i))
+                .collect()
+        }
+        [name, value, ..] => vec![*name, *value],
+        _ => return Ok(Value::Undefined),
+    };
+
+    // Headers are kept as alternating names and values in a hidden array.
+    let headers = match this.get("_customHeaders", activation)? {
+        Value::Object(headers) => headers,
+        _ => {
+            let headers: Object<'gc> = ArrayObject::empty(activation).into();
+            this.define_value(
+                activation.context.gc_context,
+                "_customHeaders",
+                headers.into(),
+                Attribute::DONT_DELETE | Attribute::DONT_ENUM,
+            );
+            headers
+        }
+    };
+
+    let length = headers.length(activation)?;
+    for (i, value) in new_headers.into_iter().enumerate() {
+        headers.set_element(activation, length + i as i32, value)?;
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Get the headers added to a `LoadVars` object with `addRequestHeader`,
+/// leaving out those that movies aren't allowed to set.
+fn request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let headers = match this.get("_customHeaders", activation)? {
+        Value::Object(headers) => headers,
+        _ => return Ok(Vec::new()),
+    };
+
+    let length = headers.length(activation)?;
+    let mut request_headers = Vec::with_capacity(length.max(0) as usize / 2);
+    for i in (0..length - 1).step_by(2) {
+        let name = headers
+            .get_element(activation, i)
+            .coerce_to_string(activation)?;
+        let value = headers
+            .get_element(activation, i + 1)
+            .coerce_to_string(activation)?;
+        let name = name.to_string();
+        if is_request_header_allowed(&name) {
+            request_headers.push((name, value.to_string()));
+        }
+    }
+
+    Ok(request_headers)
+}
+
 fn decode<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -255,13 +315,18 @@ fn spawn_load_var_fetch<'gc>(
     url: AvmString<'gc>,
     send_object: Option<(Object<'gc>, NavigationMethod)>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let request = if let Some((send_object, method)) = send_object {
-        // Send properties from `send_object`.
-        activation.object_into_request(send_object, url, Some(method))
+    let (mut request, headers) = if let Some((send_object, method)) = send_object {
+        // Send properties and headers from `send_object`.
+        let request = activation.object_into_request(send_object, url, Some(method));
+        (request, request_headers(activation, send_object)?)
     } else {
         // Not sending any parameters.
-        Request::get(url.to_utf8_lossy().into_owned())
+        let request = Request::get(url.to_utf8_lossy().into_owned());
+        (request, request_headers(activation, loader_object)?)
     };
+    for (name, value) in headers {
+        request.add_header(name, value);
+    }
 
     let future = activation.context.load_manager.load_form_into_load_vars(
         activation.context.player.clone(),
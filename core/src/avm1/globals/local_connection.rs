@@ -2,38 +2,127 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::shared_object::serialize_value;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, Value};
+use crate::backend::local_connection::LocalConnectionMessage;
 use crate::display_object::TDisplayObject;
+use crate::local_connection::{
+    encode_arguments, is_secure_movie, qualify_name, LocalConnections, MAX_ARGUMENTS_SIZE,
+};
 use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
 use gc_arena::MutationContext;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "domain" => method(domain; DONT_DELETE | READ_ONLY);
+    "connect" => method(connect; DONT_DELETE | READ_ONLY);
+    "send" => method(send; DONT_DELETE | READ_ONLY);
+    "close" => method(close; DONT_DELETE | READ_ONLY);
 };
 
+/// The domain of the movie that is running the current code.
+fn movie_domain(activation: &mut Activation<'_, '_>) -> Option<String> {
+    let movie = activation.base_clip().movie();
+    crate::local_connection::movie_domain(movie.url())
+}
+
 pub fn domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    match movie_domain(activation) {
+        Some(domain) => Ok(AvmString::new_utf8(activation.context.gc_context, domain).into()),
+        None => {
+            tracing::error!("LocalConnection::domain: Unable to parse movie URL");
+            Ok(Value::Null)
+        }
+    }
+}
+
+/// Qualify a connection name given as a method argument with the domain of
+/// the movie.
+fn connection_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: Option<&Value<'gc>>,
+) -> Result<Option<String>, Error<'gc>> {
+    let name = match name {
+        Some(name @ Value::String(_)) => name.coerce_to_string(activation)?,
+        _ => return Ok(None),
+    };
+    if name.is_empty() {
+        return Ok(None);
+    }
+
+    let domain = movie_domain(activation).unwrap_or_else(|| "localhost".to_owned());
+    Ok(Some(qualify_name(&domain, &name.to_utf8_lossy())))
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match connection_name(activation, args.get(0))? {
+        Some(name) => name,
+        None => return Ok(false.into()),
+    };
+
     let movie = activation.base_clip().movie();
+    Ok(LocalConnections::connect(&mut activation.context, this.into(), name, movie.url()).into())
+}
 
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
-        tracing::error!("LocalConnection::domain: Unable to parse movie URL");
-        return Ok(Value::Null);
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection_name = match connection_name(activation, args.get(0))? {
+        Some(name) => name,
+        None => return Ok(false.into()),
+    };
+    let method_name = match args.get(1) {
+        Some(name @ Value::String(_)) => name.coerce_to_string(activation)?,
+        _ => return Ok(false.into()),
+    };
+    if method_name.is_empty() {
+        return Ok(false.into());
+    }
+
+    let arguments = args
+        .iter()
+        .skip(2)
+        .map(|value| serialize_value(activation, *value).unwrap_or(AmfValue::Undefined))
+        .collect();
+    let arguments = encode_arguments(arguments, AMFVersion::AMF0);
+    if arguments.len() > MAX_ARGUMENTS_SIZE {
+        return Ok(false.into());
+    }
+
+    let movie = activation.base_clip().movie();
+    let message = LocalConnectionMessage {
+        connection_name,
+        method_name: method_name.to_string(),
+        arguments,
+        sender_domain: movie_domain(activation).unwrap_or_else(|| "localhost".to_owned()),
+        sender_secure: is_secure_movie(movie.url()),
     };
+    activation
+        .context
+        .local_connections
+        .send(this.into(), message);
 
-    Ok(Value::String(domain))
+    Ok(true.into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    LocalConnections::close(&mut activation.context, this.into());
+    Ok(Value::Undefined)
 }
 
 pub fn constructor<'gc>(
@@ -53,3 +142,173 @@ pub fn create_proto<'gc>(
     define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
     object.into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::function::{Executable, FunctionObject, NativeFunction};
+    use crate::avm1::test_utils::with_avm;
+    use crate::avm1::{Attribute, TObject};
+    use crate::avm2::object::TObject as Avm2TObject;
+    use crate::avm2::{
+        Activation as Avm2Activation, Namespace as Avm2Namespace, Object as Avm2Object,
+        QName as Avm2QName,
+    };
+
+    /// Records the level of the last status that an object got on its
+    /// `lastLevel` property.
+    fn on_status<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let level = match args.get(0) {
+            Some(Value::Object(info)) => info.get("level", activation)?,
+            _ => Value::Undefined,
+        };
+        this.set("lastLevel", level, activation)?;
+        Ok(Value::Undefined)
+    }
+
+    /// An `allowDomain` method that only allows `example.com`.
+    fn allow_example_com<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        _this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let domain = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+        Ok((domain.to_utf8_lossy() == "example.com").into())
+    }
+
+    fn define_method<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        name: &'static str,
+        method: NativeFunction,
+    ) {
+        let fn_proto = activation.context.avm1.prototypes().function;
+        let function = FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(method),
+            fn_proto,
+            fn_proto,
+        );
+        object.define_value(
+            activation.context.gc_context,
+            name,
+            function.into(),
+            Attribute::empty(),
+        );
+    }
+
+    fn new_object<'gc>(activation: &mut Activation<'_, 'gc>) -> Object<'gc> {
+        let object_proto = activation.context.avm1.prototypes().object;
+        ScriptObject::new(activation.context.gc_context, Some(object_proto)).into()
+    }
+
+    /// Send a message from a movie of `sender_domain` to the `_test`
+    /// connection, returning the status level that `sender` got.
+    fn send_from<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        sender: Object<'gc>,
+        sender_domain: &str,
+    ) -> Result<String, Error<'gc>> {
+        let message = LocalConnectionMessage {
+            connection_name: "_test".to_owned(),
+            method_name: "hello".to_owned(),
+            arguments: encode_arguments(vec![], AMFVersion::AMF0),
+            sender_domain: sender_domain.to_owned(),
+            sender_secure: false,
+        };
+        activation
+            .context
+            .local_connections
+            .send(sender.into(), message);
+        LocalConnections::update_connections(&mut activation.context);
+
+        let level = sender.get("lastLevel", activation)?;
+        Ok(level.coerce_to_string(activation)?.to_string())
+    }
+
+    #[test]
+    fn avm1_receiver_allows_domains_in_callback() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            let sender = new_object(activation);
+            define_method(activation, sender, "onStatus", on_status);
+            let receiver = new_object(activation);
+            assert!(LocalConnections::connect(
+                &mut activation.context,
+                receiver.into(),
+                "_test".to_owned(),
+                "http://localhost/receiver.swf",
+            ));
+
+            assert_eq!(send_from(activation, sender, "localhost")?, "status");
+            assert_eq!(send_from(activation, sender, "example.com")?, "error");
+
+            define_method(activation, receiver, "allowDomain", allow_example_com);
+            assert_eq!(send_from(activation, sender, "example.com")?, "status");
+            assert_eq!(send_from(activation, sender, "example.org")?, "error");
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn avm2_receiver_allows_domains_from_avm1() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            let sender = new_object(activation);
+            define_method(activation, sender, "onStatus", on_status);
+
+            let receiver = avm2_local_connection(&mut Avm2Activation::from_nothing(
+                activation.context.reborrow(),
+            ));
+            assert!(LocalConnections::connect(
+                &mut activation.context,
+                receiver.into(),
+                "_test".to_owned(),
+                "http://localhost/receiver.swf",
+            ));
+
+            assert_eq!(send_from(activation, sender, "localhost")?, "status");
+            assert_eq!(send_from(activation, sender, "example.com")?, "error");
+
+            receiver
+                .call_public_property(
+                    "allowDomain",
+                    &["example.com".into()],
+                    &mut Avm2Activation::from_nothing(activation.context.reborrow()),
+                )
+                .expect("allowDomain should succeed");
+            assert_eq!(send_from(activation, sender, "example.com")?, "status");
+            assert_eq!(send_from(activation, sender, "example.org")?, "error");
+
+            receiver
+                .call_public_property(
+                    "allowInsecureDomain",
+                    &["*".into()],
+                    &mut Avm2Activation::from_nothing(activation.context.reborrow()),
+                )
+                .expect("allowInsecureDomain should succeed");
+            assert_eq!(send_from(activation, sender, "example.org")?, "status");
+
+            Ok(())
+        });
+    }
+
+    fn avm2_local_connection<'gc>(activation: &mut Avm2Activation<'_, 'gc>) -> Avm2Object<'gc> {
+        let namespace = Avm2Namespace::package("flash.net", activation.context.gc_context);
+        let globals = activation.avm2().globals;
+        globals
+            .get_defined_value(activation, Avm2QName::new(namespace, "LocalConnection"))
+            .ok()
+            .and_then(|class| class.as_object())
+            .and_then(|class| class.as_class_object())
+            .expect("LocalConnection should be defined")
+            .construct(activation, &[])
+            .expect("LocalConnection should be constructible")
+    }
+}
@@ -111,6 +111,9 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "useHandCursor" => property(mc_getter!(use_hand_cursor), mc_setter!(set_use_hand_cursor); DONT_DELETE | DONT_ENUM);
     "blendMode" => property(mc_getter!(blend_mode), mc_setter!(set_blend_mode); DONT_DELETE | DONT_ENUM);
     "scrollRect" => property(mc_getter!(scroll_rect), mc_setter!(set_scroll_rect); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "tabEnabled" => property(globals::tab_enabled, globals::set_tab_enabled; DONT_DELETE | DONT_ENUM | VERSION_6);
+    "tabIndex" => property(globals::tab_index, globals::set_tab_index; DONT_DELETE | DONT_ENUM | VERSION_6);
+    "tabChildren" => property(globals::tab_children, globals::set_tab_children; DONT_DELETE | DONT_ENUM | VERSION_6);
 };
 
 /// Implements `MovieClip`
@@ -0,0 +1,67 @@
+//! NetConnection class
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Attribute, ExecutionReason, Object, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::string::AvmString;
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `NetConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only progressive downloads, which connect to `null`, are supported.
+    if matches!(args.get(0), Some(Value::Null)) {
+        return Ok(true.into());
+    }
+
+    avm1_stub!(activation, "NetConnection", "connect", "with server URL");
+
+    // Tell the script that the connection failed rather than leaving it waiting.
+    let info_object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for (key, value) in [("code", "NetConnection.Connect.Failed"), ("level", "error")] {
+        info_object.define_value(
+            activation.context.gc_context,
+            key,
+            AvmString::from(value).into(),
+            Attribute::empty(),
+        );
+    }
+    this.call_method(
+        "onStatus".into(),
+        &[info_object.into()],
+        activation,
+        ExecutionReason::Special,
+    )?;
+
+    Ok(false.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
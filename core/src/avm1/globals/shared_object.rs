@@ -9,8 +9,9 @@ use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::avm1_stub;
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
+use enumset::EnumSet;
 use flash_lso::types::Value as AmfValue;
-use flash_lso::types::{AMFVersion, Element, Lso};
+use flash_lso::types::{AMFVersion, ClassDefinition, Element, Lso};
 use gc_arena::MutationContext;
 use std::borrow::Cow;
 
@@ -55,7 +56,7 @@ pub fn get_disk_usage<'gc>(
 }
 
 /// Serialize a Value to an AmfValue
-fn serialize_value<'gc>(
+pub fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     elem: Value<'gc>,
 ) -> Option<AmfValue> {
@@ -88,12 +89,45 @@ fn serialize_value<'gc>(
             } else {
                 let mut object_body = Vec::new();
                 recursive_serialize(activation, o, &mut object_body);
-                Some(AmfValue::Object(object_body, None))
+                let class_definition =
+                    registered_class_name(activation, o).map(|name| ClassDefinition {
+                        name: name.to_utf8_lossy().into_owned(),
+                        attributes: EnumSet::empty(),
+                        static_properties: Vec::new(),
+                    });
+                Some(AmfValue::Object(object_body, class_definition))
             }
         }
     }
 }
 
+/// The symbol that the constructor of an object was registered for with
+/// `Object.registerClass`, if any.
+fn registered_class_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Option<AvmString<'gc>> {
+    let proto = match object.proto(activation) {
+        Value::Object(proto) => proto,
+        _ => return None,
+    };
+
+    let constructors: Vec<_> = activation
+        .context
+        .avm1
+        .registered_constructors(activation.swf_version())
+        .collect();
+    for (symbol, constructor) in constructors {
+        if let Ok(Value::Object(prototype)) = constructor.get("prototype", activation) {
+            if Object::ptr_eq(prototype, proto) {
+                return Some(symbol);
+            }
+        }
+    }
+
+    None
+}
+
 /// Serialize an Object and any children to a JSON object
 fn recursive_serialize<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -111,11 +145,14 @@ fn recursive_serialize<'gc>(
 }
 
 /// Deserialize a AmfValue to a Value
-fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue) -> Value<'gc> {
+pub fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue) -> Value<'gc> {
     match val {
         AmfValue::Null => Value::Null,
         AmfValue::Undefined => Value::Undefined,
         AmfValue::Number(f) => (*f).into(),
+        // AMF3 values are only sent by AVM2 movies, over `LocalConnection`.
+        AmfValue::Integer(num) => (*num).into(),
+        AmfValue::AMF3(val) => deserialize_value(activation, val),
         AmfValue::String(s) => Value::String(AvmString::new_utf8(activation.context.gc_context, s)),
         AmfValue::Bool(b) => (*b).into(),
         AmfValue::ECMAArray(_, associative, len) => {
@@ -143,12 +180,33 @@ fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue)
                 Value::Undefined
             }
         }
-        AmfValue::Object(elements, _) => {
-            // Deserialize Object
-            let obj = ScriptObject::new(
-                activation.context.gc_context,
-                Some(activation.context.avm1.prototypes().object),
-            );
+        AmfValue::Object(elements, class_definition) => {
+            // Objects of a registered class are created with its prototype, and
+            // its constructor is called after their properties are set.
+            let constructor = class_definition
+                .as_ref()
+                .filter(|class_definition| !class_definition.name.is_empty())
+                .and_then(|class_definition| {
+                    let name =
+                        AvmString::new_utf8(activation.context.gc_context, &class_definition.name);
+                    activation
+                        .context
+                        .avm1
+                        .get_registered_constructor(activation.swf_version(), name)
+                        .copied()
+                });
+            let prototype = constructor
+                .and_then(|constructor| constructor.get("prototype", activation).ok())
+                .and_then(|prototype| match prototype {
+                    Value::Object(prototype) => Some(prototype),
+                    _ => None,
+                })
+                .unwrap_or(activation.context.avm1.prototypes().object);
+            let obj = match prototype.create_bare_object(activation, prototype) {
+                Ok(obj) => obj,
+                Err(_) => return Value::Undefined,
+            };
+
             for entry in elements {
                 let value = deserialize_value(activation, entry.value());
                 let name = AvmString::new_utf8(activation.context.gc_context, &entry.name);
@@ -159,6 +217,12 @@ fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue)
                     Attribute::empty(),
                 );
             }
+
+            if let Some(constructor) = constructor {
+                if let Err(e) = constructor.construct_on_existing(activation, obj, &[]) {
+                    tracing::error!("Error constructing deserialized object: {}", e);
+                }
+            }
             obj.into()
         }
         AmfValue::Date(time, _) => {
@@ -0,0 +1,298 @@
+//! AVM1 TextField.StyleSheet object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, ExecutionReason, FunctionObject};
+use crate::avm1::globals::text_field::new_text_format;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
+use crate::backend::navigator::Request;
+use crate::html::{parse_css, Style, StyleSheet, STYLE_PROPERTIES};
+use crate::string::{AvmString, WStr};
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getStyle" => method(get_style; DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(set_style; DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(get_style_names; DONT_ENUM | DONT_DELETE);
+    "clear" => method(clear; DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(parse_css_method; DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+    "load" => method(load; DONT_ENUM | DONT_DELETE);
+    "onData" => method(on_data; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `TextField.StyleSheet`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = new_object(activation);
+    this.define_value(
+        activation.context.gc_context,
+        "_css",
+        css.into(),
+        Attribute::DONT_ENUM,
+    );
+    Ok(this.into())
+}
+
+pub fn create_constructor<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let style_sheet_proto = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, style_sheet_proto, fn_proto);
+    FunctionObject::constructor(
+        gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        style_sheet_proto.into(),
+    )
+}
+
+fn new_object<'gc>(activation: &mut Activation<'_, 'gc>) -> Object<'gc> {
+    let proto = activation.context.avm1.prototypes().object;
+    ScriptObject::new(activation.context.gc_context, Some(proto)).into()
+}
+
+/// Get the object holding the style object of each selector, by lowercase
+/// name.
+fn css_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match this.get("_css", activation)? {
+        Value::Object(css) => Ok(css),
+        _ => {
+            let css = new_object(activation);
+            this.define_value(
+                activation.context.gc_context,
+                "_css",
+                css.into(),
+                Attribute::DONT_ENUM,
+            );
+            Ok(css)
+        }
+    }
+}
+
+/// Copy the properties of a style object.
+fn copy_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let copy = new_object(activation);
+    for key in style.get_keys(activation) {
+        let value = style.get(key, activation)?;
+        copy.set(key, value, activation)?;
+    }
+    Ok(copy)
+}
+
+/// Build a style from the properties of a style object.
+pub fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Style, Error<'gc>> {
+    let mut style = Style::default();
+    for name in STYLE_PROPERTIES {
+        match object.get(*name, activation)? {
+            Value::Undefined | Value::Null => {}
+            value => {
+                let value = value.coerce_to_string(activation)?;
+                style.set_property(WStr::from_units(name.as_bytes()), &value);
+            }
+        }
+    }
+
+    Ok(style)
+}
+
+/// Read the styles of a `TextField.StyleSheet` object.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+    let css = css_object(activation, object)?;
+    for name in css.get_keys(activation) {
+        if let Value::Object(style) = css.get(name, activation)? {
+            let style = style_from_object(activation, style)?;
+            style_sheet.set_style(&name, style);
+        }
+    }
+
+    Ok(style_sheet)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new(activation.context.gc_context, name.to_ascii_lowercase());
+
+    let css = css_object(activation, this)?;
+    match css.get(name, activation)? {
+        Value::Object(style) => Ok(copy_style(activation, style)?.into()),
+        _ => Ok(Value::Null),
+    }
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new(activation.context.gc_context, name.to_ascii_lowercase());
+
+    let css = css_object(activation, this)?;
+    match args.get(1) {
+        Some(Value::Object(style)) => {
+            let style = copy_style(activation, *style)?;
+            css.set(name, style.into(), activation)?;
+        }
+        _ => {
+            css.delete(activation, name);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = css_object(activation, this)?;
+    let names = css.get_keys(activation);
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        names.into_iter().map(Value::from),
+    )
+    .into())
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = new_object(activation);
+    this.set("_css", css.into(), activation)?;
+    Ok(Value::Undefined)
+}
+
+fn parse_css_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css_text = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let rules = match parse_css(&css_text) {
+        Some(rules) => rules,
+        None => return Ok(false.into()),
+    };
+
+    // Rules add to the existing declarations of their selector.
+    let css = css_object(activation, this)?;
+    for (selector, declarations) in rules {
+        let selector = AvmString::new(activation.context.gc_context, selector.to_ascii_lowercase());
+        let style = match css.get(selector, activation)? {
+            Value::Object(style) => style,
+            _ => {
+                let style = new_object(activation);
+                css.set(selector, style.into(), activation)?;
+                style
+            }
+        };
+
+        for (name, value) in declarations {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = AvmString::new(activation.context.gc_context, value);
+            style.set(name, value.into(), activation)?;
+        }
+    }
+
+    Ok(true.into())
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style = match args.get(0) {
+        Some(Value::Object(style)) => style_from_object(activation, *style)?,
+        _ => return Ok(Value::Null),
+    };
+
+    Ok(new_text_format(activation, style.format).into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(val) => val.coerce_to_string(activation)?,
+        None => return Ok(false.into()),
+    };
+
+    // The loaded document is passed to `onData`, as with `LoadVars`.
+    let future = activation.context.load_manager.load_form_into_load_vars(
+        activation.context.player.clone(),
+        this,
+        Request::get(url.to_utf8_lossy().into_owned()),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Default implementation parses the document and forwards to onLoad.
+    let success = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => false,
+        val => this
+            .call_method(
+                "parseCSS".into(),
+                &[*val],
+                activation,
+                ExecutionReason::FunctionCall,
+            )?
+            .as_bool(activation.swf_version()),
+    };
+
+    this.call_method(
+        "onLoad".into(),
+        &[success.into()],
+        activation,
+        ExecutionReason::FunctionCall,
+    )?;
+
+    Ok(Value::Undefined)
+}
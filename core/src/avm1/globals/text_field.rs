@@ -1,5 +1,6 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::style_sheet;
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{globals, Object, ScriptObject, TObject, Value};
@@ -76,6 +77,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "password" => property(tf_getter!(password), tf_setter!(set_password));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet));
     "text" => property(tf_getter!(text), tf_setter!(set_text));
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color));
     "textHeight" => property(tf_getter!(text_height));
@@ -87,6 +89,8 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "gridFitType" => property(tf_getter!(grid_fit_type), tf_setter!(set_grid_fit_type));
     "sharpness" => property(tf_getter!(sharpness), tf_setter!(set_sharpness));
     "thickness" => property(tf_getter!(thickness), tf_setter!(set_thickness));
+    "tabEnabled" => property(globals::tab_enabled, globals::set_tab_enabled; DONT_DELETE | DONT_ENUM | VERSION_6);
+    "tabIndex" => property(globals::tab_index, globals::set_tab_index; DONT_DELETE | DONT_ENUM | VERSION_6);
 };
 
 /// Implements `TextField`
@@ -126,7 +130,7 @@ pub fn set_password<'gc>(
     Ok(())
 }
 
-fn new_text_format<'gc>(
+pub fn new_text_format<'gc>(
     activation: &mut Activation<'_, 'gc>,
     text_format: TextFormat,
 ) -> ScriptObject<'gc> {
@@ -380,6 +384,33 @@ pub fn set_html_text<'gc>(
     Ok(())
 }
 
+pub fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .avm1_style_sheet()
+        .map_or(Value::Undefined, |style_sheet| style_sheet.into()))
+}
+
+pub fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let (object, style_sheet) = match value {
+        Value::Object(object) => (
+            Some(object),
+            Some(style_sheet::style_sheet_from_object(activation, object)?),
+        ),
+        _ => (None, None),
+    };
+
+    this.set_avm1_style_sheet(object, &mut activation.context);
+    this.set_style_sheet(style_sheet, &mut activation.context);
+    Ok(())
+}
+
 pub fn background<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
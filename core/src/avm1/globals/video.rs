@@ -2,11 +2,16 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
-use crate::avm1::object::Object;
-use crate::avm1::value::Value;
-use crate::avm1::ScriptObject;
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "attachVideo" => method(attach_video; DONT_ENUM | DONT_DELETE);
+};
+
 /// Implements `Video`
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc>,
@@ -16,11 +21,36 @@ pub fn constructor<'gc>(
     Ok(Value::Undefined)
 }
 
+fn attach_video<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let video = match this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        Some(video) => video,
+        None => return Ok(Value::Undefined),
+    };
+
+    let source = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation);
+
+    if let NativeObject::NetStream(ns) = source.native() {
+        video.attach_netstream(&mut activation.context, ns);
+    } else {
+        tracing::warn!("Video.attachVideo: argument is not a NetStream");
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
-    _fn_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
 ) -> Object<'gc> {
     let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
     object.into()
 }
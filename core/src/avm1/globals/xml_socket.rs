@@ -0,0 +1,130 @@
+//! XMLSocket class
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::ExecutionReason;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::display_object::TDisplayObject;
+use crate::socket::{default_host, SocketKind};
+use gc_arena::MutationContext;
+use std::time::Duration;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
+    "onData" => method(on_data; DONT_ENUM | DONT_DELETE);
+};
+
+/// The default connection timeout, in milliseconds.
+const DEFAULT_TIMEOUT: u32 = 20000;
+
+/// Implements `XMLSocket`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+/// Implements `XMLSocket.connect`
+///
+/// Returns `false` if the connection can't be attempted; the outcome of the
+/// attempt is reported to `onConnect`.
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let host = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Null | Value::Undefined => default_host(activation.base_clip().movie().url()),
+        host => host.coerce_to_string(activation)?.to_string(),
+    };
+    let port = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    // Ports below 1024 are reserved.
+    let port = match u16::try_from(port) {
+        Ok(port) if port >= 1024 => port,
+        _ => return Ok(false.into()),
+    };
+
+    let timeout = match this.get("timeout", activation)? {
+        Value::Undefined => DEFAULT_TIMEOUT,
+        timeout => timeout.coerce_to_u32(activation)?,
+    };
+
+    activation.context.sockets.connect(
+        activation.context.navigator,
+        this.into(),
+        SocketKind::Xml,
+        host,
+        port,
+        Duration::from_millis(timeout.into()),
+    );
+
+    Ok(true.into())
+}
+
+/// Implements `XMLSocket.send`
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let data = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    activation
+        .context
+        .sockets
+        .send(this.into(), data.to_utf8_lossy().into_owned().into_bytes());
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.close`
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.sockets.close(this.into());
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.onData`, which parses the received message and
+/// passes it on to `onXML`.
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let source = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let xml_constructor = activation.context.avm1.prototypes().xml_constructor;
+    let xml = xml_constructor.construct(activation, &[source])?;
+    this.call_method(
+        "onXML".into(),
+        &[xml],
+        activation,
+        ExecutionReason::FunctionCall,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
@@ -2,6 +2,7 @@ use crate::add_field_accessors;
 use crate::avm1::{Object, ScriptObject, TObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::ColorMatrixFilter;
 
 use std::fmt;
 
@@ -31,6 +32,13 @@ impl fmt::Debug for ColorMatrixFilterObject<'_> {
 impl<'gc> ColorMatrixFilterObject<'gc> {
     add_field_accessors!([set_matrix, matrix, matrix, [f64; 4 * 5]],);
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> ColorMatrixFilter {
+        ColorMatrixFilter {
+            matrix: self.0.read().matrix.map(|value| value as f32),
+        }
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         ColorMatrixFilterObject(GcCell::allocate(
             gc_context,
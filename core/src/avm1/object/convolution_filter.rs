@@ -2,6 +2,7 @@ use crate::add_field_accessors;
 use crate::avm1::{Object, ScriptObject, TObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::{Color, ConvolutionFilter, ConvolutionFilterFlags, Fixed16};
 
 use std::fmt;
 
@@ -79,6 +80,28 @@ impl<'gc> ConvolutionFilterObject<'gc> {
         self.update_matrix_length(gc_context);
     }
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> ConvolutionFilter {
+        let this = self.0.read();
+        let mut flags = ConvolutionFilterFlags::empty();
+        flags.set(ConvolutionFilterFlags::CLAMP, this.clamp);
+        flags.set(ConvolutionFilterFlags::PRESERVE_ALPHA, this.preserve_alpha);
+        let mut matrix: Vec<Fixed16> = this.matrix.iter().map(|&v| Fixed16::from_f64(v)).collect();
+        matrix.resize(
+            this.matrix_x as usize * this.matrix_y as usize,
+            Fixed16::ZERO,
+        );
+        ConvolutionFilter {
+            num_matrix_rows: this.matrix_y,
+            num_matrix_cols: this.matrix_x,
+            matrix,
+            divisor: Fixed16::from_f64(this.divisor),
+            bias: Fixed16::from_f64(this.bias),
+            default_color: Color::from_rgb(this.color, (this.alpha * 255.0) as u8),
+            flags,
+        }
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         ConvolutionFilterObject(GcCell::allocate(
             gc_context,
@@ -1,8 +1,11 @@
 use crate::add_field_accessors;
 use crate::avm1::{Object, ScriptObject, TObject};
+use crate::context::UpdateContext;
 use crate::impl_custom_object;
 use crate::string::WStr;
 use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode as FilterMode};
+use swf::Color;
 
 use std::fmt;
 
@@ -93,6 +96,39 @@ impl<'gc> DisplacementMapFilterObject<'gc> {
         [set_scale_y, scale_y, scale_y, f64],
     );
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self, context: &mut UpdateContext<'_, 'gc>) -> DisplacementMapFilter {
+        let this = self.0.read();
+        let map_bitmap = this
+            .map_bitmap
+            .and_then(|bitmap| bitmap.as_bitmap_data_object())
+            .filter(|bitmap| !bitmap.disposed())
+            .and_then(|bitmap| {
+                let mut bitmap_data = bitmap.bitmap_data().write(context.gc_context);
+                let handle = bitmap_data.bitmap_handle(context.renderer);
+                if handle.is_some() {
+                    // The map is sampled on the GPU, so it needs any pending CPU changes
+                    bitmap_data.update_dirty_texture(context.renderer);
+                }
+                handle
+            });
+        DisplacementMapFilter {
+            color: Color::from_rgb(this.color, (this.alpha * 255.0) as u8),
+            component_x: this.component_x as u8,
+            component_y: this.component_y as u8,
+            map_bitmap,
+            map_point: this.map_point,
+            mode: match this.mode {
+                DisplacementMapFilterMode::Wrap => FilterMode::Wrap,
+                DisplacementMapFilterMode::Clamp => FilterMode::Clamp,
+                DisplacementMapFilterMode::Ignore => FilterMode::Ignore,
+                DisplacementMapFilterMode::Color => FilterMode::Color,
+            },
+            scale_x: this.scale_x as f32,
+            scale_y: this.scale_y as f32,
+        }
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         DisplacementMapFilterObject(GcCell::allocate(
             gc_context,
@@ -2,6 +2,7 @@ use crate::add_field_accessors;
 use crate::avm1::{Object, ScriptObject, TObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::{Color, DropShadowFilter, DropShadowFilterFlags, Fixed16, Fixed8};
 
 use std::fmt;
 
@@ -63,6 +64,25 @@ impl<'gc> DropShadowFilterObject<'gc> {
         [set_strength, strength, strength, f64],
     );
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> DropShadowFilter {
+        let this = self.0.read();
+        let mut flags = DropShadowFilterFlags::empty();
+        flags.set(DropShadowFilterFlags::COMPOSITE_SOURCE, !this.hide_object);
+        flags.set(DropShadowFilterFlags::INNER_SHADOW, this.inner);
+        flags.set(DropShadowFilterFlags::KNOCKOUT, this.knockout);
+        flags |= DropShadowFilterFlags::from_passes(this.quality.clamp(0, 15) as u8);
+        DropShadowFilter {
+            color: Color::from_rgb(this.color, (this.alpha * 255.0) as u8),
+            angle: Fixed16::from_f64(this.angle.to_radians()),
+            blur_x: Fixed16::from_f64(this.blur_x),
+            blur_y: Fixed16::from_f64(this.blur_y),
+            distance: Fixed16::from_f64(this.distance),
+            strength: Fixed8::from_f64(this.strength),
+            flags,
+        }
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         DropShadowFilterObject(GcCell::allocate(
             gc_context,
@@ -2,6 +2,7 @@ use crate::add_field_accessors;
 use crate::avm1::{Object, ScriptObject, TObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::{Color, Fixed16, Fixed8, GlowFilter, GlowFilterFlags};
 
 use std::fmt;
 
@@ -54,6 +55,22 @@ impl<'gc> GlowFilterObject<'gc> {
         [set_strength, strength, strength, f64],
     );
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> GlowFilter {
+        let this = self.0.read();
+        let mut flags = GlowFilterFlags::COMPOSITE_SOURCE;
+        flags.set(GlowFilterFlags::INNER_GLOW, this.inner);
+        flags.set(GlowFilterFlags::KNOCKOUT, this.knockout);
+        flags |= GlowFilterFlags::from_passes(this.quality.clamp(0, 15) as u8);
+        GlowFilter {
+            color: Color::from_rgb(this.color as u32, (this.alpha * 255.0) as u8),
+            blur_x: Fixed16::from_f64(this.blur_x),
+            blur_y: Fixed16::from_f64(this.blur_y),
+            strength: Fixed8::from_f64(this.strength),
+            flags,
+        }
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         GlowFilterObject(GcCell::allocate(
             gc_context,
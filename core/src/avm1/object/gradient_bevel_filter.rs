@@ -1,9 +1,11 @@
 use crate::add_field_accessors;
 use crate::avm1::globals::bevel_filter::BevelFilterType;
+use crate::avm1::object::gradient_glow_filter::gradient_filter;
 use crate::avm1::{Object, ScriptObject, TObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::fmt;
+use swf::GradientFilter;
 
 /// A GradientBevelFilter
 #[derive(Clone, Copy, Collect)]
@@ -75,6 +77,24 @@ impl<'gc> GradientBevelFilterObject<'gc> {
         self.0.read().ratios.clone()
     }
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> GradientFilter {
+        let this = self.0.read();
+        gradient_filter(
+            &this.colors,
+            &this.alphas,
+            &this.ratios,
+            this.blur_x,
+            this.blur_y,
+            this.angle,
+            this.distance,
+            this.strength,
+            this.quality,
+            this.type_,
+            this.knockout,
+        )
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         GradientBevelFilterObject(GcCell::allocate(
             gc_context,
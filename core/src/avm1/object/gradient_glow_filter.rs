@@ -4,6 +4,50 @@ use crate::avm1::{Object, ScriptObject, TObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::fmt;
+use swf::{Color, Fixed16, Fixed8, GradientFilter, GradientFilterFlags, GradientRecord};
+
+/// Builds the renderer filter shared by `GradientGlowFilter` and `GradientBevelFilter`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gradient_filter(
+    colors: &[u32],
+    alphas: &[f64],
+    ratios: &[u8],
+    blur_x: f64,
+    blur_y: f64,
+    angle: f64,
+    distance: f64,
+    strength: f64,
+    quality: i32,
+    type_: BevelFilterType,
+    knockout: bool,
+) -> GradientFilter {
+    let colors = colors
+        .iter()
+        .zip(alphas)
+        .zip(ratios)
+        .map(|((&color, &alpha), &ratio)| GradientRecord {
+            ratio,
+            color: Color::from_rgb(color, (alpha * 255.0) as u8),
+        })
+        .collect();
+    let mut flags = GradientFilterFlags::COMPOSITE_SOURCE;
+    flags.set(GradientFilterFlags::KNOCKOUT, knockout);
+    match type_ {
+        BevelFilterType::Inner => flags |= GradientFilterFlags::INNER_SHADOW,
+        BevelFilterType::Outer => {}
+        BevelFilterType::Full => flags |= GradientFilterFlags::ON_TOP,
+    }
+    flags |= GradientFilterFlags::from_passes(quality.clamp(0, 15) as u8);
+    GradientFilter {
+        colors,
+        blur_x: Fixed16::from_f64(blur_x),
+        blur_y: Fixed16::from_f64(blur_y),
+        angle: Fixed16::from_f64(angle.to_radians()),
+        distance: Fixed16::from_f64(distance),
+        strength: Fixed8::from_f64(strength),
+        flags,
+    }
+}
 
 /// A GradientGlowFilter
 #[derive(Clone, Copy, Collect)]
@@ -75,6 +119,24 @@ impl<'gc> GradientGlowFilterObject<'gc> {
         self.0.read().ratios.clone()
     }
 
+    /// Converts this object into a filter that can be passed to the renderer.
+    pub fn filter(&self) -> GradientFilter {
+        let this = self.0.read();
+        gradient_filter(
+            &this.colors,
+            &this.alphas,
+            &this.ratios,
+            this.blur_x,
+            this.blur_y,
+            this.angle,
+            this.distance,
+            this.strength,
+            this.quality,
+            this.type_,
+            this.knockout,
+        )
+    }
+
     pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Object<'gc>) -> Self {
         GradientGlowFilterObject(GcCell::allocate(
             gc_context,
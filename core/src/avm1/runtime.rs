@@ -506,6 +506,22 @@ impl<'gc> Avm1<'gc> {
         registry.get(symbol, is_case_sensitive)
     }
 
+    /// The constructors registered for a SWF version, with the symbols that
+    /// they were registered for.
+    pub fn registered_constructors(
+        &self,
+        swf_version: u8,
+    ) -> impl Iterator<Item = (AvmString<'gc>, FunctionObject<'gc>)> + '_ {
+        let registry = if swf_version >= 7 {
+            &self.constructor_registry_case_sensitive
+        } else {
+            &self.constructor_registry_case_insensitive
+        };
+        registry
+            .iter()
+            .map(|(symbol, constructor)| (symbol, *constructor))
+    }
+
     pub fn register_constructor(
         &mut self,
         swf_version: u8,
@@ -23,11 +23,12 @@ macro_rules! avm_debug {
 }
 
 pub mod activation;
-mod amf;
+pub mod amf;
 mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+mod decode;
 mod domain;
 mod e4x;
 pub mod error;
@@ -40,6 +41,7 @@ mod multiname;
 mod namespace;
 pub mod object;
 mod property;
+mod property_cache;
 mod property_map;
 mod qname;
 mod regexp;
@@ -50,6 +52,7 @@ mod stubs;
 mod traits;
 mod value;
 mod vector;
+mod verify;
 mod vtable;
 
 pub use crate::avm2::activation::Activation;
@@ -61,8 +64,8 @@ pub use crate::avm2::globals::flash::ui::context_menu::make_context_menu_state;
 pub use crate::avm2::multiname::Multiname;
 pub use crate::avm2::namespace::{Namespace, NamespaceData};
 pub use crate::avm2::object::{
-    ArrayObject, ClassObject, EventObject, Object, ScriptObject, SoundChannelObject, StageObject,
-    TObject,
+    ArrayObject, ClassObject, EventObject, Object, ScriptObject, SoundChannelObject, SoundObject,
+    StageObject, TObject,
 };
 pub use crate::avm2::qname::QName;
 pub use crate::avm2::value::Value;
@@ -100,6 +103,8 @@ pub struct Avm2<'gc> {
     pub flash_utils_internal: Namespace<'gc>,
     pub flash_geom_internal: Namespace<'gc>,
     pub flash_events_internal: Namespace<'gc>,
+    pub flash_text_engine_internal: Namespace<'gc>,
+    pub flash_net_internal: Namespace<'gc>,
 
     #[collect(require_static)]
     native_method_table: &'static [Option<(&'static str, NativeMethodImpl)>],
@@ -120,6 +125,12 @@ pub struct Avm2<'gc> {
     /// collector does not support weak references.
     broadcast_list: FnvHashMap<AvmString<'gc>, Vec<Object<'gc>>>,
 
+    /// The classes registered with `registerClassAlias`, by alias.
+    alias_to_class_map: FnvHashMap<AvmString<'gc>, ClassObject<'gc>>,
+
+    /// The alias that each class was last registered with.
+    class_to_alias_map: FnvHashMap<ClassObject<'gc>, AvmString<'gc>>,
+
     /// The list of 'orphan' movies - these movies have no parent,
     /// so we need to manually run their frames in `run_all_phases_avm2` to match
     /// Flash's behavior. Clips are added to this list with `add_orphan_movie`.
@@ -160,11 +171,15 @@ impl<'gc> Avm2<'gc> {
             flash_utils_internal: Namespace::internal("flash.utils", mc),
             flash_geom_internal: Namespace::internal("flash.geom", mc),
             flash_events_internal: Namespace::internal("flash.events", mc),
+            flash_text_engine_internal: Namespace::internal("flash.text.engine", mc),
+            flash_net_internal: Namespace::internal("flash.net", mc),
 
             native_method_table: Default::default(),
             native_instance_allocator_table: Default::default(),
             native_instance_init_table: Default::default(),
             broadcast_list: Default::default(),
+            alias_to_class_map: Default::default(),
+            class_to_alias_map: Default::default(),
 
             orphan_movies: Vec::new(),
 
@@ -186,6 +201,27 @@ impl<'gc> Avm2<'gc> {
         self.system_classes.as_ref().unwrap()
     }
 
+    /// Register the alias that a class is serialized with in AMF.
+    pub fn register_class_alias(&mut self, name: AvmString<'gc>, class_object: ClassObject<'gc>) {
+        self.alias_to_class_map.insert(name, class_object);
+        self.class_to_alias_map.insert(class_object, name);
+    }
+
+    /// Get the class registered with an alias.
+    pub fn get_class_by_alias(&self, name: AvmString<'gc>) -> Option<ClassObject<'gc>> {
+        self.alias_to_class_map.get(&name).copied()
+    }
+
+    /// Get the alias that a class was registered with.
+    pub fn get_alias_by_class(&self, class_object: ClassObject<'gc>) -> Option<AvmString<'gc>> {
+        self.class_to_alias_map.get(&class_object).copied()
+    }
+
+    /// The aliases that classes were registered with.
+    pub fn class_aliases(&self) -> impl Iterator<Item = AvmString<'gc>> + '_ {
+        self.alias_to_class_map.keys().copied()
+    }
+
     /// Run a script's initializer method.
     pub fn run_script_initializer(
         script: Script<'gc>,
@@ -2,6 +2,7 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::decode::DecodedBody;
 use crate::avm2::domain::Domain;
 use crate::avm2::error::{make_null_or_undefined_error, type_error};
 use crate::avm2::method::{BytecodeMethod, Method, ParamConfig};
@@ -9,6 +10,7 @@ use crate::avm2::object::{
     ArrayObject, ByteArrayObject, ClassObject, FunctionObject, NamespaceObject, ScriptObject,
 };
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::property::Property;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -18,12 +20,10 @@ use crate::avm2::QName;
 use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
 use crate::string::{AvmString, WStr, WString};
-use crate::swf::extensions::ReadSwfExt;
 use gc_arena::{Gc, GcCell};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
-use swf::avm2::read::Reader;
 use swf::avm2::types::{
     Class as AbcClass, Exception, Index, Method as AbcMethod, MethodFlags as AbcMethodFlags,
     Multiname as AbcMultiname, Namespace as AbcNamespace, Op,
@@ -871,14 +871,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        let body: Result<_, Error<'gc>> = method
-            .body()
-            .ok_or_else(|| "Cannot execute non-native method without body".into());
-        let body = body?;
-        let mut reader = Reader::new(&body.code);
+        let body = method.decoded_body(self)?;
+        let mut ip = 0;
 
         let val = loop {
-            let result = self.do_next_opcode(method, &mut reader, &body.code);
+            let result = self.do_next_opcode(method, &body, &mut ip);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
@@ -893,12 +890,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     /// If a local exception handler exists for the error, use it to handle
     /// the error. Otherwise pass the error down the stack.
-    fn handle_err<'b>(
+    fn handle_err(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-        instruction_start: usize,
+        body: &DecodedBody,
+        ip: &mut usize,
+        instruction_index: usize,
         error: Error<'gc>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let error = match error {
@@ -906,33 +903,31 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             Error::RustError(_) => return Err(error),
         };
 
-        if let Some(body) = method.body() {
-            // Use `coerce_to_object` so that we handle primitives correctly.
-            let err_object = error.coerce_to_object(self);
-            for e in body.exceptions.iter() {
-                if instruction_start >= e.from_offset as usize
-                    && instruction_start < e.to_offset as usize
-                {
-                    let mut matches = false;
-                    // A typeless catch block (e.g. `catch(er) { ... }`) will
-                    // always match.
-                    if e.type_name.0 == 0 {
-                        matches = true;
-                    } else if let Ok(err_object) = err_object {
-                        let type_name = self.pool_multiname_static(method, e.type_name)?;
-                        let ty_class = self.resolve_class(&type_name)?;
-
-                        matches = err_object.is_of_type(ty_class, self);
-                    }
+        // Use `coerce_to_object` so that we handle primitives correctly.
+        let err_object = error.coerce_to_object(self);
+        for e in body.exceptions.iter() {
+            if instruction_index >= e.from_offset as usize
+                && instruction_index < e.to_offset as usize
+            {
+                let mut matches = false;
+                // A typeless catch block (e.g. `catch(er) { ... }`) will
+                // always match.
+                if e.type_name.0 == 0 {
+                    matches = true;
+                } else if let Ok(err_object) = err_object {
+                    let type_name = self.pool_multiname_static(method, e.type_name)?;
+                    let ty_class = self.resolve_class(&type_name)?;
+
+                    matches = err_object.is_of_type(ty_class, self);
+                }
 
-                    if matches {
-                        self.clear_stack();
-                        self.push_stack(error);
+                if matches {
+                    self.clear_stack();
+                    self.push_stack(error);
 
-                        self.clear_scope();
-                        reader.seek_absolute(full_data, e.target_offset as usize);
-                        return Ok(FrameControl::Continue);
-                    }
+                    self.clear_scope();
+                    *ip = e.target_offset as usize;
+                    return Ok(FrameControl::Continue);
                 }
             }
         }
@@ -944,12 +939,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Err(Error::AvmError(error))
     }
 
-    /// Run a single action from a given action reader.
-    fn do_next_opcode<'b>(
+    /// Run the instruction at `ip` of a decoded method body, and advance
+    /// `ip` to the next instruction to run.
+    fn do_next_opcode(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        body: &DecodedBody,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 2000 {
@@ -962,207 +958,199 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             }
         }
 
-        let instruction_start = reader.pos(full_data);
-        let op = reader.read_op();
-        if let Ok(op) = op {
-            avm_debug!(self.avm2(), "Opcode: {op:?}");
-
-            let result = match op {
-                Op::PushByte { value } => self.op_push_byte(value),
-                Op::PushDouble { value } => self.op_push_double(method, value),
-                Op::PushFalse => self.op_push_false(),
-                Op::PushInt { value } => self.op_push_int(method, value),
-                Op::PushNamespace { value } => self.op_push_namespace(method, value),
-                Op::PushNaN => self.op_push_nan(),
-                Op::PushNull => self.op_push_null(),
-                Op::PushShort { value } => self.op_push_short(value),
-                Op::PushString { value } => self.op_push_string(method, value),
-                Op::PushTrue => self.op_push_true(),
-                Op::PushUint { value } => self.op_push_uint(method, value),
-                Op::PushUndefined => self.op_push_undefined(),
-                Op::Pop => self.op_pop(),
-                Op::Dup => self.op_dup(),
-                Op::GetLocal { index } => self.op_get_local(index),
-                Op::SetLocal { index } => self.op_set_local(index),
-                Op::Kill { index } => self.op_kill(index),
-                Op::Call { num_args } => self.op_call(num_args),
-                Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
-                Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, index, num_args)
-                }
-                Op::CallPropLex { index, num_args } => {
-                    self.op_call_prop_lex(method, index, num_args)
-                }
-                Op::CallPropVoid { index, num_args } => {
-                    self.op_call_prop_void(method, index, num_args)
-                }
-                Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
-                Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
-                Op::CallSuperVoid { index, num_args } => {
-                    self.op_call_super_void(method, index, num_args)
-                }
-                Op::ReturnValue => self.op_return_value(),
-                Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
-                Op::SetProperty { index } => self.op_set_property(method, index),
-                Op::InitProperty { index } => self.op_init_property(method, index),
-                Op::DeleteProperty { index } => self.op_delete_property(method, index),
-                Op::GetSuper { index } => self.op_get_super(method, index),
-                Op::SetSuper { index } => self.op_set_super(method, index),
-                Op::In => self.op_in(),
-                Op::PushScope => self.op_push_scope(),
-                Op::NewCatch { index } => self.op_newcatch(method, index),
-                Op::PushWith => self.op_push_with(),
-                Op::PopScope => self.op_pop_scope(),
-                Op::GetOuterScope { index } => self.op_get_outer_scope(index),
-                Op::GetScopeObject { index } => self.op_get_scope_object(index),
-                Op::GetGlobalScope => self.op_get_global_scope(),
-                Op::FindDef { index } => self.op_find_def(method, index),
-                Op::FindProperty { index } => self.op_find_property(method, index),
-                Op::FindPropStrict { index } => self.op_find_prop_strict(method, index),
-                Op::GetLex { index } => self.op_get_lex(method, index),
-                Op::GetSlot { index } => self.op_get_slot(index),
-                Op::SetSlot { index } => self.op_set_slot(index),
-                Op::GetGlobalSlot { index } => self.op_get_global_slot(index),
-                Op::SetGlobalSlot { index } => self.op_set_global_slot(index),
-                Op::Construct { num_args } => self.op_construct(num_args),
-                Op::ConstructProp { index, num_args } => {
-                    self.op_construct_prop(method, index, num_args)
-                }
-                Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
-                Op::NewActivation => self.op_new_activation(),
-                Op::NewObject { num_args } => self.op_new_object(num_args),
-                Op::NewFunction { index } => self.op_new_function(method, index),
-                Op::NewClass { index } => self.op_new_class(method, index),
-                Op::ApplyType { num_types } => self.op_apply_type(num_types),
-                Op::NewArray { num_args } => self.op_new_array(num_args),
-                Op::CoerceA => self.op_coerce_a(),
-                Op::CoerceB => self.op_coerce_b(),
-                Op::CoerceD => self.op_coerce_d(),
-                Op::CoerceI => self.op_coerce_i(),
-                Op::CoerceO => self.op_coerce_o(),
-                Op::CoerceS => self.op_coerce_s(),
-                Op::CoerceU => self.op_coerce_u(),
-                Op::ConvertB => self.op_convert_b(),
-                Op::ConvertI => self.op_convert_i(),
-                Op::ConvertD => self.op_convert_d(),
-                Op::ConvertO => self.op_convert_o(),
-                Op::ConvertU => self.op_convert_u(),
-                Op::ConvertS => self.op_convert_s(),
-                Op::Add => self.op_add(),
-                Op::AddI => self.op_add_i(),
-                Op::BitAnd => self.op_bitand(),
-                Op::BitNot => self.op_bitnot(),
-                Op::BitOr => self.op_bitor(),
-                Op::BitXor => self.op_bitxor(),
-                Op::DecLocal { index } => self.op_declocal(index),
-                Op::DecLocalI { index } => self.op_declocal_i(index),
-                Op::Decrement => self.op_decrement(),
-                Op::DecrementI => self.op_decrement_i(),
-                Op::Divide => self.op_divide(),
-                Op::IncLocal { index } => self.op_inclocal(index),
-                Op::IncLocalI { index } => self.op_inclocal_i(index),
-                Op::Increment => self.op_increment(),
-                Op::IncrementI => self.op_increment_i(),
-                Op::LShift => self.op_lshift(),
-                Op::Modulo => self.op_modulo(),
-                Op::Multiply => self.op_multiply(),
-                Op::MultiplyI => self.op_multiply_i(),
-                Op::Negate => self.op_negate(),
-                Op::NegateI => self.op_negate_i(),
-                Op::RShift => self.op_rshift(),
-                Op::Subtract => self.op_subtract(),
-                Op::SubtractI => self.op_subtract_i(),
-                Op::Swap => self.op_swap(),
-                Op::URShift => self.op_urshift(),
-                Op::Jump { offset } => self.op_jump(offset, reader, full_data),
-                Op::IfTrue { offset } => self.op_if_true(offset, reader, full_data),
-                Op::IfFalse { offset } => self.op_if_false(offset, reader, full_data),
-                Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, reader, full_data),
-                Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, reader, full_data),
-                Op::IfEq { offset } => self.op_if_eq(offset, reader, full_data),
-                Op::IfNe { offset } => self.op_if_ne(offset, reader, full_data),
-                Op::IfGe { offset } => self.op_if_ge(offset, reader, full_data),
-                Op::IfGt { offset } => self.op_if_gt(offset, reader, full_data),
-                Op::IfLe { offset } => self.op_if_le(offset, reader, full_data),
-                Op::IfLt { offset } => self.op_if_lt(offset, reader, full_data),
-                Op::IfNge { offset } => self.op_if_nge(offset, reader, full_data),
-                Op::IfNgt { offset } => self.op_if_ngt(offset, reader, full_data),
-                Op::IfNle { offset } => self.op_if_nle(offset, reader, full_data),
-                Op::IfNlt { offset } => self.op_if_nlt(offset, reader, full_data),
-                Op::StrictEquals => self.op_strict_equals(),
-                Op::Equals => self.op_equals(),
-                Op::GreaterEquals => self.op_greater_equals(),
-                Op::GreaterThan => self.op_greater_than(),
-                Op::LessEquals => self.op_less_equals(),
-                Op::LessThan => self.op_less_than(),
-                Op::Nop => self.op_nop(),
-                Op::Not => self.op_not(),
-                Op::HasNext => self.op_has_next(),
-                Op::HasNext2 {
-                    object_register,
-                    index_register,
-                } => self.op_has_next_2(object_register, index_register),
-                Op::NextName => self.op_next_name(),
-                Op::NextValue => self.op_next_value(),
-                Op::IsType { index } => self.op_is_type(method, index),
-                Op::IsTypeLate => self.op_is_type_late(),
-                Op::AsType { type_name } => self.op_as_type(method, type_name),
-                Op::AsTypeLate => self.op_as_type_late(),
-                Op::InstanceOf => self.op_instance_of(),
-                Op::Label => Ok(FrameControl::Continue),
-                Op::Debug {
-                    is_local_register,
-                    register_name,
-                    register,
-                } => self.op_debug(method, is_local_register, register_name, register),
-                Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(line_num),
-                Op::Bkpt => self.op_bkpt(),
-                Op::BkptLine { line_num } => self.op_bkpt_line(line_num),
-                Op::Timestamp => self.op_timestamp(),
-                Op::TypeOf => self.op_type_of(),
-                Op::EscXAttr => self.op_esc_xattr(),
-                Op::EscXElem => self.op_esc_elem(),
-                Op::LookupSwitch {
-                    default_offset,
-                    case_offsets,
-                } => self.op_lookup_switch(
-                    default_offset,
-                    &case_offsets,
-                    instruction_start,
-                    reader,
-                    full_data,
-                ),
-                Op::Coerce { index } => self.op_coerce(method, index),
-                Op::CheckFilter => self.op_check_filter(),
-                Op::Si8 => self.op_si8(),
-                Op::Si16 => self.op_si16(),
-                Op::Si32 => self.op_si32(),
-                Op::Sf32 => self.op_sf32(),
-                Op::Sf64 => self.op_sf64(),
-                Op::Li8 => self.op_li8(),
-                Op::Li16 => self.op_li16(),
-                Op::Li32 => self.op_li32(),
-                Op::Lf32 => self.op_lf32(),
-                Op::Lf64 => self.op_lf64(),
-                Op::Sxi1 => self.op_sxi1(),
-                Op::Sxi8 => self.op_sxi8(),
-                Op::Sxi16 => self.op_sxi16(),
-                Op::Throw => self.op_throw(),
-                _ => self.unknown_op(op),
-            };
-
-            if let Err(error) = result {
-                return self.handle_err(method, reader, full_data, instruction_start, error);
+        let instruction_index = *ip;
+        let op = match body.ops.get(instruction_index) {
+            Some(op) => op,
+            None => return Err("Execution fell off the end of the method".into()),
+        };
+        *ip += 1;
+
+        avm_debug!(self.avm2(), "Opcode: {op:?}");
+
+        let result = match *op {
+            Op::PushByte { value } => self.op_push_byte(value),
+            Op::PushDouble { value } => self.op_push_double(method, value),
+            Op::PushFalse => self.op_push_false(),
+            Op::PushInt { value } => self.op_push_int(method, value),
+            Op::PushNamespace { value } => self.op_push_namespace(method, value),
+            Op::PushNaN => self.op_push_nan(),
+            Op::PushNull => self.op_push_null(),
+            Op::PushShort { value } => self.op_push_short(value),
+            Op::PushString { value } => self.op_push_string(method, value),
+            Op::PushTrue => self.op_push_true(),
+            Op::PushUint { value } => self.op_push_uint(method, value),
+            Op::PushUndefined => self.op_push_undefined(),
+            Op::Pop => self.op_pop(),
+            Op::Dup => self.op_dup(),
+            Op::GetLocal { index } => self.op_get_local(index),
+            Op::SetLocal { index } => self.op_set_local(index),
+            Op::Kill { index } => self.op_kill(index),
+            Op::Call { num_args } => self.op_call(num_args),
+            Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
+            Op::CallProperty { index, num_args } => {
+                self.op_call_property(method, index, num_args, instruction_index)
             }
-            result
-        } else if let Err(e) = op {
-            tracing::error!("Parse error: {:?}", e);
-            Err(Error::RustError(Box::new(e)))
-        } else {
-            unreachable!();
+            Op::CallPropLex { index, num_args } => {
+                self.op_call_prop_lex(method, index, num_args, instruction_index)
+            }
+            Op::CallPropVoid { index, num_args } => {
+                self.op_call_prop_void(method, index, num_args, instruction_index)
+            }
+            Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
+            Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
+            Op::CallSuperVoid { index, num_args } => {
+                self.op_call_super_void(method, index, num_args)
+            }
+            Op::ReturnValue => self.op_return_value(),
+            Op::ReturnVoid => self.op_return_void(),
+            Op::GetProperty { index } => self.op_get_property(method, index, instruction_index),
+            Op::SetProperty { index } => self.op_set_property(method, index, instruction_index),
+            Op::InitProperty { index } => self.op_init_property(method, index, instruction_index),
+            Op::DeleteProperty { index } => self.op_delete_property(method, index),
+            Op::GetSuper { index } => self.op_get_super(method, index),
+            Op::SetSuper { index } => self.op_set_super(method, index),
+            Op::In => self.op_in(),
+            Op::PushScope => self.op_push_scope(),
+            Op::NewCatch { index } => self.op_newcatch(method, index),
+            Op::PushWith => self.op_push_with(),
+            Op::PopScope => self.op_pop_scope(),
+            Op::GetOuterScope { index } => self.op_get_outer_scope(index),
+            Op::GetScopeObject { index } => self.op_get_scope_object(index),
+            Op::GetGlobalScope => self.op_get_global_scope(),
+            Op::FindDef { index } => self.op_find_def(method, index),
+            Op::FindProperty { index } => self.op_find_property(method, index),
+            Op::FindPropStrict { index } => self.op_find_prop_strict(method, index),
+            Op::GetLex { index } => self.op_get_lex(method, index),
+            Op::GetSlot { index } => self.op_get_slot(index),
+            Op::SetSlot { index } => self.op_set_slot(index),
+            Op::GetGlobalSlot { index } => self.op_get_global_slot(index),
+            Op::SetGlobalSlot { index } => self.op_set_global_slot(index),
+            Op::Construct { num_args } => self.op_construct(num_args),
+            Op::ConstructProp { index, num_args } => {
+                self.op_construct_prop(method, index, num_args)
+            }
+            Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
+            Op::NewActivation => self.op_new_activation(),
+            Op::NewObject { num_args } => self.op_new_object(num_args),
+            Op::NewFunction { index } => self.op_new_function(method, index),
+            Op::NewClass { index } => self.op_new_class(method, index),
+            Op::ApplyType { num_types } => self.op_apply_type(num_types),
+            Op::NewArray { num_args } => self.op_new_array(num_args),
+            Op::CoerceA => self.op_coerce_a(),
+            Op::CoerceB => self.op_coerce_b(),
+            Op::CoerceD => self.op_coerce_d(),
+            Op::CoerceI => self.op_coerce_i(),
+            Op::CoerceO => self.op_coerce_o(),
+            Op::CoerceS => self.op_coerce_s(),
+            Op::CoerceU => self.op_coerce_u(),
+            Op::ConvertB => self.op_convert_b(),
+            Op::ConvertI => self.op_convert_i(),
+            Op::ConvertD => self.op_convert_d(),
+            Op::ConvertO => self.op_convert_o(),
+            Op::ConvertU => self.op_convert_u(),
+            Op::ConvertS => self.op_convert_s(),
+            Op::Add => self.op_add(),
+            Op::AddI => self.op_add_i(),
+            Op::BitAnd => self.op_bitand(),
+            Op::BitNot => self.op_bitnot(),
+            Op::BitOr => self.op_bitor(),
+            Op::BitXor => self.op_bitxor(),
+            Op::DecLocal { index } => self.op_declocal(index),
+            Op::DecLocalI { index } => self.op_declocal_i(index),
+            Op::Decrement => self.op_decrement(),
+            Op::DecrementI => self.op_decrement_i(),
+            Op::Divide => self.op_divide(),
+            Op::IncLocal { index } => self.op_inclocal(index),
+            Op::IncLocalI { index } => self.op_inclocal_i(index),
+            Op::Increment => self.op_increment(),
+            Op::IncrementI => self.op_increment_i(),
+            Op::LShift => self.op_lshift(),
+            Op::Modulo => self.op_modulo(),
+            Op::Multiply => self.op_multiply(),
+            Op::MultiplyI => self.op_multiply_i(),
+            Op::Negate => self.op_negate(),
+            Op::NegateI => self.op_negate_i(),
+            Op::RShift => self.op_rshift(),
+            Op::Subtract => self.op_subtract(),
+            Op::SubtractI => self.op_subtract_i(),
+            Op::Swap => self.op_swap(),
+            Op::URShift => self.op_urshift(),
+            Op::Jump { offset } => self.op_jump(offset, ip),
+            Op::IfTrue { offset } => self.op_if_true(offset, ip),
+            Op::IfFalse { offset } => self.op_if_false(offset, ip),
+            Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, ip),
+            Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, ip),
+            Op::IfEq { offset } => self.op_if_eq(offset, ip),
+            Op::IfNe { offset } => self.op_if_ne(offset, ip),
+            Op::IfGe { offset } => self.op_if_ge(offset, ip),
+            Op::IfGt { offset } => self.op_if_gt(offset, ip),
+            Op::IfLe { offset } => self.op_if_le(offset, ip),
+            Op::IfLt { offset } => self.op_if_lt(offset, ip),
+            Op::IfNge { offset } => self.op_if_nge(offset, ip),
+            Op::IfNgt { offset } => self.op_if_ngt(offset, ip),
+            Op::IfNle { offset } => self.op_if_nle(offset, ip),
+            Op::IfNlt { offset } => self.op_if_nlt(offset, ip),
+            Op::StrictEquals => self.op_strict_equals(),
+            Op::Equals => self.op_equals(),
+            Op::GreaterEquals => self.op_greater_equals(),
+            Op::GreaterThan => self.op_greater_than(),
+            Op::LessEquals => self.op_less_equals(),
+            Op::LessThan => self.op_less_than(),
+            Op::Nop => self.op_nop(),
+            Op::Not => self.op_not(),
+            Op::HasNext => self.op_has_next(),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => self.op_has_next_2(object_register, index_register),
+            Op::NextName => self.op_next_name(),
+            Op::NextValue => self.op_next_value(),
+            Op::IsType { index } => self.op_is_type(method, index),
+            Op::IsTypeLate => self.op_is_type_late(),
+            Op::AsType { type_name } => self.op_as_type(method, type_name),
+            Op::AsTypeLate => self.op_as_type_late(),
+            Op::InstanceOf => self.op_instance_of(),
+            Op::Label => Ok(FrameControl::Continue),
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => self.op_debug(method, is_local_register, register_name, register),
+            Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
+            Op::DebugLine { line_num } => self.op_debug_line(line_num),
+            Op::Bkpt => self.op_bkpt(),
+            Op::BkptLine { line_num } => self.op_bkpt_line(line_num),
+            Op::Timestamp => self.op_timestamp(),
+            Op::TypeOf => self.op_type_of(),
+            Op::EscXAttr => self.op_esc_xattr(),
+            Op::EscXElem => self.op_esc_elem(),
+            Op::LookupSwitch {
+                default_offset,
+                ref case_offsets,
+            } => self.op_lookup_switch(default_offset, case_offsets, ip),
+            Op::Coerce { index } => self.op_coerce(method, index),
+            Op::CheckFilter => self.op_check_filter(),
+            Op::Si8 => self.op_si8(),
+            Op::Si16 => self.op_si16(),
+            Op::Si32 => self.op_si32(),
+            Op::Sf32 => self.op_sf32(),
+            Op::Sf64 => self.op_sf64(),
+            Op::Li8 => self.op_li8(),
+            Op::Li16 => self.op_li16(),
+            Op::Li32 => self.op_li32(),
+            Op::Lf32 => self.op_lf32(),
+            Op::Lf64 => self.op_lf64(),
+            Op::Sxi1 => self.op_sxi1(),
+            Op::Sxi8 => self.op_sxi8(),
+            Op::Sxi16 => self.op_sxi16(),
+            Op::Throw => self.op_throw(),
+            _ => self.unknown_op(op.clone()),
+        };
+
+        if let Err(error) = result {
+            return self.handle_err(method, body, ip, instruction_index, error);
         }
+        result
     }
 
     fn unknown_op(&mut self, op: swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error<'gc>> {
@@ -1331,19 +1319,62 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
-    fn op_call_property(
+    /// Look up the trait of `object` that a static multiname refers to, using
+    /// the inline cache of the property access instruction at
+    /// `instruction_index`.
+    fn cached_trait(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        instruction_index: usize,
+        object: Object<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        let vtable = object.vtable()?;
+        method.property_cache().get_trait(
+            self.context.gc_context,
+            instruction_index,
+            vtable,
+            multiname,
+        )
+    }
+
+    /// Call a property of the object on the stack, for `callproperty` and
+    /// `callpropvoid`.
+    fn call_stack_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-        arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let args = self.pop_stack_args(arg_count);
-        let multiname = self.pool_multiname_and_initialize(method, index)?;
+        args: &[Value<'gc>],
+        instruction_index: usize,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
+
+        if multiname.has_lazy_component() {
+            let multiname = multiname.fill_with_runtime_params(self)?;
+            let receiver = self
+                .pop_stack()
+                .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+
+            return receiver.call_property(&multiname, args, self);
+        }
+
         let receiver = self
             .pop_stack()
             .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = self.cached_trait(method, instruction_index, receiver, &multiname);
 
-        let value = receiver.call_property(&multiname, &args, self)?;
+        receiver.call_resolved_property(&multiname, property, args, self)
+    }
+
+    fn op_call_property(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+        arg_count: u32,
+        instruction_index: usize,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let args = self.pop_stack_args(arg_count);
+        let value = self.call_stack_property(method, index, &args, instruction_index)?;
 
         self.push_stack(value);
 
@@ -1355,17 +1386,32 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
+        instruction_index: usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.pop_stack_args(arg_count);
-        let multiname = self.pool_multiname_and_initialize(method, index)?;
-        let receiver = self
-            .pop_stack()
-            .coerce_to_object_or_typeerror(self, Some(&multiname))?;
-        let function = receiver.get_property(&multiname, self)?.as_callable(
-            self,
-            Some(&multiname),
-            Some(receiver),
-        )?;
+        let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
+
+        let function = if multiname.has_lazy_component() {
+            let multiname = multiname.fill_with_runtime_params(self)?;
+            let receiver = self
+                .pop_stack()
+                .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+
+            receiver.get_property(&multiname, self)?.as_callable(
+                self,
+                Some(&multiname),
+                Some(receiver),
+            )?
+        } else {
+            let receiver = self
+                .pop_stack()
+                .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+            let property = self.cached_trait(method, instruction_index, receiver, &multiname);
+
+            receiver
+                .get_resolved_property(&multiname, property, self)?
+                .as_callable(self, Some(&multiname), Some(receiver))?
+        };
         let value = function.call(None, &args, self)?;
 
         self.push_stack(value);
@@ -1378,14 +1424,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
+        instruction_index: usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.pop_stack_args(arg_count);
-        let multiname = self.pool_multiname_and_initialize(method, index)?;
-        let receiver = self
-            .pop_stack()
-            .coerce_to_object_or_typeerror(self, Some(&multiname))?;
-
-        receiver.call_property(&multiname, &args, self)?;
+        self.call_stack_property(method, index, &args, instruction_index)?;
 
         Ok(FrameControl::Continue)
     }
@@ -1463,6 +1505,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
+        instruction_index: usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
 
@@ -1470,7 +1513,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            let value = object.get_property(&multiname, self)?;
+            let property = self.cached_trait(method, instruction_index, object, &multiname);
+            let value = object.get_resolved_property(&multiname, property, self)?;
             self.push_stack(value);
             return Ok(FrameControl::Continue);
         }
@@ -1510,6 +1554,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
+        instruction_index: usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack();
         let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
@@ -1518,7 +1563,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let mut object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            object.set_property(&multiname, value, self)?;
+            let property = self.cached_trait(method, instruction_index, object, &multiname);
+            object.set_resolved_property(&multiname, property, value, self)?;
             return Ok(FrameControl::Continue);
         }
 
@@ -1559,14 +1605,26 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
+        instruction_index: usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack();
-        let multiname = self.pool_multiname_and_initialize(method, index)?;
+        let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
+
+        if multiname.has_lazy_component() {
+            let multiname = multiname.fill_with_runtime_params(self)?;
+            let mut object = self
+                .pop_stack()
+                .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+            object.init_property(&multiname, value, self)?;
+
+            return Ok(FrameControl::Continue);
+        }
+
         let mut object = self
             .pop_stack()
             .coerce_to_object_or_typeerror(self, Some(&multiname))?;
-
-        object.init_property(&multiname, value, self)?;
+        let property = self.cached_trait(method, instruction_index, object, &multiname);
+        object.init_resolved_property(&multiname, property, value, self)?;
 
         Ok(FrameControl::Continue)
     }
@@ -2382,234 +2440,171 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_jump<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        reader.seek(full_data, offset);
+    fn op_jump(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
+        *ip = offset as usize;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_true<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_true(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack().coerce_to_boolean();
 
         if value {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_false<'b>(
+    fn op_if_false(
         &mut self,
         offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack().coerce_to_boolean();
 
         if !value {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_eq<'b>(
+    fn op_if_strict_eq(
         &mut self,
         offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value1 == value2 {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_ne<'b>(
+    fn op_if_strict_ne(
         &mut self,
         offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value1 != value2 {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_eq<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_eq(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value1.abstract_eq(&value2, self)? {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ne<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_ne(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if !value1.abstract_eq(&value2, self)? {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ge<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_ge(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value1.abstract_lt(&value2, self)? == Some(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_gt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_gt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value2.abstract_lt(&value1, self)? == Some(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_le<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_le(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value2.abstract_lt(&value1, self)? == Some(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_lt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_lt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value1.abstract_lt(&value2, self)? == Some(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nge<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_nge(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value1.abstract_lt(&value2, self)?.unwrap_or(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ngt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_ngt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if !value2.abstract_lt(&value1, self)?.unwrap_or(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nle<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_nle(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if value2.abstract_lt(&value1, self)?.unwrap_or(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nlt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_if_nlt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.pop_stack();
         let value1 = self.pop_stack();
 
         if !value1.abstract_lt(&value2, self)?.unwrap_or(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -2946,24 +2941,20 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     /// Implements `Op::LookupSwitch`
-    fn op_lookup_switch<'b>(
+    fn op_lookup_switch(
         &mut self,
         default_offset: i32,
         case_offsets: &[i32],
-        instruction_start: usize,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let index = self.pop_stack().coerce_to_i32(self)?;
 
         let offset = case_offsets
             .get(index as usize)
             .copied()
-            .unwrap_or(default_offset)
-            + instruction_start as i32
-            - reader.pos(full_data) as i32;
+            .unwrap_or(default_offset);
 
-        reader.seek(full_data, offset);
+        *ip = offset as usize;
         Ok(FrameControl::Continue)
     }
 
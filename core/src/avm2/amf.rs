@@ -1,20 +1,30 @@
 use crate::avm2::bytearray::ByteArrayStorage;
-use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::error::reference_error;
+use crate::avm2::object::{ByteArrayObject, ClassObject, TObject};
+use crate::avm2::property::Property;
 use crate::avm2::ArrayObject;
 use crate::avm2::ArrayStorage;
 use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use enumset::EnumSet;
+use flash_lso::amf3::custom_encoder::{CustomEncoder, ExternalDecoderFn};
+use flash_lso::amf3::read::AMF3Decoder;
+use flash_lso::amf3::write::AMF3Encoder;
+use flash_lso::read::Reader;
 use flash_lso::types::{AMFVersion, Element, Lso};
 use flash_lso::types::{Attribute, ClassDefinition, Value as AmfValue};
+use flash_lso::write::Writer;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 /// Serialize a Value to an AmfValue
 pub fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     elem: Value<'gc>,
     amf_version: AMFVersion,
-) -> Option<AmfValue> {
-    match elem {
+) -> Result<Option<AmfValue>, Error<'gc>> {
+    Ok(match elem {
         Value::Undefined => Some(AmfValue::Undefined),
         Value::Null => Some(AmfValue::Null),
         Value::Bool(b) => Some(AmfValue::Bool(b)),
@@ -40,7 +50,7 @@ pub fn serialize_value<'gc>(
                 Some(AmfValue::Undefined)
             } else if let Some(array) = o.as_array_storage() {
                 let mut values = Vec::new();
-                recursive_serialize(activation, o, &mut values, amf_version).unwrap();
+                recursive_serialize(activation, o, &mut values, amf_version)?;
 
                 let mut dense = vec![];
                 let mut sparse = vec![];
@@ -58,16 +68,20 @@ pub fn serialize_value<'gc>(
                     let len = sparse.len() as u32;
                     Some(AmfValue::ECMAArray(dense, sparse, len))
                 }
+            } else if let Some(bytearray) = o.as_bytearray() {
+                // AMF0 has no type for byte arrays.
+                (amf_version == AMFVersion::AMF3)
+                    .then(|| AmfValue::ByteArray(bytearray.bytes().to_vec()))
             } else if let Some(date) = o.as_date_object() {
                 date.date_time()
                     .map(|date_time| AmfValue::Date(date_time.timestamp_millis() as f64, None))
             } else {
                 let is_object = o
                     .instance_of()
-                    .map_or(false, |c| c == activation.avm2().classes().object);
+                    .map_or(true, |c| c == activation.avm2().classes().object);
                 if is_object {
                     let mut object_body = Vec::new();
-                    recursive_serialize(activation, o, &mut object_body, amf_version).unwrap();
+                    recursive_serialize(activation, o, &mut object_body, amf_version)?;
                     Some(AmfValue::Object(
                         object_body,
                         Some(ClassDefinition {
@@ -76,16 +90,146 @@ pub fn serialize_value<'gc>(
                             static_properties: Vec::new(),
                         }),
                     ))
+                } else if amf_version == AMFVersion::AMF3
+                    && o.is_of_type(activation.avm2().classes().iexternalizable, activation)
+                {
+                    Some(serialize_externalizable(activation, o)?)
                 } else {
-                    tracing::warn!(
-                        "Serialization is not implemented for class other than Object: {:?}",
-                        o
-                    );
-                    None
+                    Some(serialize_typed_object(activation, o, amf_version)?)
                 }
             }
         }
+    })
+}
+
+/// The alias that the class of an object was registered with, or an empty
+/// string if it wasn't.
+fn class_alias<'gc>(activation: &mut Activation<'_, 'gc>, class: ClassObject<'gc>) -> String {
+    activation
+        .avm2()
+        .get_alias_by_class(class)
+        .map(|name| name.to_utf8_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Serialize an instance of a class that implements `IExternalizable`, with
+/// the data that its `writeExternal` method writes.
+///
+/// The data is kept in a byte array until the value is written by
+/// `write_lso`, whose encoders write it in place of the members of the
+/// object.
+fn serialize_externalizable<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    obj: Object<'gc>,
+) -> Result<AmfValue, Error<'gc>> {
+    let class = obj
+        .instance_of()
+        .ok_or("Attempted to serialize a bare object as an externalizable object")?;
+    let alias = class_alias(activation, class);
+
+    let output = ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(vec![]))?;
+    obj.call_public_property("writeExternal", &[output.into()], activation)?;
+    let data = output
+        .as_bytearray()
+        .map(|bytearray| bytearray.bytes().to_vec())
+        .unwrap_or_default();
+
+    Ok(AmfValue::Custom(
+        vec![Element::new("", AmfValue::ByteArray(data))],
+        vec![],
+        Some(ClassDefinition {
+            name: alias,
+            attributes: EnumSet::only(Attribute::External),
+            static_properties: vec![],
+        }),
+    ))
+}
+
+/// Serialize an instance of a class other than `Object`, with the alias that
+/// its class was registered with.
+///
+/// The variables and read-write accessors of the class are serialized as
+/// sealed members, followed by the dynamic properties of the object.
+fn serialize_typed_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    obj: Object<'gc>,
+    amf_version: AMFVersion,
+) -> Result<AmfValue, Error<'gc>> {
+    let class = obj
+        .instance_of()
+        .ok_or("Attempted to serialize a bare object as a typed object")?;
+    let alias = class_alias(activation, class);
+    let is_sealed = class.inner_class_definition().read().is_sealed();
+
+    let mut static_properties = Vec::new();
+    let mut elements = Vec::new();
+    for name in sealed_property_names(obj, class) {
+        let value = obj.get_public_property(name, activation)?;
+        let value = serialize_value(activation, value, amf_version)?.unwrap_or(AmfValue::Undefined);
+        static_properties.push(name.to_utf8_lossy().into_owned());
+        elements.push(Element::new(name.to_utf8_lossy(), value));
     }
+
+    let mut attributes = EnumSet::empty();
+    if !is_sealed {
+        attributes.insert(Attribute::Dynamic);
+        recursive_serialize(activation, obj, &mut elements, amf_version)?;
+    }
+
+    Ok(AmfValue::Object(
+        elements,
+        Some(ClassDefinition {
+            name: alias,
+            attributes,
+            static_properties,
+        }),
+    ))
+}
+
+/// The names of the public variables and read-write accessors of an object's
+/// class, in the order that they were declared in, starting with those of its
+/// base classes.
+fn sealed_property_names<'gc>(obj: Object<'gc>, class: ClassObject<'gc>) -> Vec<AvmString<'gc>> {
+    let vtable = match obj.vtable() {
+        Some(vtable) => vtable,
+        None => return Vec::new(),
+    };
+
+    let serializable: Vec<AvmString<'gc>> = vtable
+        .public_properties()
+        .into_iter()
+        .filter(|(_, prop)| {
+            matches!(
+                prop,
+                Property::Slot { .. }
+                    | Property::Virtual {
+                        get: Some(_),
+                        set: Some(_),
+                    }
+            )
+        })
+        .map(|(name, _)| name)
+        .collect();
+
+    let mut classes = vec![class];
+    while let Some(superclass) = classes.last().and_then(|class| class.superclass_object()) {
+        classes.push(superclass);
+    }
+
+    let mut names = Vec::new();
+    for class in classes.into_iter().rev() {
+        let class = class.inner_class_definition();
+        for instance_trait in class.read().instance_traits() {
+            let name = instance_trait.name();
+            if name.namespace().is_public()
+                && serializable.contains(&name.local_name())
+                && !names.contains(&name.local_name())
+            {
+                names.push(name.local_name());
+            }
+        }
+    }
+    names
 }
 
 /// Serialize an Object and any children to a AMF object
@@ -102,7 +246,7 @@ pub fn recursive_serialize<'gc>(
             .coerce_to_string(activation)?;
         let value = obj.get_public_property(name, activation)?;
 
-        if let Some(value) = serialize_value(activation, value, amf_version) {
+        if let Some(value) = serialize_value(activation, value, amf_version)? {
             elements.push(Element::new(name.to_utf8_lossy(), value));
         }
         last_index = obj.get_next_enumerant(index, activation)?;
@@ -114,6 +258,16 @@ pub fn recursive_serialize<'gc>(
 pub fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     val: &AmfValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    deserialize_value_with_externals(activation, val, &[])
+}
+
+/// Deserialize a AmfValue to a Value, using the externalizable objects that
+/// were read by `parse_with_externals`.
+pub fn deserialize_value_with_externals<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    val: &AmfValue,
+    externals: &[Object<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(match val {
         AmfValue::Null => Value::Null,
@@ -131,7 +285,9 @@ pub fn deserialize_value<'gc>(
             // First let's create an array out of `values` (dense portion), then we add the elements onto it.
             let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
             for value in values {
-                arr.push(Some(deserialize_value(activation, value)?));
+                arr.push(Some(deserialize_value_with_externals(
+                    activation, value, externals,
+                )?));
             }
             let storage = ArrayStorage::from_storage(arr);
             let mut array = ArrayObject::from_storage(activation, storage)?;
@@ -139,7 +295,7 @@ pub fn deserialize_value<'gc>(
             for element in elements {
                 array.set_public_property(
                     AvmString::new_utf8(activation.context.gc_context, element.name()),
-                    deserialize_value(activation, element.value())?,
+                    deserialize_value_with_externals(activation, element.value(), externals)?,
                     activation,
                 )?;
             }
@@ -148,26 +304,29 @@ pub fn deserialize_value<'gc>(
         AmfValue::StrictArray(values) => {
             let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
             for value in values {
-                arr.push(Some(deserialize_value(activation, value)?));
+                arr.push(Some(deserialize_value_with_externals(
+                    activation, value, externals,
+                )?));
             }
             let storage = ArrayStorage::from_storage(arr);
             let array = ArrayObject::from_storage(activation, storage)?;
             array.into()
         }
         AmfValue::Object(elements, class) => {
+            let mut target_class = activation.avm2().classes().object;
             if let Some(class) = class {
                 if !class.name.is_empty() && class.name != "Object" {
-                    tracing::warn!("Deserializing class {:?} is not supported!", class);
+                    let name = AvmString::new_utf8(activation.context.gc_context, &class.name);
+                    match activation.avm2().get_class_by_alias(name) {
+                        Some(class_object) => target_class = class_object,
+                        None => tracing::warn!("Class alias {:?} is not registered", class.name),
+                    }
                 }
             }
 
-            let mut obj = activation
-                .avm2()
-                .classes()
-                .object
-                .construct(activation, &[])?;
+            let mut obj = target_class.construct(activation, &[])?;
             for entry in elements {
-                let value = deserialize_value(activation, entry.value())?;
+                let value = deserialize_value_with_externals(activation, entry.value(), externals)?;
                 obj.set_public_property(
                     AvmString::new_utf8(activation.context.gc_context, entry.name()),
                     value,
@@ -198,12 +357,26 @@ pub fn deserialize_value<'gc>(
         | AmfValue::VectorUInt(..)
         | AmfValue::VectorInt(..)
         | AmfValue::VectorObject(..)
-        | AmfValue::Dictionary(..)
-        | AmfValue::Custom(..) => {
+        | AmfValue::Dictionary(..) => {
             tracing::error!("Deserialization not yet implemented: {:?}", val);
             Value::Undefined
         }
-        AmfValue::AMF3(val) => deserialize_value(activation, val)?,
+        AmfValue::Custom(elements, _, _) => {
+            // The external decoders of `parse_with_externals` replace the data
+            // of each externalizable object with its index.
+            let external = match elements.first().map(|element| element.value()) {
+                Some(AmfValue::Integer(index)) => externals.get(*index as usize),
+                _ => None,
+            };
+            match external {
+                Some(external) => (*external).into(),
+                None => {
+                    tracing::error!("Externalizable object was not read: {:?}", val);
+                    Value::Undefined
+                }
+            }
+        }
+        AmfValue::AMF3(val) => deserialize_value_with_externals(activation, val, externals)?,
         AmfValue::Unsupported => Value::Undefined,
     })
 }
@@ -212,6 +385,7 @@ pub fn deserialize_value<'gc>(
 pub fn deserialize_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
     lso: &Lso,
+    externals: &[Object<'gc>],
 ) -> Result<Object<'gc>, Error<'gc>> {
     let mut obj = activation
         .avm2()
@@ -222,10 +396,212 @@ pub fn deserialize_lso<'gc>(
     for child in &lso.body {
         obj.set_public_property(
             AvmString::new_utf8(activation.context.gc_context, &child.name),
-            deserialize_value(activation, child.value())?,
+            deserialize_value_with_externals(activation, child.value(), externals)?,
             activation,
         )?;
     }
 
     Ok(obj)
 }
+
+/// The names of the classes that externalizable objects may have: the
+/// registered aliases, and the empty name of unregistered classes.
+fn external_class_names(activation: &mut Activation<'_, '_>) -> Vec<String> {
+    activation
+        .avm2()
+        .class_aliases()
+        .map(|alias| alias.to_utf8_lossy().into_owned())
+        .chain(std::iter::once(String::new()))
+        .collect()
+}
+
+/// Writes the data of an externalizable object, which `serialize_value`
+/// stores in a byte array.
+struct ExternalDataEncoder;
+
+impl CustomEncoder for ExternalDataEncoder {
+    fn encode(
+        &self,
+        elements: &[Element],
+        _class_def: &Option<ClassDefinition>,
+        _encoder: &AMF3Encoder,
+    ) -> Vec<u8> {
+        match elements.first().map(|element| element.value()) {
+            Some(AmfValue::ByteArray(data)) => data.clone(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Write an LSO, including the data of the externalizable objects in it.
+pub fn write_lso<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    lso: &mut Lso,
+) -> Result<Vec<u8>, Error<'gc>> {
+    let mut writer = Writer::default();
+    for name in external_class_names(activation) {
+        writer
+            .amf3_encoder
+            .external_encoders
+            .insert(name, Box::new(ExternalDataEncoder));
+    }
+
+    let (bytes, _) = cookie_factory::gen(writer.write(lso), Vec::new())
+        .map_err(|_| "Failed to serialize object")?;
+    Ok(bytes)
+}
+
+/// Read the data of a shared object, which was written by `write_lso`.
+///
+/// Returns `None` if the data isn't a valid LSO.
+pub fn read_lso<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytes: &[u8],
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    // `readExternal` reads the data of externalizable objects from a copy of
+    // the whole LSO.
+    let source =
+        ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(bytes.to_vec()))?;
+    let parsed = parse_with_externals(activation, source, 0, bytes, |decoder, bytes| {
+        let mut reader = Reader::default();
+        reader.amf3_decoder = decoder;
+        reader.parse(bytes).ok().map(|(_, lso)| lso)
+    })?;
+
+    match parsed {
+        Some((lso, externals)) => Ok(Some(deserialize_lso(activation, &lso, &externals)?)),
+        None => Ok(None),
+    }
+}
+
+/// The externalizable objects that the external decoders of
+/// `parse_with_externals` came across.
+#[derive(Default)]
+struct ExternalReads {
+    /// The length of the data of each object that was read, and its index in
+    /// the list of read objects, by the offset of the data.
+    read: HashMap<usize, (usize, usize)>,
+
+    /// The offset of the data of the object that stopped parsing because it
+    /// wasn't read yet, and the name of its class.
+    unread: Option<(usize, String)>,
+}
+
+/// Parse AMF data with `parse`, reading externalizable objects with the
+/// `readExternal` method of their class, at any depth.
+///
+/// The length of the data of an externalizable object is only known once
+/// `readExternal` has read it, which the AMF3 decoder can't call. So `parse`
+/// is given a decoder that stops at the first externalizable object that
+/// wasn't read yet, and returns `None` when it fails. The object is then
+/// read from `source`, a byte array that holds `bytes` at `start`, and
+/// parsing starts over, skipping the data of the objects that were read.
+///
+/// Returns the result of `parse` and the objects that were read, which
+/// `deserialize_value_with_externals` needs, or `None` if the data is
+/// invalid.
+#[allow(clippy::type_complexity)]
+pub fn parse_with_externals<'gc, T>(
+    activation: &mut Activation<'_, 'gc>,
+    source: Object<'gc>,
+    start: usize,
+    bytes: &[u8],
+    mut parse: impl FnMut(AMF3Decoder, &[u8]) -> Option<T>,
+) -> Result<Option<(T, Vec<Object<'gc>>)>, Error<'gc>> {
+    let reads = Rc::new(RefCell::new(ExternalReads::default()));
+    let mut externals = Vec::new();
+    loop {
+        let mut decoder = AMF3Decoder::default();
+        for name in external_class_names(activation) {
+            let decoder_fn = external_decoder(bytes.len(), name.clone(), reads.clone());
+            decoder.external_decoders.insert(name, decoder_fn);
+        }
+
+        if let Some(result) = parse(decoder, bytes) {
+            return Ok(Some((result, externals)));
+        }
+
+        let unread = reads.borrow_mut().unread.take();
+        let (offset, name) = match unread {
+            Some(unread) => unread,
+            None => return Ok(None),
+        };
+
+        let external = read_external(activation, source, start + offset, &name)?;
+        let end = source
+            .as_bytearray()
+            .map_or(0, |bytearray| bytearray.position());
+        let length = end.saturating_sub(start + offset);
+        reads
+            .borrow_mut()
+            .read
+            .insert(offset, (length, externals.len()));
+        externals.push(external);
+    }
+}
+
+/// Create an external decoder for the class called `name`, which decodes the
+/// objects that were read by `parse_with_externals` and stops at the others.
+///
+/// `length` is the length of the parsed data, which gives the offset of an
+/// object from the length of the data left.
+fn external_decoder(
+    length: usize,
+    name: String,
+    reads: Rc<RefCell<ExternalReads>>,
+) -> ExternalDecoderFn {
+    decoder_fn(move |i, _decoder| {
+        let offset = length - i.len();
+        let mut reads = reads.borrow_mut();
+        if let Some(&(data_length, index)) = reads.read.get(&offset) {
+            if let Some(rest) = i.get(data_length..) {
+                let placeholder = Element::new("", AmfValue::Integer(index as i32));
+                return Ok((rest, vec![placeholder]));
+            }
+        } else if reads.unread.is_none() {
+            reads.unread = Some((offset, name.clone()));
+        }
+
+        // A failure rather than an error, so that the decoder doesn't try to
+        // parse the data as something else.
+        Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Verify,
+        )))
+    })
+}
+
+/// Give a closure the signature of an external decoder.
+fn decoder_fn(
+    f: impl 'static + for<'a> Fn(&'a [u8], &mut AMF3Decoder) -> nom::IResult<&'a [u8], Vec<Element>>,
+) -> ExternalDecoderFn {
+    Rc::new(Box::new(f))
+}
+
+/// Create an instance of the class registered with `alias`, and read its data
+/// with its `readExternal` method, from `position` in `source`.
+fn read_external<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    source: Object<'gc>,
+    position: usize,
+    alias: &str,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let alias = AvmString::new_utf8(activation.context.gc_context, alias);
+    let class_object = match activation.avm2().get_class_by_alias(alias) {
+        Some(class_object) => class_object,
+        None => {
+            return Err(Error::AvmError(reference_error(
+                activation,
+                &format!("Error #1014: Class {alias} could not be found."),
+                1014,
+            )?));
+        }
+    };
+
+    let external = class_object.construct(activation, &[])?;
+    if let Some(bytearray) = source.as_bytearray() {
+        bytearray.set_position(position);
+    }
+    external.call_public_property("readExternal", &[source.into()], activation)?;
+    Ok(external)
+}
@@ -0,0 +1,346 @@
+//! Decoding of AVM2 method bodies
+
+use crate::avm2::verify::VerifyError;
+use crate::swf::extensions::ReadSwfExt;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{Exception, MethodBody, Op};
+
+/// A method body decoded into instructions, ready to be executed.
+///
+/// Branches refer to instructions by their index in `ops` instead of by byte
+/// offset: the `offset` of every jump and all of the offsets of a
+/// `LookupSwitch` hold the index of the instruction to continue at. The
+/// offsets of each exception handler are instruction indices as well, with
+/// `to_offset` being exclusive.
+#[derive(Debug)]
+pub struct DecodedBody {
+    /// The instructions of the method.
+    pub ops: Vec<Op>,
+
+    /// The exception handlers of the method.
+    pub exceptions: Vec<Exception>,
+}
+
+/// Decode the bytecode of a method body, resolving all branch targets to
+/// instruction indices.
+///
+/// Only the instructions that can be reached from the start of the method or
+/// from an exception handler are decoded, so unreachable bytes (such as junk
+/// inserted by obfuscators) don't need to hold valid instructions.
+pub fn decode_body(body: &MethodBody) -> Result<DecodedBody, VerifyError> {
+    let code = &body.code[..];
+    if code.is_empty() {
+        return Err(VerifyError::new(1043, "Invalid code_length=0."));
+    }
+
+    let mut worklist = vec![0];
+    for exception in &body.exceptions {
+        if exception.from_offset >= exception.to_offset
+            || exception.to_offset as usize > code.len()
+            || exception.target_offset as usize >= code.len()
+        {
+            return Err(VerifyError::new(
+                1054,
+                "Illegal range or target offsets in exception handler.",
+            ));
+        }
+        worklist.push(exception.target_offset as usize);
+    }
+
+    // The reachable instructions, with the byte offsets of their start and
+    // end, in no particular order.
+    let mut reachable = vec![];
+    let mut is_decoded = vec![false; code.len()];
+    while let Some(start) = worklist.pop() {
+        if is_decoded[start] {
+            continue;
+        }
+        is_decoded[start] = true;
+
+        let mut reader = Reader::new(&code[start..]);
+        let op = reader.read_op().map_err(|_| {
+            VerifyError::new(
+                1011,
+                format!(
+                    "Method contained illegal opcode {} at offset {}.",
+                    code[start], start
+                ),
+            )
+        })?;
+        let end = reader.pos(code);
+
+        for target in branch_targets(&op, start, end) {
+            // Branching to the end of the code is caught by the verifier, as
+            // falling off the end of the method.
+            match usize::try_from(target) {
+                Ok(target) if target < code.len() => worklist.push(target),
+                Ok(target) if target == code.len() => {}
+                _ => return Err(invalid_branch_target()),
+            }
+        }
+        if falls_through(&op) && end < code.len() {
+            worklist.push(end);
+        }
+
+        reachable.push((start, end, op));
+    }
+    reachable.sort_unstable_by_key(|(start, _end, _op)| *start);
+
+    // Instructions can't overlap, which would happen if a branch jumped into
+    // the middle of an instruction.
+    if reachable.windows(2).any(|pair| pair[1].0 < pair[0].1) {
+        return Err(invalid_branch_target());
+    }
+
+    // The byte offset of the start of each instruction, and of the end of
+    // the code.
+    let mut starts: Vec<usize> = reachable.iter().map(|(start, _end, _op)| *start).collect();
+    starts.push(code.len());
+
+    let mut ends = Vec::with_capacity(reachable.len());
+    let mut ops = Vec::with_capacity(reachable.len());
+    for (_start, end, op) in reachable {
+        ends.push(end);
+        ops.push(op);
+    }
+
+    for (index, op) in ops.iter_mut().enumerate() {
+        match op {
+            Op::Jump { offset }
+            | Op::IfTrue { offset }
+            | Op::IfFalse { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfEq { offset }
+            | Op::IfNe { offset }
+            | Op::IfGe { offset }
+            | Op::IfGt { offset }
+            | Op::IfLe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNge { offset }
+            | Op::IfNgt { offset }
+            | Op::IfNle { offset }
+            | Op::IfNlt { offset } => {
+                // Jumps are relative to the end of the instruction.
+                *offset = resolve_target(&starts, ends[index], *offset)?;
+            }
+            Op::LookupSwitch {
+                default_offset,
+                case_offsets,
+            } => {
+                // Switches are relative to the start of the instruction.
+                *default_offset = resolve_target(&starts, starts[index], *default_offset)?;
+                for case_offset in case_offsets.iter_mut() {
+                    *case_offset = resolve_target(&starts, starts[index], *case_offset)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut exceptions = body.exceptions.clone();
+    for exception in &mut exceptions {
+        let target = starts
+            .binary_search(&(exception.target_offset as usize))
+            .expect("Exception handlers are decoded");
+        exception.from_offset = instruction_at_or_after(&starts, exception.from_offset);
+        exception.to_offset = instruction_at_or_after(&starts, exception.to_offset);
+        exception.target_offset = target as u32;
+    }
+
+    Ok(DecodedBody { ops, exceptions })
+}
+
+/// The byte offsets that an instruction starting at `start` and ending at
+/// `end` can branch to, not including the following instruction.
+fn branch_targets(op: &Op, start: usize, end: usize) -> Vec<i64> {
+    match op {
+        Op::Jump { offset }
+        | Op::IfTrue { offset }
+        | Op::IfFalse { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfEq { offset }
+        | Op::IfNe { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset } => vec![end as i64 + *offset as i64],
+        Op::LookupSwitch {
+            default_offset,
+            case_offsets,
+        } => std::iter::once(default_offset)
+            .chain(case_offsets.iter())
+            .map(|offset| start as i64 + *offset as i64)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Whether execution can continue with the instruction following `op`.
+fn falls_through(op: &Op) -> bool {
+    !matches!(
+        op,
+        Op::Jump { .. } | Op::LookupSwitch { .. } | Op::ReturnValue | Op::ReturnVoid | Op::Throw
+    )
+}
+
+fn invalid_branch_target() -> VerifyError {
+    VerifyError::new(
+        1021,
+        "At least one branch target was not on a valid instruction in the method.",
+    )
+}
+
+/// Find the index of the instruction at `base + offset`, which must be the
+/// start of an instruction or the end of the code.
+fn resolve_target(starts: &[usize], base: usize, offset: i32) -> Result<i32, VerifyError> {
+    let target = base as i64 + offset as i64;
+    usize::try_from(target)
+        .ok()
+        .and_then(|target| starts.binary_search(&target).ok())
+        .map(|index| index as i32)
+        .ok_or_else(invalid_branch_target)
+}
+
+/// Find the index of the first instruction that starts at or after a byte
+/// offset.
+fn instruction_at_or_after(starts: &[usize], offset: u32) -> u32 {
+    starts.partition_point(|start| *start < offset as usize) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::avm2::types::Index;
+
+    fn body(code: &[u8]) -> MethodBody {
+        MethodBody {
+            method: Index::new(0),
+            max_stack: 1,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: code.to_vec(),
+            exceptions: vec![],
+            traits: vec![],
+        }
+    }
+
+    fn exception(from_offset: u32, to_offset: u32, target_offset: u32) -> Exception {
+        Exception {
+            from_offset,
+            to_offset,
+            target_offset,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        }
+    }
+
+    fn decode_error_code(body: &MethodBody) -> Option<u32> {
+        decode_body(body).err().map(|e| e.code())
+    }
+
+    #[test]
+    fn jumps_resolve_to_instruction_indices() {
+        // pushtrue, iffalse +2, pushbyte 1, returnvalue, jump -10
+        let body = body(&[
+            0x26, 0x12, 0x02, 0x00, 0x00, 0x24, 0x01, 0x48, 0x10, 0xf6, 0xff, 0xff,
+        ]);
+        let decoded = decode_body(&body).unwrap();
+        assert_eq!(
+            decoded.ops,
+            vec![
+                Op::PushTrue,
+                Op::IfFalse { offset: 3 },
+                Op::PushByte { value: 1 },
+                Op::ReturnValue,
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_switch_is_relative_to_its_start() {
+        // getlocal0, lookupswitch default +8 cases [+9], returnvoid, pushtrue,
+        // returnvalue
+        let body = body(&[
+            0xd0, 0x1b, 0x08, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x47, 0x26, 0x48,
+        ]);
+        let decoded = decode_body(&body).unwrap();
+        assert_eq!(
+            decoded.ops[1],
+            Op::LookupSwitch {
+                default_offset: 2,
+                case_offsets: vec![3].into(),
+            }
+        );
+        assert_eq!(decoded.ops.len(), 4);
+    }
+
+    #[test]
+    fn unreachable_bytes_are_skipped() {
+        // jump +2, (junk), pushbyte 1, returnvalue, (junk)
+        let body = body(&[0x10, 0x02, 0x00, 0x00, 0xff, 0xff, 0x24, 0x01, 0x48, 0xff]);
+        let decoded = decode_body(&body).unwrap();
+        assert_eq!(
+            decoded.ops,
+            vec![
+                Op::Jump { offset: 1 },
+                Op::PushByte { value: 1 },
+                Op::ReturnValue,
+            ]
+        );
+    }
+
+    #[test]
+    fn reachable_illegal_opcode() {
+        // pushtrue, (illegal opcode)
+        let body = body(&[0x26, 0xff]);
+        assert_eq!(decode_error_code(&body), Some(1011));
+    }
+
+    #[test]
+    fn empty_code() {
+        assert_eq!(decode_error_code(&body(&[])), Some(1043));
+    }
+
+    #[test]
+    fn branch_out_of_code() {
+        // jump +8
+        let body = body(&[0x10, 0x08, 0x00, 0x00]);
+        assert_eq!(decode_error_code(&body), Some(1021));
+    }
+
+    #[test]
+    fn branch_to_end_of_code() {
+        // pushtrue, iftrue +0
+        let body = body(&[0x26, 0x11, 0x00, 0x00, 0x00]);
+        let decoded = decode_body(&body).unwrap();
+        assert_eq!(decoded.ops[1], Op::IfTrue { offset: 2 });
+        assert_eq!(decoded.ops.len(), 2);
+    }
+
+    #[test]
+    fn exception_offsets_resolve_to_instruction_indices() {
+        // pushtrue, pop, returnvoid, (junk), handler: throw
+        let mut body = body(&[0x26, 0x29, 0x47, 0xff, 0x03]);
+        body.exceptions.push(exception(0, 2, 4));
+        let decoded = decode_body(&body).unwrap();
+        assert_eq!(decoded.ops.len(), 4);
+        assert_eq!(decoded.exceptions[0].from_offset, 0);
+        assert_eq!(decoded.exceptions[0].to_offset, 2);
+        assert_eq!(decoded.exceptions[0].target_offset, 3);
+    }
+
+    #[test]
+    fn invalid_exception_target() {
+        // pushtrue, pop, returnvoid
+        let mut body = body(&[0x26, 0x29, 0x47]);
+        body.exceptions.push(exception(0, 2, 3));
+        assert_eq!(decode_error_code(&body), Some(1054));
+    }
+}
@@ -68,9 +68,18 @@ pub struct SystemClasses<'gc> {
     pub shape: ClassObject<'gc>,
     pub textfield: ClassObject<'gc>,
     pub textformat: ClassObject<'gc>,
+    pub font: ClassObject<'gc>,
     pub graphics: ClassObject<'gc>,
+    pub graphicsbitmapfill: ClassObject<'gc>,
+    pub graphicsendfill: ClassObject<'gc>,
+    pub graphicsgradientfill: ClassObject<'gc>,
+    pub graphicspath: ClassObject<'gc>,
+    pub graphicssolidfill: ClassObject<'gc>,
+    pub graphicsstroke: ClassObject<'gc>,
+    pub igraphicsdata: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
+    pub iexternalizable: ClassObject<'gc>,
     pub stage: ClassObject<'gc>,
     pub sprite: ClassObject<'gc>,
     pub simplebutton: ClassObject<'gc>,
@@ -85,9 +94,15 @@ pub struct SystemClasses<'gc> {
     pub mouseevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
+    pub httpstatusevent: ClassObject<'gc>,
+    pub sampledataevent: ClassObject<'gc>,
+    pub netstatusevent: ClassObject<'gc>,
+    pub statusevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
+    pub asyncerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
@@ -95,6 +110,7 @@ pub struct SystemClasses<'gc> {
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
     pub keyboardevent: ClassObject<'gc>,
+    pub focusevent: ClassObject<'gc>,
     pub point: ClassObject<'gc>,
     pub rangeerror: ClassObject<'gc>,
     pub referenceerror: ClassObject<'gc>,
@@ -106,12 +122,15 @@ pub struct SystemClasses<'gc> {
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
+    pub textline: ClassObject<'gc>,
+    pub fontmetrics: ClassObject<'gc>,
     pub stage3d: ClassObject<'gc>,
     pub context3d: ClassObject<'gc>,
     pub indexbuffer3d: ClassObject<'gc>,
     pub vertexbuffer3d: ClassObject<'gc>,
     pub program3d: ClassObject<'gc>,
     pub urlvariables: ClassObject<'gc>,
+    pub urlrequestheader: ClassObject<'gc>,
     pub bevelfilter: ClassObject<'gc>,
     pub bitmapfilter: ClassObject<'gc>,
     pub blurfilter: ClassObject<'gc>,
@@ -125,6 +144,9 @@ pub struct SystemClasses<'gc> {
     pub texture: ClassObject<'gc>,
     pub cubetexture: ClassObject<'gc>,
     pub rectangletexture: ClassObject<'gc>,
+    pub pngencoderoptions: ClassObject<'gc>,
+    pub jpegencoderoptions: ClassObject<'gc>,
+    pub jpegxrencoderoptions: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -167,9 +189,18 @@ impl<'gc> SystemClasses<'gc> {
             shape: object,
             textfield: object,
             textformat: object,
+            font: object,
             graphics: object,
+            graphicsbitmapfill: object,
+            graphicsendfill: object,
+            graphicsgradientfill: object,
+            graphicspath: object,
+            graphicssolidfill: object,
+            graphicsstroke: object,
+            igraphicsdata: object,
             loaderinfo: object,
             bytearray: object,
+            iexternalizable: object,
             stage: object,
             sprite: object,
             simplebutton: object,
@@ -184,9 +215,15 @@ impl<'gc> SystemClasses<'gc> {
             mouseevent: object,
             progressevent: object,
             textevent: object,
+            dataevent: object,
             errorevent: object,
             ioerrorevent: object,
+            httpstatusevent: object,
+            sampledataevent: object,
+            netstatusevent: object,
+            statusevent: object,
             securityerrorevent: object,
+            asyncerrorevent: object,
             transform: object,
             colortransform: object,
             matrix: object,
@@ -194,6 +231,7 @@ impl<'gc> SystemClasses<'gc> {
             eventdispatcher: object,
             rectangle: object,
             keyboardevent: object,
+            focusevent: object,
             point: object,
             rangeerror: object,
             referenceerror: object,
@@ -205,12 +243,15 @@ impl<'gc> SystemClasses<'gc> {
             uncaughterrorevents: object,
             statictext: object,
             textlinemetrics: object,
+            textline: object,
+            fontmetrics: object,
             stage3d: object,
             context3d: object,
             indexbuffer3d: object,
             vertexbuffer3d: object,
             program3d: object,
             urlvariables: object,
+            urlrequestheader: object,
             bevelfilter: object,
             bitmapfilter: object,
             blurfilter: object,
@@ -224,6 +265,9 @@ impl<'gc> SystemClasses<'gc> {
             texture: object,
             cubetexture: object,
             rectangletexture: object,
+            pngencoderoptions: object,
+            jpegencoderoptions: object,
+            jpegxrencoderoptions: object,
         }
     }
 }
@@ -519,11 +563,12 @@ pub fn load_player_globals<'gc>(
     );
 
     // package `flash.text`
-    class(
-        flash::text::font::create_class(activation),
-        script,
+    avm2_system_class!(
+        font,
         activation,
-    )?;
+        flash::text::font::create_class(activation),
+        script
+    );
 
     // Inside this call, the macro `avm2_system_classes_playerglobal`
     // triggers classloading. Therefore, we run `load_playerglobal`
@@ -630,8 +675,26 @@ fn load_playerglobal<'gc>(
             ("flash.display", "Scene", scene),
             ("flash.display", "FrameLabel", framelabel),
             ("flash.display", "Graphics", graphics),
+            ("flash.display", "GraphicsBitmapFill", graphicsbitmapfill),
+            ("flash.display", "GraphicsEndFill", graphicsendfill),
+            (
+                "flash.display",
+                "GraphicsGradientFill",
+                graphicsgradientfill
+            ),
+            ("flash.display", "GraphicsPath", graphicspath),
+            ("flash.display", "GraphicsSolidFill", graphicssolidfill),
+            ("flash.display", "GraphicsStroke", graphicsstroke),
+            ("flash.display", "IGraphicsData", igraphicsdata),
+            ("flash.display", "JPEGEncoderOptions", jpegencoderoptions),
+            (
+                "flash.display",
+                "JPEGXREncoderOptions",
+                jpegxrencoderoptions
+            ),
             ("flash.display", "LoaderInfo", loaderinfo),
             ("flash.display", "MovieClip", movieclip),
+            ("flash.display", "PNGEncoderOptions", pngencoderoptions),
             ("flash.display", "Shape", shape),
             ("flash.display", "SimpleButton", simplebutton),
             ("flash.display", "Sprite", sprite),
@@ -658,11 +721,18 @@ fn load_playerglobal<'gc>(
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "DataEvent", dataevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
+            ("flash.events", "FocusEvent", focusevent),
             ("flash.events", "ProgressEvent", progressevent),
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
+            ("flash.events", "AsyncErrorEvent", asyncerrorevent),
             ("flash.events", "IOErrorEvent", ioerrorevent),
+            ("flash.events", "HTTPStatusEvent", httpstatusevent),
+            ("flash.events", "SampleDataEvent", sampledataevent),
+            ("flash.events", "NetStatusEvent", netstatusevent),
+            ("flash.events", "StatusEvent", statusevent),
             ("flash.events", "MouseEvent", mouseevent),
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
@@ -672,11 +742,15 @@ fn load_playerglobal<'gc>(
             ("flash.geom", "Transform", transform),
             ("flash.geom", "ColorTransform", colortransform),
             ("flash.net", "URLVariables", urlvariables),
+            ("flash.net", "URLRequestHeader", urlrequestheader),
             ("flash.utils", "ByteArray", bytearray),
+            ("flash.utils", "IExternalizable", iexternalizable),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
             ("flash.text", "TextField", textfield),
             ("flash.text", "TextLineMetrics", textlinemetrics),
+            ("flash.text.engine", "FontMetrics", fontmetrics),
+            ("flash.text.engine", "TextLine", textline),
             ("flash.filters", "BevelFilter", bevelfilter),
             ("flash.filters", "BitmapFilter", bitmapfilter),
             ("flash.filters", "BlurFilter", blurfilter),
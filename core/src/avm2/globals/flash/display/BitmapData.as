@@ -33,6 +33,8 @@ package flash.display {
         public native function noise(randomSeed:int, low:uint = 0, high:uint = 255, channelOptions:uint = 7, grayScale:Boolean = false):void;
         public native function colorTransform(rect:Rectangle, colorTransform:ColorTransform):void;
         public native function getColorBoundsRect(mask:uint, color:uint, findColor:Boolean = true):Rectangle;
+        public native function setVector(rect:Rectangle, inputVector:Vector.<uint>):void;
+        public native function histogram(hRect:Rectangle = null):Vector.<Vector.<Number>>;
         public native function scroll(x:int, y:int):void;
         public native function lock():void;
         public native function hitTest(firstPoint:Point, firstAlphaThreshold:uint, secondObject:Object, secondBitmapDataPoint:Point = null, secondAlphaThreshold:uint = 1):Boolean;
@@ -49,9 +51,24 @@ package flash.display {
         public native function fillRect(rect:Rectangle, color:uint):void;
         public native function dispose():void;
         public native function applyFilter(sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, filter:BitmapFilter):void;
+        public native function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle;
         public native function clone():BitmapData;
         public native function perlinNoise(
             baseX:Number, baseY:Number, numOctaves:uint, randomSeed:int, stitch:Boolean, fractalNoise:Boolean, channelOptions:uint = 7, grayScale:Boolean = false, offsets:Array = null
         ):void;
+        public native function threshold(
+            sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, operation:String, threshold:uint, color:uint = 0, mask:uint = 0xFFFFFFFF, copySource:Boolean = false
+        ):uint;
+        public native function paletteMap(
+            sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, redArray:Array = null, greenArray:Array = null, blueArray:Array = null, alphaArray:Array = null
+        ):void;
+        public native function merge(
+            sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, redMultiplier:uint, greenMultiplier:uint, blueMultiplier:uint, alphaMultiplier:uint
+        ):void;
+        public native function compare(otherBitmapData:BitmapData):Object;
+        public native function pixelDissolve(
+            sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, randomSeed:int = 0, numPixels:int = 0, fillColor:uint = 0
+        ):int;
+        public native function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray;
     }
 }
@@ -16,16 +16,95 @@ package flash.display
             type:String, colors:Array, alphas:Array, ratios:Array, matrix:Matrix = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0
         ): void;
         public native function clear(): void;
+        public native function copyFrom(sourceGraphics:Graphics): void;
+        public native function cubicCurveTo(controlX1:Number, controlY1:Number, controlX2:Number, controlY2:Number, anchorX:Number, anchorY:Number): void;
         public native function curveTo(controlX:Number, controlY:Number, anchorX:Number, anchorY:Number): void;
         public native function drawCircle(x:Number, y:Number, radius:Number): void;
         public native function drawEllipse(x:Number, y:Number, width:Number, height:Number): void;
+
+        public function drawGraphicsData(graphicsData:Vector.<IGraphicsData>): void {
+            for each (var item:IGraphicsData in graphicsData) {
+                if (item is GraphicsSolidFill) {
+                    var solidFill:GraphicsSolidFill = item as GraphicsSolidFill;
+                    this.beginFill(solidFill.color, solidFill.alpha);
+                } else if (item is GraphicsGradientFill) {
+                    var gradientFill:GraphicsGradientFill = item as GraphicsGradientFill;
+                    this.beginGradientFill(
+                        gradientFill.type,
+                        gradientFill.colors,
+                        gradientFill.alphas,
+                        gradientFill.ratios,
+                        gradientFill.matrix,
+                        gradientFill.spreadMethod,
+                        gradientFill.interpolationMethod,
+                        gradientFill.focalPointRatio
+                    );
+                } else if (item is GraphicsBitmapFill) {
+                    var bitmapFill:GraphicsBitmapFill = item as GraphicsBitmapFill;
+                    this.beginBitmapFill(bitmapFill.bitmapData, bitmapFill.matrix, bitmapFill.repeat, bitmapFill.smooth);
+                } else if (item is GraphicsEndFill) {
+                    this.endFill();
+                } else if (item is GraphicsStroke) {
+                    this.applyStroke(item as GraphicsStroke);
+                } else if (item is GraphicsPath) {
+                    var path:GraphicsPath = item as GraphicsPath;
+                    this.drawPath(path.commands, path.data, path.winding);
+                } else if (item is GraphicsTrianglePath) {
+                    var trianglePath:GraphicsTrianglePath = item as GraphicsTrianglePath;
+                    this.drawTriangles(trianglePath.vertices, trianglePath.indices, trianglePath.uvtData, trianglePath.culling);
+                }
+            }
+        }
+
+        private function applyStroke(stroke:GraphicsStroke): void {
+            // A stroke without a fill doesn't draw anything.
+            if (isNaN(stroke.thickness) || stroke.fill == null || stroke.fill is GraphicsEndFill) {
+                this.lineStyle();
+                return;
+            }
+
+            if (stroke.fill is GraphicsSolidFill) {
+                var solidFill:GraphicsSolidFill = stroke.fill as GraphicsSolidFill;
+                this.lineStyle(stroke.thickness, solidFill.color, solidFill.alpha, stroke.pixelHinting, stroke.scaleMode, stroke.caps, stroke.joints, stroke.miterLimit);
+                return;
+            }
+
+            this.lineStyle(stroke.thickness, 0, 1.0, stroke.pixelHinting, stroke.scaleMode, stroke.caps, stroke.joints, stroke.miterLimit);
+            if (stroke.fill is GraphicsGradientFill) {
+                var gradientFill:GraphicsGradientFill = stroke.fill as GraphicsGradientFill;
+                this.lineGradientStyle(
+                    gradientFill.type,
+                    gradientFill.colors,
+                    gradientFill.alphas,
+                    gradientFill.ratios,
+                    gradientFill.matrix,
+                    gradientFill.spreadMethod,
+                    gradientFill.interpolationMethod,
+                    gradientFill.focalPointRatio
+                );
+            } else if (stroke.fill is GraphicsBitmapFill) {
+                var bitmapFill:GraphicsBitmapFill = stroke.fill as GraphicsBitmapFill;
+                this.lineBitmapStyle(bitmapFill.bitmapData, bitmapFill.matrix, bitmapFill.repeat, bitmapFill.smooth);
+            }
+        }
+
+        public native function drawPath(commands:Vector.<int>, data:Vector.<Number>, winding:String = "evenOdd"): void;
         public native function drawRect(x:Number, y:Number, width:Number, height:Number): void;
         public native function drawRoundRect(x:Number, y:Number, width:Number, height:Number, ellipseWidth:Number, ellipseHeight:Number = NaN): void;
+        public native function drawRoundRectComplex(
+            x:Number, y:Number, width:Number, height:Number, topLeftRadius:Number, topRightRadius:Number, bottomLeftRadius:Number, bottomRightRadius:Number
+        ): void;
+        public native function drawTriangles(vertices:Vector.<Number>, indices:Vector.<int> = null, uvtData:Vector.<Number> = null, culling:String = "none"): void;
         public native function endFill(): void;
+        public native function lineBitmapStyle(bitmap:BitmapData, matrix:Matrix = null, repeat:Boolean = true, smooth:Boolean = false): void;
+        public native function lineGradientStyle(
+            type:String, colors:Array, alphas:Array, ratios:Array, matrix:Matrix = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0
+        ): void;
         public native function lineStyle(
             thickness:Number = NaN, color:uint = 0, alpha:Number = 1.0, pixelHinting:Boolean = false, scaleMode:String = "normal", caps:String = null, joints:String = null, miterLimit:Number = 3
         ): void;
         public native function lineTo(x:Number, y:Number): void;
         public native function moveTo(x:Number, y:Number): void;
+        public native function readGraphicsData(recurse:Boolean = true): Vector.<IGraphicsData>;
     }
 }
@@ -0,0 +1,6 @@
+package flash.display {
+    public final class GraphicsEndFill implements IGraphicsFill, IGraphicsData {
+        public function GraphicsEndFill() {
+        }
+    }
+}
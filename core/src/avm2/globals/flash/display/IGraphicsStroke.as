@@ -0,0 +1,3 @@
+package flash.display {
+    public interface IGraphicsStroke {}
+}
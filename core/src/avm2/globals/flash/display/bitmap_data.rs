@@ -1,7 +1,8 @@
 //! `flash.display.BitmapData` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::error::argument_error;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{argument_error, range_error, type_error};
 use crate::avm2::filters::FilterAvm2Ext;
 use crate::avm2::object::{BitmapDataObject, ByteArrayObject, Object, TObject, VectorObject};
 use crate::avm2::value::Value;
@@ -12,11 +13,12 @@ use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color};
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::is_size_valid;
 use crate::character::Character;
-use crate::display_object::Bitmap;
+use crate::display_object::{Bitmap, TDisplayObject};
 use crate::swf::BlendMode;
 use gc_arena::GcCell;
 use ruffle_render::filters::Filter;
 use ruffle_render::transform::Transform;
+use ruffle_render::utils::{encode_jpeg, encode_png};
 use std::str::FromStr;
 
 pub use crate::avm2::object::bitmap_data_allocator;
@@ -745,30 +747,137 @@ pub fn get_color_bounds_rect<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.lock`
 pub fn lock<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.BitmapData", "lock");
+    // Changes to the pixels are only uploaded when the bitmap is next rendered,
+    // so there are no intermediate updates for `lock` to prevent.
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.unlock`
 pub fn unlock<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.BitmapData", "unlock");
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.hitTest`
 pub fn hit_test<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.BitmapData", "hitTest");
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let first_point = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let top_left = (
+            first_point
+                .get_public_property("x", activation)?
+                .coerce_to_i32(activation)?,
+            first_point
+                .get_public_property("y", activation)?
+                .coerce_to_i32(activation)?,
+        );
+        let source_threshold = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?
+            .min(u8::MAX.into()) as u8;
+        let compare_object = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let point_class = activation.avm2().classes().point;
+        let rectangle_class = activation.avm2().classes().rectangle;
+
+        if compare_object.is_of_type(point_class, activation) {
+            let test_point = (
+                compare_object
+                    .get_public_property("x", activation)?
+                    .coerce_to_i32(activation)?,
+                compare_object
+                    .get_public_property("y", activation)?
+                    .coerce_to_i32(activation)?,
+            );
+            return Ok(bitmap_data
+                .read()
+                .hit_test_point(top_left, source_threshold, test_point)
+                .into());
+        } else if compare_object.is_of_type(rectangle_class, activation) {
+            let test_rect = (
+                compare_object
+                    .get_public_property("x", activation)?
+                    .coerce_to_i32(activation)?,
+                compare_object
+                    .get_public_property("y", activation)?
+                    .coerce_to_i32(activation)?,
+                compare_object
+                    .get_public_property("width", activation)?
+                    .coerce_to_i32(activation)?,
+                compare_object
+                    .get_public_property("height", activation)?
+                    .coerce_to_i32(activation)?,
+            );
+            return Ok(bitmap_data
+                .read()
+                .hit_test_rectangle(top_left, source_threshold, test_rect)
+                .into());
+        } else if let Some(other_bitmap) = compare_object.as_bitmap_data().or_else(|| {
+            compare_object
+                .as_display_object()
+                .and_then(|dobj| dobj.as_bitmap())
+                .map(|bitmap| bitmap.bitmap_data_wrapper().sync())
+        }) {
+            other_bitmap.read().check_valid(activation)?;
+            // Testing shows that a null/undefined 'secondBitmapDataPoint' parameter is treated
+            // as 'new Point(0, 0)'
+            let mut test_top_left = (0, 0);
+            if let Ok(second_point) = args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation)
+            {
+                test_top_left = (
+                    second_point
+                        .get_public_property("x", activation)?
+                        .coerce_to_i32(activation)?,
+                    second_point
+                        .get_public_property("y", activation)?
+                        .coerce_to_i32(activation)?,
+                );
+            }
+            let test_threshold = args
+                .get(4)
+                .unwrap_or(&1.into())
+                .coerce_to_u32(activation)?
+                .min(u8::MAX.into()) as u8;
+
+            let result = bitmap_data.read().hit_test_bitmapdata(
+                top_left,
+                source_threshold,
+                &other_bitmap.read(),
+                test_top_left,
+                test_threshold,
+            );
+            return Ok(result.into());
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2005: Parameter 0 is of the incorrect type. Should be type BitmapData, Bitmap, Point or Rectangle.",
+                2005,
+            )?));
+        }
+    }
+
     Ok(false.into())
 }
 
@@ -1064,11 +1173,13 @@ pub fn apply_filter<'gc>(
             .ok_or_else(|| {
                 Error::from(format!("TypeError: Error #1034: Type Coercion failed: cannot convert {} to flash.display.BitmapData.", args[0].coerce_to_string(activation).unwrap_or_default()))
             })?;
-        let source_handle = match source_bitmap
-            .write(activation.context.gc_context)
-            .bitmap_handle(activation.context.renderer)
-        {
-            Some(handle) => handle,
+        let mut source_bitmap_data = source_bitmap.write(activation.context.gc_context);
+        let source_handle = match source_bitmap_data.bitmap_handle(activation.context.renderer) {
+            Some(handle) => {
+                source_bitmap_data.update_dirty_texture(activation.context.renderer);
+                drop(source_bitmap_data);
+                handle
+            }
             None => {
                 tracing::warn!("Ignoring BitmapData.apply_filter() with an undrawable source");
                 return Ok(Value::Undefined);
@@ -1228,3 +1339,680 @@ pub fn perlin_noise<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implement `BitmapData.threshold`
+pub fn threshold<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let src_min_x = source_rect
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let src_min_y = source_rect
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let src_width = source_rect
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let src_height = source_rect
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let dest_x = dest_point
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let dest_y = dest_point
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+
+        let operation = args
+            .get(3)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let threshold = args
+            .get(4)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        let colour = args.get(5).unwrap_or(&0.into()).coerce_to_u32(activation)?;
+
+        let mask = args
+            .get(6)
+            .unwrap_or(&0xFFFFFFFFu32.into())
+            .coerce_to_u32(activation)?;
+
+        let copy_source = args.get(7).unwrap_or(&false.into()).coerce_to_boolean();
+
+        if let Some(src_bitmap) = source_bitmap.as_bitmap_data() {
+            src_bitmap.read().check_valid(activation)?;
+            // dealing with object aliasing...
+            let src_bitmap_clone: BitmapData; // only initialized if source is the same object as self
+            let src_bitmap_data_cell = src_bitmap;
+            let src_bitmap_gc_ref; // only initialized if source is a different object than self
+            let source_bitmap_ref = // holds the reference to either of the ones above
+                if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                    src_bitmap_clone = src_bitmap_data_cell.read().clone();
+                    &src_bitmap_clone
+                } else {
+                    src_bitmap_gc_ref = src_bitmap_data_cell.read();
+                    &src_bitmap_gc_ref
+                };
+
+            let modified_count = bitmap_data.write(activation.context.gc_context).threshold(
+                source_bitmap_ref,
+                (src_min_x, src_min_y, src_width, src_height),
+                (dest_x, dest_y),
+                operation.as_wstr(),
+                threshold,
+                colour,
+                mask,
+                copy_source,
+            );
+
+            return Ok(modified_count.into());
+        }
+    }
+
+    Ok(0.into())
+}
+
+/// Implement `BitmapData.paletteMap`
+pub fn palette_map<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let src_min_x = source_rect
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let src_min_y = source_rect
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let src_width = source_rect
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let src_height = source_rect
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let dest_x = dest_point
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let dest_y = dest_point
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+
+        let mut get_channel = |index: usize, shift: usize| -> Result<[u32; 256], Error<'gc>> {
+            let values = args
+                .get(index)
+                .and_then(|arg| arg.as_object())
+                .and_then(|arg| arg.as_array_storage().map(|a| a.iter().collect::<Vec<_>>()));
+            let mut array = [0_u32; 256];
+            for (i, item) in array.iter_mut().enumerate() {
+                *item = if let Some(values) = &values {
+                    values
+                        .get(i)
+                        .cloned()
+                        .flatten()
+                        .unwrap_or(Value::Undefined)
+                        .coerce_to_u32(activation)?
+                } else {
+                    // This is an "identity mapping", fulfilling the part of the spec that
+                    // says that channels which have no array provided are simply copied.
+                    (i << shift) as u32
+                }
+            }
+            Ok(array)
+        };
+
+        let red_array = get_channel(3, 16)?;
+        let green_array = get_channel(4, 8)?;
+        let blue_array = get_channel(5, 0)?;
+        let alpha_array = get_channel(6, 24)?;
+
+        if let Some(src_bitmap) = source_bitmap.as_bitmap_data() {
+            src_bitmap.read().check_valid(activation)?;
+            // dealing with object aliasing...
+            let read;
+            let source: Option<&BitmapData> = if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                None
+            } else {
+                read = src_bitmap.read();
+                Some(&read)
+            };
+
+            bitmap_data
+                .write(activation.context.gc_context)
+                .palette_map(
+                    source,
+                    (src_min_x, src_min_y, src_width, src_height),
+                    (dest_x, dest_y),
+                    (red_array, green_array, blue_array, alpha_array),
+                );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implement `BitmapData.merge`
+pub fn merge<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let src_min_x = source_rect
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let src_min_y = source_rect
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let src_width = source_rect
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let src_height = source_rect
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let dest_x = dest_point
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let dest_y = dest_point
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+
+        let red_mult = args
+            .get(3)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        let green_mult = args
+            .get(4)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        let blue_mult = args
+            .get(5)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        let alpha_mult = args
+            .get(6)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(src_bitmap) = source_bitmap.as_bitmap_data() {
+            src_bitmap.read().check_valid(activation)?;
+            // dealing with object aliasing...
+            let src_bitmap_clone: BitmapData; // only initialized if source is the same object as self
+            let src_bitmap_data_cell = src_bitmap;
+            let src_bitmap_gc_ref; // only initialized if source is a different object than self
+            let source_bitmap_ref = // holds the reference to either of the ones above
+                if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                    src_bitmap_clone = src_bitmap_data_cell.read().clone();
+                    &src_bitmap_clone
+                } else {
+                    src_bitmap_gc_ref = src_bitmap_data_cell.read();
+                    &src_bitmap_gc_ref
+                };
+
+            bitmap_data.write(activation.context.gc_context).merge(
+                source_bitmap_ref,
+                (src_min_x, src_min_y, src_width, src_height),
+                (dest_x, dest_y),
+                (red_mult, green_mult, blue_mult, alpha_mult),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implement `BitmapData.compare`
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    const EQUIVALENT: i32 = 0;
+    const DIFFERENT_WIDTHS: i32 = -3;
+    const DIFFERENT_HEIGHTS: i32 = -4;
+
+    if let Some(this_bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        this_bitmap_data.read().check_valid(activation)?;
+        let other_bitmap_data = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Null | Value::Undefined => {
+                return Err(Error::AvmError(type_error(
+                    activation,
+                    "Error #2007: Parameter otherBitmapData must be non-null.",
+                    2007,
+                )?));
+            }
+            value => value
+                .coerce_to_object(activation)?
+                .as_bitmap_data()
+                .ok_or("TypeError: Error #1034: Type Coercion failed: cannot convert to flash.display.BitmapData.")?,
+        };
+        other_bitmap_data.read().check_valid(activation)?;
+
+        let this_bitmap_data = this_bitmap_data.read();
+        let other_bitmap_data = other_bitmap_data.read();
+
+        if this_bitmap_data.width() != other_bitmap_data.width() {
+            return Ok(DIFFERENT_WIDTHS.into());
+        }
+
+        if this_bitmap_data.height() != other_bitmap_data.height() {
+            return Ok(DIFFERENT_HEIGHTS.into());
+        }
+
+        return match BitmapData::compare(&this_bitmap_data, &other_bitmap_data) {
+            Some(bitmap_data) => {
+                let bitmap_data = GcCell::allocate(activation.context.gc_context, bitmap_data);
+                let class = activation.avm2().classes().bitmapdata;
+                Ok(BitmapDataObject::from_bitmap_data(activation, bitmap_data, class)?.into())
+            }
+            None => Ok(EQUIVALENT.into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implement `BitmapData.pixelDissolve`
+pub fn pixel_dissolve<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let src_min_x = source_rect
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let src_min_y = source_rect
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let src_width = source_rect
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let src_height = source_rect
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let dest_x = dest_point
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let dest_y = dest_point
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+
+        let random_seed = args.get(3).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+        let num_pixels = args.get(4).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+        if num_pixels < 0 {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2027: Parameter numPixels must be a non-negative number.",
+                2027,
+            )?));
+        }
+
+        let fill_color = args.get(5).unwrap_or(&0.into()).coerce_to_i32(activation)?;
+
+        if let Some(src_bitmap) = source_bitmap.as_bitmap_data() {
+            src_bitmap.read().check_valid(activation)?;
+            // Dissolving a bitmap into itself fills the chosen pixels with `fillColor`.
+            let read;
+            let source: Option<&BitmapData> = if GcCell::ptr_eq(src_bitmap, bitmap_data) {
+                None
+            } else {
+                read = src_bitmap.read();
+                Some(&read)
+            };
+
+            let next_seed = bitmap_data
+                .write(activation.context.gc_context)
+                .pixel_dissolve(
+                    source,
+                    (src_min_x, src_min_y, src_width, src_height),
+                    (dest_x, dest_y),
+                    random_seed,
+                    num_pixels,
+                    fill_color.into(),
+                );
+
+            return Ok(next_seed.into());
+        }
+    }
+
+    Ok(0.into())
+}
+
+/// Implement `BitmapData.histogram`
+pub fn histogram<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let rect = match args.get(0).unwrap_or(&Value::Null) {
+            Value::Null | Value::Undefined => {
+                let bitmap_data = bitmap_data.read();
+                (
+                    0,
+                    0,
+                    bitmap_data.width() as i32,
+                    bitmap_data.height() as i32,
+                )
+            }
+            rectangle => {
+                let rectangle = rectangle.coerce_to_object(activation)?;
+                (
+                    rectangle
+                        .get_public_property("x", activation)?
+                        .coerce_to_i32(activation)?,
+                    rectangle
+                        .get_public_property("y", activation)?
+                        .coerce_to_i32(activation)?,
+                    rectangle
+                        .get_public_property("width", activation)?
+                        .coerce_to_i32(activation)?,
+                    rectangle
+                        .get_public_property("height", activation)?
+                        .coerce_to_i32(activation)?,
+                )
+            }
+        };
+
+        let histogram = bitmap_data.read().histogram(rect);
+
+        let number_class = activation.avm2().classes().number;
+        let channels = histogram
+            .iter()
+            .map(|channel| {
+                let counts = channel
+                    .iter()
+                    .map(|&count| f64::from(count).into())
+                    .collect();
+                let storage = VectorStorage::from_values(counts, false, number_class);
+                Ok(VectorObject::from_vector(storage, activation)?.into())
+            })
+            .collect::<Result<Vec<_>, Error<'gc>>>()?;
+
+        let vector_class = activation.avm2().classes().vector;
+        let number_vector_class = vector_class.apply(activation, &[number_class.into()])?;
+        let storage = VectorStorage::from_values(channels, false, number_vector_class);
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setVector`.
+pub fn set_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let rectangle = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let x = rectangle
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let y = rectangle
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let width = rectangle
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let height = rectangle
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+
+        let vector = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let colors = vector
+            .as_vector_storage()
+            .ok_or("TypeError: Parameter must be a vector")?
+            .iter()
+            .collect::<Vec<_>>();
+        // Coercing may call into user code, so this has to be done before the bitmap is locked.
+        let colors = colors
+            .into_iter()
+            .map(|color| color.coerce_to_u32(activation))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut write = bitmap_data.write(activation.context.gc_context);
+        let mut colors = colors.into_iter();
+        for y in y..y + height {
+            for x in x..x + width {
+                // Copy data from the vector until it runs out or we're finished
+                if let Some(color) = colors.next() {
+                    write.set_pixel32(x, y, (color as i32).into());
+                } else {
+                    drop(write);
+                    return Err(Error::AvmError(range_error(
+                        activation,
+                        "Error #1125: The index is out of range.",
+                        1125,
+                    )?));
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.generateFilterRect`.
+pub fn generate_filter_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let source_rect = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let source_rect = (
+            source_rect
+                .get_public_property("x", activation)?
+                .coerce_to_i32(activation)?,
+            source_rect
+                .get_public_property("y", activation)?
+                .coerce_to_i32(activation)?,
+            source_rect
+                .get_public_property("width", activation)?
+                .coerce_to_i32(activation)?,
+            source_rect
+                .get_public_property("height", activation)?
+                .coerce_to_i32(activation)?,
+        );
+
+        let filter = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let filter = Filter::from_avm2_object(activation, filter)?;
+
+        let (x, y, width, height) = filter.calculate_dest_rect(source_rect);
+        return Ok(activation
+            .avm2()
+            .classes()
+            .rectangle
+            .construct(
+                activation,
+                &[x.into(), y.into(), width.into(), height.into()],
+            )?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.encode`.
+pub fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        bitmap_data.read().check_valid(activation)?;
+        let rectangle = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let x = rectangle
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let y = rectangle
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let width = rectangle
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let height = rectangle
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+
+        let compressor = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let bitmap = bitmap_data
+            .read()
+            .to_unmultiplied_bitmap(x, y, width, height);
+        let png_options = activation.avm2().classes().pngencoderoptions;
+        let jpeg_options = activation.avm2().classes().jpegencoderoptions;
+        let jpeg_xr_options = activation.avm2().classes().jpegxrencoderoptions;
+        let encoded = if compressor.is_of_type(png_options, activation) {
+            let fast_compression = compressor
+                .get_public_property("fastCompression", activation)?
+                .coerce_to_boolean();
+            encode_png(&bitmap, fast_compression)
+        } else if compressor.is_of_type(jpeg_options, activation) {
+            let quality = compressor
+                .get_public_property("quality", activation)?
+                .coerce_to_u32(activation)?;
+            encode_jpeg(&bitmap, quality.clamp(1, 100) as u8)
+        } else if compressor.is_of_type(jpeg_xr_options, activation) {
+            avm2_stub_method!(
+                activation,
+                "flash.display.BitmapData",
+                "encode",
+                "with JPEGXREncoderOptions"
+            );
+            return Ok(Value::Null);
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        };
+        let encoded = match encoded {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                tracing::warn!("BitmapData.encode: {e}");
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2004: One of the parameters is invalid.",
+                    2004,
+                )?));
+            }
+        };
+
+        // The encoded image is written at the current position of an existing `ByteArray`.
+        let byte_array = match args.get(2).unwrap_or(&Value::Null) {
+            Value::Null | Value::Undefined => {
+                ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?
+            }
+            byte_array => byte_array.coerce_to_object(activation)?,
+        };
+        byte_array
+            .as_bytearray_mut(activation.context.gc_context)
+            .ok_or("TypeError: Parameter byteArray must be a ByteArray")?
+            .write_bytes(&encoded)?;
+        return Ok(byte_array.into());
+    }
+
+    Ok(Value::Null)
+}
@@ -1,16 +1,32 @@
 //! `flash.display.Graphics` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::error::{argument_error, type_error};
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::object::{ArrayObject, Object, TObject, VectorObject};
 use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::WStr;
-use ruffle_render::shape_utils::DrawCommand;
+use ruffle_render::bitmap::{BitmapInfo, BitmapSource};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::shape_utils::{self, DrawCommand, DrawPath, FillRule};
 use std::f64::consts::FRAC_1_SQRT_2;
-use swf::{Color, FillStyle, Fixed8, LineCapStyle, LineJoinStyle, LineStyle, Twips};
+use swf::{
+    Color, FillStyle, Fixed8, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
+};
+
+// Values of `flash.display.GraphicsPathCommand`.
+const MOVE_TO: i32 = 1;
+const LINE_TO: i32 = 2;
+const CURVE_TO: i32 = 3;
+const WIDE_MOVE_TO: i32 = 4;
+const WIDE_LINE_TO: i32 = 5;
+const CUBIC_CURVE_TO: i32 = 6;
 
 /// Convert an RGB `color` and `alpha` argument pair into a `swf::Color`.
 /// `alpha` is normalized from 0.0 - 1.0.
@@ -44,23 +60,232 @@ pub fn begin_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Reads the elements of an `Array` argument.
+fn array_values<'gc>(value: Value<'gc>) -> Vec<Value<'gc>> {
+    if let Some(object) = value.as_object() {
+        if let Some(array) = object.as_array_storage() {
+            return array
+                .iter()
+                .map(|value| value.unwrap_or(Value::Undefined))
+                .collect();
+        }
+    }
+
+    vec![]
+}
+
+/// Reads the elements of a `Vector.<Number>` or `Vector.<int>` argument.
+///
+/// Returns `None` if the argument is not a vector.
+fn number_vector_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<Option<Vec<f64>>, Error<'gc>> {
+    let object = match value.as_object() {
+        Some(object) => object,
+        None => return Ok(None),
+    };
+    let values: Vec<Value<'gc>> = match object.as_vector_storage() {
+        Some(vector) => vector.iter().collect(),
+        None => return Ok(None),
+    };
+
+    values
+        .into_iter()
+        .map(|value| value.coerce_to_number(activation))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Creates the fill style described by the arguments of `beginGradientFill` or
+/// `lineGradientStyle`.
+///
+/// Returns `None` if Flash would silently ignore the call.
+fn create_gradient_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let gradient_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let colors = array_values(args.get(1).cloned().unwrap_or(Value::Null));
+    let alphas = array_values(args.get(2).cloned().unwrap_or(Value::Null));
+    let ratios = array_values(args.get(3).cloned().unwrap_or(Value::Null));
+    let matrix = match args.get(4).cloned().unwrap_or(Value::Null) {
+        Value::Object(matrix) => object_to_matrix(matrix, activation)?,
+        _ => Matrix::IDENTITY,
+    };
+    let spread_method = args
+        .get(5)
+        .cloned()
+        .unwrap_or_else(|| "pad".into())
+        .coerce_to_string(activation)?;
+    let interpolation_method = args
+        .get(6)
+        .cloned()
+        .unwrap_or_else(|| "rgb".into())
+        .coerce_to_string(activation)?;
+    let focal_point_ratio = args
+        .get(7)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_number(activation)?;
+
+    if colors.len() != alphas.len() || colors.len() != ratios.len() {
+        return Ok(None);
+    }
+
+    let mut records = Vec::with_capacity(colors.len());
+    for ((color, alpha), ratio) in colors.into_iter().zip(alphas).zip(ratios) {
+        let color = color.coerce_to_u32(activation)?;
+        let alpha = alpha.coerce_to_number(activation)?.clamp(0.0, 1.0);
+        let ratio = ratio.coerce_to_number(activation)?.clamp(0.0, 255.0);
+        records.push(GradientRecord {
+            ratio: ratio as u8,
+            color: color_from_args(color, alpha),
+        });
+    }
+
+    let spread = if &spread_method == b"reflect" {
+        GradientSpread::Reflect
+    } else if &spread_method == b"repeat" {
+        GradientSpread::Repeat
+    } else {
+        GradientSpread::Pad
+    };
+    let interpolation = if &interpolation_method == b"linearRGB" {
+        GradientInterpolation::LinearRgb
+    } else {
+        GradientInterpolation::Rgb
+    };
+    let gradient = Gradient {
+        matrix: matrix.into(),
+        spread,
+        interpolation,
+        records,
+    };
+
+    if &gradient_type == b"linear" {
+        Ok(Some(FillStyle::LinearGradient(gradient)))
+    } else if &gradient_type == b"radial" {
+        if focal_point_ratio == 0.0 {
+            Ok(Some(FillStyle::RadialGradient(gradient)))
+        } else {
+            Ok(Some(FillStyle::FocalGradient {
+                gradient,
+                focal_point: Fixed8::from_f64(focal_point_ratio.clamp(-1.0, 1.0)),
+            }))
+        }
+    } else {
+        Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2008: Parameter type must be one of the accepted values.",
+            2008,
+        )?))
+    }
+}
+
+/// Creates the fill style described by the arguments of `beginBitmapFill` or
+/// `lineBitmapStyle`, registering the bitmap with the drawing of `this`.
+///
+/// Returns `None` if the bitmap can't be drawn.
+fn create_bitmap_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: DisplayObject<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let bitmap_data = match args
+        .get(0)
+        .and_then(|value| value.as_object())
+        .and_then(|object| object.as_bitmap_data())
+    {
+        Some(bitmap_data) => bitmap_data,
+        None => {
+            return Err(Error::AvmError(type_error(
+                activation,
+                "Error #2007: Parameter bitmap must be non-null.",
+                2007,
+            )?))
+        }
+    };
+    let matrix = match args.get(1).cloned().unwrap_or(Value::Null) {
+        Value::Object(matrix) => object_to_matrix(matrix, activation)?,
+        _ => Matrix::IDENTITY,
+    };
+    let is_repeating = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| true.into())
+        .coerce_to_boolean();
+    let is_smoothed = args
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| false.into())
+        .coerce_to_boolean();
+
+    let mut bitmap_data = bitmap_data.write(activation.context.gc_context);
+    let handle = match bitmap_data.bitmap_handle(activation.context.renderer) {
+        Some(handle) => handle,
+        None => return Ok(None),
+    };
+    // The fill is drawn from the texture, so it needs any pending CPU changes.
+    bitmap_data.update_dirty_texture(activation.context.renderer);
+    let bitmap = BitmapInfo {
+        handle,
+        width: bitmap_data.width() as u16,
+        height: bitmap_data.height() as u16,
+    };
+    drop(bitmap_data);
+
+    let id = match this.as_drawing(activation.context.gc_context) {
+        Some(mut draw) => draw.add_bitmap(bitmap),
+        None => return Ok(None),
+    };
+    // Flash matrix is in pixels. Scale from pixels to twips.
+    let matrix =
+        matrix * Matrix::scale(Twips::TWIPS_PER_PIXEL as f32, Twips::TWIPS_PER_PIXEL as f32);
+
+    Ok(Some(FillStyle::Bitmap {
+        id,
+        matrix: matrix.into(),
+        is_smoothed,
+        is_repeating,
+    }))
+}
+
 /// Implements `Graphics.beginBitmapFill`.
 pub fn begin_bitmap_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "beginBitmapFill");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        if let Some(fill_style) = create_bitmap_fill(activation, this, args)? {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                draw.set_fill_style(Some(fill_style));
+            }
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Implements `Graphics.beginGradientFill`.
 pub fn begin_gradient_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "beginGradientFill");
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        if let Some(fill_style) = create_gradient_fill(activation, args)? {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                draw.set_fill_style(Some(fill_style));
+            }
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -79,6 +304,76 @@ pub fn clear<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Graphics.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let source = match args
+            .get(0)
+            .and_then(|value| value.as_object())
+            .and_then(|object| object.as_display_object())
+        {
+            Some(source) => source,
+            None => {
+                return Err(Error::AvmError(type_error(
+                    activation,
+                    "Error #2007: Parameter sourceGraphics must be non-null.",
+                    2007,
+                )?))
+            }
+        };
+
+        let source_drawing = source
+            .as_drawing(activation.context.gc_context)
+            .map(|drawing| drawing.clone());
+        if let Some(source_drawing) = source_drawing {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                draw.copy_from(&source_drawing);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.cubicCurveTo`.
+pub fn cubic_curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let mut points = [(Twips::ZERO, Twips::ZERO); 3];
+        for (i, point) in points.iter_mut().enumerate() {
+            let x = args
+                .get(i * 2)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+            let y = args
+                .get(i * 2 + 1)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+            *point = (Twips::from_pixels(x), Twips::from_pixels(y));
+        }
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let [control_a, control_b, anchor] = points;
+            for command in
+                shape_utils::cubic_curve_to_quadratics(draw.cursor(), control_a, control_b, anchor)
+            {
+                draw.draw_command(command);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `Graphics.curveTo`.
 pub fn curve_to<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -254,6 +549,46 @@ pub fn line_style<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Graphics.lineBitmapStyle`.
+pub fn line_bitmap_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        if let Some(fill_style) = create_bitmap_fill(activation, this, args)? {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                // This only changes the fill of the line style set by `lineStyle`.
+                if let Some(line_style) = draw.line_style().cloned() {
+                    draw.set_line_style(Some(line_style.with_fill_style(fill_style)));
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineGradientStyle`.
+pub fn line_gradient_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        if let Some(fill_style) = create_gradient_fill(activation, args)? {
+            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+                // This only changes the fill of the line style set by `lineStyle`.
+                if let Some(line_style) = draw.line_style().cloned() {
+                    draw.set_line_style(Some(line_style.with_fill_style(fill_style)));
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `Graphics.lineTo`.
 pub fn line_to<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -749,3 +1084,804 @@ pub fn draw_ellipse<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Draw a quarter circle around the given center, continuing from the end of
+/// the previous command.
+///
+/// The arc is drawn from the unit circle quadrant reflected by `scale_x` and
+/// `scale_y`, backwards if `reverse` is set.
+fn draw_corner_internal(
+    draw: &mut Drawing,
+    center_x: f64,
+    center_y: f64,
+    radius: f64,
+    (scale_x, scale_y): (f64, f64),
+    reverse: bool,
+) {
+    if radius <= 0.0 {
+        return;
+    }
+
+    let point = |(x, y): (f64, f64)| {
+        (
+            Twips::from_pixels(center_x + x * radius * scale_x),
+            Twips::from_pixels(center_y + y * radius * scale_y),
+        )
+    };
+    let ucp = UNIT_CIRCLE_POINTS;
+    let curves = if reverse {
+        [(ucp[3], ucp[2]), (ucp[1], ucp[0])]
+    } else {
+        [(ucp[1], ucp[2]), (ucp[3], ucp[4])]
+    };
+
+    for (control, anchor) in curves {
+        let (x1, y1) = point(control);
+        let (x2, y2) = point(anchor);
+        draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+    }
+}
+
+/// Implements `Graphics.drawRoundRectComplex`.
+pub fn draw_round_rect_complex<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let mut values = [0.0; 8];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = args
+                .get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+        }
+        let [x, y, width, height, top_left, top_right, bottom_left, bottom_right] = values;
+
+        // Corners can't be rounder than the rectangle is large.
+        let max_radius = (width.min(height) / 2.0).max(0.0);
+        let clamp_radius = |radius: f64| radius.clamp(0.0, max_radius);
+        let top_left = clamp_radius(top_left);
+        let top_right = clamp_radius(top_right);
+        let bottom_left = clamp_radius(bottom_left);
+        let bottom_right = clamp_radius(bottom_right);
+
+        let right = x + width;
+        let bottom = y + height;
+        let point = |x: f64, y: f64| (Twips::from_pixels(x), Twips::from_pixels(y));
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let (start_x, start_y) = point(right, bottom - bottom_right);
+            draw.draw_command(DrawCommand::MoveTo {
+                x: start_x,
+                y: start_y,
+            });
+            draw_corner_internal(
+                &mut draw,
+                right - bottom_right,
+                bottom - bottom_right,
+                bottom_right,
+                (1.0, 1.0),
+                false,
+            );
+            let (x1, y1) = point(x + bottom_left, bottom);
+            draw.draw_command(DrawCommand::LineTo { x: x1, y: y1 });
+            draw_corner_internal(
+                &mut draw,
+                x + bottom_left,
+                bottom - bottom_left,
+                bottom_left,
+                (-1.0, 1.0),
+                true,
+            );
+            let (x1, y1) = point(x, y + top_left);
+            draw.draw_command(DrawCommand::LineTo { x: x1, y: y1 });
+            draw_corner_internal(
+                &mut draw,
+                x + top_left,
+                y + top_left,
+                top_left,
+                (-1.0, -1.0),
+                false,
+            );
+            let (x1, y1) = point(right - top_right, y);
+            draw.draw_command(DrawCommand::LineTo { x: x1, y: y1 });
+            draw_corner_internal(
+                &mut draw,
+                right - top_right,
+                y + top_right,
+                top_right,
+                (1.0, -1.0),
+                true,
+            );
+            draw.draw_command(DrawCommand::LineTo {
+                x: start_x,
+                y: start_y,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Draw the commands of a `GraphicsPathCommand` vector, reading their
+/// coordinates from `data`.
+fn draw_path_internal(draw: &mut Drawing, commands: &[f64], data: &[f64]) {
+    let point = |i: usize| (Twips::from_pixels(data[i]), Twips::from_pixels(data[i + 1]));
+
+    let mut i = 0;
+    for &command in commands {
+        let command = command as i32;
+        let length = match command {
+            MOVE_TO | LINE_TO => 2,
+            CURVE_TO | WIDE_MOVE_TO | WIDE_LINE_TO => 4,
+            CUBIC_CURVE_TO => 6,
+            _ => 0,
+        };
+        if i + length > data.len() {
+            // Flash stops drawing the path once it runs out of data.
+            break;
+        }
+
+        match command {
+            MOVE_TO => {
+                let (x, y) = point(i);
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+            }
+            LINE_TO => {
+                let (x, y) = point(i);
+                draw.draw_command(DrawCommand::LineTo { x, y });
+            }
+            CURVE_TO => {
+                let (x1, y1) = point(i);
+                let (x2, y2) = point(i + 2);
+                draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+            }
+            WIDE_MOVE_TO => {
+                let (x, y) = point(i + 2);
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+            }
+            WIDE_LINE_TO => {
+                let (x, y) = point(i + 2);
+                draw.draw_command(DrawCommand::LineTo { x, y });
+            }
+            CUBIC_CURVE_TO => {
+                for command in shape_utils::cubic_curve_to_quadratics(
+                    draw.cursor(),
+                    point(i),
+                    point(i + 2),
+                    point(i + 4),
+                ) {
+                    draw.draw_command(command);
+                }
+            }
+            // `NO_OP` and unknown commands are skipped.
+            _ => {}
+        }
+
+        i += length;
+    }
+}
+
+/// Implements `Graphics.drawPath`.
+pub fn draw_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let commands =
+            match number_vector_values(activation, args.get(0).cloned().unwrap_or(Value::Null))? {
+                Some(commands) => commands,
+                None => {
+                    return Err(Error::AvmError(type_error(
+                        activation,
+                        "Error #2007: Parameter commands must be non-null.",
+                        2007,
+                    )?))
+                }
+            };
+        let data =
+            match number_vector_values(activation, args.get(1).cloned().unwrap_or(Value::Null))? {
+                Some(data) => data,
+                None => {
+                    return Err(Error::AvmError(type_error(
+                        activation,
+                        "Error #2007: Parameter data must be non-null.",
+                        2007,
+                    )?))
+                }
+            };
+        let winding = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| "evenOdd".into())
+            .coerce_to_string(activation)?;
+        let winding_rule = if &winding == b"evenOdd" {
+            FillRule::EvenOdd
+        } else if &winding == b"nonZero" {
+            FillRule::NonZero
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2008: Parameter winding must be one of the accepted values.",
+                2008,
+            )?));
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_winding_rule(winding_rule);
+            draw_path_internal(&mut draw, &commands, &data);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Which triangles `Graphics.drawTriangles` should skip.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TriangleCulling {
+    None,
+    Positive,
+    Negative,
+}
+
+/// Calculates the matrix of a bitmap fill that maps the bitmap coordinates
+/// `uv` onto the corners of a triangle, both given in pixels.
+///
+/// Returns `None` if the bitmap coordinates don't form a triangle.
+fn triangle_bitmap_matrix(uv: [(f64, f64); 3], corners: [(f64, f64); 3]) -> Option<Matrix> {
+    let (u1, v1) = (uv[1].0 - uv[0].0, uv[1].1 - uv[0].1);
+    let (u2, v2) = (uv[2].0 - uv[0].0, uv[2].1 - uv[0].1);
+    let (x1, y1) = (corners[1].0 - corners[0].0, corners[1].1 - corners[0].1);
+    let (x2, y2) = (corners[2].0 - corners[0].0, corners[2].1 - corners[0].1);
+
+    let determinant = u1 * v2 - u2 * v1;
+    if determinant == 0.0 {
+        return None;
+    }
+
+    let a = (x1 * v2 - x2 * v1) / determinant;
+    let b = (y1 * v2 - y2 * v1) / determinant;
+    let c = (x2 * u1 - x1 * u2) / determinant;
+    let d = (y2 * u1 - y1 * u2) / determinant;
+    let tx = corners[0].0 - (a * uv[0].0 + c * uv[0].1);
+    let ty = corners[0].1 - (b * uv[0].0 + d * uv[0].1);
+
+    // Bitmap fills map bitmap pixels to twips.
+    let twips_per_pixel = Twips::TWIPS_PER_PIXEL as f64;
+    Some(Matrix {
+        a: (a * twips_per_pixel) as f32,
+        b: (b * twips_per_pixel) as f32,
+        c: (c * twips_per_pixel) as f32,
+        d: (d * twips_per_pixel) as f32,
+        tx: Twips::from_pixels(tx),
+        ty: Twips::from_pixels(ty),
+    })
+}
+
+/// Draw each triangle as a separate closed path.
+///
+/// If the current fill is a bitmap fill and `uvt_data` is given, the bitmap
+/// is mapped onto each triangle.
+fn draw_triangles_internal(
+    draw: &mut Drawing,
+    vertices: &[f64],
+    triangles: &[[usize; 3]],
+    uvt_data: Option<&[f64]>,
+    culling: TriangleCulling,
+) {
+    let vertex_count = vertices.len() / 2;
+    let fill_style = draw.fill_style().cloned();
+    // The UVT data contains either `(u, v)` or `(u, v, t)` for each vertex.
+    let uvt_stride = match uvt_data {
+        Some(uvt_data) if uvt_data.len() >= vertex_count * 3 => 3,
+        _ => 2,
+    };
+
+    for &[i0, i1, i2] in triangles {
+        if i0.max(i1).max(i2) >= vertex_count {
+            continue;
+        }
+        let corners = [i0, i1, i2].map(|i| (vertices[i * 2], vertices[i * 2 + 1]));
+        let [p0, p1, p2] = corners;
+
+        let winding = (p1.0 - p0.0) * (p2.1 - p0.1) - (p1.1 - p0.1) * (p2.0 - p0.0);
+        match culling {
+            TriangleCulling::Positive if winding > 0.0 => continue,
+            TriangleCulling::Negative if winding < 0.0 => continue,
+            _ => {}
+        }
+
+        if let (
+            Some(FillStyle::Bitmap {
+                id,
+                is_smoothed,
+                is_repeating,
+                ..
+            }),
+            Some(uvt_data),
+        ) = (&fill_style, uvt_data)
+        {
+            let size = draw.bitmap_size(*id);
+            let uv = [i0, i1, i2].map(|i| {
+                uvt_data
+                    .get(i * uvt_stride)
+                    .zip(uvt_data.get(i * uvt_stride + 1))
+            });
+            if let (Some(size), [Some(uv0), Some(uv1), Some(uv2)]) = (size, uv) {
+                let [uv0, uv1, uv2] = [uv0, uv1, uv2]
+                    .map(|(u, v)| (u * f64::from(size.width), v * f64::from(size.height)));
+                if let Some(matrix) = triangle_bitmap_matrix([uv0, uv1, uv2], corners) {
+                    draw.set_fill_style(Some(FillStyle::Bitmap {
+                        id: *id,
+                        matrix: matrix.into(),
+                        is_smoothed: *is_smoothed,
+                        is_repeating: *is_repeating,
+                    }));
+                }
+            }
+        }
+
+        let [p0, p1, p2] = corners.map(|(x, y)| (Twips::from_pixels(x), Twips::from_pixels(y)));
+        draw.draw_command(DrawCommand::MoveTo { x: p0.0, y: p0.1 });
+        draw.draw_command(DrawCommand::LineTo { x: p1.0, y: p1.1 });
+        draw.draw_command(DrawCommand::LineTo { x: p2.0, y: p2.1 });
+        draw.draw_command(DrawCommand::LineTo { x: p0.0, y: p0.1 });
+    }
+
+    if uvt_data.is_some() && matches!(fill_style, Some(FillStyle::Bitmap { .. })) {
+        // Restore the original bitmap matrix for anything drawn afterwards.
+        draw.set_fill_style(fill_style);
+    }
+}
+
+/// Implements `Graphics.drawTriangles`.
+pub fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let vertices =
+            match number_vector_values(activation, args.get(0).cloned().unwrap_or(Value::Null))? {
+                Some(vertices) => vertices,
+                None => {
+                    return Err(Error::AvmError(type_error(
+                        activation,
+                        "Error #2007: Parameter vertices must be non-null.",
+                        2007,
+                    )?))
+                }
+            };
+        let indices =
+            number_vector_values(activation, args.get(1).cloned().unwrap_or(Value::Null))?;
+        let uvt_data =
+            number_vector_values(activation, args.get(2).cloned().unwrap_or(Value::Null))?;
+        let culling = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| "none".into())
+            .coerce_to_string(activation)?;
+
+        let culling = if &culling == b"none" {
+            TriangleCulling::None
+        } else if &culling == b"positive" {
+            TriangleCulling::Positive
+        } else if &culling == b"negative" {
+            TriangleCulling::Negative
+        } else {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2008: Parameter culling must be one of the accepted values.",
+                2008,
+            )?));
+        };
+
+        // Without indices, every three vertices form a triangle.
+        let triangles: Vec<[usize; 3]> = match indices {
+            Some(indices) => indices
+                .chunks_exact(3)
+                .map(|i| [i[0] as usize, i[1] as usize, i[2] as usize])
+                .collect(),
+            None => (0..vertices.len() / 6)
+                .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+                .collect(),
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw_triangles_internal(
+                &mut draw,
+                &vertices,
+                &triangles,
+                uvt_data.as_deref(),
+                culling,
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Creates a `GraphicsGradientFill` describing `gradient`, transformed by `matrix`.
+fn gradient_fill_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gradient_type: &'static str,
+    gradient: &Gradient,
+    focal_point_ratio: f64,
+    matrix: Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let colors = ArrayObject::from_storage(
+        activation,
+        gradient
+            .records
+            .iter()
+            .map(|record| Value::from(record.color.to_rgb()))
+            .collect(),
+    )?;
+    let alphas = ArrayObject::from_storage(
+        activation,
+        gradient
+            .records
+            .iter()
+            .map(|record| Value::from(f64::from(record.color.a) / 255.0))
+            .collect(),
+    )?;
+    let ratios = ArrayObject::from_storage(
+        activation,
+        gradient
+            .records
+            .iter()
+            .map(|record| Value::from(record.ratio))
+            .collect(),
+    )?;
+    let spread_method = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation_method = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+    let matrix = matrix_to_object(matrix * Matrix::from(gradient.matrix), activation)?;
+
+    let class = activation.avm2().classes().graphicsgradientfill;
+    Ok(class
+        .construct(
+            activation,
+            &[
+                gradient_type.into(),
+                colors.into(),
+                alphas.into(),
+                ratios.into(),
+                matrix,
+                spread_method.into(),
+                interpolation_method.into(),
+                focal_point_ratio.into(),
+            ],
+        )?
+        .into())
+}
+
+/// Creates the `IGraphicsFill` describing `style`, transformed by `matrix`.
+///
+/// Returns `None` for bitmap fills, which can't be described yet.
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &FillStyle,
+    matrix: Matrix,
+) -> Result<Option<Value<'gc>>, Error<'gc>> {
+    let fill = match style {
+        FillStyle::Color(color) => {
+            let class = activation.avm2().classes().graphicssolidfill;
+            class
+                .construct(
+                    activation,
+                    &[color.to_rgb().into(), (f64::from(color.a) / 255.0).into()],
+                )?
+                .into()
+        }
+        FillStyle::LinearGradient(gradient) => {
+            gradient_fill_to_object(activation, "linear", gradient, 0.0, matrix)?
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_fill_to_object(activation, "radial", gradient, 0.0, matrix)?
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_fill_to_object(activation, "radial", gradient, focal_point.to_f64(), matrix)?,
+        FillStyle::Bitmap { .. } => {
+            // Drawings only keep a handle to the bitmap, not the `BitmapData` it came from,
+            // so a `GraphicsBitmapFill` can't be created.
+            avm2_stub_method!(
+                activation,
+                "flash.display.Graphics",
+                "readGraphicsData",
+                "with bitmap fills"
+            );
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(fill))
+}
+
+/// Creates the `GraphicsStroke` describing `style`, transformed by `matrix`.
+///
+/// Returns `None` for strokes with bitmap fills, which can't be described yet.
+fn line_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &LineStyle,
+    matrix: Matrix,
+) -> Result<Option<Value<'gc>>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+    let fill = match fill_style_to_object(activation, style.fill_style(), matrix)? {
+        Some(fill) => fill,
+        None => return Ok(None),
+    };
+
+    let class = activation.avm2().classes().graphicsstroke;
+    Ok(Some(
+        class
+            .construct(
+                activation,
+                &[
+                    style.width().to_pixels().into(),
+                    style.is_pixel_hinted().into(),
+                    scale_mode.into(),
+                    caps.into(),
+                    joints.into(),
+                    miter_limit.into(),
+                    fill,
+                ],
+            )?
+            .into(),
+    ))
+}
+
+/// Creates the `GraphicsPath` describing `commands`, transformed by `matrix`.
+fn commands_to_path_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    matrix: Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut path_commands = Vec::with_capacity(commands.len());
+    let mut data = Vec::with_capacity(commands.len() * 2);
+    let mut push_point = |x: Twips, y: Twips| {
+        let (x, y) = matrix * (x, y);
+        data.push(x.to_pixels().into());
+        data.push(y.to_pixels().into());
+    };
+
+    let mut pending_move = None;
+    for command in commands {
+        if let DrawCommand::MoveTo { x, y } = *command {
+            // Consecutive moves don't draw anything, so only the last one is kept.
+            pending_move = Some((x, y));
+            continue;
+        }
+        if let Some((x, y)) = pending_move.take() {
+            path_commands.push(MOVE_TO.into());
+            push_point(x, y);
+        }
+
+        match *command {
+            DrawCommand::MoveTo { .. } => {}
+            DrawCommand::LineTo { x, y } => {
+                path_commands.push(LINE_TO.into());
+                push_point(x, y);
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                path_commands.push(CURVE_TO.into());
+                push_point(x1, y1);
+                push_point(x2, y2);
+            }
+        }
+    }
+    if let Some((x, y)) = pending_move {
+        path_commands.push(MOVE_TO.into());
+        push_point(x, y);
+    }
+
+    let int_class = activation.avm2().classes().int;
+    let path_commands = VectorStorage::from_values(path_commands, false, int_class);
+    let path_commands = VectorObject::from_vector(path_commands, activation)?;
+    let number_class = activation.avm2().classes().number;
+    let data = VectorStorage::from_values(data, false, number_class);
+    let data = VectorObject::from_vector(data, activation)?;
+
+    let winding = match winding_rule {
+        FillRule::EvenOdd => "evenOdd",
+        FillRule::NonZero => "nonZero",
+    };
+
+    let class = activation.avm2().classes().graphicspath;
+    Ok(class
+        .construct(
+            activation,
+            &[path_commands.into(), data.into(), winding.into()],
+        )?
+        .into())
+}
+
+/// Appends the `IGraphicsData` describing the drawing of `display_object`,
+/// and optionally those of its children, to `graphics_data`.
+fn read_graphics_data_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+    matrix: Matrix,
+    recurse: bool,
+    graphics_data: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let drawing = display_object
+        .as_drawing(activation.context.gc_context)
+        .map(|drawing| drawing.clone());
+    if let Some(drawing) = drawing {
+        for path in drawing.paths() {
+            let (DrawPath::Fill { commands, .. } | DrawPath::Stroke { commands, .. }) = &path;
+            if commands
+                .iter()
+                .all(|command| matches!(command, DrawCommand::MoveTo { .. }))
+            {
+                // Paths that only move the pen don't draw anything.
+                continue;
+            }
+
+            match path {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
+                    let Some(fill) = fill_style_to_object(activation, style, matrix)? else {
+                        continue;
+                    };
+                    graphics_data.push(fill);
+                    graphics_data.push(commands_to_path_object(
+                        activation,
+                        &commands,
+                        winding_rule,
+                        matrix,
+                    )?);
+                    let end_fill_class = activation.avm2().classes().graphicsendfill;
+                    graphics_data.push(end_fill_class.construct(activation, &[])?.into());
+                }
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => {
+                    let Some(stroke) = line_style_to_object(activation, style, matrix)? else {
+                        continue;
+                    };
+                    graphics_data.push(stroke);
+                    graphics_data.push(commands_to_path_object(
+                        activation,
+                        &commands,
+                        FillRule::EvenOdd,
+                        matrix,
+                    )?);
+                    // A stroke without a thickness ends the line.
+                    let stroke_class = activation.avm2().classes().graphicsstroke;
+                    graphics_data.push(stroke_class.construct(activation, &[])?.into());
+                }
+            }
+        }
+    }
+
+    if recurse {
+        if let Some(container) = display_object.as_container() {
+            let children: Vec<_> = container.iter_render_list().collect();
+            for child in children {
+                let child_matrix = matrix * *child.base().matrix();
+                read_graphics_data_internal(
+                    activation,
+                    child,
+                    child_matrix,
+                    recurse,
+                    graphics_data,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Implements `Graphics.readGraphicsData`.
+pub fn read_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let recurse = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| true.into())
+            .coerce_to_boolean();
+
+        let mut graphics_data = vec![];
+        read_graphics_data_internal(
+            activation,
+            this,
+            Matrix::IDENTITY,
+            recurse,
+            &mut graphics_data,
+        )?;
+
+        let value_type = activation.avm2().classes().igraphicsdata;
+        let storage = VectorStorage::from_values(graphics_data, false, value_type);
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangle_bitmap_matrix_maps_bitmap_onto_corners() {
+        // A 4x4 bitmap stretched onto a 20x40 triangle at (10, 10).
+        let matrix = triangle_bitmap_matrix(
+            [(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)],
+            [(10.0, 10.0), (30.0, 10.0), (10.0, 50.0)],
+        );
+        assert_eq!(
+            matrix,
+            Some(Matrix {
+                a: 100.0,
+                b: 0.0,
+                c: 0.0,
+                d: 200.0,
+                tx: Twips::from_pixels(10.0),
+                ty: Twips::from_pixels(10.0),
+            })
+        );
+
+        // The bitmap rotated by 90 degrees.
+        let matrix = triangle_bitmap_matrix(
+            [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+            [(0.0, 0.0), (0.0, 1.0), (-1.0, 0.0)],
+        );
+        assert_eq!(
+            matrix,
+            Some(Matrix {
+                a: 0.0,
+                b: 20.0,
+                c: -20.0,
+                d: 0.0,
+                tx: Twips::ZERO,
+                ty: Twips::ZERO,
+            })
+        );
+    }
+
+    #[test]
+    fn triangle_bitmap_matrix_rejects_degenerate_bitmap_coordinates() {
+        let matrix = triangle_bitmap_matrix(
+            [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)],
+            [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)],
+        );
+        assert_eq!(matrix, None);
+    }
+}
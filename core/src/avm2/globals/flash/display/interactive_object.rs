@@ -1,13 +1,13 @@
 //! `flash.display.InteractiveObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::range_error;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::display_object::{TDisplayObject, TInteractiveObject};
-use crate::{avm2_stub_getter, avm2_stub_setter};
 
 /// Implements `flash.display.InteractiveObject`'s native instance constructor.
 pub fn native_instance_init<'gc>(
@@ -138,66 +138,124 @@ pub fn set_context_menu<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.tabEnabled`'s getter.
 pub fn get_tab_enabled<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.InteractiveObject", "tabEnabled");
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        return Ok(int.tab_enabled(&mut activation.context).into());
+    }
 
-    Ok(false.into())
+    Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.tabEnabled`'s setter.
 pub fn set_tab_enabled<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.InteractiveObject", "tabIndex");
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        int.set_tab_enabled_override(activation.context.gc_context, Some(value));
+    }
 
     Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.tabIndex`'s getter.
 pub fn get_tab_index<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.InteractiveObject", "tabIndex");
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        return Ok(int.tab_index().unwrap_or(-1).into());
+    }
 
-    Ok((-1).into())
+    Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.tabIndex`'s setter.
 pub fn set_tab_index<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.InteractiveObject", "tabIndex");
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        if value < 0 {
+            return Err(Error::AvmError(range_error(
+                activation,
+                &format!(
+                    "Error #2027: Parameter tabIndex must be a non-negative number; got {value}."
+                ),
+                2027,
+            )?));
+        }
+        int.set_tab_index(activation.context.gc_context, Some(value));
+    }
 
     Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.focusRect`'s getter.
 pub fn get_focus_rect<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.InteractiveObject", "focusRect");
-    Ok(Value::Null)
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        return Ok(int.focus_rect().map_or(Value::Null, Value::from));
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.focusRect`'s setter.
 pub fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // NOTE: all values other than true or null are converted to false. (false/null do differ)
-
-    // let's only warn on true, as games sometimes just set focusRect to false for some reason.
-    if matches!(args.get(0), Some(Value::Bool(true))) {
-        avm2_stub_setter!(activation, "flash.display.InteractiveObject", "focusRect");
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        // All values other than true or null are converted to false; null
+        // falls back to `Stage.stageFocusRect`.
+        let value = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Null => None,
+            Value::Bool(true) => Some(true),
+            _ => Some(false),
+        };
+        int.set_focus_rect(activation.context.gc_context, value);
     }
 
-    Ok(Value::Null)
+    Ok(Value::Undefined)
 }
@@ -1,6 +1,7 @@
 //! `flash.display.Loader` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::net::add_request_headers;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::object::TObject;
 use crate::avm2::value::Value;
@@ -66,6 +67,9 @@ pub fn load<'gc>(
             .get_public_property("url", activation)?
             .coerce_to_string(activation)?;
 
+        let mut request = Request::get(url.to_string());
+        add_request_headers(activation, url_request, &mut request)?;
+
         // This is a dummy MovieClip, which will get overwritten in `Loader`
         let content = MovieClip::new(
             Arc::new(SwfMovie::empty(activation.context.swf.version())),
@@ -86,8 +90,8 @@ pub fn load<'gc>(
         let future = activation.context.load_manager.load_movie_into_clip(
             activation.context.player.clone(),
             content.into(),
-            // FIXME - set options from the `URLRequest`
-            Request::get(url.to_string()),
+            // FIXME - set the remaining options from the `URLRequest`
+            request,
             Some(url.to_string()),
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
             Some(Avm2LoaderData {
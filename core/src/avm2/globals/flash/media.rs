@@ -4,3 +4,4 @@ pub mod sound;
 pub mod soundchannel;
 pub mod soundmixer;
 pub mod soundtransform;
+pub mod video;
@@ -2,8 +2,12 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::error::{argument_error, io_error};
+use crate::avm2::globals::flash::net::add_request_headers;
 use crate::avm2::method::{Method, NativeMethodImpl};
-use crate::avm2::object::{sound_allocator, Object, QueuedPlay, SoundChannelObject, TObject};
+use crate::avm2::object::{
+    sound_allocator, Object, QueuedPlay, SoundChannelObject, SoundObject, TObject,
+};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
@@ -189,10 +193,62 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
+        let target = args.get(0).cloned().unwrap_or(Value::Null).as_object();
+        let length = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let start_position = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| (-1).into())
+            .coerce_to_number(activation)?;
+
+        let sound = match sound_object.sound_handle() {
+            Some(sound) => sound,
+            None => return Ok(0.into()),
+        };
+
+        // A negative start position continues from the end of the last extraction.
+        let start_frame = if start_position < 0.0 {
+            sound_object.extract_position()
+        } else {
+            start_position as u32
+        };
+        let samples = match activation.context.audio.extract_sound(
+            sound,
+            start_frame,
+            length.max(0.0) as usize,
+        ) {
+            Ok(samples) => samples,
+            Err(e) => {
+                tracing::error!("Sound.extract: Failed to decode sound: {}", e);
+                vec![]
+            }
+        };
+        sound_object.set_extract_position(
+            activation.context.gc_context,
+            start_frame + samples.len() as u32,
+        );
+
+        if let Some(mut bytearray) = target
+            .as_ref()
+            .and_then(|target| target.as_bytearray_mut(activation.context.gc_context))
+        {
+            for [left, right] in &samples {
+                bytearray.write_float(*left)?;
+                bytearray.write_float(*right)?;
+            }
+        }
+
+        return Ok((samples.len() as u32).into());
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -224,6 +280,9 @@ pub fn load<'gc>(
             .get_public_property("url", activation)?
             .coerce_to_string(activation)?;
 
+        let mut request = Request::get(url.to_string());
+        add_request_headers(activation, *url_request, &mut request)?;
+
         // TODO: context parameter currently unused.
         let _sound_context = args.get(1);
         if _sound_context.is_some() {
@@ -233,8 +292,8 @@ pub fn load<'gc>(
         let future = activation.context.load_manager.load_sound_avm2(
             activation.context.player.clone(),
             this,
-            // FIXME: Set options from the `URLRequest`.
-            Request::get(url.to_string()),
+            // FIXME: Set the remaining options from the `URLRequest`.
+            request,
         );
         activation.context.navigator.spawn_future(future);
     }
@@ -244,27 +303,136 @@ pub fn load<'gc>(
 /// `Sound.loadCompressedDataFromByteArray`
 pub fn load_compressed_data_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(
-        activation,
-        "flash.media.Sound",
-        "loadCompressedDataFromByteArray"
-    );
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
+        let bytes = args.get(0).cloned().unwrap_or(Value::Null).as_object();
+        let bytes_length = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)? as usize;
+
+        let data = match bytes.as_ref().and_then(|bytes| bytes.as_bytearray()) {
+            Some(bytes) => {
+                let bytes_length = bytes_length.min(bytes.bytes_available());
+                bytes.read_bytes(bytes_length).unwrap_or_default().to_vec()
+            }
+            None => return Ok(Value::Undefined),
+        };
+
+        check_not_loaded(activation, sound_object)?;
+        match activation.context.audio.register_mp3(&data) {
+            Ok(sound) => sound_object.set_sound(&mut activation.context, sound)?,
+            Err(e) => tracing::error!(
+                "Sound.loadCompressedDataFromByteArray: Failed to register sound: {}",
+                e
+            ),
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
 /// `Sound.loadPCMFromByteArray`
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    if let Some(sound_object) = this.and_then(|this| this.as_sound_object()) {
+        let bytes = args.get(0).cloned().unwrap_or(Value::Null).as_object();
+        let num_samples = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let format = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| "float".into())
+            .coerce_to_string(activation)?;
+        let is_stereo = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| true.into())
+            .coerce_to_boolean();
+        let sample_rate = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| 44100.into())
+            .coerce_to_number(activation)?;
+
+        let is_float = &format == b"float";
+        if !is_float && &format != b"short" {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2008: Parameter format must be one of the accepted values.",
+                2008,
+            )?));
+        }
+
+        // Convert the samples to the 16-bit little-endian PCM that the audio backend plays.
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let mut data = Vec::new();
+        if let Some(bytes) = bytes.as_ref().and_then(|bytes| bytes.as_bytearray()) {
+            for _ in 0..num_samples as usize * num_channels {
+                let sample = if is_float {
+                    match bytes.read_float() {
+                        Ok(sample) => (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16,
+                        Err(_) => break,
+                    }
+                } else {
+                    match bytes.read_short() {
+                        Ok(sample) => sample,
+                        Err(_) => break,
+                    }
+                };
+                data.extend_from_slice(&sample.to_le_bytes());
+            }
+        }
+
+        check_not_loaded(activation, sound_object)?;
+        let sound = swf::Sound {
+            id: 0,
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate: sample_rate as u16,
+                is_stereo,
+                is_16_bit: true,
+            },
+            num_samples: (data.len() / (2 * num_channels)) as u32,
+            data: &data,
+        };
+        match activation.context.audio.register_sound(&sound) {
+            Ok(sound) => sound_object.set_sound(&mut activation.context, sound)?,
+            Err(e) => tracing::error!(
+                "Sound.loadPCMFromByteArray: Failed to register sound: {}",
+                e
+            ),
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Throw an error if a sound has already been loaded into `sound_object`.
+fn check_not_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sound_object: SoundObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    if sound_object.sound_handle().is_some() {
+        return Err(Error::AvmError(io_error(
+            activation,
+            "Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.",
+            2037,
+        )?));
+    }
+
+    Ok(())
+}
+
 /// Construct `Sound`'s class.
 pub fn create_class<'gc>(activation: &mut Activation<'_, 'gc>) -> GcCell<'gc, Class<'gc>> {
     let mc = activation.context.gc_context;
@@ -0,0 +1,100 @@
+//! `flash.media.Video` native methods
+
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::display_object::{TDisplayObject, Video};
+
+/// Implements `flash.media.Video`'s `init` method, which is called from the constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            let width = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_i32(activation)?;
+            let height = args
+                .get(1)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_i32(activation)?;
+            let movie = activation.context.swf.clone();
+            let new_do = Video::new(
+                activation.context.gc_context,
+                movie,
+                width,
+                height,
+                Some(this.into()),
+            );
+
+            this.init_display_object(&mut activation.context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.videoWidth`
+pub fn get_video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let width = video.current_frame().map_or(0, |bitmap| bitmap.width);
+        return Ok(width.into());
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `Video.videoHeight`
+pub fn get_video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let height = video.current_frame().map_or(0, |bitmap| bitmap.height);
+        return Ok(height.into());
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `Video.attachNetStream`
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .as_object()
+            .and_then(|object| object.as_netstream());
+
+        if let Some(stream) = source {
+            video.attach_netstream(&mut activation.context, stream);
+        } else {
+            tracing::warn!("Video.attachNetStream: argument is not a NetStream");
+        }
+    }
+
+    Ok(Value::Undefined)
+}
@@ -1,11 +1,10 @@
 package flash.net {
 
     import flash.net.URLRequest;
-    import __ruffle__.stub_method;
 
     public native function navigateToURL(request:URLRequest, window:String = null):void;
 
-    public function registerClassAlias(a:String, b:Object):void {
-        stub_method("flash.net", "registerClassAlias");
-    }
-}
\ No newline at end of file
+    public native function registerClassAlias(aliasName:String, classObject:Object):void;
+
+    public native function getClassByAlias(aliasName:String):Object;
+}
@@ -1,11 +1,17 @@
 //! `flash.net` namespace
 
+use crate::avm2::error::{reference_error, type_error};
 use crate::avm2::object::TObject;
 use crate::avm2::{Activation, Error, Object, Value};
+use crate::backend::navigator::{is_request_header_allowed, Request};
 
+pub mod local_connection;
+pub mod net_stream;
 pub mod object_encoding;
 pub mod shared_object;
+pub mod socket;
 pub mod url_loader;
+pub mod xml_socket;
 
 /// Implements `flash.net.navigateToURL`
 pub fn navigate_to_url<'gc>(
@@ -34,3 +40,106 @@ pub fn navigate_to_url<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `flash.net.registerClassAlias`
+pub fn register_class_alias<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => {
+            return Err(Error::AvmError(type_error(
+                activation,
+                "Error #2007: Parameter aliasName must be non-null.",
+                2007,
+            )?))
+        }
+        name => name.coerce_to_string(activation)?,
+    };
+    let class_object = match args
+        .get(1)
+        .and_then(|class_object| class_object.as_object())
+        .and_then(|class_object| class_object.as_class_object())
+    {
+        Some(class_object) => class_object,
+        None => {
+            return Err(Error::AvmError(type_error(
+                activation,
+                "Error #2007: Parameter classObject must be non-null.",
+                2007,
+            )?))
+        }
+    };
+
+    activation
+        .context
+        .avm2
+        .register_class_alias(name, class_object);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.getClassByAlias`
+pub fn get_class_by_alias<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => {
+            return Err(Error::AvmError(type_error(
+                activation,
+                "Error #2007: Parameter aliasName must be non-null.",
+                2007,
+            )?))
+        }
+        name => name.coerce_to_string(activation)?,
+    };
+
+    match activation.avm2().get_class_by_alias(name) {
+        Some(class_object) => Ok(class_object.into()),
+        None => Err(Error::AvmError(reference_error(
+            activation,
+            &format!("Error #1014: Class {name} could not be found."),
+            1014,
+        )?)),
+    }
+}
+
+/// Add the `requestHeaders` of a `URLRequest` to a request.
+pub fn add_request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    url_request: Object<'gc>,
+    request: &mut Request,
+) -> Result<(), Error<'gc>> {
+    let headers = url_request.get_public_property("requestHeaders", activation)?;
+    let headers: Vec<Value<'gc>> = headers
+        .as_object()
+        .and_then(|headers| {
+            headers.as_array_storage().map(|headers| {
+                headers
+                    .iter()
+                    .map(|header| header.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default();
+
+    for header in headers {
+        if let Some(header) = header.as_object() {
+            let name = header
+                .get_public_property("name", activation)?
+                .coerce_to_string(activation)?;
+            let value = header
+                .get_public_property("value", activation)?
+                .coerce_to_string(activation)?;
+            let name = name.to_string();
+            if is_request_header_allowed(&name) {
+                request.add_header(name, value.to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
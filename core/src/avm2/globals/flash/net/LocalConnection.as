@@ -1,42 +1,64 @@
 package flash.net {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
 
-    // NOTE: this entire class is a stub.
-    // Thankfully (hopefully) a lot of code like Mochicrypt doesn't actually require this to... well do anything.
     public class LocalConnection extends EventDispatcher {
 
         public var client: Object;
 
+        // The domains that may send messages to this connection, as given to
+        // `allowDomain` and `allowInsecureDomain`.
+        internal var _allowedDomains: Array = [];
+        internal var _allowedInsecureDomains: Array = [];
+
         public function LocalConnection() {
             this.client = this;
         }
 
-        public function get domain():String {
-            // FIXME - implement this - this is unrelated to the messaging functionality.
-            stub_getter("flash.net.LocalConnection", "domain");
-            return "localhost";
-        }
+        public native function get domain():String;
 
         public function close(): void {
-            stub_method("flash.net.LocalConnection", "close");
+            if (!this.closeNative()) {
+                throw new ArgumentError("Error #2083: Close failed because the object is not connected.", 2083);
+            }
         }
 
+        private native function closeNative():Boolean;
+
         public function connect(connectionName:String): void {
-            stub_method("flash.net.LocalConnection", "connect");
+            if (connectionName == null) {
+                throw new TypeError("Error #2007: Parameter connectionName must be non-null.", 2007);
+            }
+
+            if (!this.connectNative(connectionName)) {
+                throw new ArgumentError("Error #2082: Connect failed because the object is already connected.", 2082);
+            }
         }
 
+        private native function connectNative(connectionName:String):Boolean;
+
         public function send(connectionName: String, methodName: String, ... arguments): void {
-            stub_method("flash.net.LocalConnection", "send");
+            if (connectionName == null) {
+                throw new TypeError("Error #2007: Parameter connectionName must be non-null.", 2007);
+            }
+            if (methodName == null) {
+                throw new TypeError("Error #2007: Parameter methodName must be non-null.", 2007);
+            }
+
+            this.sendNative(connectionName, methodName, arguments);
         }
 
+        private native function sendNative(connectionName:String, methodName:String, arguments:Array):void;
+
         public function allowDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowDomain");
+            for each (var domain:* in domains) {
+                this._allowedDomains.push(String(domain));
+            }
         }
 
         public function allowInsecureDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowInsecureDomain");
+            for each (var domain:* in domains) {
+                this._allowedInsecureDomains.push(String(domain));
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,6 @@
 package flash.net {
     import flash.events.EventDispatcher;
+    import flash.events.NetStatusEvent;
     import flash.errors.IOError;
     import __ruffle__.stub_method;
 
@@ -7,15 +8,38 @@ package flash.net {
 
         public static var defaultObjectEncoding:uint = 3;
 
+        private var _connected:Boolean = false;
+
         public function NetConnection() {}
         public var objectEncoding:uint = NetConnection.defaultObjectEncoding; // NOPMD WronglyNamedVariable
 
         public function connect(command:String, ... arguments):void {
+            if (command === null) {
+                // Progressive download of local or HTTP files; there is no server to talk to.
+                this._connected = true;
+                this.dispatchEvent(new NetStatusEvent(NetStatusEvent.NET_STATUS, false, false, {
+                    "code": "NetConnection.Connect.Success",
+                    "level": "status"
+                }));
+                return;
+            }
+
+            // Connecting to a media server isn't supported, so tell the script
+            // that the connection failed rather than leaving it waiting.
             stub_method("flash.net.NetConnection", "connect");
+            this._connected = false;
+            this.dispatchEvent(new NetStatusEvent(NetStatusEvent.NET_STATUS, false, false, {
+                "code": "NetConnection.Connect.Failed",
+                "level": "error"
+            }));
         }
 
         public function call(command:String, responder:Responder, ... arguments):void {
             stub_method("flash.net.NetConnection", "call");
         }
+
+        public function get connected():Boolean {
+            return this._connected;
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,41 @@
+package flash.net {
+    import flash.events.EventDispatcher;
+
+    [Ruffle(InstanceAllocator)]
+    public class NetStream extends EventDispatcher {
+        public static const CONNECT_TO_FMS:String = "connectToFMS";
+        public static const DIRECT_CONNECTIONS:String = "directConnections";
+
+        private var _client:Object;
+
+        public function NetStream(connection:NetConnection, peerID:String = "connectToFMS") {
+            this._client = this;
+        }
+
+        public function get client():Object {
+            return this._client;
+        }
+
+        public function set client(client:Object):void {
+            if (client === null) {
+                throw new TypeError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            this._client = client;
+        }
+
+        public native function get bufferLength():Number;
+        public native function get bufferTime():Number;
+        public native function set bufferTime(value:Number):void;
+        public native function get bytesLoaded():uint;
+        public native function get bytesTotal():uint;
+        public native function get time():Number;
+
+        public native function close():void;
+        public native function pause():void;
+        public native function play(... args):void;
+        public native function resume():void;
+        public native function seek(offset:Number):void;
+        public native function togglePause():void;
+    }
+}
@@ -0,0 +1,223 @@
+package flash.net {
+    import flash.errors.IOError;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+
+    public class Socket extends EventDispatcher implements IDataInput, IDataOutput {
+        // Received data that hasn't been read yet. The socket appends to it
+        // as data arrives.
+        internal var _readBuffer:ByteArray = new ByteArray();
+
+        // Data that was written but not sent yet.
+        internal var _writeBuffer:ByteArray = new ByteArray();
+
+        private var _timeout:uint = 20000;
+
+        public function Socket(host:String = null, port:int = 0) {
+            if (host != null && port != 0) {
+                this.connect(host, port);
+            }
+        }
+
+        public function connect(host:String, port:int):void {
+            if (port < 0 || port > 65535) {
+                throw new SecurityError("Error #2003: Invalid socket port number specified.", 2003);
+            }
+
+            this._readBuffer.clear();
+            this._writeBuffer.clear();
+            this.connectNative(host, port, this._timeout);
+        }
+
+        private native function connectNative(host:String, port:int, timeout:uint):void;
+
+        public function close():void {
+            this.checkConnected();
+            this.closeNative();
+        }
+
+        private native function closeNative():void;
+
+        public function flush():void {
+            this.checkConnected();
+            this.sendNative(this._writeBuffer);
+            this._writeBuffer.clear();
+        }
+
+        private native function sendNative(data:ByteArray):void;
+
+        public native function get connected():Boolean;
+
+        public function get timeout():uint {
+            return this._timeout;
+        }
+
+        public function set timeout(value:uint):void {
+            this._timeout = value;
+        }
+
+        public function get bytesAvailable():uint {
+            return this._readBuffer.bytesAvailable;
+        }
+
+        public function get bytesPending():uint {
+            return this._writeBuffer.length;
+        }
+
+        public function get endian():String {
+            return this._readBuffer.endian;
+        }
+
+        public function set endian(value:String):void {
+            this._readBuffer.endian = value;
+            this._writeBuffer.endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this._readBuffer.objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this._readBuffer.objectEncoding = value;
+            this._writeBuffer.objectEncoding = value;
+        }
+
+        private function checkConnected():void {
+            if (!this.connected) {
+                throw new IOError("Error #2002: Operation attempted on invalid socket.", 2002);
+            }
+        }
+
+        public function readBoolean():Boolean {
+            this.checkConnected();
+            return this._readBuffer.readBoolean();
+        }
+
+        public function readByte():int {
+            this.checkConnected();
+            return this._readBuffer.readByte();
+        }
+
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkConnected();
+            this._readBuffer.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble():Number {
+            this.checkConnected();
+            return this._readBuffer.readDouble();
+        }
+
+        public function readFloat():Number {
+            this.checkConnected();
+            return this._readBuffer.readFloat();
+        }
+
+        public function readInt():int {
+            this.checkConnected();
+            return this._readBuffer.readInt();
+        }
+
+        public function readMultiByte(length:uint, charSet:String):String {
+            this.checkConnected();
+            return this._readBuffer.readMultiByte(length, charSet);
+        }
+
+        public function readObject():* {
+            this.checkConnected();
+            return this._readBuffer.readObject();
+        }
+
+        public function readShort():int {
+            this.checkConnected();
+            return this._readBuffer.readShort();
+        }
+
+        public function readUnsignedByte():uint {
+            this.checkConnected();
+            return this._readBuffer.readUnsignedByte();
+        }
+
+        public function readUnsignedInt():uint {
+            this.checkConnected();
+            return this._readBuffer.readUnsignedInt();
+        }
+
+        public function readUnsignedShort():uint {
+            this.checkConnected();
+            return this._readBuffer.readUnsignedShort();
+        }
+
+        public function readUTF():String {
+            this.checkConnected();
+            return this._readBuffer.readUTF();
+        }
+
+        public function readUTFBytes(length:uint):String {
+            this.checkConnected();
+            return this._readBuffer.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.checkConnected();
+            this._writeBuffer.writeBoolean(value);
+        }
+
+        public function writeByte(value:int):void {
+            this.checkConnected();
+            this._writeBuffer.writeByte(value);
+        }
+
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkConnected();
+            this._writeBuffer.writeBytes(bytes, offset, length);
+        }
+
+        public function writeDouble(value:Number):void {
+            this.checkConnected();
+            this._writeBuffer.writeDouble(value);
+        }
+
+        public function writeFloat(value:Number):void {
+            this.checkConnected();
+            this._writeBuffer.writeFloat(value);
+        }
+
+        public function writeInt(value:int):void {
+            this.checkConnected();
+            this._writeBuffer.writeInt(value);
+        }
+
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.checkConnected();
+            this._writeBuffer.writeMultiByte(value, charSet);
+        }
+
+        public function writeObject(object:*):void { // NOPMD WronglyNamedVariable
+            this.checkConnected();
+            this._writeBuffer.writeObject(object);
+        }
+
+        public function writeShort(value:int):void {
+            this.checkConnected();
+            this._writeBuffer.writeShort(value);
+        }
+
+        public function writeUnsignedInt(value:uint):void {
+            this.checkConnected();
+            this._writeBuffer.writeUnsignedInt(value);
+        }
+
+        public function writeUTF(value:String):void {
+            this.checkConnected();
+            this._writeBuffer.writeUTF(value);
+        }
+
+        public function writeUTFBytes(value:String):void {
+            this.checkConnected();
+            this._writeBuffer.writeUTFBytes(value);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+package flash.net {
+    import flash.errors.IOError;
+    import flash.events.EventDispatcher;
+
+    public class XMLSocket extends EventDispatcher {
+        private var _timeout:uint = 20000;
+
+        public function XMLSocket(host:String = null, port:int = 0) {
+            if (host != null && port != 0) {
+                this.connect(host, port);
+            }
+        }
+
+        public function connect(host:String, port:int):void {
+            if (port < 0 || port > 65535) {
+                throw new SecurityError("Error #2003: Invalid socket port number specified.", 2003);
+            }
+
+            this.connectNative(host, port, this._timeout);
+        }
+
+        private native function connectNative(host:String, port:int, timeout:uint):void;
+
+        public function close():void {
+            this.checkConnected();
+            this.closeNative();
+        }
+
+        private native function closeNative():void;
+
+        public function send(object:*):void {
+            this.checkConnected();
+            this.sendNative(String(object));
+        }
+
+        private native function sendNative(data:String):void;
+
+        public native function get connected():Boolean;
+
+        public function get timeout():int {
+            return this._timeout;
+        }
+
+        public function set timeout(value:int):void {
+            this._timeout = value;
+        }
+
+        private function checkConnected():void {
+            if (!this.connected) {
+                throw new IOError("Error #2002: Operation attempted on invalid socket.", 2002);
+            }
+        }
+    }
+}
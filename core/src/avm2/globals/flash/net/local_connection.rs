@@ -0,0 +1,137 @@
+//! `flash.net.LocalConnection` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::serialize_value;
+use crate::avm2::error::argument_error;
+use crate::avm2::object::TObject;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Object};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::local_connection::{
+    encode_arguments, is_secure_movie, movie_domain, qualify_name, LocalConnections,
+    MAX_ARGUMENTS_SIZE,
+};
+use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+
+/// The domain of the movie, used to qualify connection names.
+fn domain(activation: &mut Activation<'_, '_>) -> String {
+    movie_domain(activation.context.swf.url()).unwrap_or_else(|| "localhost".to_owned())
+}
+
+/// Implements `LocalConnection.domain`
+pub fn get_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let domain = domain(activation);
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+/// Implements `LocalConnection.connectNative`, which returns `false` if the
+/// connection name is already in use.
+pub fn connect_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let name = qualify_name(&domain(activation), &name.to_utf8_lossy());
+        let movie = activation.context.swf.clone();
+        return Ok(LocalConnections::connect(
+            &mut activation.context,
+            this.into(),
+            name,
+            movie.url(),
+        )
+        .into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `LocalConnection.closeNative`, which returns `false` if the
+/// object isn't connected.
+pub fn close_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(LocalConnections::close(&mut activation.context, this.into()).into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `LocalConnection.sendNative`
+pub fn send_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+
+    let connection_name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let method_name = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let values: Vec<Value<'gc>> = args
+        .get(2)
+        .and_then(|arguments| arguments.as_object())
+        .and_then(|arguments| {
+            arguments.as_array_storage().map(|arguments| {
+                arguments
+                    .iter()
+                    .map(|value| value.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default();
+
+    let arguments =
+        values
+            .into_iter()
+            .map(|value| {
+                Ok(serialize_value(activation, value, AMFVersion::AMF3)?
+                    .unwrap_or(AmfValue::Undefined))
+            })
+            .collect::<Result<_, Error<'gc>>>()?;
+    let arguments = encode_arguments(arguments, AMFVersion::AMF3);
+    if arguments.len() > MAX_ARGUMENTS_SIZE {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2084: The AMF encoding of the arguments cannot exceed 40K.",
+            2084,
+        )?));
+    }
+
+    let message = LocalConnectionMessage {
+        connection_name: qualify_name(&domain(activation), &connection_name.to_utf8_lossy()),
+        method_name: method_name.to_string(),
+        arguments,
+        sender_domain: domain(activation),
+        sender_secure: is_secure_movie(activation.context.swf.url()),
+    };
+    activation
+        .context
+        .local_connections
+        .send(this.into(), message);
+
+    Ok(Value::Undefined)
+}
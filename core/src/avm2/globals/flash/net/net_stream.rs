@@ -0,0 +1,167 @@
+//! `flash.net.NetStream` native methods
+
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2_stub_method;
+
+pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
+
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        let buffer_time = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
+        ns.set_buffer_time(activation.context.gc_context, buffer_time);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_bytes_loaded<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        return Ok(ns.bytes_loaded().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_bytes_total<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        return Ok(ns.bytes_total().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        return Ok((ns.time() / 1000.0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        ns.close(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        ns.pause(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        let name = args.get(0).cloned().unwrap_or(Value::Undefined);
+        if matches!(name, Value::Null | Value::Undefined) {
+            avm2_stub_method!(activation, "flash.net.NetStream", "play", "with null name");
+            return Ok(Value::Undefined);
+        }
+
+        let name = name.coerce_to_string(activation)?;
+        ns.play(&mut activation.context, name);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn resume<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        ns.resume(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        let offset = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        ns.seek(&mut activation.context, offset * 1000.0);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn toggle_pause<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.and_then(|this| this.as_netstream()) {
+        ns.toggle_pause(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -150,8 +150,8 @@ pub fn get_local<'gc>(
 
     // Load the data object from storage if it existed prior
     if let Some(saved) = activation.context.storage.get(&full_name) {
-        if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
-            data = crate::avm2::amf::deserialize_lso(activation, &lso)?.into();
+        if let Some(obj) = crate::avm2::amf::read_lso(activation, &saved)? {
+            data = obj.into();
         }
     }
 
@@ -204,7 +204,7 @@ pub fn flush<'gc>(
             AMFVersion::AMF3,
         );
 
-        let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+        let bytes = crate::avm2::amf::write_lso(activation, &mut lso)?;
 
         return Ok(activation.context.storage.put(&name, &bytes).into());
     }
@@ -0,0 +1,106 @@
+//! `flash.net.Socket` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::TObject;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Object};
+use crate::socket::{default_host, SocketKind};
+use std::time::Duration;
+
+/// Connect a `Socket` or `XMLSocket` with the arguments `(host, port,
+/// timeout)`.
+pub fn connect_socket<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    kind: SocketKind,
+    args: &[Value<'gc>],
+) -> Result<(), Error<'gc>> {
+    let host = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => default_host(activation.context.swf.url()),
+        host => host.coerce_to_string(activation)?.to_string(),
+    };
+    let port = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let timeout = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    // The port was validated by the caller.
+    let port = port.clamp(0, u16::MAX.into()) as u16;
+    let timeout = Duration::from_millis(timeout.into());
+    activation.context.sockets.connect(
+        activation.context.navigator,
+        this.into(),
+        kind,
+        host,
+        port,
+        timeout,
+    );
+
+    Ok(())
+}
+
+/// Implements `Socket.connectNative`
+pub fn connect_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        connect_socket(activation, this, SocketKind::Binary, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.closeNative`
+pub fn close_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.context.sockets.close(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.sendNative`, which sends the contents of a `ByteArray`.
+pub fn send_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let data = args
+            .get(0)
+            .and_then(|data| data.as_object())
+            .and_then(|data| data.as_bytearray().map(|data| data.bytes().to_vec()));
+        if let Some(data) = data {
+            if !data.is_empty() {
+                activation.context.sockets.send(this.into(), data);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.connected`
+pub fn get_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(activation.context.sockets.is_connected(this.into()).into());
+    }
+
+    Ok(Value::Undefined)
+}
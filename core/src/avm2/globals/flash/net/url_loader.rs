@@ -1,6 +1,7 @@
 //! `flash.net.URLLoader` native function definitions
 
 use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::net::add_request_headers;
 use crate::avm2::object::TObject;
 use crate::avm2::value::Value;
 use crate::avm2::{Error, Object};
@@ -71,9 +72,9 @@ fn spawn_fetch<'gc>(
         Some(data.coerce_to_object(activation)?)
     };
 
-    // FIXME: set `requestHeaders`, `followRedirects`, `requestHeaders`, and `userAgent`
-    // from the `URLRequest`
+    // FIXME: set `followRedirects` and `userAgent` from the `URLRequest`
     let mut request = Request::request(method, url.to_string(), None);
+    add_request_headers(activation, *url_request, &mut request)?;
 
     if let Some(data) = data {
         if data.is_of_type(activation.avm2().classes().urlvariables, activation) {
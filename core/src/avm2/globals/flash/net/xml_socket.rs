@@ -0,0 +1,43 @@
+//! `flash.net.XMLSocket` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::net::socket::connect_socket;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Object};
+use crate::socket::SocketKind;
+
+pub use crate::avm2::globals::flash::net::socket::{close_native, get_connected};
+
+/// Implements `XMLSocket.connectNative`
+pub fn connect_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        connect_socket(activation, this, SocketKind::Xml, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.sendNative`
+pub fn send_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let data = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        activation
+            .context
+            .sockets
+            .send(this.into(), data.to_utf8_lossy().into_owned().into_bytes());
+    }
+
+    Ok(Value::Undefined)
+}
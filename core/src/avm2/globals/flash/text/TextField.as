@@ -1,6 +1,8 @@
 package flash.text {
+    import flash.display.DisplayObject;
     import flash.display.InteractiveObject;
-    import __ruffle__.stub_setter;
+    import flash.geom.Rectangle;
+    import __ruffle__.stub_method;
     
     public class TextField extends InteractiveObject {
         internal var _styleSheet:StyleSheet;
@@ -30,6 +32,8 @@ package flash.text {
         public native function set borderColor(value:uint):void;
         
         public native function get bottomScrollV():int;
+
+        public native function get caretIndex():int;
         
         public native function get condenseWhite():Boolean
         public native function set condenseWhite(value:Boolean):void
@@ -69,14 +73,22 @@ package flash.text {
         
         public native function get selectable():Boolean;
         public native function set selectable(value:Boolean):void;
+
+        public native function get selectionBeginIndex():int;
+
+        public native function get selectionEndIndex():int;
         
         public function get styleSheet():StyleSheet {
             return this._styleSheet;
         }
         public function set styleSheet(value:StyleSheet):void {
             this._styleSheet = value;
-            stub_setter("flash.text.TextField", "styleSheet");
+            this.setStyleSheet(value);
         }
+
+        // Styles are copied out of the style sheet when it's set, so later
+        // changes to it only apply once it's set again.
+        private native function setStyleSheet(styleSheet:StyleSheet):void;
         
         public native function get text():String;
         public native function set text(value:String):void;
@@ -109,7 +121,22 @@ package flash.text {
         public native function get numLines():int;
 
         public native function appendText(text:String):void;
+        public native function getCharBoundaries(charIndex:int):Rectangle;
+        public native function getCharIndexAtPoint(x:Number, y:Number):int;
+        public native function getFirstCharInParagraph(charIndex:int):int;
+
+        public function getImageReference(id:String):DisplayObject {
+            stub_method("flash.text.TextField", "getImageReference");
+            return null;
+        }
+
+        public native function getLineIndexAtPoint(x:Number, y:Number):int;
+        public native function getLineIndexOfChar(charIndex:int):int;
+        public native function getLineLength(lineIndex:int):int;
         public native function getLineMetrics(lineIndex:int):TextLineMetrics;
+        public native function getLineOffset(lineIndex:int):int;
+        public native function getLineText(lineIndex:int):String;
+        public native function getParagraphLength(charIndex:int):int;
         public native function getTextFormat():TextFormat;
         public native function setTextFormat(format:TextFormat, beginIndex:int = -1, endIndex:int = -1):void;
         public native function replaceSelectedText(value:String):void;
@@ -0,0 +1,124 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    public class ContentElement {
+        public static const GRAPHIC_ELEMENT:uint = 0xFDEF;
+
+        public var userData:*;
+
+        internal var _elementFormat:ElementFormat;
+        internal var _eventMirror:EventDispatcher;
+        internal var _textRotation:String;
+
+        // The group this element is a child of, if any.
+        internal var _groupElement:GroupElement;
+
+        // The text block this element is the content of, if any.
+        internal var _textBlock:TextBlock;
+
+        public function ContentElement(elementFormat:ElementFormat = null,
+                                       eventMirror:EventDispatcher = null,
+                                       textRotation:String = "rotate0") {
+            this._elementFormat = elementFormat;
+            this._eventMirror = eventMirror;
+            this.textRotation = textRotation;
+        }
+
+        public function get elementFormat():ElementFormat {
+            return this._elementFormat;
+        }
+
+        public function set elementFormat(value:ElementFormat):void {
+            this._elementFormat = value;
+            this.invalidate();
+        }
+
+        public function get eventMirror():EventDispatcher {
+            return this._eventMirror;
+        }
+
+        public function set eventMirror(value:EventDispatcher):void {
+            this._eventMirror = value;
+            this.invalidate();
+        }
+
+        public function get textRotation():String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value:String):void {
+            if ([TextRotation.ROTATE_0, TextRotation.ROTATE_90, TextRotation.ROTATE_180,
+                 TextRotation.ROTATE_270, TextRotation.AUTO].indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter textRotation must be one of the accepted values.", 2008);
+            }
+            this._textRotation = value;
+            this.invalidate();
+        }
+
+        public function get groupElement():GroupElement {
+            return this._groupElement;
+        }
+
+        // The text of this element, with `GRAPHIC_ELEMENT` standing in for
+        // each graphic.
+        public function get rawText():String {
+            return null;
+        }
+
+        public function get text():String {
+            var rawText:String = this.rawText;
+            if (rawText == null) {
+                return null;
+            }
+            return rawText.split(String.fromCharCode(GRAPHIC_ELEMENT)).join("");
+        }
+
+        public function get textBlock():TextBlock {
+            var element:ContentElement = this;
+            while (element._groupElement != null) {
+                element = element._groupElement;
+            }
+            return element._textBlock;
+        }
+
+        public function get textBlockBeginIndex():int {
+            var index:int = 0;
+            var element:ContentElement = this;
+            while (element._groupElement != null) {
+                var group:GroupElement = element._groupElement;
+                var count:int = group.getElementIndex(element);
+                for (var i:int = 0; i < count; i++) {
+                    index += group.getElementAt(i).rawText.length;
+                }
+                element = group;
+            }
+            return index;
+        }
+
+        // Append the runs of this element to the given arrays, one entry per
+        // leaf element: its raw text, its format, and its graphic element or
+        // null.
+        internal function flatten(texts:Array, formats:Array, graphics:Array, inherited:ElementFormat):void {
+            var rawText:String = this.rawText;
+            if (rawText == null || rawText.length == 0) {
+                return;
+            }
+            var format:ElementFormat = this._elementFormat != null ? this._elementFormat : inherited;
+            if (format == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            texts.push(rawText);
+            formats.push(format);
+            graphics.push(this as GraphicElement);
+        }
+
+        // Mark the lines of the containing text block as invalid, after the
+        // content of this element has changed.
+        internal function invalidate():void {
+            var textBlock:TextBlock = this.textBlock;
+            if (textBlock != null) {
+                textBlock.invalidateLines();
+            }
+        }
+    }
+}
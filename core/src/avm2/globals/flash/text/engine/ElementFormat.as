@@ -0,0 +1,253 @@
+package flash.text.engine {
+    import flash.errors.IllegalOperationError;
+
+    public final class ElementFormat {
+        private var _alignmentBaseline:String;
+        private var _alpha:Number;
+        private var _baselineShift:Number;
+        private var _breakOpportunity:String;
+        private var _color:uint;
+        private var _digitCase:String;
+        private var _digitWidth:String;
+        private var _dominantBaseline:String;
+        private var _fontDescription:FontDescription;
+        private var _fontSize:Number;
+        private var _kerning:String;
+        private var _ligatureLevel:String;
+        private var _locale:String;
+        private var _textRotation:String;
+        private var _trackingLeft:Number;
+        private var _trackingRight:Number;
+        private var _typographicCase:String;
+        private var _locked:Boolean = false;
+
+        public function ElementFormat(fontDescription:FontDescription = null,
+                                      fontSize:Number = 12,
+                                      color:uint = 0,
+                                      alpha:Number = 1,
+                                      textRotation:String = "auto",
+                                      dominantBaseline:String = "roman",
+                                      alignmentBaseline:String = "useDominantBaseline",
+                                      baselineShift:Number = 0,
+                                      kerning:String = "on",
+                                      trackingRight:Number = 0,
+                                      trackingLeft:Number = 0,
+                                      locale:String = "en",
+                                      breakOpportunity:String = "auto",
+                                      digitCase:String = "default",
+                                      digitWidth:String = "default",
+                                      ligatureLevel:String = "common",
+                                      typographicCase:String = "default") {
+            this.fontDescription = fontDescription != null ? fontDescription : new FontDescription();
+            this.fontSize = fontSize;
+            this.color = color;
+            this.alpha = alpha;
+            this.textRotation = textRotation;
+            this.dominantBaseline = dominantBaseline;
+            this.alignmentBaseline = alignmentBaseline;
+            this.baselineShift = baselineShift;
+            this.kerning = kerning;
+            this.trackingRight = trackingRight;
+            this.trackingLeft = trackingLeft;
+            this.locale = locale;
+            this.breakOpportunity = breakOpportunity;
+            this.digitCase = digitCase;
+            this.digitWidth = digitWidth;
+            this.ligatureLevel = ligatureLevel;
+            this.typographicCase = typographicCase;
+        }
+
+        public function get alignmentBaseline():String {
+            return this._alignmentBaseline;
+        }
+
+        public function set alignmentBaseline(value:String):void {
+            checkLocked();
+            checkValue(value, "alignmentBaseline", [TextBaseline.ROMAN, TextBaseline.ASCENT, TextBaseline.DESCENT, TextBaseline.IDEOGRAPHIC_TOP, TextBaseline.IDEOGRAPHIC_CENTER, TextBaseline.IDEOGRAPHIC_BOTTOM, TextBaseline.USE_DOMINANT_BASELINE]);
+            this._alignmentBaseline = value;
+        }
+
+        public function get alpha():Number {
+            return this._alpha;
+        }
+
+        public function set alpha(value:Number):void {
+            checkLocked();
+            this._alpha = value;
+        }
+
+        public function get baselineShift():Number {
+            return this._baselineShift;
+        }
+
+        public function set baselineShift(value:Number):void {
+            checkLocked();
+            this._baselineShift = value;
+        }
+
+        public function get breakOpportunity():String {
+            return this._breakOpportunity;
+        }
+
+        public function set breakOpportunity(value:String):void {
+            checkLocked();
+            checkValue(value, "breakOpportunity", [BreakOpportunity.ALL, BreakOpportunity.ANY, BreakOpportunity.AUTO, BreakOpportunity.NONE]);
+            this._breakOpportunity = value;
+        }
+
+        public function get color():uint {
+            return this._color;
+        }
+
+        public function set color(value:uint):void {
+            checkLocked();
+            this._color = value;
+        }
+
+        public function get digitCase():String {
+            return this._digitCase;
+        }
+
+        public function set digitCase(value:String):void {
+            checkLocked();
+            checkValue(value, "digitCase", [DigitCase.DEFAULT, DigitCase.LINING, DigitCase.OLD_STYLE]);
+            this._digitCase = value;
+        }
+
+        public function get digitWidth():String {
+            return this._digitWidth;
+        }
+
+        public function set digitWidth(value:String):void {
+            checkLocked();
+            checkValue(value, "digitWidth", [DigitWidth.DEFAULT, DigitWidth.PROPORTIONAL, DigitWidth.TABULAR]);
+            this._digitWidth = value;
+        }
+
+        public function get dominantBaseline():String {
+            return this._dominantBaseline;
+        }
+
+        public function set dominantBaseline(value:String):void {
+            checkLocked();
+            checkValue(value, "dominantBaseline", [TextBaseline.ROMAN, TextBaseline.ASCENT, TextBaseline.DESCENT, TextBaseline.IDEOGRAPHIC_TOP, TextBaseline.IDEOGRAPHIC_CENTER, TextBaseline.IDEOGRAPHIC_BOTTOM]);
+            this._dominantBaseline = value;
+        }
+
+        public function get fontDescription():FontDescription {
+            return this._fontDescription;
+        }
+
+        public function set fontDescription(value:FontDescription):void {
+            checkLocked();
+            this._fontDescription = value;
+        }
+
+        public function get fontSize():Number {
+            return this._fontSize;
+        }
+
+        public function set fontSize(value:Number):void {
+            checkLocked();
+            this._fontSize = value;
+        }
+
+        public function get kerning():String {
+            return this._kerning;
+        }
+
+        public function set kerning(value:String):void {
+            checkLocked();
+            checkValue(value, "kerning", [Kerning.ON, Kerning.OFF, Kerning.AUTO]);
+            this._kerning = value;
+        }
+
+        public function get ligatureLevel():String {
+            return this._ligatureLevel;
+        }
+
+        public function set ligatureLevel(value:String):void {
+            checkLocked();
+            checkValue(value, "ligatureLevel", [LigatureLevel.NONE, LigatureLevel.MINIMUM, LigatureLevel.COMMON, LigatureLevel.UNCOMMON, LigatureLevel.EXOTIC]);
+            this._ligatureLevel = value;
+        }
+
+        public function get locale():String {
+            return this._locale;
+        }
+
+        public function set locale(value:String):void {
+            checkLocked();
+            this._locale = value;
+        }
+
+        public function get textRotation():String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value:String):void {
+            checkLocked();
+            checkValue(value, "textRotation", [TextRotation.ROTATE_0, TextRotation.ROTATE_90, TextRotation.ROTATE_180, TextRotation.ROTATE_270, TextRotation.AUTO]);
+            this._textRotation = value;
+        }
+
+        public function get trackingLeft():Number {
+            return this._trackingLeft;
+        }
+
+        public function set trackingLeft(value:Number):void {
+            checkLocked();
+            this._trackingLeft = value;
+        }
+
+        public function get trackingRight():Number {
+            return this._trackingRight;
+        }
+
+        public function set trackingRight(value:Number):void {
+            checkLocked();
+            this._trackingRight = value;
+        }
+
+        public function get typographicCase():String {
+            return this._typographicCase;
+        }
+
+        public function set typographicCase(value:String):void {
+            checkLocked();
+            checkValue(value, "typographicCase", [TypographicCase.DEFAULT, TypographicCase.TITLE, TypographicCase.CAPS, TypographicCase.SMALL_CAPS, TypographicCase.UPPERCASE, TypographicCase.LOWERCASE, TypographicCase.CAPS_AND_SMALL_CAPS]);
+            this._typographicCase = value;
+        }
+
+        public function get locked():Boolean {
+            return this._locked;
+        }
+
+        public function set locked(value:Boolean):void {
+            checkLocked();
+            this._locked = value;
+        }
+
+        public native function getFontMetrics():FontMetrics;
+
+        public function clone():ElementFormat {
+            return new ElementFormat(this._fontDescription, this._fontSize, this._color, this._alpha,
+                                     this._textRotation, this._dominantBaseline, this._alignmentBaseline,
+                                     this._baselineShift, this._kerning, this._trackingRight, this._trackingLeft,
+                                     this._locale, this._breakOpportunity, this._digitCase, this._digitWidth,
+                                     this._ligatureLevel, this._typographicCase);
+        }
+
+        private function checkLocked():void {
+            if (this._locked) {
+                throw new IllegalOperationError("Error #2185: The ElementFormat object is locked and cannot be modified.", 2185);
+            }
+        }
+
+        private static function checkValue(value:String, name:String, accepted:Array):void {
+            if (accepted.indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+package flash.text.engine {
+    import flash.errors.IllegalOperationError;
+
+    public final class FontDescription {
+        private var _fontName:String;
+        private var _fontWeight:String;
+        private var _fontPosture:String;
+        private var _fontLookup:String;
+        private var _renderingMode:String;
+        private var _cffHinting:String;
+        private var _locked:Boolean = false;
+
+        public function FontDescription(fontName:String = "_serif",
+                                        fontWeight:String = "normal",
+                                        fontPosture:String = "normal",
+                                        fontLookup:String = "device",
+                                        renderingMode:String = "cff",
+                                        cffHinting:String = "horizontalStem") {
+            this.fontName = fontName;
+            this.fontWeight = fontWeight;
+            this.fontPosture = fontPosture;
+            this.fontLookup = fontLookup;
+            this.renderingMode = renderingMode;
+            this.cffHinting = cffHinting;
+        }
+
+        public static native function isFontCompatible(fontName:String, fontWeight:String, fontPosture:String):Boolean;
+        public static native function isDeviceFontCompatible(fontName:String, fontWeight:String, fontPosture:String):Boolean;
+
+        public function get fontName():String {
+            return this._fontName;
+        }
+
+        public function set fontName(value:String):void {
+            checkLocked();
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter fontName must be non-null.", 2007);
+            }
+            this._fontName = value;
+        }
+
+        public function get fontWeight():String {
+            return this._fontWeight;
+        }
+
+        public function set fontWeight(value:String):void {
+            checkLocked();
+            checkValue(value, "fontWeight", [FontWeight.NORMAL, FontWeight.BOLD]);
+            this._fontWeight = value;
+        }
+
+        public function get fontPosture():String {
+            return this._fontPosture;
+        }
+
+        public function set fontPosture(value:String):void {
+            checkLocked();
+            checkValue(value, "fontPosture", [FontPosture.NORMAL, FontPosture.ITALIC]);
+            this._fontPosture = value;
+        }
+
+        public function get fontLookup():String {
+            return this._fontLookup;
+        }
+
+        public function set fontLookup(value:String):void {
+            checkLocked();
+            checkValue(value, "fontLookup", [FontLookup.DEVICE, FontLookup.EMBEDDED_CFF]);
+            this._fontLookup = value;
+        }
+
+        public function get renderingMode():String {
+            return this._renderingMode;
+        }
+
+        public function set renderingMode(value:String):void {
+            checkLocked();
+            checkValue(value, "renderingMode", [RenderingMode.NORMAL, RenderingMode.CFF]);
+            this._renderingMode = value;
+        }
+
+        public function get cffHinting():String {
+            return this._cffHinting;
+        }
+
+        public function set cffHinting(value:String):void {
+            checkLocked();
+            checkValue(value, "cffHinting", [CFFHinting.NONE, CFFHinting.HORIZONTAL_STEM]);
+            this._cffHinting = value;
+        }
+
+        public function get locked():Boolean {
+            return this._locked;
+        }
+
+        public function set locked(value:Boolean):void {
+            checkLocked();
+            this._locked = value;
+        }
+
+        public function clone():FontDescription {
+            return new FontDescription(this._fontName, this._fontWeight, this._fontPosture,
+                                       this._fontLookup, this._renderingMode, this._cffHinting);
+        }
+
+        private function checkLocked():void {
+            if (this._locked) {
+                throw new IllegalOperationError("Error #2185: The FontDescription object is locked and cannot be modified.", 2185);
+            }
+        }
+
+        private static function checkValue(value:String, name:String, accepted:Array):void {
+            if (accepted.indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    public final class GroupElement extends ContentElement {
+        private var _elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+
+        public function GroupElement(elements:Vector.<ContentElement> = null,
+                                     elementFormat:ElementFormat = null,
+                                     eventMirror:EventDispatcher = null,
+                                     textRotation:String = "rotate0") {
+            super(elementFormat, eventMirror, textRotation);
+            this.setElements(elements);
+        }
+
+        override public function get rawText():String {
+            var rawText:String = "";
+            for each (var element:ContentElement in this._elements) {
+                var elementText:String = element.rawText;
+                if (elementText != null) {
+                    rawText += elementText;
+                }
+            }
+            return rawText;
+        }
+
+        public function get elementCount():int {
+            return this._elements.length;
+        }
+
+        public function getElementAt(index:int):ContentElement {
+            if (index < 0 || index >= this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._elements[index];
+        }
+
+        public function getElementAtCharIndex(charIndex:int):ContentElement {
+            if (charIndex < 0) {
+                return null;
+            }
+            var index:int = 0;
+            for each (var element:ContentElement in this._elements) {
+                var elementText:String = element.rawText;
+                var length:int = elementText != null ? elementText.length : 0;
+                if (charIndex < index + length) {
+                    var group:GroupElement = element as GroupElement;
+                    if (group != null) {
+                        return group.getElementAtCharIndex(charIndex - index);
+                    }
+                    return element;
+                }
+                index += length;
+            }
+            return null;
+        }
+
+        public function getElementIndex(element:ContentElement):int {
+            return this._elements.indexOf(element);
+        }
+
+        public function setElements(value:Vector.<ContentElement>):void {
+            this.replaceElements(0, this._elements.length, value);
+        }
+
+        public function replaceElements(beginIndex:int, endIndex:int, newElements:Vector.<ContentElement>):Vector.<ContentElement> {
+            if (beginIndex < 0 || endIndex < beginIndex || endIndex > this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            if (newElements != null) {
+                for each (var element:ContentElement in newElements) {
+                    // Elements of this group may only be reused from the
+                    // range being replaced.
+                    var index:int = this._elements.indexOf(element);
+                    var isReplaced:Boolean = index >= beginIndex && index < endIndex;
+                    if (element == null || element == this || element._textBlock != null ||
+                        (element._groupElement != null && !isReplaced)) {
+                        throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+                    }
+                }
+            }
+
+            var removed:Vector.<ContentElement> = this._elements.splice(beginIndex, endIndex - beginIndex);
+            for each (var old:ContentElement in removed) {
+                old._groupElement = null;
+            }
+            if (newElements != null) {
+                for (var i:int = 0; i < newElements.length; i++) {
+                    var newElement:ContentElement = newElements[i];
+                    newElement._groupElement = this;
+                    this._elements.splice(beginIndex + i, 0, newElement);
+                }
+            }
+            this.invalidate();
+            return removed;
+        }
+
+        public function groupElements(beginIndex:int, endIndex:int):GroupElement {
+            if (beginIndex < 0 || endIndex <= beginIndex || endIndex > this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var removed:Vector.<ContentElement> = this.replaceElements(beginIndex, endIndex, null);
+            var group:GroupElement = new GroupElement(removed);
+            var replacement:Vector.<ContentElement> = new Vector.<ContentElement>();
+            replacement.push(group);
+            this.replaceElements(beginIndex, beginIndex, replacement);
+            return group;
+        }
+
+        public function ungroupAt(groupIndex:int):void {
+            var group:GroupElement = this.getElementAt(groupIndex) as GroupElement;
+            if (group == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this.replaceElements(groupIndex, groupIndex + 1, null);
+            var children:Vector.<ContentElement> = group.replaceElements(0, group.elementCount, null);
+            this.replaceElements(groupIndex, groupIndex, children);
+        }
+
+        public function splitTextElement(elementIndex:int, splitIndex:int):TextElement {
+            var element:TextElement = this.getElementAt(elementIndex) as TextElement;
+            if (element == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            var text:String = element.rawText != null ? element.rawText : "";
+            if (splitIndex < 0 || splitIndex > text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+
+            var newElement:TextElement = new TextElement(text.substring(splitIndex), element.elementFormat,
+                                                         element.eventMirror, element.textRotation);
+            element.text = text.substring(0, splitIndex);
+            var replacement:Vector.<ContentElement> = new Vector.<ContentElement>();
+            replacement.push(newElement);
+            this.replaceElements(elementIndex + 1, elementIndex + 1, replacement);
+            return newElement;
+        }
+
+        public function mergeTextElements(beginIndex:int, endIndex:int):TextElement {
+            if (beginIndex < 0 || endIndex < beginIndex || endIndex >= this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var text:String = "";
+            for (var i:int = beginIndex; i <= endIndex; i++) {
+                var element:TextElement = this._elements[i] as TextElement;
+                if (element == null) {
+                    throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+                }
+                if (element.rawText != null) {
+                    text += element.rawText;
+                }
+            }
+
+            var merged:TextElement = this._elements[beginIndex] as TextElement;
+            this.replaceElements(beginIndex + 1, endIndex + 1, null);
+            merged.text = text;
+            return merged;
+        }
+
+        override internal function flatten(texts:Array, formats:Array, graphics:Array, inherited:ElementFormat):void {
+            var format:ElementFormat = this.elementFormat != null ? this.elementFormat : inherited;
+            for each (var element:ContentElement in this._elements) {
+                element.flatten(texts, formats, graphics, format);
+            }
+        }
+    }
+}
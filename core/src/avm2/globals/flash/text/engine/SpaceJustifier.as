@@ -0,0 +1,62 @@
+package flash.text.engine {
+    public final class SpaceJustifier extends TextJustifier {
+        private var _letterSpacing:Boolean;
+        private var _minimumSpacing:Number = 0.5;
+        private var _optimumSpacing:Number = 1.0;
+        private var _maximumSpacing:Number = 1.5;
+
+        public function SpaceJustifier(locale:String = "en", lineJustification:String = "unjustified", letterSpacing:Boolean = false) {
+            super(locale, lineJustification);
+            this._letterSpacing = letterSpacing;
+        }
+
+        public function get letterSpacing():Boolean {
+            return this._letterSpacing;
+        }
+
+        public function set letterSpacing(value:Boolean):void {
+            this._letterSpacing = value;
+        }
+
+        public function get minimumSpacing():Number {
+            return this._minimumSpacing;
+        }
+
+        public function set minimumSpacing(value:Number):void {
+            if (value < 0 || value > this._optimumSpacing) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._minimumSpacing = value;
+        }
+
+        public function get optimumSpacing():Number {
+            return this._optimumSpacing;
+        }
+
+        public function set optimumSpacing(value:Number):void {
+            if (value < this._minimumSpacing || value > this._maximumSpacing) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._optimumSpacing = value;
+        }
+
+        public function get maximumSpacing():Number {
+            return this._maximumSpacing;
+        }
+
+        public function set maximumSpacing(value:Number):void {
+            if (value < this._optimumSpacing) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._maximumSpacing = value;
+        }
+
+        override public function clone():TextJustifier {
+            var justifier:SpaceJustifier = new SpaceJustifier(this.locale, this.lineJustification, this._letterSpacing);
+            justifier._minimumSpacing = this._minimumSpacing;
+            justifier._optimumSpacing = this._optimumSpacing;
+            justifier._maximumSpacing = this._maximumSpacing;
+            return justifier;
+        }
+    }
+}
@@ -0,0 +1,422 @@
+package flash.text.engine {
+    import __ruffle__.stub_method;
+
+    [Ruffle(InstanceAllocator)]
+    public final class TextBlock {
+        public var userData:*;
+
+        private var _content:ContentElement;
+        private var _bidiLevel:int = 0;
+        private var _baselineZero:String = TextBaseline.ROMAN;
+        private var _baselineFontDescription:FontDescription = null;
+        private var _baselineFontSize:Number = 12;
+        private var _lineRotation:String = TextRotation.ROTATE_0;
+        private var _tabStops:Vector.<TabStop> = null;
+        private var _textJustifier:TextJustifier = new SpaceJustifier("en", LineJustification.UNJUSTIFIED, false);
+        private var _applyNonLinearFontScaling:Boolean = true;
+        private var _firstLine:TextLine = null;
+        private var _lastLine:TextLine = null;
+        private var _firstInvalidLine:TextLine = null;
+
+        // Set by `layoutLine`.
+        internal var _textLineCreationResult:String = null;
+
+        public function TextBlock(content:ContentElement = null,
+                                  tabStops:Vector.<TabStop> = null,
+                                  textJustifier:TextJustifier = null,
+                                  lineRotation:String = "rotate0",
+                                  baselineZero:String = "roman",
+                                  bidiLevel:int = 0,
+                                  applyNonLinearFontScaling:Boolean = true,
+                                  baselineFontDescription:FontDescription = null,
+                                  baselineFontSize:Number = 12.0) {
+            if (content != null) {
+                this.content = content;
+            }
+            if (tabStops != null) {
+                this.tabStops = tabStops;
+            }
+            if (textJustifier != null) {
+                this.textJustifier = textJustifier;
+            }
+            this.lineRotation = lineRotation;
+            this.baselineZero = baselineZero;
+            this.bidiLevel = bidiLevel;
+            this.applyNonLinearFontScaling = applyNonLinearFontScaling;
+            this.baselineFontDescription = baselineFontDescription;
+            this.baselineFontSize = baselineFontSize;
+        }
+
+        public function get content():ContentElement {
+            return this._content;
+        }
+
+        public function set content(value:ContentElement):void {
+            if (value != null && value != this._content &&
+                (value._textBlock != null || value._groupElement != null)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._content != null) {
+                this._content._textBlock = null;
+            }
+            this._content = value;
+            if (value != null) {
+                value._textBlock = this;
+            }
+            this.invalidateLines();
+        }
+
+        public function get bidiLevel():int {
+            return this._bidiLevel;
+        }
+
+        public function set bidiLevel(value:int):void {
+            if (value < 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._bidiLevel = value;
+            this.invalidateLines();
+        }
+
+        public function get baselineZero():String {
+            return this._baselineZero;
+        }
+
+        public function set baselineZero(value:String):void {
+            if ([TextBaseline.ROMAN, TextBaseline.ASCENT, TextBaseline.DESCENT, TextBaseline.IDEOGRAPHIC_TOP,
+                 TextBaseline.IDEOGRAPHIC_CENTER, TextBaseline.IDEOGRAPHIC_BOTTOM].indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter baselineZero must be one of the accepted values.", 2008);
+            }
+            this._baselineZero = value;
+            this.invalidateLines();
+        }
+
+        public function get baselineFontDescription():FontDescription {
+            return this._baselineFontDescription;
+        }
+
+        public function set baselineFontDescription(value:FontDescription):void {
+            this._baselineFontDescription = value;
+            this.invalidateLines();
+        }
+
+        public function get baselineFontSize():Number {
+            return this._baselineFontSize;
+        }
+
+        public function set baselineFontSize(value:Number):void {
+            if (value < 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._baselineFontSize = value;
+            this.invalidateLines();
+        }
+
+        public function get lineRotation():String {
+            return this._lineRotation;
+        }
+
+        public function set lineRotation(value:String):void {
+            if ([TextRotation.ROTATE_0, TextRotation.ROTATE_90, TextRotation.ROTATE_180,
+                 TextRotation.ROTATE_270].indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter lineRotation must be one of the accepted values.", 2008);
+            }
+            this._lineRotation = value;
+            this.invalidateLines();
+        }
+
+        public function get tabStops():Vector.<TabStop> {
+            return this._tabStops != null ? this._tabStops.concat() : null;
+        }
+
+        public function set tabStops(value:Vector.<TabStop>):void {
+            this._tabStops = value != null ? value.concat() : null;
+            this.invalidateLines();
+        }
+
+        public function get textJustifier():TextJustifier {
+            return this._textJustifier.clone();
+        }
+
+        public function set textJustifier(value:TextJustifier):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter textJustifier must be non-null.", 2007);
+            }
+            this._textJustifier = value.clone();
+            this.invalidateLines();
+        }
+
+        public function get applyNonLinearFontScaling():Boolean {
+            return this._applyNonLinearFontScaling;
+        }
+
+        public function set applyNonLinearFontScaling(value:Boolean):void {
+            this._applyNonLinearFontScaling = value;
+        }
+
+        public function get firstLine():TextLine {
+            return this._firstLine;
+        }
+
+        public function get lastLine():TextLine {
+            return this._lastLine;
+        }
+
+        public function get firstInvalidLine():TextLine {
+            return this._firstInvalidLine;
+        }
+
+        public function get textLineCreationResult():String {
+            return this._textLineCreationResult;
+        }
+
+        public function createTextLine(previousLine:TextLine = null,
+                                       width:Number = 1000000,
+                                       lineOffset:Number = 0.0,
+                                       fitSomething:Boolean = false):TextLine {
+            return this.layout(null, previousLine, width, fitSomething);
+        }
+
+        public function recreateTextLine(textLine:TextLine,
+                                         previousLine:TextLine = null,
+                                         width:Number = 1000000,
+                                         lineOffset:Number = 0.0,
+                                         fitSomething:Boolean = false):TextLine {
+            if (textLine == null) {
+                throw new TypeError("Error #2007: Parameter textLine must be non-null.", 2007);
+            }
+            if (textLine == previousLine) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (textLine._textBlock != null) {
+                textLine._textBlock.releaseLines(textLine, textLine);
+            }
+            return this.layout(textLine, previousLine, width, fitSomething);
+        }
+
+        public function releaseLines(firstLine:TextLine, lastLine:TextLine):void {
+            if (firstLine == null || lastLine == null) {
+                throw new TypeError("Error #2007: Parameter firstLine must be non-null.", 2007);
+            }
+            if (firstLine._textBlock != this || lastLine._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = firstLine._previousLine;
+            var after:TextLine = lastLine._nextLine;
+            var line:TextLine = firstLine;
+            while (line != null) {
+                var next:TextLine = line._nextLine;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line._validity != TextLineValidity.STATIC) {
+                    line._validity = TextLineValidity.INVALID;
+                }
+                if (line == lastLine) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before != null) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after != null) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
+            }
+            this.updateFirstInvalidLine();
+        }
+
+        public function releaseLineCreationData():void {
+            this.clearPreparedLines();
+        }
+
+        public function getTextLineAtCharIndex(charIndex:int):TextLine {
+            if (this._content == null || charIndex < 0 || charIndex >= this._content.rawText.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                var begin:int = line._textBlockBeginIndex;
+                if (begin <= charIndex && charIndex < begin + line._rawTextLength) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function findNextAtomBoundary(afterCharIndex:int):int {
+            var rawText:String = this.checkedRawText(afterCharIndex);
+            var index:int = afterCharIndex + 1;
+            if (isLowSurrogate(rawText, index)) {
+                index++;
+            }
+            return index;
+        }
+
+        public function findPreviousAtomBoundary(beforeCharIndex:int):int {
+            var rawText:String = this.checkedRawText(beforeCharIndex - 1);
+            var index:int = beforeCharIndex - 1;
+            if (isLowSurrogate(rawText, index)) {
+                index--;
+            }
+            return index;
+        }
+
+        public function findNextWordBoundary(afterCharIndex:int):int {
+            var rawText:String = this.checkedRawText(afterCharIndex);
+            var isWord:Boolean = isWordCharacter(rawText, afterCharIndex);
+            var index:int = afterCharIndex + 1;
+            while (index < rawText.length && isWordCharacter(rawText, index) == isWord) {
+                index++;
+            }
+            return index;
+        }
+
+        public function findPreviousWordBoundary(beforeCharIndex:int):int {
+            var rawText:String = this.checkedRawText(beforeCharIndex - 1);
+            var isWord:Boolean = isWordCharacter(rawText, beforeCharIndex - 1);
+            var index:int = beforeCharIndex - 1;
+            while (index > 0 && isWordCharacter(rawText, index - 1) == isWord) {
+                index--;
+            }
+            return index;
+        }
+
+        public function dump():String {
+            stub_method("flash.text.engine.TextBlock", "dump");
+            return "";
+        }
+
+        // Flattens the content into the paragraph that lines are broken
+        // out of, which is kept until the next call or `clearPreparedLines`.
+        private native function prepareLines(texts:Array, formats:Array, graphics:Array):void;
+
+        private native function clearPreparedLines():void;
+
+        private native function hasPreparedLines():Boolean;
+
+        private native function layoutLine(reuse:TextLine,
+                                           beginIndex:int,
+                                           width:Number,
+                                           fitSomething:Boolean):TextLine;
+
+        private function layout(reuse:TextLine, previousLine:TextLine, width:Number, fitSomething:Boolean):TextLine {
+            if (isNaN(width) || width < 0 || width > TextLine.MAX_LINE_WIDTH) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (previousLine != null && previousLine._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._content == null) {
+                this._textLineCreationResult = TextLineCreationResult.COMPLETE;
+                return null;
+            }
+
+            var beginIndex:int = 0;
+            if (previousLine != null) {
+                beginIndex = previousLine._textBlockBeginIndex + previousLine._rawTextLength;
+            }
+            // The paragraph is prepared once for each sequence of lines, which
+            // starts over when the first line is created.
+            if (previousLine == null || !this.hasPreparedLines()) {
+                var texts:Array = [];
+                var formats:Array = [];
+                var graphics:Array = [];
+                this._content.flatten(texts, formats, graphics, null);
+                this.prepareLines(texts, formats, graphics);
+            }
+
+            var line:TextLine = this.layoutLine(reuse, beginIndex, width, fitSomething);
+            if (line == null) {
+                return null;
+            }
+
+            // The lines after the previous one no longer follow on from it.
+            var next:TextLine = previousLine != null ? previousLine._nextLine : this._firstLine;
+            if (next != null) {
+                this.releaseLines(next, this._lastLine);
+            }
+
+            line._textBlock = this;
+            line._textBlockBeginIndex = beginIndex;
+            line._specifiedWidth = width;
+            line._validity = TextLineValidity.VALID;
+            line._previousLine = previousLine;
+            line._nextLine = null;
+            if (previousLine != null) {
+                previousLine._nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
+
+            line.placeGraphics();
+            line.buildMirrorRegions();
+            this.updateFirstInvalidLine();
+            return line;
+        }
+
+        // The leaf element containing the character at the given index.
+        internal function elementAtCharIndex(charIndex:int):ContentElement {
+            var group:GroupElement = this._content as GroupElement;
+            if (group != null) {
+                return group.getElementAtCharIndex(charIndex);
+            }
+            return this._content;
+        }
+
+        // Mark all lines as invalid, after the content or the formatting of
+        // the block changed.
+        internal function invalidateLines():void {
+            this.clearPreparedLines();
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (line._validity != TextLineValidity.STATIC) {
+                    line._validity = TextLineValidity.INVALID;
+                }
+            }
+            this.updateFirstInvalidLine();
+        }
+
+        // Called when user code marks a line as invalid.
+        internal function lineInvalidated(line:TextLine):void {
+            this.updateFirstInvalidLine();
+        }
+
+        private function updateFirstInvalidLine():void {
+            this._firstInvalidLine = null;
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (line._validity == TextLineValidity.INVALID) {
+                    this._firstInvalidLine = line;
+                    return;
+                }
+            }
+        }
+
+        private function checkedRawText(index:int):String {
+            var rawText:String = this._content != null ? this._content.rawText : "";
+            if (index < 0 || index >= rawText.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return rawText;
+        }
+
+        private static function isLowSurrogate(rawText:String, index:int):Boolean {
+            var code:Number = rawText.charCodeAt(index);
+            return code >= 0xDC00 && code <= 0xDFFF;
+        }
+
+        internal static function isWordCharacter(rawText:String, index:int):Boolean {
+            var code:Number = rawText.charCodeAt(index);
+            if (isNaN(code) || code == ContentElement.GRAPHIC_ELEMENT) {
+                return false;
+            }
+            var c:String = rawText.charAt(index);
+            return c.toLowerCase() != c.toUpperCase() || (code >= 0x30 && code <= 0x39) || code == 0x5F || code > 0x2E7F;
+        }
+    }
+}
@@ -0,0 +1,45 @@
+package flash.text.engine {
+    public class TextJustifier {
+        private var _locale:String;
+        private var _lineJustification:String;
+
+        public function TextJustifier(locale:String, lineJustification:String) {
+            if (!(this is SpaceJustifier) && !(this is EastAsianJustifier)) {
+                throw new ArgumentError("Error #2012: TextJustifier$ class cannot be instantiated.", 2012);
+            }
+            if (locale == null) {
+                throw new TypeError("Error #2007: Parameter locale must be non-null.", 2007);
+            }
+            this._locale = locale;
+            this.lineJustification = lineJustification;
+        }
+
+        public static function getJustifierForLocale(locale:String):TextJustifier {
+            var language:String = locale != null ? locale.substr(0, 2).toLowerCase() : "";
+            if (language == "ja" || language == "zh") {
+                return new EastAsianJustifier(locale);
+            }
+            return new SpaceJustifier(locale);
+        }
+
+        public function get locale():String {
+            return this._locale;
+        }
+
+        public function get lineJustification():String {
+            return this._lineJustification;
+        }
+
+        public function set lineJustification(value:String):void {
+            if ([LineJustification.UNJUSTIFIED, LineJustification.ALL_BUT_LAST,
+                 LineJustification.ALL_BUT_MANDATORY_BREAK, LineJustification.ALL_INCLUDING_LAST].indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter lineJustification must be one of the accepted values.", 2008);
+            }
+            this._lineJustification = value;
+        }
+
+        public function clone():TextJustifier {
+            return null;
+        }
+    }
+}
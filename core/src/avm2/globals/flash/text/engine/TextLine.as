@@ -0,0 +1,368 @@
+package flash.text.engine {
+    import __ruffle__.stub_method;
+    import flash.display.DisplayObject;
+    import flash.display.DisplayObjectContainer;
+    import flash.errors.IllegalOperationError;
+    import flash.events.EventDispatcher;
+    import flash.events.MouseEvent;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+
+    [Ruffle(NativeInstanceInit)]
+    public final class TextLine extends DisplayObjectContainer {
+        public static const MAX_LINE_WIDTH:int = 1000000;
+
+        // The number of entries in `_atoms` for each atom.
+        internal static const ATOM_STRIDE:int = 7;
+
+        public var userData:*;
+
+        // Lines are created without running the constructor, so these only
+        // have the default values of their types until they are set.
+
+        // Set by `TextBlock.layoutLine`. Each atom is stored as its begin and
+        // end index in the text block, its bounds relative to the baseline,
+        // and its bidi level.
+        internal var _atoms:Array;
+        internal var _ascent:Number = 0;
+        internal var _descent:Number = 0;
+        internal var _textWidth:Number = 0;
+        internal var _unjustifiedTextWidth:Number = 0;
+        internal var _rawTextLength:int = 0;
+        internal var _hasTabs:Boolean = false;
+
+        internal var _textBlock:TextBlock;
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _specifiedWidth:Number = 0;
+        internal var _validity:String;
+        internal var _nextLine:TextLine;
+        internal var _previousLine:TextLine;
+        internal var _mirrorRegions:Vector.<TextLineMirrorRegion>;
+        internal var _hasGraphicElement:Boolean = false;
+        internal var _forwardsMouseEvents:Boolean = false;
+
+        public function TextLine() {
+            throw new ArgumentError("Error #2012: TextLine$ class cannot be instantiated.", 2012);
+        }
+
+        public function get ascent():Number {
+            return this._ascent;
+        }
+
+        public function get descent():Number {
+            return this._descent;
+        }
+
+        public function get totalAscent():Number {
+            return this._ascent;
+        }
+
+        public function get totalDescent():Number {
+            return this._descent;
+        }
+
+        public function get totalHeight():Number {
+            return this._ascent + this._descent;
+        }
+
+        public function get textHeight():Number {
+            return this._ascent + this._descent;
+        }
+
+        public function get textWidth():Number {
+            return this._textWidth;
+        }
+
+        public function get unjustifiedTextWidth():Number {
+            return this._unjustifiedTextWidth;
+        }
+
+        public function get specifiedWidth():Number {
+            return this._specifiedWidth;
+        }
+
+        public function get atomCount():int {
+            return this._atoms.length / ATOM_STRIDE;
+        }
+
+        public function get rawTextLength():int {
+            return this._rawTextLength;
+        }
+
+        public function get hasGraphicElement():Boolean {
+            return this._hasGraphicElement;
+        }
+
+        public function get hasTabs():Boolean {
+            return this._hasTabs;
+        }
+
+        public function get mirrorRegions():Vector.<TextLineMirrorRegion> {
+            return this._mirrorRegions;
+        }
+
+        public function get nextLine():TextLine {
+            return this._nextLine;
+        }
+
+        public function get previousLine():TextLine {
+            return this._previousLine;
+        }
+
+        public function get textBlock():TextBlock {
+            return this._textBlock;
+        }
+
+        public function get textBlockBeginIndex():int {
+            return this._textBlockBeginIndex;
+        }
+
+        public function get validity():String {
+            return this._validity;
+        }
+
+        public function set validity(value:String):void {
+            if ([TextLineValidity.VALID, TextLineValidity.POSSIBLY_INVALID, TextLineValidity.INVALID,
+                 TextLineValidity.STATIC].indexOf(value) == -1) {
+                throw new ArgumentError("Error #2008: Parameter validity must be one of the accepted values.", 2008);
+            }
+            if (this._validity == TextLineValidity.STATIC || value == TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._validity = value;
+            if (this._textBlock != null && value != TextLineValidity.POSSIBLY_INVALID) {
+                this._textBlock.lineInvalidated(this);
+            }
+        }
+
+        public function getAtomBounds(atomIndex:int):Rectangle {
+            var i:int = this.atomOffset(atomIndex);
+            return new Rectangle(this._atoms[i + 2], this._atoms[i + 4], this._atoms[i + 3], this._atoms[i + 5]);
+        }
+
+        public function getAtomCenter(atomIndex:int):Number {
+            var i:int = this.atomOffset(atomIndex);
+            return this._atoms[i + 2] + this._atoms[i + 3] / 2;
+        }
+
+        public function getAtomBidiLevel(atomIndex:int):int {
+            return this._atoms[this.atomOffset(atomIndex) + 6];
+        }
+
+        public function getAtomTextBlockBeginIndex(atomIndex:int):int {
+            return this._atoms[this.atomOffset(atomIndex)];
+        }
+
+        public function getAtomTextBlockEndIndex(atomIndex:int):int {
+            return this._atoms[this.atomOffset(atomIndex) + 1];
+        }
+
+        public function getAtomGraphic(atomIndex:int):DisplayObject {
+            var element:GraphicElement = this.atomElement(atomIndex) as GraphicElement;
+            return element != null ? element.graphic : null;
+        }
+
+        public function getAtomTextRotation(atomIndex:int):String {
+            var element:ContentElement = this.atomElement(atomIndex);
+            if (element == null || element.textRotation == TextRotation.AUTO) {
+                return TextRotation.ROTATE_0;
+            }
+            return element.textRotation;
+        }
+
+        public function getAtomIndexAtCharIndex(charIndex:int):int {
+            for (var i:int = 0; i < this._atoms.length; i += ATOM_STRIDE) {
+                if (this._atoms[i] <= charIndex && charIndex < this._atoms[i + 1]) {
+                    return i / ATOM_STRIDE;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
+            var point:Point = this.globalToLocal(new Point(stageX, stageY));
+            var top:Number = -this._ascent;
+            var bottom:Number = this._descent;
+            if (point.y < top || point.y > bottom) {
+                return -1;
+            }
+            for (var i:int = 0; i < this._atoms.length; i += ATOM_STRIDE) {
+                var x:Number = this._atoms[i + 2];
+                if (point.x >= x && point.x < x + this._atoms[i + 3]) {
+                    return i / ATOM_STRIDE;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean {
+            var begin:int = this._atoms[this.atomOffset(atomIndex)];
+            if (begin == 0 || this._textBlock == null || this._textBlock.content == null) {
+                return true;
+            }
+            var rawText:String = this._textBlock.content.rawText;
+            return TextBlock.isWordCharacter(rawText, begin - 1) != TextBlock.isWordCharacter(rawText, begin);
+        }
+
+        public function getBaselinePosition(baseline:String):Number {
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this._ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this._descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this._descent - this._ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter baseline must be one of the accepted values.", 2008);
+            }
+        }
+
+        public function getMirrorRegion(mirror:EventDispatcher):TextLineMirrorRegion {
+            if (this._mirrorRegions != null) {
+                for each (var region:TextLineMirrorRegion in this._mirrorRegions) {
+                    if (region.mirror == mirror) {
+                        return region;
+                    }
+                }
+            }
+            return null;
+        }
+
+        public function flushAtomData():void {
+            // Atom data is always kept, so there is nothing to flush.
+        }
+
+        public function dump():String {
+            stub_method("flash.text.engine.TextLine", "dump");
+            return "";
+        }
+
+        override public function addChild(child:DisplayObject):DisplayObject {
+            throw unsupported();
+        }
+
+        override public function addChildAt(child:DisplayObject, index:int):DisplayObject {
+            throw unsupported();
+        }
+
+        override public function removeChild(child:DisplayObject):DisplayObject {
+            throw unsupported();
+        }
+
+        override public function removeChildAt(index:int):DisplayObject {
+            throw unsupported();
+        }
+
+        override public function removeChildren(beginIndex:int = 0, endIndex:int = 0x7fffffff):void {
+            throw unsupported();
+        }
+
+        override public function setChildIndex(child:DisplayObject, index:int):void {
+            throw unsupported();
+        }
+
+        override public function swapChildren(child1:DisplayObject, child2:DisplayObject):void {
+            throw unsupported();
+        }
+
+        override public function swapChildrenAt(index1:int, index2:int):void {
+            throw unsupported();
+        }
+
+        private static function unsupported():IllegalOperationError {
+            return new IllegalOperationError("Error #2181: The TextLine class does not implement this property or method.", 2181);
+        }
+
+        private function atomOffset(atomIndex:int):int {
+            if (atomIndex < 0 || atomIndex >= this.atomCount) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return atomIndex * ATOM_STRIDE;
+        }
+
+        private function atomElement(atomIndex:int):ContentElement {
+            var begin:int = this._atoms[this.atomOffset(atomIndex)];
+            return this._textBlock != null ? this._textBlock.elementAtCharIndex(begin) : null;
+        }
+
+        // Place the graphic elements of this line, replacing any from a
+        // previous layout.
+        internal function placeGraphics():void {
+            while (this.numChildren > 0) {
+                super.removeChildAt(0);
+            }
+            this._hasGraphicElement = false;
+            for (var i:int = 0; i < this._atoms.length; i += ATOM_STRIDE) {
+                var element:GraphicElement = this._textBlock.elementAtCharIndex(this._atoms[i]) as GraphicElement;
+                if (element == null) {
+                    continue;
+                }
+                this._hasGraphicElement = true;
+                if (element.graphic != null) {
+                    element.graphic.x = this._atoms[i + 2];
+                    element.graphic.y = this._atoms[i + 4];
+                    super.addChild(element.graphic);
+                }
+            }
+        }
+
+        // Build the mirror regions of this line: one for each run of atoms
+        // belonging to an element with an event mirror.
+        internal function buildMirrorRegions():void {
+            this._mirrorRegions = null;
+            var previous:TextLineMirrorRegion = null;
+            for (var i:int = 0; i < this._atoms.length; i += ATOM_STRIDE) {
+                var element:ContentElement = this._textBlock.elementAtCharIndex(this._atoms[i]);
+                if (element == null || element.eventMirror == null) {
+                    continue;
+                }
+                var bounds:Rectangle = new Rectangle(this._atoms[i + 2], this._atoms[i + 4],
+                                                     this._atoms[i + 3], this._atoms[i + 5]);
+                if (previous != null && previous._element == element && previous._bounds.right == bounds.left) {
+                    previous._bounds = previous._bounds.union(bounds);
+                    continue;
+                }
+
+                var region:TextLineMirrorRegion = new TextLineMirrorRegion();
+                region._bounds = bounds;
+                region._element = element;
+                region._textLine = this;
+                region._previousRegion = previous;
+                if (previous != null) {
+                    previous._nextRegion = region;
+                }
+                if (this._mirrorRegions == null) {
+                    this._mirrorRegions = new Vector.<TextLineMirrorRegion>();
+                }
+                this._mirrorRegions.push(region);
+                previous = region;
+            }
+
+            if (this._mirrorRegions != null && !this._forwardsMouseEvents) {
+                this._forwardsMouseEvents = true;
+                for each (var type:String in [MouseEvent.CLICK, MouseEvent.DOUBLE_CLICK, MouseEvent.MOUSE_DOWN,
+                                              MouseEvent.MOUSE_MOVE, MouseEvent.MOUSE_OUT, MouseEvent.MOUSE_OVER,
+                                              MouseEvent.MOUSE_UP, MouseEvent.MOUSE_WHEEL, MouseEvent.ROLL_OUT,
+                                              MouseEvent.ROLL_OVER]) {
+                    this.addEventListener(type, this.forwardMouseEvent);
+                }
+            }
+        }
+
+        // Send mouse events over a mirror region on to its mirror.
+        private function forwardMouseEvent(event:MouseEvent):void {
+            if (this._mirrorRegions == null) {
+                return;
+            }
+            for each (var region:TextLineMirrorRegion in this._mirrorRegions) {
+                if (region._bounds.contains(this.mouseX, this.mouseY)) {
+                    region.mirror.dispatchEvent(event.clone());
+                }
+            }
+        }
+    }
+}
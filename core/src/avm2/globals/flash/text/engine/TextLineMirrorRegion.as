@@ -0,0 +1,39 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+
+    public final class TextLineMirrorRegion {
+        internal var _bounds:Rectangle;
+        internal var _element:ContentElement;
+        internal var _nextRegion:TextLineMirrorRegion;
+        internal var _previousRegion:TextLineMirrorRegion;
+        internal var _textLine:TextLine;
+
+        public function TextLineMirrorRegion() {
+        }
+
+        public function get bounds():Rectangle {
+            return this._bounds.clone();
+        }
+
+        public function get element():ContentElement {
+            return this._element;
+        }
+
+        public function get mirror():EventDispatcher {
+            return this._element.eventMirror;
+        }
+
+        public function get nextRegion():TextLineMirrorRegion {
+            return this._nextRegion;
+        }
+
+        public function get previousRegion():TextLineMirrorRegion {
+            return this._previousRegion;
+        }
+
+        public function get textLine():TextLine {
+            return this._textLine;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! `flash.text.engine.ElementFormat` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::text::engine::font_description::FontSelection;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use swf::Twips;
+
+/// Implements `ElementFormat.getFontMetrics`
+pub fn get_font_metrics<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Null),
+    };
+
+    let size = this
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let description = this
+        .get_public_property("fontDescription", activation)?
+        .coerce_to_object(activation)?;
+    let selection = FontSelection::from_description(activation, description)?;
+
+    // The em box sits on the baseline, and is split between the ascent and
+    // descent of the font in proportion.
+    let (ascent, line_gap) = match selection.resolve(activation) {
+        Some(font) => {
+            let height = Twips::from_pixels(size);
+            let ascent = font.get_baseline_for_height(height).to_pixels();
+            let descent = font.get_descent_for_height(height).to_pixels();
+            let leading = font.get_leading_for_height(height).to_pixels();
+            if ascent + descent > 0.0 {
+                (size * ascent / (ascent + descent), leading)
+            } else {
+                (size, leading)
+            }
+        }
+        None => (size * 0.8, 0.0),
+    };
+
+    let rectangle_class = activation.avm2().classes().rectangle;
+    let em_box = rectangle_class.construct(
+        activation,
+        &[0.into(), (-ascent).into(), size.into(), size.into()],
+    )?;
+
+    // Fonts don't carry their decoration metrics, so these use the usual
+    // proportions of a Latin font.
+    let font_metrics_class = activation.avm2().classes().fontmetrics;
+    let font_metrics = font_metrics_class.construct(
+        activation,
+        &[
+            em_box.into(),
+            (-size * 0.3).into(),
+            (size / 20.0).into(),
+            (size / 10.0).into(),
+            (size / 20.0).into(),
+            (size / 6.0).into(),
+            0.7.into(),
+            (-size / 3.0).into(),
+            0.7.into(),
+            line_gap.into(),
+        ],
+    )?;
+
+    Ok(font_metrics.into())
+}
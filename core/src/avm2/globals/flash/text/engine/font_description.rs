@@ -0,0 +1,170 @@
+//! `flash.text.engine.FontDescription` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::font::{Font, FontDescriptor, FontType};
+
+/// The font that a `FontDescription` selects.
+pub struct FontSelection {
+    /// The font names to try, in order.
+    pub names: Vec<String>,
+    pub is_bold: bool,
+    pub is_italic: bool,
+    pub is_embedded: bool,
+}
+
+impl FontSelection {
+    /// Read the font selected by a `FontDescription` object.
+    pub fn from_description<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        description: Object<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        let names = description
+            .get_public_property("fontName", activation)?
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        let is_bold = &*description
+            .get_public_property("fontWeight", activation)?
+            .coerce_to_string(activation)?
+            == b"bold";
+        let is_italic = &*description
+            .get_public_property("fontPosture", activation)?
+            .coerce_to_string(activation)?
+            == b"italic";
+        let is_embedded = &*description
+            .get_public_property("fontLookup", activation)?
+            .coerce_to_string(activation)?
+            == b"embeddedCFF";
+
+        Ok(Self {
+            names,
+            is_bold,
+            is_italic,
+            is_embedded,
+        })
+    }
+
+    /// Find the font to render with.
+    ///
+    /// Embedded fonts fall back to device fonts when none of the names are
+    /// available, as do device fonts to the default device font.
+    pub fn resolve<'gc>(&self, activation: &mut Activation<'_, 'gc>) -> Option<Font<'gc>> {
+        if self.is_embedded {
+            for name in &self.names {
+                let font = embedded_cff_font(activation, name, self.is_bold, self.is_italic);
+                if font.is_some() {
+                    return font;
+                }
+            }
+        }
+
+        let name = self.names.first().map_or("_serif", |name| name.as_str());
+        activation.context.library.get_or_load_device_font(
+            activation.context.gc_context,
+            activation.context.font_backend,
+            name,
+            self.is_bold,
+            self.is_italic,
+        )
+    }
+}
+
+/// Find an embedded CFF font, either from the movie's library or registered
+/// with `Font.registerFont`.
+fn embedded_cff_font<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &str,
+    is_bold: bool,
+    is_italic: bool,
+) -> Option<Font<'gc>> {
+    let library = activation
+        .context
+        .library
+        .library_for_movie_mut(activation.context.swf.clone());
+    if let Some(font) = library.get_cff_font_by_name(name, is_bold, is_italic) {
+        return Some(font);
+    }
+
+    let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+    activation
+        .context
+        .library
+        .registered_fonts()
+        .iter()
+        .copied()
+        .find(|font| font.font_type() == FontType::EmbeddedCFF && *font.descriptor() == descriptor)
+}
+
+/// Read the `fontName`, `fontWeight` and `fontPosture` arguments of the
+/// compatibility checks.
+fn font_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<(String, bool, bool), Error<'gc>> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let is_bold = &*args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?
+        == b"bold";
+    let is_italic = &*args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?
+        == b"italic";
+
+    Ok((name, is_bold, is_italic))
+}
+
+/// Implements `FontDescription.isFontCompatible`
+pub fn is_font_compatible<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, is_bold, is_italic) = font_arguments(activation, args)?;
+
+    Ok(embedded_cff_font(activation, &name, is_bold, is_italic)
+        .is_some()
+        .into())
+}
+
+/// Implements `FontDescription.isDeviceFontCompatible`
+pub fn is_device_font_compatible<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, is_bold, is_italic) = font_arguments(activation, args)?;
+
+    let font = activation.context.library.get_or_load_device_font(
+        activation.context.gc_context,
+        activation.context.font_backend,
+        &name,
+        is_bold,
+        is_italic,
+    );
+
+    // Fonts that aren't installed are substituted with the default device
+    // font.
+    let is_substituted = match (font, activation.context.library.device_font()) {
+        (Some(font), Some(device_font)) => Font::ptr_eq(font, device_font),
+        (font, _) => font.is_none(),
+    };
+
+    Ok((!is_substituted).into())
+}
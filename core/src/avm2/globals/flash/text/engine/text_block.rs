@@ -0,0 +1,377 @@
+//! `flash.text.engine.TextBlock` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::text::engine::font_description::FontSelection;
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Multiname;
+use crate::avm2::{ArrayObject, ArrayStorage, Error};
+use crate::display_object::{MovieClip, TDisplayObject};
+use crate::font::EvalParameters;
+use crate::html::{
+    BreakOpportunity, LineJustification, LineLayout, RunContent, TabAlignment, TabStop,
+    TextBlockLayout, TextBlockRun,
+};
+use crate::string::WString;
+use crate::tag_utils::SwfMovie;
+use std::sync::Arc;
+use swf::{Color, Twips};
+
+pub use crate::avm2::object::textblock_allocator as text_block_allocator;
+
+/// Read the values of an `Array` object.
+fn array_values<'gc>(value: Value<'gc>) -> Vec<Value<'gc>> {
+    value
+        .as_object()
+        .and_then(|object| {
+            object.as_array_storage().map(|array| {
+                array
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Build the run for one content element of the block.
+fn element_run<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    format: Object<'gc>,
+    graphic: Option<Object<'gc>>,
+    start: usize,
+    end: usize,
+) -> Result<TextBlockRun<'gc>, Error<'gc>> {
+    let break_opportunity = format
+        .get_public_property("breakOpportunity", activation)?
+        .coerce_to_string(activation)?;
+    let break_opportunity = match &*break_opportunity.to_utf8_lossy() {
+        "any" => BreakOpportunity::Any,
+        "all" => BreakOpportunity::All,
+        "none" => BreakOpportunity::None,
+        _ => BreakOpportunity::Auto,
+    };
+
+    let content = if let Some(graphic) = graphic {
+        let width = graphic
+            .get_public_property("elementWidth", activation)?
+            .coerce_to_number(activation)?;
+        let height = graphic
+            .get_public_property("elementHeight", activation)?
+            .coerce_to_number(activation)?;
+        RunContent::Graphic {
+            width: Twips::from_pixels(width),
+            height: Twips::from_pixels(height),
+        }
+    } else {
+        let description = format
+            .get_public_property("fontDescription", activation)?
+            .coerce_to_object(activation)?;
+        let font = FontSelection::from_description(activation, description)?
+            .resolve(activation)
+            .ok_or("No font available to lay out text")?;
+
+        let size = format
+            .get_public_property("fontSize", activation)?
+            .coerce_to_number(activation)?;
+        let kerning = &*format
+            .get_public_property("kerning", activation)?
+            .coerce_to_string(activation)?
+            != b"off";
+        let tracking = format
+            .get_public_property("trackingLeft", activation)?
+            .coerce_to_number(activation)?
+            + format
+                .get_public_property("trackingRight", activation)?
+                .coerce_to_number(activation)?;
+        let rgb = format
+            .get_public_property("color", activation)?
+            .coerce_to_u32(activation)?;
+        let alpha = format
+            .get_public_property("alpha", activation)?
+            .coerce_to_number(activation)?;
+        let baseline_shift = format
+            .get_public_property("baselineShift", activation)?
+            .coerce_to_number(activation)?;
+
+        RunContent::Text {
+            font,
+            params: EvalParameters::from_parts(
+                Twips::from_pixels(size),
+                Twips::from_pixels(tracking),
+                kerning,
+            ),
+            color: Color::from_rgb(rgb, (alpha.clamp(0.0, 1.0) * 255.0) as u8),
+            baseline_shift: Twips::from_pixels(baseline_shift),
+        }
+    };
+
+    Ok(TextBlockRun {
+        start,
+        end,
+        content,
+        break_opportunity,
+    })
+}
+
+/// Read the `tabStops` of a text block, sorted by position.
+fn tab_stops<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Vec<TabStop>, Error<'gc>> {
+    let stops: Vec<Value<'gc>> = match this
+        .get_public_property("tabStops", activation)?
+        .as_object()
+    {
+        Some(vector) => vector
+            .as_vector_storage()
+            .map(|vector| vector.iter().collect())
+            .unwrap_or_default(),
+        None => return Ok(vec![]),
+    };
+
+    let mut tab_stops = Vec::with_capacity(stops.len());
+    for stop in stops {
+        let stop = match stop.as_object() {
+            Some(stop) => stop,
+            None => continue,
+        };
+        let alignment = stop
+            .get_public_property("alignment", activation)?
+            .coerce_to_string(activation)?;
+        let alignment = match &*alignment.to_utf8_lossy() {
+            "center" => TabAlignment::Center,
+            "end" => TabAlignment::End,
+            "decimal" => TabAlignment::Decimal,
+            _ => TabAlignment::Start,
+        };
+        let position = stop
+            .get_public_property("position", activation)?
+            .coerce_to_number(activation)?;
+        let decimal_alignment_token =
+            match stop.get_public_property("decimalAlignmentToken", activation)? {
+                Value::Null | Value::Undefined => WString::new(),
+                token => token.coerce_to_string(activation)?.as_wstr().into(),
+            };
+        tab_stops.push(TabStop {
+            alignment,
+            position: Twips::from_pixels(position),
+            decimal_alignment_token,
+        });
+    }
+    tab_stops.sort_by_key(|stop| stop.position);
+
+    Ok(tab_stops)
+}
+
+/// Create the display object for a new text line.
+fn create_text_line<'gc>(activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().textline;
+    let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+    let clip = MovieClip::new(movie, activation.context.gc_context);
+    clip.set_avm2_class(activation.context.gc_context, Some(class));
+
+    let object = StageObject::for_display_object_childless(activation, clip.into(), class)?;
+    clip.set_object2(&mut activation.context, object.into());
+
+    Ok(object.into())
+}
+
+/// Store the atoms and metrics of a line in its hidden properties.
+fn set_line_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut line_object: Object<'gc>,
+    line: &LineLayout,
+) -> Result<(), Error<'gc>> {
+    let mut atoms = ArrayStorage::new(0);
+    for atom in &line.atoms {
+        atoms.push(atom.start.into());
+        atoms.push(atom.end.into());
+        atoms.push(atom.x.to_pixels().into());
+        atoms.push(atom.width.to_pixels().into());
+        atoms.push((-atom.ascent).to_pixels().into());
+        atoms.push((atom.ascent + atom.descent).to_pixels().into());
+        atoms.push(atom.bidi_level.into());
+    }
+    let atoms = ArrayObject::from_storage(activation, atoms)?;
+
+    let namespace = activation.avm2().flash_text_engine_internal;
+    let values: [(&'static str, Value<'gc>); 7] = [
+        ("_atoms", atoms.into()),
+        ("_ascent", line.ascent.to_pixels().into()),
+        ("_descent", line.descent.to_pixels().into()),
+        ("_textWidth", line.text_width.to_pixels().into()),
+        (
+            "_unjustifiedTextWidth",
+            line.unjustified_text_width.to_pixels().into(),
+        ),
+        ("_rawTextLength", (line.end - line.start).into()),
+        ("_hasTabs", line.has_tabs.into()),
+    ];
+    for (name, value) in values {
+        line_object.set_property(&Multiname::new(namespace, name), value, activation)?;
+    }
+
+    Ok(())
+}
+
+/// Implements `TextBlock.prepareLines`, which flattens the content of the
+/// block into the paragraph that its lines are broken out of.
+///
+/// The content is passed as parallel arrays holding the raw text, format and
+/// graphic element (or null) of each leaf content element.
+pub fn prepare_lines<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+    let text_block = this
+        .as_text_block()
+        .ok_or("TextBlock.prepareLines called on a non-TextBlock")?;
+
+    let texts = array_values(args.get(0).cloned().unwrap_or(Value::Undefined));
+    let formats = array_values(args.get(1).cloned().unwrap_or(Value::Undefined));
+    let graphics = array_values(args.get(2).cloned().unwrap_or(Value::Undefined));
+
+    let mut text = WString::new();
+    let mut runs = vec![];
+    for (i, raw_text) in texts.into_iter().enumerate() {
+        let raw_text = raw_text.coerce_to_string(activation)?;
+        let format = formats
+            .get(i)
+            .and_then(|format| format.as_object())
+            .ok_or("Content element is missing its format")?;
+        let graphic = graphics.get(i).and_then(|graphic| graphic.as_object());
+
+        let start = text.len();
+        text.push_str(&raw_text);
+        runs.push(element_run(activation, format, graphic, start, text.len())?);
+    }
+
+    let tab_stops = tab_stops(activation, this)?;
+    let justification = this
+        .get_public_property("textJustifier", activation)?
+        .coerce_to_object(activation)?
+        .get_public_property("lineJustification", activation)?
+        .coerce_to_string(activation)?;
+    let justification = match &*justification.to_utf8_lossy() {
+        "allButLast" | "allButMandatoryBreak" => LineJustification::AllButLast,
+        "allIncludingLast" => LineJustification::AllIncludingLastLine,
+        _ => LineJustification::Unjustified,
+    };
+    let bidi_level = this
+        .get_public_property("bidiLevel", activation)?
+        .coerce_to_i32(activation)?
+        .clamp(0, 125) as u8;
+
+    let layout = TextBlockLayout::new(text, runs, tab_stops, justification, bidi_level);
+    text_block.set_layout(activation.context.gc_context, Some(layout));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextBlock.clearPreparedLines`, which drops the paragraph
+/// prepared by `prepareLines`.
+pub fn clear_prepared_lines<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text_block) = this.and_then(|this| this.as_text_block()) {
+        text_block.set_layout(activation.context.gc_context, None);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextBlock.hasPreparedLines`.
+pub fn has_prepared_lines<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .and_then(|this| this.as_text_block())
+        .map_or(false, |text_block| text_block.layout().is_some())
+        .into())
+}
+
+/// Implements `TextBlock.layoutLine`, which breaks the next line out of the
+/// paragraph prepared by `prepareLines`.
+pub fn layout_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Null),
+    };
+    let text_block = this
+        .as_text_block()
+        .ok_or("TextBlock.layoutLine called on a non-TextBlock")?;
+
+    let reuse = args.get(0).and_then(|line| line.as_object());
+    let begin_index = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?
+        .max(0) as usize;
+    let width = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let fit_something = args
+        .get(3)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    let (line, text_len) = match &*text_block.layout() {
+        Some(layout) => (
+            layout.lay_out_line(begin_index, Twips::from_pixels(width), fit_something),
+            layout.len(),
+        ),
+        None => return Err("TextBlock.layoutLine called before prepareLines".into()),
+    };
+
+    let result = match &line {
+        None if begin_index >= text_len => "complete",
+        None => "insufficientWidth",
+        Some(line) if line.is_emergency => "emergency",
+        Some(_) => "success",
+    };
+    let namespace = activation.avm2().flash_text_engine_internal;
+    this.set_property(
+        &Multiname::new(namespace, "_textLineCreationResult"),
+        result.into(),
+        activation,
+    )?;
+
+    let line = match line {
+        Some(line) => line,
+        None => return Ok(Value::Null),
+    };
+
+    let line_object = match reuse {
+        Some(line_object) => line_object,
+        None => create_text_line(activation)?,
+    };
+    if let Some(dobj) = line_object.as_display_object() {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.clear();
+            if let Some(layout) = &*text_block.layout() {
+                layout.draw_line(&line, &mut drawing);
+            }
+        }
+    }
+    set_line_data(activation, line_object, &line)?;
+
+    Ok(line_object.into())
+}
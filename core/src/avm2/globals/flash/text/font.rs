@@ -2,8 +2,9 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::error::argument_error;
 use crate::avm2::method::{Method, NativeMethodImpl};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{font_allocator, ClassObject, FontObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
@@ -11,6 +12,7 @@ use crate::avm2::QName;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
 use crate::avm2_stub_method;
 use crate::character::Character;
+use crate::font::{Font, FontType};
 use crate::string::AvmString;
 use gc_arena::GcCell;
 
@@ -22,6 +24,16 @@ pub fn instance_init<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
+
+        if let Some(font_object) = this.as_font_object() {
+            let class_object = this
+                .instance_of()
+                .ok_or("Attempted to construct Font on a bare object.")?;
+
+            if let Some(font) = font_for_class(activation, class_object) {
+                font_object.set_font(activation.context.gc_context, font);
+            }
+        }
     }
 
     Ok(Value::Undefined)
@@ -36,31 +48,38 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Find the font symbol that a `Font` subclass is linked to.
+fn font_for_class<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    class_object: ClassObject<'gc>,
+) -> Option<Font<'gc>> {
+    let (movie, character_id) = activation
+        .context
+        .library
+        .avm2_class_registry()
+        .class_symbol(class_object)?;
+
+    match activation
+        .context
+        .library
+        .library_for_movie_mut(movie)
+        .character_by_id(character_id)
+    {
+        Some(Character::Font(font)) => Some(*font),
+        _ => None,
+    }
+}
+
 /// Implements `Font.fontName`
 pub fn font_name<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.and_then(|this| this.instance_of()).and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
-        if let Some(Character::Font(font)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            return Ok(AvmString::new_utf8(
-                activation.context.gc_context,
-                font.descriptor().class(),
-            )
-            .into());
-        }
+    if let Some(font) = this.and_then(|this| this.as_font()) {
+        return Ok(
+            AvmString::new_utf8(activation.context.gc_context, font.descriptor().class()).into(),
+        );
     }
 
     Ok(Value::Undefined)
@@ -68,30 +87,17 @@ pub fn font_name<'gc>(
 
 /// Implements `Font.fontStyle`
 pub fn font_style<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.and_then(|this| this.instance_of()).and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
-        if let Some(Character::Font(font)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            return match (font.descriptor().bold(), font.descriptor().italic()) {
-                (false, false) => Ok("regular".into()),
-                (false, true) => Ok("italic".into()),
-                (true, false) => Ok("bold".into()),
-                (true, true) => Ok("boldItalic".into()),
-            };
-        }
+    if let Some(font) = this.and_then(|this| this.as_font()) {
+        return match (font.descriptor().bold(), font.descriptor().italic()) {
+            (false, false) => Ok("regular".into()),
+            (false, true) => Ok("italic".into()),
+            (true, false) => Ok("bold".into()),
+            (true, true) => Ok("boldItalic".into()),
+        };
     }
 
     Ok(Value::Undefined)
@@ -99,26 +105,16 @@ pub fn font_style<'gc>(
 
 /// Implements `Font.fontType`
 pub fn font_type<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.and_then(|this| this.instance_of()).and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
-        if let Some(Character::Font(_)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            //TODO: How do we distinguish between CFF and non-CFF embedded fonts?
-            return Ok("embedded".into());
-        }
+    if let Some(font) = this.and_then(|this| this.as_font()) {
+        return match font.font_type() {
+            FontType::Embedded => Ok("embedded".into()),
+            FontType::EmbeddedCFF => Ok("embeddedCFF".into()),
+            FontType::Device => Ok("device".into()),
+        };
     }
 
     Ok(Value::Undefined)
@@ -130,27 +126,14 @@ pub fn has_glyphs<'gc>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.and_then(|this| this.instance_of()).and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
+    if let Some(font) = this.and_then(|this| this.as_font()) {
         let my_str = args
             .get(0)
             .cloned()
             .unwrap_or(Value::Undefined)
             .coerce_to_string(activation)?;
 
-        if let Some(Character::Font(font)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            return Ok(font.has_glyphs_for_str(&my_str).into());
-        }
+        return Ok(font.has_glyphs_for_str(&my_str).into());
     }
 
     Ok(Value::Undefined)
@@ -160,20 +143,64 @@ pub fn has_glyphs<'gc>(
 pub fn enumerate_fonts<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "enumerateFonts");
-    Ok(ArrayObject::from_storage(activation, ArrayStorage::new(0))?.into())
+    let enumerate_device_fonts = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Bool(false))
+        .coerce_to_boolean();
+    if enumerate_device_fonts {
+        avm2_stub_method!(
+            activation,
+            "flash.text.Font",
+            "enumerateFonts",
+            "with device fonts"
+        );
+    }
+
+    let mut fonts = activation
+        .context
+        .library
+        .library_for_movie_mut(activation.context.swf.clone())
+        .embedded_fonts();
+    for &font in activation.context.library.registered_fonts() {
+        if !fonts.iter().any(|f| Font::ptr_eq(*f, font)) {
+            fonts.push(font);
+        }
+    }
+
+    let mut storage = ArrayStorage::new(0);
+    for font in fonts {
+        storage.push(FontObject::for_font(activation, font)?.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
 }
 
 /// `Font.registerFont`
 pub fn register_font<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "registerFont");
-    Ok(Value::Undefined)
+    let font = args
+        .get(0)
+        .and_then(|font| font.as_object())
+        .and_then(|font| font.as_class_object())
+        .and_then(|class_object| font_for_class(activation, class_object));
+
+    match font {
+        Some(font) => {
+            activation.context.library.register_font(font);
+            Ok(Value::Undefined)
+        }
+        None => Err(Error::AvmError(argument_error(
+            activation,
+            "Error #1508: The value specified for argument font is invalid.",
+            1508,
+        )?)),
+    }
 }
 
 /// Construct `Font`'s class.
@@ -190,6 +217,7 @@ pub fn create_class<'gc>(activation: &mut Activation<'_, 'gc>) -> GcCell<'gc, Cl
     let mut write = class.write(mc);
 
     write.set_attributes(ClassAttributes::SEALED);
+    write.set_instance_allocator(font_allocator);
 
     const PUBLIC_INSTANCE_PROPERTIES: &[(
         &str,
@@ -0,0 +1,120 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject, TextFormatObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{parse_css, Style, StyleSheet, STYLE_PROPERTIES};
+use crate::string::{AvmString, WStr};
+
+/// Build a style from the properties of a style object.
+pub fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Style, Error<'gc>> {
+    let mut style = Style::default();
+    for name in STYLE_PROPERTIES {
+        match object.get_public_property(*name, activation)? {
+            Value::Undefined | Value::Null => {}
+            value => {
+                let value = value.coerce_to_string(activation)?;
+                style.set_property(WStr::from_units(name.as_bytes()), &value);
+            }
+        }
+    }
+
+    Ok(style)
+}
+
+/// Read the styles of a `StyleSheet` object.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+    let names = object.get_public_property("styleNames", activation)?;
+    let names: Vec<Value<'gc>> = names
+        .as_object()
+        .and_then(|names| {
+            names.as_array_storage().map(|names| {
+                names
+                    .iter()
+                    .map(|name| name.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default();
+
+    for name in names {
+        let style = object.call_public_property("getStyle", &[name], activation)?;
+        if let Some(style) = style.as_object() {
+            let name = name.coerce_to_string(activation)?;
+            let style = style_from_object(activation, style)?;
+            style_sheet.set_style(&name, style);
+        }
+    }
+
+    Ok(style_sheet)
+}
+
+/// Implements `StyleSheet.transform`
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format_object = match args.get(0).and_then(|format| format.as_object()) {
+        Some(format_object) => format_object,
+        None => return Ok(Value::Null),
+    };
+
+    let style = style_from_object(activation, format_object)?;
+    Ok(TextFormatObject::from_text_format(activation, style.format)?.into())
+}
+
+/// Implements `StyleSheet.innerParseCss`, which parses a CSS document into
+/// an object holding the style object of each selector, or null if the
+/// document is malformed.
+pub fn inner_parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let rules = match parse_css(&css) {
+        Some(rules) => rules,
+        None => return Ok(Value::Null),
+    };
+
+    let object_class = activation.avm2().classes().object;
+    let mut result = object_class.construct(activation, &[])?;
+    for (selector, declarations) in rules {
+        let selector = AvmString::new(activation.context.gc_context, selector.to_ascii_lowercase());
+
+        // Rules for the same selector add to its existing declarations, as
+        // selectors are case-insensitive.
+        let mut style = match result
+            .get_public_property(selector, activation)?
+            .as_object()
+        {
+            Some(style) => style,
+            None => {
+                let style = object_class.construct(activation, &[])?;
+                result.set_public_property(selector, style.into(), activation)?;
+                style
+            }
+        };
+
+        for (name, value) in declarations {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = AvmString::new(activation.context.gc_context, value);
+            style.set_public_property(name, value.into(), activation)?;
+        }
+    }
+
+    Ok(result.into())
+}
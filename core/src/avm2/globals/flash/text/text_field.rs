@@ -1,6 +1,8 @@
 //! `flash.text.TextField` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::range_error;
+use crate::avm2::globals::flash::text::style_sheet::style_sheet_from_object;
 use crate::avm2::object::{Object, TObject, TextFormatObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
@@ -10,7 +12,7 @@ use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use std::sync::Arc;
-use swf::Color;
+use swf::{Color, Twips};
 
 /// Implements `flash.text.TextField`'s `init` method, which is called from the constructor.
 pub fn init<'gc>(
@@ -403,6 +405,28 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `TextField.setStyleSheet`, which applies the styles of a
+/// `StyleSheet` to the HTML text of the field.
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.get(0).and_then(|style_sheet| style_sheet.as_object()) {
+            Some(style_sheet) => Some(style_sheet_from_object(activation, style_sheet)?),
+            None => None,
+        };
+
+        this.set_style_sheet(style_sheet, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_length<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -1315,3 +1339,273 @@ pub fn set_restrict<'gc>(
     avm2_stub_setter!(activation, "flash.text.TextField", "restrict");
     Ok(Value::Undefined)
 }
+
+pub fn get_caret_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .selection()
+            .map_or(0, |selection| selection.to())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_selection_begin_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .selection()
+            .map_or(0, |selection| selection.start())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_selection_end_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .selection()
+            .map_or(0, |selection| selection.end())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Coerce an index argument, treating negative indices as out of bounds.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    arg: usize,
+) -> Result<Option<usize>, Error<'gc>> {
+    let index = args
+        .get(arg)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    Ok(usize::try_from(index).ok())
+}
+
+/// Coerce a point argument, in pixels, to local coordinates.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error<'gc>> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+/// Get the range of text on a line, throwing a `RangeError` if it doesn't
+/// exist.
+fn line_range<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: EditText<'gc>,
+    args: &[Value<'gc>],
+) -> Result<(usize, usize), Error<'gc>> {
+    let range = index_arg(activation, args, 0)?.and_then(|line| this.line_range(line));
+    match range {
+        Some(range) => Ok(range),
+        None => Err(Error::AvmError(range_error(
+            activation,
+            "Error #2006: The supplied index is out of bounds.",
+            2006,
+        )?)),
+    }
+}
+
+pub fn get_char_boundaries<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let bounds = index_arg(activation, args, 0)?.and_then(|index| this.char_bounds(index));
+        if let Some(bounds) = bounds {
+            let rectangle_class = activation.avm2().classes().rectangle;
+            return Ok(rectangle_class
+                .construct(
+                    activation,
+                    &[
+                        bounds.x_min.to_pixels().into(),
+                        bounds.y_min.to_pixels().into(),
+                        bounds.width().to_pixels().into(),
+                        bounds.height().to_pixels().into(),
+                    ],
+                )?
+                .into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_char_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let point = point_args(activation, args)?;
+        return Ok(this
+            .char_index_at_point(point)
+            .map_or(-1, |index| index as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let point = point_args(activation, args)?;
+        return Ok(this
+            .line_index_at_point(point)
+            .map_or(-1, |line| line as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_of_char<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let line = index_arg(activation, args, 0)?.and_then(|index| this.line_index_of_char(index));
+        return Ok(line.map_or(-1, |line| line as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let (start, end) = line_range(activation, this, args)?;
+        return Ok((end - start).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_offset<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let (start, _end) = line_range(activation, this, args)?;
+        return Ok(start.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let (start, end) = line_range(activation, this, args)?;
+        let text = this.text();
+        return Ok(AvmString::new(activation.context.gc_context, &text[start..end]).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_first_char_in_paragraph<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let range = index_arg(activation, args, 0)?.and_then(|index| this.paragraph_range(index));
+        return Ok(range.map_or(-1, |(start, _end)| start as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_paragraph_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let range = index_arg(activation, args, 0)?.and_then(|index| this.paragraph_range(index));
+        return Ok(range.map_or(-1, |(start, end)| (end - start) as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
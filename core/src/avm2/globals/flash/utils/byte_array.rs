@@ -1,4 +1,8 @@
 use crate::avm2::activation::Activation;
+use crate::avm2::amf::{
+    deserialize_value, deserialize_value_with_externals, parse_with_externals, serialize_value,
+    write_lso,
+};
 use crate::avm2::bytearray::{Endian, ObjectEncoding};
 pub use crate::avm2::object::byte_array_allocator;
 use crate::avm2::object::{Object, TObject};
@@ -9,7 +13,6 @@ use crate::string::AvmString;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
-use flash_lso::amf3::read::AMF3Decoder;
 use flash_lso::types::{AMFVersion, Element};
 
 /// Implements `flash.utils.ByteArray`'s instance constructor.
@@ -794,36 +797,53 @@ pub fn read_object<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
-        if let Some(bytearray) = this.as_bytearray() {
-            let bytes = bytearray
-                .read_at(bytearray.bytes_available(), bytearray.position())
-                .map_err(|e| e.to_avm(activation))?;
-            let (bytes_left, value) = match bytearray.object_encoding() {
-                ObjectEncoding::Amf0 => {
-                    let mut decoder = AMF0Decoder::default();
-                    let (extra, amf) = decoder
-                        .parse_single_element(bytes)
-                        .map_err(|_| "Error: Invalid object")?;
-                    (
-                        extra.len(),
-                        crate::avm2::amf::deserialize_value(activation, &amf)?,
-                    )
-                }
-                ObjectEncoding::Amf3 => {
-                    let mut decoder = AMF3Decoder::default();
-                    let (extra, amf) = decoder
-                        .parse_single_element(bytes)
-                        .map_err(|_| "Error: Invalid object")?;
-                    (
-                        extra.len(),
-                        crate::avm2::amf::deserialize_value(activation, &amf)?,
-                    )
-                }
-            };
+        // Copy the bytes, as deserializing may run constructors that use this `ByteArray`.
+        let (bytes, position, object_encoding) = match this.as_bytearray() {
+            Some(bytearray) => (
+                bytearray
+                    .read_at(bytearray.bytes_available(), bytearray.position())
+                    .map_err(|e| e.to_avm(activation))?
+                    .to_vec(),
+                bytearray.position(),
+                bytearray.object_encoding(),
+            ),
+            None => return Ok(Value::Undefined),
+        };
+
+        let (bytes_left, value) = match object_encoding {
+            ObjectEncoding::Amf0 => {
+                let mut decoder = AMF0Decoder::default();
+                let (extra, amf) = decoder
+                    .parse_single_element(&bytes)
+                    .map_err(|_| "Error: Invalid object")?;
+                (extra.len(), deserialize_value(activation, &amf)?)
+            }
+            ObjectEncoding::Amf3 => {
+                // Externalizable objects read their data from this `ByteArray`.
+                let parsed = parse_with_externals(
+                    activation,
+                    this,
+                    position,
+                    &bytes,
+                    |mut decoder, bytes| {
+                        decoder
+                            .parse_single_element(bytes)
+                            .ok()
+                            .map(|(extra, amf)| (extra.len(), amf))
+                    },
+                )?;
+                let ((extra, amf), externals) = parsed.ok_or("Error: Invalid object")?;
+                (
+                    extra,
+                    deserialize_value_with_externals(activation, &amf, &externals)?,
+                )
+            }
+        };
 
-            bytearray.set_position(bytearray.len() - bytes_left);
-            return Ok(value);
+        if let Some(bytearray) = this.as_bytearray() {
+            bytearray.set_position(position + bytes.len() - bytes_left);
         }
+        return Ok(value);
     }
 
     Ok(Value::Undefined)
@@ -835,23 +855,23 @@ pub fn write_object<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
-        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
-            let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
-            let amf_version = match bytearray.object_encoding() {
-                ObjectEncoding::Amf0 => AMFVersion::AMF0,
-                ObjectEncoding::Amf3 => AMFVersion::AMF3,
+        let amf_version = match this.as_bytearray().map(|b| b.object_encoding()) {
+            Some(ObjectEncoding::Amf0) => AMFVersion::AMF0,
+            Some(ObjectEncoding::Amf3) => AMFVersion::AMF3,
+            None => return Ok(Value::Undefined),
+        };
+        let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
+        if let Some(amf) = serialize_value(activation, obj, amf_version)? {
+            let element = Element::new("", amf);
+            let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
+            let bytes = write_lso(activation, &mut lso)?;
+            // This is kind of hacky: We need to strip out the header and any padding so that we only write
+            // the value. In the future, there should be a method to do this in the flash_lso crate.
+            let element_padding = match amf_version {
+                AMFVersion::AMF0 => 8,
+                AMFVersion::AMF3 => 7,
             };
-            if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj, amf_version) {
-                let element = Element::new("", amf);
-                let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
-                let bytes = flash_lso::write::write_to_bytes(&mut lso)
-                    .map_err(|_| "Failed to serialize object")?;
-                // This is kind of hacky: We need to strip out the header and any padding so that we only write
-                // the value. In the future, there should be a method to do this in the flash_lso crate.
-                let element_padding = match amf_version {
-                    AMFVersion::AMF0 => 8,
-                    AMFVersion::AMF3 => 7,
-                };
+            if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
                 bytearray.write_bytes(
                     &bytes[flash_lso::write::header_length(&lso.header) + element_padding
                         ..bytes.len() - 1],
@@ -54,8 +54,19 @@ include "flash/display/ColorCorrectionSupport.as"
 include "flash/display/FocusDirection.as"
 include "flash/display/FrameLabel.as"
 include "flash/display/GradientType.as"
+include "flash/display/IGraphicsData.as"
+include "flash/display/IGraphicsFill.as"
+include "flash/display/IGraphicsPath.as"
+include "flash/display/IGraphicsStroke.as"
+include "flash/display/GraphicsBitmapFill.as"
+include "flash/display/GraphicsEndFill.as"
+include "flash/display/GraphicsGradientFill.as"
+include "flash/display/GraphicsPath.as"
 include "flash/display/GraphicsPathCommand.as"
 include "flash/display/GraphicsPathWinding.as"
+include "flash/display/GraphicsSolidFill.as"
+include "flash/display/GraphicsStroke.as"
+include "flash/display/GraphicsTrianglePath.as"
 include "flash/display/InterpolationMethod.as"
 include "flash/display/JointStyle.as"
 include "flash/display/JPEGEncoderOptions.as"
@@ -213,6 +224,7 @@ include "flash/net/IDynamicPropertyOutput.as"
 include "flash/net/IDynamicPropertyWriter.as"
 include "flash/net/LocalConnection.as"
 include "flash/net/NetConnection.as"
+include "flash/net/NetStream.as"
 include "flash/net/NetGroupReceiveMode.as"
 include "flash/net/NetGroupReplicationStrategy.as"
 include "flash/net/NetGroupSendMode.as"
@@ -221,12 +233,14 @@ include "flash/net/ObjectEncoding.as"
 include "flash/net/Responder.as"
 include "flash/net/SharedObject.as"
 include "flash/net/SharedObjectFlushStatus.as"
+include "flash/net/Socket.as"
 include "flash/net/URLLoader.as"
 include "flash/net/URLLoaderDataFormat.as"
 include "flash/net/URLRequest.as"
 include "flash/net/URLRequestHeader.as"
 include "flash/net/URLRequestMethod.as"
 include "flash/net/URLVariables.as"
+include "flash/net/XMLSocket.as"
 
 include "flash/profiler/Telemetry.as"
 include "flash/printing/PrintJobOrientation.as"
@@ -264,21 +278,36 @@ include "flash/text/TextFormatAlign.as"
 include "flash/text/TextInteractionMode.as"
 include "flash/text/TextLineMetrics.as"
 
+include "flash/text/engine/TextJustifier.as" // TextJustifier is a superclass of SpaceJustifier and EastAsianJustifier
+
 include "flash/text/engine/BreakOpportunity.as"
 include "flash/text/engine/CFFHinting.as"
+include "flash/text/engine/ContentElement.as"
 include "flash/text/engine/DigitCase.as"
 include "flash/text/engine/DigitWidth.as"
+include "flash/text/engine/EastAsianJustifier.as"
+include "flash/text/engine/ElementFormat.as"
+include "flash/text/engine/FontDescription.as"
 include "flash/text/engine/FontLookup.as"
+include "flash/text/engine/FontMetrics.as"
 include "flash/text/engine/FontPosture.as"
 include "flash/text/engine/FontWeight.as"
+include "flash/text/engine/GraphicElement.as"
+include "flash/text/engine/GroupElement.as"
 include "flash/text/engine/JustificationStyle.as"
 include "flash/text/engine/Kerning.as"
 include "flash/text/engine/LigatureLevel.as"
 include "flash/text/engine/LineJustification.as"
 include "flash/text/engine/RenderingMode.as"
+include "flash/text/engine/SpaceJustifier.as"
 include "flash/text/engine/TabAlignment.as"
+include "flash/text/engine/TabStop.as"
 include "flash/text/engine/TextBaseline.as"
+include "flash/text/engine/TextBlock.as"
+include "flash/text/engine/TextElement.as"
+include "flash/text/engine/TextLine.as"
 include "flash/text/engine/TextLineCreationResult.as"
+include "flash/text/engine/TextLineMirrorRegion.as"
 include "flash/text/engine/TextLineValidity.as"
 include "flash/text/engine/TextRotation.as"
 include "flash/text/engine/TypographicCase.as"
@@ -1,13 +1,18 @@
 //! AVM2 methods
 
 use crate::avm2::activation::Activation;
+use crate::avm2::decode::DecodedBody;
+use crate::avm2::error::verify_error;
 use crate::avm2::object::Object;
+use crate::avm2::property_cache::PropertyCache;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
+use crate::avm2::verify::{verify_method, VerifyError};
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::string::AvmString;
 use gc_arena::{Collect, Gc, MutationContext};
+use std::cell::RefCell;
 use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -128,6 +133,14 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// The method body decoded into instructions, or the reason it failed
+    /// verification, once it has been run.
+    #[collect(require_static)]
+    decoded_body: RefCell<Option<Result<Rc<DecodedBody>, VerifyError>>>,
+
+    /// The inline caches of the property access instructions of this method.
+    property_cache: PropertyCache<'gc>,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -162,6 +175,8 @@ impl<'gc> BytecodeMethod<'gc> {
                         signature,
                         return_type,
                         is_function,
+                        decoded_body: RefCell::new(None),
+                        property_cache: PropertyCache::new(activation.context.gc_context),
                     });
                 }
             }
@@ -175,6 +190,8 @@ impl<'gc> BytecodeMethod<'gc> {
             signature,
             return_type: Multiname::any(activation.context.gc_context),
             is_function,
+            decoded_body: RefCell::new(None),
+            property_cache: PropertyCache::new(activation.context.gc_context),
         })
     }
 
@@ -204,6 +221,44 @@ impl<'gc> BytecodeMethod<'gc> {
         }
     }
 
+    /// Get the method body decoded into instructions, decoding and verifying
+    /// it the first time it's needed.
+    ///
+    /// A method that fails verification throws a `VerifyError` every time
+    /// it's called.
+    pub fn decoded_body(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Rc<DecodedBody>, Error<'gc>> {
+        let cached = self.decoded_body.borrow().clone();
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let body = self
+                    .body()
+                    .ok_or("Cannot execute non-native method without body")?;
+                let result = verify_method(&self.abc, body).map(Rc::new);
+                *self.decoded_body.borrow_mut() = Some(result.clone());
+                result
+            }
+        };
+
+        match result {
+            Ok(decoded_body) => Ok(decoded_body),
+            Err(e) => Err(Error::AvmError(verify_error(
+                activation,
+                e.message(),
+                e.code(),
+            )?)),
+        }
+    }
+
+    /// Get the inline caches of the property access instructions of this
+    /// method.
+    pub fn property_cache(&self) -> PropertyCache<'gc> {
+        self.property_cache
+    }
+
     /// Get the list of method params for this method.
     pub fn signature(&self) -> &[ParamConfig<'gc>] {
         &self.signature
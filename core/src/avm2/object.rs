@@ -20,7 +20,9 @@ use crate::avm2::QName;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::context::UpdateContext;
 use crate::display_object::DisplayObject;
+use crate::font::Font;
 use crate::html::TextFormat;
+use crate::streams::NetStream;
 use crate::string::AvmString;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
@@ -39,10 +41,12 @@ mod dispatch_object;
 mod domain_object;
 mod error_object;
 mod event_object;
+mod font_object;
 mod function_object;
 mod index_buffer_3d_object;
 mod loaderinfo_object;
 mod namespace_object;
+mod netstream_object;
 mod primitive_object;
 mod program_3d_object;
 mod proxy_object;
@@ -53,6 +57,7 @@ mod sound_object;
 mod soundchannel_object;
 mod stage3d_object;
 mod stage_object;
+mod textblock_object;
 mod textformat_object;
 mod texture_object;
 mod vector_object;
@@ -71,12 +76,14 @@ pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::{appdomain_allocator, DomainObject};
 pub use crate::avm2::object::error_object::{error_allocator, ErrorObject};
 pub use crate::avm2::object::event_object::{event_allocator, EventObject};
+pub use crate::avm2::object::font_object::{font_allocator, FontObject};
 pub use crate::avm2::object::function_object::{function_allocator, FunctionObject};
 pub use crate::avm2::object::index_buffer_3d_object::IndexBuffer3DObject;
 pub use crate::avm2::object::loaderinfo_object::{
     loader_info_allocator, LoaderInfoObject, LoaderStream,
 };
 pub use crate::avm2::object::namespace_object::{namespace_allocator, NamespaceObject};
+pub use crate::avm2::object::netstream_object::{netstream_allocator, NetStreamObject};
 pub use crate::avm2::object::primitive_object::{primitive_allocator, PrimitiveObject};
 pub use crate::avm2::object::program_3d_object::Program3DObject;
 pub use crate::avm2::object::proxy_object::{proxy_allocator, ProxyObject};
@@ -87,6 +94,7 @@ pub use crate::avm2::object::sound_object::{sound_allocator, QueuedPlay, SoundDa
 pub use crate::avm2::object::soundchannel_object::{soundchannel_allocator, SoundChannelObject};
 pub use crate::avm2::object::stage3d_object::{stage_3d_allocator, Stage3DObject};
 pub use crate::avm2::object::stage_object::{stage_allocator, StageObject};
+pub use crate::avm2::object::textblock_object::{textblock_allocator, TextBlockObject};
 pub use crate::avm2::object::textformat_object::{textformat_allocator, TextFormatObject};
 pub use crate::avm2::object::texture_object::TextureObject;
 pub use crate::avm2::object::vector_object::{vector_allocator, VectorObject};
@@ -124,6 +132,7 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
         DictionaryObject(DictionaryObject<'gc>),
         QNameObject(QNameObject<'gc>),
         TextFormatObject(TextFormatObject<'gc>),
+        FontObject(FontObject<'gc>),
         ProxyObject(ProxyObject<'gc>),
         ErrorObject(ErrorObject<'gc>),
         Stage3DObject(Stage3DObject<'gc>),
@@ -132,6 +141,8 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
         VertexBuffer3DObject(VertexBuffer3DObject<'gc>),
         TextureObject(TextureObject<'gc>),
         Program3DObject(Program3DObject<'gc>),
+        NetStreamObject(NetStreamObject<'gc>),
+        TextBlockObject(TextBlockObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -161,13 +172,30 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This corresponds directly to the AVM2 operation `getproperty`, with the
     /// exception that it does not special-case object lookups on dictionary
     /// structured objects.
-    #[allow(unused_mut)] //Not unused.
     fn get_property(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.get_resolved_property(multiname, property, activation)
+    }
+
+    /// Retrieve a property whose trait has already been looked up in the
+    /// vtable of this object, as by `get_property`.
+    ///
+    /// `property` must be the result of looking up `multiname` in the current
+    /// vtable of this object; `None` means the property is not a trait.
+    ///
+    /// This method should not be overridden.
+    #[allow(unused_mut)] //Not unused.
+    fn get_resolved_property(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 self.base().get_slot(slot_id)
             }
@@ -261,7 +289,25 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.set_resolved_property(multiname, property, value, activation)
+    }
+
+    /// Set a property whose trait has already been looked up in the vtable of
+    /// this object, as by `set_property`.
+    ///
+    /// `property` must be the result of looking up `multiname` in the current
+    /// vtable of this object; `None` means the property is not a trait.
+    ///
+    /// This method should not be overridden.
+    fn set_resolved_property(
+        &mut self,
+        multiname: &Multiname<'gc>,
+        property: Option<Property>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match property {
             Some(Property::Slot { slot_id }) => {
                 let value = self
                     .vtable()
@@ -339,7 +385,25 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.init_resolved_property(multiname, property, value, activation)
+    }
+
+    /// Initialize a property whose trait has already been looked up in the
+    /// vtable of this object, as by `init_property`.
+    ///
+    /// `property` must be the result of looking up `multiname` in the current
+    /// vtable of this object; `None` means the property is not a trait.
+    ///
+    /// This method should not be overridden.
+    fn init_resolved_property(
+        &mut self,
+        multiname: &Multiname<'gc>,
+        property: Option<Property>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 let value = self
                     .vtable()
@@ -402,14 +466,32 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This method should not be overridden.
     ///
     /// This corresponds directly to the `callproperty` operation in AVM2.
-    #[allow(unused_mut)]
     fn call_property(
+        self,
+        multiname: &Multiname<'gc>,
+        arguments: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.call_resolved_property(multiname, property, arguments, activation)
+    }
+
+    /// Call a named property whose trait has already been looked up in the
+    /// vtable of this object, as by `call_property`.
+    ///
+    /// `property` must be the result of looking up `multiname` in the current
+    /// vtable of this object; `None` means the property is not a trait.
+    ///
+    /// This method should not be overridden.
+    #[allow(unused_mut)]
+    fn call_resolved_property(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 let obj = self.base().get_slot(slot_id)?.as_callable(
                     activation,
@@ -1211,6 +1293,16 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Unwrap this object's NetStream.
+    fn as_netstream(self) -> Option<NetStream<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as a text block.
+    fn as_text_block(self) -> Option<TextBlockObject<'gc>> {
+        None
+    }
+
     /// Unwrap this object's bitmap data
     fn as_bitmap_data(&self) -> Option<GcCell<'gc, BitmapData<'gc>>> {
         None
@@ -1253,6 +1345,16 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Get this object as a `FontObject`, if it is one.
+    fn as_font_object(&self) -> Option<FontObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as a font.
+    fn as_font(&self) -> Option<Font<'gc>> {
+        None
+    }
+
     /// Unwrap this object as an Error.
     fn as_error_object(&self) -> Option<ErrorObject<'gc>> {
         None
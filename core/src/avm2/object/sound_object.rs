@@ -1,11 +1,12 @@
 //! Object representation for sounds
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::object::{ByteArrayObject, ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
-use crate::avm2::Error;
-use crate::backend::audio::SoundHandle;
+use crate::avm2::{Avm2, Error};
+use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
 use core::fmt;
@@ -29,6 +30,7 @@ pub fn sound_allocator<'gc>(
             sound_data: SoundData::NotLoaded {
                 queued_plays: Vec::new(),
             },
+            extract_position: 0,
         },
     ))
     .into())
@@ -54,6 +56,9 @@ pub struct SoundObjectData<'gc> {
 
     /// The sound this object holds.
     sound_data: SoundData<'gc>,
+
+    /// The sample frame after the last one read by `Sound.extract`.
+    extract_position: u32,
 }
 
 #[derive(Collect)]
@@ -88,23 +93,98 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    pub fn extract_position(self) -> u32 {
+        self.0.read().extract_position
+    }
+
+    pub fn set_extract_position(self, mc: MutationContext<'gc, '_>, extract_position: u32) {
+        self.0.write(mc).extract_position = extract_position;
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(
         self,
         queued: QueuedPlay<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<bool, Error<'gc>> {
-        let mut this = self.0.write(activation.context.gc_context);
-        match &mut this.sound_data {
-            SoundData::NotLoaded { queued_plays } => {
-                queued_plays.push(queued);
-                // We don't know the length yet, so return the `SoundChannel`
-                Ok(true)
+        if let Some(sound) = self.sound_handle() {
+            return play_queued(queued, sound, activation);
+        }
+
+        // A sound with no source generates its audio with `sampleData` handlers.
+        let is_dynamic = Object::from(self)
+            .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+            .coerce_to_boolean();
+        if is_dynamic {
+            if let Some(instance) = activation.context.start_dynamic_sound(self) {
+                attach_sound_channel(queued, instance, activation);
+            }
+            return Ok(true);
+        }
+
+        if let SoundData::NotLoaded { queued_plays } =
+            &mut self.0.write(activation.context.gc_context).sound_data
+        {
+            queued_plays.push(queued);
+        }
+        // We don't know the length yet, so return the `SoundChannel`
+        Ok(true)
+    }
+
+    /// Dispatch a `sampleData` event to ask the listeners of this sound for the sample
+    /// frames starting at `position`, and return the frames that they supplied.
+    pub fn request_samples(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        position: f64,
+    ) -> Vec<[f32; 2]> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        match self.dispatch_sample_data(&mut activation, position) {
+            Ok(samples) => samples,
+            Err(e) => {
+                tracing::error!(
+                    "Encountered AVM2 error when dispatching `sampleData` event: {}",
+                    e
+                );
+                vec![]
             }
-            SoundData::Loaded { sound } => play_queued(queued, *sound, activation),
         }
     }
 
+    fn dispatch_sample_data(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        position: f64,
+    ) -> Result<Vec<[f32; 2]>, Error<'gc>> {
+        let data = ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?;
+        let sample_data_event_class = activation.avm2().classes().sampledataevent;
+        let event = sample_data_event_class.construct(
+            activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                position.into(),
+                data.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, event, self.into())?;
+
+        // The samples are interleaved stereo 32-bit floats, read from the start of the
+        // data regardless of its position.
+        let data = data
+            .as_bytearray()
+            .ok_or("SampleDataEvent data should be a ByteArray")?;
+        Ok((0..data.len() / 8)
+            .map(|i| {
+                [
+                    data.read_float_at(i * 8).unwrap_or_default(),
+                    data.read_float_at(i * 8 + 4).unwrap_or_default(),
+                ]
+            })
+            .collect())
+    }
+
     pub fn set_sound(
         self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -148,23 +228,32 @@ fn play_queued<'gc>(
         .context
         .start_sound(sound, &queued.sound_info, None, None)
     {
-        if let Some(sound_transform) = queued.sound_transform {
-            activation
-                .context
-                .set_local_sound_transform(instance, sound_transform);
-        }
-
-        queued
-            .sound_channel
-            .as_sound_channel()
-            .unwrap()
-            .set_sound_instance(activation, instance);
+        attach_sound_channel(queued, instance, activation);
+    }
+    Ok(true)
+}
 
+/// Associate the `SoundChannel` of a play call with the sound instance that it started.
+fn attach_sound_channel<'gc>(
+    queued: QueuedPlay<'gc>,
+    instance: SoundInstanceHandle,
+    activation: &mut Activation<'_, 'gc>,
+) {
+    if let Some(sound_transform) = queued.sound_transform {
         activation
             .context
-            .attach_avm2_sound_channel(instance, queued.sound_channel);
+            .set_local_sound_transform(instance, sound_transform);
     }
-    Ok(true)
+
+    queued
+        .sound_channel
+        .as_sound_channel()
+        .unwrap()
+        .set_sound_instance(activation, instance);
+
+    activation
+        .context
+        .attach_avm2_sound_channel(instance, queued.sound_channel);
 }
 
 impl<'gc> TObject<'gc> for SoundObject<'gc> {
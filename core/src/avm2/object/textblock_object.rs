@@ -0,0 +1,83 @@
+//! Object representation for TextBlock
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::TextBlockLayout;
+use core::fmt;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// A class instance allocator that allocates TextBlock objects.
+pub fn textblock_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class);
+
+    Ok(TextBlockObject(GcCell::allocate(
+        activation.context.gc_context,
+        TextBlockObjectData { base, layout: None },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct TextBlockObject<'gc>(GcCell<'gc, TextBlockObjectData<'gc>>);
+
+impl fmt::Debug for TextBlockObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextBlockObject")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct TextBlockObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The content of the block, prepared for breaking into lines.
+    ///
+    /// This is built when the first line of the block is created and reused
+    /// for the following ones, until the content or formatting of the block
+    /// changes.
+    layout: Option<TextBlockLayout<'gc>>,
+}
+
+impl<'gc> TextBlockObject<'gc> {
+    pub fn layout(&self) -> Ref<Option<TextBlockLayout<'gc>>> {
+        Ref::map(self.0.read(), |read| &read.layout)
+    }
+
+    pub fn set_layout(&self, mc: MutationContext<'gc, '_>, layout: Option<TextBlockLayout<'gc>>) {
+        self.0.write(mc).layout = layout;
+    }
+}
+
+impl<'gc> TObject<'gc> for TextBlockObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_text_block(self) -> Option<TextBlockObject<'gc>> {
+        Some(self)
+    }
+}
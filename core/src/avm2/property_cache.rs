@@ -0,0 +1,202 @@
+//! Inline caches for property lookups
+
+use crate::avm2::property::Property;
+use crate::avm2::vtable::VTable;
+use crate::avm2::Multiname;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// The result of the last trait lookup made by a property access instruction.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+struct CacheEntry<'gc> {
+    /// The vtable of the object the lookup was made on.
+    vtable: VTable<'gc>,
+
+    /// The version of `vtable` at the time of the lookup.
+    version: u32,
+
+    /// The trait that was found, if any.
+    property: Option<Property>,
+}
+
+/// The inline caches of the property access instructions of a method,
+/// indexed by instruction.
+///
+/// Each instruction remembers the trait it found on the vtable of the last
+/// object it accessed, so that accessing objects of the same class again
+/// skips the lookup. Only the vtable lookup is cached: when no trait was
+/// found, the instruction still looks up dynamic properties and the prototype
+/// chain every time, so changes to those can't make a cache entry stale.
+/// Adding traits to a vtable changes its version, which invalidates the
+/// entries made against it.
+///
+/// Only instructions whose multiname has no runtime components may use the
+/// cache, since the entries don't record the name that was looked up.
+///
+/// `getproperty`, `setproperty`, `initproperty`, `callproperty`,
+/// `callpropvoid` and `callproplex` use the cache. `getlex` isn't cached, as
+/// it searches every object of the scope chain and the global scripts rather
+/// than the vtable of a single receiver. The `super` instructions aren't
+/// either: they look up the trait in the vtable of the superclass instead of
+/// the receiver, and only appear in overriding methods and constructors,
+/// which don't usually run in tight loops.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub struct PropertyCache<'gc>(GcCell<'gc, Vec<Option<CacheEntry<'gc>>>>);
+
+impl<'gc> PropertyCache<'gc> {
+    pub fn new(mc: MutationContext<'gc, '_>) -> Self {
+        Self(GcCell::allocate(mc, Vec::new()))
+    }
+
+    /// Look up the trait that `multiname` refers to in `vtable`, as
+    /// `VTable::get_trait` does, using and updating the cache entry of the
+    /// instruction at `index`.
+    pub fn get_trait(
+        self,
+        mc: MutationContext<'gc, '_>,
+        index: usize,
+        vtable: VTable<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        let version = vtable.version();
+        if let Some(Some(entry)) = self.0.read().get(index) {
+            if VTable::ptr_eq(entry.vtable, vtable) && entry.version == version {
+                return entry.property;
+            }
+        }
+
+        let property = vtable.get_trait(multiname);
+        let mut entries = self.0.write(mc);
+        if entries.len() <= index {
+            entries.resize(index + 1, None);
+        }
+        entries[index] = Some(CacheEntry {
+            vtable,
+            version,
+            property,
+        });
+
+        property
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::activation::Activation;
+    use crate::avm2::object::TObject;
+    use crate::avm2::QName;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+
+    /// Run `test` with an activation of an empty movie.
+    fn with_activation<F>(test: F)
+    where
+        F: for<'a, 'gc> FnOnce(&mut Activation<'a, 'gc>),
+    {
+        let movie = SwfMovie::empty(32);
+        let player = PlayerBuilder::new().with_movie(movie).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            test(&mut activation);
+        });
+    }
+
+    #[test]
+    fn install_const_trait_late_invalidates_entries() {
+        with_activation(|activation| {
+            let mc = activation.context.gc_context;
+            let public = activation.avm2().public_namespace;
+
+            // The player globals are installed on the global object of the
+            // script that defines `Object`.
+            let (_, script) = activation
+                .avm2()
+                .globals
+                .get_defining_script(&Multiname::new(public, "Object"))
+                .unwrap()
+                .expect("Object should be defined");
+            let (_, mut global, _) = script.init();
+            let vtable = global.vtable().expect("Global objects have a vtable");
+
+            let cache = PropertyCache::new(mc);
+            let name = Multiname::new(public, "lateConstant");
+            assert!(cache.get_trait(mc, 0, vtable, &name).is_none());
+
+            let int_class = activation.avm2().classes().int;
+            global.install_const_late(mc, QName::new(public, "lateConstant"), 5.into(), int_class);
+            assert!(matches!(
+                cache.get_trait(mc, 0, vtable, &name),
+                Some(Property::Slot { .. })
+            ));
+        });
+    }
+
+    #[test]
+    fn replaced_trait_invalidates_entries() {
+        with_activation(|activation| {
+            let mc = activation.context.gc_context;
+            let public = activation.avm2().public_namespace;
+            let int_class = activation.avm2().classes().int;
+
+            // `first` is in slot 1, and `second` is installed in slot 2.
+            let vtable = VTable::newcatch(mc, &QName::new(public, "first"));
+            vtable.install_const_trait_late(mc, QName::new(public, "second"), 5.into(), int_class);
+
+            let cache = PropertyCache::new(mc);
+            let name = Multiname::new(public, "second");
+            assert!(matches!(
+                cache.get_trait(mc, 0, vtable, &name),
+                Some(Property::Slot { slot_id: 2 })
+            ));
+
+            // Point `second` at the slot of `first`.
+            vtable.copy_property_for_interface(
+                mc,
+                QName::new(public, "first"),
+                QName::new(public, "second"),
+            );
+            assert!(matches!(
+                cache.get_trait(mc, 0, vtable, &name),
+                Some(Property::Slot { slot_id: 1 })
+            ));
+        });
+    }
+
+    #[test]
+    fn entries_are_not_shared_between_vtables() {
+        with_activation(|activation| {
+            let mc = activation.context.gc_context;
+            let public = activation.avm2().public_namespace;
+
+            // Both vtables have a single trait in slot 1, under different names,
+            // and the same version.
+            let a = VTable::newcatch(mc, &QName::new(public, "a"));
+            let b = VTable::newcatch(mc, &QName::new(public, "b"));
+            assert_eq!(a.version(), b.version());
+
+            let cache = PropertyCache::new(mc);
+            let name = Multiname::new(public, "a");
+            assert!(matches!(
+                cache.get_trait(mc, 0, a, &name),
+                Some(Property::Slot { slot_id: 1 })
+            ));
+            assert!(cache.get_trait(mc, 0, b, &name).is_none());
+            assert!(matches!(
+                cache.get_trait(mc, 0, a, &name),
+                Some(Property::Slot { slot_id: 1 })
+            ));
+
+            // A copy has the same traits and version, but gets its own entry.
+            let copy = a.duplicate(mc);
+            let name = Multiname::new(public, "b");
+            assert!(cache.get_trait(mc, 0, copy, &name).is_none());
+            assert!(matches!(
+                cache.get_trait(mc, 0, b, &name),
+                Some(Property::Slot { slot_id: 1 })
+            ));
+        });
+    }
+}
@@ -0,0 +1,604 @@
+//! AVM2 bytecode verification
+
+use crate::avm2::decode::{decode_body, DecodedBody};
+use swf::avm2::types::{AbcFile, Index, MethodBody, Multiname as AbcMultiname, Op};
+
+/// A problem found while verifying a method body.
+///
+/// This is thrown to ActionScript as a `VerifyError` when the method is
+/// called.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyError {
+    code: u32,
+    message: String,
+}
+
+impl VerifyError {
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: format!("Error #{}: {}", code, message.into()),
+        }
+    }
+
+    /// The Flash Player error code of this error.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// The message of this error, including its error code.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// The depths of the operand stack and the scope stack before an
+/// instruction runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Frame {
+    stack_depth: u32,
+    scope_depth: u32,
+}
+
+/// Decode and verify a method body.
+///
+/// Every instruction that can be reached is checked to not underflow or
+/// overflow the operand stack and the scope stack, to only access registers
+/// and constant pool entries that exist, and to not run past the end of the
+/// method. Branches that meet must agree on the depth of both stacks.
+pub fn verify_method(abc: &AbcFile, body: &MethodBody) -> Result<DecodedBody, VerifyError> {
+    let decoded = decode_body(body)?;
+    let ops = &decoded.ops;
+
+    // The scope stack of a method starts out empty, above the scopes it
+    // inherited.
+    let max_scope_depth = body.max_scope_depth.saturating_sub(body.init_scope_depth);
+
+    let mut frames = vec![None; ops.len()];
+    let mut worklist = vec![];
+    let entry = Frame {
+        stack_depth: 0,
+        scope_depth: 0,
+    };
+    merge(&mut frames, &mut worklist, 0, entry)?;
+
+    // Exception handlers start with only the thrown value on the stack.
+    for exception in &decoded.exceptions {
+        if body.max_stack < 1 {
+            return Err(VerifyError::new(1023, "Stack overflow occurred."));
+        }
+        let handler = Frame {
+            stack_depth: 1,
+            scope_depth: 0,
+        };
+        merge(
+            &mut frames,
+            &mut worklist,
+            exception.target_offset as usize,
+            handler,
+        )?;
+    }
+
+    while let Some(index) = worklist.pop() {
+        let frame = frames[index].expect("Queued instructions have a frame");
+        let op = &ops[index];
+
+        check_registers(op, body.num_locals)?;
+        if let Op::NewCatch { index } = *op {
+            if index.0 as usize >= decoded.exceptions.len() {
+                return Err(VerifyError::new(
+                    1107,
+                    "The ABC data is corrupt, attempt to read out of bounds.",
+                ));
+            }
+        }
+
+        let (pops, pushes) = stack_effect(abc, op)?;
+        if frame.stack_depth < pops {
+            return Err(VerifyError::new(1024, "Stack underflow occurred."));
+        }
+        let stack_depth = frame.stack_depth - pops + pushes;
+        if stack_depth > body.max_stack {
+            return Err(VerifyError::new(1023, "Stack overflow occurred."));
+        }
+
+        let scope_depth = match *op {
+            Op::PushScope | Op::PushWith => {
+                if frame.scope_depth >= max_scope_depth {
+                    return Err(VerifyError::new(1017, "Scope stack overflow occurred."));
+                }
+                frame.scope_depth + 1
+            }
+            Op::PopScope => {
+                if frame.scope_depth == 0 {
+                    return Err(VerifyError::new(1018, "Scope stack underflow occurred."));
+                }
+                frame.scope_depth - 1
+            }
+            Op::GetScopeObject { index } => {
+                if index as u32 >= frame.scope_depth {
+                    return Err(VerifyError::new(
+                        1019,
+                        format!("Getscopeobject {index} is out of bounds."),
+                    ));
+                }
+                frame.scope_depth
+            }
+            _ => frame.scope_depth,
+        };
+
+        let next = Frame {
+            stack_depth,
+            scope_depth,
+        };
+        match *op {
+            Op::Jump { offset } => merge(&mut frames, &mut worklist, offset as usize, next)?,
+            Op::IfTrue { offset }
+            | Op::IfFalse { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfEq { offset }
+            | Op::IfNe { offset }
+            | Op::IfGe { offset }
+            | Op::IfGt { offset }
+            | Op::IfLe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNge { offset }
+            | Op::IfNgt { offset }
+            | Op::IfNle { offset }
+            | Op::IfNlt { offset } => {
+                merge(&mut frames, &mut worklist, offset as usize, next)?;
+                merge(&mut frames, &mut worklist, index + 1, next)?;
+            }
+            Op::LookupSwitch {
+                default_offset,
+                ref case_offsets,
+            } => {
+                merge(&mut frames, &mut worklist, default_offset as usize, next)?;
+                for case_offset in case_offsets.iter() {
+                    merge(&mut frames, &mut worklist, *case_offset as usize, next)?;
+                }
+            }
+            Op::ReturnValue | Op::ReturnVoid | Op::Throw => {}
+            _ => merge(&mut frames, &mut worklist, index + 1, next)?,
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Record that the instruction at `index` can run with the stack depths of
+/// `frame`, queueing it to be verified if it wasn't reached before.
+fn merge(
+    frames: &mut [Option<Frame>],
+    worklist: &mut Vec<usize>,
+    index: usize,
+    frame: Frame,
+) -> Result<(), VerifyError> {
+    match frames.get_mut(index) {
+        None => Err(VerifyError::new(
+            1020,
+            "Code cannot fall off the end of a method.",
+        )),
+        Some(Some(existing)) if existing.stack_depth != frame.stack_depth => Err(VerifyError::new(
+            1030,
+            format!(
+                "Stack depth is unbalanced. {} != {}.",
+                existing.stack_depth, frame.stack_depth
+            ),
+        )),
+        Some(Some(existing)) if existing.scope_depth != frame.scope_depth => Err(VerifyError::new(
+            1031,
+            format!(
+                "Scope depth is unbalanced. {} != {}.",
+                existing.scope_depth, frame.scope_depth
+            ),
+        )),
+        Some(Some(_)) => Ok(()),
+        Some(slot @ None) => {
+            *slot = Some(frame);
+            worklist.push(index);
+            Ok(())
+        }
+    }
+}
+
+/// Check that the registers used by an instruction exist.
+fn check_registers(op: &Op, num_locals: u32) -> Result<(), VerifyError> {
+    let check = |register: u32| {
+        if register < num_locals {
+            Ok(())
+        } else {
+            Err(VerifyError::new(
+                1025,
+                format!("An invalid register {register} was accessed."),
+            ))
+        }
+    };
+
+    match *op {
+        Op::GetLocal { index }
+        | Op::SetLocal { index }
+        | Op::Kill { index }
+        | Op::IncLocal { index }
+        | Op::IncLocalI { index }
+        | Op::DecLocal { index }
+        | Op::DecLocalI { index } => check(index),
+        Op::HasNext2 {
+            object_register,
+            index_register,
+        } => {
+            check(object_register)?;
+            check(index_register)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The number of values that the name of a property access pops from the
+/// stack, in addition to the operands of the instruction.
+fn runtime_name_count(abc: &AbcFile, index: Index<AbcMultiname>) -> Result<u32, VerifyError> {
+    if index.0 == 0 {
+        return Ok(0);
+    }
+
+    let multinames = &abc.constant_pool.multinames;
+    match multinames.get(index.0 as usize - 1) {
+        Some(AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA) => Ok(2),
+        Some(
+            AbcMultiname::RTQName { .. }
+            | AbcMultiname::RTQNameA { .. }
+            | AbcMultiname::MultinameL { .. }
+            | AbcMultiname::MultinameLA { .. },
+        ) => Ok(1),
+        Some(_) => Ok(0),
+        None => Err(VerifyError::new(
+            1032,
+            format!(
+                "Cpool index {} is out of range {}.",
+                index.0,
+                multinames.len() + 1
+            ),
+        )),
+    }
+}
+
+/// The number of values an instruction pops from the operand stack, and the
+/// number of values it pushes.
+fn stack_effect(abc: &AbcFile, op: &Op) -> Result<(u32, u32), VerifyError> {
+    Ok(match *op {
+        Op::PushByte { .. }
+        | Op::PushShort { .. }
+        | Op::PushInt { .. }
+        | Op::PushUint { .. }
+        | Op::PushDouble { .. }
+        | Op::PushString { .. }
+        | Op::PushNamespace { .. }
+        | Op::PushConstant { .. }
+        | Op::PushTrue
+        | Op::PushFalse
+        | Op::PushNull
+        | Op::PushUndefined
+        | Op::PushNaN
+        | Op::GetLocal { .. }
+        | Op::NewCatch { .. }
+        | Op::GetOuterScope { .. }
+        | Op::GetScopeObject { .. }
+        | Op::GetGlobalScope
+        | Op::FindDef { .. }
+        | Op::GetLex { .. }
+        | Op::GetGlobalSlot { .. }
+        | Op::NewActivation
+        | Op::NewFunction { .. }
+        | Op::HasNext2 { .. } => (0, 1),
+
+        Op::Pop
+        | Op::SetLocal { .. }
+        | Op::PushScope
+        | Op::PushWith
+        | Op::SetGlobalSlot { .. }
+        | Op::ReturnValue
+        | Op::Throw
+        | Op::DxnsLate
+        | Op::IfTrue { .. }
+        | Op::IfFalse { .. }
+        | Op::LookupSwitch { .. } => (1, 0),
+
+        Op::SetSlot { .. }
+        | Op::Si8
+        | Op::Si16
+        | Op::Si32
+        | Op::Sf32
+        | Op::Sf64
+        | Op::IfStrictEq { .. }
+        | Op::IfStrictNe { .. }
+        | Op::IfEq { .. }
+        | Op::IfNe { .. }
+        | Op::IfGe { .. }
+        | Op::IfGt { .. }
+        | Op::IfLe { .. }
+        | Op::IfLt { .. }
+        | Op::IfNge { .. }
+        | Op::IfNgt { .. }
+        | Op::IfNle { .. }
+        | Op::IfNlt { .. } => (2, 0),
+
+        Op::Dup => (1, 2),
+        Op::Swap => (2, 2),
+
+        Op::GetSlot { .. }
+        | Op::NewClass { .. }
+        | Op::Coerce { .. }
+        | Op::CoerceA
+        | Op::CoerceB
+        | Op::CoerceD
+        | Op::CoerceI
+        | Op::CoerceO
+        | Op::CoerceS
+        | Op::CoerceU
+        | Op::ConvertB
+        | Op::ConvertD
+        | Op::ConvertI
+        | Op::ConvertO
+        | Op::ConvertS
+        | Op::ConvertU
+        | Op::AsType { .. }
+        | Op::IsType { .. }
+        | Op::BitNot
+        | Op::Decrement
+        | Op::DecrementI
+        | Op::Increment
+        | Op::IncrementI
+        | Op::Negate
+        | Op::NegateI
+        | Op::Not
+        | Op::TypeOf
+        | Op::EscXAttr
+        | Op::EscXElem
+        | Op::CheckFilter
+        | Op::Sxi1
+        | Op::Sxi8
+        | Op::Sxi16
+        | Op::Li8
+        | Op::Li16
+        | Op::Li32
+        | Op::Lf32
+        | Op::Lf64 => (1, 1),
+
+        Op::Add
+        | Op::AddI
+        | Op::Subtract
+        | Op::SubtractI
+        | Op::Multiply
+        | Op::MultiplyI
+        | Op::Divide
+        | Op::Modulo
+        | Op::BitAnd
+        | Op::BitOr
+        | Op::BitXor
+        | Op::LShift
+        | Op::RShift
+        | Op::URShift
+        | Op::Equals
+        | Op::StrictEquals
+        | Op::LessThan
+        | Op::LessEquals
+        | Op::GreaterThan
+        | Op::GreaterEquals
+        | Op::In
+        | Op::InstanceOf
+        | Op::AsTypeLate
+        | Op::IsTypeLate
+        | Op::HasNext
+        | Op::NextName
+        | Op::NextValue => (2, 1),
+
+        Op::Call { num_args } => (num_args + 2, 1),
+        Op::CallMethod { num_args, .. } | Op::CallStatic { num_args, .. } => (num_args + 1, 1),
+        Op::CallProperty { index, num_args }
+        | Op::CallPropLex { index, num_args }
+        | Op::CallSuper { index, num_args } => (num_args + 1 + runtime_name_count(abc, index)?, 1),
+        Op::CallPropVoid { index, num_args } | Op::CallSuperVoid { index, num_args } => {
+            (num_args + 1 + runtime_name_count(abc, index)?, 0)
+        }
+        Op::Construct { num_args } => (num_args + 1, 1),
+        Op::ConstructProp { index, num_args } => {
+            (num_args + 1 + runtime_name_count(abc, index)?, 1)
+        }
+        Op::ConstructSuper { num_args } => (num_args + 1, 0),
+        Op::NewObject { num_args } => (num_args * 2, 1),
+        Op::NewArray { num_args } => (num_args, 1),
+        Op::ApplyType { num_types } => (num_types + 1, 1),
+
+        Op::GetProperty { index }
+        | Op::GetSuper { index }
+        | Op::DeleteProperty { index }
+        | Op::GetDescendants { index } => (1 + runtime_name_count(abc, index)?, 1),
+        Op::SetProperty { index } | Op::InitProperty { index } | Op::SetSuper { index } => {
+            (2 + runtime_name_count(abc, index)?, 0)
+        }
+        Op::FindProperty { index } | Op::FindPropStrict { index } => {
+            (runtime_name_count(abc, index)?, 1)
+        }
+
+        Op::ReturnVoid
+        | Op::Jump { .. }
+        | Op::PopScope
+        | Op::Kill { .. }
+        | Op::IncLocal { .. }
+        | Op::IncLocalI { .. }
+        | Op::DecLocal { .. }
+        | Op::DecLocalI { .. }
+        | Op::Nop
+        | Op::Label
+        | Op::Dxns { .. }
+        | Op::Debug { .. }
+        | Op::DebugFile { .. }
+        | Op::DebugLine { .. }
+        | Op::Bkpt
+        | Op::BkptLine { .. }
+        | Op::Timestamp => (0, 0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::avm2::types::{ConstantPool, Exception};
+
+    fn abc() -> AbcFile {
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![AbcMultiname::RTQNameL],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        }
+    }
+
+    fn body(max_stack: u32, num_locals: u32, max_scope_depth: u32, code: &[u8]) -> MethodBody {
+        MethodBody {
+            method: Index::new(0),
+            max_stack,
+            num_locals,
+            init_scope_depth: 0,
+            max_scope_depth,
+            code: code.to_vec(),
+            exceptions: vec![],
+            traits: vec![],
+        }
+    }
+
+    fn verify_error_code(body: &MethodBody) -> Option<u32> {
+        verify_method(&abc(), body).err().map(|e| e.code())
+    }
+
+    #[test]
+    fn valid_method() {
+        // getlocal0, pushscope, pushbyte 1, iftrue +3, pushbyte 2, pop,
+        // popscope, returnvoid
+        let body = body(
+            1,
+            1,
+            1,
+            &[
+                0xd0, 0x30, 0x24, 0x01, 0x11, 0x03, 0x00, 0x00, 0x24, 0x02, 0x29, 0x1d, 0x47,
+            ],
+        );
+        let decoded = verify_method(&abc(), &body).unwrap();
+        assert_eq!(decoded.ops.len(), 8);
+        assert_eq!(decoded.ops[3], Op::IfTrue { offset: 6 });
+    }
+
+    #[test]
+    fn stack_overflow() {
+        // pushtrue, pushtrue, returnvoid
+        let body = body(1, 1, 0, &[0x26, 0x26, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1023));
+    }
+
+    #[test]
+    fn stack_underflow() {
+        // pop, returnvoid
+        let body = body(1, 1, 0, &[0x29, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1024));
+    }
+
+    #[test]
+    fn runtime_names_pop_from_the_stack() {
+        // getlocal0, getproperty RTQNameL, returnvoid
+        let body = body(1, 1, 0, &[0xd0, 0x66, 0x01, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1024));
+    }
+
+    #[test]
+    fn scope_overflow() {
+        // getlocal0, pushscope, getlocal0, pushscope, returnvoid
+        let body = body(1, 1, 1, &[0xd0, 0x30, 0xd0, 0x30, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1017));
+    }
+
+    #[test]
+    fn scope_underflow() {
+        // popscope, returnvoid
+        let body = body(1, 1, 1, &[0x1d, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1018));
+    }
+
+    #[test]
+    fn invalid_register() {
+        // getlocal 3, returnvalue
+        let body = body(1, 1, 0, &[0x62, 0x03, 0x48]);
+        assert_eq!(verify_error_code(&body), Some(1025));
+    }
+
+    #[test]
+    fn branch_into_instruction() {
+        // pushbyte 1, jump -5 (into the operand of pushbyte)
+        let body = body(1, 1, 0, &[0x24, 0x01, 0x10, 0xfb, 0xff, 0xff]);
+        assert_eq!(verify_error_code(&body), Some(1021));
+    }
+
+    #[test]
+    fn fall_off_end() {
+        // pushtrue, pop
+        let body = body(1, 1, 0, &[0x26, 0x29]);
+        assert_eq!(verify_error_code(&body), Some(1020));
+    }
+
+    #[test]
+    fn unbalanced_stack() {
+        // pushtrue, iftrue +1, pushtrue, returnvoid
+        let body = body(1, 1, 0, &[0x26, 0x11, 0x01, 0x00, 0x00, 0x26, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1030));
+    }
+
+    #[test]
+    fn illegal_opcode() {
+        let body = body(1, 1, 0, &[0xff, 0x47]);
+        assert_eq!(verify_error_code(&body), Some(1011));
+    }
+
+    #[test]
+    fn invalid_exception_range() {
+        // pushtrue, pop, returnvoid
+        let mut body = body(1, 1, 0, &[0x26, 0x29, 0x47]);
+        body.exceptions.push(Exception {
+            from_offset: 0,
+            to_offset: 10,
+            target_offset: 2,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        });
+        assert_eq!(verify_error_code(&body), Some(1054));
+    }
+
+    #[test]
+    fn exception_handler() {
+        // pushtrue, pop, returnvoid, handler: throw
+        let mut body = body(1, 1, 0, &[0x26, 0x29, 0x47, 0x03]);
+        body.exceptions.push(Exception {
+            from_offset: 0,
+            to_offset: 2,
+            target_offset: 3,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        });
+        let decoded = verify_method(&abc(), &body).unwrap();
+        assert_eq!(decoded.exceptions[0].from_offset, 0);
+        assert_eq!(decoded.exceptions[0].to_offset, 2);
+        assert_eq!(decoded.exceptions[0].target_offset, 3);
+    }
+}
@@ -39,6 +39,10 @@ pub struct VTableData<'gc> {
     method_table: Vec<ClassBoundMethod<'gc>>,
 
     default_slots: Vec<Option<Value<'gc>>>,
+
+    /// Incremented whenever `resolved_traits` changes, so that property
+    /// lookups cached against this vtable can tell they are stale.
+    version: u32,
 }
 
 // TODO: it might make more sense to just bind the Method to the VTable (and this its class and scope) directly
@@ -64,6 +68,7 @@ impl<'gc> VTable<'gc> {
                 slot_classes: vec![],
                 method_table: vec![],
                 default_slots: vec![],
+                version: 0,
             },
         ))
     }
@@ -88,6 +93,7 @@ impl<'gc> VTable<'gc> {
                 // -1 shift.
                 default_slots: vec![None, None],
                 slot_classes: vec![PropertyClass::Any, PropertyClass::Any],
+                version: 0,
             },
         ));
 
@@ -98,6 +104,15 @@ impl<'gc> VTable<'gc> {
         VTable(GcCell::allocate(mc, self.0.read().clone()))
     }
 
+    pub fn ptr_eq(a: Self, b: Self) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
+    /// The number of times the traits of this vtable have changed.
+    pub fn version(self) -> u32 {
+        self.0.read().version
+    }
+
     pub fn resolved_traits(&self) -> Ref<'_, PropertyMap<'gc, Property>> {
         Ref::map(self.0.read(), |v| &v.resolved_traits)
     }
@@ -244,6 +259,7 @@ impl<'gc> VTable<'gc> {
         let mut write = self.0.write(activation.context.gc_context);
         let write = write.deref_mut();
 
+        write.version = write.version.wrapping_add(1);
         write.defining_class = Some(defining_class);
         write.scope = Some(scope);
 
@@ -465,6 +481,7 @@ impl<'gc> VTable<'gc> {
     ) -> u32 {
         let mut write = self.0.write(mc);
 
+        write.version = write.version.wrapping_add(1);
         write.default_slots.push(Some(value));
         let new_slot_id = write.default_slots.len() as u32 - 1;
         write
@@ -488,6 +505,7 @@ impl<'gc> VTable<'gc> {
         let prop = write.resolved_traits.get(public_name).cloned();
 
         if let Some(prop) = prop {
+            write.version = write.version.wrapping_add(1);
             write.resolved_traits.insert(interface_name, prop);
         }
     }
@@ -1,11 +1,14 @@
 use crate::{
     avm1::SoundObject,
-    avm2::SoundChannelObject,
+    avm2::{SoundChannelObject, SoundObject as Avm2SoundObject},
+    context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
 };
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "audio")]
 pub mod decoders;
@@ -40,6 +43,50 @@ pub enum RegisterError {
     ShortMp3,
 }
 
+/// The format of the audio played by a substream.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubstreamFormat {
+    /// Audio in one of the formats that SWF sounds use.
+    Swf(swf::SoundFormat),
+
+    /// AAC audio, described by its `AudioSpecificConfig`.
+    Aac(Vec<u8>),
+}
+
+/// The packets of compressed audio that make up a substream, such as the audio tags of an
+/// FLV file.
+///
+/// Packets keep being appended while the substream plays, so they are shared between the
+/// `NetStream` that downloads them and the audio backend that plays them.
+#[derive(Clone, Debug, Default)]
+pub struct Substream(Arc<Mutex<VecDeque<Vec<u8>>>>);
+
+impl Substream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a packet to be played after the ones that were already appended.
+    pub fn append(&self, packet: &[u8]) {
+        self.0
+            .lock()
+            .expect("Cannot be called reentrant")
+            .push_back(packet.to_vec());
+    }
+}
+
+impl Iterator for Substream {
+    type Item = Vec<u8>;
+
+    /// Take the next packet to play, if it has been appended yet.
+    fn next(&mut self) -> Option<Vec<u8>> {
+        self.0
+            .lock()
+            .expect("Cannot be called reentrant")
+            .pop_front()
+    }
+}
+
 pub trait AudioBackend: Downcast {
     fn play(&mut self);
     fn pause(&mut self);
@@ -69,6 +116,17 @@ pub trait AudioBackend: Downcast {
         handle: &swf::SoundStreamHead,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a sound whose compressed audio is appended to `substream` while it
+    /// plays, such as the audio track of a `NetStream`.
+    ///
+    /// The sound ends once it runs out of packets, so they should be appended ahead of
+    /// playback.
+    fn start_substream(
+        &mut self,
+        substream: Substream,
+        format: &SubstreamFormat,
+    ) -> Result<SoundInstanceHandle, DecodeError>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -130,6 +188,37 @@ pub trait AudioBackend: Downcast {
 
     /// Returns the last whole window of output samples.
     fn get_sample_history(&self) -> [[f32; 2]; 1024];
+
+    /// Starts playing a sound whose audio is generated at runtime, such as an AVM2 `Sound`
+    /// with a `sampleData` listener.
+    ///
+    /// The sound plays 44.1 kHz stereo sample frames queued with `append_dynamic_samples`.
+    fn start_dynamic_sound(&mut self) -> SoundInstanceHandle;
+
+    /// Queues sample frames to be played by a dynamic sound instance.
+    ///
+    /// If `is_last` is set, the sound ends once the queued samples have been played.
+    fn append_dynamic_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_last: bool,
+    );
+
+    /// Get the number of sample frames queued for a dynamic sound instance that have not
+    /// been played yet.
+    /// Returns `None` if the sound is not/no longer playing, or the backend doesn't play
+    /// dynamic sounds.
+    fn dynamic_samples_queued(&self, instance: SoundInstanceHandle) -> Option<usize>;
+
+    /// Decode up to `num_frames` sample frames of a sound as 44.1 kHz stereo, starting at
+    /// `start_frame`.
+    fn extract_sound(
+        &mut self,
+        sound: SoundHandle,
+        start_frame: u32,
+        num_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError>;
 }
 
 impl_downcast!(AudioBackend);
@@ -215,6 +304,14 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::from_raw_parts(0, 0))
     }
 
+    fn start_substream(
+        &mut self,
+        _substream: Substream,
+        _format: &SubstreamFormat,
+    ) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+    }
+
     fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
 
     fn stop_all_sounds(&mut self) {}
@@ -257,6 +354,31 @@ impl AudioBackend for NullAudioBackend {
     fn get_sample_history(&self) -> [[f32; 2]; 1024] {
         [[0.0f32; 2]; 1024]
     }
+
+    fn start_dynamic_sound(&mut self) -> SoundInstanceHandle {
+        SoundInstanceHandle::from_raw_parts(0, 0)
+    }
+
+    fn append_dynamic_samples(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _samples: &[[f32; 2]],
+        _is_last: bool,
+    ) {
+    }
+
+    fn dynamic_samples_queued(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
+    fn extract_sound(
+        &mut self,
+        _sound: SoundHandle,
+        _start_frame: u32,
+        _num_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        Ok(vec![])
+    }
 }
 
 impl Default for NullAudioBackend {
@@ -300,6 +422,17 @@ impl<'gc> AudioManager<'gc> {
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
     pub const STREAM_DEFAULT_SYNC_THRESHOLD: f64 = 0.2;
 
+    /// The minimum number of sample frames that a `sampleData` handler must supply to keep a
+    /// dynamic sound playing.
+    ///
+    /// When a handler supplies fewer, the sound ends after playing them.
+    pub const MIN_DYNAMIC_SOUND_SAMPLES: usize = 2048;
+
+    /// The maximum number of sample frames that a `sampleData` handler may supply.
+    ///
+    /// Any further frames are ignored.
+    pub const MAX_DYNAMIC_SOUND_SAMPLES: usize = 8192;
+
     pub fn new() -> Self {
         Self {
             sounds: Vec::with_capacity(Self::MAX_SOUNDS),
@@ -389,6 +522,37 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                dynamic: None,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Start playing an AVM2 sound that generates its audio with `sampleData` handlers.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        source: Avm2SoundObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound();
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                dynamic: Some(DynamicSound {
+                    source,
+                    position: 0.0,
+                    block_size: Self::MIN_DYNAMIC_SOUND_SAMPLES,
+                }),
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -398,6 +562,69 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Dispatch `sampleData` events for the dynamic sounds that are running out of samples.
+    ///
+    /// More samples are requested once fewer than the last handler supplied are left to
+    /// play, so that one block of samples is always queued behind the one that is playing.
+    pub fn update_dynamic_sounds(context: &mut UpdateContext<'_, 'gc>) {
+        let instances: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter(|sound| sound.dynamic.is_some())
+            .map(|sound| sound.instance)
+            .collect();
+
+        for instance in instances {
+            // Earlier handlers may have stopped this sound.
+            let dynamic = match context
+                .audio_manager
+                .sounds
+                .iter()
+                .find(|sound| sound.instance == instance)
+                .and_then(|sound| sound.dynamic.clone())
+            {
+                Some(dynamic) => dynamic,
+                None => continue,
+            };
+            match context.audio.dynamic_samples_queued(instance) {
+                Some(queued) if queued < dynamic.block_size => {}
+                _ => continue,
+            }
+
+            let mut samples = dynamic.source.request_samples(context, dynamic.position);
+            if samples.len() > Self::MAX_DYNAMIC_SOUND_SAMPLES {
+                tracing::warn!(
+                    "sampleData handler supplied {} sample frames, only playing {}",
+                    samples.len(),
+                    Self::MAX_DYNAMIC_SOUND_SAMPLES
+                );
+                samples.truncate(Self::MAX_DYNAMIC_SOUND_SAMPLES);
+            }
+            let is_last = samples.len() < Self::MIN_DYNAMIC_SOUND_SAMPLES;
+            context
+                .audio
+                .append_dynamic_samples(instance, &samples, is_last);
+
+            if let Some(sound) = context
+                .audio_manager
+                .sounds
+                .iter_mut()
+                .find(|sound| sound.instance == instance)
+            {
+                sound.dynamic = if is_last {
+                    None
+                } else {
+                    Some(DynamicSound {
+                        position: dynamic.position + samples.len() as f64,
+                        block_size: samples.len(),
+                        ..dynamic
+                    })
+                };
+            }
+        }
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -486,6 +713,40 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                dynamic: None,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Start playing the audio track of a `NetStream`.
+    pub fn start_substream(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        substream: Substream,
+        format: &SubstreamFormat,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = match audio.start_substream(substream, format) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    tracing::error!("Couldn't start NetStream audio: {}", e);
+                    return None;
+                }
+            };
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                dynamic: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -668,6 +929,24 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The state of this sound if its audio is generated by `sampleData` handlers, until the
+    /// last samples have been supplied.
+    dynamic: Option<DynamicSound<'gc>>,
+}
+
+/// A playing sound whose audio is generated by the `sampleData` handlers of an AVM2 `Sound`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct DynamicSound<'gc> {
+    /// The `Sound` that `sampleData` events are dispatched to.
+    source: Avm2SoundObject<'gc>,
+
+    /// The number of sample frames supplied so far.
+    position: f64,
+
+    /// The number of sample frames supplied by the last `sampleData` handler.
+    block_size: usize,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
@@ -1,19 +1,28 @@
 //! Audio decoders.
 
+#[cfg(feature = "aac")]
+mod aac;
 mod adpcm;
 #[cfg(feature = "mp3")]
 mod mp3;
 #[cfg(feature = "nellymoser")]
 mod nellymoser;
 mod pcm;
+#[cfg(feature = "speex")]
+mod speex;
 
+#[cfg(feature = "aac")]
+pub use aac::AacDecoder;
 pub use adpcm::AdpcmDecoder;
 #[cfg(feature = "mp3")]
 pub use mp3::{mp3_metadata, Mp3Decoder};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
+#[cfg(feature = "speex")]
+pub use speex::{SpeexDecoder, SpeexEventDecoder};
 
+use crate::backend::audio::{Substream, SubstreamFormat};
 use crate::tag_utils::{ControlFlow, SwfSlice};
 use std::io::{Cursor, Read};
 use swf::{AudioCompression, SoundFormat, TagCode};
@@ -28,8 +37,15 @@ pub enum Error {
     #[error("Couldn't decode ADPCM")]
     InvalidAdpcm(#[from] adpcm::Error),
 
+    #[cfg(feature = "aac")]
+    #[error("Couldn't decode AAC")]
+    InvalidAac(#[from] aac::Error),
+
     #[error("Unhandled compression {0:?}")]
     UnhandledCompression(AudioCompression),
+
+    #[error("Unhandled compression AAC")]
+    UnhandledAac,
 }
 
 /// An audio decoder. Can be used as an `Iterator` to return stero sample frames.
@@ -45,7 +61,7 @@ pub trait Decoder: Iterator<Item = [i16; 2]> + Send + Sync {
 /// Instantiate a decoder for the compression that the sound data uses.
 pub fn make_decoder<R: 'static + Read + Send + Sync>(
     format: &SoundFormat,
-    data: R,
+    #[allow(unused_mut)] mut data: R,
 ) -> Result<Box<dyn Decoder>, Error> {
     let decoder: Box<dyn Decoder> = match format.compression {
         AudioCompression::UncompressedUnknownEndian => {
@@ -75,6 +91,13 @@ pub fn make_decoder<R: 'static + Read + Send + Sync>(
         AudioCompression::Nellymoser => {
             Box::new(NellymoserDecoder::new(data, format.sample_rate.into()))
         }
+        #[cfg(feature = "speex")]
+        AudioCompression::Speex => {
+            // The audio of an event sound is a single packet.
+            let mut packet = Vec::new();
+            let _ = data.read_to_end(&mut packet);
+            Box::new(SpeexEventDecoder::new(packet))
+        }
         _ => return Err(Error::UnhandledCompression(format.compression)),
     };
     Ok(decoder)
@@ -92,6 +115,67 @@ impl<T: Decoder + ?Sized> Decoder for Box<T> {
     }
 }
 
+/// Instantiate a decoder that plays the packets of a substream as they are appended.
+pub fn make_substream_decoder(
+    format: &SubstreamFormat,
+    substream: Substream,
+) -> Result<Box<dyn Decoder>, Error> {
+    match format {
+        // Each ADPCM packet starts with its own header.
+        SubstreamFormat::Swf(format) if format.compression == AudioCompression::Adpcm => {
+            Ok(Box::new(AdpcmStreamDecoder::new(format, substream)?))
+        }
+        // Speex frames are padded at the end of each packet.
+        #[cfg(feature = "speex")]
+        SubstreamFormat::Swf(format) if format.compression == AudioCompression::Speex => {
+            Ok(Box::new(SpeexDecoder::new(substream)))
+        }
+        SubstreamFormat::Swf(format) => make_decoder(format, SubstreamReader::new(substream)),
+        #[cfg(feature = "aac")]
+        SubstreamFormat::Aac(audio_specific_config) => {
+            Ok(Box::new(AacDecoder::new(audio_specific_config, substream)?))
+        }
+        #[cfg(not(feature = "aac"))]
+        SubstreamFormat::Aac(_) => Err(Error::UnhandledAac),
+    }
+}
+
+/// Reads the packets of a substream as one continuous stream of bytes, for the decoders
+/// of formats that don't need to know where packets start.
+struct SubstreamReader {
+    substream: Substream,
+
+    /// The packet that is being read.
+    packet: Cursor<Vec<u8>>,
+}
+
+impl SubstreamReader {
+    fn new(substream: Substream) -> Self {
+        Self {
+            substream,
+            packet: Cursor::new(Vec::new()),
+        }
+    }
+}
+
+impl Read for SubstreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let len = self.packet.read(buf)?;
+            if len > 0 || buf.is_empty() {
+                return Ok(len);
+            }
+
+            // Reached the end of this packet; the substream ends if the next one hasn't
+            // been appended yet.
+            match self.substream.next() {
+                Some(packet) => self.packet = Cursor::new(packet),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
 /// A "stream" sound is a sound that has its data distributed across `SoundStreamBlock` tags,
 /// one per each frame of a MovieClip. The sound is synced to the MovieClip's timeline, and will
 /// stop/seek as the MovieClip stops/seeks.
@@ -143,31 +227,37 @@ impl Iterator for StandardStreamDecoder {
 /// other compression formats that remain the same as if they were a single sound clip.
 /// Therefore, we must recreate the decoder with each `SoundStreamBlock` to parse the additional
 /// headers.
-pub struct AdpcmStreamDecoder {
+///
+/// The same applies to the ADPCM audio tags of an FLV file, so `P` is any source of packets.
+pub struct AdpcmStreamDecoder<P: Iterator> {
     format: SoundFormat,
-    tag_reader: StreamTagReader,
-    decoder: AdpcmDecoder<Cursor<SwfSlice>>,
+    packets: P,
+    decoder: AdpcmDecoder<Cursor<P::Item>>,
 }
 
-impl AdpcmStreamDecoder {
-    fn new(stream_info: &swf::SoundStreamHead, swf_data: SwfSlice) -> Result<Self, Error> {
-        let movie = swf_data.movie.clone();
-        let mut tag_reader = StreamTagReader::new(stream_info, swf_data);
-        let audio_data = tag_reader.next().unwrap_or_else(|| SwfSlice::empty(movie));
+impl<P: Iterator> AdpcmStreamDecoder<P>
+where
+    P::Item: AsRef<[u8]>,
+{
+    fn new(format: &SoundFormat, mut packets: P) -> Result<Self, Error> {
+        let audio_data = packets.next().ok_or(adpcm::Error::Invalid)?;
         let decoder = AdpcmDecoder::new(
             Cursor::new(audio_data),
-            stream_info.stream_format.is_stereo,
-            stream_info.stream_format.sample_rate,
+            format.is_stereo,
+            format.sample_rate,
         )?;
         Ok(Self {
-            format: stream_info.stream_format.clone(),
-            tag_reader,
+            format: format.clone(),
+            packets,
             decoder,
         })
     }
 }
 
-impl Decoder for AdpcmStreamDecoder {
+impl<P: Iterator + Send + Sync> Decoder for AdpcmStreamDecoder<P>
+where
+    P::Item: AsRef<[u8]> + Send + Sync,
+{
     fn num_channels(&self) -> u8 {
         self.decoder.num_channels()
     }
@@ -176,7 +266,10 @@ impl Decoder for AdpcmStreamDecoder {
     }
 }
 
-impl Iterator for AdpcmStreamDecoder {
+impl<P: Iterator> Iterator for AdpcmStreamDecoder<P>
+where
+    P::Item: AsRef<[u8]>,
+{
     type Item = [i16; 2];
 
     #[inline]
@@ -185,7 +278,7 @@ impl Iterator for AdpcmStreamDecoder {
             // Return sample frames until the decoder has exhausted
             // the SoundStreamBlock tag.
             Some(sample_frame)
-        } else if let Some(audio_data) = self.tag_reader.next() {
+        } else if let Some(audio_data) = self.packets.next() {
             // We've reached the end of the sound stream block tag, so
             // read the next one and recreate the decoder.
             // `AdpcmDecoder` read the ADPCM header when it is created.
@@ -204,17 +297,27 @@ impl Iterator for AdpcmStreamDecoder {
 }
 
 /// Makes a `StreamDecoder` for the given stream. `swf_data` should be the MovieClip's tag data.
-/// Generally this will return a `StandardStreamDecoder`, except for ADPCM streams.
+/// Generally this will return a `StandardStreamDecoder`, except for ADPCM and Speex streams.
 pub fn make_stream_decoder(
     stream_info: &swf::SoundStreamHead,
     swf_data: SwfSlice,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> =
-        if stream_info.stream_format.compression == AudioCompression::Adpcm {
-            Box::new(AdpcmStreamDecoder::new(stream_info, swf_data)?)
-        } else {
-            Box::new(StandardStreamDecoder::new(stream_info, swf_data)?)
-        };
+    let decoder: Box<dyn Decoder + Send> = match stream_info.stream_format.compression {
+        AudioCompression::Adpcm => {
+            let tag_reader = StreamTagReader::new(stream_info, swf_data);
+            Box::new(AdpcmStreamDecoder::new(
+                &stream_info.stream_format,
+                tag_reader,
+            )?)
+        }
+        // Each `SoundStreamBlock` is a Speex packet, padded at the end.
+        #[cfg(feature = "speex")]
+        AudioCompression::Speex => {
+            let tag_reader = StreamTagReader::new(stream_info, swf_data);
+            Box::new(SpeexDecoder::new(tag_reader))
+        }
+        _ => Box::new(StandardStreamDecoder::new(stream_info, swf_data)?),
+    };
     Ok(decoder)
 }
 
@@ -0,0 +1,179 @@
+use crate::backend::audio::decoders::Decoder;
+use bitstream_io::{BigEndian, BitRead, BitReader};
+use symphonia::{
+    core::{
+        self, audio,
+        codecs::{self, Decoder as _},
+        errors,
+        formats::Packet,
+    },
+    default::codecs::AacDecoder as SymphoniaAacDecoder,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't decode AAC frame")]
+    FrameDecode(#[from] errors::Error),
+
+    #[error("Invalid AudioSpecificConfig")]
+    InvalidConfig,
+
+    #[error("Invalid channels")]
+    InvalidChannels,
+}
+
+/// The sample rates that an `AudioSpecificConfig` can refer to by index.
+const SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+/// Decodes raw AAC frames, one per packet, such as the audio tags of an FLV file.
+pub struct AacDecoder<P> {
+    packets: P,
+    decoder: SymphoniaAacDecoder,
+    sample_buf: audio::SampleBuffer<i16>,
+    cur_sample: usize,
+    sample_rate: u16,
+    num_channels: u8,
+}
+
+impl<P: Iterator<Item = Vec<u8>>> AacDecoder<P> {
+    /// Creates a decoder for the stream described by `audio_specific_config`, which is the
+    /// payload of the AAC sequence header that comes before the frames.
+    pub fn new(audio_specific_config: &[u8], packets: P) -> Result<Self, Error> {
+        let (sample_rate, num_channels) = read_audio_specific_config(audio_specific_config)?;
+        let channels = match num_channels {
+            1 => audio::Channels::FRONT_LEFT,
+            2 => audio::Channels::FRONT_LEFT | audio::Channels::FRONT_RIGHT,
+            _ => return Err(Error::InvalidChannels),
+        };
+
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_sample_rate(sample_rate)
+            .with_channels(channels)
+            .with_extra_data(audio_specific_config.into());
+        let decoder = SymphoniaAacDecoder::try_new(&codec_params, &Default::default())?;
+
+        Ok(Self {
+            packets,
+            decoder,
+            sample_buf: audio::SampleBuffer::new(0, audio::SignalSpec::new(sample_rate, channels)),
+            cur_sample: 0,
+            sample_rate: sample_rate.try_into().map_err(|_| Error::InvalidConfig)?,
+            num_channels,
+        })
+    }
+
+    /// Decodes the next frame into `sample_buf`.
+    ///
+    /// Returns `false` once no more packets are available.
+    fn next_frame(&mut self) -> bool {
+        self.cur_sample = 0;
+        for packet in self.packets.by_ref() {
+            let packet = Packet::new_from_boxed_slice(0, 0, 0, packet.into_boxed_slice());
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    if self.sample_buf.capacity() < decoded.capacity() {
+                        // Ensure our buffer has enough space for the decoded samples.
+                        self.sample_buf = audio::SampleBuffer::new(
+                            decoded.capacity() as core::units::Duration,
+                            *decoded.spec(),
+                        );
+                    }
+                    self.sample_buf.copy_interleaved_ref(decoded);
+                    return true;
+                }
+                // Decode errors are not fatal.
+                Err(errors::Error::DecodeError(_)) => (),
+                Err(_) => break,
+            }
+        }
+        false
+    }
+}
+
+impl<P: Iterator<Item = Vec<u8>>> Iterator for AacDecoder<P> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur_sample >= self.sample_buf.len() {
+            if !self.next_frame() {
+                return None;
+            }
+        }
+
+        let sample_buf = self.sample_buf.samples();
+        if self.num_channels == 2 {
+            let samples: [i16; 2] = [sample_buf[self.cur_sample], sample_buf[self.cur_sample + 1]];
+            self.cur_sample += 2;
+            Some(samples)
+        } else {
+            let sample = sample_buf[self.cur_sample];
+            self.cur_sample += 1;
+            Some([sample, sample])
+        }
+    }
+}
+
+impl<P: Iterator<Item = Vec<u8>> + Send + Sync> Decoder for AacDecoder<P> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        self.num_channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+/// Reads the sample rate and the number of channels from an `AudioSpecificConfig`
+/// (ISO 14496-3 1.6.2.1).
+fn read_audio_specific_config(config: &[u8]) -> Result<(u32, u8), Error> {
+    let mut reader = BitReader::endian(config, BigEndian);
+    let mut read = |bits| reader.read::<u32>(bits).map_err(|_| Error::InvalidConfig);
+
+    let object_type = read(5)?;
+    if object_type == 31 {
+        // Escape value for the extended object types.
+        read(6)?;
+    }
+    let sample_rate = match read(4)? {
+        0xF => read(24)?,
+        index => *SAMPLE_RATES
+            .get(index as usize)
+            .ok_or(Error::InvalidConfig)?,
+    };
+    let num_channels = read(4)? as u8;
+
+    Ok((sample_rate, num_channels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_config() {
+        // AAC LC, 44.1 kHz, stereo.
+        assert_eq!(
+            read_audio_specific_config(&[0x12, 0x10]).unwrap(),
+            (44100, 2)
+        );
+        // AAC LC, 22.05 kHz, mono.
+        assert_eq!(
+            read_audio_specific_config(&[0x13, 0x88]).unwrap(),
+            (22050, 1)
+        );
+        // Explicit sample rate of 12345 Hz, mono.
+        assert_eq!(
+            read_audio_specific_config(&[0x17, 0x80, 0x18, 0x1C, 0x88]).unwrap(),
+            (12345, 1)
+        );
+        assert!(read_audio_specific_config(&[0x12]).is_err());
+    }
+}
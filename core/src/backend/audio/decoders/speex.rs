@@ -0,0 +1,93 @@
+use super::{Decoder, SeekableDecoder};
+use std::iter::Once;
+
+/// Decodes Speex audio. Flash Player always uses wideband Speex at 16kHz.
+///
+/// The audio is read from packets, such as the `SoundStreamBlock` tags of a stream sound or the
+/// audio tags of an FLV file. Each may hold several frames, and ends with padding.
+pub struct SpeexDecoder<P: Iterator> {
+    decoder: speex_rs::Decoder<P>,
+}
+
+impl<P: Iterator> SpeexDecoder<P>
+where
+    P::Item: AsRef<[u8]>,
+{
+    pub fn new(packets: P) -> Self {
+        Self {
+            decoder: speex_rs::Decoder::new(packets, speex_rs::Mode::Wideband),
+        }
+    }
+}
+
+impl<P: Iterator> Iterator for SpeexDecoder<P>
+where
+    P::Item: AsRef<[u8]>,
+{
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.decoder.next()?;
+        Some([sample, sample])
+    }
+}
+
+impl<P: Iterator + Send + Sync> Decoder for SpeexDecoder<P>
+where
+    P::Item: AsRef<[u8]> + Send + Sync,
+{
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.decoder.sample_rate() as u16
+    }
+}
+
+/// Decodes the Speex audio of an event sound, which is stored as a single packet.
+pub struct SpeexEventDecoder<R> {
+    data: R,
+    decoder: SpeexDecoder<Once<R>>,
+}
+
+impl<R: AsRef<[u8]> + Clone> SpeexEventDecoder<R> {
+    pub fn new(data: R) -> Self {
+        Self {
+            decoder: SpeexDecoder::new(std::iter::once(data.clone())),
+            data,
+        }
+    }
+}
+
+impl<R: AsRef<[u8]>> Iterator for SpeexEventDecoder<R> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.next()
+    }
+}
+
+impl<R: AsRef<[u8]> + Send + Sync> Decoder for SpeexEventDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        self.decoder.num_channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.decoder.sample_rate()
+    }
+}
+
+impl<R: AsRef<[u8]> + Clone + Send + Sync> SeekableDecoder for SpeexEventDecoder<R> {
+    #[inline]
+    fn reset(&mut self) {
+        self.decoder
+            .decoder
+            .reset(std::iter::once(self.data.clone()));
+    }
+}
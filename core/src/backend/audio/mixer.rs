@@ -1,9 +1,11 @@
 use super::decoders::{self, AdpcmDecoder, Decoder, PcmDecoder, SeekableDecoder};
-use super::{SoundHandle, SoundInstanceHandle, SoundTransform};
+use super::{SoundHandle, SoundInstanceHandle, SoundTransform, Substream, SubstreamFormat};
 use crate::backend::audio::{DecodeError, RegisterError};
 use crate::tag_utils::SwfSlice;
 use generational_arena::Arena;
+use std::collections::{HashMap, VecDeque};
 use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
 
@@ -69,6 +71,13 @@ pub struct AudioMixer {
 
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
+
+    /// The sample queues of the playing dynamic sound instances.
+    dynamic_sounds: HashMap<SoundInstanceHandle, DynamicSoundQueue>,
+
+    /// The streams that `Sound.extract` decodes sounds with, so that extracting the
+    /// samples of a sound block by block doesn't decode it from the start every time.
+    extract_streams: HashMap<SoundHandle, ExtractStream>,
 }
 
 /// An audio stream.
@@ -242,6 +251,8 @@ impl AudioMixer {
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
+            dynamic_sounds: HashMap::new(),
+            extract_streams: HashMap::new(),
         }
     }
 
@@ -323,13 +334,22 @@ impl AudioMixer {
                 data,
                 format.sample_rate.into(),
             )),
+            #[cfg(feature = "speex")]
+            AudioCompression::Speex => {
+                Box::new(decoders::SpeexEventDecoder::new(data.into_inner()))
+            }
             _ => return Err(decoders::Error::UnhandledCompression(format.compression)),
         };
         Ok(decoder)
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::resample(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn resample(mut stream: impl Stream, output_sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
@@ -339,7 +359,7 @@ impl AudioMixer {
             stream,
             interpolator,
             sample_rate,
-            self.output_sample_rate.into(),
+            output_sample_rate.into(),
         ))
     }
 
@@ -554,6 +574,26 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a sound that plays the packets appended to a substream.
+    ///
+    /// The sound ends once it runs out of packets.
+    pub fn start_substream(
+        &mut self,
+        substream: Substream,
+        format: &SubstreamFormat,
+    ) -> Result<SoundInstanceHandle, DecodeError> {
+        let decoder = decoders::make_substream_decoder(format, substream)?;
+        let stream = DecoderStream::new(decoder);
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_stream(stream));
+        Ok(handle)
+    }
+
     /// Starts a sound.
     ///
     /// The sound must have been registered using `AudioMixer::register_sound`.
@@ -594,6 +634,7 @@ impl AudioMixer {
             .lock()
             .expect("Cannot be called reentrant");
         sound_instances.remove(sound);
+        self.dynamic_sounds.remove(&sound);
     }
 
     pub fn stop_all_sounds(&mut self) {
@@ -609,6 +650,138 @@ impl AudioMixer {
             sound_instances.remove(i);
         }
         sound_instances.clear();
+        self.dynamic_sounds.clear();
+    }
+
+    /// Starts a sound whose 44.1 kHz stereo sample frames are generated at runtime.
+    ///
+    /// The samples are queued with `AudioMixer::append_dynamic_samples`. Silence is played
+    /// whenever the queue runs dry, until the last samples have been appended.
+    pub fn start_dynamic_sound(&mut self) -> SoundInstanceHandle {
+        let samples = Arc::new(DynamicSamples::default());
+        let stream = DynamicStream::new(Arc::clone(&samples));
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        // Forget the queues of the dynamic sounds that have finished playing.
+        self.dynamic_sounds
+            .retain(|instance, _| sound_instances.contains(*instance));
+        let handle = sound_instances.insert(SoundInstance::new_stream(stream));
+        self.dynamic_sounds.insert(
+            handle,
+            DynamicSoundQueue {
+                samples,
+                num_appended: 0,
+            },
+        );
+        handle
+    }
+
+    /// Queues sample frames to be played by a dynamic sound instance.
+    ///
+    /// If `is_last` is set, the sound ends once the queued samples have been played.
+    pub fn append_dynamic_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_last: bool,
+    ) {
+        use dasp::Sample;
+
+        if let Some(queue) = self.dynamic_sounds.get_mut(&instance) {
+            let mut pending = queue
+                .samples
+                .pending
+                .lock()
+                .expect("Cannot be called reentrant");
+            pending.frames.extend(
+                samples
+                    .iter()
+                    .map(|[left, right]| [left.to_sample(), right.to_sample()]),
+            );
+            pending.is_finished |= is_last;
+            queue.num_appended += samples.len();
+        }
+    }
+
+    /// Returns the number of sample frames queued for a dynamic sound instance that
+    /// have not been played yet.
+    ///
+    /// Returns `None` if the sound is no longer playing.
+    pub fn dynamic_samples_queued(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        if !sound_instances.contains(instance) {
+            return None;
+        }
+        let queue = self.dynamic_sounds.get(&instance)?;
+        let num_played = queue.samples.num_played.load(Ordering::Relaxed);
+        Some(queue.num_appended.saturating_sub(num_played))
+    }
+
+    /// Decodes up to `num_frames` sample frames of a registered sound, starting at
+    /// `start_frame`. The samples are resampled to 44.1 kHz stereo, which is also the rate
+    /// that `start_frame` is given in.
+    ///
+    /// Decoding continues from where the last call for the same sound stopped, unless
+    /// `start_frame` is elsewhere.
+    pub fn extract_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        start_frame: u32,
+        num_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        use dasp::Sample;
+
+        let sound = match self.sounds.get(sound_handle) {
+            Some(sound) => sound,
+            None => return Ok(vec![]),
+        };
+        let is_at_start_frame = matches!(
+            self.extract_streams.get(&sound_handle),
+            Some(extract) if extract.position == start_frame
+        );
+        if !is_at_start_frame {
+            let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+            let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+            let settings = swf::SoundInfo {
+                event: swf::SoundEvent::Start,
+                in_sample: Some(start_frame),
+                out_sample: None,
+                num_loops: 1,
+                envelope: None,
+            };
+            let stream = EventSoundStream::new_with_settings(
+                decoder,
+                &settings,
+                sound.num_sample_frames,
+                sound.skip_sample_frames,
+            );
+            self.extract_streams.insert(
+                sound_handle,
+                ExtractStream {
+                    stream: Box::new(Self::resample(stream, 44100)),
+                    position: start_frame,
+                },
+            );
+        }
+        let extract = self
+            .extract_streams
+            .get_mut(&sound_handle)
+            .expect("Extract stream should exist");
+
+        let mut frames = vec![];
+        while frames.len() < num_frames && !extract.stream.is_exhausted() {
+            let [left, right] = extract.stream.next();
+            frames.push([left.to_sample(), right.to_sample()]);
+        }
+        extract.position += frames.len() as u32;
+        Ok(frames)
     }
 
     /// Returns the position of a playing sound in milliseconds.
@@ -734,6 +907,7 @@ impl AudioMixerProxy {
 
 /// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>>`.
 /// Not having this trait causes problems when trying to use `Cursor<Vec<u8>>`.
+#[derive(Clone)]
 struct ArcAsRef(Arc<[u8]>);
 
 impl AsRef<[u8]> for ArcAsRef {
@@ -846,6 +1020,132 @@ impl Stream for EventSoundStream {
     }
 }
 
+/// The sample frames queued for a dynamic sound instance.
+///
+/// These are shared between the main thread, which appends to them, and the audio thread,
+/// which plays them.
+#[derive(Default)]
+struct DynamicSamples {
+    /// The frames that were appended since the audio thread last took them.
+    pending: Mutex<PendingSamples>,
+
+    /// The number of sample frames that the audio thread has played.
+    num_played: AtomicUsize,
+}
+
+#[derive(Default)]
+struct PendingSamples {
+    /// The 44.1 kHz sample frames that have not been taken by the audio thread yet.
+    frames: Vec<[i16; 2]>,
+
+    /// Whether no more samples will be appended, so that the sound ends once all frames
+    /// have been played.
+    is_finished: bool,
+}
+
+/// The main thread's end of the sample queue of a dynamic sound instance.
+struct DynamicSoundQueue {
+    samples: Arc<DynamicSamples>,
+
+    /// The number of sample frames appended so far.
+    num_appended: usize,
+}
+
+/// A stream that plays sample frames generated at runtime, such as by ActionScript
+/// `sampleData` handlers.
+///
+/// The stream takes all the pending frames of its queue at once, so that the queue is
+/// only locked when the frames it took before have all been played.
+struct DynamicStream {
+    samples: Arc<DynamicSamples>,
+
+    /// The frames taken from the queue.
+    frames: Vec<[i16; 2]>,
+
+    /// The index in `frames` of the next frame to play.
+    next_frame: usize,
+
+    /// Whether the frames taken last were the last ones of the sound.
+    is_finished: bool,
+
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl DynamicStream {
+    fn new(samples: Arc<DynamicSamples>) -> Self {
+        Self {
+            samples,
+            frames: vec![],
+            next_frame: 0,
+            is_finished: false,
+            position: 0,
+            is_exhausted: false,
+        }
+    }
+
+    /// Swap the frames that have been played for the pending frames of the queue.
+    ///
+    /// This never blocks the audio thread: if the main thread is appending frames, they
+    /// are taken on a later call instead.
+    fn take_pending_frames(&mut self) {
+        if let Ok(mut pending) = self.samples.pending.try_lock() {
+            self.frames.clear();
+            std::mem::swap(&mut self.frames, &mut pending.frames);
+            self.next_frame = 0;
+            self.is_finished = pending.is_finished;
+        }
+    }
+}
+
+impl dasp::signal::Signal for DynamicStream {
+    type Frame = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Self::Frame {
+        if self.next_frame >= self.frames.len() {
+            self.take_pending_frames();
+        }
+
+        if let Some(&frame) = self.frames.get(self.next_frame) {
+            self.next_frame += 1;
+            self.position += 1;
+            self.samples
+                .num_played
+                .store(self.position as usize, Ordering::Relaxed);
+            frame
+        } else {
+            // Play silence until more samples are appended.
+            self.is_exhausted = self.is_finished;
+            [0, 0]
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
+/// A stream that `Sound.extract` decodes a sound with, and the 44.1 kHz sample frame
+/// it's at.
+struct ExtractStream {
+    stream: Box<dyn Stream>,
+    position: u32,
+}
+
+impl Stream for DynamicStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        44100
+    }
+}
+
 /// A stream that converts a source stream to a different sample rate.
 struct ConverterStream<S, I>(dasp::signal::interpolate::Converter<S, I>)
 where
@@ -1062,6 +1362,15 @@ macro_rules! impl_audio_mixer_backend {
                 .start_stream(stream_handle, clip_frame, clip_data, stream_info)
         }
 
+        #[inline]
+        fn start_substream(
+            &mut self,
+            substream: $crate::backend::audio::Substream,
+            format: &$crate::backend::audio::SubstreamFormat,
+        ) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_substream(substream, format)
+        }
+
         #[inline]
         fn start_sound(
             &mut self,
@@ -1128,5 +1437,36 @@ macro_rules! impl_audio_mixer_backend {
         fn get_sample_history(&self) -> [[f32; 2]; 1024] {
             self.$mixer.get_sample_history()
         }
+
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> SoundInstanceHandle {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_samples(
+            &mut self,
+            instance: SoundInstanceHandle,
+            samples: &[[f32; 2]],
+            is_last: bool,
+        ) {
+            self.$mixer
+                .append_dynamic_samples(instance, samples, is_last)
+        }
+
+        #[inline]
+        fn dynamic_samples_queued(&self, instance: SoundInstanceHandle) -> Option<usize> {
+            self.$mixer.dynamic_samples_queued(instance)
+        }
+
+        #[inline]
+        fn extract_sound(
+            &mut self,
+            sound: SoundHandle,
+            start_frame: u32,
+            num_frames: usize,
+        ) -> Result<Vec<[f32; 2]>, DecodeError> {
+            self.$mixer.extract_sound(sound, start_frame, num_frames)
+        }
     };
 }
@@ -0,0 +1,43 @@
+/// The contents of a TrueType or OpenType font file.
+pub struct FontFile {
+    /// The raw file data. This may be a font collection (TTC/OTC).
+    pub data: Vec<u8>,
+
+    /// The index of the face to use within a font collection, or 0 for a
+    /// single font file.
+    pub index: u32,
+}
+
+/// A backend that provides the system fonts used to render device text.
+pub trait FontBackend {
+    /// Find the font best matching the given family name and style.
+    ///
+    /// The name is the family name requested by the movie, such as "Arial".
+    /// The generic `_sans`, `_serif` and `_typewriter` names are resolved by
+    /// the player before calling this. If no face of the family has the
+    /// requested style, another face of the same family may be returned,
+    /// but a different family should not be substituted.
+    fn load_font(&mut self, name: &str, is_bold: bool, is_italic: bool) -> Option<FontFile>;
+}
+
+/// Font backend that provides no fonts, leaving only the embedded device
+/// font for device text.
+pub struct NullFontBackend {}
+
+impl NullFontBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FontBackend for NullFontBackend {
+    fn load_font(&mut self, _name: &str, _is_bold: bool, _is_italic: bool) -> Option<FontFile> {
+        None
+    }
+}
+
+impl Default for NullFontBackend {
+    fn default() -> Self {
+        NullFontBackend::new()
+    }
+}
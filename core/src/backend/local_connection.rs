@@ -0,0 +1,227 @@
+//! Delivery of `LocalConnection` messages between players
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A method call sent over a `LocalConnection`.
+#[derive(Clone, Debug)]
+pub struct LocalConnectionMessage {
+    /// The fully qualified name of the receiving connection.
+    pub connection_name: String,
+
+    /// The name of the method to call on the receiver.
+    pub method_name: String,
+
+    /// The arguments of the call, encoded as an AMF body with one element
+    /// per argument.
+    pub arguments: Vec<u8>,
+
+    /// The domain of the movie that sent the message.
+    pub sender_domain: String,
+
+    /// Whether the movie that sent the message was loaded over HTTPS.
+    pub sender_secure: bool,
+}
+
+/// A backend that connects the `LocalConnection`s of a player to those of
+/// other players.
+///
+/// Connections between movies of the same player never go through the
+/// backend; it's only asked about the names that the player itself doesn't
+/// have a connection for.
+pub trait LocalConnectionBackend {
+    /// Claim a connection name for this player.
+    ///
+    /// Returns `false` if another player is already connected with that name.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Release a connection name claimed with `connect`.
+    fn close(&mut self, name: &str);
+
+    /// Send a message to the player that is connected with the name of the
+    /// message.
+    ///
+    /// Returns `false` if no other player is connected with that name.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool;
+
+    /// Take the messages that other players sent to this player.
+    fn receive(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+/// Local connection backend for a player that can't talk to other players.
+pub struct NullLocalConnectionBackend {}
+
+impl NullLocalConnectionBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for NullLocalConnectionBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalConnectionBackend for NullLocalConnectionBackend {
+    fn connect(&mut self, _name: &str) -> bool {
+        true
+    }
+
+    fn close(&mut self, _name: &str) {}
+
+    fn send(&mut self, _message: LocalConnectionMessage) -> bool {
+        false
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        vec![]
+    }
+}
+
+#[derive(Default)]
+struct LocalConnectionHubData {
+    /// The player that claimed each connection name.
+    names: HashMap<String, usize>,
+
+    /// The messages waiting to be received by each player.
+    inboxes: HashMap<usize, Vec<LocalConnectionMessage>>,
+
+    next_id: usize,
+}
+
+/// Exchanges `LocalConnection` messages between the players of one process.
+///
+/// Each player is given its own backend with `backend`.
+#[derive(Clone, Default)]
+pub struct LocalConnectionHub(Arc<Mutex<LocalConnectionHubData>>);
+
+impl LocalConnectionHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the backend of a new player connected to this hub.
+    pub fn backend(&self) -> HubLocalConnectionBackend {
+        let mut hub = self.0.lock().expect("Local connection hub is poisoned");
+        let id = hub.next_id;
+        hub.next_id += 1;
+        hub.inboxes.insert(id, vec![]);
+
+        HubLocalConnectionBackend {
+            hub: self.clone(),
+            id,
+        }
+    }
+}
+
+/// The backend of a player connected to a `LocalConnectionHub`.
+pub struct HubLocalConnectionBackend {
+    hub: LocalConnectionHub,
+
+    /// The ID of the player within the hub.
+    id: usize,
+}
+
+impl LocalConnectionBackend for HubLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        let mut hub = self.hub.0.lock().expect("Local connection hub is poisoned");
+        match hub.names.get(name) {
+            Some(id) => *id == self.id,
+            None => {
+                hub.names.insert(name.to_owned(), self.id);
+                true
+            }
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        let mut hub = self.hub.0.lock().expect("Local connection hub is poisoned");
+        if hub.names.get(name) == Some(&self.id) {
+            hub.names.remove(name);
+        }
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        let mut hub = self.hub.0.lock().expect("Local connection hub is poisoned");
+        let receiver = match hub.names.get(&message.connection_name) {
+            Some(receiver) if *receiver != self.id => *receiver,
+            _ => return false,
+        };
+
+        match hub.inboxes.get_mut(&receiver) {
+            Some(inbox) => {
+                inbox.push(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        let mut hub = self.hub.0.lock().expect("Local connection hub is poisoned");
+        hub.inboxes
+            .get_mut(&self.id)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for HubLocalConnectionBackend {
+    fn drop(&mut self) {
+        // Release the names of a player that goes away.
+        if let Ok(mut hub) = self.hub.0.lock() {
+            let id = self.id;
+            hub.names.retain(|_, owner| *owner != id);
+            hub.inboxes.remove(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection_name: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection_name: connection_name.to_owned(),
+            method_name: "method".to_owned(),
+            arguments: vec![],
+            sender_domain: "localhost".to_owned(),
+            sender_secure: false,
+        }
+    }
+
+    #[test]
+    fn hub_delivers_between_players() {
+        let hub = LocalConnectionHub::new();
+        let mut sender = hub.backend();
+        let mut receiver = hub.backend();
+
+        assert!(receiver.connect("_test"));
+        assert!(!sender.connect("_test"));
+
+        assert!(sender.send(message("_test")));
+        assert!(!sender.send(message("_other")));
+        assert!(!receiver.send(message("_test")));
+
+        let received = receiver.receive();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].connection_name, "_test");
+        assert!(sender.receive().is_empty());
+    }
+
+    #[test]
+    fn hub_releases_names() {
+        let hub = LocalConnectionHub::new();
+        let mut first = hub.backend();
+        let mut second = hub.backend();
+
+        assert!(first.connect("_test"));
+        first.close("_test");
+        assert!(second.connect("_test"));
+
+        drop(second);
+        assert!(first.connect("_test"));
+    }
+}
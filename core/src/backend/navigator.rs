@@ -1,11 +1,14 @@
 //! Browser-related platform functions
 
 use crate::loader::Error;
+use crate::socket::{ConnectionState, SocketAction, SocketHandle};
 use crate::string::WStr;
 use indexmap::IndexMap;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
 use swf::avm1::types::SendVarsMethod;
 use url::Url;
 
@@ -40,6 +43,71 @@ impl NavigationMethod {
     }
 }
 
+/// The request headers that Flash doesn't allow movies to set.
+const FORBIDDEN_REQUEST_HEADERS: &[&str] = &[
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Ranges",
+    "Age",
+    "Allow",
+    "Allowed",
+    "Authorization",
+    "Charge-To",
+    "Connect",
+    "Connection",
+    "Content-Length",
+    "Content-Location",
+    "Content-Range",
+    "Cookie",
+    "Date",
+    "Delete",
+    "ETag",
+    "Expect",
+    "Get",
+    "Head",
+    "Host",
+    "If-Modified-Since",
+    "Keep-Alive",
+    "Last-Modified",
+    "Location",
+    "Max-Forwards",
+    "Options",
+    "Origin",
+    "Post",
+    "Proxy-Authenticate",
+    "Proxy-Authorization",
+    "Proxy-Connection",
+    "Public",
+    "Put",
+    "Range",
+    "Referer",
+    "Request-Range",
+    "Retry-After",
+    "Server",
+    "TE",
+    "Trace",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "URI",
+    "User-Agent",
+    "Vary",
+    "Via",
+    "Warning",
+    "WWW-Authenticate",
+    "x-flash-version",
+];
+
+/// Whether a movie may send a request header called `name`.
+///
+/// Flash doesn't let movies set the headers that the browser controls,
+/// whatever the case of their name.
+pub fn is_request_header_allowed(name: &str) -> bool {
+    !FORBIDDEN_REQUEST_HEADERS
+        .iter()
+        .any(|forbidden| forbidden.eq_ignore_ascii_case(name))
+}
+
 /// A fetch request.
 pub struct Request {
     /// The URL of the request.
@@ -53,6 +121,9 @@ pub struct Request {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// The headers to send with the request, as name and value pairs.
+    headers: Vec<(String, String)>,
 }
 
 impl Request {
@@ -62,6 +133,7 @@ impl Request {
             url,
             method: NavigationMethod::Get,
             body: None,
+            headers: Vec::new(),
         }
     }
 
@@ -71,13 +143,19 @@ impl Request {
             url,
             method: NavigationMethod::Post,
             body,
+            headers: Vec::new(),
         }
     }
 
     /// Construct a request with the given method and data
     #[allow(clippy::self_named_constructors)]
     pub fn request(method: NavigationMethod, url: String, body: Option<(Vec<u8>, String)>) -> Self {
-        Self { url, method, body }
+        Self {
+            url,
+            method,
+            body,
+            headers: Vec::new(),
+        }
     }
 
     /// Retrieve the URL of this request.
@@ -98,6 +176,16 @@ impl Request {
     pub fn set_body(&mut self, body: (Vec<u8>, String)) {
         self.body = Some(body);
     }
+
+    /// Retrieve the headers of this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Add a header to this request.
+    pub fn add_header(&mut self, name: String, value: String) {
+        self.headers.push((name, value));
+    }
 }
 
 /// A response to a fetch request.
@@ -105,10 +193,96 @@ pub struct Response {
     /// The final URL obtained after any redirects.
     pub url: String,
 
+    /// The HTTP status code of the response, or 0 if the request wasn't made
+    /// over HTTP (e.g. for local files).
+    pub status: u16,
+
+    /// Whether the request was redirected to `url`.
+    pub redirected: bool,
+
+    /// The headers of the response, as name and value pairs.
+    pub headers: Vec<(String, String)>,
+
     /// The contents of the response body.
     pub body: Vec<u8>,
 }
 
+/// The body of a response, handed over in chunks as it is downloaded.
+pub trait ResponseBody {
+    /// Wait for the next chunk of the body to arrive.
+    ///
+    /// Resolves to `None` once the whole body has been received.
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error>;
+}
+
+/// A response to a fetch request, whose body is still being downloaded.
+pub struct StreamingResponse {
+    /// The final URL obtained after any redirects.
+    pub url: String,
+
+    /// The HTTP status code of the response, or 0 if the request wasn't made
+    /// over HTTP (e.g. for local files).
+    pub status: u16,
+
+    /// Whether the request was redirected to `url`.
+    pub redirected: bool,
+
+    /// The headers of the response, as name and value pairs.
+    pub headers: Vec<(String, String)>,
+
+    /// The contents of the response body.
+    pub body: Box<dyn ResponseBody>,
+}
+
+impl StreamingResponse {
+    /// The size of the body announced by the `Content-Length` header, if any.
+    pub fn content_length(&self) -> Option<usize> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse().ok())
+    }
+
+    /// Wait for the rest of the body to be downloaded.
+    pub async fn into_response(mut self) -> Result<Response, Error> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.body.next_chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(Response {
+            url: self.url,
+            status: self.status,
+            redirected: self.redirected,
+            headers: self.headers,
+            body,
+        })
+    }
+}
+
+impl From<Response> for StreamingResponse {
+    /// Hand over the body of an already downloaded response as a single chunk.
+    fn from(response: Response) -> Self {
+        Self {
+            url: response.url,
+            status: response.status,
+            redirected: response.redirected,
+            headers: response.headers,
+            body: Box::new(CompleteBody(Some(response.body))),
+        }
+    }
+}
+
+/// The body of a response that has been downloaded in full.
+struct CompleteBody(Option<Vec<u8>>);
+
+impl ResponseBody for CompleteBody {
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let chunk = self.0.take();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
 /// Type alias for pinned, boxed, and owned futures that output a falliable
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
@@ -147,6 +321,16 @@ pub trait NavigatorBackend {
     /// Fetch data and return it some time in the future.
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error>;
 
+    /// Fetch data, and return the response as soon as its body starts arriving.
+    ///
+    /// This is used by media that can be played while it downloads, such as
+    /// `NetStream` videos. Backends that can't download progressively may hand
+    /// over the whole body at once, which is what the default implementation does.
+    fn fetch_streaming(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
+        let fetch = self.fetch(request);
+        Box::pin(async move { Ok(fetch.await?.into()) })
+    }
+
     /// Arrange for a future to be run at some point in the... well, future.
     ///
     /// This function must be called to ensure a future is actually computed.
@@ -162,6 +346,32 @@ pub trait NavigatorBackend {
     /// Changing http -> https for example. This function may alter any part of the
     /// URL (generally only if configured to do so by the user).
     fn pre_process_url(&self, url: Url) -> Url;
+
+    /// Open a socket connection to `host:port`.
+    ///
+    /// The outcome of the connection attempt, received data and the server
+    /// closing the connection must be reported on `sender` with the given
+    /// `handle`. Data to send arrives on `receiver`; the connection should be
+    /// closed once all senders of `receiver` have been dropped.
+    ///
+    /// Backends that can't open sockets may leave this out; the default
+    /// implementation reports that the connection failed.
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+        handle: SocketHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        tracing::warn!(
+            "Can't connect to socket {}:{}, as sockets aren't supported",
+            host,
+            port
+        );
+        let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -297,7 +507,13 @@ impl NavigatorBackend for NullNavigatorBackend {
 
             let body = std::fs::read(path).map_err(|e| Error::FetchError(e.to_string()))?;
 
-            Ok(Response { url, body })
+            Ok(Response {
+                url,
+                status: 0,
+                redirected: false,
+                headers: Vec::new(),
+                body,
+            })
         })
     }
 
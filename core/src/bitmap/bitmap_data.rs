@@ -43,6 +43,13 @@ impl LehmerRng {
     }
 }
 
+/// Feedback masks of maximal-length Galois LFSRs, indexed by their number of bits.
+/// Used by `pixel_dissolve`, which only needs enough bits to cover the largest possible bitmap.
+const LFSR_TAPS: [u32; 25] = [
+    0, 0, 0x3, 0x6, 0xC, 0x14, 0x30, 0x60, 0xB8, 0x110, 0x240, 0x500, 0xE08, 0x1C80, 0x3802,
+    0x6000, 0xD008, 0x12000, 0x20400, 0x72000, 0x90000, 0x140000, 0x300000, 0x420000, 0xE10000,
+];
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Collect)]
 #[collect(no_drop)]
 pub struct Color(i32);
@@ -579,6 +586,35 @@ impl<'gc> BitmapData<'gc> {
         Ok(result)
     }
 
+    /// Copies the given area into a `Bitmap` with unmultiplied alpha, for encoding to an image file.
+    ///
+    /// Opaque bitmaps produce an RGB bitmap, and transparent ones an RGBA bitmap.
+    pub fn to_unmultiplied_bitmap(&self, x: i32, y: i32, width: i32, height: i32) -> Bitmap {
+        let x0 = x.max(0) as u32;
+        let y0 = y.max(0) as u32;
+        let x1 = (x + width).clamp(0, self.width as i32) as u32;
+        let y1 = (y + height).clamp(0, self.height as i32) as u32;
+        let (width, height) = (x1.saturating_sub(x0), y1.saturating_sub(y0));
+
+        let format = if self.transparency {
+            BitmapFormat::Rgba
+        } else {
+            BitmapFormat::Rgb
+        };
+        let mut data = Vec::with_capacity((width * height) as usize * format.bytes_per_pixel());
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let color = self.pixels[(x + y * self.width) as usize].to_un_multiplied_alpha();
+                data.extend_from_slice(&[color.red(), color.green(), color.blue()]);
+                if self.transparency {
+                    data.push(color.alpha());
+                }
+            }
+        }
+
+        Bitmap::new(width, height, format, data)
+    }
+
     pub fn get_vector(&self, x: i32, y: i32, width: i32, height: i32) -> Vec<Avm2Value<'gc>> {
         let x0 = x.max(0) as u32;
         let y0 = y.max(0) as u32;
@@ -1035,6 +1071,164 @@ impl<'gc> BitmapData<'gc> {
         }
     }
 
+    /// Replaces `num_pixels` pseudo-randomly chosen pixels in `src_rect` with the pixels of
+    /// `source_bitmap`, or with `fill_color` if `source_bitmap` is `None` (meaning `self`).
+    ///
+    /// Returns the seed to pass to the next call to continue the dissolve.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pixel_dissolve(
+        &mut self,
+        source_bitmap: Option<&Self>,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        random_seed: i32,
+        num_pixels: i32,
+        fill_color: Color,
+    ) -> i32 {
+        let (src_x, src_y, src_width, src_height) = src_rect;
+        let (dest_min_x, dest_min_y) = dest_point;
+
+        // Only pixels that are inside of the source bitmap can be picked.
+        let source_width = source_bitmap.unwrap_or(self).width() as i32;
+        let source_height = source_bitmap.unwrap_or(self).height() as i32;
+        let src_min_x = src_x.max(0);
+        let src_min_y = src_y.max(0);
+        let src_width = (src_x + src_width).min(source_width) - src_min_x;
+        let src_height = (src_y + src_height).min(source_height) - src_min_y;
+        if src_width <= 0 || src_height <= 0 {
+            return random_seed;
+        }
+
+        let area = src_width as u32 * src_height as u32;
+        let fill_color = fill_color.to_premultiplied_alpha(self.transparency());
+
+        // The pixels are visited in the order of a maximal-length Galois LFSR, which goes
+        // through every state in `1..2^bits` exactly once before repeating.
+        let bits = (u32::BITS - area.leading_zeros()).clamp(2, LFSR_TAPS.len() as u32 - 1);
+        let taps = LFSR_TAPS[bits as usize];
+        let state_mask = (1u32 << bits) - 1;
+        let num_pixels = (num_pixels.max(0) as u32).min(area.min(state_mask));
+        let mut state = random_seed as u32 & state_mask;
+        if state == 0 {
+            state = 1;
+        }
+
+        let mut dissolved = 0;
+        while dissolved < num_pixels {
+            let index = state - 1;
+            let lsb = state & 1;
+            state >>= 1;
+            if lsb != 0 {
+                state ^= taps;
+            }
+            if index >= area {
+                continue;
+            }
+            dissolved += 1;
+
+            let x = src_min_x + (index % src_width as u32) as i32;
+            let y = src_min_y + (index / src_width as u32) as i32;
+            let dest_x = x - src_x + dest_min_x;
+            let dest_y = y - src_y + dest_min_y;
+            if !self.is_point_in_bounds(dest_x, dest_y) {
+                continue;
+            }
+
+            let color = match source_bitmap {
+                Some(source_bitmap) => source_bitmap.get_pixel_raw(x as u32, y as u32).unwrap(),
+                None => fill_color,
+            };
+            self.set_pixel32_raw(dest_x as u32, dest_y as u32, color);
+        }
+
+        state as i32
+    }
+
+    /// Counts how often every value of every channel occurs in the given area.
+    ///
+    /// Returns the red, green, blue and alpha histograms, in that order.
+    pub fn histogram(&self, rect: (i32, i32, i32, i32)) -> [[u32; 256]; 4] {
+        let (x, y, width, height) = rect;
+        let x0 = x.max(0) as u32;
+        let y0 = y.max(0) as u32;
+        let x1 = (x + width).clamp(0, self.width as i32) as u32;
+        let y1 = (y + height).clamp(0, self.height as i32) as u32;
+
+        let mut histogram = [[0; 256]; 4];
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let color = self.pixels[(x + y * self.width) as usize].to_un_multiplied_alpha();
+                histogram[0][color.red() as usize] += 1;
+                histogram[1][color.green() as usize] += 1;
+                histogram[2][color.blue() as usize] += 1;
+                histogram[3][color.alpha() as usize] += 1;
+            }
+        }
+        histogram
+    }
+
+    fn is_opaque_at(&self, x: i32, y: i32, alpha_threshold: u8) -> bool {
+        self.is_point_in_bounds(x, y)
+            && self.get_pixel_raw(x as u32, y as u32).unwrap().alpha() >= alpha_threshold
+    }
+
+    /// Tests whether the pixel under `test_point` is at least `alpha_threshold` opaque,
+    /// when this bitmap is positioned at `top_left`.
+    pub fn hit_test_point(
+        &self,
+        top_left: (i32, i32),
+        alpha_threshold: u8,
+        test_point: (i32, i32),
+    ) -> bool {
+        self.is_opaque_at(
+            test_point.0 - top_left.0,
+            test_point.1 - top_left.1,
+            alpha_threshold,
+        )
+    }
+
+    /// Tests whether any pixel under `test_rect` is at least `alpha_threshold` opaque,
+    /// when this bitmap is positioned at `top_left`.
+    pub fn hit_test_rectangle(
+        &self,
+        top_left: (i32, i32),
+        alpha_threshold: u8,
+        test_rect: (i32, i32, i32, i32),
+    ) -> bool {
+        let (x, y, width, height) = test_rect;
+        let x0 = (x - top_left.0).max(0);
+        let y0 = (y - top_left.1).max(0);
+        let x1 = (x - top_left.0 + width).min(self.width as i32);
+        let y1 = (y - top_left.1 + height).min(self.height as i32);
+
+        (y0..y1).any(|y| (x0..x1).any(|x| self.is_opaque_at(x, y, alpha_threshold)))
+    }
+
+    /// Tests whether an opaque enough pixel of this bitmap, positioned at `top_left`,
+    /// overlaps an opaque enough pixel of `test_bitmap`, positioned at `test_top_left`.
+    pub fn hit_test_bitmapdata(
+        &self,
+        top_left: (i32, i32),
+        alpha_threshold: u8,
+        test_bitmap: &Self,
+        test_top_left: (i32, i32),
+        test_alpha_threshold: u8,
+    ) -> bool {
+        let offset_x = test_top_left.0 - top_left.0;
+        let offset_y = test_top_left.1 - top_left.1;
+        let x0 = offset_x.max(0);
+        let y0 = offset_y.max(0);
+        let x1 = (offset_x + test_bitmap.width as i32).min(self.width as i32);
+        let y1 = (offset_y + test_bitmap.height as i32).min(self.height as i32);
+
+        (y0..y1).any(|y| {
+            (x0..x1).any(|x| {
+                self.is_opaque_at(x, y, alpha_threshold)
+                    && test_bitmap.is_opaque_at(x - offset_x, y - offset_y, test_alpha_threshold)
+            })
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn perlin_noise(
         &mut self,
@@ -5,7 +5,11 @@ use crate::avm1::SystemProperties;
 use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject, Value as Avm2Value};
 use crate::backend::{
-    audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    audio::{
+        AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle, Substream, SubstreamFormat,
+    },
+    font::FontBackend,
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
@@ -18,8 +22,11 @@ use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::socket::Sockets;
+use crate::streams::StreamManager;
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
@@ -92,6 +99,13 @@ pub struct UpdateContext<'a, 'gc> {
     /// The video backend, used for video decoding
     pub video: &'a mut dyn VideoBackend,
 
+    /// The font backend, used to load system fonts for device text.
+    pub font_backend: &'a mut dyn FontBackend,
+
+    /// The local connection backend, used to exchange `LocalConnection`
+    /// messages with other players.
+    pub local_connection_backend: &'a mut dyn LocalConnectionBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -183,6 +197,15 @@ pub struct UpdateContext<'a, 'gc> {
     ///
     /// If we are not doing frame processing, then this is `FramePhase::Enter`.
     pub frame_phase: &'a mut FramePhase,
+
+    /// Manager of in-progress media streams.
+    pub stream_manager: &'a mut StreamManager<'gc>,
+
+    /// Open socket connections.
+    pub sockets: &'a mut Sockets<'gc>,
+
+    /// Connected `LocalConnection`s and the messages sent over them.
+    pub local_connections: &'a mut LocalConnections<'gc>,
 }
 
 /// Convenience methods for controlling audio.
@@ -231,6 +254,22 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(
+        &mut self,
+        source: crate::avm2::SoundObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager.start_dynamic_sound(self.audio, source)
+    }
+
+    pub fn start_substream(
+        &mut self,
+        substream: Substream,
+        format: &SubstreamFormat,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager
+            .start_substream(self.audio, substream, format)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -316,6 +355,8 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             log: self.log,
             ui: self.ui,
             video: self.video,
+            font_backend: self.font_backend,
+            local_connection_backend: self.local_connection_backend,
             storage: self.storage,
             rng: self.rng,
             stage: self.stage,
@@ -345,6 +386,9 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             frame_rate: self.frame_rate,
             actions_since_timeout_check: self.actions_since_timeout_check,
             frame_phase: self.frame_phase,
+            stream_manager: self.stream_manager,
+            sockets: self.sockets,
+            local_connections: self.local_connections,
         }
     }
 
@@ -561,6 +561,14 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
             MouseCursor::Arrow
         }
     }
+
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
+
+    fn is_keyboard_activatable(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.enabled()
+    }
 }
 
 impl<'gc> Avm1ButtonData<'gc> {
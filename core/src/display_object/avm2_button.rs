@@ -817,6 +817,14 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
             MouseCursor::Arrow
         }
     }
+
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
+
+    fn is_keyboard_activatable(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
 }
 
 impl<'gc> Avm2ButtonData<'gc> {
@@ -8,7 +8,8 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+    StageObject as Avm2StageObject,
 };
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
@@ -19,7 +20,9 @@ use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject}
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, TextFormat};
+use crate::html::{
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, StyleSheet, TextFormat,
+};
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -27,10 +30,13 @@ use crate::vminterface::{AvmObject, Instantiator};
 use chrono::Utc;
 use core::fmt;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use instant::Instant;
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::shape_utils::DrawCommand;
 use ruffle_render::transform::Transform;
-use std::{cell::Ref, cell::RefMut, sync::Arc};
+use std::cell::{Ref, RefCell, RefMut};
+use std::rc::Rc;
+use std::sync::Arc;
 use swf::{Color, Twips};
 
 use super::interactive::Avm2MousePick;
@@ -85,6 +91,18 @@ pub struct EditTextData<'gc> {
     /// rendering.
     text_spans: FormatSpans,
 
+    /// The style sheet applied to HTML text, if any.
+    style_sheet: Option<StyleSheet>,
+
+    /// The HTML that the text spans were generated from, kept while a style
+    /// sheet is present as the spans can't be raised back to it.
+    #[collect(require_static)]
+    html_source: Option<WString>,
+
+    /// The `TextField.StyleSheet` object that `style_sheet` was read from
+    /// (AVM1 only).
+    avm1_style_sheet: Option<Avm1Object<'gc>>,
+
     /// The color of the background fill. Only applied when has_border and has_background.
     #[collect(require_static)]
     background_color: Color,
@@ -132,6 +150,11 @@ pub struct EditTextData<'gc> {
     /// Information about the layout's current lines. Used by scroll properties.
     line_data: Vec<LineData>,
 
+    /// The positions that the caret can be placed at in the current layout,
+    /// computed when first needed.
+    #[collect(require_static)]
+    caret_stops: RefCell<Option<Rc<[CaretStop]>>>,
+
     /// How many lines down the text is offset by. 1-based index.
     scroll: usize,
 
@@ -191,6 +214,56 @@ fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
     line_data
 }
 
+/// Whether a character is part of a word, for word selection and movement.
+fn is_word_char(c: u16) -> bool {
+    char::from_u32(c.into()).map_or(false, |c| c.is_alphanumeric() || c == '_')
+}
+
+/// Find the start of the word before `index`, skipping any preceding
+/// non-word characters.
+fn prev_word_boundary(text: &WStr, index: usize) -> usize {
+    let mut index = index;
+    while index > 0 && !is_word_char(text.at(index - 1)) {
+        index -= 1;
+    }
+    while index > 0 && is_word_char(text.at(index - 1)) {
+        index -= 1;
+    }
+    index
+}
+
+/// Find the start of the word after `index`, skipping the rest of the word at
+/// `index` and any following non-word characters.
+fn next_word_boundary(text: &WStr, index: usize) -> usize {
+    let mut index = index;
+    while index < text.len() && is_word_char(text.at(index)) {
+        index += 1;
+    }
+    while index < text.len() && !is_word_char(text.at(index)) {
+        index += 1;
+    }
+    index
+}
+
+/// Find the range of the word touching `index`, or of the single character
+/// at `index` if it isn't part of a word.
+fn word_range(text: &WStr, index: usize) -> (usize, usize) {
+    let is_word_at = |i: usize| i < text.len() && is_word_char(text.at(i));
+    if !is_word_at(index) && !(index > 0 && is_word_at(index - 1)) {
+        return (index, string_utils::next_char_boundary(text, index));
+    }
+
+    let mut start = index;
+    while start > 0 && is_word_at(start - 1) {
+        start -= 1;
+    }
+    let mut end = index;
+    while is_word_at(end) {
+        end += 1;
+    }
+    (start, end)
+}
+
 impl<'gc> EditText<'gc> {
     /// Creates a new `EditText` from an SWF `DefineEditText` tag.
     pub fn from_swf_tag(
@@ -204,7 +277,7 @@ impl<'gc> EditText<'gc> {
 
         let text = WString::from_utf8(&text.to_str_lossy(encoding));
         let mut text_spans = if swf_tag.is_html() {
-            FormatSpans::from_html(&text, default_format, swf_tag.is_multiline())
+            FormatSpans::from_html(&text, default_format, None, swf_tag.is_multiline())
         } else {
             FormatSpans::from_text(text, default_format)
         };
@@ -254,6 +327,9 @@ impl<'gc> EditText<'gc> {
             EditTextData {
                 base,
                 text_spans,
+                style_sheet: None,
+                html_source: None,
+                avm1_style_sheet: None,
                 static_data: gc_arena::Gc::allocate(
                     context.gc_context,
                     EditTextStatic {
@@ -281,6 +357,7 @@ impl<'gc> EditText<'gc> {
                 render_settings: Default::default(),
                 hscroll: 0.0,
                 line_data,
+                caret_stops: RefCell::new(None),
                 scroll: 1,
                 max_chars: 0,
             },
@@ -337,6 +414,7 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.html_source = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -344,7 +422,11 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let read = self.0.read();
+            match &read.html_source {
+                Some(html_source) => html_source.clone(),
+                None => read.text_spans.to_html(),
+            }
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -358,8 +440,10 @@ impl<'gc> EditText<'gc> {
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
+                write.style_sheet.as_ref(),
                 write.flags.contains(EditTextFlag::MULTILINE),
             );
+            write.html_source = write.style_sheet.is_some().then(|| text.into());
             drop(write);
 
             self.relayout(context);
@@ -404,7 +488,9 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn is_editable(self) -> bool {
-        !self.0.read().flags.contains(EditTextFlag::READ_ONLY)
+        // Text fields with a style sheet can't be edited by the user.
+        let read = self.0.read();
+        !read.flags.contains(EditTextFlag::READ_ONLY) && read.style_sheet.is_none()
     }
 
     pub fn set_editable(self, is_editable: bool, context: &mut UpdateContext<'_, 'gc>) {
@@ -546,13 +632,39 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
+        let mut write = self.0.write(context.gc_context);
+        write.text_spans.replace_text(from, to, text, None);
+        write.html_source = None;
+        drop(write);
         self.relayout(context);
     }
 
+    /// Set the style sheet applied to the HTML text of this text field, and
+    /// restyle the current HTML text with it.
+    pub fn set_style_sheet(
+        self,
+        style_sheet: Option<StyleSheet>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        let html = self.html_text();
+        self.0.write(context.gc_context).style_sheet = style_sheet;
+        if self.is_html() {
+            self.set_html_text(&html, context);
+        }
+    }
+
+    pub fn avm1_style_sheet(self) -> Option<Avm1Object<'gc>> {
+        self.0.read().avm1_style_sheet
+    }
+
+    pub fn set_avm1_style_sheet(
+        self,
+        style_sheet: Option<Avm1Object<'gc>>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        self.0.write(context.gc_context).avm1_style_sheet = style_sheet;
+    }
+
     /// Construct a base text transform for a particular `EditText` span.
     ///
     /// This `text_transform` is separate from and relative to the base
@@ -716,6 +828,7 @@ impl<'gc> EditText<'gc> {
 
         edit_text.line_data = get_line_data(&new_layout);
         edit_text.layout = new_layout;
+        *edit_text.caret_stops.get_mut() = None;
         edit_text.intrinsic_bounds = intrinsic_bounds;
         // reset scroll
         edit_text.hscroll = 0.0;
@@ -1135,55 +1248,368 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).max_chars = value;
     }
 
-    pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
-        let text = self.0.read();
-        let position = self.global_to_local(position);
-        let position = (
-            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
-            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
-        );
+    /// Get the positions that the caret can be placed at, in text order.
+    fn caret_stops(self) -> Rc<[CaretStop]> {
+        let edit_text = self.0.read();
+        let mut caret_stops = edit_text.caret_stops.borrow_mut();
+        caret_stops
+            .get_or_insert_with(|| Self::compute_caret_stops(&edit_text).into())
+            .clone()
+    }
 
-        for layout_box in text.layout.iter() {
-            let origin = layout_box.bounds().origin();
-            let mut matrix = Matrix::translate(origin.x(), origin.y());
-            matrix.invert();
-            let local_position = matrix * position;
-
-            if let Some((text, _tf, font, params, color)) =
-                layout_box.as_renderable_text(text.text_spans.text())
-            {
-                let mut result = None;
-                let baseline_adjustment =
-                    font.get_baseline_for_height(params.height()) - params.height();
-                font.evaluate(
-                    text,
-                    self.text_transform(color, baseline_adjustment),
+    /// Find the positions that the caret can be placed at in the layout of
+    /// a text field.
+    fn compute_caret_stops(edit_text: &EditTextData<'gc>) -> Vec<CaretStop> {
+        let text = edit_text.text_spans.text();
+        let mut stops: Vec<CaretStop> = vec![];
+
+        for layout_box in edit_text.layout.iter() {
+            let (start, end, font, params) = match layout_box.content() {
+                LayoutContent::Text {
+                    start,
+                    end,
+                    font,
                     params,
-                    |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.0 >= x
-                            && local_position.0 <= x + advance
-                            && local_position.1 >= Twips::ZERO
-                            && local_position.1 <= params.height()
-                        {
-                            if local_position.0 >= x + (advance / 2) {
-                                result = Some(string_utils::next_char_boundary(text, pos));
-                            } else {
-                                result = Some(pos);
-                            }
-                        }
-                    },
+                    ..
+                } => (*start, *end, *font, *params),
+                _ => continue,
+            };
+            let box_text = match text.slice(start..end) {
+                Some(box_text) => box_text,
+                None => continue,
+            };
+
+            let bounds = layout_box.bounds();
+            let line = edit_text
+                .line_data
+                .iter()
+                .position(|line| bounds.offset_y() <= line.extent)
+                .unwrap_or(0);
+
+            let mut end_x = bounds.offset_x();
+            font.evaluate(
+                box_text,
+                Transform::default(),
+                params,
+                |pos, _transform, _glyph: &Glyph, advance, x| {
+                    stops.push(CaretStop {
+                        index: start + pos,
+                        x: bounds.offset_x() + x,
+                        advance: Some(advance),
+                        line,
+                    });
+                    end_x = bounds.offset_x() + x + advance;
+                },
+            );
+
+            // A trailing line break belongs to its line, but the caret can't
+            // be placed after it without moving to the next line.
+            let end_index = if box_text.ends_with(b'\n') || box_text.ends_with(b'\r') {
+                end - 1
+            } else {
+                end
+            };
+            if stops.last().map_or(true, |stop| stop.index != end_index) {
+                stops.push(CaretStop {
+                    index: end_index,
+                    x: end_x,
+                    advance: None,
+                    line,
+                });
+            }
+        }
+
+        stops
+    }
+
+    /// The offset of the layout in the local coordinates of this text field,
+    /// taking padding and scrolling into account.
+    fn layout_offset(self) -> (Twips, Twips) {
+        let edit_text = self.0.read();
+        let scroll_offset = if edit_text.scroll > 1 {
+            edit_text
+                .line_data
+                .get(edit_text.scroll - 1)
+                .map_or(Twips::ZERO, |line| line.offset)
+        } else {
+            Twips::ZERO
+        };
+
+        (
+            edit_text.bounds.x_min + Twips::from_pixels(Self::INTERNAL_PADDING)
+                - Twips::from_pixels(edit_text.hscroll),
+            edit_text.bounds.y_min + Twips::from_pixels(Self::INTERNAL_PADDING) - scroll_offset,
+        )
+    }
+
+    /// Convert a position in the local coordinates of this text field to the
+    /// coordinates of its layout.
+    fn local_to_layout(self, position: (Twips, Twips)) -> (Twips, Twips) {
+        let offset = self.layout_offset();
+        (position.0 - offset.0, position.1 - offset.1)
+    }
+
+    /// Find the caret stop on the given line that is horizontally closest to
+    /// `x`.
+    fn nearest_stop_on_line(stops: &[CaretStop], line: usize, x: Twips) -> Option<CaretStop> {
+        stops
+            .iter()
+            .filter(|stop| stop.line == line)
+            .min_by_key(|stop| (stop.x - x).get().abs())
+            .copied()
+    }
+
+    /// Find the caret stop of the given text index.
+    fn stop_for_index(stops: &[CaretStop], index: usize) -> Option<CaretStop> {
+        stops
+            .iter()
+            .find(|stop| stop.index == index)
+            .or_else(|| stops.iter().rev().find(|stop| stop.index < index))
+            .or_else(|| stops.first())
+            .copied()
+    }
+
+    /// Find the text index closest to a position on the screen.
+    ///
+    /// Positions above, below or beside the text resolve to the closest
+    /// index on the nearest line. Returns `None` if there is no text.
+    pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
+        let position = self.local_to_layout(self.global_to_local(position));
+        let stops = self.caret_stops();
+        let line = {
+            let edit_text = self.0.read();
+            let line_data = &edit_text.line_data;
+            line_data
+                .iter()
+                .position(|line| position.1 <= line.extent)
+                .unwrap_or_else(|| line_data.len().saturating_sub(1))
+        };
+
+        Self::nearest_stop_on_line(&stops, line, position.0).map(|stop| stop.index)
+    }
+
+    /// Find the index that the caret moves to when moving `line_delta` lines
+    /// up (if negative) or down from `index`, keeping its horizontal position.
+    fn index_on_other_line(self, index: usize, line_delta: isize) -> usize {
+        let stops = self.caret_stops();
+        let current = match Self::stop_for_index(&stops, index) {
+            Some(current) => current,
+            None => return index,
+        };
+
+        let line = current.line as isize + line_delta;
+        if line < 0 {
+            return 0;
+        }
+        if line as usize >= self.0.read().line_data.len() {
+            return self.text_length();
+        }
+
+        Self::nearest_stop_on_line(&stops, line as usize, current.x)
+            .map_or(index, |stop| stop.index)
+    }
+
+    /// Find the index at the start or the end of the line containing `index`.
+    fn line_edge_index(self, index: usize, at_end: bool) -> usize {
+        let stops = self.caret_stops();
+        let current = match Self::stop_for_index(&stops, index) {
+            Some(current) => current,
+            None => return index,
+        };
+
+        let mut line_stops = stops.iter().filter(|stop| stop.line == current.line);
+        let edge = if at_end {
+            line_stops.last()
+        } else {
+            line_stops.next()
+        };
+        edge.map_or(index, |stop| stop.index)
+    }
+
+    /// Get the bounds of the character at `index`, in local coordinates.
+    ///
+    /// Returns `None` if the character isn't displayed, such as line breaks.
+    pub fn char_bounds(self, index: usize) -> Option<Rectangle<Twips>> {
+        let stop = *self
+            .caret_stops()
+            .iter()
+            .find(|stop| stop.index == index && stop.advance.is_some())?;
+        let advance = stop.advance?;
+        let line = *self.0.read().line_data.get(stop.line)?;
+        let offset = self.layout_offset();
+
+        Some(Rectangle {
+            x_min: offset.0 + stop.x,
+            x_max: offset.0 + stop.x + advance,
+            y_min: offset.1 + line.offset,
+            y_max: offset.1 + line.extent,
+        })
+    }
+
+    /// Get the index of the character at a position in local coordinates,
+    /// if there is one.
+    pub fn char_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        let line = self.line_index_at_point(position)?;
+        let x = self.local_to_layout(position).0;
+        self.caret_stops()
+            .iter()
+            .find(|stop| {
+                stop.line == line
+                    && stop
+                        .advance
+                        .map_or(false, |advance| x >= stop.x && x < stop.x + advance)
+            })
+            .map(|stop| stop.index)
+    }
+
+    /// Get the index of the line at a position in local coordinates, if
+    /// there is one.
+    pub fn line_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        let y = self.local_to_layout(position).1;
+        self.0
+            .read()
+            .line_data
+            .iter()
+            .position(|line| y >= line.offset && y < line.extent)
+    }
+
+    /// Get the index of the line that the character at `index` is on.
+    pub fn line_index_of_char(self, index: usize) -> Option<usize> {
+        if index >= self.text_length() {
+            return None;
+        }
+        Self::stop_for_index(&self.caret_stops(), index).map(|stop| stop.line)
+    }
+
+    /// Get the range of the text on a line, including its line break, if any.
+    pub fn line_range(self, line: usize) -> Option<(usize, usize)> {
+        // The first caret stop of each line is the index of its first character.
+        // Empty text still has a first line.
+        let mut line_starts: Vec<usize> = vec![0];
+        for stop in self.caret_stops().iter() {
+            while line_starts.len() <= stop.line {
+                line_starts.push(stop.index);
+            }
+        }
+
+        let start = *line_starts.get(line)?;
+        let end = line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or_else(|| self.text_length());
+        Some((start, end))
+    }
+
+    /// Get the range of the paragraph containing the character at `index`,
+    /// including its line break, if any.
+    pub fn paragraph_range(self, index: usize) -> Option<(usize, usize)> {
+        let text = self.text();
+        if index >= text.len() {
+            return None;
+        }
+
+        let is_line_break = |c: u16| c == b'\n' as u16 || c == b'\r' as u16;
+        let start = text[..index].rfind(is_line_break).map_or(0, |pos| pos + 1);
+        let end = text[index..]
+            .find(is_line_break)
+            .map_or(text.len(), |pos| index + pos + 1);
+        Some((start, end))
+    }
+
+    /// How many more characters the user may enter according to
+    /// `max_chars`, or `None` if there is no limit.
+    fn remaining_chars(self) -> Option<usize> {
+        let read = self.0.read();
+        if read.max_chars == 0 {
+            None
+        } else {
+            let max_chars = read.max_chars.max(0) as usize;
+            Some(max_chars.saturating_sub(read.text_spans.text().len()))
+        }
+    }
+
+    /// Replace the selected text with `text`, placing the caret after it.
+    fn replace_selection(self, text: &WStr, context: &mut UpdateContext<'_, 'gc>) {
+        if let Some(selection) = self.selection() {
+            self.replace_text(selection.start(), selection.end(), text, context);
+            self.set_selection(
+                Some(TextSelection::for_position(selection.start() + text.len())),
+                context.gc_context,
+            );
+        }
+    }
+
+    /// Dispatch the AVM2 `textInput` event for text that the user is about to
+    /// enter.
+    ///
+    /// Returns `false` if a script cancelled the event, in which case the text
+    /// must not be entered.
+    fn dispatch_text_input(self, context: &mut UpdateContext<'_, 'gc>, text: &WStr) -> bool {
+        if !self.movie().is_action_script_3() {
+            return true;
+        }
+        let target = match self.object2() {
+            Avm2Value::Object(target) => target,
+            _ => return true,
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let text = AvmString::new(activation.context.gc_context, text);
+        let text_event_class = activation.avm2().classes().textevent;
+        let event = match text_event_class.construct(
+            &mut activation,
+            &[
+                "textInput".into(), /* type */
+                true.into(),        /* bubbles */
+                true.into(),        /* cancelable */
+                text.into(),        /* text */
+            ],
+        ) {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!("Failed to construct TextEvent: {}", e);
+                return true;
+            }
+        };
+
+        match Avm2::dispatch_event(&mut activation.context, event, target) {
+            Ok(was_not_cancelled) => was_not_cancelled,
+            Err(e) => {
+                tracing::error!(
+                    "Encountered AVM2 error when dispatching `textInput` event: {}",
+                    e
                 );
-                if result.is_some() {
-                    return result;
-                }
+                true
             }
         }
+    }
 
-        None
+    /// Notify scripts that the user changed the text: AVM1 fires `onChanged`
+    /// and updates the bound variable, while AVM2 dispatches `Event.CHANGE`.
+    fn notify_changed(self, context: &mut UpdateContext<'_, 'gc>) {
+        if self.movie().is_action_script_3() {
+            if let Avm2Value::Object(target) = self.object2() {
+                let event = Avm2EventObject::bare_event(context, "change", true, false);
+                if let Err(e) = Avm2::dispatch_event(context, event, target) {
+                    tracing::error!(
+                        "Encountered AVM2 error when dispatching `change` event: {}",
+                        e
+                    );
+                }
+            }
+        } else {
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Propagate Text Binding]"),
+                self.into(),
+            );
+            self.propagate_text_binding(&mut activation);
+            self.on_changed(&mut activation);
+        }
     }
 
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc>) {
-        if self.0.read().flags.contains(EditTextFlag::READ_ONLY) {
+        if !self.is_editable() {
             return;
         }
 
@@ -1192,11 +1618,7 @@ impl<'gc> EditText<'gc> {
             match character as u8 {
                 8 | 127 if !selection.is_caret() => {
                     // Backspace or delete with multiple characters selected
-                    self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
-                    self.set_selection(
-                        Some(TextSelection::for_position(selection.start())),
-                        context.gc_context,
-                    );
+                    self.replace_selection(WStr::empty(), context);
                     changed = true;
                 }
                 8 => {
@@ -1225,28 +1647,12 @@ impl<'gc> EditText<'gc> {
                     }
                 }
                 code if !(code as char).is_control() => {
-                    let can_insert = {
-                        let read = self.0.read();
-                        let max_chars = read.max_chars;
-                        if max_chars == 0 {
-                            true
-                        } else {
-                            let text_len = read.text_spans.text().len();
-                            text_len < max_chars.max(0) as usize
-                        }
-                    };
-                    if can_insert {
-                        self.replace_text(
-                            selection.start(),
-                            selection.end(),
-                            &WString::from_char(character),
-                            context,
-                        );
-                        let new_start = selection.start() + character.len_utf8();
-                        self.set_selection(
-                            Some(TextSelection::for_position(new_start)),
-                            context.gc_context,
-                        );
+                    let can_insert = self
+                        .remaining_chars()
+                        .map_or(true, |remaining| remaining > 0);
+                    let text = WString::from_char(character);
+                    if can_insert && self.dispatch_text_input(context, &text) {
+                        self.replace_selection(&text, context);
                         changed = true;
                     }
                 }
@@ -1254,70 +1660,145 @@ impl<'gc> EditText<'gc> {
             }
 
             if changed {
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    self.into(),
-                );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
+                self.notify_changed(context);
             }
         }
     }
 
     /// Listens for keyboard text control commands.
     ///
+    /// The arrow keys move the caret by characters and lines (or by words
+    /// while Ctrl is held), Home and End move it to the start or end of the
+    /// line (or of the text while Ctrl is held), and Page Up and Page Down
+    /// move it by the number of visible lines. Holding Shift extends the
+    /// selection instead of moving the caret.
+    ///
     /// TODO: Add explicit text control events (#4452).
     pub fn handle_text_control_event(
         self,
         context: &mut UpdateContext<'_, 'gc>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if let ClipEvent::KeyPress { key_code } = event {
-            let mut edit_text = self.0.write(context.gc_context);
-            let selection = edit_text.selection;
-            if let Some(mut selection) = selection {
-                let text = edit_text.text_spans.text();
-                let length = text.len();
-                match key_code {
-                    ButtonKeyCode::Left => {
-                        if (context.input.is_key_down(KeyCode::Shift) || selection.is_caret())
-                            && selection.to > 0
-                        {
-                            selection.to = string_utils::prev_char_boundary(text, selection.to);
-                            if !context.input.is_key_down(KeyCode::Shift) {
-                                selection.from = selection.to;
-                            }
-                        } else if !context.input.is_key_down(KeyCode::Shift) {
-                            selection.to = selection.start();
-                            selection.from = selection.to;
-                        }
-                        selection.clamp(length);
-                        edit_text.selection = Some(selection);
-                        return ClipEventResult::Handled;
-                    }
-                    ButtonKeyCode::Right => {
-                        if (context.input.is_key_down(KeyCode::Shift) || selection.is_caret())
-                            && selection.to < length
-                        {
-                            selection.to = string_utils::next_char_boundary(text, selection.to);
-                            if !context.input.is_key_down(KeyCode::Shift) {
-                                selection.from = selection.to;
-                            }
-                        } else if !context.input.is_key_down(KeyCode::Shift) {
-                            selection.to = selection.end();
-                            selection.from = selection.to;
-                        }
-                        selection.clamp(length);
-                        edit_text.selection = Some(selection);
-                        return ClipEventResult::Handled;
+        let key_code = match event {
+            ClipEvent::KeyPress { key_code } => key_code,
+            _ => return ClipEventResult::NotHandled,
+        };
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return ClipEventResult::NotHandled,
+        };
+
+        let is_shift_down = context.input.is_key_down(KeyCode::Shift);
+        let is_ctrl_down = context.input.is_key_down(KeyCode::Control);
+        let text = self.text();
+        let page_lines = (self.bottom_scroll() + 1)
+            .saturating_sub(self.scroll())
+            .max(1) as isize;
+
+        let to = match key_code {
+            ButtonKeyCode::Left if is_ctrl_down => prev_word_boundary(&text, selection.to),
+            ButtonKeyCode::Left if !is_shift_down && !selection.is_caret() => selection.start(),
+            ButtonKeyCode::Left => string_utils::prev_char_boundary(&text, selection.to),
+            ButtonKeyCode::Right if is_ctrl_down => next_word_boundary(&text, selection.to),
+            ButtonKeyCode::Right if !is_shift_down && !selection.is_caret() => selection.end(),
+            ButtonKeyCode::Right => string_utils::next_char_boundary(&text, selection.to),
+            ButtonKeyCode::Up => self.index_on_other_line(selection.to, -1),
+            ButtonKeyCode::Down => self.index_on_other_line(selection.to, 1),
+            ButtonKeyCode::PgUp => self.index_on_other_line(selection.to, -page_lines),
+            ButtonKeyCode::PgDown => self.index_on_other_line(selection.to, page_lines),
+            ButtonKeyCode::Home if is_ctrl_down => 0,
+            ButtonKeyCode::Home => self.line_edge_index(selection.to, false),
+            ButtonKeyCode::End if is_ctrl_down => text.len(),
+            ButtonKeyCode::End => self.line_edge_index(selection.to, true),
+            _ => return ClipEventResult::NotHandled,
+        };
+
+        let from = if is_shift_down { selection.from } else { to };
+        self.set_selection(Some(TextSelection::for_range(from, to)), context.gc_context);
+        ClipEventResult::Handled
+    }
+
+    /// Handles the Ctrl+key shortcuts of text fields: copying, cutting and
+    /// pasting text with the clipboard, and selecting all text.
+    pub fn handle_text_shortcut(self, context: &mut UpdateContext<'_, 'gc>, key_code: KeyCode) {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        match key_code {
+            KeyCode::A => {
+                if self.is_selectable() {
+                    self.set_selection(
+                        Some(TextSelection::for_range(0, self.text_length())),
+                        context.gc_context,
+                    );
+                }
+            }
+            KeyCode::C | KeyCode::X => {
+                // Password fields don't reveal their text.
+                if selection.is_caret() || self.is_password() {
+                    return;
+                }
+
+                let text = self.text();
+                let selected = &text[selection.start()..selection.end()];
+                context
+                    .ui
+                    .set_clipboard_content(selected.to_utf8_lossy().into_owned());
+
+                if key_code == KeyCode::X && self.is_editable() {
+                    self.replace_selection(WStr::empty(), context);
+                    self.notify_changed(context);
+                }
+            }
+            KeyCode::V => {
+                if !self.is_editable() {
+                    return;
+                }
+
+                let content = context.ui.clipboard_content().replace("\r\n", "\n");
+                let content = if self.is_multiline() {
+                    content.as_str()
+                } else {
+                    // Only the first line is pasted into single-line fields.
+                    content.split(['\r', '\n']).next().unwrap_or_default()
+                };
+                let mut text = WString::from_utf8(content);
+                if let Some(remaining) = self.remaining_chars() {
+                    let remaining = remaining + selection.end() - selection.start();
+                    if text.len() > remaining {
+                        // Drop the last character if it doesn't fit whole,
+                        // rather than splitting a surrogate pair.
+                        let end = string_utils::prev_char_boundary(&text, remaining + 1);
+                        text = text[..end].into();
                     }
-                    _ => (),
+                }
+
+                if !text.is_empty() && self.dispatch_text_input(context, &text) {
+                    self.replace_selection(&text, context);
+                    self.notify_changed(context);
                 }
             }
+            _ => {}
         }
+    }
 
-        ClipEventResult::NotHandled
+    /// Extend the selection to the mouse position while the user drags the
+    /// mouse over this text field.
+    pub fn drag_selection(self, context: &mut UpdateContext<'_, 'gc>) {
+        if !self.is_selectable() {
+            return;
+        }
+
+        if let Some(selection) = self.selection() {
+            if let Some(index) = self.screen_position_to_index(*context.mouse_position) {
+                self.set_selection(
+                    Some(TextSelection::for_range(selection.from, index)),
+                    context.gc_context,
+                );
+            }
+        }
     }
 
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc>) {
@@ -1799,7 +2280,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 
     fn filter_clip_event(self, event: ClipEvent) -> ClipEventResult {
-        if event != ClipEvent::Press {
+        if event != ClipEvent::Press && event != ClipEvent::Release {
             return ClipEventResult::NotHandled;
         }
 
@@ -1809,19 +2290,39 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     fn event_dispatch(
         self,
         context: &mut UpdateContext<'_, 'gc>,
-        _event: ClipEvent<'gc>,
+        event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        if event == ClipEvent::Release {
+            // AVM2 records clicks when dispatching the `click` event.
+            if !context.is_action_script_3() {
+                self.set_last_click(context.gc_context, Some(Instant::now()));
+            }
+            return ClipEventResult::Handled;
+        }
+
         let tracker = context.focus_tracker;
+        if !tracker.allow_mouse_focus_change(context, self.into()) {
+            return ClipEventResult::Handled;
+        }
         tracker.set(Some(self.into()), context);
-        if let Some(position) = self
+
+        let index = self
             .screen_position_to_index(*context.mouse_position)
-            .map(TextSelection::for_position)
-        {
-            self.0.write(context.gc_context).selection = Some(position);
+            .unwrap_or_else(|| self.text_length());
+        let selection = if self.is_double_click(Instant::now()) {
+            // Double-clicking selects the word under the mouse.
+            let (start, end) = word_range(&self.text(), index);
+            TextSelection::for_range(start, end)
         } else {
-            self.0.write(context.gc_context).selection =
-                Some(TextSelection::for_position(self.text_length()));
-        }
+            match self.selection() {
+                // Shift-clicking extends the selection.
+                Some(selection) if context.input.is_key_down(KeyCode::Shift) => {
+                    TextSelection::for_range(selection.from, index)
+                }
+                _ => TextSelection::for_position(index),
+            }
+        };
+        self.set_selection(Some(selection), context.gc_context);
 
         ClipEventResult::Handled
     }
@@ -1865,6 +2366,10 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     fn mouse_cursor(self, _context: &mut UpdateContext<'_, 'gc>) -> MouseCursor {
         MouseCursor::IBeam
     }
+
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.is_editable()
+    }
 }
 
 bitflags::bitflags! {
@@ -1908,6 +2413,20 @@ pub struct TextSelection {
     to: usize,
 }
 
+/// A position that the caret can be placed at in the laid-out text.
+#[derive(Copy, Clone, Debug)]
+struct CaretStop {
+    /// The index in the text that the caret is placed before.
+    index: usize,
+    /// The horizontal position of the caret in the layout.
+    x: Twips,
+    /// The advance of the character at `index`, or `None` if it isn't
+    /// displayed.
+    advance: Option<Twips>,
+    /// The index of the line in `line_data`.
+    line: usize,
+}
+
 /// Information about the start and end y-coordinates of a given line of text
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -1981,3 +2500,48 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prev_word_boundary_skips_to_word_start() {
+        let text = WStr::from_units(b"foo bar_1, baz");
+        assert_eq!(prev_word_boundary(text, 14), 11);
+        assert_eq!(prev_word_boundary(text, 11), 4);
+        assert_eq!(prev_word_boundary(text, 6), 4);
+        assert_eq!(prev_word_boundary(text, 4), 0);
+        assert_eq!(prev_word_boundary(text, 0), 0);
+    }
+
+    #[test]
+    fn next_word_boundary_skips_to_next_word() {
+        let text = WStr::from_units(b"foo bar_1, baz");
+        assert_eq!(next_word_boundary(text, 0), 4);
+        assert_eq!(next_word_boundary(text, 5), 11);
+        assert_eq!(next_word_boundary(text, 9), 11);
+        assert_eq!(next_word_boundary(text, 11), 14);
+        assert_eq!(next_word_boundary(text, 14), 14);
+    }
+
+    #[test]
+    fn word_range_selects_touching_word() {
+        let text = WStr::from_units(b"foo bar_1, baz");
+        assert_eq!(word_range(text, 0), (0, 3));
+        assert_eq!(word_range(text, 2), (0, 3));
+        assert_eq!(word_range(text, 3), (0, 3));
+        assert_eq!(word_range(text, 6), (4, 9));
+        assert_eq!(word_range(text, 14), (11, 14));
+    }
+
+    #[test]
+    fn word_range_selects_single_non_word_character() {
+        let text = WStr::from_units(b"a, b");
+        assert_eq!(word_range(text, 2), (2, 3));
+
+        let mut text = WString::from_utf8("a ");
+        text.push_char('\u{1F600}');
+        assert_eq!(word_range(&text, 2), (2, 4));
+    }
+}
@@ -74,6 +74,10 @@ bitflags! {
 
         /// Whether this `InteractiveObject` accepts double-clicks.
         const DOUBLE_CLICK_ENABLED = 1 << 1;
+
+        /// Whether the children of this `InteractiveObject` can be focused
+        /// with the Tab key.
+        const TAB_CHILDREN = 1 << 2;
     }
 }
 
@@ -90,15 +94,30 @@ pub struct InteractiveObjectBase<'gc> {
     /// display object.
     #[collect(require_static)]
     last_click: Option<Instant>,
+
+    /// Whether this object can be focused with the Tab key, or `None` if a
+    /// script hasn't set it.
+    tab_enabled: Option<bool>,
+
+    /// The position of this object in the tab order, or `None` if a script
+    /// hasn't set it.
+    tab_index: Option<i32>,
+
+    /// Whether a yellow rectangle is drawn around this object when it's
+    /// focused with the Tab key, or `None` to use the stage's setting.
+    focus_rect: Option<bool>,
 }
 
 impl<'gc> Default for InteractiveObjectBase<'gc> {
     fn default() -> Self {
         Self {
             base: Default::default(),
-            flags: InteractiveObjectFlags::MOUSE_ENABLED,
+            flags: InteractiveObjectFlags::MOUSE_ENABLED | InteractiveObjectFlags::TAB_CHILDREN,
             context_menu: Avm2Value::Null,
             last_click: None,
+            tab_enabled: None,
+            tab_index: None,
+            focus_rect: None,
         }
     }
 }
@@ -163,6 +182,80 @@ pub trait TInteractiveObject<'gc>:
         self.raw_interactive_mut(mc).context_menu = value;
     }
 
+    /// Whether this object is in the tab order, as set by a script.
+    ///
+    /// `None` means that the default for this kind of object is used.
+    fn tab_enabled_override(self) -> Option<bool> {
+        self.raw_interactive().tab_enabled
+    }
+
+    fn set_tab_enabled_override(self, mc: MutationContext<'gc, '_>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).tab_enabled = value;
+    }
+
+    /// Whether this object is in the tab order when no script has set
+    /// `tabEnabled`.
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        false
+    }
+
+    /// Whether this object can be focused with the Tab key.
+    fn tab_enabled(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.tab_enabled_override()
+            .unwrap_or_else(|| self.tab_enabled_default(context))
+    }
+
+    /// The position of this object in the tab order, if a script set one.
+    fn tab_index(self) -> Option<i32> {
+        self.raw_interactive().tab_index
+    }
+
+    fn set_tab_index(self, mc: MutationContext<'gc, '_>, value: Option<i32>) {
+        self.raw_interactive_mut(mc).tab_index = value;
+    }
+
+    /// Whether the children of this object can be focused with the Tab key.
+    fn tab_children(self) -> bool {
+        self.raw_interactive()
+            .flags
+            .contains(InteractiveObjectFlags::TAB_CHILDREN)
+    }
+
+    fn set_tab_children(self, mc: MutationContext<'gc, '_>, value: bool) {
+        self.raw_interactive_mut(mc)
+            .flags
+            .set(InteractiveObjectFlags::TAB_CHILDREN, value)
+    }
+
+    /// Whether a focus rectangle is drawn around this object, or `None` to
+    /// use the stage's setting.
+    fn focus_rect(self) -> Option<bool> {
+        self.raw_interactive().focus_rect
+    }
+
+    fn set_focus_rect(self, mc: MutationContext<'gc, '_>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).focus_rect = value;
+    }
+
+    /// Whether a click at `time` follows the last click on this object
+    /// closely enough to count as a double click.
+    fn is_double_click(self, time: Instant) -> bool {
+        self.raw_interactive()
+            .last_click
+            .map(|lc| time - lc < Duration::from_secs(1))
+            .unwrap_or(false)
+    }
+
+    /// Record the time of the last click on this object.
+    fn set_last_click(self, mc: MutationContext<'gc, '_>, time: Option<Instant>) {
+        self.raw_interactive_mut(mc).last_click = time;
+    }
+
+    /// Whether pressing Enter or Space while this object has focus clicks it.
+    fn is_keyboard_activatable(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        false
+    }
+
     /// Filter the incoming clip event.
     ///
     /// If this returns `Handled`, then the rest of the event handling
@@ -259,18 +352,13 @@ pub trait TInteractiveObject<'gc>:
                 ClipEventResult::Handled
             }
             ClipEvent::Release => {
-                let read = self.raw_interactive();
-                let last_click = read.last_click;
                 let this_click = Instant::now();
 
-                let is_double_click = read
+                let is_double_click = self
+                    .raw_interactive()
                     .flags
                     .contains(InteractiveObjectFlags::DOUBLE_CLICK_ENABLED)
-                    && last_click
-                        .map(|lc| this_click - lc < Duration::from_secs(1))
-                        .unwrap_or(false);
-
-                drop(read);
+                    && self.is_double_click(this_click);
 
                 if is_double_click {
                     let avm2_event = Avm2EventObject::mouse_event(
@@ -287,7 +375,7 @@ pub trait TInteractiveObject<'gc>:
                         tracing::error!("Got error when dispatching {:?} to AVM2: {}", event, e);
                     }
 
-                    self.raw_interactive_mut(context.gc_context).last_click = None;
+                    self.set_last_click(context.gc_context, None);
                 } else {
                     let avm2_event = Avm2EventObject::mouse_event(
                         &mut activation,
@@ -303,7 +391,7 @@ pub trait TInteractiveObject<'gc>:
                         tracing::error!("Got error when dispatching {:?} to AVM2: {}", event, e);
                     }
 
-                    self.raw_interactive_mut(context.gc_context).last_click = Some(this_click);
+                    self.set_last_click(context.gc_context, Some(this_click));
                 }
 
                 ClipEventResult::Handled
@@ -321,7 +409,7 @@ pub trait TInteractiveObject<'gc>:
                     tracing::error!("Got error when dispatching {:?} to AVM2: {}", event, e);
                 }
 
-                self.raw_interactive_mut(context.gc_context).last_click = None;
+                self.set_last_click(context.gc_context, None);
 
                 ClipEventResult::Handled
             }
@@ -368,7 +456,7 @@ pub trait TInteractiveObject<'gc>:
                     rollout_target = tgt.parent();
                 }
 
-                self.raw_interactive_mut(context.gc_context).last_click = None;
+                self.set_last_click(context.gc_context, None);
 
                 ClipEventResult::Handled
             }
@@ -8,6 +8,7 @@ use crate::avm2::{
     QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
+use crate::backend::font::FontFile;
 use crate::backend::ui::MouseCursor;
 use bitflags::bitflags;
 
@@ -28,7 +29,7 @@ use crate::display_object::{
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
-use crate::font::Font;
+use crate::font::{Font, FontDescriptor, FontType};
 use crate::limits::ExecutionLimit;
 use crate::prelude::*;
 use crate::string::{AvmString, WStr, WString};
@@ -3049,6 +3050,14 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
             MouseCursor::Arrow
         }
     }
+
+    fn tab_enabled_default(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.is_button_mode(context)
+    }
+
+    fn is_keyboard_activatable(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.enabled() && self.is_button_mode(context)
+    }
 }
 
 impl<'gc> MovieClipData<'gc> {
@@ -3595,10 +3604,30 @@ impl<'gc, 'a> MovieClipData<'gc> {
     #[inline]
     fn define_font_4(
         &mut self,
-        _context: &mut UpdateContext<'_, 'gc>,
-        _reader: &mut SwfStream<'a>,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'a>,
     ) -> Result<(), Error> {
-        tracing::warn!("DefineFont4 tag (TLF text) is not implemented");
+        let font = reader.read_define_font_4()?;
+        let font_id = font.id;
+        let name = font.name.to_string_lossy(reader.encoding());
+        let descriptor = FontDescriptor::from_parts(&name, font.is_bold, font.is_italic);
+
+        // Without embedded data, the tag only names a device font.
+        let data = match font.data {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+        let file = FontFile {
+            data: data.to_vec(),
+            index: 0,
+        };
+        match Font::from_font_file(context.gc_context, file, descriptor, FontType::EmbeddedCFF) {
+            Some(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font_id, Character::Font(font_object)),
+            None => tracing::warn!("Unable to parse DefineFont4 font {}", name),
+        }
         Ok(())
     }
 
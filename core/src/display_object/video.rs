@@ -7,6 +7,7 @@ use crate::avm2::{
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
 use crate::prelude::*;
+use crate::streams::NetStream;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::{AvmObject, Instantiator};
 use core::fmt;
@@ -47,7 +48,15 @@ pub struct VideoData<'gc> {
     base: DisplayObjectBase<'gc>,
 
     /// The source of the video data (e.g. an external file, a SWF bitstream)
-    source: GcCell<'gc, VideoSource>,
+    source: GcCell<'gc, VideoSource<'gc>>,
+
+    /// The movie that created this video player.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
+
+    /// The display size of this video player, for sources that do not define
+    /// their own.
+    size: (i32, i32),
 
     /// The decoder stream that this video source is associated to.
     stream: VideoStream,
@@ -83,22 +92,34 @@ pub enum VideoStream {
 }
 
 #[derive(Clone, Debug, Collect)]
-#[collect(require_static)]
-pub enum VideoSource {
+#[collect(no_drop)]
+pub enum VideoSource<'gc> {
     /// A video bitstream embedded inside of a SWF movie.
     Swf {
         /// The movie that defined this video stream.
+        #[collect(require_static)]
         movie: Arc<SwfMovie>,
 
         /// The video stream definition.
+        #[collect(require_static)]
         streamdef: DefineVideoStream,
 
         /// The locations of each embedded sub-bitstream for each video frame.
         ///
         /// Each frame consists of a start and end parameter which can be used
         /// to reconstruct a reference to the embedded bitstream.
+        #[collect(require_static)]
         frames: BTreeMap<u32, (usize, usize)>,
     },
+
+    /// A video stream played by a `NetStream`.
+    ///
+    /// Frames are decoded by the stream itself; the video player only
+    /// displays them.
+    NetStream { stream: NetStream<'gc> },
+
+    /// A video player that has no source attached yet.
+    Unconnected,
 }
 
 impl<'gc> Video<'gc> {
@@ -108,10 +129,11 @@ impl<'gc> Video<'gc> {
         streamdef: DefineVideoStream,
         mc: MutationContext<'gc, '_>,
     ) -> Self {
+        let size = (streamdef.width.into(), streamdef.height.into());
         let source = GcCell::allocate(
             mc,
             VideoSource::Swf {
-                movie,
+                movie: movie.clone(),
                 streamdef,
                 frames: BTreeMap::new(),
             },
@@ -122,6 +144,8 @@ impl<'gc> Video<'gc> {
             VideoData {
                 base: Default::default(),
                 source,
+                movie,
+                size,
                 stream: VideoStream::Uninstantiated(0),
                 decoded_frame: None,
                 object: None,
@@ -130,6 +154,55 @@ impl<'gc> Video<'gc> {
         ))
     }
 
+    /// Construct a Video object with no source, such as one created by
+    /// ActionScript.
+    pub fn new(
+        mc: MutationContext<'gc, '_>,
+        movie: Arc<SwfMovie>,
+        width: i32,
+        height: i32,
+        object: Option<AvmObject<'gc>>,
+    ) -> Self {
+        let source = GcCell::allocate(mc, VideoSource::Unconnected);
+
+        Video(GcCell::allocate(
+            mc,
+            VideoData {
+                base: Default::default(),
+                source,
+                movie,
+                size: (width, height),
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                object,
+                keyframes: BTreeSet::new(),
+            },
+        ))
+    }
+
+    /// Display the frames decoded by a `NetStream` in this video player.
+    pub fn attach_netstream(self, context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        write.source = GcCell::allocate(context.gc_context, VideoSource::NetStream { stream });
+        write.stream = VideoStream::Uninstantiated(0);
+        write.decoded_frame = None;
+        write.keyframes = BTreeSet::new();
+    }
+
+    /// The frame currently displayed by this video player.
+    pub fn current_frame(self) -> Option<BitmapInfo> {
+        let read = self.0.read();
+        let source = read.source.read();
+        match &*source {
+            VideoSource::Swf { .. } => read
+                .decoded_frame
+                .as_ref()
+                .map(|(_, bitmap)| bitmap.clone()),
+            VideoSource::NetStream { stream } => stream.last_decoded_bitmap(),
+            VideoSource::Unconnected => None,
+        }
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -155,6 +228,7 @@ impl<'gc> Video<'gc> {
 
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
+            _ => tracing::warn!("Tried to preload a SWF video frame into a non-SWF video"),
         }
     }
 
@@ -178,6 +252,8 @@ impl<'gc> Video<'gc> {
 
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames),
+            // Only embedded videos are seeked through the display object.
+            VideoSource::NetStream { .. } | VideoSource::Unconnected => return,
         };
 
         if let Some(num_frames) = num_frames {
@@ -271,6 +347,7 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
+            VideoSource::NetStream { .. } | VideoSource::Unconnected => return,
         };
 
         drop(read);
@@ -320,7 +397,18 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         let mut write = self.0.write(context.gc_context);
 
-        let (stream, movie, keyframes) = match &*write.source.read() {
+        if write.object.is_none() && !write.movie.is_action_script_3() {
+            let object: Avm1Object<'_> = Avm1StageObject::for_display_object(
+                context.gc_context,
+                (*self).into(),
+                context.avm1.prototypes().video,
+            )
+            .into();
+            write.object = Some(object.into());
+        }
+
+        let source = write.source;
+        let instantiated = match &*source.read() {
             VideoSource::Swf {
                 streamdef,
                 movie,
@@ -364,7 +452,21 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                     }
                 }
 
-                (stream, movie.clone(), keyframes)
+                Some((stream, keyframes))
+            }
+            VideoSource::NetStream { .. } | VideoSource::Unconnected => None,
+        };
+
+        let (stream, keyframes) = match instantiated {
+            Some(instantiated) => instantiated,
+            None => {
+                drop(write);
+
+                if !context.is_action_script_3() && run_frame {
+                    self.run_frame_avm1(context);
+                }
+
+                return;
             }
         };
 
@@ -379,16 +481,6 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         write.stream = VideoStream::Instantiated(stream);
         write.keyframes = keyframes;
 
-        if write.object.is_none() && !movie.is_action_script_3() {
-            let object: Avm1Object<'_> = Avm1StageObject::for_display_object(
-                context.gc_context,
-                (*self).into(),
-                context.avm1.prototypes().video,
-            )
-            .into();
-            write.object = Some(object.into());
-        }
-
         drop(write);
 
         self.seek(context, starting_seek);
@@ -421,17 +513,17 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
+            VideoSource::NetStream { .. } | VideoSource::Unconnected => 0,
         }
     }
 
     fn self_bounds(&self) -> Rectangle<Twips> {
-        match (*self.0.read().source.read()).borrow() {
-            VideoSource::Swf { streamdef, .. } => Rectangle {
-                x_min: Twips::ZERO,
-                y_min: Twips::ZERO,
-                x_max: Twips::from_pixels_i32(streamdef.width.into()),
-                y_max: Twips::from_pixels_i32(streamdef.height.into()),
-            },
+        let (width, height) = self.0.read().size;
+        Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels_i32(width),
+            y_max: Twips::from_pixels_i32(height),
         }
     }
 
@@ -445,7 +537,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         let read = self.0.read();
 
-        if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
+        if let Some(bitmap) = self.current_frame() {
             let mut transform = context.transform_stack.transform();
             let bounds = self.self_bounds();
 
@@ -462,6 +554,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                     frames,
                     movie,
                 } => (streamdef.is_smoothed, frames.len(), movie.version()),
+                VideoSource::NetStream { .. } | VideoSource::Unconnected => {
+                    (false, 0, read.movie.version())
+                }
             };
 
             let smoothing = match (context.stage.quality(), version) {
@@ -474,7 +569,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
             context
                 .commands
-                .render_bitmap(bitmap.handle.clone(), transform, smoothing);
+                .render_bitmap(bitmap.handle, transform, smoothing);
         } else {
             tracing::warn!("Video has no decoded frame to render.");
         }
@@ -487,8 +582,6 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     }
 
     fn movie(&self) -> Arc<SwfMovie> {
-        match &*self.0.read().source.read() {
-            VideoSource::Swf { movie, .. } => movie.clone(),
-        }
+        self.0.read().movie.clone()
     }
 }
@@ -3,7 +3,7 @@ use gc_arena::Collect;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
-use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
 use std::cell::Cell;
 use swf::{FillStyle, LineStyle, Rectangle, Twips};
 
@@ -77,8 +77,13 @@ impl Drawing {
 
                     this.set_line_style(None);
                 }
-                DrawPath::Fill { style, commands } => {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
                     this.set_fill_style(Some(style.clone()));
+                    this.set_winding_rule(winding_rule);
 
                     for command in commands {
                         this.draw_command(command);
@@ -119,12 +124,21 @@ impl Drawing {
                     x: self.cursor.0,
                     y: self.cursor.1,
                 }],
+                winding_rule: FillRule::EvenOdd,
             });
         }
         self.fill_start = self.cursor;
         self.dirty.set(true);
     }
 
+    /// Sets the rule used to fill the fill that is currently being drawn.
+    pub fn set_winding_rule(&mut self, winding_rule: FillRule) {
+        if let Some(fill) = &mut self.current_fill {
+            fill.winding_rule = winding_rule;
+            self.dirty.set(true);
+        }
+    }
+
     pub fn clear(&mut self) {
         self.current_fill = None;
         self.current_line = None;
@@ -138,6 +152,29 @@ impl Drawing {
         self.fill_start = (Twips::ZERO, Twips::ZERO);
     }
 
+    /// Replaces the contents of this drawing with a copy of `other`.
+    pub fn copy_from(&mut self, other: &Self) {
+        let render_handle = self.render_handle.take();
+        *self = other.clone();
+        self.render_handle.set(render_handle);
+        self.dirty.set(true);
+    }
+
+    /// The style of the fill that is currently being drawn, if any.
+    pub fn fill_style(&self) -> Option<&FillStyle> {
+        self.current_fill.as_ref().map(|fill| &fill.style)
+    }
+
+    /// The style of the line that is currently being drawn, if any.
+    pub fn line_style(&self) -> Option<&LineStyle> {
+        self.current_line.as_ref().map(|line| &line.style)
+    }
+
+    /// The current position of the pen.
+    pub fn cursor(&self) -> (Twips, Twips) {
+        self.cursor
+    }
+
     pub fn set_line_style(&mut self, style: Option<LineStyle>) {
         if let Some(mut existing) = self.current_line.take() {
             existing.is_closed = self.cursor == self.fill_start;
@@ -211,71 +248,8 @@ impl Drawing {
     pub fn render(&self, context: &mut RenderContext) {
         if self.dirty.get() {
             self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
-                }
-            }
-
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                })
-            }
-
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo {
-                        x: self.fill_start.0,
-                        y: self.fill_start.1,
-                    });
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
-
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo {
-                        x: self.fill_start.0,
-                        y: self.fill_start.1,
-                    });
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
-
             let shape = DistilledShape {
-                paths,
+                paths: self.paths(),
                 shape_bounds: self.shape_bounds.clone(),
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
@@ -295,6 +269,77 @@ impl Drawing {
         }
     }
 
+    /// All of the paths in this drawing, including any fills and lines that are still
+    /// being drawn.
+    pub fn paths(&self) -> Vec<DrawPath<'_>> {
+        let mut paths = Vec::with_capacity(self.paths.len());
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
+                        winding_rule: fill.winding_rule,
+                    });
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
+                }
+            }
+        }
+
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: fill.winding_rule,
+            })
+        }
+
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo {
+                    x: self.fill_start.0,
+                    y: self.fill_start.1,
+                });
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo {
+                    x: self.fill_start.0,
+                    y: self.fill_start.1,
+                });
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        paths
+    }
+
     pub fn self_bounds(&self) -> &Rectangle<Twips> {
         &self.shape_bounds
     }
@@ -308,7 +353,11 @@ impl Drawing {
         for path in &self.paths {
             match path {
                 DrawingPath::Fill(fill) => {
-                    if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+                    if shape_utils::draw_command_fill_hit_test(
+                        &fill.commands,
+                        fill.winding_rule,
+                        point,
+                    ) {
                         return true;
                     }
                 }
@@ -327,7 +376,7 @@ impl Drawing {
 
         // The pending fill will auto-close.
         if let Some(fill) = &self.current_fill {
-            if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, fill.winding_rule, point) {
                 return true;
             }
         }
@@ -416,6 +465,7 @@ impl BitmapSource for Drawing {
 struct DrawingFill {
     style: FillStyle,
     commands: Vec<DrawCommand>,
+    winding_rule: FillRule,
 }
 
 #[derive(Debug, Clone)]
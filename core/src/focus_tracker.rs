@@ -1,20 +1,48 @@
 use crate::avm1::Avm1;
 use crate::avm1::Value;
-use crate::context::UpdateContext;
+use crate::avm2::{Activation as Avm2Activation, Avm2, Value as Avm2Value};
+use crate::context::{RenderContext, UpdateContext};
 pub use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{InteractiveObject, TInteractiveObject, TextSelection};
 use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use swf::{Color, Twips};
+
+/// The color of the rectangle drawn around objects focused with the Tab key.
+const FOCUS_RECT_COLOR: Color = Color::from_rgb(0xFFFF00, 255);
+
+/// The thickness of the focus rectangle, in pixels.
+const FOCUS_RECT_THICKNESS: f32 = 2.0;
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
 
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
-pub struct FocusTracker<'gc>(GcCell<'gc, Option<DisplayObject<'gc>>>);
+struct FocusTrackerData<'gc> {
+    /// The focused object, if any.
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether the focus was moved with the keyboard, in which case a focus
+    /// rectangle is drawn around the focused object.
+    highlight: bool,
+}
 
 impl<'gc> FocusTracker<'gc> {
     pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
-        Self(GcCell::allocate(gc_context, None))
+        Self(GcCell::allocate(
+            gc_context,
+            FocusTrackerData {
+                focus: None,
+                highlight: false,
+            },
+        ))
     }
 
     pub fn get(&self) -> Option<DisplayObject<'gc>> {
-        *self.0.read()
+        self.0.read().focus
     }
 
     pub fn set(
@@ -27,7 +55,11 @@ impl<'gc> FocusTracker<'gc> {
                 context.ui.open_virtual_keyboard();
             }
         }
-        let old = std::mem::replace(&mut *self.0.write(context.gc_context), focused_element);
+        let old = {
+            let mut write = self.0.write(context.gc_context);
+            write.highlight = false;
+            std::mem::replace(&mut write.focus, focused_element)
+        };
 
         if old.is_none() && focused_element.is_none() {
             // We didn't have anything, we still don't, no change.
@@ -61,4 +93,218 @@ impl<'gc> FocusTracker<'gc> {
             ],
         );
     }
+
+    /// Move the focus to the next object in the tab order, or to the previous
+    /// one if `reverse` is set, as when the user presses Tab or Shift+Tab.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc>, reverse: bool) {
+        let tab_order = Self::tab_order(context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let current = self.get().and_then(|focus| {
+            tab_order
+                .iter()
+                .position(|object| DisplayObject::ptr_eq(object.as_displayobject(), focus))
+        });
+        let len = tab_order.len();
+        let next = match (current, reverse) {
+            (Some(current), false) => (current + 1) % len,
+            (Some(current), true) => (current + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+        let next = tab_order[next].as_displayobject();
+
+        // Tab key code.
+        if !self.dispatch_focus_change(context, "keyFocusChange", next, reverse, 9) {
+            return;
+        }
+
+        self.set(Some(next), context);
+        self.0.write(context.gc_context).highlight = true;
+
+        // Text fields focused with the keyboard have all of their text selected.
+        if let Some(text) = next.as_edit_text() {
+            text.set_selection(
+                Some(TextSelection::for_range(0, text.text_length())),
+                context.gc_context,
+            );
+        }
+    }
+
+    /// Dispatch the AVM2 `mouseFocusChange` event for an attempt to focus an
+    /// object by clicking it.
+    ///
+    /// Returns `false` if a script prevented the focus change.
+    pub fn allow_mouse_focus_change(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        new_focus: DisplayObject<'gc>,
+    ) -> bool {
+        if self
+            .get()
+            .map_or(false, |focus| DisplayObject::ptr_eq(focus, new_focus))
+        {
+            return true;
+        }
+
+        self.dispatch_focus_change(context, "mouseFocusChange", new_focus, false, 0)
+    }
+
+    /// Dispatch an AVM2 `FocusEvent` to the focused object (or the stage) for
+    /// an attempt to move the focus to `new_focus`.
+    ///
+    /// Returns `false` if a script prevented the focus change.
+    fn dispatch_focus_change(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        event_type: &'static str,
+        new_focus: DisplayObject<'gc>,
+        shift_key: bool,
+        key_code: u32,
+    ) -> bool {
+        if !context.is_action_script_3() {
+            return true;
+        }
+
+        let target = self.get().unwrap_or_else(|| context.stage.into());
+        let target = match target.object2() {
+            Avm2Value::Object(target) => target,
+            _ => return true,
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let focus_event_class = activation.avm2().classes().focusevent;
+        let event = match focus_event_class.construct(
+            &mut activation,
+            &[
+                event_type.into(),   /* type */
+                true.into(),         /* bubbles */
+                true.into(),         /* cancelable */
+                new_focus.object2(), /* relatedObject */
+                shift_key.into(),    /* shiftKey */
+                key_code.into(),     /* keyCode */
+            ],
+        ) {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!("Failed to construct FocusEvent: {}", e);
+                return true;
+            }
+        };
+
+        match Avm2::dispatch_event(&mut activation.context, event, target) {
+            Ok(was_not_cancelled) => was_not_cancelled,
+            Err(e) => {
+                tracing::error!(
+                    "Encountered AVM2 error when dispatching `{}` event: {}",
+                    event_type,
+                    e
+                );
+                true
+            }
+        }
+    }
+
+    /// Find the objects that can be focused with the Tab key, in tab order.
+    ///
+    /// If any of them has a `tabIndex`, only the objects with a `tabIndex`
+    /// are included, ordered by it. Otherwise, objects are ordered by their
+    /// position on the stage: top to bottom, then left to right.
+    ///
+    /// TODO: Flash Player's automatic order uses an undocumented heuristic
+    /// that groups objects into rows even when their tops aren't aligned, so
+    /// the order above only matches it when objects are laid out in distinct
+    /// rows.
+    fn tab_order(context: &mut UpdateContext<'_, 'gc>) -> Vec<InteractiveObject<'gc>> {
+        let stage = context.stage;
+        let mut tab_order = vec![];
+        Self::collect_tab_candidates(context, stage.into(), &mut tab_order);
+
+        if tab_order.iter().any(|object| object.tab_index().is_some()) {
+            tab_order.retain(|object| object.tab_index().is_some());
+            tab_order.sort_by_key(|object| object.tab_index());
+        } else {
+            tab_order.sort_by_key(|object| {
+                let bounds = object.as_displayobject().world_bounds();
+                (bounds.y_min, bounds.x_min)
+            });
+        }
+
+        tab_order
+    }
+
+    /// Collect the visible objects below `parent` that can be focused with the
+    /// Tab key, in display list order.
+    fn collect_tab_candidates(
+        context: &mut UpdateContext<'_, 'gc>,
+        parent: DisplayObject<'gc>,
+        candidates: &mut Vec<InteractiveObject<'gc>>,
+    ) {
+        let container = match parent.as_container() {
+            Some(container) => container,
+            None => return,
+        };
+
+        for child in container.iter_render_list() {
+            if !child.visible() {
+                continue;
+            }
+
+            let interactive = child.as_interactive();
+            if let Some(interactive) = interactive {
+                if interactive.tab_enabled(context) {
+                    candidates.push(interactive);
+                }
+            }
+
+            if interactive.map_or(true, |interactive| interactive.tab_children()) {
+                Self::collect_tab_candidates(context, child, candidates);
+            }
+        }
+    }
+
+    /// Draw the focus rectangle around the focused object, if it was focused
+    /// with the keyboard.
+    pub fn render_highlight(&self, context: &mut RenderContext<'_, 'gc>) {
+        let data = self.0.read();
+        let focus = match data.focus {
+            Some(focus) if data.highlight && focus.visible() => focus,
+            _ => return,
+        };
+
+        // Text fields show their caret instead.
+        if focus.as_edit_text().is_some() {
+            return;
+        }
+
+        let show_focus_rect = focus
+            .as_interactive()
+            .and_then(|interactive| interactive.focus_rect())
+            .unwrap_or_else(|| context.stage.stage_focus_rect());
+        if !show_focus_rect {
+            return;
+        }
+
+        let bounds = focus.world_bounds();
+        let view_matrix = *context.stage.base().matrix();
+        let (x_min, y_min) = view_matrix * (bounds.x_min, bounds.y_min);
+        let (x_max, y_max) = view_matrix * (bounds.x_max, bounds.y_max);
+        let width = (x_max - x_min).to_pixels() as f32;
+        let height = (y_max - y_min).to_pixels() as f32;
+        let thickness = Twips::from_pixels(FOCUS_RECT_THICKNESS.into());
+
+        for (width, height, x, y) in [
+            (width, FOCUS_RECT_THICKNESS, x_min, y_min),
+            (width, FOCUS_RECT_THICKNESS, x_min, y_max - thickness),
+            (FOCUS_RECT_THICKNESS, height, x_min, y_min),
+            (FOCUS_RECT_THICKNESS, height, x_max - thickness, y_min),
+        ] {
+            context.commands.draw_rect(
+                FOCUS_RECT_COLOR,
+                Matrix::create_box(width, height, 0.0, x, y),
+            );
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::backend::font::FontFile;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
@@ -6,7 +7,11 @@ use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::transform::Transform;
 use std::cell::{Cell, Ref, RefCell};
 use std::cmp::max;
+use std::rc::Rc;
 
+mod face;
+
+use face::FontFace;
 pub use swf::TextGridFit;
 
 /// Certain Flash routines measure text by rounding down to the nearest whole pixel.
@@ -35,8 +40,7 @@ pub struct EvalParameters {
 
 impl EvalParameters {
     /// Construct eval parameters from their individual parts.
-    #[allow(dead_code)]
-    fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
+    pub fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
         Self {
             height,
             letter_spacing,
@@ -69,7 +73,7 @@ pub struct Font<'gc>(Gc<'gc, FontData>);
 struct FontData {
     /// The list of glyphs defined in the font.
     /// Used directly by `DefineText` tags.
-    glyphs: Vec<Glyph>,
+    glyphs: Vec<Rc<Glyph>>,
 
     /// A map from a Unicode code point to glyph in the `glyphs` array.
     /// Used by `DefineEditText` tags.
@@ -97,6 +101,31 @@ struct FontData {
 
     /// The identity of the font.
     descriptor: FontDescriptor,
+
+    /// The font file this font was loaded from, if it is a device font
+    /// provided by the font backend or an embedded DefineFont4 font.
+    face: Option<Rc<FontFace>>,
+
+    /// The glyphs of `face` by code point, built as characters are first
+    /// used. `None` records that the face has no glyph for the character.
+    face_glyphs: RefCell<fnv::FnvHashMap<u16, Option<Rc<Glyph>>>>,
+
+    /// Where this font came from.
+    font_type: FontType,
+}
+
+/// The origin of a font, as reported by `flash.text.Font.fontType`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FontType {
+    /// A font embedded with a DefineFont1/2/3 tag.
+    Embedded,
+
+    /// An OpenType font embedded with a DefineFont4 tag, for use by
+    /// `flash.text.engine`.
+    EmbeddedCFF,
+
+    /// A system font provided by the font backend.
+    Device,
 }
 
 impl<'gc> Font<'gc> {
@@ -129,10 +158,10 @@ impl<'gc> Font<'gc> {
                 shape_handle: Cell::new(handle),
                 advance: swf_glyph.advance,
                 shape: RefCell::new(None),
-                swf_glyph,
+                outline: GlyphOutline::Swf(swf_glyph),
             };
             let index = glyphs.len();
-            glyphs.push(glyph);
+            glyphs.push(Rc::new(glyph));
             code_point_to_glyph.insert(glyph_code, index);
         }
         let kerning_pairs: fnv::FnvHashMap<(u16, u16), Twips> = if let Some(layout) = &tag.layout {
@@ -159,32 +188,73 @@ impl<'gc> Font<'gc> {
                 descent,
                 leading,
                 descriptor,
+                face: None,
+                face_glyphs: Default::default(),
+                font_type: FontType::Embedded,
             },
         ))
     }
 
+    /// Load a font from a TrueType or OpenType font file, either a system
+    /// font or the data of a DefineFont4 tag.
+    ///
+    /// Returns `None` if the file could not be parsed.
+    pub fn from_font_file(
+        gc_context: MutationContext<'gc, '_>,
+        file: FontFile,
+        descriptor: FontDescriptor,
+        font_type: FontType,
+    ) -> Option<Font<'gc>> {
+        let font_face = Rc::new(FontFace::new(file)?);
+        let (ascent, descent, leading) = font_face.metrics();
+
+        Some(Font(Gc::allocate(
+            gc_context,
+            FontData {
+                glyphs: vec![],
+                code_point_to_glyph: fnv::FnvHashMap::default(),
+                scale: face::EM_SIZE,
+                kerning_pairs: fnv::FnvHashMap::default(),
+                ascent,
+                descent,
+                leading,
+                descriptor,
+                face: Some(font_face),
+                face_glyphs: Default::default(),
+                font_type,
+            },
+        )))
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
-        !self.0.glyphs.is_empty()
+        !self.0.glyphs.is_empty() || self.0.face.is_some()
     }
 
     /// Returns a glyph entry by index.
     /// Used by `Text` display objects.
     pub fn get_glyph(&self, i: usize) -> Option<&Glyph> {
-        self.0.glyphs.get(i)
+        self.0.glyphs.get(i).map(|glyph| &**glyph)
     }
 
     /// Returns a glyph entry by character.
     /// Used by `EditText` display objects.
-    pub fn get_glyph_for_char(&self, c: char) -> Option<&Glyph> {
+    pub fn get_glyph_for_char(&self, c: char) -> Option<Rc<Glyph>> {
         // TODO: Properly handle UTF-16/out-of-bounds code points.
         let code_point = c as u16;
-        if let Some(index) = self.0.code_point_to_glyph.get(&code_point) {
-            self.get_glyph(*index)
-        } else {
-            None
+        if let Some(face) = &self.0.face {
+            return self
+                .0
+                .face_glyphs
+                .borrow_mut()
+                .entry(code_point)
+                .or_insert_with(|| Glyph::from_face(face, code_point).map(Rc::new))
+                .clone();
         }
+
+        let index = self.0.code_point_to_glyph.get(&code_point)?;
+        self.0.glyphs.get(*index).cloned()
     }
 
     /// Determine if this font contains all the glyphs within a given string.
@@ -203,6 +273,25 @@ impl<'gc> Font<'gc> {
     /// to the advance value between these two characters.
     /// Returns 0 twips if no kerning offset exists between these two characters.
     pub fn get_kerning_offset(&self, left: char, right: char) -> Twips {
+        // Device fonts are kerned between glyphs, rather than code points.
+        if let Some(face) = &self.0.face {
+            return match (
+                self.get_glyph_for_char(left),
+                self.get_glyph_for_char(right),
+            ) {
+                (Some(left), Some(right)) => match (&left.outline, &right.outline) {
+                    (
+                        GlyphOutline::FontFile { glyph_id: left, .. },
+                        GlyphOutline::FontFile {
+                            glyph_id: right, ..
+                        },
+                    ) => Twips::new(face.kerning(*left, *right)),
+                    _ => Twips::ZERO,
+                },
+                _ => Twips::ZERO,
+            };
+        }
+
         // TODO: Properly handle UTF-16/out-of-bounds code points.
         let left_code_point = left as u16;
         let right_code_point = right as u16;
@@ -236,7 +325,10 @@ impl<'gc> Font<'gc> {
 
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
-        !self.0.kerning_pairs.is_empty()
+        match &self.0.face {
+            Some(face) => face.has_kerning(),
+            None => !self.0.kerning_pairs.is_empty(),
+        }
     }
 
     pub fn scale(&self) -> f32 {
@@ -280,7 +372,7 @@ impl<'gc> Font<'gc> {
                 let twips_advance =
                     Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing;
 
-                glyph_func(pos, &transform, glyph, twips_advance, x);
+                glyph_func(pos, &transform, &*glyph, twips_advance, x);
 
                 // Step horizontally.
                 transform.matrix.tx += twips_advance;
@@ -289,6 +381,26 @@ impl<'gc> Font<'gc> {
         }
     }
 
+    /// Returns the advance of a single character, including letter spacing
+    /// and any kerning against the character following it.
+    ///
+    /// Returns `None` if the font has no glyph for the character.
+    pub fn get_char_advance(
+        &self,
+        c: char,
+        next: Option<char>,
+        params: EvalParameters,
+    ) -> Option<Twips> {
+        let glyph = self.get_glyph_for_char(c)?;
+        let mut advance = Twips::new(glyph.advance.into());
+        if params.kerning && self.has_kerning_info() {
+            advance += self.get_kerning_offset(c, next.unwrap_or('\0'));
+        }
+        let scale = params.height.get() as f32 / self.scale();
+
+        Some(Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing)
+    }
+
     /// Measure a particular string's metrics (width and height).
     ///
     /// The `round` flag causes the returned coordinates to be rounded down to
@@ -412,6 +524,14 @@ impl<'gc> Font<'gc> {
     pub fn descriptor(&self) -> &FontDescriptor {
         &self.0.descriptor
     }
+
+    pub fn font_type(&self) -> FontType {
+        self.0.font_type
+    }
+
+    pub fn ptr_eq(a: Font<'gc>, b: Font<'gc>) -> bool {
+        Gc::ptr_eq(a.0, b.0)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -426,14 +546,68 @@ pub struct Glyph {
     // this literally copies the shape_record, which is wasteful...)
     shape: RefCell<Option<swf::Shape>>,
     // The underlying glyph record, containing its shape.
-    swf_glyph: swf::Glyph,
+    outline: GlyphOutline,
+}
+
+/// Where the shape of a glyph comes from.
+#[derive(Debug, Clone)]
+enum GlyphOutline {
+    /// A glyph defined by a `DefineFont` tag.
+    Swf(swf::Glyph),
+
+    /// A glyph of a font file.
+    /// The glyph record is converted from the font's outline on first use.
+    FontFile {
+        face: Rc<FontFace>,
+        glyph_id: u16,
+        code: u16,
+        swf_glyph: RefCell<Option<swf::Glyph>>,
+    },
 }
 
 impl Glyph {
+    /// Build the glyph of a font file for a character, if the font has one.
+    /// Its shape is converted from the font's outline on first use.
+    fn from_face(face: &Rc<FontFace>, code_point: u16) -> Option<Self> {
+        let ttf_face = face.face();
+        let glyph_id = char::from_u32(code_point.into())
+            .and_then(|c| ttf_face.glyph_index(c))?
+            .0;
+        Some(Self {
+            shape_handle: Cell::new(None),
+            advance: face.advance(&ttf_face, glyph_id),
+            shape: RefCell::new(None),
+            outline: GlyphOutline::FontFile {
+                face: face.clone(),
+                glyph_id,
+                code: code_point,
+                swf_glyph: RefCell::new(None),
+            },
+        })
+    }
+
+    /// Calls `f` with the underlying glyph record.
+    fn with_swf_glyph<R>(&self, f: impl FnOnce(&swf::Glyph) -> R) -> R {
+        match &self.outline {
+            GlyphOutline::Swf(swf_glyph) => f(swf_glyph),
+            GlyphOutline::FontFile {
+                face,
+                glyph_id,
+                code,
+                swf_glyph,
+            } => {
+                let mut swf_glyph = swf_glyph.borrow_mut();
+                let swf_glyph = swf_glyph
+                    .get_or_insert_with(|| face.outline_glyph(*glyph_id, *code, self.advance));
+                f(swf_glyph)
+            }
+        }
+    }
+
     pub fn shape_handle(&self, renderer: &mut dyn RenderBackend) -> ShapeHandle {
         if self.shape_handle.get().is_none() {
-            self.shape_handle
-                .set(Some(renderer.register_glyph_shape(&self.swf_glyph)))
+            let handle = self.with_swf_glyph(|swf_glyph| renderer.register_glyph_shape(swf_glyph));
+            self.shape_handle.set(Some(handle));
         }
         self.shape_handle.get().unwrap()
     }
@@ -441,9 +615,7 @@ impl Glyph {
     pub fn as_shape(&self) -> Ref<'_, swf::Shape> {
         let mut write = self.shape.borrow_mut();
         if write.is_none() {
-            *write = Some(ruffle_render::shape_utils::swf_glyph_to_shape(
-                &self.swf_glyph,
-            ));
+            *write = Some(self.with_swf_glyph(ruffle_render::shape_utils::swf_glyph_to_shape));
         }
         drop(write);
         let read = self.shape.borrow();
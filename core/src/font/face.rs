@@ -0,0 +1,482 @@
+//! Device fonts loaded from TrueType and OpenType font files.
+
+use crate::backend::font::FontFile;
+use ruffle_render::shape_utils::{cubic_curve_to_quadratics, DrawCommand};
+use std::cell::RefCell;
+use std::fmt;
+use swf::{Rectangle, ShapeRecord, StyleChangeData, Twips};
+use ttf_parser::gpos::{PairAdjustment, PositioningSubtable};
+use ttf_parser::{Face, GlyphId, OutlineBuilder, Tag};
+
+/// The size of the EM square that glyphs are scaled to, the same as for
+/// `DefineFont3` glyphs.
+pub const EM_SIZE: f32 = 20480.0;
+
+/// A face of a TrueType or OpenType font file.
+///
+/// Glyph outlines and kerning are read from the font data on demand, as
+/// system fonts can contain tens of thousands of glyphs.
+pub struct FontFace {
+    data: Vec<u8>,
+    index: u32,
+
+    /// The scale from font units to the EM square used for glyphs.
+    scale: f32,
+
+    /// The lookups of the GPOS `kern` feature, if the face has any.
+    kern_lookups: Vec<u16>,
+
+    /// Kerning adjustments between pairs of glyphs, cached as they're looked up.
+    kerning_cache: RefCell<fnv::FnvHashMap<(u16, u16), i32>>,
+}
+
+impl FontFace {
+    /// Parse a font file, returning `None` if it isn't a usable font.
+    pub fn new(file: FontFile) -> Option<Self> {
+        let face = Face::parse(&file.data, file.index).ok()?;
+        let kern_lookups = face
+            .tables()
+            .gpos
+            .map(|gpos| {
+                let mut lookups: Vec<u16> = gpos
+                    .features
+                    .into_iter()
+                    .filter(|feature| feature.tag == Tag::from_bytes(b"kern"))
+                    .flat_map(|feature| feature.lookup_indices)
+                    .collect();
+                lookups.sort_unstable();
+                lookups.dedup();
+                lookups
+            })
+            .unwrap_or_default();
+
+        let scale = EM_SIZE / f32::from(face.units_per_em());
+
+        Some(Self {
+            data: file.data,
+            index: file.index,
+            scale,
+            kern_lookups,
+            kerning_cache: Default::default(),
+        })
+    }
+
+    /// Parse the face.
+    ///
+    /// This is cheap, and the face has already been validated in `new`.
+    pub fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, self.index).expect("Font face was parsed before")
+    }
+
+    /// Scale a distance in font units to the glyph EM square.
+    fn to_em(&self, value: impl Into<f32>) -> i32 {
+        (value.into() * self.scale).round() as i32
+    }
+
+    /// Returns the ascent, descent and leading of the face, scaled to the
+    /// glyph EM square.
+    pub fn metrics(&self) -> (u16, u16, i16) {
+        let face = self.face();
+        let ascent = self.to_em(face.ascender()).clamp(0, u16::MAX.into());
+        let descent = self
+            .to_em(-f32::from(face.descender()))
+            .clamp(0, u16::MAX.into());
+        let leading = self
+            .to_em(face.line_gap())
+            .clamp(i16::MIN.into(), i16::MAX.into());
+        (ascent as u16, descent as u16, leading as i16)
+    }
+
+    /// Returns the advance of a glyph, scaled to the glyph EM square.
+    pub fn advance(&self, face: &Face<'_>, glyph_id: u16) -> i16 {
+        let advance = face.glyph_hor_advance(GlyphId(glyph_id)).unwrap_or(0);
+        self.to_em(advance).min(i16::MAX.into()) as i16
+    }
+
+    /// Returns whether this face contains kerning information.
+    pub fn has_kerning(&self) -> bool {
+        !self.kern_lookups.is_empty() || self.face().tables().kern.is_some()
+    }
+
+    /// Returns the horizontal adjustment between two glyphs, scaled to the
+    /// glyph EM square.
+    pub fn kerning(&self, left: u16, right: u16) -> i32 {
+        if let Some(kerning) = self.kerning_cache.borrow().get(&(left, right)) {
+            return *kerning;
+        }
+
+        let face = self.face();
+        let (left_id, right_id) = (GlyphId(left), GlyphId(right));
+        let kerning = if self.kern_lookups.is_empty() {
+            face.tables()
+                .kern
+                .map(|kern| {
+                    kern.subtables
+                        .into_iter()
+                        .filter(|subtable| subtable.horizontal && !subtable.variable)
+                        .filter_map(|subtable| subtable.glyphs_kerning(left_id, right_id))
+                        .fold(0i16, |sum, value| sum.saturating_add(value))
+                })
+                .unwrap_or_default()
+        } else {
+            self.gpos_kerning(&face, left_id, right_id)
+        };
+        let kerning = self.to_em(kerning);
+
+        self.kerning_cache
+            .borrow_mut()
+            .insert((left, right), kerning);
+        kerning
+    }
+
+    /// Look up the pair adjustment between two glyphs from the GPOS `kern`
+    /// feature.
+    fn gpos_kerning(&self, face: &Face<'_>, left: GlyphId, right: GlyphId) -> i16 {
+        let gpos = match face.tables().gpos {
+            Some(gpos) => gpos,
+            None => return 0,
+        };
+
+        let mut kerning = 0i16;
+        for lookup in self
+            .kern_lookups
+            .iter()
+            .filter_map(|&index| gpos.lookups.get(index))
+        {
+            // Only the first subtable covering the left glyph applies.
+            for subtable in lookup.subtables.into_iter::<PositioningSubtable>() {
+                let pair = match subtable {
+                    PositioningSubtable::Pair(pair) => pair,
+                    _ => continue,
+                };
+                let coverage_index = match pair.coverage().get(left) {
+                    Some(index) => index,
+                    None => continue,
+                };
+                let value = match pair {
+                    PairAdjustment::Format1 { sets, .. } => sets
+                        .get(coverage_index)
+                        .and_then(|set| set.get(right))
+                        .map(|(value, _)| value.x_advance),
+                    PairAdjustment::Format2 {
+                        classes, matrix, ..
+                    } => matrix
+                        .get((classes.0.get(left), classes.1.get(right)))
+                        .map(|(value, _)| value.x_advance),
+                };
+                if let Some(value) = value {
+                    kerning = kerning.saturating_add(value);
+                    break;
+                }
+            }
+        }
+        kerning
+    }
+
+    /// Convert the outline of a glyph into an SWF glyph.
+    ///
+    /// The Y axis is flipped to point downwards, as in SWF shapes.
+    pub fn outline_glyph(&self, glyph_id: u16, code: u16, advance: i16) -> swf::Glyph {
+        let face = self.face();
+        let mut builder = GlyphBuilder::new(self.scale);
+        let bounds = face
+            .outline_glyph(GlyphId(glyph_id), &mut builder)
+            .map(|rect| Rectangle {
+                x_min: Twips::new(self.to_em(rect.x_min)),
+                x_max: Twips::new(self.to_em(rect.x_max)),
+                y_min: Twips::new(-self.to_em(rect.y_max)),
+                y_max: Twips::new(-self.to_em(rect.y_min)),
+            });
+
+        swf::Glyph {
+            shape_records: builder.records,
+            code,
+            advance,
+            bounds,
+        }
+    }
+}
+
+impl fmt::Debug for FontFace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontFace")
+            .field("len", &self.data.len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Builds SWF shape records from a glyph outline.
+struct GlyphBuilder {
+    records: Vec<ShapeRecord>,
+
+    /// The scale from font units to the glyph EM square.
+    scale: f32,
+
+    /// The current pen position, in the glyph EM square.
+    x: i32,
+    y: i32,
+
+    /// The start of the current contour.
+    start_x: i32,
+    start_y: i32,
+}
+
+impl GlyphBuilder {
+    fn new(scale: f32) -> Self {
+        Self {
+            records: Vec::new(),
+            scale,
+            x: 0,
+            y: 0,
+            start_x: 0,
+            start_y: 0,
+        }
+    }
+
+    /// Scale a point in font units to the glyph EM square.
+    fn round(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x * self.scale).round() as i32,
+            -((y * self.scale).round() as i32),
+        )
+    }
+
+    /// Add a quadratic curve, with points in the glyph EM square.
+    fn curve(&mut self, (control_x, control_y): (i32, i32), (anchor_x, anchor_y): (i32, i32)) {
+        self.records.push(ShapeRecord::CurvedEdge {
+            control_delta_x: Twips::new(control_x - self.x),
+            control_delta_y: Twips::new(control_y - self.y),
+            anchor_delta_x: Twips::new(anchor_x - control_x),
+            anchor_delta_y: Twips::new(anchor_y - control_y),
+        });
+        self.x = anchor_x;
+        self.y = anchor_y;
+    }
+}
+
+impl OutlineBuilder for GlyphBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x_rounded, y_rounded) = self.round(x, y);
+        // The first contour also selects the glyph's fill.
+        let fill_style_1 = if self.records.is_empty() {
+            Some(1)
+        } else {
+            None
+        };
+        self.records
+            .push(ShapeRecord::StyleChange(Box::new(StyleChangeData {
+                move_to: Some((Twips::new(x_rounded), Twips::new(y_rounded))),
+                fill_style_0: None,
+                fill_style_1,
+                line_style: None,
+                new_styles: None,
+            })));
+        self.x = x_rounded;
+        self.y = y_rounded;
+        self.start_x = x_rounded;
+        self.start_y = y_rounded;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x_rounded, y_rounded) = self.round(x, y);
+        self.records.push(ShapeRecord::StraightEdge {
+            delta_x: Twips::new(x_rounded - self.x),
+            delta_y: Twips::new(y_rounded - self.y),
+        });
+        self.x = x_rounded;
+        self.y = y_rounded;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.curve(self.round(x1, y1), self.round(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // SWF shapes only have quadratic curves.
+        let to_twips = |(x, y): (i32, i32)| (Twips::new(x), Twips::new(y));
+        let commands = cubic_curve_to_quadratics(
+            to_twips((self.x, self.y)),
+            to_twips(self.round(x1, y1)),
+            to_twips(self.round(x2, y2)),
+            to_twips(self.round(x, y)),
+        );
+        for command in commands {
+            if let DrawCommand::CurveTo { x1, y1, x2, y2 } = command {
+                self.curve((x1.get(), y1.get()), (x2.get(), y2.get()));
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        // Fills must be closed, but font contours may end short of the start.
+        if (self.x, self.y) != (self.start_x, self.start_y) {
+            self.records.push(ShapeRecord::StraightEdge {
+                delta_x: Twips::new(self.start_x - self.x),
+                delta_y: Twips::new(self.start_y - self.y),
+            });
+            self.x = self.start_x;
+            self.y = self.start_y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a font with 1000 units per EM and three empty glyphs, each
+    /// 500 units wide, along with the given extra tables.
+    fn build_font(extra_tables: &[(&[u8; 4], Vec<u8>)]) -> FontFile {
+        let mut head = vec![0; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+
+        let mut hhea = vec![0; 36];
+        hhea[4..6].copy_from_slice(&800i16.to_be_bytes());
+        hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+        hhea[8..10].copy_from_slice(&90i16.to_be_bytes());
+        hhea[34..36].copy_from_slice(&3u16.to_be_bytes());
+
+        let mut maxp = 0x5000u32.to_be_bytes().to_vec();
+        maxp.extend_from_slice(&3u16.to_be_bytes());
+
+        // An advance of 500 and a left side bearing of 0 for each glyph.
+        let hmtx = [0x01, 0xF4, 0, 0].repeat(3);
+
+        let mut tables: Vec<(&[u8; 4], Vec<u8>)> = vec![
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"maxp", maxp),
+            (b"hmtx", hmtx),
+        ];
+        tables.extend(extra_tables.iter().cloned());
+        tables.sort_by_key(|(tag, _)| **tag);
+
+        let mut data = 0x00010000u32.to_be_bytes().to_vec();
+        data.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        data.extend_from_slice(&[0; 6]);
+        let mut offset = data.len() + tables.len() * 16;
+        for (tag, table) in &tables {
+            data.extend_from_slice(&tag[..]);
+            data.extend_from_slice(&[0; 4]);
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            offset += (table.len() + 3) & !3;
+        }
+        for (_, table) in &tables {
+            data.extend_from_slice(table);
+            data.resize((data.len() + 3) & !3, 0);
+        }
+
+        FontFile { data, index: 0 }
+    }
+
+    /// Builds a `kern` table with a single format 0 subtable.
+    fn kern_table(pairs: &[(u16, u16, i16)]) -> Vec<u8> {
+        let mut subtable = vec![];
+        subtable.extend_from_slice(&(pairs.len() as u16).to_be_bytes());
+        subtable.extend_from_slice(&[0; 6]);
+        for (left, right, value) in pairs {
+            subtable.extend_from_slice(&left.to_be_bytes());
+            subtable.extend_from_slice(&right.to_be_bytes());
+            subtable.extend_from_slice(&value.to_be_bytes());
+        }
+
+        // Version 0, one subtable.
+        let mut table = vec![0, 0, 0, 1];
+        // Subtable version, length, format 0 and horizontal coverage.
+        table.extend_from_slice(&[0, 0]);
+        table.extend_from_slice(&(subtable.len() as u16 + 6).to_be_bytes());
+        table.extend_from_slice(&[0, 1]);
+        table.extend(subtable);
+        table
+    }
+
+    #[test]
+    fn metrics_are_scaled_to_em_square() {
+        let face = FontFace::new(build_font(&[])).unwrap();
+        assert_eq!(face.metrics(), (16384, 4096, 1843));
+        assert_eq!(face.advance(&face.face(), 1), 10240);
+    }
+
+    #[test]
+    fn kerning_from_kern_table() {
+        let kern = kern_table(&[(1, 2, -100), (2, 1, 50)]);
+        let face = FontFace::new(build_font(&[(b"kern", kern)])).unwrap();
+
+        assert!(face.has_kerning());
+        assert_eq!(face.kerning(1, 2), -2048);
+        assert_eq!(face.kerning(2, 1), 1024);
+        assert_eq!(face.kerning(1, 1), 0);
+        // Cached lookups give the same result.
+        assert_eq!(face.kerning(1, 2), -2048);
+    }
+
+    #[test]
+    fn no_kerning() {
+        let face = FontFace::new(build_font(&[])).unwrap();
+        assert!(!face.has_kerning());
+        assert_eq!(face.kerning(1, 2), 0);
+    }
+
+    /// Returns the points that the pen passes through.
+    fn trace(records: &[ShapeRecord]) -> Vec<(i32, i32)> {
+        let mut points = vec![];
+        let (mut x, mut y) = (0, 0);
+        for record in records {
+            match record {
+                ShapeRecord::StyleChange(style_change) => {
+                    if let Some((move_x, move_y)) = style_change.move_to {
+                        x = move_x.get();
+                        y = move_y.get();
+                    }
+                }
+                ShapeRecord::StraightEdge { delta_x, delta_y } => {
+                    x += delta_x.get();
+                    y += delta_y.get();
+                }
+                ShapeRecord::CurvedEdge {
+                    control_delta_x,
+                    control_delta_y,
+                    anchor_delta_x,
+                    anchor_delta_y,
+                } => {
+                    x += control_delta_x.get() + anchor_delta_x.get();
+                    y += control_delta_y.get() + anchor_delta_y.get();
+                }
+            }
+            points.push((x, y));
+        }
+        points
+    }
+
+    #[test]
+    fn contours_are_closed() {
+        let mut builder = GlyphBuilder::new(1.0);
+        builder.move_to(0.0, 0.0);
+        builder.line_to(100.0, 0.0);
+        builder.line_to(100.0, 100.0);
+        builder.close();
+        let points = trace(&builder.records);
+        assert_eq!(points, [(0, 0), (100, 0), (100, -100), (0, 0)]);
+    }
+
+    #[test]
+    fn cubic_curves_become_quadratic_curves() {
+        let mut builder = GlyphBuilder::new(1.0);
+        builder.move_to(0.0, 0.0);
+        builder.curve_to(0.0, 1000.0, 1000.0, 1000.0, 1000.0, 0.0);
+
+        let curves = builder
+            .records
+            .iter()
+            .filter(|record| matches!(record, ShapeRecord::CurvedEdge { .. }))
+            .count();
+        assert!(curves > 1);
+
+        let points = trace(&builder.records);
+        assert_eq!(points.last(), Some(&(1000, 0)));
+        // The curve is split evenly, so its midpoint is one of the anchors.
+        assert!(points.contains(&(500, -750)));
+    }
+}
@@ -1,14 +1,21 @@
 //! HTML related utilities
 
 mod dimensions;
+mod fte;
 mod iterators;
 mod layout;
+mod style_sheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
+pub use fte::{
+    BreakOpportunity, LineJustification, LineLayout, RunContent, TabAlignment, TabStop,
+    TextBlockLayout, TextBlockRun,
+};
 pub use layout::{LayoutBox, LayoutContent, LayoutMetrics};
+pub use style_sheet::{parse_css, CssRules, Display, Style, StyleSheet, STYLE_PROPERTIES};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -0,0 +1,727 @@
+//! Line layout for `flash.text.engine`.
+//!
+//! A text block is a single paragraph made out of runs of text sharing one
+//! format, and inline graphics. `TextBlock.createTextLine` breaks it into
+//! lines one at a time. The block is prepared once, when its first line is
+//! created, and each line is then broken out of it in turn.
+
+use crate::drawing::Drawing;
+use crate::font::{EvalParameters, Font};
+use crate::string::{WStr, WString};
+use gc_arena::Collect;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use swf::{Color, FillStyle, Twips};
+use unicode_bidi::{BidiInfo, Level};
+
+/// The spacing of the tab stops used when a text block has none.
+const DEFAULT_TAB_INTERVAL: f64 = 48.0;
+
+/// Where a line may be broken within a run of text.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Collect)]
+#[collect(require_static)]
+pub enum BreakOpportunity {
+    /// Lines break at spaces, hyphens and around ideographs.
+    Auto,
+
+    /// Lines may break between any two characters.
+    Any,
+
+    /// Every character is on a line of its own.
+    All,
+
+    /// Lines never break within the run.
+    None,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Collect)]
+#[collect(require_static)]
+pub enum TabAlignment {
+    Start,
+    Center,
+    End,
+    Decimal,
+}
+
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+pub struct TabStop {
+    pub alignment: TabAlignment,
+    pub position: Twips,
+
+    /// The text that is aligned to a decimal tab stop, usually a decimal
+    /// point.
+    pub decimal_alignment_token: WString,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Collect)]
+#[collect(require_static)]
+pub enum LineJustification {
+    Unjustified,
+    AllButLast,
+    AllIncludingLastLine,
+}
+
+/// What a run of a text block displays.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub enum RunContent<'gc> {
+    Text {
+        font: Font<'gc>,
+        params: EvalParameters,
+        #[collect(require_static)]
+        color: Color,
+
+        /// How far the run is moved down from the baseline.
+        #[collect(require_static)]
+        baseline_shift: Twips,
+    },
+
+    /// A graphic element, sitting on the baseline. Its single character is
+    /// `ContentElement.GRAPHIC_ELEMENT`.
+    Graphic {
+        #[collect(require_static)]
+        width: Twips,
+        #[collect(require_static)]
+        height: Twips,
+    },
+}
+
+/// A range of the text of a block that shares the same content element.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct TextBlockRun<'gc> {
+    pub start: usize,
+    pub end: usize,
+    pub content: RunContent<'gc>,
+    pub break_opportunity: BreakOpportunity,
+}
+
+/// The smallest indivisible unit of a line, a single character or graphic.
+#[derive(Clone, Debug)]
+pub struct LineAtom {
+    /// The range of the text of the block covered by this atom.
+    pub start: usize,
+    pub end: usize,
+
+    /// The index of the run this atom belongs to.
+    pub run: usize,
+
+    /// The horizontal position of the atom within the line, in visual order.
+    pub x: Twips,
+    pub width: Twips,
+
+    /// The extent of the atom above and below the baseline.
+    pub ascent: Twips,
+    pub descent: Twips,
+
+    pub bidi_level: u8,
+}
+
+/// A line broken from a text block.
+#[derive(Clone, Debug)]
+pub struct LineLayout {
+    /// The range of the text of the block on this line.
+    pub start: usize,
+    pub end: usize,
+
+    /// The atoms of the line, in logical order.
+    pub atoms: Vec<LineAtom>,
+
+    pub ascent: Twips,
+    pub descent: Twips,
+    pub text_width: Twips,
+    pub unjustified_text_width: Twips,
+    pub has_tabs: bool,
+
+    /// Whether the line had to be broken in the middle of a word, because
+    /// no break opportunity fit.
+    pub is_emergency: bool,
+}
+
+/// A text block prepared for breaking into lines.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct TextBlockLayout<'gc> {
+    #[collect(require_static)]
+    text: WString,
+    runs: Vec<TextBlockRun<'gc>>,
+    tab_stops: Vec<TabStop>,
+    justification: LineJustification,
+    paragraph_level: u8,
+
+    /// The resolved bidi level of each code unit of `text`.
+    levels: Vec<u8>,
+}
+
+impl<'gc> TextBlockLayout<'gc> {
+    /// Prepare a text block for layout.
+    ///
+    /// The runs must be sorted and cover all of `text`. The tab stops must be
+    /// sorted by position.
+    pub fn new(
+        text: WString,
+        runs: Vec<TextBlockRun<'gc>>,
+        tab_stops: Vec<TabStop>,
+        justification: LineJustification,
+        bidi_level: u8,
+    ) -> Self {
+        let paragraph_level = bidi_level & 1;
+
+        let mut utf8 = String::with_capacity(text.len());
+        let mut offsets = Vec::with_capacity(text.len());
+        for (i, c) in text.char_indices() {
+            offsets.push((i, utf8.len()));
+            utf8.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+
+        let mut levels = vec![paragraph_level; text.len()];
+        let default_level = Level::new(paragraph_level).ok();
+        let bidi_info = BidiInfo::new(&utf8, default_level);
+        for (n, &(start, byte)) in offsets.iter().enumerate() {
+            let end = offsets.get(n + 1).map_or(text.len(), |&(end, _)| end);
+            let level = bidi_info.levels[byte].number();
+            levels[start..end].fill(level);
+        }
+
+        Self {
+            text,
+            runs,
+            tab_stops,
+            justification,
+            paragraph_level,
+            levels,
+        }
+    }
+
+    /// The character starting at the given position, and its length in code
+    /// units.
+    fn char_at(&self, i: usize) -> (char, usize) {
+        match self.text.slice(i..).and_then(|rest| rest.chars().next()) {
+            Some(Ok(c)) => (c, c.len_utf16()),
+            _ => (char::REPLACEMENT_CHARACTER, 1),
+        }
+    }
+
+    /// The length of the text of the block.
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    fn run_at(&self, i: usize) -> Option<usize> {
+        let index = self.runs.partition_point(|run| run.end <= i);
+        self.runs
+            .get(index)
+            .filter(|run| run.start <= i)
+            .map(|_| index)
+    }
+
+    /// The advance of the character at the given position, without tabs.
+    fn advance(&self, run: &TextBlockRun<'gc>, c: char, end: usize) -> Twips {
+        match &run.content {
+            RunContent::Text { font, params, .. } => {
+                if is_line_separator(c) {
+                    return Twips::ZERO;
+                }
+                let next = if end < run.end {
+                    Some(self.char_at(end).0)
+                } else {
+                    None
+                };
+                font.get_char_advance(c, next, *params).unwrap_or_default()
+            }
+            RunContent::Graphic { width, .. } => *width,
+        }
+    }
+
+    /// The width of the text from `start` up to the next tab, line separator
+    /// or, if given, decimal alignment token.
+    fn segment_width(&self, start: usize, token: Option<&WStr>) -> Twips {
+        let mut width = Twips::ZERO;
+        let mut i = start;
+        while i < self.text.len() {
+            if let Some(token) = token {
+                let at_token = self
+                    .text
+                    .slice(i..)
+                    .map_or(false, |rest| rest.starts_with(token));
+                if !token.is_empty() && at_token {
+                    break;
+                }
+            }
+            let (c, len) = self.char_at(i);
+            if c == '\t' || is_line_separator(c) {
+                break;
+            }
+            if let Some(run) = self.run_at(i) {
+                width += self.advance(&self.runs[run], c, i + len);
+            }
+            i += len;
+        }
+        width
+    }
+
+    /// The advance of a tab at the given pen position.
+    fn tab_advance(&self, pen: Twips, end: usize) -> Twips {
+        let stop = match self.tab_stops.iter().find(|stop| stop.position > pen) {
+            Some(stop) => stop,
+            None => {
+                let interval = Twips::from_pixels(DEFAULT_TAB_INTERVAL).get();
+                let next = (pen.get() / interval + 1) * interval;
+                return Twips::new(next) - pen;
+            }
+        };
+
+        let target = match stop.alignment {
+            TabAlignment::Start => stop.position,
+            TabAlignment::End => stop.position - self.segment_width(end, None),
+            TabAlignment::Center => {
+                stop.position - Twips::new(self.segment_width(end, None).get() / 2)
+            }
+            TabAlignment::Decimal => {
+                stop.position - self.segment_width(end, Some(&*stop.decimal_alignment_token))
+            }
+        };
+        (target - pen).max(Twips::ZERO)
+    }
+
+    /// Whether a line may be broken after the character at `i`.
+    fn can_break_after(&self, run: &TextBlockRun<'gc>, c: char, end: usize) -> bool {
+        if end >= self.text.len() {
+            return false;
+        }
+        let (next, _) = self.char_at(end);
+        match run.break_opportunity {
+            BreakOpportunity::None => false,
+            BreakOpportunity::Any | BreakOpportunity::All => true,
+            BreakOpportunity::Auto => {
+                (is_breaking_space(c) && !is_breaking_space(next))
+                    || (c == '-' && !is_breaking_space(next))
+                    || is_ideographic(c)
+                    || is_ideographic(next)
+                    || matches!(run.content, RunContent::Graphic { .. })
+                    || self.run_at(end).map_or(false, |next_run| {
+                        matches!(self.runs[next_run].content, RunContent::Graphic { .. })
+                    })
+            }
+        }
+    }
+
+    /// Break the next line of the block, starting at `start`.
+    ///
+    /// Returns `None` if not even one atom fits in `width` and `fit_something`
+    /// is not set, or if there is no text left.
+    pub fn lay_out_line(
+        &self,
+        start: usize,
+        width: Twips,
+        fit_something: bool,
+    ) -> Option<LineLayout> {
+        if start >= self.text.len() {
+            return None;
+        }
+
+        let mut atoms: Vec<LineAtom> = vec![];
+        let mut pen = Twips::ZERO;
+        let mut last_break = None;
+        let mut has_tabs = false;
+        let mut is_emergency = false;
+        let mut is_forced_break = false;
+        let mut overflowed = false;
+
+        let mut i = start;
+        while i < self.text.len() {
+            let (c, mut len) = self.char_at(i);
+            if c == '\r' && self.char_at(i + len).0 == '\n' {
+                len += 1;
+            }
+            let end = i + len;
+            let run_index = match self.run_at(i) {
+                Some(run_index) => run_index,
+                None => {
+                    i = end;
+                    continue;
+                }
+            };
+            let run = &self.runs[run_index];
+
+            let advance = if c == '\t' {
+                has_tabs = true;
+                self.tab_advance(pen, end)
+            } else {
+                self.advance(run, c, end)
+            };
+
+            if !is_breaking_space(c) && pen + advance > width {
+                if atoms.is_empty() {
+                    if !fit_something {
+                        return None;
+                    }
+                    is_emergency = true;
+                } else {
+                    overflowed = true;
+                    break;
+                }
+            }
+
+            let (ascent, descent) = run_metrics(run);
+            atoms.push(LineAtom {
+                start: i,
+                end,
+                run: run_index,
+                x: pen,
+                width: advance,
+                ascent,
+                descent,
+                bidi_level: self.levels[i],
+            });
+            pen += advance;
+            i = end;
+
+            if is_line_separator(c) {
+                is_forced_break = true;
+                break;
+            }
+            if is_emergency || run.break_opportunity == BreakOpportunity::All {
+                break;
+            }
+            if self.can_break_after(run, c, end) {
+                last_break = Some(atoms.len());
+            }
+        }
+
+        if overflowed {
+            match last_break {
+                Some(count) => atoms.truncate(count),
+                None => is_emergency = true,
+            }
+        }
+
+        let end = atoms.last().map_or(start, |atom| atom.end);
+        let is_last_line = is_forced_break || end >= self.text.len();
+        self.finish_line(atoms, start, width, has_tabs, is_emergency, is_last_line)
+    }
+
+    /// Apply justification and bidi reordering to the atoms of a line.
+    fn finish_line(
+        &self,
+        mut atoms: Vec<LineAtom>,
+        start: usize,
+        width: Twips,
+        has_tabs: bool,
+        is_emergency: bool,
+        is_last_line: bool,
+    ) -> Option<LineLayout> {
+        let end = atoms.last()?.end;
+
+        // Trailing whitespace, and whitespace before tabs, takes the
+        // direction of the paragraph (UAX #9, rule L1).
+        let mut reset = true;
+        for atom in atoms.iter_mut().rev() {
+            let c = self.char_at(atom.start).0;
+            if c == '\t' || is_line_separator(c) {
+                atom.bidi_level = self.paragraph_level;
+                reset = true;
+            } else if reset && c.is_whitespace() {
+                atom.bidi_level = self.paragraph_level;
+            } else {
+                reset = false;
+            }
+        }
+
+        let trailing_spaces = atoms
+            .iter()
+            .rev()
+            .take_while(|atom| {
+                let c = self.char_at(atom.start).0;
+                is_breaking_space(c) || is_line_separator(c)
+            })
+            .count();
+        let content_atoms = atoms.len() - trailing_spaces;
+        let mut text_width = atoms
+            .iter()
+            .fold(Twips::ZERO, |width, atom| width + atom.width);
+        let unjustified_text_width = text_width;
+
+        let justify = match self.justification {
+            LineJustification::Unjustified => false,
+            LineJustification::AllButLast => !is_last_line,
+            LineJustification::AllIncludingLastLine => true,
+        };
+        if justify {
+            let content_width = atoms[..content_atoms]
+                .iter()
+                .fold(Twips::ZERO, |width, atom| width + atom.width);
+            let spaces: Vec<usize> = (0..content_atoms)
+                .filter(|&n| is_breaking_space(self.char_at(atoms[n].start).0))
+                .collect();
+            let extra = width - content_width;
+            if extra > Twips::ZERO && !spaces.is_empty() {
+                let count = spaces.len() as i32;
+                for (n, &index) in spaces.iter().enumerate() {
+                    let share =
+                        extra.get() * (n as i32 + 1) / count - extra.get() * n as i32 / count;
+                    atoms[index].width += Twips::new(share);
+                }
+                text_width += extra;
+            }
+        }
+
+        // Lay the atoms out from left to right, in visual order.
+        let levels: Vec<u8> = atoms.iter().map(|atom| atom.bidi_level).collect();
+        let mut x = Twips::ZERO;
+        for index in visual_order(&levels) {
+            atoms[index].x = x;
+            x += atoms[index].width;
+        }
+
+        let ascent = atoms.iter().map(|atom| atom.ascent).max()?;
+        let descent = atoms.iter().map(|atom| atom.descent).max()?;
+
+        Some(LineLayout {
+            start,
+            end,
+            atoms,
+            ascent,
+            descent,
+            text_width,
+            unjustified_text_width,
+            has_tabs,
+            is_emergency,
+        })
+    }
+
+    /// Draw the glyphs of a line, with the baseline at y = 0.
+    pub fn draw_line(&self, line: &LineLayout, drawing: &mut Drawing) {
+        for atom in &line.atoms {
+            let run = &self.runs[atom.run];
+            let (font, params, color, baseline_shift) = match &run.content {
+                RunContent::Text {
+                    font,
+                    params,
+                    color,
+                    baseline_shift,
+                } => (font, params, color, *baseline_shift),
+                RunContent::Graphic { .. } => continue,
+            };
+
+            let mut c = self.char_at(atom.start).0;
+            if c.is_whitespace() || c.is_control() {
+                continue;
+            }
+            if atom.bidi_level & 1 != 0 {
+                c = mirror(c);
+            }
+            let glyph = match font.get_glyph_for_char(c) {
+                Some(glyph) => glyph,
+                None => continue,
+            };
+
+            let scale = params.height().get() as f32 / font.scale();
+            let transform = |x: Twips, y: Twips| {
+                (
+                    atom.x + Twips::new((x.get() as f32 * scale) as i32),
+                    baseline_shift + Twips::new((y.get() as f32 * scale) as i32),
+                )
+            };
+
+            let shape = glyph.as_shape();
+            let shape = DistilledShape::from(&*shape);
+            for path in shape.paths {
+                let commands = match path {
+                    DrawPath::Fill { commands, .. } => commands,
+                    DrawPath::Stroke { .. } => continue,
+                };
+
+                // Each glyph is a separate fill, so that overlapping glyphs
+                // don't cancel each other out.
+                drawing.set_fill_style(Some(FillStyle::Color(color.clone())));
+                for command in commands {
+                    drawing.draw_command(match command {
+                        DrawCommand::MoveTo { x, y } => {
+                            let (x, y) = transform(x, y);
+                            DrawCommand::MoveTo { x, y }
+                        }
+                        DrawCommand::LineTo { x, y } => {
+                            let (x, y) = transform(x, y);
+                            DrawCommand::LineTo { x, y }
+                        }
+                        DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                            let (x1, y1) = transform(x1, y1);
+                            let (x2, y2) = transform(x2, y2);
+                            DrawCommand::CurveTo { x1, y1, x2, y2 }
+                        }
+                    });
+                }
+                drawing.set_fill_style(None);
+            }
+        }
+    }
+}
+
+/// The extent of a run above and below the baseline.
+fn run_metrics(run: &TextBlockRun<'_>) -> (Twips, Twips) {
+    match &run.content {
+        RunContent::Text {
+            font,
+            params,
+            baseline_shift,
+            ..
+        } => (
+            font.get_baseline_for_height(params.height()) - *baseline_shift,
+            font.get_descent_for_height(params.height()) + *baseline_shift,
+        ),
+        RunContent::Graphic { height, .. } => (*height, Twips::ZERO),
+    }
+}
+
+/// The order to display atoms with the given bidi levels in (UAX #9, rule
+/// L2): every sequence at or above each odd level is reversed, from the
+/// highest level down.
+fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let highest = levels.iter().copied().max().unwrap_or(0);
+    let lowest_odd = levels
+        .iter()
+        .copied()
+        .filter(|level| level & 1 != 0)
+        .min()
+        .unwrap_or(highest + 1);
+
+    let mut level = highest;
+    while level >= lowest_odd && level > 0 {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] >= level {
+                let start = i;
+                while i < order.len() && levels[order[i]] >= level {
+                    i += 1;
+                }
+                order[start..i].reverse();
+            } else {
+                i += 1;
+            }
+        }
+        level -= 1;
+    }
+
+    order
+}
+
+fn is_line_separator(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Spaces that lines may break after, and which may hang past the end of a
+/// line.
+fn is_breaking_space(c: char) -> bool {
+    matches!(c, ' ' | '\u{1680}' | '\u{2000}'..='\u{2006}' | '\u{2008}'..='\u{200A}' | '\u{205F}' | '\u{3000}')
+}
+
+fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{2E80}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FFEF}'
+        | '\u{20000}'..='\u{3FFFF}'
+    )
+}
+
+/// The mirrored form of a character displayed right-to-left.
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '<' => '>',
+        '>' => '<',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '«' => '»',
+        '»' => '«',
+        '‹' => '›',
+        '›' => '‹',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A block of graphic elements with the given widths, in pixels.
+    fn graphics_block(widths: &[f64]) -> TextBlockLayout<'static> {
+        let mut text = WString::new();
+        let mut runs = vec![];
+        for (i, width) in widths.iter().enumerate() {
+            text.push_char('\u{FDEF}');
+            runs.push(TextBlockRun {
+                start: i,
+                end: i + 1,
+                content: RunContent::Graphic {
+                    width: Twips::from_pixels(*width),
+                    height: Twips::from_pixels(10.0),
+                },
+                break_opportunity: BreakOpportunity::Auto,
+            });
+        }
+        TextBlockLayout::new(text, runs, vec![], LineJustification::Unjustified, 0)
+    }
+
+    #[test]
+    fn run_at_finds_containing_run() {
+        let block = graphics_block(&[10.0, 20.0, 30.0]);
+        assert_eq!(block.run_at(0), Some(0));
+        assert_eq!(block.run_at(1), Some(1));
+        assert_eq!(block.run_at(2), Some(2));
+        assert_eq!(block.run_at(3), None);
+    }
+
+    #[test]
+    fn lines_break_around_graphics() {
+        let block = graphics_block(&[100.0, 100.0, 100.0]);
+
+        let line = block
+            .lay_out_line(0, Twips::from_pixels(250.0), false)
+            .unwrap();
+        assert_eq!((line.start, line.end), (0, 2));
+        assert_eq!(line.text_width, Twips::from_pixels(200.0));
+        assert!(!line.is_emergency);
+
+        let line = block
+            .lay_out_line(2, Twips::from_pixels(250.0), false)
+            .unwrap();
+        assert_eq!((line.start, line.end), (2, 3));
+        assert!(block
+            .lay_out_line(3, Twips::from_pixels(250.0), false)
+            .is_none());
+    }
+
+    #[test]
+    fn too_narrow_lines_need_fit_something() {
+        let block = graphics_block(&[100.0]);
+        assert!(block
+            .lay_out_line(0, Twips::from_pixels(50.0), false)
+            .is_none());
+
+        let line = block
+            .lay_out_line(0, Twips::from_pixels(50.0), true)
+            .unwrap();
+        assert!(line.is_emergency);
+        assert_eq!(line.atoms.len(), 1);
+    }
+
+    #[test]
+    fn visual_order_reverses_rtl_runs() {
+        assert_eq!(visual_order(&[0, 0, 0]), vec![0, 1, 2]);
+        assert_eq!(visual_order(&[1, 1, 1]), vec![2, 1, 0]);
+        assert_eq!(visual_order(&[0, 1, 1, 0]), vec![0, 2, 1, 3]);
+        // Numbers inside right-to-left text keep their order.
+        assert_eq!(visual_order(&[1, 2, 2, 1]), vec![3, 1, 2, 0]);
+    }
+}
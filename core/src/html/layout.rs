@@ -427,15 +427,23 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
+        let font_name = span.font.to_utf8_lossy();
         let library = context.library.library_for_movie_mut(self.movie.clone());
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+        // If this text field is set to use device fonts, look for a matching system font.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
         if let Some(font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+            .get_font_by_name(&font_name, span.bold, span.italic)
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| context.library.device_font())
+            .or_else(|| {
+                context.library.get_or_load_device_font(
+                    context.gc_context,
+                    context.font_backend,
+                    &font_name,
+                    span.bold,
+                    span.italic,
+                )
+            })
         {
             self.font = Some(font);
             return self.font;
@@ -488,12 +496,21 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     /// should be appended after line fixup has completed, but before the text
     /// cursor is moved down.
     fn append_bullet(&mut self, context: &mut UpdateContext<'_, 'gc>, span: &TextSpan) {
+        let font_name = span.font.to_utf8_lossy();
         let library = context.library.library_for_movie_mut(self.movie.clone());
 
         if let Some(bullet_font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+            .get_font_by_name(&font_name, span.bold, span.italic)
             .filter(|f| f.has_glyphs())
-            .or_else(|| context.library.device_font())
+            .or_else(|| {
+                context.library.get_or_load_device_font(
+                    context.gc_context,
+                    context.font_backend,
+                    &font_name,
+                    span.bold,
+                    span.italic,
+                )
+            })
             .or(self.font)
         {
             let mut bullet_cursor = self.cursor;
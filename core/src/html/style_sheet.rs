@@ -0,0 +1,286 @@
+//! CSS style sheets applied to HTML text
+
+use crate::html::text_format::TextFormat;
+use crate::string::{Integer, WStr, WString};
+use gc_arena::Collect;
+use std::collections::HashMap;
+
+/// The style properties that Flash understands, by their AS name.
+pub const STYLE_PROPERTIES: &[&str] = &[
+    "color",
+    "display",
+    "fontFamily",
+    "fontSize",
+    "fontStyle",
+    "fontWeight",
+    "kerning",
+    "leading",
+    "letterSpacing",
+    "marginLeft",
+    "marginRight",
+    "textAlign",
+    "textDecoration",
+    "textIndent",
+];
+
+/// How an element is laid out relative to its surrounding text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Display {
+    /// The element ends its line.
+    Block,
+
+    /// The element flows with the surrounding text.
+    Inline,
+
+    /// The element and its contents are hidden.
+    None,
+}
+
+/// The formatting that a style sheet applies to one selector.
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    pub format: TextFormat,
+    pub display: Option<Display>,
+}
+
+impl Style {
+    /// Apply a style property, such as `fontSize`, to this style.
+    ///
+    /// Unknown properties and values that can't be parsed are ignored.
+    pub fn set_property(&mut self, name: &WStr, value: &WStr) {
+        let value = value.trim();
+        let format = &mut self.format;
+        match &*name.to_utf8_lossy() {
+            "color" => format.color = parse_color(value).or(format.color),
+            "display" => {
+                self.display = match &*value.to_ascii_lowercase().to_utf8_lossy() {
+                    "block" => Some(Display::Block),
+                    "inline" => Some(Display::Inline),
+                    "none" => Some(Display::None),
+                    _ => self.display,
+                }
+            }
+            "fontFamily" => {
+                let family = value
+                    .split(b',')
+                    .map(|family| {
+                        family
+                            .trim()
+                            .trim_matches(|c: u16| c == b'"' as u16 || c == b'\'' as u16)
+                    })
+                    .find(|family| !family.is_empty());
+                if let Some(family) = family {
+                    // Generic families are replaced with the matching device font.
+                    format.font = Some(match &*family.to_ascii_lowercase().to_utf8_lossy() {
+                        "sans-serif" => WString::from_utf8("_sans"),
+                        "serif" => WString::from_utf8("_serif"),
+                        "mono" | "monospace" => WString::from_utf8("_typewriter"),
+                        _ => family.into(),
+                    });
+                }
+            }
+            "fontSize" => format.size = parse_length(value).or(format.size),
+            "fontStyle" => {
+                format.italic = match &*value.to_ascii_lowercase().to_utf8_lossy() {
+                    "italic" => Some(true),
+                    "normal" => Some(false),
+                    _ => format.italic,
+                }
+            }
+            "fontWeight" => {
+                format.bold = match &*value.to_ascii_lowercase().to_utf8_lossy() {
+                    "bold" => Some(true),
+                    "normal" => Some(false),
+                    _ => format.bold,
+                }
+            }
+            "kerning" => {
+                format.kerning = match &*value.to_ascii_lowercase().to_utf8_lossy() {
+                    "true" => Some(true),
+                    "false" => Some(false),
+                    _ => format.kerning,
+                }
+            }
+            "leading" => format.leading = parse_length(value).or(format.leading),
+            "letterSpacing" => {
+                format.letter_spacing = parse_length(value).or(format.letter_spacing)
+            }
+            "marginLeft" => format.left_margin = parse_length(value).or(format.left_margin),
+            "marginRight" => format.right_margin = parse_length(value).or(format.right_margin),
+            "textAlign" => {
+                format.align = match &*value.to_ascii_lowercase().to_utf8_lossy() {
+                    "left" => Some(swf::TextAlign::Left),
+                    "center" => Some(swf::TextAlign::Center),
+                    "right" => Some(swf::TextAlign::Right),
+                    "justify" => Some(swf::TextAlign::Justify),
+                    _ => format.align,
+                }
+            }
+            "textDecoration" => {
+                format.underline = match &*value.to_ascii_lowercase().to_utf8_lossy() {
+                    "underline" => Some(true),
+                    "none" => Some(false),
+                    _ => format.underline,
+                }
+            }
+            "textIndent" => format.indent = parse_length(value).or(format.indent),
+            _ => {}
+        }
+    }
+}
+
+/// A set of styles, keyed by selector, that is applied to the HTML text of a
+/// text field.
+///
+/// Selectors are either tag names, such as `p`, or class names, such as
+/// `.headline`.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: HashMap<WString, Style>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style of a selector, replacing any previous style.
+    ///
+    /// Selectors are case-insensitive.
+    pub fn set_style(&mut self, selector: &WStr, style: Style) {
+        self.styles.insert(selector.to_ascii_lowercase(), style);
+    }
+
+    /// Get the style that applies to an element with the given tag name and
+    /// `class` attribute.
+    ///
+    /// The style of the class takes precedence over the style of the tag.
+    pub fn element_style(&self, tag: &WStr, class: Option<&WStr>) -> Style {
+        let mut style = self
+            .styles
+            .get(&tag.to_ascii_lowercase())
+            .cloned()
+            .unwrap_or_default();
+
+        if let Some(class) = class {
+            let mut selector = WString::from_utf8(".");
+            selector.push_str(class);
+            if let Some(class_style) = self.styles.get(&selector.to_ascii_lowercase()) {
+                style.format = class_style.format.clone().mix_with(style.format);
+                style.display = class_style.display.or(style.display);
+            }
+        }
+
+        style
+    }
+}
+
+/// A parsed CSS rule set: each selector, along with its declarations.
+pub type CssRules = Vec<(WString, Vec<(WString, WString)>)>;
+
+/// Parse a CSS document into its rules.
+///
+/// A rule with several selectors is returned once for each selector, and
+/// hyphenated property names such as `font-size` are converted to the names
+/// used by ActionScript, such as `fontSize`. Returns `None` if the document
+/// is malformed.
+pub fn parse_css(css: &WStr) -> Option<CssRules> {
+    let css = strip_comments(css);
+    let mut rest: &WStr = &css;
+    let mut rules = vec![];
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let open = rest.find(b'{')?;
+        let close = rest.find(b'}')?;
+        if close < open {
+            return None;
+        }
+        let selectors = &rest[..open];
+        let body = &rest[open + 1..close];
+        rest = &rest[close + 1..];
+
+        let mut declarations = vec![];
+        for declaration in body.split(b';') {
+            if declaration.trim().is_empty() {
+                continue;
+            }
+            let colon = declaration.find(b':')?;
+            let name = declaration[..colon].trim();
+            if name.is_empty() {
+                return None;
+            }
+            declarations.push((camel_case(name), declaration[colon + 1..].trim().into()));
+        }
+
+        for selector in selectors.split(b',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+            rules.push((selector.into(), declarations.clone()));
+        }
+    }
+
+    Some(rules)
+}
+
+/// Remove the `/* ... */` comments from a CSS document.
+fn strip_comments(css: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut rest = css;
+    while let Some(start) = rest.find(WStr::from_units(b"/*")) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        match rest.find(WStr::from_units(b"*/")) {
+            Some(end) => rest = &rest[end + 2..],
+            None => return result,
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Convert a hyphenated CSS property name, such as `font-size`, to camel case.
+fn camel_case(name: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut capitalize = false;
+    for c in name.iter() {
+        if c == b'-' as u16 {
+            capitalize = true;
+        } else if capitalize {
+            capitalize = false;
+            match u8::try_from(c) {
+                Ok(c) => result.push_byte(c.to_ascii_uppercase()),
+                Err(_) => result.push(c),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parse a `#RRGGBB` color.
+fn parse_color(value: &WStr) -> Option<swf::Color> {
+    let hex = value.strip_prefix(b'#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_wstr_radix(hex, 16).ok()?;
+    Some(swf::Color::from_rgb(rgb, 0))
+}
+
+/// Parse a length in pixels, such as `12` or `12px`.
+fn parse_length(value: &WStr) -> Option<f64> {
+    let value = value
+        .strip_suffix(WStr::from_units(b"px"))
+        .or_else(|| value.strip_suffix(WStr::from_units(b"pt")))
+        .unwrap_or(value);
+    value.trim().parse().ok()
+}
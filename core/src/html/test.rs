@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{parse_css, Style, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -864,3 +865,60 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn parse_css_rules() {
+    let rules = parse_css(WStr::from_units(
+        b"/* headings */ h1, .title { font-size: 20px; font-weight: bold }\np { color: #FF0000; }",
+    ))
+    .unwrap();
+
+    let declarations = vec![
+        (WString::from_utf8("fontSize"), WString::from_utf8("20px")),
+        (WString::from_utf8("fontWeight"), WString::from_utf8("bold")),
+    ];
+    assert_eq!(
+        rules,
+        vec![
+            (WString::from_utf8("h1"), declarations.clone()),
+            (WString::from_utf8(".title"), declarations),
+            (
+                WString::from_utf8("p"),
+                vec![(WString::from_utf8("color"), WString::from_utf8("#FF0000"))]
+            ),
+        ]
+    );
+
+    assert_eq!(parse_css(WStr::from_units(b"p { color: #FF0000; ")), None);
+    assert_eq!(parse_css(WStr::from_units(b"p { color }")), None);
+}
+
+#[test]
+fn formatspans_from_html_with_style_sheet() {
+    let mut style_sheet = StyleSheet::new();
+    let mut title = Style::default();
+    title.set_property(WStr::from_units(b"fontSize"), WStr::from_units(b"20px"));
+    title.set_property(WStr::from_units(b"fontWeight"), WStr::from_units(b"bold"));
+    style_sheet.set_style(WStr::from_units(b".title"), title);
+    let mut hidden = Style::default();
+    hidden.set_property(WStr::from_units(b"display"), WStr::from_units(b"none"));
+    style_sheet.set_style(WStr::from_units(b"Hidden"), hidden);
+
+    let fs = FormatSpans::from_html(
+        WStr::from_units(b"<p class=\"title\">Title</p><p>Body<hidden> secret</hidden></p>"),
+        TextFormat {
+            size: Some(12.0),
+            ..Default::default()
+        },
+        Some(&style_sheet),
+        true,
+    );
+
+    assert_eq!(WStr::from_units(b"Title\nBody\n"), fs.text());
+    let title = fs.get_text_format(0, 5);
+    assert_eq!(title.size, Some(20.0));
+    assert_eq!(title.bold, Some(true));
+    let body = fs.get_text_format(6, 10);
+    assert_eq!(body.size, Some(12.0));
+    assert_eq!(body.bold, None);
+}
@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::{Display, StyleSheet};
 use crate::string::{Integer, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -564,12 +565,18 @@ impl FormatSpans {
 
     /// Lower an HTML tree into text-span representation.
     ///
-    /// This is the "legacy" implementation of this process: it only looks for
-    /// a handful of presentational attributes in the HTML tree to generate
-    /// styling. There's also a `lower_from_css` that respects both
-    /// presentational markup and CSS stylesheets.
-    pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+    /// Styling is generated from the handful of presentational tags and
+    /// attributes that Flash supports, as well as the styles of `style_sheet`
+    /// that match each element's tag name and `class` attribute. Styles from
+    /// the style sheet take precedence over presentational markup.
+    pub fn from_html(
+        html: &WStr,
+        default_format: TextFormat,
+        style_sheet: Option<&StyleSheet>,
+        is_multiline: bool,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
+        let mut display_stack = vec![Display::Inline];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
 
@@ -759,9 +766,30 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+
+                    let mut display = match &e.name().to_ascii_lowercase()[..] {
+                        b"p" | b"li" if is_multiline => Display::Block,
+                        _ => Display::Inline,
+                    };
+                    if let Some(style_sheet) = style_sheet {
+                        let tag = decode_to_wstr(e.name());
+                        let class = attribute(b"class");
+                        let style = style_sheet.element_style(&tag, class.as_deref());
+                        format = style.format.mix_with(format);
+                        display = style.display.unwrap_or(display);
+                    }
+                    // The contents of hidden elements stay hidden.
+                    if display_stack.last() == Some(&Display::None) {
+                        display = Display::None;
+                    }
+
                     format_stack.push(format);
+                    display_stack.push(display);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => {
+                    if display_stack.last() == Some(&Display::None) {
+                        continue;
+                    }
                     let e = decode_to_wstr(e.escaped());
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
@@ -787,14 +815,14 @@ impl FormatSpans {
                             // Skip pop from `format_stack`.
                             continue;
                         }
-                        b"p" | b"li" if is_multiline => {
-                            text.push_byte(b'\n');
-                            if let Some(span) = spans.last_mut() {
-                                span.span_length += 1;
-                            }
-                        }
                         _ => {}
                     }
+                    if display_stack.pop() == Some(Display::Block) && is_multiline {
+                        text.push_byte(b'\n');
+                        if let Some(span) = spans.last_mut() {
+                            span.span_length += 1;
+                        }
+                    }
                     format_stack.pop();
                 }
                 Ok(Event::Eof) => break,
@@ -33,9 +33,12 @@ mod html;
 mod library;
 pub mod limits;
 pub mod loader;
+pub mod local_connection;
 mod locale;
 mod player;
 mod prelude;
+pub mod socket;
+pub mod streams;
 pub mod string;
 pub mod tag_utils;
 pub mod timer;
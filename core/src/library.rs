@@ -1,10 +1,11 @@
 use crate::avm1::PropertyMap as Avm1PropertyMap;
 use crate::avm2::{ClassObject as Avm2ClassObject, Domain as Avm2Domain};
 use crate::backend::audio::SoundHandle;
+use crate::backend::font::FontBackend;
 use crate::character::Character;
 
 use crate::display_object::{Bitmap, Graphic, MorphShape, TDisplayObject, Text};
-use crate::font::{Font, FontDescriptor};
+use crate::font::{Font, FontDescriptor, FontType};
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -103,6 +104,8 @@ pub struct MovieLibrary<'gc> {
     export_characters: Avm1PropertyMap<'gc, Character<'gc>>,
     jpeg_tables: Option<Vec<u8>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
+    /// DefineFont4 fonts, which can only be used by `flash.text.engine`.
+    cff_fonts: HashMap<FontDescriptor, Font<'gc>>,
     avm2_domain: Option<Avm2Domain<'gc>>,
 }
 
@@ -113,6 +116,7 @@ impl<'gc> MovieLibrary<'gc> {
             export_characters: Avm1PropertyMap::new(),
             jpeg_tables: None,
             fonts: HashMap::new(),
+            cff_fonts: HashMap::new(),
             avm2_domain: None,
         }
     }
@@ -121,7 +125,11 @@ impl<'gc> MovieLibrary<'gc> {
         // TODO(Herschel): What is the behavior if id already exists?
         if !self.contains_character(id) {
             if let Character::Font(font) = character {
-                self.fonts.insert(font.descriptor().clone(), font);
+                if font.font_type() == FontType::EmbeddedCFF {
+                    self.cff_fonts.insert(font.descriptor().clone(), font);
+                } else {
+                    self.fonts.insert(font.descriptor().clone(), font);
+                }
             }
 
             self.characters.insert(id, character);
@@ -245,6 +253,33 @@ impl<'gc> MovieLibrary<'gc> {
         self.fonts.get(&descriptor).copied()
     }
 
+    /// Find a DefineFont4 font by it's name and parameters.
+    pub fn get_cff_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+
+        self.cff_fonts.get(&descriptor).copied()
+    }
+
+    /// Returns the fonts with glyphs defined in this movie, in character ID
+    /// order.
+    pub fn embedded_fonts(&self) -> Vec<Font<'gc>> {
+        let mut fonts: Vec<_> = self
+            .characters
+            .iter()
+            .filter_map(|(id, character)| match character {
+                Character::Font(font) if font.has_glyphs() => Some((*id, *font)),
+                _ => None,
+            })
+            .collect();
+        fonts.sort_unstable_by_key(|(id, _)| *id);
+        fonts.into_iter().map(|(_, font)| font).collect()
+    }
+
     /// Returns the `Graphic` with the given character ID.
     /// Returns `None` if the ID does not exist or is not a `Graphic`.
     pub fn get_graphic(&self, id: CharacterId) -> Option<Graphic<'gc>> {
@@ -357,6 +392,14 @@ pub struct Library<'gc> {
     /// The embedded device font.
     device_font: Option<Font<'gc>>,
 
+    /// Device fonts loaded from the font backend, by the descriptor they were
+    /// requested with. `None` records that no system font matched.
+    device_fonts: HashMap<FontDescriptor, Option<Font<'gc>>>,
+
+    /// Fonts registered with `flash.text.Font.registerFont`, in the order
+    /// they were registered.
+    registered_fonts: Vec<Font<'gc>>,
+
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
@@ -369,6 +412,10 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
             val.trace(cc);
         }
         self.device_font.trace(cc);
+        for font in self.device_fonts.values() {
+            font.trace(cc);
+        }
+        self.registered_fonts.trace(cc);
         self.avm2_class_registry.trace(cc);
     }
 }
@@ -378,6 +425,8 @@ impl<'gc> Library<'gc> {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
+            device_fonts: HashMap::new(),
+            registered_fonts: Vec::new(),
             avm2_class_registry: Default::default(),
         }
     }
@@ -402,6 +451,75 @@ impl<'gc> Library<'gc> {
         self.device_font = Some(font);
     }
 
+    /// Returns the system font best matching the given name and style,
+    /// loading it from the font backend on first use.
+    ///
+    /// Falls back to the embedded device font if no system font matches.
+    pub fn get_or_load_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        font_backend: &mut dyn FontBackend,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(font) = self.device_fonts.get(&descriptor) {
+            return font.or(self.device_font);
+        }
+
+        // The generic font names map to the usual fonts of each platform.
+        let class = descriptor.class();
+        let candidates: &[&str] = match class {
+            "_sans" => &[
+                "Arial",
+                "Helvetica",
+                "Liberation Sans",
+                "DejaVu Sans",
+                "Noto Sans",
+            ],
+            "_serif" => &[
+                "Times New Roman",
+                "Times",
+                "Liberation Serif",
+                "DejaVu Serif",
+                "Noto Serif",
+            ],
+            "_typewriter" => &[
+                "Courier New",
+                "Courier",
+                "Liberation Mono",
+                "DejaVu Sans Mono",
+                "Noto Sans Mono",
+            ],
+            _ => std::slice::from_ref(&class),
+        };
+        let font = candidates.iter().find_map(|name| {
+            let file = font_backend.load_font(name, is_bold, is_italic)?;
+            let font = Font::from_font_file(gc_context, file, descriptor.clone(), FontType::Device);
+            if font.is_none() {
+                tracing::warn!("Unable to parse system font {}", name);
+            }
+            font
+        });
+
+        self.device_fonts.insert(descriptor, font);
+        font.or(self.device_font)
+    }
+
+    /// Returns the fonts registered with `flash.text.Font.registerFont`.
+    pub fn registered_fonts(&self) -> &[Font<'gc>] {
+        &self.registered_fonts
+    }
+
+    /// Make a font available to all movies through
+    /// `flash.text.Font.enumerateFonts`.
+    pub fn register_font(&mut self, font: Font<'gc>) {
+        if !self.registered_fonts.iter().any(|f| Font::ptr_eq(*f, font)) {
+            self.registered_fonts.push(font);
+        }
+    }
+
     /// Get the AVM2 class registry.
     pub fn avm2_class_registry(&self) -> &Avm2ClassRegistry<'gc> {
         &self.avm2_class_registry
@@ -5,15 +5,16 @@ use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::ArrayObject as Avm2ArrayObject;
 use crate::avm2::object::ByteArrayObject;
 use crate::avm2::object::EventObject as Avm2EventObject;
 use crate::avm2::object::LoaderStream;
 use crate::avm2::object::TObject as _;
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
+    Activation as Avm2Activation, ArrayStorage, Avm2, Domain as Avm2Domain, Object as Avm2Object,
     Value as Avm2Value,
 };
-use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::navigator::{OwnedFuture, Request, Response};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{
     Bitmap, DisplayObject, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
@@ -22,6 +23,7 @@ use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::Player;
+use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -148,9 +150,28 @@ pub enum Error {
     #[error("Non-sound loader spawned as sound loader")]
     NotSoundLoader,
 
+    #[error("Non-NetStream loader spawned as NetStream loader")]
+    NotNetStreamLoader,
+
     #[error("Could not fetch: {0}")]
     FetchError(String),
 
+    /// The server responded with an HTTP status that isn't a success.
+    #[error("HTTP status is not ok, got {status}")]
+    HttpNotOk {
+        /// The final URL obtained after any redirects.
+        url: String,
+
+        /// The HTTP status code of the response.
+        status: u16,
+
+        /// Whether the request was redirected to `url`.
+        redirected: bool,
+
+        /// The headers of the response, as name and value pairs.
+        headers: Vec<(String, String)>,
+    },
+
     #[error("Invalid SWF: {0}")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
@@ -174,6 +195,117 @@ pub enum Error {
     Avm2Error(String),
 }
 
+/// The HTTP status of a finished fetch, as reported to scripts by
+/// `httpStatus` events.
+struct HttpStatus<'a> {
+    /// The HTTP status code, or 0 if no HTTP response was received.
+    status: u16,
+
+    /// Whether the request was redirected.
+    redirected: bool,
+
+    /// The URL the response came from, if there was a response.
+    url: Option<&'a str>,
+
+    /// The headers of the response.
+    headers: &'a [(String, String)],
+}
+
+impl<'a> HttpStatus<'a> {
+    fn of(result: &'a Result<Response, Error>) -> Self {
+        match result {
+            Ok(response) => Self::from_response(response),
+            Err(error) => Self::from_error(error),
+        }
+    }
+
+    fn from_response(response: &'a Response) -> Self {
+        Self {
+            status: response.status,
+            redirected: response.redirected,
+            url: Some(&response.url),
+            headers: &response.headers,
+        }
+    }
+
+    fn from_error(error: &'a Error) -> Self {
+        match error {
+            Error::HttpNotOk {
+                url,
+                status,
+                redirected,
+                headers,
+            } => Self {
+                status: *status,
+                redirected: *redirected,
+                url: Some(url),
+                headers,
+            },
+            _ => Self {
+                status: 0,
+                redirected: false,
+                url: None,
+                headers: &[],
+            },
+        }
+    }
+
+    /// Dispatch an AVM2 `httpStatus` event describing this status to `target`.
+    fn dispatch_event<'gc>(
+        &self,
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+    ) -> Result<(), Error> {
+        let header_class = activation.avm2().classes().urlrequestheader;
+        let mut headers = Vec::with_capacity(self.headers.len());
+        for (name, value) in self.headers {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = AvmString::new_utf8(activation.context.gc_context, value);
+            let header = header_class
+                .construct(activation, &[name.into(), value.into()])
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            headers.push(Some(header.into()));
+        }
+        let headers =
+            Avm2ArrayObject::from_storage(activation, ArrayStorage::from_storage(headers))
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        let http_status_evt_cls = activation.avm2().classes().httpstatusevent;
+        let mut http_status_evt = http_status_evt_cls
+            .construct(
+                activation,
+                &[
+                    "httpStatus".into(),
+                    false.into(),
+                    false.into(),
+                    self.status.into(),
+                    self.redirected.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        let url = match self.url {
+            Some(url) => AvmString::new_utf8(activation.context.gc_context, url).into(),
+            None => Avm2Value::Null,
+        };
+        http_status_evt
+            .set_public_property("responseURL", url, activation)
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+        http_status_evt
+            .set_public_property("responseHeaders", headers.into(), activation)
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        if let Err(e) = Avm2::dispatch_event(&mut activation.context, http_status_evt, target) {
+            tracing::error!(
+                "Encountered AVM2 error when broadcasting `httpStatus` event: {}",
+                e
+            );
+        }
+
+        Ok(())
+    }
+}
+
 impl From<crate::avm1::Error<'_>> for Error {
     fn from(error: crate::avm1::Error<'_>) -> Self {
         Error::Avm1Error(error.to_string())
@@ -211,7 +343,8 @@ impl<'gc> LoadManager<'gc> {
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
-            | Loader::SoundAvm2 { self_handle, .. } => *self_handle = Some(handle),
+            | Loader::SoundAvm2 { self_handle, .. }
+            | Loader::NetStream { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
     }
@@ -264,6 +397,7 @@ impl<'gc> LoadManager<'gc> {
             loader_status: LoaderStatus::Pending,
             movie: None,
             avm2_data,
+            http_status: 0,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
@@ -288,6 +422,7 @@ impl<'gc> LoadManager<'gc> {
             loader_status: LoaderStatus::Pending,
             movie: None,
             avm2_data,
+            http_status: 0,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
@@ -404,6 +539,31 @@ impl<'gc> LoadManager<'gc> {
         loader.sound_loader_avm2(player, request)
     }
 
+    /// Kick off the download of a file to be played by a `NetStream`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_netstream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_stream: NetStream<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::NetStream {
+            self_handle: None,
+            target_stream,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.stream_loader(player, request)
+    }
+
+    /// Stop buffering data into a `NetStream`, such as when it starts playing another file.
+    pub fn cancel_netstream_loads(&mut self, stream: NetStream<'gc>) {
+        self.0.retain(|_, loader| {
+            !matches!(loader, Loader::NetStream { target_stream, .. } if *target_stream == stream)
+        });
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
@@ -509,6 +669,9 @@ pub enum Loader<'gc> {
 
         /// AVM2 specific data for this SWF.
         avm2_data: Option<Avm2LoaderData<'gc>>,
+
+        /// The HTTP status of the response, or 0 if there was none.
+        http_status: u16,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -561,6 +724,16 @@ pub enum Loader<'gc> {
         /// The target AVM1 object to load the audio into.
         target_object: Avm2Object<'gc>,
     },
+
+    /// Loader that is buffering video or audio into a NetStream.
+    NetStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The stream to buffer data into.
+        target_stream: NetStream<'gc>,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -764,7 +937,14 @@ impl<'gc> Loader<'gc> {
                 Loader::movie_loader_start(handle, uc)
             })?;
 
-            match fetch.await {
+            let response = fetch.await;
+            if !replacing_root_movie {
+                player.lock().unwrap().update(|uc| {
+                    Loader::movie_loader_http_status(handle, uc, HttpStatus::of(&response))
+                })?;
+            }
+
+            match response {
                 Ok(response) if replacing_root_movie => {
                     ContentType::sniff(&response.body).expect(ContentType::Swf)?;
 
@@ -924,6 +1104,7 @@ impl<'gc> Loader<'gc> {
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
             let data = fetch.await;
+            let http_status = HttpStatus::of(&data).status;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| {
@@ -949,7 +1130,7 @@ impl<'gc> Loader<'gc> {
 
                         let _ = that.call_method(
                             "onHTTPStatus".into(),
-                            &[200.into()],
+                            &[http_status.into()],
                             &mut activation,
                             ExecutionReason::Special,
                         );
@@ -974,11 +1155,9 @@ impl<'gc> Loader<'gc> {
                     }
                     Err(_) => {
                         // TODO: Log "Error opening URL" trace similar to the Flash Player?
-                        // Simulate 404 HTTP status. This should probably be fired elsewhere
-                        // because a failed local load doesn't fire a 404.
                         let _ = that.call_method(
                             "onHTTPStatus".into(),
-                            &[404.into()],
+                            &[http_status.into()],
                             &mut activation,
                             ExecutionReason::Special,
                         );
@@ -1083,6 +1262,9 @@ impl<'gc> Loader<'gc> {
                             );
                         }
 
+                        HttpStatus::from_response(&response)
+                            .dispatch_event(&mut activation, target)?;
+
                         set_data(response.body, &mut activation, target, data_format);
 
                         let complete_evt = Avm2EventObject::bare_default_event(
@@ -1097,7 +1279,9 @@ impl<'gc> Loader<'gc> {
                             );
                         }
                     }
-                    Err(_err) => {
+                    Err(err) => {
+                        HttpStatus::from_error(&err).dispatch_event(&mut activation, target)?;
+
                         // Testing with Flash shoes that the 'data' property is cleared
                         // when an error occurs
 
@@ -1291,6 +1475,83 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a NetStream load call.
+    fn stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::NetStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotNetStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch_streaming(request);
+            let response = fetch.await;
+
+            let mut body = player.lock().unwrap().update(|uc| {
+                let stream = Loader::netstream_target(uc, handle)?;
+                match response {
+                    Ok(response) => {
+                        stream.set_bytes_total(uc, response.content_length());
+                        Ok(Some(response.body))
+                    }
+                    Err(_err) => {
+                        stream.report_error(uc);
+                        Ok(None)
+                    }
+                }
+            })?;
+
+            // Hand every chunk over to the stream as it arrives, so that playback can start
+            // before the download finishes.
+            while let Some(body) = &mut body {
+                let chunk = body.next_chunk().await;
+                let is_finished = player.lock().unwrap().update(|uc| {
+                    let stream = Loader::netstream_target(uc, handle)?;
+                    match chunk {
+                        Ok(Some(chunk)) => {
+                            stream.append_data(uc, &chunk);
+                            Ok(false)
+                        }
+                        Ok(None) => {
+                            stream.finish_loading(uc);
+                            Ok(true)
+                        }
+                        Err(_err) => {
+                            stream.report_error(uc);
+                            Ok(true)
+                        }
+                    }
+                })?;
+                if is_finished {
+                    break;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// The stream that a NetStream loader is buffering data into.
+    fn netstream_target(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<NetStream<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::NetStream { target_stream, .. }) => Ok(target_stream),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotNetStreamLoader),
+        }
+    }
+
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
@@ -1553,14 +1814,43 @@ impl<'gc> Loader<'gc> {
         Ok(())
     }
 
+    /// Record the HTTP status of a movie loader's response and report it to
+    /// script code.
+    fn movie_loader_http_status(
+        handle: Index,
+        uc: &mut UpdateContext<'_, 'gc>,
+        status: HttpStatus<'_>,
+    ) -> Result<(), Error> {
+        let event_handler = match uc.load_manager.get_loader_mut(handle) {
+            Some(Loader::Movie {
+                event_handler,
+                http_status,
+                ..
+            }) => {
+                *http_status = status.status;
+                *event_handler
+            }
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        if let Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) = event_handler {
+            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+            status.dispatch_event(&mut activation, loader_info)?;
+        }
+
+        Ok(())
+    }
+
     /// Report a movie loader completion to script code.
     fn movie_loader_complete(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
-        let (clip, event_handler) = match uc.load_manager.get_loader_mut(handle) {
+        let (clip, event_handler, http_status) = match uc.load_manager.get_loader_mut(handle) {
             Some(Loader::Movie {
                 target_clip,
                 event_handler,
+                http_status,
                 ..
-            }) => (*target_clip, *event_handler),
+            }) => (*target_clip, *event_handler, *http_status),
             None => return Err(Error::Cancelled),
             _ => unreachable!(),
         };
@@ -1572,8 +1862,7 @@ impl<'gc> Loader<'gc> {
                     broadcaster,
                     uc,
                     "broadcastMessage".into(),
-                    // TODO: Pass an actual httpStatus argument instead of 0.
-                    &["onLoadComplete".into(), clip.object(), 0.into()],
+                    &["onLoadComplete".into(), clip.object(), http_status.into()],
                 );
             }
             // This is fired after we process the movie's first frame,
@@ -1608,12 +1897,13 @@ impl<'gc> Loader<'gc> {
         //error types we can actually inspect.
         //This also can get errors from decoding an invalid SWF file,
         //too. We should distinguish those to player code.
-        let (clip, event_handler) = match uc.load_manager.get_loader_mut(handle) {
+        let (clip, event_handler, http_status) = match uc.load_manager.get_loader_mut(handle) {
             Some(Loader::Movie {
                 target_clip,
                 event_handler,
+                http_status,
                 ..
-            }) => (*target_clip, *event_handler),
+            }) => (*target_clip, *event_handler, *http_status),
             None => return Err(Error::Cancelled),
             _ => unreachable!(),
         };
@@ -1629,6 +1919,7 @@ impl<'gc> Loader<'gc> {
                         "onLoadError".into(),
                         clip.object(),
                         "LoadNeverCompleted".into(),
+                        http_status.into(),
                     ],
                 );
             }
@@ -0,0 +1,490 @@
+//! LocalConnection implementation
+
+use crate::avm1::globals::shared_object::deserialize_value as avm1_deserialize_value;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    Attribute as Avm1Attribute, ExecutionReason as Avm1ExecutionReason,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::amf::deserialize_value as avm2_deserialize_value;
+use crate::avm2::object::TObject as Avm2TObject;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, Multiname as Avm2Multiname,
+    Object as Avm2Object, Value as Avm2Value,
+};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use gc_arena::{Collect, CollectionContext};
+use std::collections::BTreeMap;
+
+/// The largest size of the encoded arguments of a message.
+pub const MAX_ARGUMENTS_SIZE: usize = 40 * 1024;
+
+/// A connected `LocalConnection` object.
+#[derive(Clone)]
+struct Connection<'gc> {
+    target: AvmObject<'gc>,
+
+    /// The domain of the movie that connected the object.
+    domain: String,
+
+    /// Whether the movie that connected the object was loaded over HTTPS.
+    secure: bool,
+}
+
+/// Holds the `LocalConnection`s of the player, and the messages sent over
+/// them that weren't delivered yet.
+#[derive(Default)]
+pub struct LocalConnections<'gc> {
+    /// The connected objects, by fully qualified connection name.
+    connections: BTreeMap<String, Connection<'gc>>,
+
+    /// The messages sent since the last update, with the object that sent
+    /// each of them.
+    messages: Vec<(AvmObject<'gc>, LocalConnectionMessage)>,
+}
+
+unsafe impl<'gc> Collect for LocalConnections<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for connection in self.connections.values() {
+            connection.target.trace(cc);
+        }
+        for (sender, _) in &self.messages {
+            sender.trace(cc);
+        }
+    }
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect `target`, which belongs to the movie at `movie_url`, with a
+    /// fully qualified connection name.
+    ///
+    /// Returns `false` if `target` is already connected, or if the name is in
+    /// use by this or another player.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: AvmObject<'gc>,
+        name: String,
+        movie_url: &str,
+    ) -> bool {
+        let connections = &mut context.local_connections.connections;
+        if connections.contains_key(&name)
+            || connections
+                .values()
+                .any(|connection| AvmObject::ptr_eq(connection.target, target))
+        {
+            return false;
+        }
+
+        if !context.local_connection_backend.connect(&name) {
+            return false;
+        }
+
+        let connection = Connection {
+            target,
+            domain: movie_domain(movie_url).unwrap_or_else(|| "localhost".to_owned()),
+            secure: is_secure_movie(movie_url),
+        };
+        connections.insert(name, connection);
+        true
+    }
+
+    /// Close the connection of `target`.
+    ///
+    /// Returns `false` if `target` isn't connected.
+    pub fn close(context: &mut UpdateContext<'_, 'gc>, target: AvmObject<'gc>) -> bool {
+        let name = match context.local_connections.name_of(target) {
+            Some(name) => name,
+            None => return false,
+        };
+
+        context.local_connections.connections.remove(&name);
+        context.local_connection_backend.close(&name);
+        true
+    }
+
+    /// Whether `target` is connected.
+    pub fn is_connected(&self, target: AvmObject<'gc>) -> bool {
+        self.name_of(target).is_some()
+    }
+
+    fn name_of(&self, target: AvmObject<'gc>) -> Option<String> {
+        self.connections
+            .iter()
+            .find(|(_, connection)| AvmObject::ptr_eq(connection.target, target))
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Queue a message to be delivered on the next update.
+    ///
+    /// `sender` receives a status event once the message was delivered, or
+    /// couldn't be.
+    pub fn send(&mut self, sender: AvmObject<'gc>, message: LocalConnectionMessage) {
+        self.messages.push((sender, message));
+    }
+
+    /// Deliver the messages sent since the last update, both by this player
+    /// and by other players.
+    pub fn update_connections(context: &mut UpdateContext<'_, 'gc>) {
+        let messages = std::mem::take(&mut context.local_connections.messages);
+        for (sender, message) in messages {
+            let receiver = context
+                .local_connections
+                .connections
+                .get(&message.connection_name)
+                .cloned();
+            let delivered = match receiver {
+                Some(receiver) => Self::deliver(context, &receiver, &message),
+                None => context.local_connection_backend.send(message),
+            };
+
+            Self::status_event(context, sender, delivered);
+        }
+
+        for message in context.local_connection_backend.receive() {
+            let receiver = context
+                .local_connections
+                .connections
+                .get(&message.connection_name)
+                .cloned();
+            if let Some(receiver) = receiver {
+                Self::deliver(context, &receiver, &message);
+            }
+        }
+    }
+
+    /// Call the method of a message on the connection that receives it.
+    ///
+    /// Returns `false` if the receiver doesn't accept messages from the
+    /// domain of the sender.
+    fn deliver(
+        context: &mut UpdateContext<'_, 'gc>,
+        receiver: &Connection<'gc>,
+        message: &LocalConnectionMessage,
+    ) -> bool {
+        if !Self::is_sender_allowed(context, receiver, message) {
+            return false;
+        }
+
+        let arguments = decode_arguments(&message.arguments);
+        match receiver.target {
+            AvmObject::Avm1(object) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection]"),
+                );
+                let arguments: Vec<Avm1Value<'gc>> = arguments
+                    .iter()
+                    .map(|element| avm1_deserialize_value(&mut activation, element.value()))
+                    .collect();
+                let method_name =
+                    AvmString::new_utf8(activation.context.gc_context, &message.method_name);
+                if let Err(e) = object.call_method(
+                    method_name,
+                    &arguments,
+                    &mut activation,
+                    Avm1ExecutionReason::FunctionCall,
+                ) {
+                    tracing::error!(
+                        "Got error when calling LocalConnection.{}: {}",
+                        message.method_name,
+                        e
+                    );
+                }
+            }
+            AvmObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                match Self::call_avm2_client(&mut activation, object, message, &arguments) {
+                    Ok(()) => {}
+                    // Errors thrown by the client are reported to the
+                    // receiving connection.
+                    Err(Avm2Error::AvmError(error)) => {
+                        Self::async_error_event(&mut activation, object, message, error)
+                    }
+                    Err(e) => tracing::error!(
+                        "Got error when calling LocalConnection client.{}: {}",
+                        message.method_name,
+                        e
+                    ),
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Whether the receiver of a message accepts messages from the domain of
+    /// its sender.
+    ///
+    /// Messages from the domain of the receiver are always accepted, unless
+    /// the receiver was loaded over HTTPS and the sender wasn't. Otherwise,
+    /// AVM1 receivers decide in their `allowDomain` or `allowInsecureDomain`
+    /// method, and AVM2 receivers must have allowed the domain with the
+    /// methods of the same name.
+    fn is_sender_allowed(
+        context: &mut UpdateContext<'_, 'gc>,
+        receiver: &Connection<'gc>,
+        message: &LocalConnectionMessage,
+    ) -> bool {
+        let insecure = receiver.secure && !message.sender_secure;
+        if !insecure && receiver.domain.eq_ignore_ascii_case(&message.sender_domain) {
+            return true;
+        }
+
+        match receiver.target {
+            AvmObject::Avm1(object) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection allowDomain]"),
+                );
+                let method_name = if insecure {
+                    "allowInsecureDomain"
+                } else {
+                    "allowDomain"
+                };
+                let domain =
+                    AvmString::new_utf8(activation.context.gc_context, &message.sender_domain);
+                match object.call_method(
+                    method_name.into(),
+                    &[domain.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    Ok(allowed) => allowed.as_bool(activation.swf_version()),
+                    Err(e) => {
+                        tracing::error!(
+                            "Got error when calling LocalConnection.{}: {}",
+                            method_name,
+                            e
+                        );
+                        false
+                    }
+                }
+            }
+            AvmObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let lists: &[&str] = if insecure {
+                    &["_allowedInsecureDomains"]
+                } else {
+                    &["_allowedDomains", "_allowedInsecureDomains"]
+                };
+
+                let mut allowed = false;
+                for list in lists {
+                    match Self::avm2_allowed_domains(&mut activation, object, list) {
+                        Ok(domains) => {
+                            allowed |= domains.iter().any(|domain| {
+                                domain == "*" || domain.eq_ignore_ascii_case(&message.sender_domain)
+                            })
+                        }
+                        Err(e) => tracing::error!(
+                            "Got error when reading the allowed domains of LocalConnection: {}",
+                            e
+                        ),
+                    }
+                }
+                allowed
+            }
+        }
+    }
+
+    /// Read one of the lists of domains that an AVM2 `LocalConnection`
+    /// accepts messages from.
+    fn avm2_allowed_domains(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        object: Avm2Object<'gc>,
+        list: &'static str,
+    ) -> Result<Vec<String>, Avm2Error<'gc>> {
+        let namespace = activation.avm2().flash_net_internal;
+        let domains = object
+            .get_property(&Avm2Multiname::new(namespace, list), activation)?
+            .as_object();
+        let domains: Vec<Avm2Value<'gc>> = match domains
+            .as_ref()
+            .and_then(|domains| domains.as_array_storage())
+        {
+            Some(domains) => domains
+                .iter()
+                .map(|domain| domain.unwrap_or(Avm2Value::Undefined))
+                .collect(),
+            None => return Ok(vec![]),
+        };
+
+        domains
+            .into_iter()
+            .map(|domain| Ok(domain.coerce_to_string(activation)?.to_string()))
+            .collect()
+    }
+
+    fn call_avm2_client(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        object: Avm2Object<'gc>,
+        message: &LocalConnectionMessage,
+        arguments: &[Element],
+    ) -> Result<(), Avm2Error<'gc>> {
+        let client = object
+            .get_public_property("client", activation)?
+            .coerce_to_object(activation)?;
+        let arguments = arguments
+            .iter()
+            .map(|element| avm2_deserialize_value(activation, element.value()))
+            .collect::<Result<Vec<Avm2Value<'gc>>, Avm2Error<'gc>>>()?;
+        let method_name = AvmString::new_utf8(activation.context.gc_context, &message.method_name);
+        client.call_public_property(method_name, &arguments, activation)?;
+
+        Ok(())
+    }
+
+    /// Report an error thrown by the client of an AVM2 connection while
+    /// handling a message.
+    fn async_error_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        object: Avm2Object<'gc>,
+        message: &LocalConnectionMessage,
+        error: Avm2Value<'gc>,
+    ) {
+        let text = AvmString::new_utf8(
+            activation.context.gc_context,
+            format!(
+                "Error #2095: flash.net.LocalConnection was unable to invoke callback {}.",
+                message.method_name
+            ),
+        );
+        let async_error_event_class = activation.avm2().classes().asyncerrorevent;
+        let event = async_error_event_class.construct(
+            activation,
+            &[
+                "asyncError".into(),
+                false.into(),
+                false.into(),
+                text.into(),
+                error,
+            ],
+        );
+        let result =
+            event.and_then(|event| Avm2::dispatch_event(&mut activation.context, event, object));
+        if let Err(e) = result {
+            tracing::error!(
+                "Got error when dispatching AVM2 AsyncErrorEvent from LocalConnection: {}",
+                e
+            );
+        }
+    }
+
+    /// Tell the sender of a message whether it was delivered.
+    fn status_event(context: &mut UpdateContext<'_, 'gc>, sender: AvmObject<'gc>, delivered: bool) {
+        let level = if delivered { "status" } else { "error" };
+        match sender {
+            AvmObject::Avm1(object) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection Status]"),
+                );
+                let info_object = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                info_object.define_value(
+                    activation.context.gc_context,
+                    "level",
+                    level.into(),
+                    Avm1Attribute::empty(),
+                );
+
+                if let Err(e) = object.call_method(
+                    "onStatus".into(),
+                    &[info_object.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!(
+                        "Got error when dispatching AVM1 onStatus event from LocalConnection: {}",
+                        e
+                    );
+                }
+            }
+            AvmObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let status_event_class = activation.avm2().classes().statusevent;
+                let event = status_event_class.construct(
+                    &mut activation,
+                    &[
+                        "status".into(),
+                        false.into(),
+                        false.into(),
+                        Avm2Value::Null,
+                        level.into(),
+                    ],
+                );
+                let result = event
+                    .and_then(|event| Avm2::dispatch_event(&mut activation.context, event, object));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Got error when dispatching AVM2 StatusEvent from LocalConnection: {}",
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Qualify a connection name with the domain of the movie using it.
+///
+/// Names that start with an underscore are shared by all domains, and names
+/// that already include a domain are left as they are.
+pub fn qualify_name(domain: &str, name: &str) -> String {
+    if name.starts_with('_') || name.contains(':') {
+        name.to_owned()
+    } else {
+        format!("{domain}:{name}")
+    }
+}
+
+/// The domain of a movie, as reported by `LocalConnection.domain`.
+///
+/// Returns `None` if the URL of the movie is invalid.
+pub fn movie_domain(movie_url: &str) -> Option<String> {
+    let url = url::Url::parse(movie_url).ok()?;
+    if url.scheme() == "file" {
+        return Some("localhost".to_owned());
+    }
+
+    Some(url.domain().unwrap_or("localhost").to_owned())
+}
+
+/// Whether a movie was loaded over HTTPS, which makes its connections only
+/// accept messages from insecure movies that were allowed with
+/// `allowInsecureDomain`.
+pub fn is_secure_movie(movie_url: &str) -> bool {
+    url::Url::parse(movie_url).map_or(false, |url| url.scheme() == "https")
+}
+
+/// Encode the arguments of a message as an AMF body.
+pub fn encode_arguments(arguments: Vec<AmfValue>, version: AMFVersion) -> Vec<u8> {
+    let elements = arguments
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| Element::new(index.to_string(), value))
+        .collect();
+    let mut lso = Lso::new(elements, "", version);
+    flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default()
+}
+
+/// Decode the arguments of a message encoded with `encode_arguments`.
+pub fn decode_arguments(arguments: &[u8]) -> Vec<Element> {
+    match flash_lso::read::Reader::default().parse(arguments) {
+        Ok(lso) => lso.body,
+        Err(_) => {
+            tracing::error!("Failed to decode LocalConnection message arguments");
+            vec![]
+        }
+    }
+}
@@ -12,6 +12,8 @@ use crate::avm2::{
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    font::FontBackend,
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
@@ -37,8 +39,11 @@ use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
+use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::prelude::*;
+use crate::socket::Sockets;
+use crate::streams::StreamManager;
 use crate::string::AvmString;
 use crate::stub::StubCollection;
 use crate::tag_utils::SwfMovie;
@@ -153,6 +158,15 @@ struct GcRootData<'gc> {
 
     /// Manager of active sound instances.
     audio_manager: AudioManager<'gc>,
+
+    /// List of actively playing streams to decode.
+    stream_manager: StreamManager<'gc>,
+
+    /// Open socket connections.
+    sockets: Sockets<'gc>,
+
+    /// Connected `LocalConnection`s and the messages sent over them.
+    local_connections: LocalConnections<'gc>,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -176,6 +190,9 @@ impl<'gc> GcRootData<'gc> {
         &mut Option<ContextMenuState<'gc>>,
         &mut ExternalInterface<'gc>,
         &mut AudioManager<'gc>,
+        &mut StreamManager<'gc>,
+        &mut Sockets<'gc>,
+        &mut LocalConnections<'gc>,
     ) {
         (
             self.stage,
@@ -192,6 +209,9 @@ impl<'gc> GcRootData<'gc> {
             &mut self.current_context_menu,
             &mut self.external_interface,
             &mut self.audio_manager,
+            &mut self.stream_manager,
+            &mut self.sockets,
+            &mut self.local_connections,
         )
     }
 }
@@ -205,6 +225,8 @@ type Storage = Box<dyn StorageBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
+type Fonts = Box<dyn FontBackend>;
+type LocalConnection = Box<dyn LocalConnectionBackend>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -233,6 +255,8 @@ pub struct Player {
     log: Log,
     ui: Ui,
     video: Video,
+    font_backend: Fonts,
+    local_connection: LocalConnection,
 
     transform_stack: TransformStack,
 
@@ -526,6 +550,10 @@ impl Player {
             });
 
             self.update_timers(dt);
+            self.update_streams(dt);
+            self.update_dynamic_sounds();
+            self.update_sockets();
+            self.update_local_connections();
             self.audio.tick();
         }
     }
@@ -1028,6 +1056,51 @@ impl Player {
                 }
             }
 
+            // Text fields handle clipboard and select all shortcuts, which use
+            // Command instead of Control on macOS.
+            if let PlayerEvent::KeyDown { key_code, .. } = event {
+                if context.input.is_key_down(KeyCode::Control)
+                    || context.input.is_key_down(KeyCode::Command)
+                {
+                    if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                        text.handle_text_shortcut(context, key_code);
+                    }
+                }
+            }
+
+            // Tab moves the focus, and Enter or Space clicks a focused button.
+            if !key_press_handled {
+                match event {
+                    PlayerEvent::KeyDown {
+                        key_code: KeyCode::Tab,
+                        ..
+                    } => {
+                        let reverse = context.input.is_key_down(KeyCode::Shift);
+                        let tracker = context.focus_tracker;
+                        tracker.cycle(context, reverse);
+                    }
+                    PlayerEvent::KeyDown {
+                        key_code: KeyCode::Return | KeyCode::Space,
+                        ..
+                    } => {
+                        if let Some(button) = context
+                            .focus_tracker
+                            .get()
+                            .and_then(|o| o.as_interactive())
+                            .filter(|o| o.is_keyboard_activatable(context))
+                        {
+                            for event in [ClipEvent::Press, ClipEvent::Release] {
+                                button.handle_clip_event(context, event);
+                                if context.is_action_script_3() {
+                                    button.event_dispatch_to_avm2(context, event);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
             // Propagate clip events.
             let (clip_event, listener) = match event {
                 PlayerEvent::KeyDown { .. } => {
@@ -1107,6 +1180,16 @@ impl Player {
             // Update the dragged object here to keep it constantly in sync with the mouse position.
             self.mutate_with_update_context(|context| {
                 Self::update_drag(context);
+
+                // Dragging the mouse over a text field selects its text.
+                if let PlayerEvent::MouseMove { .. } = event {
+                    if let Some(text) = context
+                        .mouse_down_object
+                        .and_then(|o| o.as_displayobject().as_edit_text())
+                    {
+                        text.drag_selection(context);
+                    }
+                }
             });
 
             let is_mouse_moved = old_pos != self.mouse_pos;
@@ -1504,6 +1587,9 @@ impl Player {
             };
 
             stage.render(&mut render_context);
+            root_data
+                .focus_tracker
+                .render_highlight(&mut render_context);
 
             background_color =
                 if stage.window_mode() != WindowMode::Transparent || stage.is_fullscreen() {
@@ -1711,6 +1797,9 @@ impl Player {
                 current_context_menu,
                 external_interface,
                 audio_manager,
+                stream_manager,
+                sockets,
+                local_connections,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1737,6 +1826,8 @@ impl Player {
                 storage: self.storage.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
+                font_backend: self.font_backend.deref_mut(),
+                local_connection_backend: self.local_connection.deref_mut(),
                 avm1_shared_objects,
                 avm2_shared_objects,
                 unbound_text_fields,
@@ -1757,6 +1848,9 @@ impl Player {
                 actions_since_timeout_check: &mut self.actions_since_timeout_check,
                 frame_phase: &mut self.frame_phase,
                 stub_tracker: &mut self.stub_tracker,
+                stream_manager,
+                sockets,
+                local_connections,
             };
 
             let old_frame_rate = *update_context.frame_rate;
@@ -1866,6 +1960,26 @@ impl Player {
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
 
+    /// Advance all playing media streams.
+    pub fn update_streams(&mut self, dt: f64) {
+        self.mutate_with_update_context(|context| StreamManager::tick(context, dt));
+    }
+
+    /// Request more samples for the sounds generated by `sampleData` handlers.
+    pub fn update_dynamic_sounds(&mut self) {
+        self.mutate_with_update_context(AudioManager::update_dynamic_sounds);
+    }
+
+    /// Deliver the events of socket connections.
+    pub fn update_sockets(&mut self) {
+        self.mutate_with_update_context(Sockets::update_sockets);
+    }
+
+    /// Deliver the messages sent over `LocalConnection`s.
+    pub fn update_local_connections(&mut self) {
+        self.mutate_with_update_context(LocalConnections::update_connections);
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -1931,6 +2045,8 @@ pub struct PlayerBuilder {
     storage: Option<Storage>,
     ui: Option<Ui>,
     video: Option<Video>,
+    font_backend: Option<Fonts>,
+    local_connection: Option<LocalConnection>,
 
     // Misc. player configuration
     autoplay: bool,
@@ -1968,6 +2084,8 @@ impl PlayerBuilder {
             storage: None,
             ui: None,
             video: None,
+            font_backend: None,
+            local_connection: None,
 
             autoplay: false,
             scale_mode: StageScaleMode::ShowAll,
@@ -2049,6 +2167,25 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the font backend of the player, used to find system fonts for
+    /// device text.
+    #[inline]
+    pub fn with_font_backend(mut self, font_backend: impl 'static + FontBackend) -> Self {
+        self.font_backend = Some(Box::new(font_backend));
+        self
+    }
+
+    /// Sets the local connection backend of the player, used to exchange
+    /// `LocalConnection` messages with other players.
+    #[inline]
+    pub fn with_local_connection(
+        mut self,
+        local_connection: impl 'static + LocalConnectionBackend,
+    ) -> Self {
+        self.local_connection = Some(Box::new(local_connection));
+        self
+    }
+
     /// Sets whether the movie will start playing immediately upon load.
     #[inline]
     pub fn with_autoplay(mut self, autoplay: bool) -> Self {
@@ -2170,6 +2307,12 @@ impl PlayerBuilder {
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let font_backend = self
+            .font_backend
+            .unwrap_or_else(|| Box::new(font::NullFontBackend::new()));
+        let local_connection = self
+            .local_connection
+            .unwrap_or_else(|| Box::new(local_connection::NullLocalConnectionBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
 
@@ -2186,6 +2329,8 @@ impl PlayerBuilder {
                 storage,
                 ui,
                 video,
+                font_backend,
+                local_connection,
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2252,6 +2397,9 @@ impl PlayerBuilder {
                                 ),
                                 timers: Timers::new(),
                                 unbound_text_fields: Vec::new(),
+                                stream_manager: StreamManager::new(),
+                                sockets: Sockets::new(),
+                                local_connections: LocalConnections::new(),
                             },
                         ),
                     },
@@ -0,0 +1,453 @@
+//! Binary and XML socket connections
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ExecutionReason as Avm1ExecutionReason, TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::object::{EventObject as Avm2EventObject, TObject as Avm2TObject};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, Multiname as Avm2Multiname,
+    Object as Avm2Object, Value as Avm2Value,
+};
+use crate::backend::navigator::NavigatorBackend;
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use gc_arena::{Collect, CollectionContext};
+use generational_arena::{Arena, Index};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+pub type SocketHandle = Index;
+
+/// The text of the `securityError` event of connections that aren't allowed.
+const SECURITY_ERROR_MESSAGE: &str =
+    "Error #2048: Security sandbox violation: cannot load data from the socket.";
+
+/// The outcome of an attempt to open a socket connection.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConnectionState {
+    /// The connection was established.
+    Connected,
+
+    /// The connection could not be established.
+    Failed,
+
+    /// The connection was refused by the socket policy of the player.
+    NotAllowed,
+}
+
+/// Something that happened to a socket connection, as reported by the
+/// navigator backend.
+#[derive(Debug)]
+pub enum SocketAction {
+    /// The connection attempt finished.
+    Connect(SocketHandle, ConnectionState),
+
+    /// Data was received from the server.
+    Data(SocketHandle, Vec<u8>),
+
+    /// The server closed the connection.
+    Close(SocketHandle),
+}
+
+/// The kind of socket, which determines how received data is delivered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SocketKind {
+    /// An AVM2 `flash.net.Socket`, which buffers raw bytes.
+    Binary,
+
+    /// An `XMLSocket`, which exchanges null-terminated messages.
+    Xml,
+}
+
+struct Socket<'gc> {
+    /// The object that receives the events of this socket.
+    target: AvmObject<'gc>,
+
+    kind: SocketKind,
+
+    /// The channel to send outgoing data on.
+    ///
+    /// Dropping it closes the connection.
+    sender: Sender<Vec<u8>>,
+
+    /// Received data that doesn't make up a complete XML message yet.
+    xml_buffer: Vec<u8>,
+
+    connected: bool,
+}
+
+unsafe impl<'gc> Collect for Socket<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        self.target.trace(cc);
+    }
+}
+
+/// Holds all open socket connections of the player.
+pub struct Sockets<'gc> {
+    sockets: Arena<Socket<'gc>>,
+
+    /// The channel that the navigator reports socket activity on.
+    receiver: Receiver<SocketAction>,
+    sender: Sender<SocketAction>,
+}
+
+unsafe impl<'gc> Collect for Sockets<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for (_, socket) in self.sockets.iter() {
+            socket.trace(cc)
+        }
+    }
+}
+
+impl<'gc> Default for Sockets<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> Sockets<'gc> {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            sockets: Arena::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    /// Open a connection to `host:port` for `target`, closing the previous
+    /// connection of `target` if it has one.
+    ///
+    /// The outcome is reported asynchronously with a `connect` event, or an
+    /// error event if the connection fails.
+    pub fn connect(
+        &mut self,
+        navigator: &mut dyn NavigatorBackend,
+        target: AvmObject<'gc>,
+        kind: SocketKind,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) {
+        self.close(target);
+
+        let (sender, receiver) = channel();
+        let handle = self.sockets.insert(Socket {
+            target,
+            kind,
+            sender,
+            xml_buffer: vec![],
+            connected: false,
+        });
+        navigator.connect_socket(host, port, timeout, handle, receiver, self.sender.clone());
+    }
+
+    /// Whether `target` has an established connection.
+    pub fn is_connected(&self, target: AvmObject<'gc>) -> bool {
+        self.find(target)
+            .map_or(false, |handle| self.sockets[handle].connected)
+    }
+
+    /// Send data over the connection of `target`.
+    ///
+    /// XML messages are terminated with a null byte. Returns `false` if
+    /// `target` isn't connected.
+    pub fn send(&mut self, target: AvmObject<'gc>, mut data: Vec<u8>) -> bool {
+        let socket = match self.find(target) {
+            Some(handle) if self.sockets[handle].connected => &self.sockets[handle],
+            _ => return false,
+        };
+
+        if socket.kind == SocketKind::Xml {
+            data.push(0);
+        }
+
+        // A closed channel means that the connection was lost, which will be
+        // reported by the navigator.
+        let _ = socket.sender.send(data);
+        true
+    }
+
+    /// Close the connection of `target`, if it has one.
+    ///
+    /// Closing a socket from script doesn't fire a `close` event.
+    pub fn close(&mut self, target: AvmObject<'gc>) {
+        if let Some(handle) = self.find(target) {
+            self.sockets.remove(handle);
+        }
+    }
+
+    fn find(&self, target: AvmObject<'gc>) -> Option<SocketHandle> {
+        self.sockets
+            .iter()
+            .find(|(_, socket)| AvmObject::ptr_eq(socket.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Deliver the events of all socket activity reported since the last
+    /// update.
+    pub fn update_sockets(context: &mut UpdateContext<'_, 'gc>) {
+        let actions: Vec<SocketAction> = context.sockets.receiver.try_iter().collect();
+
+        for action in actions {
+            match action {
+                SocketAction::Connect(handle, state) => {
+                    let socket = match context.sockets.sockets.get_mut(handle) {
+                        Some(socket) => socket,
+                        None => continue,
+                    };
+                    let target = socket.target;
+                    if state == ConnectionState::Connected {
+                        socket.connected = true;
+                    } else {
+                        context.sockets.sockets.remove(handle);
+                    }
+
+                    Self::connect_event(context, target, state);
+                }
+                SocketAction::Data(handle, data) => {
+                    let socket = match context.sockets.sockets.get_mut(handle) {
+                        Some(socket) => socket,
+                        None => continue,
+                    };
+                    let target = socket.target;
+                    match socket.kind {
+                        SocketKind::Binary => Self::data_event(context, target, data),
+                        SocketKind::Xml => {
+                            socket.xml_buffer.extend(data);
+
+                            let mut messages = vec![];
+                            while let Some(end) = socket.xml_buffer.iter().position(|b| *b == 0) {
+                                let message: Vec<u8> = socket.xml_buffer.drain(..=end).collect();
+                                messages
+                                    .push(String::from_utf8_lossy(&message[..end]).into_owned());
+                            }
+
+                            for message in messages {
+                                Self::xml_event(context, target, message);
+                            }
+                        }
+                    }
+                }
+                SocketAction::Close(handle) => {
+                    let socket = match context.sockets.sockets.remove(handle) {
+                        Some(socket) => socket,
+                        None => continue,
+                    };
+
+                    Self::close_event(context, socket.target);
+                }
+            }
+        }
+    }
+
+    fn connect_event(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: AvmObject<'gc>,
+        state: ConnectionState,
+    ) {
+        match target {
+            AvmObject::Avm1(object) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket]"),
+                );
+                let success = state == ConnectionState::Connected;
+                if let Err(e) = object.call_method(
+                    "onConnect".into(),
+                    &[success.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Got error when calling XMLSocket.onConnect: {}", e);
+                }
+            }
+            AvmObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let event = match state {
+                    ConnectionState::Connected => Ok(Avm2EventObject::bare_default_event(
+                        &mut activation.context,
+                        "connect",
+                    )),
+                    ConnectionState::Failed => {
+                        let io_error_event_class = activation.avm2().classes().ioerrorevent;
+                        io_error_event_class.construct(
+                            &mut activation,
+                            &[
+                                "ioError".into(),
+                                false.into(),
+                                false.into(),
+                                "Error #2031: Socket Error.".into(),
+                                2031.into(),
+                            ],
+                        )
+                    }
+                    ConnectionState::NotAllowed => {
+                        let security_error_event_class =
+                            activation.avm2().classes().securityerrorevent;
+                        security_error_event_class.construct(
+                            &mut activation,
+                            &[
+                                "securityError".into(),
+                                false.into(),
+                                false.into(),
+                                SECURITY_ERROR_MESSAGE.into(),
+                                2048.into(),
+                            ],
+                        )
+                    }
+                };
+
+                Self::dispatch_avm2_event(&mut activation, event, object);
+            }
+        }
+    }
+
+    fn data_event(context: &mut UpdateContext<'_, 'gc>, target: AvmObject<'gc>, data: Vec<u8>) {
+        let object = match target {
+            AvmObject::Avm2(object) => object,
+            AvmObject::Avm1(_) => return,
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let event = Self::append_to_read_buffer(&mut activation, object, &data).and_then(|()| {
+            let progress_event_class = activation.avm2().classes().progressevent;
+            progress_event_class.construct(
+                &mut activation,
+                &[
+                    "socketData".into(),
+                    false.into(),
+                    false.into(),
+                    data.len().into(),
+                    0.into(),
+                ],
+            )
+        });
+
+        Self::dispatch_avm2_event(&mut activation, event, object);
+    }
+
+    /// Append received data to the read buffer of a `Socket`, discarding the
+    /// data that was already read from it.
+    fn append_to_read_buffer(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        object: Avm2Object<'gc>,
+        data: &[u8],
+    ) -> Result<(), Avm2Error<'gc>> {
+        let namespace = activation.avm2().flash_net_internal;
+        let read_buffer = object
+            .get_property(&Avm2Multiname::new(namespace, "_readBuffer"), activation)?
+            .as_object();
+        let mut read_buffer = match read_buffer
+            .as_ref()
+            .and_then(|buffer| buffer.as_bytearray_mut(activation.context.gc_context))
+        {
+            Some(read_buffer) => read_buffer,
+            None => return Ok(()),
+        };
+
+        let unread = read_buffer
+            .bytes()
+            .get(read_buffer.position()..)
+            .unwrap_or_default()
+            .to_vec();
+        read_buffer.clear();
+        read_buffer.write_bytes(&unread)?;
+        read_buffer.write_bytes(data)?;
+        read_buffer.set_position(0);
+
+        Ok(())
+    }
+
+    fn xml_event(context: &mut UpdateContext<'_, 'gc>, target: AvmObject<'gc>, message: String) {
+        match target {
+            AvmObject::Avm1(object) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket]"),
+                );
+                let message = AvmString::new_utf8(activation.context.gc_context, message);
+                if let Err(e) = object.call_method(
+                    "onData".into(),
+                    &[Avm1Value::from(message)],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Got error when calling XMLSocket.onData: {}", e);
+                }
+            }
+            AvmObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let message = AvmString::new_utf8(activation.context.gc_context, message);
+                let data_event_class = activation.avm2().classes().dataevent;
+                let event = data_event_class.construct(
+                    &mut activation,
+                    &[
+                        "data".into(),
+                        false.into(),
+                        false.into(),
+                        Avm2Value::from(message),
+                    ],
+                );
+
+                Self::dispatch_avm2_event(&mut activation, event, object);
+            }
+        }
+    }
+
+    fn close_event(context: &mut UpdateContext<'_, 'gc>, target: AvmObject<'gc>) {
+        match target {
+            AvmObject::Avm1(object) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[XMLSocket]"),
+                );
+                if let Err(e) = object.call_method(
+                    "onClose".into(),
+                    &[],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Got error when calling XMLSocket.onClose: {}", e);
+                }
+            }
+            AvmObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let event = Avm2EventObject::bare_default_event(&mut activation.context, "close");
+                Self::dispatch_avm2_event(&mut activation, Ok(event), object);
+            }
+        }
+    }
+
+    fn dispatch_avm2_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        event: Result<Avm2Object<'gc>, Avm2Error<'gc>>,
+        target: Avm2Object<'gc>,
+    ) {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!("Failed to construct socket event: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = Avm2::dispatch_event(&mut activation.context, event, target) {
+            tracing::error!(
+                "Encountered AVM2 error when dispatching socket event: {}",
+                e
+            );
+        }
+    }
+}
+
+/// The host to connect to when a movie doesn't give one, which is the host
+/// that the movie was loaded from.
+pub fn default_host(movie_url: &str) -> String {
+    url::Url::parse(movie_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_owned))
+        .unwrap_or_else(|| "localhost".to_owned())
+}
@@ -0,0 +1,1198 @@
+//! NetStream implementation
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ArrayObject as Avm1ArrayObject, Attribute as Avm1Attribute,
+    ExecutionReason as Avm1ExecutionReason, ScriptObject as Avm1ScriptObject,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::object::TObject as Avm2TObject;
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    Avm2, Error as Avm2Error, Object as Avm2Object, Value as Avm2Value,
+};
+use crate::backend::audio::{SoundInstanceHandle, Substream, SubstreamFormat};
+use crate::backend::navigator::Request;
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use crate::vminterface::AvmObject;
+use flv_rs::{
+    AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, CodecId as FlvCodecId,
+    Error as FlvError, FlvReader, FrameType as FlvFrameType, Header as FlvHeader,
+    ScriptData as FlvScriptData, SoundFormat as FlvSoundFormat, SoundSize as FlvSoundSize,
+    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
+    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+};
+use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_video::frame::EncodedFrame;
+use ruffle_video::VideoStreamHandle;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use swf::{AudioCompression, VideoCodec, VideoDeblocking};
+
+/// Manager for all media streams.
+///
+/// This does *not* handle data transport, which is delegated to
+/// `LoadManager`. `StreamManager` only handles the playback of the data
+/// that `LoadManager` hands over to each stream as it arrives.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct StreamManager<'gc> {
+    /// List of streams that are currently playing.
+    ///
+    /// This is not the list of all created `NetStream`s; only the ones that
+    /// have been told to play and haven't been paused or finished since.
+    playing_streams: Vec<NetStream<'gc>>,
+}
+
+impl<'gc> Default for StreamManager<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> StreamManager<'gc> {
+    pub fn new() -> Self {
+        Self {
+            playing_streams: Vec::new(),
+        }
+    }
+
+    /// Add a stream to the list of playing streams, if it isn't already.
+    pub fn ensure_playing(context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
+        if !context.stream_manager.playing_streams.contains(&stream) {
+            context.stream_manager.playing_streams.push(stream);
+        }
+    }
+
+    /// Remove a stream from the list of playing streams.
+    pub fn ensure_paused(context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
+        context
+            .stream_manager
+            .playing_streams
+            .retain(|other| *other != stream);
+    }
+
+    pub fn is_playing(context: &UpdateContext<'_, 'gc>, stream: NetStream<'gc>) -> bool {
+        context.stream_manager.playing_streams.contains(&stream)
+    }
+
+    /// Advance all playing streams by `dt` milliseconds.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let streams = context.stream_manager.playing_streams.clone();
+        for stream in streams {
+            stream.tick(context, dt);
+        }
+    }
+}
+
+/// A stream of media data, such as an FLV file, that can be played back.
+///
+/// This is the engine-side representation of both the AVM1 and AVM2
+/// `NetStream` classes. Video frames decoded by the stream are picked up by
+/// any `Video` display object attached to it.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct NetStream<'gc>(GcCell<'gc, NetStreamData<'gc>>);
+
+impl<'gc> PartialEq for NetStream<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        GcCell::ptr_eq(self.0, other.0)
+    }
+}
+
+impl<'gc> Eq for NetStream<'gc> {}
+
+impl fmt::Debug for NetStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetStream")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct NetStreamData<'gc> {
+    /// All data loaded in the stream so far.
+    #[collect(require_static)]
+    buffer: Arc<Vec<u8>>,
+
+    /// The size of the media file, if the server reported it.
+    bytes_total: Option<usize>,
+
+    /// Whether the whole media file has been loaded.
+    is_loaded: bool,
+
+    /// The buffer position of the next tag to be played.
+    offset: usize,
+
+    /// The buffer position of the first tag that hasn't been scanned for
+    /// keyframes and audio yet.
+    ///
+    /// Only the tags before this position are complete, so playback never
+    /// goes past it.
+    scan_offset: usize,
+
+    /// The type of media in the buffer, once it has been identified.
+    #[collect(require_static)]
+    stream_type: Option<NetStreamType>,
+
+    /// The current playhead position, in milliseconds.
+    stream_time: f64,
+
+    /// How much media must be buffered ahead of the playhead before playback
+    /// starts, in seconds.
+    buffer_time: f64,
+
+    /// Whether playback is waiting for more data to be buffered.
+    is_buffering: bool,
+
+    /// The most recently decoded video frame.
+    #[collect(require_static)]
+    last_decoded_bitmap: Option<BitmapInfo>,
+
+    /// The sound instance currently playing this stream's audio track.
+    #[collect(require_static)]
+    sound_instance: Option<SoundInstanceHandle>,
+
+    /// The audio packets that are queued for `sound_instance`.
+    ///
+    /// Audio tags are appended to it as they are downloaded.
+    #[collect(require_static)]
+    substream: Option<Substream>,
+
+    /// The AVM side of this stream, which receives status events and script
+    /// data callbacks.
+    avm_object: Option<AvmObject<'gc>>,
+}
+
+impl NetStreamData<'_> {
+    /// The amount of media buffered ahead of the playhead, in seconds.
+    fn buffer_length(&self) -> f64 {
+        match &self.stream_type {
+            Some(NetStreamType::Flv { last_timestamp, .. }) => {
+                ((*last_timestamp as f64 - self.stream_time) / 1000.0).max(0.0)
+            }
+            None => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum NetStreamType {
+    /// The stream is an FLV file.
+    Flv {
+        header: FlvHeader,
+
+        /// The video decoder for this stream, created upon the first video
+        /// tag.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The ID to give to the next decoded video frame.
+        frame_id: u32,
+
+        /// The frame size reported by the stream's metadata, if any.
+        size: Option<(u16, u16)>,
+
+        /// The buffer position of every video keyframe loaded so far, indexed
+        /// by timestamp.
+        ///
+        /// Seeks snap to the last keyframe before the requested time.
+        keyframes: BTreeMap<i32, usize>,
+
+        /// The format of the stream's audio track, set by its first audio tag.
+        audio_format: Option<SubstreamFormat>,
+
+        /// The latest timestamp of all tags loaded so far.
+        last_timestamp: i32,
+    },
+}
+
+/// How far `NetStream::advance` got.
+enum AdvanceResult {
+    /// The playhead reached the requested time.
+    Playing,
+
+    /// The playhead caught up with the loaded data.
+    Stalled,
+
+    /// The playhead reached the end of the stream.
+    Finished,
+}
+
+impl<'gc> NetStream<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>, avm_object: Option<AvmObject<'gc>>) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            NetStreamData {
+                buffer: Arc::new(Vec::new()),
+                bytes_total: None,
+                is_loaded: false,
+                offset: 0,
+                scan_offset: 0,
+                stream_type: None,
+                stream_time: 0.0,
+                buffer_time: 0.1,
+                is_buffering: false,
+                last_decoded_bitmap: None,
+                sound_instance: None,
+                substream: None,
+                avm_object,
+            },
+        ))
+    }
+
+    pub fn set_avm_object(self, gc_context: MutationContext<'gc, '_>, avm_object: AvmObject<'gc>) {
+        self.0.write(gc_context).avm_object = Some(avm_object);
+    }
+
+    /// The number of bytes of media data loaded so far.
+    pub fn bytes_loaded(self) -> usize {
+        self.0.read().buffer.len()
+    }
+
+    /// The size of the media file, or the number of bytes loaded so far if
+    /// the size isn't known.
+    pub fn bytes_total(self) -> usize {
+        let read = self.0.read();
+        read.bytes_total.unwrap_or(read.buffer.len())
+    }
+
+    /// The current playhead position, in milliseconds.
+    pub fn time(self) -> f64 {
+        self.0.read().stream_time
+    }
+
+    /// How much media must be buffered before playback starts, in seconds.
+    pub fn buffer_time(self) -> f64 {
+        self.0.read().buffer_time
+    }
+
+    pub fn set_buffer_time(self, gc_context: MutationContext<'gc, '_>, buffer_time: f64) {
+        self.0.write(gc_context).buffer_time = buffer_time.max(0.0);
+    }
+
+    /// The amount of media buffered ahead of the playhead, in seconds.
+    pub fn buffer_length(self) -> f64 {
+        self.0.read().buffer_length()
+    }
+
+    /// The video frame that attached `Video` objects should display.
+    pub fn last_decoded_bitmap(self) -> Option<BitmapInfo> {
+        self.0.read().last_decoded_bitmap.clone()
+    }
+
+    /// Start loading and playing the media file at `url`.
+    ///
+    /// Playback begins once enough of the file has been buffered.
+    pub fn play(self, context: &mut UpdateContext<'_, 'gc>, url: AvmString<'gc>) {
+        self.reset(context);
+
+        let future = context.load_manager.load_netstream(
+            context.player.clone(),
+            self,
+            Request::get(url.to_string()),
+        );
+        context.navigator.spawn_future(future);
+
+        StreamManager::ensure_playing(context, self);
+        self.0.write(context.gc_context).is_buffering = true;
+    }
+
+    /// Stop playback, discarding all loaded data.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.reset(context);
+    }
+
+    pub fn pause(self, context: &mut UpdateContext<'_, 'gc>) {
+        if !StreamManager::is_playing(context, self) {
+            return;
+        }
+
+        StreamManager::ensure_paused(context, self);
+        self.stop_audio(context);
+        self.trigger_status_event(
+            context,
+            &[("code", "NetStream.Pause.Notify"), ("level", "status")],
+        );
+    }
+
+    pub fn resume(self, context: &mut UpdateContext<'_, 'gc>) {
+        if StreamManager::is_playing(context, self) {
+            return;
+        }
+
+        StreamManager::ensure_playing(context, self);
+        if !self.0.read().is_buffering {
+            self.start_audio(context);
+        }
+        self.trigger_status_event(
+            context,
+            &[("code", "NetStream.Unpause.Notify"), ("level", "status")],
+        );
+    }
+
+    pub fn toggle_pause(self, context: &mut UpdateContext<'_, 'gc>) {
+        if StreamManager::is_playing(context, self) {
+            self.pause(context);
+        } else {
+            self.resume(context);
+        }
+    }
+
+    /// Seek to the last keyframe at or before `time`, given in milliseconds.
+    ///
+    /// Only the part of the stream that has been loaded can be seeked into.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc>, time: f64) {
+        let mut write = self.0.write(context.gc_context);
+        let (is_time_loaded, keyframe) = match &write.stream_type {
+            Some(NetStreamType::Flv {
+                keyframes,
+                last_timestamp,
+                ..
+            }) => (
+                write.is_loaded || time <= *last_timestamp as f64,
+                keyframes
+                    .range(..=time.max(0.0) as i32)
+                    .next_back()
+                    .or_else(|| keyframes.iter().next())
+                    .map(|(timestamp, offset)| (*timestamp, *offset)),
+            ),
+            None => (true, None),
+        };
+        if !is_time_loaded {
+            drop(write);
+            self.trigger_status_event(
+                context,
+                &[("code", "NetStream.Seek.InvalidTime"), ("level", "error")],
+            );
+            return;
+        }
+
+        if let Some((timestamp, offset)) = keyframe {
+            write.offset = offset;
+            write.stream_time = timestamp as f64;
+        }
+        let stream_time = write.stream_time;
+        let is_buffering = write.is_buffering;
+        drop(write);
+
+        // Decode the keyframe immediately, so that paused streams display it.
+        self.advance(context, stream_time);
+
+        if StreamManager::is_playing(context, self) && !is_buffering {
+            self.start_audio(context);
+        }
+
+        self.trigger_status_event(
+            context,
+            &[("code", "NetStream.Seek.Notify"), ("level", "status")],
+        );
+    }
+
+    /// Record the size of the media file that is being loaded.
+    pub fn set_bytes_total(self, context: &mut UpdateContext<'_, 'gc>, bytes_total: Option<usize>) {
+        self.0.write(context.gc_context).bytes_total = bytes_total;
+    }
+
+    /// Accept the next chunk of the media file that is being loaded.
+    ///
+    /// The tags that are complete become available for playback right away.
+    pub fn append_data(self, context: &mut UpdateContext<'_, 'gc>, data: &[u8]) {
+        let mut write = self.0.write(context.gc_context);
+        Arc::make_mut(&mut write.buffer).extend_from_slice(data);
+        let has_header = write.stream_type.is_some();
+        drop(write);
+
+        if has_header || self.parse_header(context) {
+            self.scan_tags(context);
+        }
+    }
+
+    /// Report that the whole media file has been loaded.
+    pub fn finish_loading(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        write.is_loaded = true;
+        let has_header = write.stream_type.is_some();
+        drop(write);
+
+        if !has_header {
+            tracing::error!("NetStream data is too short to be a media file");
+            self.report_invalid_file(context);
+        }
+    }
+
+    /// Report that the media file could not be loaded.
+    pub fn report_error(self, context: &mut UpdateContext<'_, 'gc>) {
+        StreamManager::ensure_paused(context, self);
+        self.stop_audio(context);
+        self.0.write(context.gc_context).is_buffering = false;
+        self.trigger_status_event(
+            context,
+            &[
+                ("code", "NetStream.Play.StreamNotFound"),
+                ("level", "error"),
+            ],
+        );
+    }
+
+    /// Advance playback by `dt` milliseconds.
+    pub fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let read = self.0.read();
+        if read.stream_type.is_none() {
+            // Still waiting for the header.
+            return;
+        }
+        let is_buffering = read.is_buffering;
+        drop(read);
+
+        if is_buffering && !self.finish_buffering(context) {
+            return;
+        }
+
+        let end_time = self.0.read().stream_time + dt;
+        match self.advance(context, end_time) {
+            AdvanceResult::Playing => {
+                // The audio track ends early if it runs out of packets while
+                // they're downloading; pick it up where the playhead is.
+                let instance = self.0.read().sound_instance;
+                if let Some(instance) = instance {
+                    if !context.audio_manager.is_sound_playing(instance) {
+                        self.start_audio(context);
+                    }
+                }
+            }
+            AdvanceResult::Stalled => {
+                self.0.write(context.gc_context).is_buffering = true;
+                self.stop_audio(context);
+                self.trigger_status_event(
+                    context,
+                    &[("code", "NetStream.Buffer.Empty"), ("level", "status")],
+                );
+            }
+            AdvanceResult::Finished => {
+                StreamManager::ensure_paused(context, self);
+                self.stop_audio(context);
+                self.trigger_status_event(
+                    context,
+                    &[("code", "NetStream.Buffer.Flush"), ("level", "status")],
+                );
+                self.trigger_status_event(
+                    context,
+                    &[("code", "NetStream.Play.Stop"), ("level", "status")],
+                );
+                self.trigger_status_event(
+                    context,
+                    &[("code", "NetStream.Buffer.Empty"), ("level", "status")],
+                );
+            }
+        }
+    }
+
+    /// Stop playback and forget all loaded data.
+    fn reset(self, context: &mut UpdateContext<'_, 'gc>) {
+        StreamManager::ensure_paused(context, self);
+        self.stop_audio(context);
+        context.load_manager.cancel_netstream_loads(self);
+
+        let mut write = self.0.write(context.gc_context);
+        write.buffer = Arc::new(Vec::new());
+        write.bytes_total = None;
+        write.is_loaded = false;
+        write.offset = 0;
+        write.scan_offset = 0;
+        write.stream_type = None;
+        write.stream_time = 0.0;
+        write.is_buffering = false;
+        write.last_decoded_bitmap = None;
+    }
+
+    /// Identify the media file from its header.
+    ///
+    /// Returns false if there isn't enough data to do so yet, or if the file
+    /// isn't supported.
+    fn parse_header(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let buffer = self.0.read().buffer.clone();
+        let mut reader = FlvReader::from_source(&buffer);
+        let header = match FlvHeader::parse(&mut reader) {
+            Ok(header) => header,
+            Err(FlvError::EndOfData) => return false,
+            Err(e) => {
+                tracing::error!("NetStream data is not a supported media file: {}", e);
+                self.report_invalid_file(context);
+                return false;
+            }
+        };
+        let data_start = reader.position();
+
+        let mut write = self.0.write(context.gc_context);
+        write.offset = data_start;
+        write.scan_offset = data_start;
+        write.stream_time = 0.0;
+        write.stream_type = Some(NetStreamType::Flv {
+            header,
+            video_stream: None,
+            frame_id: 0,
+            size: None,
+            keyframes: BTreeMap::new(),
+            audio_format: None,
+            last_timestamp: 0,
+        });
+        drop(write);
+
+        if StreamManager::is_playing(context, self) {
+            self.trigger_status_event(
+                context,
+                &[("code", "NetStream.Play.Start"), ("level", "status")],
+            );
+        }
+
+        true
+    }
+
+    /// Stop loading a media file that can't be played.
+    fn report_invalid_file(self, context: &mut UpdateContext<'_, 'gc>) {
+        context.load_manager.cancel_netstream_loads(self);
+        StreamManager::ensure_paused(context, self);
+        self.0.write(context.gc_context).is_buffering = false;
+        self.trigger_status_event(
+            context,
+            &[
+                ("code", "NetStream.Play.FileStructureInvalid"),
+                ("level", "error"),
+            ],
+        );
+    }
+
+    /// Index the keyframes of every complete tag that has been loaded since
+    /// the last scan, and queue their audio for playback.
+    fn scan_tags(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut guard = self.0.write(context.gc_context);
+        let write = &mut *guard;
+        let buffer = write.buffer.clone();
+        let (keyframes, audio_format, last_timestamp) = match &mut write.stream_type {
+            Some(NetStreamType::Flv {
+                keyframes,
+                audio_format,
+                last_timestamp,
+                ..
+            }) => (keyframes, audio_format, last_timestamp),
+            None => return,
+        };
+
+        // An incomplete tag is left for the next scan, once the rest of it
+        // has been loaded.
+        let mut reader = FlvReader::from_parts(&buffer, write.scan_offset);
+        let mut tag_start = reader.position();
+        while let Ok(tag) = FlvTag::parse(&mut reader) {
+            *last_timestamp = (*last_timestamp).max(tag.timestamp);
+            match tag.data {
+                FlvTagData::Video(FlvVideoData {
+                    frame_type: FlvFrameType::Keyframe,
+                    ..
+                }) => {
+                    keyframes.insert(tag.timestamp, tag_start);
+                }
+                FlvTagData::Audio(audio_data) => {
+                    let packet = read_audio_packet(audio_format, &audio_data);
+                    if let (Some(packet), Some(substream)) = (packet, &write.substream) {
+                        substream.append(packet);
+                    }
+                }
+                _ => {}
+            }
+            tag_start = reader.position();
+        }
+        write.scan_offset = reader.position();
+
+        // Start the audio track once its first packets have been loaded.
+        let needs_audio =
+            audio_format.is_some() && write.substream.is_none() && !write.is_buffering;
+        drop(guard);
+        if needs_audio && StreamManager::is_playing(context, self) {
+            self.start_audio(context);
+        }
+    }
+
+    /// Start playback once enough data has been buffered.
+    ///
+    /// Returns true if playback started.
+    fn finish_buffering(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let read = self.0.read();
+        let is_ready = read.is_loaded
+            || (read.scan_offset > read.offset && read.buffer_length() >= read.buffer_time);
+        let stream_time = read.stream_time;
+        drop(read);
+        if !is_ready {
+            return false;
+        }
+
+        self.0.write(context.gc_context).is_buffering = false;
+
+        // Process everything at the playhead, which includes the metadata
+        // and the first video frame at the start of the stream.
+        self.advance(context, stream_time);
+
+        self.trigger_status_event(
+            context,
+            &[("code", "NetStream.Buffer.Full"), ("level", "status")],
+        );
+        self.start_audio(context);
+        true
+    }
+
+    /// Play every loaded tag from the current position up to `end_time`.
+    fn advance(self, context: &mut UpdateContext<'_, 'gc>, end_time: f64) -> AdvanceResult {
+        // Tag callbacks can re-enter the stream (e.g. a script seeking from
+        // `onMetaData`), so we read from our own reference to the buffer and
+        // re-check the position after each tag.
+        loop {
+            let read = self.0.read();
+            let buffer = read.buffer.clone();
+            let mut reader = FlvReader::from_parts(&buffer[..read.scan_offset], read.offset);
+            let is_loaded = read.is_loaded;
+            drop(read);
+
+            let tag = match FlvTag::parse(&mut reader) {
+                Ok(tag) => tag,
+                Err(_) if is_loaded => {
+                    self.0.write(context.gc_context).stream_time = end_time;
+                    return AdvanceResult::Finished;
+                }
+                Err(_) => return AdvanceResult::Stalled,
+            };
+
+            let timestamp = tag.timestamp as f64;
+            let mut write = self.0.write(context.gc_context);
+            if timestamp > end_time {
+                write.stream_time = end_time;
+                return AdvanceResult::Playing;
+            }
+            write.offset = reader.position();
+            write.stream_time = write.stream_time.max(timestamp);
+            drop(write);
+
+            match tag.data {
+                FlvTagData::Video(video_data) => self.decode_video(context, video_data),
+                FlvTagData::Script(script_data) => self.run_script_data(context, script_data),
+                // The audio track is queued up for the audio backend when it's
+                // loaded.
+                FlvTagData::Audio(_) => {}
+                FlvTagData::Invalid(e) => tracing::warn!("Skipping invalid FLV tag: {}", e),
+            }
+        }
+    }
+
+    fn decode_video(self, context: &mut UpdateContext<'_, 'gc>, video_data: FlvVideoData<'_>) {
+        let codec = match video_data.codec_id {
+            FlvCodecId::SorensonH263 => VideoCodec::H263,
+            FlvCodecId::ScreenVideo => VideoCodec::ScreenVideo,
+            FlvCodecId::Vp6 => VideoCodec::Vp6,
+            FlvCodecId::Vp6Alpha => VideoCodec::Vp6WithAlpha,
+            FlvCodecId::ScreenVideo2 => VideoCodec::ScreenVideoV2,
+            unsupported => {
+                tracing::warn!("Unsupported FLV video codec: {:?}", unsupported);
+                return;
+            }
+        };
+        let packet = match video_data.data {
+            FlvVideoPacket::Data(packet) => packet,
+            _ => return,
+        };
+
+        // Convert the packet into the form used by SWF `VideoFrame` tags.
+        let data = match codec {
+            // Screen video frames in SWF files retain the FLV frame type and
+            // codec byte.
+            VideoCodec::ScreenVideo | VideoCodec::ScreenVideoV2 => {
+                let mut data =
+                    vec![((video_data.frame_type as u8) << 4) | video_data.codec_id as u8];
+                data.extend_from_slice(packet);
+                data
+            }
+            // VP6 frames in FLV files have a leading size adjustment byte.
+            VideoCodec::Vp6 | VideoCodec::Vp6WithAlpha => {
+                packet.get(1..).unwrap_or_default().to_vec()
+            }
+            _ => packet.to_vec(),
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        let (stream, frame_id) = match &mut write.stream_type {
+            Some(NetStreamType::Flv {
+                video_stream,
+                frame_id,
+                size,
+                ..
+            }) => {
+                let stream = match video_stream {
+                    Some(stream) => *stream,
+                    None => {
+                        let bounds = size
+                            .or_else(|| vp6_frame_size(codec, packet))
+                            .unwrap_or((0, 0));
+                        match context.video.register_video_stream(
+                            1,
+                            bounds,
+                            codec,
+                            VideoDeblocking::UseVideoPacketValue,
+                        ) {
+                            Ok(stream) => {
+                                *video_stream = Some(stream);
+                                stream
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Got error when registering FLV video stream: {}",
+                                    e
+                                );
+                                return;
+                            }
+                        }
+                    }
+                };
+
+                *frame_id += 1;
+                (stream, *frame_id - 1)
+            }
+            None => return,
+        };
+        drop(write);
+
+        let encoded_frame = EncodedFrame {
+            codec,
+            data: &data,
+            frame_id,
+        };
+        if let Err(e) = context
+            .video
+            .preload_video_stream_frame(stream, encoded_frame)
+        {
+            tracing::error!("Got error when preloading FLV video frame: {}", e);
+            return;
+        }
+
+        let encoded_frame = EncodedFrame {
+            codec,
+            data: &data,
+            frame_id,
+        };
+        match context
+            .video
+            .decode_video_stream_frame(stream, encoded_frame, context.renderer)
+        {
+            Ok(bitmap) => {
+                self.0.write(context.gc_context).last_decoded_bitmap = Some(bitmap);
+            }
+            Err(e) => tracing::error!("Got error when decoding FLV video frame: {}", e),
+        }
+    }
+
+    /// Call the AVM callbacks named in a script data tag, such as
+    /// `onMetaData` and `onCuePoint`.
+    fn run_script_data(self, context: &mut UpdateContext<'_, 'gc>, script_data: FlvScriptData<'_>) {
+        for variable in &script_data.0 {
+            if variable.name == b"onMetaData" {
+                self.read_metadata(context.gc_context, &variable.data);
+            }
+
+            let avm_object = self.0.read().avm_object;
+            match avm_object {
+                Some(AvmObject::Avm1(object)) => {
+                    let mut activation = Avm1Activation::from_stub(
+                        context.reborrow(),
+                        Avm1ActivationIdentifier::root("[NetStream Script Data]"),
+                    );
+                    let name =
+                        AvmString::new_utf8_bytes(activation.context.gc_context, variable.name);
+                    let argument = flv_to_avm1_value(&mut activation, &variable.data);
+                    if let Err(e) = object.call_method(
+                        name,
+                        &[argument],
+                        &mut activation,
+                        Avm1ExecutionReason::Special,
+                    ) {
+                        tracing::error!("Got error when calling NetStream.{}: {}", name, e);
+                    }
+                }
+                Some(AvmObject::Avm2(object)) => {
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    let name =
+                        AvmString::new_utf8_bytes(activation.context.gc_context, variable.name);
+                    if let Err(e) = call_avm2_client(&mut activation, object, name, &variable.data)
+                    {
+                        tracing::error!("Got error when calling NetStream client.{}: {}", name, e);
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Pick up the frame size from an `onMetaData` object.
+    fn read_metadata(self, gc_context: MutationContext<'gc, '_>, metadata: &FlvValue<'_>) {
+        let variables = match metadata {
+            FlvValue::Object(variables) | FlvValue::EcmaArray(variables) => variables,
+            _ => return,
+        };
+        let dimension = |name: &[u8]| {
+            variables.iter().find_map(|variable| match variable.data {
+                FlvValue::Number(n) if variable.name == name && n > 0.0 => Some(n as u16),
+                _ => None,
+            })
+        };
+
+        if let (Some(width), Some(height)) = (dimension(b"width"), dimension(b"height")) {
+            if let Some(NetStreamType::Flv { size, .. }) = &mut self.0.write(gc_context).stream_type
+            {
+                *size = Some((width, height));
+            }
+        }
+    }
+
+    /// Start the audio track from the current playhead position.
+    fn start_audio(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.stop_audio(context);
+
+        let read = self.0.read();
+        let mut audio_format = match &read.stream_type {
+            Some(NetStreamType::Flv {
+                audio_format: Some(audio_format),
+                ..
+            }) => Some(audio_format.clone()),
+            _ => return,
+        };
+
+        // Queue up the audio that has already been loaded; the rest is
+        // appended as it arrives.
+        let substream = Substream::new();
+        let mut has_packets = false;
+        let mut reader = FlvReader::from_parts(&read.buffer[..read.scan_offset], read.offset);
+        while let Ok(tag) = FlvTag::parse(&mut reader) {
+            if let FlvTagData::Audio(audio_data) = tag.data {
+                if let Some(packet) = read_audio_packet(&mut audio_format, &audio_data) {
+                    substream.append(packet);
+                    has_packets = true;
+                }
+            }
+        }
+        drop(read);
+
+        if !has_packets {
+            // The audio track has ended, or its next packets haven't been
+            // loaded yet.
+            return;
+        }
+
+        let format = audio_format.expect("Audio format was set above");
+        let instance = context.start_substream(substream.clone(), &format);
+        let mut write = self.0.write(context.gc_context);
+        write.sound_instance = instance;
+        write.substream = Some(substream);
+    }
+
+    fn stop_audio(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        write.substream = None;
+        let instance = write.sound_instance.take();
+        drop(write);
+        if let Some(instance) = instance {
+            context.stop_sound(instance);
+        }
+    }
+
+    /// Send a status event to the AVM side of this stream: `onStatus` in AVM1,
+    /// or a `NetStatusEvent` in AVM2.
+    fn trigger_status_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        values: &[(&'static str, &'static str)],
+    ) {
+        let avm_object = self.0.read().avm_object;
+        match avm_object {
+            Some(AvmObject::Avm1(object)) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetStream Status Event]"),
+                );
+                let info_object = Avm1ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                for (key, value) in values {
+                    info_object.define_value(
+                        activation.context.gc_context,
+                        *key,
+                        AvmString::from(*value).into(),
+                        Avm1Attribute::empty(),
+                    );
+                }
+
+                if let Err(e) = object.call_method(
+                    "onStatus".into(),
+                    &[info_object.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!(
+                        "Got error when dispatching AVM1 onStatus event from NetStream: {}",
+                        e
+                    );
+                }
+            }
+            Some(AvmObject::Avm2(object)) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                if let Err(e) = dispatch_avm2_status_event(&mut activation, object, values) {
+                    tracing::error!(
+                        "Got error when dispatching AVM2 NetStatusEvent from NetStream: {}",
+                        e
+                    );
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// Determine the display size of a VP6 keyframe, for use as the decoder's
+/// bounds when the stream has no metadata.
+fn vp6_frame_size(codec: VideoCodec, packet: &[u8]) -> Option<(u16, u16)> {
+    let adjustment = *packet.first()?;
+    let frame = match codec {
+        VideoCodec::Vp6 => packet.get(1..)?,
+        // Skip the alpha data offset.
+        VideoCodec::Vp6WithAlpha => packet.get(4..)?,
+        _ => return None,
+    };
+
+    let is_keyframe = frame.first()? & 0x80 == 0;
+    if !is_keyframe {
+        return None;
+    }
+
+    let is_multistream = frame[0] & 0x01 != 0;
+    let profile = (frame.get(1)? >> 1) & 0x03;
+    let dimensions_start = if is_multistream || profile == 0 { 4 } else { 2 };
+    let display_rows = *frame.get(dimensions_start + 2)? as u16;
+    let display_cols = *frame.get(dimensions_start + 3)? as u16;
+
+    Some((
+        (display_cols * 16).saturating_sub((adjustment >> 4) as u16),
+        (display_rows * 16).saturating_sub((adjustment & 0x0F) as u16),
+    ))
+}
+
+/// Pick the encoded audio out of an FLV audio tag.
+///
+/// The first audio tag sets the format of the stream's audio track, and
+/// tags in any other format are ignored.
+fn read_audio_packet<'a>(
+    audio_format: &mut Option<SubstreamFormat>,
+    audio_data: &FlvAudioData<'a>,
+) -> Option<&'a [u8]> {
+    let (format, packet) = match audio_data.data {
+        FlvAudioDataType::AacSequenceHeader(config) => {
+            (SubstreamFormat::Aac(config.to_vec()), None)
+        }
+        FlvAudioDataType::AacRaw(packet) => {
+            // AAC frames can only be decoded after their sequence header.
+            return match audio_format {
+                Some(SubstreamFormat::Aac(_)) => Some(packet),
+                _ => None,
+            };
+        }
+        FlvAudioDataType::Raw(packet) => match swf_sound_format(audio_data) {
+            Some(format) => (SubstreamFormat::Swf(format), Some(packet)),
+            None => {
+                if audio_format.is_none() {
+                    tracing::warn!("Unsupported FLV audio format: {:?}", audio_data.format);
+                }
+                return None;
+            }
+        },
+    };
+
+    match audio_format {
+        Some(existing) if *existing == format => packet,
+        Some(_) => {
+            tracing::warn!("FLV audio format changed mid-stream; ignoring audio tag");
+            None
+        }
+        None => {
+            *audio_format = Some(format);
+            packet
+        }
+    }
+}
+
+/// The SWF sound format that matches an FLV audio tag that isn't AAC.
+fn swf_sound_format(audio_data: &FlvAudioData<'_>) -> Option<swf::SoundFormat> {
+    let (compression, sample_rate) = match audio_data.format {
+        FlvSoundFormat::LinearPcmPlatformEndian => (
+            AudioCompression::UncompressedUnknownEndian,
+            audio_data.rate.hz(),
+        ),
+        FlvSoundFormat::Adpcm => (AudioCompression::Adpcm, audio_data.rate.hz()),
+        FlvSoundFormat::LinearPcmLittleEndian => {
+            (AudioCompression::Uncompressed, audio_data.rate.hz())
+        }
+        FlvSoundFormat::Mp3 => (AudioCompression::Mp3, audio_data.rate.hz()),
+        FlvSoundFormat::Mp38kHz => (AudioCompression::Mp3, 8000),
+        // The fixed rate Nellymoser variants decode like any other Nellymoser sound.
+        FlvSoundFormat::Nellymoser => (AudioCompression::Nellymoser, audio_data.rate.hz()),
+        FlvSoundFormat::Nellymoser16kHz => (AudioCompression::Nellymoser, 16000),
+        FlvSoundFormat::Nellymoser8kHz => (AudioCompression::Nellymoser, 8000),
+        FlvSoundFormat::Speex => (AudioCompression::Speex, 16000),
+        _ => return None,
+    };
+
+    Some(swf::SoundFormat {
+        compression,
+        sample_rate,
+        is_stereo: audio_data.sound_type == FlvSoundType::Stereo,
+        is_16_bit: audio_data.size == FlvSoundSize::Bits16,
+    })
+}
+
+/// Convert an FLV script data value into an AVM1 value.
+fn flv_to_avm1_value<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    value: &FlvValue<'_>,
+) -> Avm1Value<'gc> {
+    match value {
+        FlvValue::Number(n) => (*n).into(),
+        FlvValue::Boolean(b) => (*b).into(),
+        FlvValue::String(s) | FlvValue::LongString(s) | FlvValue::MovieClip(s) => {
+            AvmString::new_utf8_bytes(activation.context.gc_context, s).into()
+        }
+        FlvValue::Null => Avm1Value::Null,
+        FlvValue::Undefined | FlvValue::Reference(_) => Avm1Value::Undefined,
+        FlvValue::Object(variables) | FlvValue::EcmaArray(variables) => {
+            let object = Avm1ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            for variable in variables {
+                let name = AvmString::new_utf8_bytes(activation.context.gc_context, variable.name);
+                let value = flv_to_avm1_value(activation, &variable.data);
+                object.define_value(
+                    activation.context.gc_context,
+                    name,
+                    value,
+                    Avm1Attribute::empty(),
+                );
+            }
+            object.into()
+        }
+        FlvValue::StrictArray(values) => {
+            let values: Vec<_> = values
+                .iter()
+                .map(|value| flv_to_avm1_value(activation, value))
+                .collect();
+            Avm1ArrayObject::new(
+                activation.context.gc_context,
+                activation.context.avm1.prototypes().array,
+                values,
+            )
+            .into()
+        }
+        FlvValue::Date { unix_time, .. } => {
+            let date_constructor = activation.context.avm1.prototypes().date_constructor;
+            date_constructor
+                .construct(activation, &[(*unix_time).into()])
+                .unwrap_or(Avm1Value::Undefined)
+        }
+    }
+}
+
+/// Convert an FLV script data value into an AVM2 value.
+fn flv_to_avm2_value<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    value: &FlvValue<'_>,
+) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+    Ok(match value {
+        FlvValue::Number(n) => (*n).into(),
+        FlvValue::Boolean(b) => (*b).into(),
+        FlvValue::String(s) | FlvValue::LongString(s) | FlvValue::MovieClip(s) => {
+            AvmString::new_utf8_bytes(activation.context.gc_context, s).into()
+        }
+        FlvValue::Null => Avm2Value::Null,
+        FlvValue::Undefined | FlvValue::Reference(_) => Avm2Value::Undefined,
+        FlvValue::Object(variables) | FlvValue::EcmaArray(variables) => {
+            let mut object = activation
+                .avm2()
+                .classes()
+                .object
+                .construct(activation, &[])?;
+            for variable in variables {
+                let name = AvmString::new_utf8_bytes(activation.context.gc_context, variable.name);
+                let value = flv_to_avm2_value(activation, &variable.data)?;
+                object.set_public_property(name, value, activation)?;
+            }
+            object.into()
+        }
+        FlvValue::StrictArray(values) => {
+            let mut storage = Vec::with_capacity(values.len());
+            for value in values {
+                storage.push(Some(flv_to_avm2_value(activation, value)?));
+            }
+            Avm2ArrayObject::from_storage(activation, Avm2ArrayStorage::from_storage(storage))?
+                .into()
+        }
+        FlvValue::Date { unix_time, .. } => activation
+            .avm2()
+            .classes()
+            .date
+            .construct(activation, &[(*unix_time).into()])?
+            .into(),
+    })
+}
+
+/// Call a script data callback on an AVM2 `NetStream`'s client object.
+fn call_avm2_client<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    net_stream: Avm2Object<'gc>,
+    name: AvmString<'gc>,
+    value: &FlvValue<'_>,
+) -> Result<(), Avm2Error<'gc>> {
+    let client = net_stream.get_public_property("client", activation)?;
+    if let Avm2Value::Object(client) = client {
+        if client.has_public_property(name, activation) {
+            let argument = flv_to_avm2_value(activation, value)?;
+            client.call_public_property(name, &[argument], activation)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn dispatch_avm2_status_event<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    net_stream: Avm2Object<'gc>,
+    values: &[(&'static str, &'static str)],
+) -> Result<(), Avm2Error<'gc>> {
+    let mut info_object = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    for (key, value) in values {
+        info_object.set_public_property(*key, AvmString::from(*value).into(), activation)?;
+    }
+
+    let net_status_event = activation.avm2().classes().netstatusevent.construct(
+        activation,
+        &[
+            "netStatus".into(),
+            false.into(),
+            false.into(),
+            info_object.into(),
+        ],
+    )?;
+    Avm2::dispatch_event(&mut activation.context, net_status_event, net_stream)?;
+
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use ruffle_core::backend::font::{FontBackend, FontFile};
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use ttf_parser::{name, name_id, os2, Style, Width};
+
+/// How deep to descend into subdirectories of a font directory.
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// A font face found while scanning the font directories.
+struct FontEntry {
+    path: PathBuf,
+    index: u32,
+
+    /// The lowercase family and full names of the face.
+    names: Vec<String>,
+    is_bold: bool,
+    is_italic: bool,
+    weight: u16,
+    is_normal_width: bool,
+}
+
+/// A font backend that searches the system font directories for TrueType
+/// and OpenType fonts.
+pub struct DesktopFontBackend {
+    directories: Vec<PathBuf>,
+
+    /// The faces found in `directories`, scanned on the first lookup.
+    entries: Option<Vec<FontEntry>>,
+}
+
+impl DesktopFontBackend {
+    /// Creates a font backend searching the system font directories, followed
+    /// by the given directories.
+    pub fn new(extra_directories: Vec<PathBuf>) -> Self {
+        let mut directories = system_font_directories();
+        directories.extend(extra_directories);
+        Self {
+            directories,
+            entries: None,
+        }
+    }
+}
+
+impl FontBackend for DesktopFontBackend {
+    fn load_font(&mut self, name: &str, is_bold: bool, is_italic: bool) -> Option<FontFile> {
+        let directories = &self.directories;
+        let entries = self.entries.get_or_insert_with(|| scan(directories));
+        let entry = find_entry(entries, name, is_bold, is_italic)?;
+
+        match fs::read(&entry.path) {
+            Ok(data) => Some(FontFile {
+                data,
+                index: entry.index,
+            }),
+            Err(e) => {
+                tracing::warn!("Couldn't read font {}: {}", entry.path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+/// Find the face best matching the given family name and style.
+///
+/// Prefers the face with the requested style, falling back to another face
+/// of the same family. Among faces with the same style, prefers the usual
+/// width and weight over condensed or light variants.
+fn find_entry<'a>(
+    entries: &'a [FontEntry],
+    name: &str,
+    is_bold: bool,
+    is_italic: bool,
+) -> Option<&'a FontEntry> {
+    let name = name.to_lowercase();
+    let weight = if is_bold { 700 } else { 400 };
+    entries
+        .iter()
+        .filter(|entry| entry.names.contains(&name))
+        .min_by_key(|entry| {
+            (
+                usize::from(entry.is_bold != is_bold) + usize::from(entry.is_italic != is_italic),
+                !entry.is_normal_width,
+                entry.weight.abs_diff(weight),
+            )
+        })
+}
+
+/// The directories that fonts are installed in on this platform.
+fn system_font_directories() -> Vec<PathBuf> {
+    let mut directories = vec![];
+    if cfg!(target_os = "windows") {
+        if let Some(windows) = std::env::var_os("WINDIR") {
+            directories.push(PathBuf::from(windows).join("Fonts"));
+        }
+        if let Some(local) = dirs::data_local_dir() {
+            directories.push(local.join("Microsoft").join("Windows").join("Fonts"));
+        }
+    } else if cfg!(target_os = "macos") {
+        directories.push("/System/Library/Fonts".into());
+        directories.push("/Library/Fonts".into());
+    } else {
+        directories.push("/usr/share/fonts".into());
+        directories.push("/usr/local/share/fonts".into());
+        if let Some(home) = dirs::home_dir() {
+            directories.push(home.join(".fonts"));
+        }
+    }
+    if let Some(user) = dirs::font_dir() {
+        directories.push(user);
+    }
+    directories
+}
+
+/// Find all font faces in the given directories.
+fn scan(directories: &[PathBuf]) -> Vec<FontEntry> {
+    let mut entries = vec![];
+    for directory in directories {
+        scan_directory(directory, 0, &mut entries);
+    }
+    tracing::info!("Found {} system font faces", entries.len());
+    entries
+}
+
+fn scan_directory(directory: &Path, depth: usize, entries: &mut Vec<FontEntry>) {
+    let read_dir = match fs::read_dir(directory) {
+        Ok(read_dir) => read_dir,
+        Err(_) => return,
+    };
+
+    for dir_entry in read_dir.flatten() {
+        let path = dir_entry.path();
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH {
+                scan_directory(&path, depth + 1, entries);
+            }
+            continue;
+        }
+
+        let is_font = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| {
+                matches!(
+                    extension.to_ascii_lowercase().as_str(),
+                    "ttf" | "otf" | "ttc" | "otc"
+                )
+            })
+            .unwrap_or(false);
+        if !is_font {
+            continue;
+        }
+
+        // Font files can be large, so only the tables needed to match
+        // faces are read here. The whole file is read once it's requested.
+        match read_faces(&path) {
+            Ok(faces) => entries.extend(faces),
+            Err(e) => tracing::debug!("Couldn't read font {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// The largest `name` or `OS/2` table that will be read while scanning.
+const MAX_TABLE_LENGTH: u32 = 1 << 20;
+
+/// Read the names and style of each face of a font file, from its `name` and
+/// `OS/2` tables.
+fn read_faces(path: &Path) -> io::Result<Vec<FontEntry>> {
+    let mut file = File::open(path)?;
+
+    // A font collection starts with the offsets of its faces.
+    let tag = read_bytes(&mut file, 0, 4)?;
+    let face_offsets = if tag == b"ttcf" {
+        let num_faces = read_u32(&read_bytes(&mut file, 8, 4)?, 0);
+        let offsets = read_bytes(&mut file, 12, num_faces.min(256) * 4)?;
+        offsets
+            .chunks(4)
+            .map(|offset| read_u32(offset, 0))
+            .collect()
+    } else {
+        vec![0]
+    };
+
+    let mut faces = vec![];
+    for (index, face_offset) in face_offsets.into_iter().enumerate() {
+        let face_offset = u64::from(face_offset);
+        let num_tables = read_u16(&read_bytes(&mut file, face_offset + 4, 2)?, 0);
+        let records = read_bytes(&mut file, face_offset + 12, u32::from(num_tables) * 16)?;
+
+        let mut name_table = None;
+        let mut os2_table = None;
+        for record in records.chunks(16) {
+            let table = match &record[..4] {
+                b"name" => &mut name_table,
+                b"OS/2" => &mut os2_table,
+                _ => continue,
+            };
+            let length = read_u32(record, 12);
+            if length <= MAX_TABLE_LENGTH {
+                *table = Some(read_bytes(&mut file, read_u32(record, 8).into(), length)?);
+            }
+        }
+
+        // A face without names can't be requested.
+        let Some(names) = name_table.as_deref().and_then(name::Table::parse) else {
+            continue;
+        };
+        let os2 = os2_table.as_deref().and_then(os2::Table::parse);
+        faces.push(FontEntry {
+            path: path.to_owned(),
+            index: index as u32,
+            names: face_names(names.names),
+            is_bold: os2.map_or(false, |os2| os2.is_bold()),
+            is_italic: os2.map_or(false, |os2| os2.style() != Style::Normal),
+            weight: os2.map_or(400, |os2| os2.weight().to_number()),
+            is_normal_width: os2.map_or(true, |os2| os2.width() == Width::Normal),
+        });
+    }
+    Ok(faces)
+}
+
+/// Read `length` bytes of a file, starting at `offset`.
+fn read_bytes(file: &mut File, offset: u64, length: u32) -> io::Result<Vec<u8>> {
+    let mut data = vec![0; length as usize];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut data)?;
+    Ok(data)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+/// The names that a face can be requested by: its family names, and its
+/// full name (such as "Arial Bold").
+fn face_names(names: name::Names<'_>) -> Vec<String> {
+    let mut names: Vec<String> = names
+        .into_iter()
+        .filter(|name| {
+            matches!(
+                name.name_id,
+                name_id::FAMILY | name_id::TYPOGRAPHIC_FAMILY | name_id::FULL_NAME
+            )
+        })
+        .filter_map(|name| name.to_string())
+        .map(|name| name.to_lowercase())
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, is_bold: bool, is_italic: bool, weight: u16) -> FontEntry {
+        FontEntry {
+            path: PathBuf::from(format!("{name}-{weight}-{is_bold}-{is_italic}.ttf")),
+            index: 0,
+            names: vec![name.to_lowercase()],
+            is_bold,
+            is_italic,
+            weight,
+            is_normal_width: true,
+        }
+    }
+
+    fn find<'a>(entries: &'a [FontEntry], name: &str, bold: bool, italic: bool) -> &'a Path {
+        &find_entry(entries, name, bold, italic).unwrap().path
+    }
+
+    #[test]
+    fn matches_family_and_style() {
+        let entries = [
+            entry("Arial", false, false, 400),
+            entry("Arial", true, false, 700),
+            entry("Arial", false, true, 400),
+            entry("Arial", true, true, 700),
+            entry("Times New Roman", false, false, 400),
+        ];
+
+        assert_eq!(find(&entries, "arial", false, false), entries[0].path);
+        assert_eq!(find(&entries, "Arial", true, false), entries[1].path);
+        assert_eq!(find(&entries, "ARIAL", false, true), entries[2].path);
+        assert_eq!(find(&entries, "Arial", true, true), entries[3].path);
+        assert_eq!(
+            find(&entries, "Times New Roman", true, false),
+            entries[4].path
+        );
+        assert!(find_entry(&entries, "Courier New", false, false).is_none());
+    }
+
+    #[test]
+    fn prefers_usual_weight_and_width() {
+        let mut condensed = entry("Noto Sans", false, false, 400);
+        condensed.is_normal_width = false;
+        let entries = [
+            condensed,
+            entry("Noto Sans", false, false, 100),
+            entry("Noto Sans", false, false, 400),
+            entry("Noto Sans", true, false, 900),
+            entry("Noto Sans", true, false, 700),
+        ];
+
+        assert_eq!(find(&entries, "Noto Sans", false, false), entries[2].path);
+        assert_eq!(find(&entries, "Noto Sans", true, false), entries[4].path);
+        // Without an italic face, the upright face of the same weight is used.
+        assert_eq!(find(&entries, "Noto Sans", true, true), entries[4].path);
+    }
+}
@@ -9,6 +9,7 @@
 mod audio;
 mod custom_event;
 mod executor;
+mod font;
 mod navigator;
 mod storage;
 mod task;
@@ -141,6 +142,17 @@ struct Opt {
     /// The version of the player to emulate
     #[clap(long)]
     player_version: Option<u8>,
+
+    /// An additional directory to search for device fonts.
+    /// This can be repeated multiple times.
+    #[clap(long = "font-dir", action = clap::ArgAction::Append)]
+    font_dirs: Vec<PathBuf>,
+
+    /// Allow movies to open socket connections to the given address,
+    /// in the form `host:port`.
+    /// This can be repeated multiple times.
+    #[clap(long = "socket-allow", action = clap::ArgAction::Append)]
+    socket_allow: Vec<String>,
 }
 
 #[cfg(feature = "render_trace")]
@@ -312,6 +324,7 @@ impl App {
             event_loop.create_proxy(),
             opt.proxy.clone(),
             opt.upgrade_to_https,
+            opt.socket_allow.iter().cloned().collect(),
         );
 
         let viewport_size = window.inner_size();
@@ -338,6 +351,7 @@ impl App {
             .with_renderer(renderer)
             .with_storage(storage::DiskStorageBackend::new()?)
             .with_ui(ui::DesktopUiBackend::new(window.clone())?)
+            .with_font_backend(font::DesktopFontBackend::new(opt.font_dirs.clone()))
             .with_autoplay(true)
             .with_letterbox(opt.letterbox)
             .with_quality(opt.quality)
@@ -637,6 +651,9 @@ fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> KeyCode {
         VirtualKeyCode::Return => KeyCode::Return,
         VirtualKeyCode::LShift | VirtualKeyCode::RShift => KeyCode::Shift,
         VirtualKeyCode::LControl | VirtualKeyCode::RControl => KeyCode::Control,
+        VirtualKeyCode::LWin | VirtualKeyCode::RWin if cfg!(target_os = "macos") => {
+            KeyCode::Command
+        }
         VirtualKeyCode::LAlt | VirtualKeyCode::RAlt => KeyCode::Alt,
         VirtualKeyCode::Capital => KeyCode::CapsLock,
         VirtualKeyCode::Escape => KeyCode::Escape,
@@ -1,16 +1,22 @@
 //! Navigator backend for web
 
 use crate::custom_event::RuffleEvent;
-use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
-};
+use futures::AsyncReadExt;
+use isahc::{config::RedirectPolicy, prelude::*, AsyncBody, HttpClient, Request as IsahcRequest};
 use ruffle_core::backend::navigator::{
-    NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response,
+    NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response, ResponseBody,
+    StreamingResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
 use std::rc::Rc;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
 use url::Url;
 use winit::event_loop::EventLoopProxy;
 
@@ -30,6 +36,9 @@ pub struct ExternalNavigatorBackend {
     client: Option<Rc<HttpClient>>,
 
     upgrade_to_https: bool,
+
+    /// The `host:port` addresses that movies may open sockets to.
+    socket_allow: HashSet<String>,
 }
 
 impl ExternalNavigatorBackend {
@@ -40,6 +49,7 @@ impl ExternalNavigatorBackend {
         event_loop: EventLoopProxy<RuffleEvent>,
         proxy: Option<Url>,
         upgrade_to_https: bool,
+        socket_allow: HashSet<String>,
     ) -> Self {
         let proxy = proxy.and_then(|url| url.as_str().parse().ok());
         let builder = HttpClient::builder()
@@ -61,6 +71,7 @@ impl ExternalNavigatorBackend {
             client,
             base_url,
             upgrade_to_https,
+            socket_allow,
         }
     }
 }
@@ -113,6 +124,11 @@ impl NavigatorBackend for ExternalNavigatorBackend {
     }
 
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
+        let fetch = self.fetch_streaming(request);
+        Box::pin(async move { fetch.await?.into_response().await })
+    }
+
+    fn fetch_streaming(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
         // TODO: honor sandbox type (local-with-filesystem, local-with-network, remote, ...)
         let full_url = match self.base_url.join(request.url()) {
             Ok(url) => url,
@@ -155,47 +171,77 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     Err(e)
                 }).map_err(|e| Error::FetchError(e.to_string()))?;
 
-                Ok(Response { url, body })
+                Ok(Response {
+                    url,
+                    status: 0,
+                    redirected: false,
+                    headers: Vec::new(),
+                    body,
+                }
+                .into())
             }),
             _ => Box::pin(async move {
                 let client =
                     client.ok_or_else(|| Error::FetchError("Network unavailable".to_string()))?;
 
-                let isahc_request = match request.method() {
+                let mut isahc_request = match request.method() {
                     NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
                     NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
                 };
+                for (name, value) in request.headers() {
+                    isahc_request = isahc_request.header(name, value);
+                }
 
                 let (body_data, _) = request.body().clone().unwrap_or_default();
                 let body = isahc_request
                     .body(body_data)
                     .map_err(|e| Error::FetchError(e.to_string()))?;
 
+                // isahc records the URI it ended up at after following
+                // redirects, in the same form as the URI it was asked for.
+                let request_uri = body.uri().clone();
                 let mut response = client
                     .send_async(body)
                     .await
                     .map_err(|e| Error::FetchError(e.to_string()))?;
 
-                if !response.status().is_success() {
-                    return Err(Error::FetchError(format!(
-                        "HTTP status is not ok, got {}",
-                        response.status()
-                    )));
-                }
-
-                let url = if let Some(uri) = response.effective_uri() {
-                    uri.to_string()
+                let effective_uri = response.effective_uri().unwrap_or(&request_uri);
+                let redirected = *effective_uri != request_uri;
+                let url = if redirected {
+                    effective_uri.to_string()
                 } else {
-                    processed_url.into()
+                    processed_url.to_string()
                 };
+                let status = response.status().as_u16();
+                let headers = response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.to_string(),
+                            String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                        )
+                    })
+                    .collect();
 
-                let mut body = vec![];
-                response
-                    .copy_to(&mut body)
-                    .await
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
+                if !response.status().is_success() {
+                    return Err(Error::HttpNotOk {
+                        url,
+                        status,
+                        redirected,
+                        headers,
+                    });
+                }
 
-                Ok(Response { url, body })
+                Ok(StreamingResponse {
+                    url,
+                    status,
+                    redirected,
+                    headers,
+                    body: Box::new(IsahcResponseBody(Rc::new(Cell::new(Some(
+                        response.into_body(),
+                    ))))),
+                })
             }),
         }
     }
@@ -216,4 +262,107 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
         url
     }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let address = format!("{host}:{port}");
+        if !self.socket_allow.contains(&address) {
+            tracing::warn!(
+                "Movie tried to connect to socket {}, which is not allowed. Use --socket-allow {} to allow it.",
+                address,
+                address
+            );
+            let _ = sender.send(SocketAction::Connect(handle, ConnectionState::NotAllowed));
+            return;
+        }
+
+        std::thread::spawn(move || {
+            let stream = address
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .and_then(|addr| TcpStream::connect_timeout(&addr, timeout).ok());
+            let mut stream = match stream {
+                Some(stream) => stream,
+                None => {
+                    tracing::warn!("Failed to connect to socket {}", address);
+                    let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+                    return;
+                }
+            };
+
+            let mut read_stream = match stream.try_clone() {
+                Ok(read_stream) => read_stream,
+                Err(e) => {
+                    tracing::warn!("Failed to connect to socket {}: {}", address, e);
+                    let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+                    return;
+                }
+            };
+            let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Connected));
+
+            std::thread::spawn(move || {
+                let mut buffer = [0; 4096];
+                loop {
+                    match read_stream.read(&mut buffer) {
+                        Ok(0) | Err(_) => {
+                            let _ = sender.send(SocketAction::Close(handle));
+                            break;
+                        }
+                        Ok(read) => {
+                            let data = buffer[..read].to_vec();
+                            if sender.send(SocketAction::Data(handle, data)).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            });
+
+            // The player drops its end of the channel when the movie closes
+            // the socket.
+            while let Ok(data) = receiver.recv() {
+                if stream.write_all(&data).is_err() {
+                    break;
+                }
+            }
+            let _ = stream.shutdown(Shutdown::Both);
+        });
+    }
+}
+
+/// The body of an HTTP response, read in chunks as they arrive.
+///
+/// The body is put back after each chunk, since every chunk is read by its own future.
+struct IsahcResponseBody(Rc<Cell<Option<AsyncBody>>>);
+
+impl ResponseBody for IsahcResponseBody {
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let slot = self.0.clone();
+        Box::pin(async move {
+            let Some(mut body) = slot.take() else {
+                return Ok(None);
+            };
+
+            let mut chunk = vec![0; 16384];
+            let len = body
+                .read(&mut chunk)
+                .await
+                .map_err(|e| Error::FetchError(e.to_string()))?;
+            if len == 0 {
+                return Ok(None);
+            }
+
+            chunk.truncate(len);
+            slot.set(Some(body));
+            Ok(Some(chunk))
+        })
+    }
 }
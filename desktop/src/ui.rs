@@ -59,6 +59,16 @@ impl UiBackend for DesktopUiBackend {
         }
     }
 
+    fn clipboard_content(&mut self) -> String {
+        match self.clipboard.get_text() {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Couldn't get clipboard contents: {:?}", e);
+                String::new()
+            }
+        }
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("Unexpected end of FLV data")]
+    EndOfData,
+
+    #[error("Not an FLV file")]
+    WrongMagic,
+
+    #[error("Unknown tag type: {0}")]
+    UnknownTagType(u8),
+
+    #[error("Unknown audio format: {0}")]
+    UnknownAudioFormat(u8),
+
+    #[error("Unknown video codec: {0}")]
+    UnknownVideoCodec(u8),
+
+    #[error("Unknown video frame type: {0}")]
+    UnknownVideoFrameType(u8),
+
+    #[error("Unknown AVC packet type: {0}")]
+    UnknownAvcPacketType(u8),
+
+    #[error("Unknown script data value type: {0}")]
+    UnknownValueType(u8),
+
+    #[error("Script data variable name is not a string")]
+    InvalidVariableName,
+
+    #[error("Script data values are nested too deeply")]
+    NestingTooDeep,
+}
@@ -0,0 +1,90 @@
+use crate::error::Error;
+use crate::reader::FlvReader;
+
+/// The header at the start of every FLV file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub version: u8,
+    pub has_audio: bool,
+    pub has_video: bool,
+
+    /// The offset of the first tag's `PreviousTagSize` field.
+    pub data_offset: u32,
+}
+
+impl Header {
+    /// Parse an FLV header.
+    ///
+    /// On success, the reader is left positioned at the first tag, having
+    /// skipped over any extra header data and the zero `PreviousTagSize0`
+    /// field. On failure, the reader position is left unchanged.
+    pub fn parse(reader: &mut FlvReader<'_>) -> Result<Self, Error> {
+        let start = reader.position();
+        let result = Self::parse_inner(reader);
+        if result.is_err() {
+            reader.seek(start);
+        }
+        result
+    }
+
+    fn parse_inner(reader: &mut FlvReader<'_>) -> Result<Self, Error> {
+        let start = reader.position();
+        if reader.read(3)? != b"FLV" {
+            return Err(Error::WrongMagic);
+        }
+
+        let version = reader.read_u8()?;
+        let flags = reader.read_u8()?;
+        let data_offset = reader.read_u32()?;
+
+        let skip = (data_offset as usize).saturating_sub(reader.position() - start);
+        reader.read(skip)?;
+        let _previous_tag_size = reader.read_u32()?;
+
+        Ok(Self {
+            version,
+            has_audio: flags & 0x04 != 0,
+            has_video: flags & 0x01 != 0,
+            data_offset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_header() {
+        let data = [
+            b'F', b'L', b'V', 0x01, 0x05, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(
+            Header::parse(&mut reader),
+            Ok(Header {
+                version: 1,
+                has_audio: true,
+                has_video: true,
+                data_offset: 9,
+            })
+        );
+        assert_eq!(reader.position(), 13);
+    }
+
+    #[test]
+    fn parse_wrong_magic() {
+        let data = [b'F', b'W', b'S', 0x01, 0x05, 0x00, 0x00, 0x00, 0x09];
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Header::parse(&mut reader), Err(Error::WrongMagic));
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    fn parse_truncated_header() {
+        let data = [b'F', b'L', b'V', 0x01, 0x05, 0x00, 0x00];
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Header::parse(&mut reader), Err(Error::EndOfData));
+        assert_eq!(reader.position(), 0);
+    }
+}
@@ -0,0 +1,23 @@
+//! # flv-rs
+//!
+//! Library for demuxing Flash Video (FLV) files.
+//!
+//! An FLV file consists of a `Header` followed by a sequence of `Tag`s, each
+//! of which carries audio, video, or script data along with a timestamp.
+//! Parsing is zero-copy: all returned data borrows from the input buffer.
+
+mod error;
+mod header;
+mod reader;
+mod script;
+mod sound;
+mod tag;
+mod video;
+
+pub use error::Error;
+pub use header::Header;
+pub use reader::FlvReader;
+pub use script::{ScriptData, Value, Variable};
+pub use sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+pub use tag::{Tag, TagData};
+pub use video::{CodecId, FrameType, VideoData, VideoPacket};
@@ -0,0 +1,116 @@
+use crate::error::Error;
+
+/// A cursor over a byte buffer holding FLV data.
+///
+/// All multi-byte values in FLV are big-endian.
+#[derive(Clone, Debug)]
+pub struct FlvReader<'a> {
+    source: &'a [u8],
+    position: usize,
+}
+
+impl<'a> FlvReader<'a> {
+    /// Create a reader positioned at the start of `source`.
+    pub fn from_source(source: &'a [u8]) -> Self {
+        Self::from_parts(source, 0)
+    }
+
+    /// Create a reader positioned at `position` within `source`.
+    ///
+    /// This is intended to resume reading from a position previously obtained
+    /// with `into_parts`, possibly after more data has been appended.
+    pub fn from_parts(source: &'a [u8], position: usize) -> Self {
+        Self { source, position }
+    }
+
+    /// Split the reader back into its buffer and position.
+    pub fn into_parts(self) -> (&'a [u8], usize) {
+        (self.source, self.position)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn seek(&mut self, position: usize) {
+        self.position = position.min(self.source.len());
+    }
+
+    /// The number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.source.len() - self.position
+    }
+
+    /// Read `count` bytes, advancing the reader.
+    pub fn read(&mut self, count: usize) -> Result<&'a [u8], Error> {
+        if self.remaining() < count {
+            return Err(Error::EndOfData);
+        }
+
+        let data = &self.source[self.position..self.position + count];
+        self.position += count;
+        Ok(data)
+    }
+
+    /// Read everything up to the end of the buffer.
+    pub fn read_to_end(&mut self) -> &'a [u8] {
+        let data = &self.source[self.position..];
+        self.position = self.source.len();
+        data
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, Error> {
+        let data = self.read(2)?;
+        Ok(u16::from_be_bytes([data[0], data[1]]))
+    }
+
+    pub fn read_i16(&mut self) -> Result<i16, Error> {
+        Ok(self.read_u16()? as i16)
+    }
+
+    pub fn read_u24(&mut self) -> Result<u32, Error> {
+        let data = self.read(3)?;
+        Ok(u32::from_be_bytes([0, data[0], data[1], data[2]]))
+    }
+
+    /// Read a signed 24-bit value, as used for AVC composition times.
+    pub fn read_i24(&mut self) -> Result<i32, Error> {
+        let value = self.read_u24()?;
+        Ok(((value << 8) as i32) >> 8)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, Error> {
+        let data = self.read(4)?;
+        Ok(u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, Error> {
+        let data = self.read(8)?;
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(data);
+        Ok(f64::from_be_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_values() {
+        let data = [
+            0x01, 0x02, 0x03, 0xFF, 0xFF, 0xFE, 0x3F, 0xF0, 0, 0, 0, 0, 0, 0,
+        ];
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(reader.read_u16(), Ok(0x0102));
+        assert_eq!(reader.read_u8(), Ok(0x03));
+        assert_eq!(reader.read_i24(), Ok(-2));
+        assert_eq!(reader.read_f64(), Ok(1.0));
+        assert_eq!(reader.read_u8(), Err(Error::EndOfData));
+        assert_eq!(reader.position(), data.len());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::error::Error;
+use crate::reader::FlvReader;
+
+/// An AMF0 value, as stored in script data tags.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    Number(f64),
+    Boolean(bool),
+    String(&'a [u8]),
+    Object(Vec<Variable<'a>>),
+    MovieClip(&'a [u8]),
+    Null,
+    Undefined,
+    Reference(u16),
+    EcmaArray(Vec<Variable<'a>>),
+    StrictArray(Vec<Value<'a>>),
+    Date { unix_time: f64, local_offset: i16 },
+    LongString(&'a [u8]),
+}
+
+/// A named value, as found in objects, ECMA arrays, and at the top level of
+/// script data tags.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable<'a> {
+    pub name: &'a [u8],
+    pub data: Value<'a>,
+}
+
+/// The contents of a script data tag.
+///
+/// This is usually a single `onMetaData` or `onCuePoint` variable, whose
+/// value is the argument to pass to the callback of the same name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptData<'a>(pub Vec<Variable<'a>>);
+
+/// The type marker that terminates objects and ECMA arrays.
+const OBJECT_END_MARKER: u8 = 9;
+
+/// How deeply objects and arrays may be nested before parsing fails.
+const MAX_DEPTH: usize = 64;
+
+impl<'a> ScriptData<'a> {
+    /// Parse the contents of a script data tag spanning `data_size` bytes.
+    pub fn parse(reader: &mut FlvReader<'a>, data_size: u32) -> Result<Self, Error> {
+        let mut reader = FlvReader::from_source(reader.read(data_size as usize)?);
+        let mut vars = vec![];
+
+        while reader.remaining() > 0 {
+            let name = match Value::parse(&mut reader)? {
+                Value::String(name) | Value::LongString(name) => name,
+                _ => return Err(Error::InvalidVariableName),
+            };
+            let data = Value::parse(&mut reader)?;
+            vars.push(Variable { name, data });
+        }
+
+        Ok(Self(vars))
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Parse a single AMF0 value, including its type marker.
+    pub fn parse(reader: &mut FlvReader<'a>) -> Result<Self, Error> {
+        Self::parse_nested(reader, 0)
+    }
+
+    /// Parse a value found `depth` objects or arrays deep.
+    fn parse_nested(reader: &mut FlvReader<'a>, depth: usize) -> Result<Self, Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::NestingTooDeep);
+        }
+
+        Ok(match reader.read_u8()? {
+            0 => Self::Number(reader.read_f64()?),
+            1 => Self::Boolean(reader.read_u8()? != 0),
+            2 => Self::String(read_string(reader)?),
+            3 => Self::Object(read_object_body(reader, depth + 1)?),
+            4 => Self::MovieClip(read_string(reader)?),
+            5 => Self::Null,
+            6 => Self::Undefined,
+            7 => Self::Reference(reader.read_u16()?),
+            8 => {
+                // The length is only a hint; the array is terminated like an object.
+                let _approximate_length = reader.read_u32()?;
+                Self::EcmaArray(read_object_body(reader, depth + 1)?)
+            }
+            10 => {
+                let length = reader.read_u32()?;
+                let mut values = Vec::new();
+                for _ in 0..length {
+                    values.push(Self::parse_nested(reader, depth + 1)?);
+                }
+                Self::StrictArray(values)
+            }
+            11 => Self::Date {
+                unix_time: reader.read_f64()?,
+                local_offset: reader.read_i16()?,
+            },
+            12 => {
+                let length = reader.read_u32()?;
+                Self::LongString(reader.read(length as usize)?)
+            }
+            unknown => return Err(Error::UnknownValueType(unknown)),
+        })
+    }
+}
+
+fn read_string<'a>(reader: &mut FlvReader<'a>) -> Result<&'a [u8], Error> {
+    let length = reader.read_u16()?;
+    reader.read(length as usize)
+}
+
+/// Read object properties up to and including the end marker.
+fn read_object_body<'a>(
+    reader: &mut FlvReader<'a>,
+    depth: usize,
+) -> Result<Vec<Variable<'a>>, Error> {
+    let mut vars = vec![];
+
+    loop {
+        let name = read_string(reader)?;
+        if name.is_empty() {
+            let position = reader.position();
+            if reader.read_u8()? == OBJECT_END_MARKER {
+                break;
+            }
+            reader.seek(position);
+        }
+
+        let data = Value::parse_nested(reader, depth)?;
+        vars.push(Variable { name, data });
+    }
+
+    Ok(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_metadata() {
+        let mut data = vec![];
+        data.extend_from_slice(&[0x02, 0x00, 0x0A]);
+        data.extend_from_slice(b"onMetaData");
+        data.extend_from_slice(&[0x08, 0x00, 0x00, 0x00, 0x02]);
+        data.extend_from_slice(&[0x00, 0x08]);
+        data.extend_from_slice(b"duration");
+        data.push(0x00);
+        data.extend_from_slice(&2.5f64.to_be_bytes());
+        data.extend_from_slice(&[0x00, 0x04]);
+        data.extend_from_slice(b"tags");
+        data.extend_from_slice(&[0x0A, 0x00, 0x00, 0x00, 0x02, 0x01, 0x01, 0x05]);
+        data.extend_from_slice(&[0x00, 0x00, 0x09]);
+
+        let mut reader = FlvReader::from_source(&data);
+        let script = ScriptData::parse(&mut reader, data.len() as u32).unwrap();
+        assert_eq!(
+            script,
+            ScriptData(vec![Variable {
+                name: b"onMetaData",
+                data: Value::EcmaArray(vec![
+                    Variable {
+                        name: b"duration",
+                        data: Value::Number(2.5),
+                    },
+                    Variable {
+                        name: b"tags",
+                        data: Value::StrictArray(vec![Value::Boolean(true), Value::Null]),
+                    },
+                ]),
+            }])
+        );
+    }
+
+    #[test]
+    fn parse_deeply_nested_arrays() {
+        // Each level is a strict array containing a single value.
+        let data = [0x0A, 0x00, 0x00, 0x00, 0x01].repeat(200_000);
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Value::parse(&mut reader), Err(Error::NestingTooDeep));
+
+        let mut data = [0x0A, 0x00, 0x00, 0x00, 0x01].repeat(MAX_DEPTH);
+        data.push(0x05);
+        let mut reader = FlvReader::from_source(&data);
+        assert!(Value::parse(&mut reader).is_ok());
+    }
+
+    #[test]
+    fn parse_deeply_nested_objects() {
+        // Each level is an object with a single property named "a".
+        let data = [0x03, 0x00, 0x01, b'a'].repeat(200_000);
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Value::parse(&mut reader), Err(Error::NestingTooDeep));
+    }
+
+    #[test]
+    fn parse_unknown_value() {
+        let data = [0x02, 0x00, 0x01, b'a', 0x0D];
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(
+            ScriptData::parse(&mut reader, data.len() as u32),
+            Err(Error::UnknownValueType(0x0D))
+        );
+    }
+}
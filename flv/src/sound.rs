@@ -0,0 +1,143 @@
+use crate::error::Error;
+use crate::reader::FlvReader;
+
+/// The codec used by an audio tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundFormat {
+    LinearPcmPlatformEndian = 0,
+    Adpcm = 1,
+    Mp3 = 2,
+    LinearPcmLittleEndian = 3,
+    Nellymoser16kHz = 4,
+    Nellymoser8kHz = 5,
+    Nellymoser = 6,
+    G711ALawPcm = 7,
+    G711MuLawPcm = 8,
+    Aac = 10,
+    Speex = 11,
+    Mp38kHz = 14,
+    DeviceSpecific = 15,
+}
+
+impl SoundFormat {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            0 => Self::LinearPcmPlatformEndian,
+            1 => Self::Adpcm,
+            2 => Self::Mp3,
+            3 => Self::LinearPcmLittleEndian,
+            4 => Self::Nellymoser16kHz,
+            5 => Self::Nellymoser8kHz,
+            6 => Self::Nellymoser,
+            7 => Self::G711ALawPcm,
+            8 => Self::G711MuLawPcm,
+            10 => Self::Aac,
+            11 => Self::Speex,
+            14 => Self::Mp38kHz,
+            15 => Self::DeviceSpecific,
+            _ => return None,
+        })
+    }
+}
+
+/// The nominal sample rate of an audio tag.
+///
+/// Some formats ignore this field: AAC is always flagged as 44kHz, and the
+/// fixed-rate Nellymoser and MP3 variants carry their rate in the format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundRate {
+    R5_500 = 0,
+    R11_000 = 1,
+    R22_000 = 2,
+    R44_000 = 3,
+}
+
+impl SoundRate {
+    /// The sample rate in Hz.
+    pub fn hz(self) -> u16 {
+        match self {
+            Self::R5_500 => 5512,
+            Self::R11_000 => 11025,
+            Self::R22_000 => 22050,
+            Self::R44_000 => 44100,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundSize {
+    Bits8 = 0,
+    Bits16 = 1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundType {
+    Mono = 0,
+    Stereo = 1,
+}
+
+/// The payload of an audio tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AudioDataType<'a> {
+    /// Raw encoded audio, for every format other than AAC.
+    Raw(&'a [u8]),
+
+    /// An AAC `AudioSpecificConfig`.
+    AacSequenceHeader(&'a [u8]),
+
+    /// Raw AAC frame data.
+    AacRaw(&'a [u8]),
+}
+
+/// An audio tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AudioData<'a> {
+    pub format: SoundFormat,
+    pub rate: SoundRate,
+    pub size: SoundSize,
+    pub sound_type: SoundType,
+    pub data: AudioDataType<'a>,
+}
+
+impl<'a> AudioData<'a> {
+    /// Parse the contents of an audio tag spanning `data_size` bytes.
+    pub fn parse(reader: &mut FlvReader<'a>, data_size: u32) -> Result<Self, Error> {
+        let mut reader = FlvReader::from_source(reader.read(data_size as usize)?);
+        let flags = reader.read_u8()?;
+        let format =
+            SoundFormat::from_u8(flags >> 4).ok_or(Error::UnknownAudioFormat(flags >> 4))?;
+        let rate = match (flags >> 2) & 0b11 {
+            0 => SoundRate::R5_500,
+            1 => SoundRate::R11_000,
+            2 => SoundRate::R22_000,
+            _ => SoundRate::R44_000,
+        };
+        let size = if flags & 0b10 != 0 {
+            SoundSize::Bits16
+        } else {
+            SoundSize::Bits8
+        };
+        let sound_type = if flags & 0b1 != 0 {
+            SoundType::Stereo
+        } else {
+            SoundType::Mono
+        };
+
+        let data = if format == SoundFormat::Aac {
+            match reader.read_u8()? {
+                0 => AudioDataType::AacSequenceHeader(reader.read_to_end()),
+                _ => AudioDataType::AacRaw(reader.read_to_end()),
+            }
+        } else {
+            AudioDataType::Raw(reader.read_to_end())
+        };
+
+        Ok(Self {
+            format,
+            rate,
+            size,
+            sound_type,
+            data,
+        })
+    }
+}
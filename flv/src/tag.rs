@@ -0,0 +1,154 @@
+use crate::error::Error;
+use crate::reader::FlvReader;
+use crate::script::ScriptData;
+use crate::sound::AudioData;
+use crate::video::VideoData;
+
+/// The payload of a tag.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TagData<'a> {
+    Audio(AudioData<'a>),
+    Video(VideoData<'a>),
+    Script(ScriptData<'a>),
+
+    /// A tag whose header was readable but whose payload was not.
+    ///
+    /// The tag can be skipped over and the rest of the file read normally.
+    Invalid(Error),
+}
+
+/// A single tag within an FLV file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tag<'a> {
+    /// The time at which this tag applies, in milliseconds.
+    pub timestamp: i32,
+    pub stream_id: u32,
+    pub data: TagData<'a>,
+}
+
+const AUDIO_TAG: u8 = 8;
+const VIDEO_TAG: u8 = 9;
+const SCRIPT_TAG: u8 = 18;
+
+/// The size of a tag header, not counting the trailing `PreviousTagSize`.
+const TAG_HEADER_SIZE: u32 = 11;
+
+impl<'a> Tag<'a> {
+    /// Parse a tag and its trailing `PreviousTagSize` field.
+    ///
+    /// If the buffer does not yet contain the complete tag, this returns
+    /// `Error::EndOfData` and leaves the reader position unchanged, so that
+    /// parsing can be retried once more data has arrived.
+    pub fn parse(reader: &mut FlvReader<'a>) -> Result<Self, Error> {
+        let start = reader.position();
+        let result = Self::parse_inner(reader);
+        if result.is_err() {
+            reader.seek(start);
+        }
+        result
+    }
+
+    fn parse_inner(reader: &mut FlvReader<'a>) -> Result<Self, Error> {
+        // The upper bits flag filtered (encrypted) tags, which we don't support.
+        let tag_type = reader.read_u8()? & 0x1F;
+        let data_size = reader.read_u24()?;
+        let timestamp = reader.read_u24()?;
+        let timestamp_extended = reader.read_u8()?;
+        let stream_id = reader.read_u24()?;
+
+        let payload = reader.read(data_size as usize)?;
+        let _previous_tag_size = reader.read_u32()?;
+
+        let mut payload_reader = FlvReader::from_source(payload);
+        let data = match tag_type {
+            AUDIO_TAG => AudioData::parse(&mut payload_reader, data_size).map(TagData::Audio),
+            VIDEO_TAG => VideoData::parse(&mut payload_reader, data_size).map(TagData::Video),
+            SCRIPT_TAG => ScriptData::parse(&mut payload_reader, data_size).map(TagData::Script),
+            unknown => Err(Error::UnknownTagType(unknown)),
+        }
+        .unwrap_or_else(TagData::Invalid);
+
+        Ok(Self {
+            timestamp: ((timestamp_extended as u32) << 24 | timestamp) as i32,
+            stream_id,
+            data,
+        })
+    }
+
+    /// The total size of a tag with the given payload size, including its
+    /// header and trailing `PreviousTagSize`.
+    pub fn total_size(data_size: u32) -> u32 {
+        TAG_HEADER_SIZE + data_size + 4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sound::{AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::video::{CodecId, FrameType, VideoPacket};
+
+    fn tag(tag_type: u8, timestamp: u32, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![tag_type];
+        data.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+        data.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+        data.push((timestamp >> 24) as u8);
+        data.extend_from_slice(&[0, 0, 0]);
+        data.extend_from_slice(payload);
+        data.extend_from_slice(&Tag::total_size(payload.len() as u32).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn parse_tags() {
+        let mut data = tag(VIDEO_TAG, 40, &[0x12, 0xAA, 0xBB]);
+        data.extend(tag(AUDIO_TAG, 0x01000000, &[0x2F, 0x01, 0x02]));
+        data.extend(tag(7, 80, &[0x00]));
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(
+            Tag::parse(&mut reader),
+            Ok(Tag {
+                timestamp: 40,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::SorensonH263,
+                    data: VideoPacket::Data(&[0xAA, 0xBB]),
+                }),
+            })
+        );
+        assert_eq!(
+            Tag::parse(&mut reader),
+            Ok(Tag {
+                timestamp: 0x01000000,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::Mp3,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::Raw(&[0x01, 0x02]),
+                }),
+            })
+        );
+        assert_eq!(
+            Tag::parse(&mut reader),
+            Ok(Tag {
+                timestamp: 80,
+                stream_id: 0,
+                data: TagData::Invalid(Error::UnknownTagType(7)),
+            })
+        );
+        assert_eq!(Tag::parse(&mut reader), Err(Error::EndOfData));
+    }
+
+    #[test]
+    fn parse_partial_tag() {
+        let data = tag(VIDEO_TAG, 40, &[0x12, 0xAA, 0xBB]);
+
+        let mut reader = FlvReader::from_source(&data[..data.len() - 1]);
+        assert_eq!(Tag::parse(&mut reader), Err(Error::EndOfData));
+        assert_eq!(reader.position(), 0);
+    }
+}
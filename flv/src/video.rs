@@ -0,0 +1,120 @@
+use crate::error::Error;
+use crate::reader::FlvReader;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameType {
+    Keyframe = 1,
+    Interframe = 2,
+    InterframeDisposable = 3,
+    Generated = 4,
+    CommandFrame = 5,
+}
+
+impl FrameType {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            1 => Self::Keyframe,
+            2 => Self::Interframe,
+            3 => Self::InterframeDisposable,
+            4 => Self::Generated,
+            5 => Self::CommandFrame,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodecId {
+    Jpeg = 1,
+    SorensonH263 = 2,
+    ScreenVideo = 3,
+    Vp6 = 4,
+    Vp6Alpha = 5,
+    ScreenVideo2 = 6,
+    Avc = 7,
+}
+
+impl CodecId {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            1 => Self::Jpeg,
+            2 => Self::SorensonH263,
+            3 => Self::ScreenVideo,
+            4 => Self::Vp6,
+            5 => Self::Vp6Alpha,
+            6 => Self::ScreenVideo2,
+            7 => Self::Avc,
+            _ => return None,
+        })
+    }
+}
+
+/// The payload of a video tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VideoPacket<'a> {
+    /// Encoded frame data, for every codec other than AVC.
+    ///
+    /// Note that VP6 packets in FLV are prefixed with a byte holding the
+    /// horizontal and vertical size adjustments, which SWF-embedded VP6
+    /// frames do not have.
+    Data(&'a [u8]),
+
+    /// An AVC `AVCDecoderConfigurationRecord`.
+    AvcSequenceHeader(&'a [u8]),
+
+    /// One or more AVC NAL units.
+    AvcNalu {
+        composition_time_offset: i32,
+        data: &'a [u8],
+    },
+
+    /// The end of an AVC sequence.
+    AvcEndOfSequence,
+
+    /// A video info/command frame, holding a single command byte.
+    CommandFrame(u8),
+}
+
+/// A video tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VideoData<'a> {
+    pub frame_type: FrameType,
+    pub codec_id: CodecId,
+    pub data: VideoPacket<'a>,
+}
+
+impl<'a> VideoData<'a> {
+    /// Parse the contents of a video tag spanning `data_size` bytes.
+    pub fn parse(reader: &mut FlvReader<'a>, data_size: u32) -> Result<Self, Error> {
+        let mut reader = FlvReader::from_source(reader.read(data_size as usize)?);
+        let flags = reader.read_u8()?;
+        let frame_type =
+            FrameType::from_u8(flags >> 4).ok_or(Error::UnknownVideoFrameType(flags >> 4))?;
+        let codec_id =
+            CodecId::from_u8(flags & 0x0F).ok_or(Error::UnknownVideoCodec(flags & 0x0F))?;
+
+        let data = if frame_type == FrameType::CommandFrame {
+            VideoPacket::CommandFrame(reader.read_u8()?)
+        } else if codec_id == CodecId::Avc {
+            let packet_type = reader.read_u8()?;
+            let composition_time_offset = reader.read_i24()?;
+            match packet_type {
+                0 => VideoPacket::AvcSequenceHeader(reader.read_to_end()),
+                1 => VideoPacket::AvcNalu {
+                    composition_time_offset,
+                    data: reader.read_to_end(),
+                },
+                2 => VideoPacket::AvcEndOfSequence,
+                unknown => return Err(Error::UnknownAvcPacketType(unknown)),
+            }
+        } else {
+            VideoPacket::Data(reader.read_to_end())
+        };
+
+        Ok(Self {
+            frame_type,
+            codec_id,
+            data,
+        })
+    }
+}
@@ -11,12 +11,14 @@ use ruffle_render::bitmap::{
 use ruffle_render::color_transform::ColorTransform;
 use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::error::Error;
+use ruffle_render::filters::{cpu, Filter};
 use ruffle_render::matrix::Matrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::sync::Arc;
 use swf::{BlendMode, Color};
 use wasm_bindgen::{Clamped, JsCast, JsValue};
@@ -96,6 +98,7 @@ enum CanvasDrawCommand {
     Fill {
         path: Path2d,
         fill_style: CanvasFillStyle,
+        winding_rule: CanvasWindingRule,
     },
 }
 
@@ -139,7 +142,8 @@ struct CanvasBitmap {
 #[allow(dead_code)]
 #[derive(Debug)]
 struct BitmapData {
-    bitmap: Bitmap,
+    /// The pixels as last uploaded, which the CPU filters read from.
+    bitmap: RefCell<Bitmap>,
     image_data: ImageData,
     canvas: HtmlCanvasElement,
     context: CanvasRenderingContext2d,
@@ -178,7 +182,7 @@ impl BitmapData {
             .put_image_data(&image_data, 0.0, 0.0)
             .into_js_result()?;
         Ok(BitmapData {
-            bitmap,
+            bitmap: RefCell::new(bitmap),
             image_data,
             canvas,
             context,
@@ -195,10 +199,21 @@ impl BitmapData {
         self.context
             .put_image_data(&image_data, 0.0, 0.0)
             .into_js_result()?;
+        *self.bitmap.borrow_mut() = bitmap;
         Ok(())
     }
 }
 
+/// The result of a filter, which was already applied on the CPU.
+#[derive(Debug)]
+struct CanvasSyncHandle(Bitmap);
+
+impl SyncHandle for CanvasSyncHandle {
+    fn retrieve_offscreen_texture(self: Box<Self>) -> Result<Bitmap, Error> {
+        Ok(self.0)
+    }
+}
+
 impl WebCanvasRenderBackend {
     pub fn new(
         canvas: &HtmlCanvasElement,
@@ -471,6 +486,40 @@ impl RenderBackend for WebCanvasRenderBackend {
         None
     }
 
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        // Canvas filters can't express most of these, so they're applied on the CPU instead.
+        let map_bitmap = match &filter {
+            Filter::DisplacementMapFilter(filter) => filter
+                .map_bitmap
+                .as_ref()
+                .map(|map_bitmap| as_bitmap_data(map_bitmap).bitmap.borrow().clone()),
+            _ => None,
+        };
+        let destination = as_bitmap_data(&destination);
+        let mut result = destination.bitmap.borrow().clone();
+        cpu::apply_filter(
+            &as_bitmap_data(&source).bitmap.borrow(),
+            source_point,
+            source_size,
+            &mut result,
+            dest_point,
+            &filter,
+            map_bitmap.as_ref(),
+        );
+        if let Err(e) = destination.update_pixels(result.clone()) {
+            log::error!("Failed to update filtered bitmap: {:?}", e);
+        }
+        Some(Box::new(CanvasSyncHandle(result)))
+    }
+
     fn submit_frame(&mut self, clear: Color, commands: CommandList) {
         self.begin_frame(clear);
         commands.execute(self);
@@ -539,7 +588,11 @@ impl CommandHandler for WebCanvasRenderBackend {
                 if let Some(shape) = self.shapes.get(shape.0) {
                     for command in shape.0.iter() {
                         match command {
-                            CanvasDrawCommand::Fill { path, fill_style } => {
+                            CanvasDrawCommand::Fill {
+                                path,
+                                fill_style,
+                                winding_rule,
+                            } => {
                                 if transform_dirty {
                                     let _ = self.context.set_transform(
                                         transform.matrix.a.into(),
@@ -556,10 +609,8 @@ impl CommandHandler for WebCanvasRenderBackend {
                                         let color =
                                             color.color_transform(&transform.color_transform);
                                         self.context.set_fill_style(&color.0.into());
-                                        self.context.fill_with_path_2d_and_winding(
-                                            path,
-                                            CanvasWindingRule::Evenodd,
-                                        );
+                                        self.context
+                                            .fill_with_path_2d_and_winding(path, *winding_rule);
                                     }
                                     CanvasFillStyle::Gradient(gradient) => {
                                         self.set_color_filter(&transform);
@@ -583,13 +634,11 @@ impl CommandHandler for WebCanvasRenderBackend {
                                             );
                                             self.context.fill_with_path_2d_and_winding(
                                                 &untransformed_path,
-                                                CanvasWindingRule::Evenodd,
+                                                *winding_rule,
                                             );
                                         } else {
-                                            self.context.fill_with_path_2d_and_winding(
-                                                path,
-                                                CanvasWindingRule::Evenodd,
-                                            );
+                                            self.context
+                                                .fill_with_path_2d_and_winding(path, *winding_rule);
                                         }
 
                                         self.clear_color_filter();
@@ -598,10 +647,8 @@ impl CommandHandler for WebCanvasRenderBackend {
                                         self.set_color_filter(&transform);
                                         self.context.set_image_smoothing_enabled(bitmap.smoothed);
                                         self.context.set_fill_style(&bitmap.pattern);
-                                        self.context.fill_with_path_2d_and_winding(
-                                            path,
-                                            CanvasWindingRule::Evenodd,
-                                        );
+                                        self.context
+                                            .fill_with_path_2d_and_winding(path, *winding_rule);
                                         self.clear_color_filter();
                                     }
                                 }
@@ -822,7 +869,7 @@ fn swf_shape_to_canvas_commands(
     bitmap_source: &dyn BitmapSource,
     backend: &mut WebCanvasRenderBackend,
 ) -> ShapeData {
-    use ruffle_render::shape_utils::DrawPath;
+    use ruffle_render::shape_utils::{DrawPath, FillRule};
     use swf::{FillStyle, LineCapStyle, LineJoinStyle};
 
     // Some browsers will vomit if you try to load/draw an image with 0 width/height.
@@ -838,7 +885,9 @@ fn swf_shape_to_canvas_commands(
     for path in &shape.paths {
         match path {
             DrawPath::Fill {
-                commands, style, ..
+                commands,
+                style,
+                winding_rule,
             } => {
                 let canvas_path = Path2d::new().expect("Path2d constructor must succeed");
                 canvas_path.add_path_with_transformation(
@@ -893,6 +942,10 @@ fn swf_shape_to_canvas_commands(
                 canvas_data.0.push(CanvasDrawCommand::Fill {
                     path: canvas_path,
                     fill_style,
+                    winding_rule: match winding_rule {
+                        FillRule::EvenOdd => CanvasWindingRule::Evenodd,
+                        FillRule::NonZero => CanvasWindingRule::Nonzero,
+                    },
                 });
             }
             DrawPath::Stroke {
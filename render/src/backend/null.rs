@@ -1,10 +1,14 @@
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::sync::Arc;
 
 use crate::backend::{RenderBackend, ShapeHandle, ViewportDimensions};
-use crate::bitmap::{Bitmap, BitmapHandle, BitmapHandleImpl, BitmapSize, BitmapSource, SyncHandle};
+use crate::bitmap::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapHandleImpl, BitmapSize, BitmapSource, SyncHandle,
+};
 use crate::commands::CommandList;
 use crate::error::Error;
+use crate::filters::{cpu, Filter};
 use crate::quality::StageQuality;
 use crate::shape_utils::DistilledShape;
 use gc_arena::MutationContext;
@@ -32,10 +36,25 @@ impl NullRenderer {
         Self { dimensions }
     }
 }
-#[derive(Clone, Debug)]
-struct NullBitmapHandle;
+/// Keeps the pixels of a bitmap around, so that filters can be applied on the CPU.
+#[derive(Debug)]
+struct NullBitmapHandle(RefCell<Bitmap>);
 impl BitmapHandleImpl for NullBitmapHandle {}
 
+fn as_null_bitmap(handle: &BitmapHandle) -> &NullBitmapHandle {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a NullBitmapHandle")
+}
+
+/// The result of a filter, which was already applied on the CPU.
+#[derive(Debug)]
+struct NullSyncHandle(Bitmap);
+impl SyncHandle for NullSyncHandle {
+    fn retrieve_offscreen_texture(self: Box<Self>) -> Result<Bitmap, Error> {
+        Ok(self.0)
+    }
+}
+
 impl RenderBackend for NullRenderer {
     fn viewport_dimensions(&self) -> ViewportDimensions {
         self.dimensions
@@ -72,18 +91,53 @@ impl RenderBackend for NullRenderer {
         None
     }
 
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let map_bitmap = match &filter {
+            Filter::DisplacementMapFilter(filter) => filter
+                .map_bitmap
+                .as_ref()
+                .map(|map_bitmap| as_null_bitmap(map_bitmap).0.borrow().clone()),
+            _ => None,
+        };
+        // `source` may equal `destination`, so the result is built in a copy.
+        let mut result = as_null_bitmap(&destination).0.borrow().clone();
+        cpu::apply_filter(
+            &as_null_bitmap(&source).0.borrow(),
+            source_point,
+            source_size,
+            &mut result,
+            dest_point,
+            &filter,
+            map_bitmap.as_ref(),
+        );
+        *as_null_bitmap(&destination).0.borrow_mut() = result.clone();
+        Some(Box::new(NullSyncHandle(result)))
+    }
+
     fn submit_frame(&mut self, _clear: Color, _commands: CommandList) {}
-    fn register_bitmap(&mut self, _bitmap: Bitmap) -> Result<BitmapHandle, Error> {
-        Ok(BitmapHandle(Arc::new(NullBitmapHandle)))
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        Ok(BitmapHandle(Arc::new(NullBitmapHandle(RefCell::new(
+            bitmap.to_rgba(),
+        )))))
     }
 
     fn update_texture(
         &mut self,
-        _bitmap: &BitmapHandle,
-        _width: u32,
-        _height: u32,
-        _rgba: Vec<u8>,
+        bitmap: &BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
     ) -> Result<(), Error> {
+        *as_null_bitmap(bitmap).0.borrow_mut() =
+            Bitmap::new(width, height, BitmapFormat::Rgba, rgba);
         Ok(())
     }
 
@@ -27,6 +27,12 @@ pub enum Error {
     #[error("Empty GIF")]
     EmptyGif,
 
+    #[error("Failed to encode PNG")]
+    PngEncoding(#[from] png::EncodingError),
+
+    #[error("Failed to encode JPEG")]
+    JpegEncoding(#[from] jpeg_encoder::EncodingError),
+
     #[error("Unsupported DefineBitsLossless{0} format {1:?}")]
     UnsupportedLosslessFormat(u8, swf::BitmapFormat),
 
@@ -1,6 +1,8 @@
 use crate::bitmap::BitmapHandle;
 use swf::Color;
 
+pub mod cpu;
+
 #[derive(Debug, Clone)]
 pub enum Filter {
     BevelFilter(swf::BevelFilter),
@@ -39,6 +41,89 @@ impl From<&swf::Filter> for Filter {
     }
 }
 
+impl Filter {
+    /// Calculates the area that this filter may draw to when it's applied to `source_rect`,
+    /// where both rectangles are given as `(x, y, width, height)` in pixels.
+    pub fn calculate_dest_rect(&self, source_rect: (i32, i32, i32, i32)) -> (i32, i32, i32, i32) {
+        let (x, y, width, height) = source_rect;
+        // How far the blur spreads the image in each direction, and the offset of the effect.
+        let (blur_x, blur_y, offset, inner) = match self {
+            Filter::BlurFilter(filter) => (
+                blur_extent(filter.blur_x.to_f32(), filter.num_passes()),
+                blur_extent(filter.blur_y.to_f32(), filter.num_passes()),
+                None,
+                false,
+            ),
+            Filter::GlowFilter(filter) => (
+                blur_extent(filter.blur_x.to_f32(), filter.num_passes()),
+                blur_extent(filter.blur_y.to_f32(), filter.num_passes()),
+                None,
+                filter.is_inner(),
+            ),
+            Filter::DropShadowFilter(filter) => (
+                blur_extent(filter.blur_x.to_f32(), filter.num_passes()),
+                blur_extent(filter.blur_y.to_f32(), filter.num_passes()),
+                Some((filter.angle.to_f32(), filter.distance.to_f32())),
+                filter.is_inner(),
+            ),
+            Filter::BevelFilter(filter) => (
+                blur_extent(filter.blur_x.to_f32(), filter.num_passes()),
+                blur_extent(filter.blur_y.to_f32(), filter.num_passes()),
+                Some((filter.angle.to_f32(), filter.distance.to_f32())),
+                filter.is_inner(),
+            ),
+            Filter::GradientGlowFilter(filter) | Filter::GradientBevelFilter(filter) => (
+                blur_extent(filter.blur_x.to_f32(), filter.num_passes()),
+                blur_extent(filter.blur_y.to_f32(), filter.num_passes()),
+                Some((filter.angle.to_f32(), filter.distance.to_f32())),
+                filter.is_inner(),
+            ),
+            Filter::ColorMatrixFilter(_)
+            | Filter::ConvolutionFilter(_)
+            | Filter::DisplacementMapFilter(_) => return source_rect,
+        };
+        if inner {
+            // Inner effects never draw outside of the source.
+            return source_rect;
+        }
+
+        let (offset_x, offset_y) = offset
+            .map(|(angle, distance)| {
+                (
+                    (angle.cos() * distance).round() as i32,
+                    (angle.sin() * distance).round() as i32,
+                )
+            })
+            .unwrap_or_default();
+        // Bevels draw both a highlight and a shadow, in opposite directions.
+        let two_sided = matches!(
+            self,
+            Filter::BevelFilter(_) | Filter::GradientBevelFilter(_)
+        );
+        let (left, right) = if two_sided {
+            (blur_x + offset_x.abs(), blur_x + offset_x.abs())
+        } else {
+            ((blur_x - offset_x).max(0), (blur_x + offset_x).max(0))
+        };
+        let (top, bottom) = if two_sided {
+            (blur_y + offset_y.abs(), blur_y + offset_y.abs())
+        } else {
+            ((blur_y - offset_y).max(0), (blur_y + offset_y).max(0))
+        };
+        (
+            x - left,
+            y - top,
+            width + left + right,
+            height + top + bottom,
+        )
+    }
+}
+
+/// How many pixels a blur of `blur` pixels, applied `passes` times, spreads in each direction.
+fn blur_extent(blur: f32, passes: u8) -> i32 {
+    ((blur.max(0.0) * passes as f32) / 2.0).ceil() as i32
+}
+
 impl Default for Filter {
     fn default() -> Self {
         // A default colormatrix is a filter that essentially does nothing,
@@ -0,0 +1,1104 @@
+//! CPU implementations of every [`Filter`], working directly on RGBA bitmaps.
+//!
+//! These mirror the shaders of the wgpu backend, so that backends without a shader path
+//! (and headless tools) can still apply filters, and so that the GPU output has something
+//! to be compared against.
+
+use crate::bitmap::{Bitmap, BitmapFormat};
+use crate::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use swf::{
+    BevelFilter, BevelFilterFlags, BlurFilter, Color, ColorMatrixFilter, ConvolutionFilter,
+    DropShadowFilter, GlowFilter, GlowFilterFlags, GradientFilter, GradientFilterFlags,
+    GradientRecord,
+};
+
+/// A premultiplied RGBA color, with every channel in the range `0.0..=1.0`.
+type Pixel = [f32; 4];
+
+const TRANSPARENT: Pixel = [0.0; 4];
+
+/// Applies `filter` to the `source_size` area of `source` starting at `source_point`,
+/// and writes the result into `destination` at `dest_point`.
+///
+/// Like in Flash, effects that spread past the source area (such as outer glows) are drawn
+/// around `dest_point`, as far as [`Filter::calculate_dest_rect`] reaches.
+/// Only the source area itself is ever read from `source`.
+///
+/// `map_bitmap` holds the pixels of the map of a [`DisplacementMapFilter`], and is ignored otherwise.
+pub fn apply_filter(
+    source: &Bitmap,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+    destination: &mut Bitmap,
+    dest_point: (u32, u32),
+    filter: &Filter,
+    map_bitmap: Option<&Bitmap>,
+) {
+    let (offset_x, offset_y, dest_width, dest_height) =
+        filter.calculate_dest_rect((0, 0, source_size.0 as i32, source_size.1 as i32));
+    let source = Image::from_bitmap(source)
+        .crop((source_point.0 as i32, source_point.1 as i32), source_size)
+        .crop(
+            (offset_x, offset_y),
+            (dest_width as u32, dest_height as u32),
+        );
+    let result = match filter {
+        Filter::BevelFilter(filter) => bevel(&source, filter),
+        Filter::BlurFilter(filter) => blur(&source, filter),
+        Filter::ColorMatrixFilter(filter) => color_matrix(&source, filter),
+        Filter::ConvolutionFilter(filter) => convolution(&source, filter),
+        Filter::DisplacementMapFilter(filter) => {
+            let map = map_bitmap.map(Image::from_bitmap);
+            displacement_map(&source, filter, map.as_ref())
+        }
+        Filter::DropShadowFilter(filter) => drop_shadow(&source, filter),
+        Filter::GlowFilter(filter) => glow(&source, filter),
+        Filter::GradientBevelFilter(filter) => gradient_bevel(&source, filter),
+        Filter::GradientGlowFilter(filter) => gradient_glow(&source, filter),
+    };
+    result.write_to(
+        destination,
+        (
+            dest_point.0 as i32 + offset_x,
+            dest_point.1 as i32 + offset_y,
+        ),
+    );
+}
+
+#[derive(Clone, Debug)]
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<Pixel>,
+}
+
+impl Image {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![TRANSPARENT; width as usize * height as usize],
+        }
+    }
+
+    fn from_bitmap(bitmap: &Bitmap) -> Self {
+        let bytes_per_pixel = bitmap.format().bytes_per_pixel();
+        let pixels = bitmap
+            .data()
+            .chunks_exact(bytes_per_pixel)
+            .map(|chunk| {
+                let alpha = if bitmap.format() == BitmapFormat::Rgba {
+                    chunk[3]
+                } else {
+                    255
+                };
+                [
+                    chunk[0] as f32 / 255.0,
+                    chunk[1] as f32 / 255.0,
+                    chunk[2] as f32 / 255.0,
+                    alpha as f32 / 255.0,
+                ]
+            })
+            .collect();
+        Self {
+            width: bitmap.width(),
+            height: bitmap.height(),
+            pixels,
+        }
+    }
+
+    /// Copies out the given area. Anything outside of this image is transparent.
+    fn crop(&self, point: (i32, i32), size: (u32, u32)) -> Self {
+        let mut result = Self::new(size.0, size.1);
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                result.set(x, y, self.get(x as i32 + point.0, y as i32 + point.1));
+            }
+        }
+        result
+    }
+
+    fn get(&self, x: i32, y: i32) -> Pixel {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return TRANSPARENT;
+        }
+        self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
+    fn get_clamped(&self, x: i32, y: i32) -> Pixel {
+        if self.width == 0 || self.height == 0 {
+            return TRANSPARENT;
+        }
+        self.get(
+            x.clamp(0, self.width as i32 - 1),
+            y.clamp(0, self.height as i32 - 1),
+        )
+    }
+
+    fn set(&mut self, x: u32, y: u32, pixel: Pixel) {
+        self.pixels[y as usize * self.width as usize + x as usize] = pixel;
+    }
+
+    /// Bilinearly samples the alpha at the given pixel position, where integers are pixel centers.
+    fn sample_alpha(&self, x: f32, y: f32) -> f32 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let (x0, y0) = (x0 as i32, y0 as i32);
+        let top = lerp(self.get(x0, y0)[3], self.get(x0 + 1, y0)[3], fx);
+        let bottom = lerp(self.get(x0, y0 + 1)[3], self.get(x0 + 1, y0 + 1)[3], fx);
+        lerp(top, bottom, fy)
+    }
+
+    /// Produces a new image of the same size by calling `f` with every position.
+    fn map(&self, mut f: impl FnMut(u32, u32) -> Pixel) -> Self {
+        let mut result = Self::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                result.set(x, y, f(x, y));
+            }
+        }
+        result
+    }
+
+    /// Writes this image into `bitmap` at `point`, cutting off anything that doesn't fit.
+    fn write_to(&self, bitmap: &mut Bitmap, point: (i32, i32)) {
+        let bitmap_width = bitmap.width() as i32;
+        let bitmap_height = bitmap.height() as i32;
+        let bytes_per_pixel = bitmap.format().bytes_per_pixel();
+        let data = bitmap.data_mut();
+        for y in 0..self.height {
+            let dest_y = point.1 + y as i32;
+            if dest_y < 0 {
+                continue;
+            }
+            if dest_y >= bitmap_height {
+                break;
+            }
+            for x in 0..self.width {
+                let dest_x = point.0 + x as i32;
+                if dest_x < 0 {
+                    continue;
+                }
+                if dest_x >= bitmap_width {
+                    break;
+                }
+                let pixel = self.pixels[(y * self.width + x) as usize];
+                let index =
+                    (dest_y as usize * bitmap_width as usize + dest_x as usize) * bytes_per_pixel;
+                for (channel, value) in pixel.iter().take(bytes_per_pixel).enumerate() {
+                    data[index + channel] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+            }
+        }
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn premultiply(color: Pixel) -> Pixel {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+fn unmultiply(color: Pixel) -> Pixel {
+    if color[3] == 0.0 {
+        return TRANSPARENT;
+    }
+    [
+        color[0] / color[3],
+        color[1] / color[3],
+        color[2] / color[3],
+        color[3],
+    ]
+}
+
+fn scale(color: Pixel, factor: f32) -> Pixel {
+    color.map(|channel| channel * factor)
+}
+
+fn add_scaled(sum: &mut Pixel, color: Pixel, factor: f32) {
+    for (sum, channel) in sum.iter_mut().zip(color) {
+        *sum += channel * factor;
+    }
+}
+
+/// Composites `top` over `bottom`.
+fn over(top: Pixel, bottom: Pixel) -> Pixel {
+    let inverse = 1.0 - top[3];
+    [
+        top[0] + bottom[0] * inverse,
+        top[1] + bottom[1] * inverse,
+        top[2] + bottom[2] * inverse,
+        top[3] + bottom[3] * inverse,
+    ]
+}
+
+fn color_to_f32(color: &Color) -> Pixel {
+    [
+        color.r as f32 / 255.0,
+        color.g as f32 / 255.0,
+        color.b as f32 / 255.0,
+        color.a as f32 / 255.0,
+    ]
+}
+
+/// Converts an angle and distance into an offset in pixels.
+fn filter_offset(angle: f32, distance: f32) -> (f32, f32) {
+    (angle.cos() * distance, angle.sin() * distance)
+}
+
+/// Blurs the image with `passes` iterations of a separable box blur.
+/// Like Flash, `blur_x` and `blur_y` are the width of the box in pixels, and 0 passes don't blur at all.
+fn box_blur(image: &Image, blur_x: f32, blur_y: f32, passes: u8) -> Image {
+    let radius_x = (blur_x - 1.0).max(0.0) / 2.0;
+    let radius_y = (blur_y - 1.0).max(0.0) / 2.0;
+    let mut result = image.clone();
+    for _ in 0..passes {
+        result = box_blur_pass(&result, radius_x, true);
+        result = box_blur_pass(&result, radius_y, false);
+    }
+    result
+}
+
+/// A single horizontal or vertical blur. A fractional radius gives the outermost pixels a partial weight.
+///
+/// Every row (or column) keeps a running sum of the pixels inside the box as it moves along,
+/// so the cost doesn't depend on the radius.
+fn box_blur_pass(image: &Image, radius: f32, horizontal: bool) -> Image {
+    if radius <= 0.0 {
+        return image.clone();
+    }
+    let whole = radius.floor() as i32;
+    let fraction = radius - whole as f32;
+    let total = radius * 2.0 + 1.0;
+    let (length, lines) = if horizontal {
+        (image.width as i32, image.height)
+    } else {
+        (image.height as i32, image.width)
+    };
+    let index = |line: u32, position: i32| {
+        if horizontal {
+            line as usize * image.width as usize + position as usize
+        } else {
+            position as usize * image.width as usize + line as usize
+        }
+    };
+
+    let mut result = Image::new(image.width, image.height);
+    for line in 0..lines {
+        let sample = |position: i32| {
+            if position < 0 || position >= length {
+                TRANSPARENT
+            } else {
+                image.pixels[index(line, position)]
+            }
+        };
+        let mut sum = TRANSPARENT;
+        for position in -whole..=whole {
+            add_scaled(&mut sum, sample(position), 1.0);
+        }
+        for position in 0..length {
+            let mut pixel = sum;
+            if fraction > 0.0 {
+                add_scaled(&mut pixel, sample(position - whole - 1), fraction);
+                add_scaled(&mut pixel, sample(position + whole + 1), fraction);
+            }
+            result.pixels[index(line, position)] = scale(pixel, 1.0 / total);
+            add_scaled(&mut sum, sample(position + whole + 1), 1.0);
+            add_scaled(&mut sum, sample(position - whole), -1.0);
+        }
+    }
+    result
+}
+
+fn blur(source: &Image, filter: &BlurFilter) -> Image {
+    box_blur(
+        source,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    )
+}
+
+fn color_matrix(source: &Image, filter: &ColorMatrixFilter) -> Image {
+    let m = &filter.matrix;
+    source.map(|x, y| {
+        let [r, g, b, a] = unmultiply(source.get(x as i32, y as i32));
+        let row = |i: usize| {
+            (m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * a + m[i + 4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        premultiply([row(0), row(5), row(10), row(15)])
+    })
+}
+
+fn convolution(source: &Image, filter: &ConvolutionFilter) -> Image {
+    // Flash Player doesn't support matrices larger than 15x15.
+    let matrix_x = filter.num_matrix_cols.min(15) as i32;
+    let matrix_y = filter.num_matrix_rows.min(15) as i32;
+    let default_color = color_to_f32(&filter.default_color);
+    let divisor = if filter.divisor.to_f32() == 0.0 {
+        1.0
+    } else {
+        filter.divisor.to_f32()
+    };
+    let bias = filter.bias.to_f32() / 255.0;
+
+    source.map(|x, y| {
+        let (x, y) = (x as i32, y as i32);
+        let mut sum = TRANSPARENT;
+        for matrix_row in 0..matrix_y {
+            for matrix_column in 0..matrix_x {
+                let weight = filter
+                    .matrix
+                    .get((matrix_row * filter.num_matrix_cols as i32 + matrix_column) as usize)
+                    .map_or(0.0, |value| value.to_f32());
+                let sample_x = x + matrix_column - matrix_x / 2;
+                let sample_y = y + matrix_row - matrix_y / 2;
+                let in_bounds = sample_x >= 0
+                    && sample_y >= 0
+                    && sample_x < source.width as i32
+                    && sample_y < source.height as i32;
+                let sample = if in_bounds || filter.is_clamped() {
+                    unmultiply(source.get_clamped(sample_x, sample_y))
+                } else {
+                    default_color
+                };
+                add_scaled(&mut sum, sample, weight);
+            }
+        }
+        let mut color = sum.map(|channel| (channel / divisor + bias).clamp(0.0, 1.0));
+        if filter.is_preserve_alpha() {
+            color[3] = source.get(x, y)[3];
+        }
+        premultiply(color)
+    })
+}
+
+fn displacement_map(source: &Image, filter: &DisplacementMapFilter, map: Option<&Image>) -> Image {
+    let Some(map) = map else {
+        // Without a map there's nothing to displace the source with
+        return source.clone();
+    };
+    let color = premultiply(color_to_f32(&filter.color));
+    let component = |color: Pixel, component: u8| {
+        let unmultiplied = unmultiply(color);
+        match component {
+            1 => unmultiplied[0],
+            2 => unmultiplied[1],
+            4 => unmultiplied[2],
+            8 => color[3],
+            // An unknown channel means no displacement.
+            _ => 128.0 / 255.0,
+        }
+    };
+
+    source.map(|x, y| {
+        let (source_x, source_y) = (x as i32, y as i32);
+        let original = source.get(source_x, source_y);
+
+        let map_x = x as i32 - filter.map_point.0;
+        let map_y = y as i32 - filter.map_point.1;
+        if map_x < 0 || map_y < 0 || map_x >= map.width as i32 || map_y >= map.height as i32 {
+            // Only the area covered by the map is displaced.
+            return original;
+        }
+        let map_color = map.get(map_x, map_y);
+        let offset_x =
+            (component(map_color, filter.component_x) * 255.0 - 128.0) * filter.scale_x / 256.0;
+        let offset_y =
+            (component(map_color, filter.component_y) * 255.0 - 128.0) * filter.scale_y / 256.0;
+        let displaced_x = (source_x as f32 + 0.5 + offset_x).floor() as i32;
+        let displaced_y = (source_y as f32 + 0.5 + offset_y).floor() as i32;
+        let in_bounds = displaced_x >= 0
+            && displaced_y >= 0
+            && displaced_x < source.width as i32
+            && displaced_y < source.height as i32;
+        if in_bounds {
+            return source.get(displaced_x, displaced_y);
+        }
+        match filter.mode {
+            DisplacementMapFilterMode::Clamp => source.get_clamped(displaced_x, displaced_y),
+            DisplacementMapFilterMode::Color => color,
+            DisplacementMapFilterMode::Ignore => original,
+            DisplacementMapFilterMode::Wrap => source.get(
+                displaced_x.rem_euclid(source.width as i32),
+                displaced_y.rem_euclid(source.height as i32),
+            ),
+        }
+    })
+}
+
+/// The settings shared by glows and drop shadows.
+struct GlowOptions {
+    offset: (f32, f32),
+    strength: f32,
+    inner: bool,
+    knockout: bool,
+    composite_source: bool,
+}
+
+/// Composites a glow, whose color at every pixel is chosen by `color` from the glow's strength there.
+fn composite_glow(
+    source: &Image,
+    blurred: &Image,
+    options: &GlowOptions,
+    color: impl Fn(f32) -> Pixel,
+) -> Image {
+    source.map(|x, y| {
+        let source_color = source.get(x as i32, y as i32);
+        // Anything that was moved in from outside of the blurred area is transparent.
+        let blur_alpha =
+            blurred.sample_alpha(x as f32 - options.offset.0, y as f32 - options.offset.1);
+        if options.inner {
+            // An inner glow fills the shape wherever the blurred copy has faded out.
+            let glow = scale(
+                color(((1.0 - blur_alpha) * options.strength).clamp(0.0, 1.0)),
+                source_color[3],
+            );
+            if options.knockout || !options.composite_source {
+                glow
+            } else {
+                over(glow, source_color)
+            }
+        } else {
+            let glow = color((blur_alpha * options.strength).clamp(0.0, 1.0));
+            if options.knockout {
+                scale(glow, 1.0 - source_color[3])
+            } else if !options.composite_source {
+                glow
+            } else {
+                over(source_color, glow)
+            }
+        }
+    })
+}
+
+fn glow(source: &Image, filter: &GlowFilter) -> Image {
+    let blurred = box_blur(
+        source,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    );
+    let color = color_to_f32(&filter.color);
+    let options = GlowOptions {
+        offset: (0.0, 0.0),
+        strength: filter.strength.to_f32(),
+        inner: filter.is_inner(),
+        knockout: filter.is_knockout(),
+        composite_source: filter.flags.contains(GlowFilterFlags::COMPOSITE_SOURCE),
+    };
+    composite_glow(source, &blurred, &options, |alpha| {
+        premultiply([color[0], color[1], color[2], color[3] * alpha])
+    })
+}
+
+fn drop_shadow(source: &Image, filter: &DropShadowFilter) -> Image {
+    let blurred = box_blur(
+        source,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    );
+    let color = color_to_f32(&filter.color);
+    // A drop shadow is an (inner or outer) glow that's been moved away from the object.
+    let options = GlowOptions {
+        offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32()),
+        strength: filter.strength.to_f32(),
+        inner: filter.is_inner(),
+        knockout: filter.is_knockout(),
+        composite_source: !filter.hide_object(),
+    };
+    composite_glow(source, &blurred, &options, |alpha| {
+        premultiply([color[0], color[1], color[2], color[3] * alpha])
+    })
+}
+
+fn gradient_glow(source: &Image, filter: &GradientFilter) -> Image {
+    let blurred = box_blur(
+        source,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    );
+    let options = GlowOptions {
+        offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32()),
+        strength: filter.strength.to_f32(),
+        inner: filter.is_inner(),
+        knockout: filter.is_knockout(),
+        composite_source: filter.flags.contains(GradientFilterFlags::COMPOSITE_SOURCE),
+    };
+    // The strength of the glow picks a position on the gradient, instead of an alpha for one color.
+    composite_glow(source, &blurred, &options, |t| {
+        premultiply(gradient_color(&filter.colors, t))
+    })
+}
+
+/// The settings shared by bevels and gradient bevels.
+struct BevelOptions {
+    offset: (f32, f32),
+    strength: f32,
+    inner: bool,
+    on_top: bool,
+    knockout: bool,
+    composite_source: bool,
+}
+
+/// Composites a bevel, whose color at every pixel is chosen by `color` from the difference
+/// between the highlight (positive) and the shadow (negative) there.
+fn composite_bevel(
+    source: &Image,
+    blurred: &Image,
+    options: &BevelOptions,
+    color: impl Fn(f32) -> Pixel,
+) -> Image {
+    let (offset_x, offset_y) = options.offset;
+    source.map(|x, y| {
+        let source_color = source.get(x as i32, y as i32);
+        // Edges facing the light (which shines along `-offset`) get the highlight,
+        // and edges facing away from it get the shadow.
+        let highlight_alpha = blurred.sample_alpha(x as f32 + offset_x, y as f32 + offset_y);
+        let shadow_alpha = blurred.sample_alpha(x as f32 - offset_x, y as f32 - offset_y);
+        let difference = ((highlight_alpha - shadow_alpha) * options.strength).clamp(-1.0, 1.0);
+        let mut bevel = color(difference);
+
+        let outer = !options.on_top && !options.inner;
+        if !options.on_top {
+            if options.inner {
+                bevel = scale(bevel, source_color[3]);
+            } else {
+                bevel = scale(bevel, 1.0 - source_color[3]);
+            }
+        }
+
+        if options.knockout || !options.composite_source {
+            bevel
+        } else if outer {
+            [
+                source_color[0] + bevel[0],
+                source_color[1] + bevel[1],
+                source_color[2] + bevel[2],
+                source_color[3] + bevel[3],
+            ]
+        } else {
+            over(bevel, source_color)
+        }
+    })
+}
+
+fn bevel(source: &Image, filter: &BevelFilter) -> Image {
+    let blurred = box_blur(
+        source,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    );
+    let highlight = color_to_f32(&filter.highlight_color);
+    let shadow = color_to_f32(&filter.shadow_color);
+    let options = BevelOptions {
+        offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32()),
+        strength: filter.strength.to_f32(),
+        inner: filter.is_inner(),
+        on_top: filter.is_on_top(),
+        knockout: filter.is_knockout(),
+        composite_source: filter.flags.contains(BevelFilterFlags::COMPOSITE_SOURCE),
+    };
+    composite_bevel(source, &blurred, &options, |difference| {
+        let (color, alpha) = if difference > 0.0 {
+            (highlight, difference)
+        } else {
+            (shadow, -difference)
+        };
+        premultiply([color[0], color[1], color[2], color[3] * alpha])
+    })
+}
+
+fn gradient_bevel(source: &Image, filter: &GradientFilter) -> Image {
+    let blurred = box_blur(
+        source,
+        filter.blur_x.to_f32(),
+        filter.blur_y.to_f32(),
+        filter.num_passes(),
+    );
+    let options = BevelOptions {
+        offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32()),
+        strength: filter.strength.to_f32(),
+        inner: filter.is_inner(),
+        on_top: filter.is_on_top(),
+        knockout: filter.is_knockout(),
+        composite_source: filter.flags.contains(GradientFilterFlags::COMPOSITE_SOURCE),
+    };
+    // Full highlight is the start of the gradient and full shadow is the end,
+    // with flat areas sampling the middle.
+    composite_bevel(source, &blurred, &options, |difference| {
+        premultiply(gradient_color(&filter.colors, 0.5 - difference * 0.5))
+    })
+}
+
+/// Finds the (unmultiplied) color at `t` of a gradient, where `0.0..=1.0` covers all ratios.
+fn gradient_color(records: &[GradientRecord], t: f32) -> Pixel {
+    let ratio = t * 255.0;
+    let Some(first) = records.first() else {
+        return TRANSPARENT;
+    };
+    if ratio <= first.ratio as f32 {
+        return color_to_f32(&first.color);
+    }
+    for pair in records.windows(2) {
+        let (last, next) = (&pair[0], &pair[1]);
+        if ratio <= next.ratio as f32 {
+            let range = next.ratio as f32 - last.ratio as f32;
+            let a = if range > 0.0 {
+                (ratio - last.ratio as f32) / range
+            } else {
+                1.0
+            };
+            let last = color_to_f32(&last.color);
+            let next = color_to_f32(&next.color);
+            return [
+                lerp(last[0], next[0], a),
+                lerp(last[1], next[1], a),
+                lerp(last[2], next[2], a),
+                lerp(last[3], next[3], a),
+            ];
+        }
+    }
+    records
+        .last()
+        .map_or(TRANSPARENT, |last| color_to_f32(&last.color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilterFlags, ConvolutionFilterFlags, DropShadowFilterFlags, Fixed16, Fixed8};
+
+    fn solid_square(size: u32, inset: u32, color: [u8; 4]) -> Bitmap {
+        let mut data = vec![0; (size * size * 4) as usize];
+        for y in inset..size - inset {
+            for x in inset..size - inset {
+                let index = ((y * size + x) * 4) as usize;
+                data[index..index + 4].copy_from_slice(&color);
+            }
+        }
+        Bitmap::new(size, size, BitmapFormat::Rgba, data)
+    }
+
+    fn pixel(bitmap: &Bitmap, x: u32, y: u32) -> [u8; 4] {
+        let index = ((y * bitmap.width() + x) * 4) as usize;
+        let mut result = [0; 4];
+        result.copy_from_slice(&bitmap.data()[index..index + 4]);
+        result
+    }
+
+    fn apply(source: &Bitmap, filter: Filter) -> Bitmap {
+        let mut destination = Bitmap::new(
+            source.width(),
+            source.height(),
+            BitmapFormat::Rgba,
+            vec![0; source.data().len()],
+        );
+        apply_filter(
+            source,
+            (0, 0),
+            (source.width(), source.height()),
+            &mut destination,
+            (0, 0),
+            &filter,
+            None,
+        );
+        destination
+    }
+
+    /// A square of `color` inside a magenta border, which no filter may ever read.
+    fn bordered_square(size: u32, border: u32, color: [u8; 4]) -> Bitmap {
+        let mut bitmap = solid_square(size, 0, [0xFF, 0x00, 0xFF, 0xFF]);
+        let inner = solid_square(size, border, color);
+        for y in border..size - border {
+            for x in border..size - border {
+                let index = ((y * size + x) * 4) as usize;
+                bitmap.data_mut()[index..index + 4]
+                    .copy_from_slice(&inner.data()[index..index + 4]);
+            }
+        }
+        bitmap
+    }
+
+    /// Applies `filter` to the inside of a [`bordered_square`], drawing it at the same position.
+    fn apply_inside_border(
+        source: &Bitmap,
+        border: u32,
+        filter: Filter,
+        map_bitmap: Option<&Bitmap>,
+    ) -> Bitmap {
+        let size = source.width();
+        let mut destination =
+            Bitmap::new(size, size, BitmapFormat::Rgba, vec![0; source.data().len()]);
+        apply_filter(
+            source,
+            (border, border),
+            (size - border * 2, size - border * 2),
+            &mut destination,
+            (border, border),
+            &filter,
+            map_bitmap,
+        );
+        destination
+    }
+
+    fn convolution_filter(
+        cols: u8,
+        rows: u8,
+        matrix: &[f32],
+        flags: ConvolutionFilterFlags,
+    ) -> Filter {
+        Filter::ConvolutionFilter(ConvolutionFilter {
+            num_matrix_rows: rows,
+            num_matrix_cols: cols,
+            matrix: matrix
+                .iter()
+                .map(|&value| Fixed16::from_f32(value))
+                .collect(),
+            divisor: Fixed16::ONE,
+            bias: Fixed16::ZERO,
+            default_color: Color::from_rgba(0xFF00FF00),
+            flags,
+        })
+    }
+
+    /// Blurs with a box that is summed up from scratch for every pixel.
+    fn naive_box_blur_pass(image: &Image, radius: f32, horizontal: bool) -> Image {
+        let whole = radius.floor() as i32;
+        let fraction = radius - whole as f32;
+        let direction = if horizontal { (1, 0) } else { (0, 1) };
+        image.map(|x, y| {
+            let sample = |offset: i32| {
+                image.get(
+                    x as i32 + direction.0 * offset,
+                    y as i32 + direction.1 * offset,
+                )
+            };
+            let mut sum = TRANSPARENT;
+            for offset in -whole..=whole {
+                add_scaled(&mut sum, sample(offset), 1.0);
+            }
+            add_scaled(&mut sum, sample(-whole - 1), fraction);
+            add_scaled(&mut sum, sample(whole + 1), fraction);
+            scale(sum, 1.0 / (radius * 2.0 + 1.0))
+        })
+    }
+
+    #[test]
+    fn default_color_matrix_is_identity() {
+        let source = solid_square(8, 2, [0x40, 0x20, 0x10, 0x80]);
+        let result = apply(&source, Filter::default());
+        assert_eq!(result.data(), source.data());
+    }
+
+    #[test]
+    fn color_matrix_offsets() {
+        let source = solid_square(4, 0, [0xFF, 0xFF, 0xFF, 0xFF]);
+        #[rustfmt::skip]
+        let matrix = [
+            0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 255.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ];
+        let result = apply(
+            &source,
+            Filter::ColorMatrixFilter(ColorMatrixFilter { matrix }),
+        );
+        assert_eq!(pixel(&result, 1, 1), [0x00, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn blur_spreads_alpha() {
+        let source = solid_square(20, 4, [0xFF, 0x00, 0x00, 0xFF]);
+        let result = apply(
+            &source,
+            Filter::BlurFilter(BlurFilter {
+                blur_x: Fixed16::from_f32(5.0),
+                blur_y: Fixed16::from_f32(5.0),
+                flags: BlurFilterFlags::from_passes(1),
+            }),
+        );
+        // The center keeps its color, and the blur reaches 2 pixels beyond the square.
+        assert_eq!(pixel(&result, 10, 10), [0xFF, 0x00, 0x00, 0xFF]);
+        assert!(pixel(&result, 2, 10)[3] > 0);
+        assert_eq!(pixel(&result, 1, 10), [0; 4]);
+        // Blurring never changes the hue of a single colored shape.
+        let [r, g, b, a] = pixel(&result, 4, 4);
+        assert_eq!((g, b), (0, 0));
+        assert_eq!(r, a);
+    }
+
+    #[test]
+    fn knockout_glow_hides_source() {
+        let source = solid_square(16, 6, [0x00, 0x00, 0xFF, 0xFF]);
+        let result = apply(
+            &source,
+            Filter::GlowFilter(GlowFilter {
+                color: Color::from_rgb(0x00FF00, 255),
+                blur_x: Fixed16::from_f32(4.0),
+                blur_y: Fixed16::from_f32(4.0),
+                strength: Fixed8::from_f32(2.0),
+                flags: GlowFilterFlags::KNOCKOUT
+                    | GlowFilterFlags::COMPOSITE_SOURCE
+                    | GlowFilterFlags::from_passes(1),
+            }),
+        );
+        assert_eq!(pixel(&result, 8, 8), [0; 4]);
+        let [r, g, b, a] = pixel(&result, 5, 8);
+        assert_eq!((r, b), (0, 0));
+        assert!(a > 0);
+        assert_eq!(g, a);
+    }
+
+    #[test]
+    fn running_sum_blur_matches_naive_blur() {
+        let mut image = Image::new(23, 17);
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let alpha = ((x * 7 + y * 13) % 11) as f32 / 10.0;
+                image.set(
+                    x,
+                    y,
+                    premultiply([x as f32 / 23.0, 0.5, y as f32 / 17.0, alpha]),
+                );
+            }
+        }
+        for radius in [0.5, 1.0, 2.25, 4.0, 30.5] {
+            for horizontal in [true, false] {
+                let expected = naive_box_blur_pass(&image, radius, horizontal);
+                let actual = box_blur_pass(&image, radius, horizontal);
+                for (expected, actual) in expected.pixels.iter().zip(&actual.pixels) {
+                    for (expected, actual) in expected.iter().zip(actual) {
+                        assert!(
+                            (expected - actual).abs() < 1e-4,
+                            "radius {radius}: expected {expected}, got {actual}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_passes_dont_blur() {
+        let source = solid_square(12, 4, [0x10, 0x20, 0x30, 0xFF]);
+        let result = apply(
+            &source,
+            Filter::BlurFilter(BlurFilter {
+                blur_x: Fixed16::from_f32(8.0),
+                blur_y: Fixed16::from_f32(8.0),
+                flags: BlurFilterFlags::from_passes(0),
+            }),
+        );
+        assert_eq!(result.data(), source.data());
+    }
+
+    #[test]
+    fn outer_glow_draws_past_source_area() {
+        let source = solid_square(10, 0, [0x00, 0x00, 0xFF, 0xFF]);
+        let mut destination = Bitmap::new(30, 30, BitmapFormat::Rgba, vec![0; 30 * 30 * 4]);
+        apply_filter(
+            &source,
+            (0, 0),
+            (10, 10),
+            &mut destination,
+            (10, 10),
+            &Filter::GlowFilter(GlowFilter {
+                color: Color::from_rgb(0xFF0000, 255),
+                blur_x: Fixed16::from_f32(6.0),
+                blur_y: Fixed16::from_f32(6.0),
+                strength: Fixed8::from_f32(1.0),
+                flags: GlowFilterFlags::COMPOSITE_SOURCE | GlowFilterFlags::from_passes(1),
+            }),
+            None,
+        );
+        assert_eq!(pixel(&destination, 15, 15), [0x00, 0x00, 0xFF, 0xFF]);
+        let [r, g, b, a] = pixel(&destination, 9, 15);
+        assert!(a > 0);
+        assert_eq!((r, g, b), (a, 0, 0));
+        assert_eq!(pixel(&destination, 15, 5), [0; 4]);
+    }
+
+    #[test]
+    fn convolution_clamps_to_source_area() {
+        let source = bordered_square(12, 2, [0x00, 0x00, 0xFF, 0xFF]);
+        // Every pixel takes the color of the pixel 3 columns to its left.
+        let filter = convolution_filter(
+            7,
+            1,
+            &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            ConvolutionFilterFlags::CLAMP,
+        );
+        let result = apply_inside_border(&source, 2, filter, None);
+        assert_eq!(pixel(&result, 2, 6), [0x00, 0x00, 0xFF, 0xFF]);
+        assert_eq!(pixel(&result, 9, 6), [0x00, 0x00, 0xFF, 0xFF]);
+        // Nothing is drawn into the border.
+        assert_eq!(pixel(&result, 1, 6), [0; 4]);
+    }
+
+    #[test]
+    fn convolution_uses_default_color_outside_source_area() {
+        let source = bordered_square(12, 2, [0x00, 0x00, 0xFF, 0xFF]);
+        let filter = convolution_filter(
+            7,
+            1,
+            &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            ConvolutionFilterFlags::empty(),
+        );
+        let result = apply_inside_border(&source, 2, filter, None);
+        assert_eq!(pixel(&result, 4, 6), [0x00, 0xFF, 0x00, 0xFF]);
+        assert_eq!(pixel(&result, 5, 6), [0x00, 0x00, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn convolution_preserves_alpha() {
+        let source = solid_square(6, 0, [0x40, 0x40, 0x40, 0x80]);
+        let filter = convolution_filter(
+            1,
+            1,
+            &[2.0],
+            ConvolutionFilterFlags::CLAMP | ConvolutionFilterFlags::PRESERVE_ALPHA,
+        );
+        // The unmultiplied color is doubled, but the alpha stays the same.
+        assert_eq!(
+            pixel(&apply(&source, filter), 3, 3),
+            [0x80, 0x80, 0x80, 0x80]
+        );
+    }
+
+    #[test]
+    fn convolution_ignores_matrix_past_15x15() {
+        let mut source = solid_square(20, 0, [0x00, 0x00, 0xFF, 0xFF]);
+        let index = ((10 * 20 + 10) * 4) as usize;
+        source.data_mut()[index..index + 4].copy_from_slice(&[0xFF, 0x00, 0x00, 0xFF]);
+
+        // Only the first 15 rows and columns are used, so the weight in the
+        // middle of those copies every pixel, and the last row and column of
+        // the 16x16 matrix have no effect.
+        let mut matrix = vec![0.0; 16 * 16];
+        matrix[7 * 16 + 7] = 1.0;
+        for i in 0..16 {
+            matrix[15 * 16 + i] = 100.0;
+            matrix[i * 16 + 15] = 100.0;
+        }
+        let filter = convolution_filter(16, 16, &matrix, ConvolutionFilterFlags::CLAMP);
+        assert_eq!(apply(&source, filter).data(), source.data());
+    }
+
+    #[test]
+    fn displacement_map_wraps_inside_source_area() {
+        // The left half of the inside is red, the right half is blue.
+        let mut source = bordered_square(12, 2, [0xFF, 0x00, 0x00, 0xFF]);
+        for y in 2..10 {
+            for x in 6..10 {
+                let index = ((y * 12 + x) * 4) as usize;
+                source.data_mut()[index..index + 4].copy_from_slice(&[0x00, 0x00, 0xFF, 0xFF]);
+            }
+        }
+        // A red channel of 0 moves every pixel 4 pixels to the left.
+        let map = solid_square(8, 0, [0x00, 0x80, 0x80, 0xFF]);
+        let filter = Filter::DisplacementMapFilter(DisplacementMapFilter {
+            component_x: 1,
+            component_y: 2,
+            mode: DisplacementMapFilterMode::Wrap,
+            scale_x: 8.0,
+            scale_y: 0.0,
+            ..Default::default()
+        });
+        let result = apply_inside_border(&source, 2, filter.clone(), Some(&map));
+        assert_eq!(pixel(&result, 2, 4), [0x00, 0x00, 0xFF, 0xFF]);
+        assert_eq!(pixel(&result, 9, 4), [0xFF, 0x00, 0x00, 0xFF]);
+
+        // Without a map, the source area is copied as is.
+        let result = apply_inside_border(&source, 2, filter, None);
+        assert_eq!(pixel(&result, 2, 4), [0xFF, 0x00, 0x00, 0xFF]);
+        assert_eq!(pixel(&result, 1, 4), [0; 4]);
+    }
+
+    #[test]
+    fn bevel_lights_edges_facing_the_light() {
+        let source = solid_square(24, 6, [0x80, 0x80, 0x80, 0xFF]);
+        let result = apply(
+            &source,
+            Filter::BevelFilter(BevelFilter {
+                shadow_color: Color::from_rgb(0x000000, 255),
+                highlight_color: Color::from_rgb(0xFFFFFF, 255),
+                blur_x: Fixed16::from_f32(4.0),
+                blur_y: Fixed16::from_f32(4.0),
+                angle: Fixed16::from_f32(std::f32::consts::FRAC_PI_4),
+                distance: Fixed16::from_f32(2.0),
+                strength: Fixed8::from_f32(1.0),
+                flags: BevelFilterFlags::INNER_SHADOW
+                    | BevelFilterFlags::COMPOSITE_SOURCE
+                    | BevelFilterFlags::from_passes(1),
+            }),
+        );
+        let top_left = pixel(&result, 7, 7);
+        let bottom_right = pixel(&result, 16, 16);
+        assert!(top_left[0] > 0x80, "{top_left:?}");
+        assert!(bottom_right[0] < 0x80, "{bottom_right:?}");
+        // The center is flat, and an inner bevel never draws outside of the shape.
+        assert_eq!(pixel(&result, 12, 12), [0x80, 0x80, 0x80, 0xFF]);
+        assert_eq!(pixel(&result, 4, 4), [0; 4]);
+    }
+
+    #[test]
+    fn drop_shadow_is_offset_and_hides_object() {
+        let source = solid_square(10, 0, [0x00, 0x00, 0xFF, 0xFF]);
+        let mut destination = Bitmap::new(30, 30, BitmapFormat::Rgba, vec![0; 30 * 30 * 4]);
+        apply_filter(
+            &source,
+            (0, 0),
+            (10, 10),
+            &mut destination,
+            (10, 10),
+            &Filter::DropShadowFilter(DropShadowFilter {
+                color: Color::from_rgb(0x000000, 255),
+                blur_x: Fixed16::from_f32(1.0),
+                blur_y: Fixed16::from_f32(1.0),
+                angle: Fixed16::ZERO,
+                distance: Fixed16::from_f32(5.0),
+                strength: Fixed8::from_f32(1.0),
+                flags: DropShadowFilterFlags::from_passes(1),
+            }),
+            None,
+        );
+        // Only the shadow is drawn, 5 pixels to the right of the object.
+        assert_eq!(pixel(&destination, 12, 15), [0; 4]);
+        assert_eq!(pixel(&destination, 16, 15), [0x00, 0x00, 0x00, 0xFF]);
+        assert_eq!(pixel(&destination, 24, 15), [0x00, 0x00, 0x00, 0xFF]);
+        assert_eq!(pixel(&destination, 25, 15), [0; 4]);
+    }
+
+    #[test]
+    fn gradient_color_interpolates_between_ratios() {
+        let records = [
+            GradientRecord {
+                ratio: 0,
+                color: Color::from_rgba(0x00000000),
+            },
+            GradientRecord {
+                ratio: 255,
+                color: Color::from_rgba(0xFFFFFFFF),
+            },
+        ];
+        assert_eq!(gradient_color(&records, 0.0), [0.0; 4]);
+        assert_eq!(gradient_color(&records, 1.0), [1.0; 4]);
+        let middle = gradient_color(&records, 0.5);
+        assert!((middle[3] - 0.5).abs() < 1e-6);
+    }
+}
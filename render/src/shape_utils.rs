@@ -71,9 +71,32 @@ pub enum DrawPath<'a> {
     Fill {
         style: &'a FillStyle,
         commands: Vec<DrawCommand>,
+        winding_rule: FillRule,
     },
 }
 
+/// The rule used to decide which parts of a fill are inside of it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the path an odd number of times.
+    /// This is what SWF shapes use.
+    #[default]
+    EvenOdd,
+
+    /// A point is inside if the path winds around it at least once.
+    NonZero,
+}
+
+impl FillRule {
+    /// Whether a point with the given winding number is inside the fill.
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding & 0b1 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
 /// `DistilledShape` represents a ready-to-be-consumed collection of paths (both fills and strokes)
 /// that has been converted down from another source (such as SWF's `swf::Shape` format).
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -125,6 +148,73 @@ impl DrawCommand {
     }
 }
 
+/// The maximum distance, in twips, that the quadratic approximation of a cubic curve
+/// may stray from the real curve.
+const CUBIC_CURVE_TOLERANCE: f64 = 2.0;
+
+/// The maximum number of times a cubic curve will be split in half while approximating it.
+const MAX_CUBIC_CURVE_SUBDIVISIONS: u32 = 8;
+
+/// Approximates the cubic Bezier curve starting at `start`, with control points `control_a`
+/// and `control_b` and ending at `anchor`, using a series of quadratic curves.
+///
+/// Shapes can only contain quadratic curves, so this is how Flash supports `cubicCurveTo`.
+pub fn cubic_curve_to_quadratics(
+    start: (Twips, Twips),
+    control_a: (Twips, Twips),
+    control_b: (Twips, Twips),
+    anchor: (Twips, Twips),
+) -> Vec<DrawCommand> {
+    let to_f64 = |(x, y): (Twips, Twips)| (x.get() as f64, y.get() as f64);
+    let mut commands = vec![];
+    approximate_cubic_curve(
+        [
+            to_f64(start),
+            to_f64(control_a),
+            to_f64(control_b),
+            to_f64(anchor),
+        ],
+        0,
+        &mut commands,
+    );
+    commands
+}
+
+fn approximate_cubic_curve(
+    [p0, p1, p2, p3]: [(f64, f64); 4],
+    depth: u32,
+    commands: &mut Vec<DrawCommand>,
+) {
+    // The distance between the midpoint approximation and the cubic curve is at most
+    // `sqrt(3) / 36 * |p3 - 3 * p2 + 3 * p1 - p0|`.
+    let dx = p3.0 - 3.0 * p2.0 + 3.0 * p1.0 - p0.0;
+    let dy = p3.1 - 3.0 * p2.1 + 3.0 * p1.1 - p0.1;
+    let error = 3f64.sqrt() / 36.0 * (dx * dx + dy * dy).sqrt();
+    if error <= CUBIC_CURVE_TOLERANCE || depth >= MAX_CUBIC_CURVE_SUBDIVISIONS {
+        // Average the control points of the quadratics that are tangent at either end.
+        let control_x = (3.0 * (p1.0 + p2.0) - p0.0 - p3.0) / 4.0;
+        let control_y = (3.0 * (p1.1 + p2.1) - p0.1 - p3.1) / 4.0;
+        commands.push(DrawCommand::CurveTo {
+            x1: Twips::new(control_x.round() as i32),
+            y1: Twips::new(control_y.round() as i32),
+            x2: Twips::new(p3.0.round() as i32),
+            y2: Twips::new(p3.1.round() as i32),
+        });
+        return;
+    }
+
+    // Split the curve in half using de Casteljau's algorithm.
+    let mid = |a: (f64, f64), b: (f64, f64)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+    let p01 = mid(p0, p1);
+    let p12 = mid(p1, p2);
+    let p23 = mid(p2, p3);
+    let p012 = mid(p01, p12);
+    let p123 = mid(p12, p23);
+    let p0123 = mid(p012, p123);
+    approximate_cubic_curve([p0, p01, p012, p0123], depth + 1, commands);
+    approximate_cubic_curve([p0123, p123, p23, p3], depth + 1, commands);
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Point {
     x: Twips,
@@ -512,6 +602,7 @@ impl<'a> ShapeConverter<'a> {
             self.commands.push(DrawPath::Fill {
                 style,
                 commands: path.to_draw_commands().collect(),
+                winding_rule: FillRule::EvenOdd,
             });
             path.segments.clear();
         }
@@ -620,6 +711,7 @@ mod tests {
                     y: Twips::from_pixels(100.0),
                 },
             ],
+            winding_rule: FillRule::EvenOdd,
         }];
         assert_eq!(commands, expected);
     }
@@ -684,9 +776,46 @@ mod tests {
                     y: Twips::from_pixels(100.0),
                 },
             ],
+            winding_rule: FillRule::EvenOdd,
         }];
         assert_eq!(commands, expected);
     }
+
+    /// A cubic curve should be approximated by quadratic curves that end at its anchor.
+    #[test]
+    fn cubic_curve_approximation() {
+        let point = |x, y| (Twips::from_pixels(x), Twips::from_pixels(y));
+
+        // A straight line only needs a single curve.
+        let commands = cubic_curve_to_quadratics(
+            point(0.0, 0.0),
+            point(10.0, 0.0),
+            point(20.0, 0.0),
+            point(30.0, 0.0),
+        );
+        assert_eq!(
+            commands,
+            vec![DrawCommand::CurveTo {
+                x1: Twips::from_pixels(15.0),
+                y1: Twips::ZERO,
+                x2: Twips::from_pixels(30.0),
+                y2: Twips::ZERO,
+            }]
+        );
+
+        // An S-shaped curve can't be drawn with a single quadratic curve.
+        let commands = cubic_curve_to_quadratics(
+            point(0.0, 0.0),
+            point(100.0, -100.0),
+            point(0.0, 200.0),
+            point(100.0, 100.0),
+        );
+        assert!(commands.len() > 1);
+        assert_eq!(
+            commands.last().map(DrawCommand::end_point),
+            Some(point(100.0, 100.0))
+        );
+    }
 }
 
 /* SHAPEFLAG HITTEST (point-in-contour)
@@ -828,7 +957,11 @@ pub fn shape_hit_test(
 }
 
 /// Test whether the given point is contained within the paths specified by the draw commands.
-pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips, Twips)) -> bool {
+pub fn draw_command_fill_hit_test(
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    test_point: (Twips, Twips),
+) -> bool {
     let mut cursor = (Twips::ZERO, Twips::ZERO);
     let mut fill_start = (Twips::ZERO, Twips::ZERO);
     let mut winding = 0;
@@ -855,7 +988,7 @@ pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips,
         winding += winding_number_line(test_point, cursor, fill_start);
     }
 
-    winding & 0b1 != 0
+    winding_rule.is_inside(winding)
 }
 
 /// Test whether the given point is contained within the strokes specified by the draw commands.
@@ -1,5 +1,5 @@
 use crate::bitmap::BitmapSource;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
 use enum_map::Enum;
 use lyon::path::Path;
 use lyon::tessellation::{
@@ -42,9 +42,9 @@ impl ShapeTessellator {
         self.lyon_mesh = VertexBuffers::new();
         for path in shape.paths {
             let (fill_style, lyon_path, next_is_stroke) = match &path {
-                DrawPath::Fill { style, commands } => {
-                    (*style, ruffle_path_to_lyon_path(commands, true), false)
-                }
+                DrawPath::Fill {
+                    style, commands, ..
+                } => (*style, ruffle_path_to_lyon_path(commands, true), false),
                 DrawPath::Stroke {
                     style,
                     commands,
@@ -133,9 +133,12 @@ impl ShapeTessellator {
             let mut buffers_builder =
                 BuffersBuilder::new(&mut self.lyon_mesh, RuffleVertexCtor { color });
             let result = match path {
-                DrawPath::Fill { .. } => self.fill_tess.tessellate_path(
+                DrawPath::Fill { winding_rule, .. } => self.fill_tess.tessellate_path(
                     &lyon_path,
-                    &FillOptions::even_odd(),
+                    &match winding_rule {
+                        FillRule::EvenOdd => FillOptions::even_odd(),
+                        FillRule::NonZero => FillOptions::non_zero(),
+                    },
                     &mut buffers_builder,
                 ),
                 DrawPath::Stroke { style, .. } => {
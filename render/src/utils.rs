@@ -400,6 +400,47 @@ fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     ))
 }
 
+/// Encodes a bitmap with unmultiplied alpha as a PNG file.
+///
+/// RGB bitmaps are written without an alpha channel.
+pub fn encode_png(bitmap: &Bitmap, fast_compression: bool) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, bitmap.width(), bitmap.height());
+    encoder.set_color(match bitmap.format() {
+        BitmapFormat::Rgb => png::ColorType::Rgb,
+        BitmapFormat::Rgba => png::ColorType::Rgba,
+    });
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(if fast_compression {
+        png::Compression::Fast
+    } else {
+        png::Compression::Default
+    });
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(bitmap.data())?;
+    writer.finish()?;
+    Ok(out)
+}
+
+/// Encodes a bitmap with unmultiplied alpha as a baseline JPEG file.
+///
+/// JPEG has no alpha channel, so any transparency is discarded.
+/// `quality` ranges from 1 (smallest) to 100 (best).
+pub fn encode_jpeg(bitmap: &Bitmap, quality: u8) -> Result<Vec<u8>, Error> {
+    use jpeg_encoder::{ColorType, Encoder};
+
+    let width = u16::try_from(bitmap.width()).map_err(|_| Error::TooLarge)?;
+    let height = u16::try_from(bitmap.height()).map_err(|_| Error::TooLarge)?;
+    let color_type = match bitmap.format() {
+        BitmapFormat::Rgb => ColorType::Rgb,
+        BitmapFormat::Rgba => ColorType::Rgba,
+    };
+    let mut out = Vec::new();
+    let encoder = Encoder::new(&mut out, quality.clamp(1, 100));
+    encoder.encode(bitmap.data(), width, height, color_type)?;
+    Ok(out)
+}
+
 /// Converts standard RBGA to premultiplied alpha.
 fn premultiply_alpha_rgba(rgba: &mut [u8]) {
     rgba.chunks_exact_mut(4).for_each(|rgba| {
@@ -4,6 +4,9 @@
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) uv: vec2<f32>,
+    /// The untransformed position inside the quad, for sampling intermediate
+    /// textures that share the coordinate space of the render target.
+    @location(1) quad_uv: vec2<f32>,
 };
 
 #if use_push_constants == true
@@ -36,5 +39,5 @@ fn main_vertex(in: FilterVertexInput) -> VertexOutput {
     let matrix_ = textureTransforms.texture_matrix;
     let uv = (mat3x3<f32>(matrix_[0].xyz, matrix_[1].xyz, matrix_[2].xyz) * vec3<f32>(in.position, 1.0)).xy;
     let pos = common::globals.view_matrix * transforms.world_matrix * vec4<f32>(in.position.x, in.position.y, 0.0, 1.0);
-    return VertexOutput(pos, uv);
+    return VertexOutput(pos, uv, in.position);
 }
@@ -0,0 +1,70 @@
+#import filter
+
+struct Filter {
+    // The matrix is stored row by row, four values per element.
+    matrix: array<vec4<f32>, 64>,
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    clamp: u32,
+    preserve_alpha: u32,
+    width: f32,
+    height: f32,
+    // The source rectangle, in texture coordinates of the source.
+    source_min: vec2<f32>,
+    source_max: vec2<f32>,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+#endif
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+fn unmultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let source = unmultiply(textureSample(filter::texture, filter::texture_sampler, in.uv));
+    let center = vec2<f32>(f32(f.matrix_x / 2u), f32(f.matrix_y / 2u));
+
+    let size = vec2<f32>(f.width, f.height);
+    let half_texel = vec2<f32>(0.5) / size;
+    var sum = vec4<f32>(0.0);
+    for (var y = 0u; y < f.matrix_y; y += 1u) {
+        for (var x = 0u; x < f.matrix_x; x += 1u) {
+            let index = y * f.matrix_x + x;
+            let weight = f.matrix[index / 4u][index % 4u];
+            let uv = in.uv + (vec2<f32>(f32(x), f32(y)) - center) / size;
+            // Anything outside of the source rectangle is either its closest edge pixel,
+            // or the default color, even if the source texture is larger.
+            let in_bounds = all(uv >= f.source_min) && all(uv < f.source_max);
+            let clamped = clamp(uv, f.source_min + half_texel, f.source_max - half_texel);
+            let sample = unmultiply(textureSampleLevel(filter::texture, filter::texture_sampler, clamped, 0.0));
+            sum += weight * select(f.default_color, sample, in_bounds || f.clamp != 0u);
+        }
+    }
+
+    var divisor = f.divisor;
+    if (divisor == 0.0) {
+        divisor = 1.0;
+    }
+    var color = saturate(sum / divisor + vec4<f32>(f.bias / 255.0));
+    if (f.preserve_alpha != 0u) {
+        color.a = source.a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
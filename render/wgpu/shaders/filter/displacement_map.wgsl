@@ -0,0 +1,85 @@
+#import filter
+
+struct Filter {
+    color: vec4<f32>,
+    map_point: vec2<f32>,
+    scale: vec2<f32>,
+    source_size: vec2<f32>,
+    map_size: vec2<f32>,
+    target_size: vec2<f32>,
+    // The source rectangle, in texture coordinates of the source.
+    source_min: vec2<f32>,
+    source_max: vec2<f32>,
+    component_x: u32,
+    component_y: u32,
+    // 0 = clamp, 1 = color, 2 = ignore, 3 = wrap
+    mode: u32,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var map: texture_2d<f32>;
+    @group(2) @binding(2) var map_sampler: sampler;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var map: texture_2d<f32>;
+    @group(4) @binding(2) var map_sampler: sampler;
+#endif
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+/// Reads a channel (as a `BitmapDataChannel` flag) of the unmultiplied map color.
+fn get_component(color: vec4<f32>, component: u32) -> f32 {
+    var unmultiplied = vec3<f32>(0.0);
+    if (color.a > 0.0) {
+        unmultiplied = color.rgb / color.a;
+    }
+    switch (component) {
+        case 1u: { return unmultiplied.r; }
+        case 2u: { return unmultiplied.g; }
+        case 4u: { return unmultiplied.b; }
+        case 8u: { return color.a; }
+        // An unknown channel means no displacement.
+        default: { return 128.0 / 255.0; }
+    }
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let source = textureSample(filter::texture, filter::texture_sampler, in.uv);
+
+    let map_uv = (floor(in.quad_uv * f.target_size) - f.map_point + vec2<f32>(0.5)) / f.map_size;
+    let map_color = textureSample(map, map_sampler, map_uv);
+    if (any(map_uv < vec2<f32>(0.0)) || any(map_uv > vec2<f32>(1.0))) {
+        // Only the area covered by the map is displaced.
+        return source;
+    }
+
+    let offset = vec2<f32>(
+        (get_component(map_color, f.component_x) * 255.0 - 128.0) * f.scale.x / 256.0,
+        (get_component(map_color, f.component_y) * 255.0 - 128.0) * f.scale.y / 256.0,
+    );
+    // Only the source rectangle is displaced, even if the source texture is larger.
+    let uv = in.uv + offset / f.source_size;
+    if (all(uv >= f.source_min) && all(uv < f.source_max)) {
+        return textureSampleLevel(filter::texture, filter::texture_sampler, uv, 0.0);
+    }
+    let half_texel = vec2<f32>(0.5) / f.source_size;
+    switch (f.mode) {
+        case 1u: { return vec4<f32>(f.color.rgb * f.color.a, f.color.a); }
+        case 2u: { return source; }
+        case 3u: {
+            let extent = f.source_max - f.source_min;
+            let wrapped = f.source_min + fract((uv - f.source_min) / extent) * extent;
+            return textureSampleLevel(filter::texture, filter::texture_sampler, wrapped, 0.0);
+        }
+        default: {
+            let clamped = clamp(uv, f.source_min + half_texel, f.source_max - half_texel);
+            return textureSampleLevel(filter::texture, filter::texture_sampler, clamped, 0.0);
+        }
+    }
+}
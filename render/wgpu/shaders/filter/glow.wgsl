@@ -0,0 +1,58 @@
+#import filter
+
+struct Filter {
+    color: vec4<f32>,
+    offset: vec2<f32>,
+    strength: f32,
+    inner: u32,
+    knockout: u32,
+    composite_source: u32,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var blurred: texture_2d<f32>;
+    @group(2) @binding(2) var blurred_sampler: sampler;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var blurred: texture_2d<f32>;
+    @group(4) @binding(2) var blurred_sampler: sampler;
+#endif
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let source = textureSample(filter::texture, filter::texture_sampler, in.uv);
+
+    // Drop shadows are a glow that has been moved by `offset`.
+    // Anything that was moved in from outside of the blurred area is transparent.
+    let blur_uv = in.quad_uv - f.offset;
+    let blurred_sample = textureSample(blurred, blurred_sampler, blur_uv);
+    let in_bounds = all(blur_uv >= vec2<f32>(0.0)) && all(blur_uv <= vec2<f32>(1.0));
+    let blur_alpha = select(0.0, blurred_sample.a, in_bounds);
+
+    if (f.inner != 0u) {
+        // An inner glow fills the shape wherever the blurred copy has faded out.
+        let alpha = saturate((1.0 - blur_alpha) * f.strength) * f.color.a * source.a;
+        let glow = vec4<f32>(f.color.rgb * alpha, alpha);
+        if (f.knockout != 0u || f.composite_source == 0u) {
+            return glow;
+        }
+        return glow + source * (1.0 - glow.a);
+    } else {
+        let alpha = saturate(blur_alpha * f.strength) * f.color.a;
+        let glow = vec4<f32>(f.color.rgb * alpha, alpha);
+        if (f.knockout != 0u) {
+            return glow * (1.0 - source.a);
+        }
+        if (f.composite_source == 0u) {
+            return glow;
+        }
+        return source + glow * (1.0 - source.a);
+    }
+}
@@ -0,0 +1,65 @@
+#import filter
+
+struct Filter {
+    offset: vec2<f32>,
+    strength: f32,
+    // 0 = inner, 1 = outer, 2 = full
+    bevel_type: u32,
+    knockout: u32,
+    composite_source: u32,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var blurred: texture_2d<f32>;
+    @group(2) @binding(2) var blurred_sampler: sampler;
+    @group(2) @binding(3) var gradient: texture_2d<f32>;
+    @group(2) @binding(4) var gradient_sampler: sampler;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var blurred: texture_2d<f32>;
+    @group(4) @binding(2) var blurred_sampler: sampler;
+    @group(4) @binding(3) var gradient: texture_2d<f32>;
+    @group(4) @binding(4) var gradient_sampler: sampler;
+#endif
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+fn blurred_alpha(uv: vec2<f32>) -> f32 {
+    let sample = textureSample(blurred, blurred_sampler, uv);
+    let in_bounds = all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0));
+    return select(0.0, sample.a, in_bounds);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let source = textureSample(filter::texture, filter::texture_sampler, in.uv);
+
+    let highlight_alpha = blurred_alpha(in.quad_uv + f.offset);
+    let shadow_alpha = blurred_alpha(in.quad_uv - f.offset);
+    let difference = clamp((highlight_alpha - shadow_alpha) * f.strength, -1.0, 1.0);
+
+    // Full highlight is the start of the gradient and full shadow is the end,
+    // with flat areas sampling the middle.
+    let t = 0.5 - difference * 0.5;
+    let color = textureSample(gradient, gradient_sampler, vec2<f32>(t, 0.0));
+    var bevel = vec4<f32>(color.rgb * color.a, color.a);
+
+    if (f.bevel_type == 0u) {
+        bevel *= source.a;
+    } else if (f.bevel_type == 1u) {
+        bevel *= 1.0 - source.a;
+    }
+
+    if (f.knockout != 0u || f.composite_source == 0u) {
+        return bevel;
+    }
+    if (f.bevel_type == 1u) {
+        return source + bevel;
+    }
+    return bevel + source * (1.0 - bevel.a);
+}
@@ -0,0 +1,65 @@
+#import filter
+
+struct Filter {
+    offset: vec2<f32>,
+    strength: f32,
+    inner: u32,
+    knockout: u32,
+    composite_source: u32,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var blurred: texture_2d<f32>;
+    @group(2) @binding(2) var blurred_sampler: sampler;
+    @group(2) @binding(3) var gradient: texture_2d<f32>;
+    @group(2) @binding(4) var gradient_sampler: sampler;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var blurred: texture_2d<f32>;
+    @group(4) @binding(2) var blurred_sampler: sampler;
+    @group(4) @binding(3) var gradient: texture_2d<f32>;
+    @group(4) @binding(4) var gradient_sampler: sampler;
+#endif
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let source = textureSample(filter::texture, filter::texture_sampler, in.uv);
+
+    let blur_uv = in.quad_uv - f.offset;
+    let blurred_sample = textureSample(blurred, blurred_sampler, blur_uv);
+    let in_bounds = all(blur_uv >= vec2<f32>(0.0)) && all(blur_uv <= vec2<f32>(1.0));
+    let blur_alpha = select(0.0, blurred_sample.a, in_bounds);
+
+    // The strength of the glow picks a position on the gradient, instead of an alpha for one color.
+    var t: f32;
+    if (f.inner != 0u) {
+        t = saturate((1.0 - blur_alpha) * f.strength);
+    } else {
+        t = saturate(blur_alpha * f.strength);
+    }
+    let color = textureSample(gradient, gradient_sampler, vec2<f32>(t, 0.0));
+    var glow = vec4<f32>(color.rgb * color.a, color.a);
+
+    if (f.inner != 0u) {
+        glow *= source.a;
+        if (f.knockout != 0u || f.composite_source == 0u) {
+            return glow;
+        }
+        return glow + source * (1.0 - glow.a);
+    } else {
+        if (f.knockout != 0u) {
+            return glow * (1.0 - source.a);
+        }
+        if (f.composite_source == 0u) {
+            return glow;
+        }
+        return source + glow * (1.0 - source.a);
+    }
+}
@@ -1,5 +1,9 @@
 use crate::globals::GlobalsUniform;
-use crate::{ColorAdjustments, GradientUniforms, TextureTransforms, Transforms};
+use crate::{
+    BevelFilterUniform, ColorAdjustments, ConvolutionFilterUniform, DisplacementMapFilterUniform,
+    GlowFilterUniform, GradientBevelFilterUniform, GradientGlowFilterUniform, GradientUniforms,
+    TextureTransforms, Transforms,
+};
 
 #[derive(Debug)]
 pub struct BindLayouts {
@@ -11,6 +15,12 @@ pub struct BindLayouts {
     pub blend: wgpu::BindGroupLayout,
     pub color_matrix_filter: wgpu::BindGroupLayout,
     pub blur_filter: wgpu::BindGroupLayout,
+    pub glow_filter: wgpu::BindGroupLayout,
+    pub bevel_filter: wgpu::BindGroupLayout,
+    pub convolution_filter: wgpu::BindGroupLayout,
+    pub displacement_map_filter: wgpu::BindGroupLayout,
+    pub gradient_glow_filter: wgpu::BindGroupLayout,
+    pub gradient_bevel_filter: wgpu::BindGroupLayout,
 }
 
 impl BindLayouts {
@@ -211,6 +221,53 @@ impl BindLayouts {
             label: create_debug_label!("Blur filter binds").as_deref(),
         });
 
+        let glow_filter = create_filter_texture_layout(
+            device,
+            std::mem::size_of::<GlowFilterUniform>(),
+            create_debug_label!("Glow filter binds"),
+        );
+
+        let bevel_filter = create_filter_texture_layout(
+            device,
+            std::mem::size_of::<BevelFilterUniform>(),
+            create_debug_label!("Bevel filter binds"),
+        );
+
+        let convolution_filter =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<
+                            ConvolutionFilterUniform,
+                        >() as u64),
+                    },
+                    count: None,
+                }],
+                label: create_debug_label!("Convolution filter binds").as_deref(),
+            });
+
+        let displacement_map_filter = create_filter_texture_layout(
+            device,
+            std::mem::size_of::<DisplacementMapFilterUniform>(),
+            create_debug_label!("Displacement map filter binds"),
+        );
+
+        let gradient_glow_filter = create_gradient_filter_layout(
+            device,
+            std::mem::size_of::<GradientGlowFilterUniform>(),
+            create_debug_label!("Gradient glow filter binds"),
+        );
+
+        let gradient_bevel_filter = create_gradient_filter_layout(
+            device,
+            std::mem::size_of::<GradientBevelFilterUniform>(),
+            create_debug_label!("Gradient bevel filter binds"),
+        );
+
         Self {
             globals,
             transforms,
@@ -220,6 +277,109 @@ impl BindLayouts {
             blend,
             color_matrix_filter,
             blur_filter,
+            glow_filter,
+            bevel_filter,
+            convolution_filter,
+            displacement_map_filter,
+            gradient_glow_filter,
+            gradient_bevel_filter,
         }
     }
 }
+
+/// Creates the layout for a filter that samples another texture besides its source,
+/// such as a blurred copy of the source or a displacement map.
+/// The filter arguments are followed by that texture and a sampler for it.
+fn create_filter_texture_layout(
+    device: &wgpu::Device,
+    uniform_size: usize,
+    label: Option<String>,
+) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(uniform_size as u64),
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+        label: label.as_deref(),
+    })
+}
+
+/// Creates the layout for a gradient glow or bevel filter.
+/// Like `create_filter_texture_layout`, but additionally followed by the gradient ramp and its sampler.
+fn create_gradient_filter_layout(
+    device: &wgpu::Device,
+    uniform_size: usize,
+    label: Option<String>,
+) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(uniform_size as u64),
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 4,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+        label: label.as_deref(),
+    })
+}
@@ -167,6 +167,87 @@ impl From<TessGradient> for GradientUniforms {
     }
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct GlowFilterUniform {
+    color: [f32; 4],
+    offset: [f32; 2],
+    strength: f32,
+    inner: u32,
+    knockout: u32,
+    composite_source: u32,
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct BevelFilterUniform {
+    highlight_color: [f32; 4],
+    shadow_color: [f32; 4],
+    offset: [f32; 2],
+    strength: f32,
+    bevel_type: u32,
+    knockout: u32,
+    composite_source: u32,
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct ConvolutionFilterUniform {
+    matrix: [[f32; 4]; 64],
+    default_color: [f32; 4],
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    clamp: u32,
+    preserve_alpha: u32,
+    width: f32,
+    height: f32,
+    source_min: [f32; 2],
+    source_max: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct DisplacementMapFilterUniform {
+    color: [f32; 4],
+    map_point: [f32; 2],
+    scale: [f32; 2],
+    source_size: [f32; 2],
+    map_size: [f32; 2],
+    target_size: [f32; 2],
+    source_min: [f32; 2],
+    source_max: [f32; 2],
+    component_x: u32,
+    component_y: u32,
+    mode: u32,
+    _padding: [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct GradientGlowFilterUniform {
+    offset: [f32; 2],
+    strength: f32,
+    inner: u32,
+    knockout: u32,
+    composite_source: u32,
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct GradientBevelFilterUniform {
+    offset: [f32; 2],
+    strength: f32,
+    bevel_type: u32,
+    knockout: u32,
+    composite_source: u32,
+    _padding: [u32; 2],
+}
+
 #[derive(Debug)]
 pub enum QueueSyncHandle {
     AlreadyCopied {
@@ -10,7 +10,7 @@ use crate::buffer_builder::BufferBuilder;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::bitmap::BitmapSource;
 use ruffle_render::tessellator::{Bitmap, Draw as LyonDraw, DrawType as TessDrawType, Gradient};
-use swf::{CharacterId, GradientInterpolation};
+use swf::{CharacterId, GradientInterpolation, GradientRecord};
 
 /// How big to make gradient textures. Larger will keep more detail, but be slower and use more memory.
 const GRADIENT_SIZE: usize = 256;
@@ -130,6 +130,89 @@ fn srgb_to_linear(color: f32) -> f32 {
     }
 }
 
+/// Builds the color ramp of a gradient, as `GRADIENT_SIZE` RGBA texels.
+pub fn gradient_ramp(
+    records: &[GradientRecord],
+    interpolation: GradientInterpolation,
+) -> [u8; GRADIENT_SIZE * 4] {
+    if records.is_empty() {
+        return [0; GRADIENT_SIZE * 4];
+    }
+
+    let mut colors = [0; GRADIENT_SIZE * 4];
+
+    let convert = if interpolation == GradientInterpolation::LinearRgb {
+        |c| srgb_to_linear(c / 255.0) * 255.0
+    } else {
+        |c| c
+    };
+
+    for t in 0..GRADIENT_SIZE {
+        let mut last = 0;
+        let mut next = 0;
+
+        for (i, record) in records.iter().enumerate().rev() {
+            if (record.ratio as usize) < t {
+                last = i;
+                next = (i + 1).min(records.len() - 1);
+                break;
+            }
+        }
+        assert!(last == next || last + 1 == next);
+
+        let last_record = &records[last];
+        let next_record = &records[next];
+
+        let a = if next == last {
+            // this can happen if we are before the first gradient record, or after the last one
+            0.0
+        } else {
+            (t as f32 - last_record.ratio as f32)
+                / (next_record.ratio as f32 - last_record.ratio as f32)
+        };
+        colors[t * 4] = lerp(
+            convert(last_record.color.r as f32),
+            convert(next_record.color.r as f32),
+            a,
+        ) as u8;
+        colors[(t * 4) + 1] = lerp(
+            convert(last_record.color.g as f32),
+            convert(next_record.color.g as f32),
+            a,
+        ) as u8;
+        colors[(t * 4) + 2] = lerp(
+            convert(last_record.color.b as f32),
+            convert(next_record.color.b as f32),
+            a,
+        ) as u8;
+        colors[(t * 4) + 3] = lerp(last_record.color.a as f32, next_record.color.a as f32, a) as u8;
+    }
+
+    colors
+}
+
+/// Uploads a color ramp created by `gradient_ramp` into a texture.
+pub fn create_gradient_texture(descriptors: &Descriptors, colors: &[u8]) -> wgpu::Texture {
+    descriptors.device.create_texture_with_data(
+        &descriptors.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: GRADIENT_SIZE as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        colors,
+    )
+}
+
 fn lerp(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
 }
@@ -147,79 +230,8 @@ impl PendingDrawType {
         uniform_buffers: &mut BufferBuilder,
     ) -> Self {
         let tex_transforms_index = create_texture_transforms(&gradient.matrix, uniform_buffers);
-        let colors = if gradient.records.is_empty() {
-            [0; GRADIENT_SIZE * 4]
-        } else {
-            let mut colors = [0; GRADIENT_SIZE * 4];
-
-            let convert = if gradient.interpolation == GradientInterpolation::LinearRgb {
-                |c| srgb_to_linear(c / 255.0) * 255.0
-            } else {
-                |c| c
-            };
-
-            for t in 0..GRADIENT_SIZE {
-                let mut last = 0;
-                let mut next = 0;
-
-                for (i, record) in gradient.records.iter().enumerate().rev() {
-                    if (record.ratio as usize) < t {
-                        last = i;
-                        next = (i + 1).min(gradient.records.len() - 1);
-                        break;
-                    }
-                }
-                assert!(last == next || last + 1 == next);
-
-                let last_record = &gradient.records[last];
-                let next_record = &gradient.records[next];
-
-                let a = if next == last {
-                    // this can happen if we are before the first gradient record, or after the last one
-                    0.0
-                } else {
-                    (t as f32 - last_record.ratio as f32)
-                        / (next_record.ratio as f32 - last_record.ratio as f32)
-                };
-                colors[t * 4] = lerp(
-                    convert(last_record.color.r as f32),
-                    convert(next_record.color.r as f32),
-                    a,
-                ) as u8;
-                colors[(t * 4) + 1] = lerp(
-                    convert(last_record.color.g as f32),
-                    convert(next_record.color.g as f32),
-                    a,
-                ) as u8;
-                colors[(t * 4) + 2] = lerp(
-                    convert(last_record.color.b as f32),
-                    convert(next_record.color.b as f32),
-                    a,
-                ) as u8;
-                colors[(t * 4) + 3] =
-                    lerp(last_record.color.a as f32, next_record.color.a as f32, a) as u8;
-            }
-
-            colors
-        };
-        let texture = descriptors.device.create_texture_with_data(
-            &descriptors.queue,
-            &wgpu::TextureDescriptor {
-                label: None,
-                size: wgpu::Extent3d {
-                    width: GRADIENT_SIZE as u32,
-                    height: 1,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Unorm,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-            &colors[..],
-        );
+        let colors = gradient_ramp(&gradient.records, gradient.interpolation);
+        let texture = create_gradient_texture(descriptors, &colors);
         let view = texture.create_view(&Default::default());
 
         let gradient = uniform_buffers
@@ -39,6 +39,12 @@ pub struct Pipelines {
     pub complex_blends: EnumMap<ComplexBlend, ShapePipeline>,
     pub color_matrix_filter: wgpu::RenderPipeline,
     pub blur_filter: wgpu::RenderPipeline,
+    pub glow_filter: wgpu::RenderPipeline,
+    pub bevel_filter: wgpu::RenderPipeline,
+    pub convolution_filter: wgpu::RenderPipeline,
+    pub displacement_map_filter: wgpu::RenderPipeline,
+    pub gradient_glow_filter: wgpu::RenderPipeline,
+    pub gradient_bevel_filter: wgpu::RenderPipeline,
 }
 
 impl ShapePipeline {
@@ -198,112 +204,93 @@ impl Pipelines {
             .try_into()
             .unwrap();
 
-        let color_matrix_filter_bindings = if device.limits().max_push_constant_size > 0 {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.bitmap,
-                &bind_layouts.color_matrix_filter,
-            ]
-        } else {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.transforms,
-                &bind_layouts.color_transforms,
-                &bind_layouts.bitmap,
-                &bind_layouts.color_matrix_filter,
-            ]
-        };
+        let color_matrix_filter = create_filter_pipeline(
+            "Color Matrix",
+            device,
+            format,
+            &shaders.color_matrix_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.color_matrix_filter,
+            full_push_constants,
+        );
 
-        let color_matrix_filter_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &color_matrix_filter_bindings,
-                push_constant_ranges: full_push_constants,
-            });
-
-        let color_matrix_filter = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: create_debug_label!("Color Matrix Filter").as_deref(),
-            layout: Some(&color_matrix_filter_layout),
-            vertex: wgpu::VertexState {
-                module: &shaders.color_matrix_filter,
-                entry_point: "main_vertex",
-                buffers: &VERTEX_BUFFERS_DESCRIPTION_POS,
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::default(),
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shaders.color_matrix_filter,
-                entry_point: "main_fragment",
-                targets: &[Some(format.into())],
-            }),
-            multiview: None,
-        });
+        let blur_filter = create_filter_pipeline(
+            "Blur",
+            device,
+            format,
+            &shaders.blur_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.blur_filter,
+            full_push_constants,
+        );
 
-        let blur_filter_bindings = if device.limits().max_push_constant_size > 0 {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.bitmap,
-                &bind_layouts.blur_filter,
-            ]
-        } else {
-            vec![
-                &bind_layouts.globals,
-                &bind_layouts.transforms,
-                &bind_layouts.color_transforms,
-                &bind_layouts.bitmap,
-                &bind_layouts.blur_filter,
-            ]
-        };
+        let glow_filter = create_filter_pipeline(
+            "Glow",
+            device,
+            format,
+            &shaders.glow_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.glow_filter,
+            full_push_constants,
+        );
 
-        let blur_filter_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: None,
-            bind_group_layouts: &blur_filter_bindings,
-            push_constant_ranges: full_push_constants,
-        });
-
-        let blur_filter = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: create_debug_label!("Blur Filter").as_deref(),
-            layout: Some(&blur_filter_layout),
-            vertex: wgpu::VertexState {
-                module: &shaders.blur_filter,
-                entry_point: "main_vertex",
-                buffers: &VERTEX_BUFFERS_DESCRIPTION_POS,
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::default(),
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: msaa_sample_count,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shaders.blur_filter,
-                entry_point: "main_fragment",
-                targets: &[Some(format.into())],
-            }),
-            multiview: None,
-        });
+        let bevel_filter = create_filter_pipeline(
+            "Bevel",
+            device,
+            format,
+            &shaders.bevel_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.bevel_filter,
+            full_push_constants,
+        );
+
+        let convolution_filter = create_filter_pipeline(
+            "Convolution",
+            device,
+            format,
+            &shaders.convolution_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.convolution_filter,
+            full_push_constants,
+        );
+
+        let displacement_map_filter = create_filter_pipeline(
+            "Displacement Map",
+            device,
+            format,
+            &shaders.displacement_map_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.displacement_map_filter,
+            full_push_constants,
+        );
+
+        let gradient_glow_filter = create_filter_pipeline(
+            "Gradient Glow",
+            device,
+            format,
+            &shaders.gradient_glow_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.gradient_glow_filter,
+            full_push_constants,
+        );
+
+        let gradient_bevel_filter = create_filter_pipeline(
+            "Gradient Bevel",
+            device,
+            format,
+            &shaders.gradient_bevel_filter,
+            msaa_sample_count,
+            bind_layouts,
+            &bind_layouts.gradient_bevel_filter,
+            full_push_constants,
+        );
 
         Self {
             color: color_pipelines,
@@ -312,6 +299,12 @@ impl Pipelines {
             complex_blends: complex_blend_pipelines,
             color_matrix_filter,
             blur_filter,
+            glow_filter,
+            bevel_filter,
+            convolution_filter,
+            displacement_map_filter,
+            gradient_glow_filter,
+            gradient_bevel_filter,
         }
     }
 }
@@ -465,3 +458,46 @@ fn create_shape_pipeline(
         },
     )
 }
+
+/// Creates a pipeline for a filter shader, which draws a quad sampling from a bitmap
+/// bind group, with the filter specific arguments bound after it.
+#[allow(clippy::too_many_arguments)]
+fn create_filter_pipeline(
+    name: &str,
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    shader: &wgpu::ShaderModule,
+    msaa_sample_count: u32,
+    bind_layouts: &BindLayouts,
+    filter_layout: &wgpu::BindGroupLayout,
+    push_constant_ranges: &[wgpu::PushConstantRange],
+) -> wgpu::RenderPipeline {
+    let bindings = if device.limits().max_push_constant_size > 0 {
+        vec![&bind_layouts.globals, &bind_layouts.bitmap, filter_layout]
+    } else {
+        vec![
+            &bind_layouts.globals,
+            &bind_layouts.transforms,
+            &bind_layouts.color_transforms,
+            &bind_layouts.bitmap,
+            filter_layout,
+        ]
+    };
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &bindings,
+        push_constant_ranges,
+    });
+
+    device.create_render_pipeline(&create_pipeline_descriptor(
+        create_debug_label!("{} Filter", name).as_deref(),
+        shader,
+        shader,
+        &pipeline_layout,
+        None,
+        &[Some(format.into())],
+        &VERTEX_BUFFERS_DESCRIPTION_POS,
+        msaa_sample_count,
+    ))
+}
@@ -16,6 +16,12 @@ pub struct Shaders {
     pub blend_shaders: EnumMap<ComplexBlend, wgpu::ShaderModule>,
     pub color_matrix_filter: wgpu::ShaderModule,
     pub blur_filter: wgpu::ShaderModule,
+    pub glow_filter: wgpu::ShaderModule,
+    pub bevel_filter: wgpu::ShaderModule,
+    pub convolution_filter: wgpu::ShaderModule,
+    pub displacement_map_filter: wgpu::ShaderModule,
+    pub gradient_glow_filter: wgpu::ShaderModule,
+    pub gradient_bevel_filter: wgpu::ShaderModule,
 }
 
 impl Shaders {
@@ -68,6 +74,48 @@ impl Shaders {
             "filter/blur.wgsl",
             include_str!("../shaders/filter/blur.wgsl"),
         );
+        let glow_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/glow.wgsl",
+            include_str!("../shaders/filter/glow.wgsl"),
+        );
+        let bevel_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/bevel.wgsl",
+            include_str!("../shaders/filter/bevel.wgsl"),
+        );
+        let convolution_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/convolution.wgsl",
+            include_str!("../shaders/filter/convolution.wgsl"),
+        );
+        let displacement_map_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/displacement_map.wgsl",
+            include_str!("../shaders/filter/displacement_map.wgsl"),
+        );
+        let gradient_glow_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient_glow.wgsl",
+            include_str!("../shaders/filter/gradient_glow.wgsl"),
+        );
+        let gradient_bevel_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient_bevel.wgsl",
+            include_str!("../shaders/filter/gradient_bevel.wgsl"),
+        );
         let gradient_shader = make_shader(
             device,
             &mut composer,
@@ -96,6 +144,12 @@ impl Shaders {
             blend_shaders,
             color_matrix_filter,
             blur_filter,
+            glow_filter,
+            bevel_filter,
+            convolution_filter,
+            displacement_map_filter,
+            gradient_glow_filter,
+            gradient_bevel_filter,
         }
     }
 }
@@ -4,19 +4,26 @@ pub mod target;
 use crate::backend::RenderTargetMode;
 use crate::blend::ComplexBlend;
 use crate::buffer_pool::TexturePool;
-use crate::mesh::Mesh;
+use crate::mesh::{create_gradient_texture, gradient_ramp, Mesh};
 use crate::surface::commands::{chunk_blends, Chunk, CommandRenderer};
 use crate::uniform_buffer::BufferStorage;
 use crate::utils::{remove_srgb, supported_sample_count};
 use crate::{
-    ColorAdjustments, Descriptors, MaskState, Pipelines, PushConstants, Texture, TextureTransforms,
+    as_texture, BevelFilterUniform, ColorAdjustments, ConvolutionFilterUniform, Descriptors,
+    DisplacementMapFilterUniform, GlowFilterUniform, GradientBevelFilterUniform,
+    GradientGlowFilterUniform, MaskState, Pipelines, PushConstants, Texture, TextureTransforms,
     Transforms, UniformBuffer, DEFAULT_COLOR_ADJUSTMENTS,
 };
 use ruffle_render::commands::CommandList;
-use ruffle_render::filters::Filter;
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
 use ruffle_render::quality::StageQuality;
+use std::cell::Cell;
 use std::sync::Arc;
-use swf::{BlurFilter, ColorMatrixFilter};
+use swf::{
+    BevelFilter, BevelFilterFlags, BlurFilter, BlurFilterFlags, Color, ColorMatrixFilter,
+    ConvolutionFilter, DropShadowFilter, GlowFilter, GlowFilterFlags, GradientFilter,
+    GradientFilterFlags, GradientInterpolation,
+};
 use target::CommandTarget;
 use tracing::instrument;
 use wgpu::util::DeviceExt;
@@ -378,6 +385,29 @@ impl Surface {
         dest_point: (u32, u32),
         filter: Filter,
     ) {
+        // Effects like outer glows and drop shadows can draw outside of the source area.
+        // Their source is copied into a larger transparent texture first, so that every
+        // filter can draw into the whole destination area like it would into the source.
+        let (offset_x, offset_y, dest_width, dest_height) =
+            filter.calculate_dest_rect((0, 0, source_size.0 as i32, source_size.1 as i32));
+        let padded_source;
+        let (source_texture, source_point, source_size) =
+            if (dest_width, dest_height) == (source_size.0 as i32, source_size.1 as i32) {
+                (source_texture, source_point, source_size)
+            } else {
+                let dest_size = (dest_width as u32, dest_height as u32);
+                padded_source = pad_source(
+                    descriptors,
+                    draw_encoder,
+                    source_texture,
+                    source_point,
+                    source_size,
+                    ((-offset_x) as u32, (-offset_y) as u32),
+                    dest_size,
+                );
+                (&padded_source, (0, 0), dest_size)
+            };
+
         let target = match filter {
             Filter::ColorMatrixFilter(filter) => self.apply_color_matrix(
                 descriptors,
@@ -397,6 +427,69 @@ impl Surface {
                 source_size,
                 &filter,
             ),
+            Filter::GlowFilter(filter) => self.apply_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::DropShadowFilter(filter) => self.apply_drop_shadow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::BevelFilter(filter) => self.apply_bevel(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::GradientGlowFilter(filter) => self.apply_gradient_glow(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::GradientBevelFilter(filter) => self.apply_gradient_bevel(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::ConvolutionFilter(filter) => self.apply_convolution(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
+            Filter::DisplacementMapFilter(filter) => self.apply_displacement_map(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+            ),
             _ => {
                 tracing::warn!("Unsupported filter {filter:?}");
                 // Apply a default color matrix - it's essentially a blit
@@ -417,26 +510,51 @@ impl Surface {
         // the background clear color applied)
         target.ensure_cleared(draw_encoder);
 
+        // The filtered area may start above or to the left of the destination bitmap,
+        // in which case that part is cut off.
+        let dest_x = dest_point.0 as i32 + offset_x;
+        let dest_y = dest_point.1 as i32 + offset_y;
+        let target_origin = ((-dest_x).max(0) as u32, (-dest_y).max(0) as u32);
+        let dest_origin = (dest_x.max(0) as u32, dest_y.max(0) as u32);
+        if dest_origin.0 >= dest_texture.width || dest_origin.1 >= dest_texture.height {
+            return;
+        }
+        let width = target
+            .width()
+            .saturating_sub(target_origin.0)
+            .min(dest_texture.width - dest_origin.0);
+        let height = target
+            .height()
+            .saturating_sub(target_origin.1)
+            .min(dest_texture.height - dest_origin.1);
+        if width == 0 || height == 0 {
+            return;
+        }
+
         draw_encoder.copy_texture_to_texture(
             wgpu::ImageCopyTexture {
                 texture: target.color_texture(),
                 mip_level: 0,
-                origin: wgpu::Origin3d { x: 0, y: 0, z: 0 },
+                origin: wgpu::Origin3d {
+                    x: target_origin.0,
+                    y: target_origin.1,
+                    z: 0,
+                },
                 aspect: Default::default(),
             },
             wgpu::ImageCopyTexture {
                 texture: &dest_texture.texture,
                 mip_level: 0,
                 origin: wgpu::Origin3d {
-                    x: dest_point.0,
-                    y: dest_point.1,
+                    x: dest_origin.0,
+                    y: dest_origin.1,
                     z: 0,
                 },
                 aspect: Default::default(),
             },
             wgpu::Extent3d {
-                width: (target.width()).min(dest_texture.width - dest_point.0),
-                height: (target.height()).min(dest_texture.height - dest_point.1),
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
         )
@@ -467,30 +585,7 @@ impl Surface {
             draw_encoder,
         );
         let texture_transform =
-            descriptors
-                .device
-                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: None,
-                    contents: bytemuck::cast_slice(&[TextureTransforms {
-                        u_matrix: [
-                            [
-                                1.0,
-                                0.0,
-                                0.0,
-                                (source_point.0 as f32 / source_size.0 as f32),
-                            ],
-                            [
-                                0.0,
-                                1.0,
-                                0.0,
-                                (source_point.1 as f32 / source_size.1 as f32),
-                            ],
-                            [0.0, 0.0, 1.0, 0.0],
-                            [0.0, 0.0, 0.0, 1.0],
-                        ],
-                    }]),
-                    usage: wgpu::BufferUsages::UNIFORM,
-                });
+            create_source_transform(descriptors, source_texture, source_point, source_size);
         let source_view = source_texture.texture.create_view(&Default::default());
         let bitmap_group = descriptors
             .device
@@ -574,6 +669,9 @@ impl Surface {
         target
     }
 
+    /// Blurs the source with `filter.num_passes()` iterations of a separable box blur.
+    /// The resulting target is aligned with the source region, so that it can
+    /// also be sampled alongside the source by `composite_blurred`.
     #[allow(clippy::too_many_arguments)]
     pub fn apply_blur(
         &self,
@@ -586,62 +684,29 @@ impl Surface {
         filter: &BlurFilter,
     ) -> CommandTarget {
         let targets = [
-            CommandTarget::new(
-                descriptors,
-                texture_pool,
-                wgpu::Extent3d {
-                    width: source_size.0,
-                    height: source_size.1,
-                    depth_or_array_layers: 1,
-                },
-                self.format,
-                self.sample_count,
-                RenderTargetMode::FreshBuffer(wgpu::Color::TRANSPARENT),
-                draw_encoder,
-            ),
-            CommandTarget::new(
-                descriptors,
-                texture_pool,
-                wgpu::Extent3d {
-                    width: source_size.0,
-                    height: source_size.1,
-                    depth_or_array_layers: 1,
-                },
-                self.format,
-                self.sample_count,
-                RenderTargetMode::FreshBuffer(wgpu::Color::TRANSPARENT),
-                draw_encoder,
-            ),
+            self.create_filter_target(descriptors, texture_pool, draw_encoder, source_size),
+            self.create_filter_target(descriptors, texture_pool, draw_encoder, source_size),
         ];
         let texture_transform =
-            descriptors
-                .device
-                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: None,
-                    contents: bytemuck::cast_slice(&[TextureTransforms {
-                        u_matrix: [
-                            [
-                                1.0,
-                                0.0,
-                                0.0,
-                                (source_point.0 as f32 / source_size.0 as f32),
-                            ],
-                            [
-                                0.0,
-                                1.0,
-                                0.0,
-                                (source_point.1 as f32 / source_size.1 as f32),
-                            ],
-                            [0.0, 0.0, 1.0, 0.0],
-                            [0.0, 0.0, 0.0, 1.0],
-                        ],
-                    }]),
-                    usage: wgpu::BufferUsages::UNIFORM,
-                });
+            create_source_transform(descriptors, source_texture, source_point, source_size);
         let source_view = source_texture.texture.create_view(&Default::default());
-        for i in 0..2 {
-            let blur_x = (filter.blur_x.to_f32() - 1.0).max(0.0);
-            let blur_y = (filter.blur_y.to_f32() - 1.0).max(0.0);
+
+        // The blur shader samples `2 * radius + 1` times, inbetween pixels when the radius
+        // isn't whole. That gives us a box of exactly `blur` pixels wide.
+        // A quality of 0 doesn't blur at all, so the source is just copied over.
+        let passes = filter.num_passes();
+        let (radius_x, radius_y) = if passes == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                (filter.blur_x.to_f32() - 1.0).max(0.0) / 2.0,
+                (filter.blur_y.to_f32() - 1.0).max(0.0) / 2.0,
+            )
+        };
+
+        // Every pass is made of a horizontal and a vertical step, so we always end on `targets[1]`.
+        let steps = passes.max(1) as usize * 2;
+        for i in 0..steps {
             let current = &targets[i % 2];
             let (previous_view, previous_transform, previous_width, previous_height) = if i == 0 {
                 (
@@ -681,13 +746,18 @@ impl Surface {
                         },
                     ],
                 });
+            let (step_x, step_y) = if i % 2 == 0 {
+                (radius_x, 0.0)
+            } else {
+                (0.0, radius_y)
+            };
             let buffer = descriptors
                 .device
                 .create_buffer_init(&wgpu::util::BufferInitDescriptor {
                     label: create_debug_label!("Filter arguments").as_deref(),
                     contents: bytemuck::cast_slice(&[
-                        blur_x * ((i as u32) % 2) as f32,
-                        blur_y * (((i as u32) % 2) + 1) as f32,
+                        step_x,
+                        step_y,
                         previous_width,
                         previous_height,
                     ]),
@@ -703,49 +773,802 @@ impl Surface {
                         resource: buffer.as_entire_binding(),
                     }],
                 });
-            let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: create_debug_label!("Color matrix filter").as_deref(),
-                color_attachments: &[current.color_attachments()],
-                depth_stencil_attachment: None,
-            });
-            render_pass.set_pipeline(&self.pipelines.blur_filter);
-
-            render_pass.set_bind_group(0, current.globals().bind_group(), &[]);
-            if descriptors.limits.max_push_constant_size > 0 {
-                render_pass.set_push_constants(
-                    wgpu::ShaderStages::VERTEX_FRAGMENT,
-                    0,
-                    bytemuck::cast_slice(&[PushConstants {
-                        transforms: Transforms {
-                            world_matrix: [
-                                [current.width() as f32, 0.0, 0.0, 0.0],
-                                [0.0, current.height() as f32, 0.0, 0.0],
-                                [0.0, 0.0, 1.0, 0.0],
-                                [0.0, 0.0, 0.0, 1.0],
-                            ],
-                        },
-                        colors: DEFAULT_COLOR_ADJUSTMENTS,
-                    }]),
-                );
-                render_pass.set_bind_group(1, &bitmap_group, &[]);
-                render_pass.set_bind_group(2, &filter_group, &[]);
-            } else {
-                render_pass.set_bind_group(1, current.whole_frame_bind_group(descriptors), &[0]);
-                render_pass.set_bind_group(2, &descriptors.default_color_bind_group, &[0]);
-                render_pass.set_bind_group(3, &bitmap_group, &[]);
-                render_pass.set_bind_group(4, &filter_group, &[]);
-            }
-
-            render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
-            render_pass.set_index_buffer(
-                descriptors.quad.indices.slice(..),
-                wgpu::IndexFormat::Uint32,
+            draw_filter_quad(
+                descriptors,
+                draw_encoder,
+                current,
+                &self.pipelines.blur_filter,
+                &bitmap_group,
+                &filter_group,
+                create_debug_label!("Blur filter pass {}", i).as_deref(),
             );
-            render_pass.draw_indexed(0..6, 0, 0..1);
         }
         targets
             .into_iter()
             .last()
             .expect("Targets should not be empty")
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_glow(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &GlowFilter,
+    ) -> CommandTarget {
+        let blurred = self.apply_blur(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &BlurFilter {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                flags: BlurFilterFlags::from_passes(filter.num_passes()),
+            },
+        );
+        let uniform = GlowFilterUniform {
+            color: color_to_f32(&filter.color),
+            offset: [0.0, 0.0],
+            strength: filter.strength.to_f32(),
+            inner: filter.is_inner() as u32,
+            knockout: filter.is_knockout() as u32,
+            composite_source: filter.flags.contains(GlowFilterFlags::COMPOSITE_SOURCE) as u32,
+            _padding: Default::default(),
+        };
+        self.composite_blurred(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &blurred,
+            None,
+            &self.pipelines.glow_filter,
+            &descriptors.bind_layouts.glow_filter,
+            bytemuck::cast_slice(&[uniform]),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_drop_shadow(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &DropShadowFilter,
+    ) -> CommandTarget {
+        let blurred = self.apply_blur(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &BlurFilter {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                flags: BlurFilterFlags::from_passes(filter.num_passes()),
+            },
+        );
+        // A drop shadow is an (inner or outer) glow that's been moved away from the object.
+        let uniform = GlowFilterUniform {
+            color: color_to_f32(&filter.color),
+            offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32(), source_size),
+            strength: filter.strength.to_f32(),
+            inner: filter.is_inner() as u32,
+            knockout: filter.is_knockout() as u32,
+            composite_source: !filter.hide_object() as u32,
+            _padding: Default::default(),
+        };
+        self.composite_blurred(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &blurred,
+            None,
+            &self.pipelines.glow_filter,
+            &descriptors.bind_layouts.glow_filter,
+            bytemuck::cast_slice(&[uniform]),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_bevel(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &BevelFilter,
+    ) -> CommandTarget {
+        let blurred = self.apply_blur(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &BlurFilter {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                flags: BlurFilterFlags::from_passes(filter.num_passes()),
+            },
+        );
+        let uniform = BevelFilterUniform {
+            highlight_color: color_to_f32(&filter.highlight_color),
+            shadow_color: color_to_f32(&filter.shadow_color),
+            offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32(), source_size),
+            strength: filter.strength.to_f32(),
+            bevel_type: if filter.is_on_top() {
+                2
+            } else if filter.is_inner() {
+                0
+            } else {
+                1
+            },
+            knockout: filter.is_knockout() as u32,
+            composite_source: filter.flags.contains(BevelFilterFlags::COMPOSITE_SOURCE) as u32,
+            _padding: Default::default(),
+        };
+        self.composite_blurred(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &blurred,
+            None,
+            &self.pipelines.bevel_filter,
+            &descriptors.bind_layouts.bevel_filter,
+            bytemuck::cast_slice(&[uniform]),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_gradient_glow(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &GradientFilter,
+    ) -> CommandTarget {
+        let blurred = self.apply_blur(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &BlurFilter {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                flags: BlurFilterFlags::from_passes(filter.num_passes()),
+            },
+        );
+        let gradient = create_filter_gradient(descriptors, filter);
+        let uniform = GradientGlowFilterUniform {
+            offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32(), source_size),
+            strength: filter.strength.to_f32(),
+            inner: filter.is_inner() as u32,
+            knockout: filter.is_knockout() as u32,
+            composite_source: filter.flags.contains(GradientFilterFlags::COMPOSITE_SOURCE) as u32,
+            _padding: Default::default(),
+        };
+        self.composite_blurred(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &blurred,
+            Some(&gradient),
+            &self.pipelines.gradient_glow_filter,
+            &descriptors.bind_layouts.gradient_glow_filter,
+            bytemuck::cast_slice(&[uniform]),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_gradient_bevel(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &GradientFilter,
+    ) -> CommandTarget {
+        let blurred = self.apply_blur(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &BlurFilter {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                flags: BlurFilterFlags::from_passes(filter.num_passes()),
+            },
+        );
+        let gradient = create_filter_gradient(descriptors, filter);
+        let uniform = GradientBevelFilterUniform {
+            offset: filter_offset(filter.angle.to_f32(), filter.distance.to_f32(), source_size),
+            strength: filter.strength.to_f32(),
+            bevel_type: if filter.is_on_top() {
+                2
+            } else if filter.is_inner() {
+                0
+            } else {
+                1
+            },
+            knockout: filter.is_knockout() as u32,
+            composite_source: filter.flags.contains(GradientFilterFlags::COMPOSITE_SOURCE) as u32,
+            _padding: Default::default(),
+        };
+        self.composite_blurred(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &blurred,
+            Some(&gradient),
+            &self.pipelines.gradient_bevel_filter,
+            &descriptors.bind_layouts.gradient_bevel_filter,
+            bytemuck::cast_slice(&[uniform]),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_convolution(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &ConvolutionFilter,
+    ) -> CommandTarget {
+        let target =
+            self.create_filter_target(descriptors, texture_pool, draw_encoder, source_size);
+
+        // Flash Player doesn't support matrices larger than 15x15.
+        let matrix_x = filter.num_matrix_cols.min(15);
+        let matrix_y = filter.num_matrix_rows.min(15);
+        let mut matrix = [[0.0; 4]; 64];
+        for y in 0..matrix_y as usize {
+            for x in 0..matrix_x as usize {
+                let index = y * matrix_x as usize + x;
+                matrix[index / 4][index % 4] = filter
+                    .matrix
+                    .get(y * filter.num_matrix_cols as usize + x)
+                    .map_or(0.0, |value| value.to_f32());
+            }
+        }
+        let uniform = ConvolutionFilterUniform {
+            matrix,
+            default_color: color_to_f32(&filter.default_color),
+            bias: filter.bias.to_f32(),
+            divisor: filter.divisor.to_f32(),
+            matrix_x: matrix_x as u32,
+            matrix_y: matrix_y as u32,
+            clamp: filter.is_clamped() as u32,
+            preserve_alpha: filter.is_preserve_alpha() as u32,
+            width: source_texture.width as f32,
+            height: source_texture.height as f32,
+            source_min: source_min(source_texture, source_point),
+            source_max: source_max(source_texture, source_point, source_size),
+        };
+
+        let texture_transform =
+            create_source_transform(descriptors, source_texture, source_point, source_size);
+        let source_view = source_texture.texture.create_view(&Default::default());
+        let bitmap_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Bitmap copy group").as_deref(),
+                layout: &descriptors.bind_layouts.bitmap,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: texture_transform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, false),
+                        ),
+                    },
+                ],
+            });
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[uniform]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &descriptors.bind_layouts.convolution_filter,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+        draw_filter_quad(
+            descriptors,
+            draw_encoder,
+            &target,
+            &self.pipelines.convolution_filter,
+            &bitmap_group,
+            &filter_group,
+            create_debug_label!("Convolution filter").as_deref(),
+        );
+        target
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_displacement_map(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &DisplacementMapFilter,
+    ) -> CommandTarget {
+        let map_texture = match &filter.map_bitmap {
+            Some(map_bitmap) => as_texture(map_bitmap),
+            None => {
+                // Without a map there's nothing to displace the source with
+                return self.apply_color_matrix(
+                    descriptors,
+                    texture_pool,
+                    draw_encoder,
+                    source_texture,
+                    source_point,
+                    source_size,
+                    &Default::default(),
+                );
+            }
+        };
+        let target =
+            self.create_filter_target(descriptors, texture_pool, draw_encoder, source_size);
+        let uniform = DisplacementMapFilterUniform {
+            color: color_to_f32(&filter.color),
+            map_point: [filter.map_point.0 as f32, filter.map_point.1 as f32],
+            scale: [filter.scale_x, filter.scale_y],
+            source_size: [source_texture.width as f32, source_texture.height as f32],
+            map_size: [map_texture.width as f32, map_texture.height as f32],
+            target_size: [source_size.0 as f32, source_size.1 as f32],
+            source_min: source_min(source_texture, source_point),
+            source_max: source_max(source_texture, source_point, source_size),
+            component_x: filter.component_x as u32,
+            component_y: filter.component_y as u32,
+            mode: match filter.mode {
+                DisplacementMapFilterMode::Clamp => 0,
+                DisplacementMapFilterMode::Color => 1,
+                DisplacementMapFilterMode::Ignore => 2,
+                DisplacementMapFilterMode::Wrap => 3,
+            },
+            _padding: Default::default(),
+        };
+
+        let texture_transform =
+            create_source_transform(descriptors, source_texture, source_point, source_size);
+        let source_view = source_texture.texture.create_view(&Default::default());
+        let map_view = map_texture.texture.create_view(&Default::default());
+        let bitmap_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Bitmap copy group").as_deref(),
+                layout: &descriptors.bind_layouts.bitmap,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: texture_transform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, false),
+                        ),
+                    },
+                ],
+            });
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[uniform]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &descriptors.bind_layouts.displacement_map_filter,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&map_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, false),
+                        ),
+                    },
+                ],
+            });
+        draw_filter_quad(
+            descriptors,
+            draw_encoder,
+            &target,
+            &self.pipelines.displacement_map_filter,
+            &bitmap_group,
+            &filter_group,
+            create_debug_label!("Displacement map filter").as_deref(),
+        );
+        target
+    }
+
+    /// Runs a filter shader that combines the source with a blurred copy of it,
+    /// as produced by `apply_blur`. `uniform` holds the filter specific arguments.
+    #[allow(clippy::too_many_arguments)]
+    fn composite_blurred(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        blurred: &CommandTarget,
+        gradient: Option<&wgpu::TextureView>,
+        pipeline: &wgpu::RenderPipeline,
+        layout: &wgpu::BindGroupLayout,
+        uniform: &[u8],
+    ) -> CommandTarget {
+        let target =
+            self.create_filter_target(descriptors, texture_pool, draw_encoder, source_size);
+        let texture_transform =
+            create_source_transform(descriptors, source_texture, source_point, source_size);
+        let source_view = source_texture.texture.create_view(&Default::default());
+        let bitmap_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Bitmap copy group").as_deref(),
+                layout: &descriptors.bind_layouts.bitmap,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: texture_transform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, false),
+                        ),
+                    },
+                ],
+            });
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: uniform,
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(blurred.color_view()),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(
+                    descriptors.bitmap_samplers.get_sampler(false, true),
+                ),
+            },
+        ];
+        if let Some(gradient) = gradient {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(gradient),
+            });
+            entries.push(wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::Sampler(
+                    descriptors.bitmap_samplers.get_sampler(false, true),
+                ),
+            });
+        }
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout,
+                entries: &entries,
+            });
+        draw_filter_quad(
+            descriptors,
+            draw_encoder,
+            &target,
+            pipeline,
+            &bitmap_group,
+            &filter_group,
+            create_debug_label!("Blurred filter composite").as_deref(),
+        );
+        target
+    }
+
+    fn create_filter_target(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        size: (u32, u32),
+    ) -> CommandTarget {
+        CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            self.format,
+            self.sample_count,
+            RenderTargetMode::FreshBuffer(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        )
+    }
+}
+
+/// Copies the `source_size` area of `source_texture` at `source_point` into a new transparent
+/// texture of `size`, placing it at `offset`. Anything outside of the source area is left out,
+/// as filters only ever see the source area.
+fn pad_source(
+    descriptors: &Descriptors,
+    draw_encoder: &mut wgpu::CommandEncoder,
+    source_texture: &Texture,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+    offset: (u32, u32),
+    size: (u32, u32),
+) -> Texture {
+    let extent = wgpu::Extent3d {
+        width: size.0,
+        height: size.1,
+        depth_or_array_layers: 1,
+    };
+    let texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+        label: create_debug_label!("Padded filter source").as_deref(),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC,
+    });
+    let width = source_size
+        .0
+        .min(source_texture.width.saturating_sub(source_point.0));
+    let height = source_size
+        .1
+        .min(source_texture.height.saturating_sub(source_point.1));
+    if width > 0 && height > 0 {
+        draw_encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: &source_texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: source_point.0,
+                    y: source_point.1,
+                    z: 0,
+                },
+                aspect: Default::default(),
+            },
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: offset.0,
+                    y: offset.1,
+                    z: 0,
+                },
+                aspect: Default::default(),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+    Texture {
+        texture: Arc::new(texture),
+        bind_linear: Default::default(),
+        bind_nearest: Default::default(),
+        texture_offscreen: Default::default(),
+        copy_count: Cell::new(0),
+        width: size.0,
+        height: size.1,
+    }
+}
+
+/// Creates the texture transform that maps a filter target onto the source region.
+fn create_source_transform(
+    descriptors: &Descriptors,
+    source_texture: &Texture,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+) -> wgpu::Buffer {
+    let [min_x, min_y] = source_min(source_texture, source_point);
+    let [max_x, max_y] = source_max(source_texture, source_point, source_size);
+    descriptors
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&[TextureTransforms {
+                u_matrix: [
+                    [max_x - min_x, 0.0, 0.0, 0.0],
+                    [0.0, max_y - min_y, 0.0, 0.0],
+                    [min_x, min_y, 1.0, 0.0],
+                    [0.0, 0.0, 0.0, 1.0],
+                ],
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        })
+}
+
+/// The top left corner of the source region, in texture coordinates.
+fn source_min(source_texture: &Texture, source_point: (u32, u32)) -> [f32; 2] {
+    [
+        source_point.0 as f32 / source_texture.width as f32,
+        source_point.1 as f32 / source_texture.height as f32,
+    ]
+}
+
+/// The bottom right corner of the source region, in texture coordinates.
+fn source_max(
+    source_texture: &Texture,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+) -> [f32; 2] {
+    [
+        (source_point.0 + source_size.0).min(source_texture.width) as f32
+            / source_texture.width as f32,
+        (source_point.1 + source_size.1).min(source_texture.height) as f32
+            / source_texture.height as f32,
+    ]
+}
+
+/// Draws a quad covering the whole target with the given filter pipeline.
+fn draw_filter_quad(
+    descriptors: &Descriptors,
+    draw_encoder: &mut wgpu::CommandEncoder,
+    target: &CommandTarget,
+    pipeline: &wgpu::RenderPipeline,
+    bitmap_group: &wgpu::BindGroup,
+    filter_group: &wgpu::BindGroup,
+    label: Option<&str>,
+) {
+    let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label,
+        color_attachments: &[target.color_attachments()],
+        depth_stencil_attachment: None,
+    });
+    render_pass.set_pipeline(pipeline);
+
+    render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
+    if descriptors.limits.max_push_constant_size > 0 {
+        render_pass.set_push_constants(
+            wgpu::ShaderStages::VERTEX_FRAGMENT,
+            0,
+            bytemuck::cast_slice(&[PushConstants {
+                transforms: Transforms {
+                    world_matrix: [
+                        [target.width() as f32, 0.0, 0.0, 0.0],
+                        [0.0, target.height() as f32, 0.0, 0.0],
+                        [0.0, 0.0, 1.0, 0.0],
+                        [0.0, 0.0, 0.0, 1.0],
+                    ],
+                },
+                colors: DEFAULT_COLOR_ADJUSTMENTS,
+            }]),
+        );
+        render_pass.set_bind_group(1, bitmap_group, &[]);
+        render_pass.set_bind_group(2, filter_group, &[]);
+    } else {
+        render_pass.set_bind_group(1, target.whole_frame_bind_group(descriptors), &[0]);
+        render_pass.set_bind_group(2, &descriptors.default_color_bind_group, &[0]);
+        render_pass.set_bind_group(3, bitmap_group, &[]);
+        render_pass.set_bind_group(4, filter_group, &[]);
+    }
+
+    render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
+    render_pass.set_index_buffer(
+        descriptors.quad.indices.slice(..),
+        wgpu::IndexFormat::Uint32,
+    );
+    render_pass.draw_indexed(0..6, 0, 0..1);
+}
+
+/// Creates the color ramp of a gradient glow or bevel filter.
+fn create_filter_gradient(descriptors: &Descriptors, filter: &GradientFilter) -> wgpu::TextureView {
+    let colors = gradient_ramp(&filter.colors, GradientInterpolation::Rgb);
+    create_gradient_texture(descriptors, &colors).create_view(&Default::default())
+}
+
+/// Converts a filter color into the straight alpha floats used by the filter shaders.
+fn color_to_f32(color: &Color) -> [f32; 4] {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+/// Calculates how far a shadow is moved, in texture coordinates of a target of `size`.
+/// `angle` is in radians, and `distance` is in pixels.
+fn filter_offset(angle: f32, distance: f32, size: (u32, u32)) -> [f32; 2] {
+    [
+        angle.cos() * distance / size.0 as f32,
+        angle.sin() * distance / size.1 as f32,
+    ]
 }
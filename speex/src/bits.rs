@@ -0,0 +1,88 @@
+/// Reads a Speex bitstream, most significant bit first.
+///
+/// Like `SpeexBits` in libspeex, reading past the end of the data doesn't
+/// fail immediately: it returns zeroes and marks the reader as overflowed,
+/// which the decoder checks once the frame is complete.
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    overflow: bool,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            overflow: false,
+        }
+    }
+
+    /// The number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The number of bits left to read, which is zero after an overflow.
+    pub fn remaining(&self) -> usize {
+        if self.overflow {
+            0
+        } else {
+            self.data.len() * 8 - self.pos
+        }
+    }
+
+    pub fn has_overflowed(&self) -> bool {
+        self.overflow
+    }
+
+    fn check(&mut self, num_bits: usize) -> bool {
+        if self.pos + num_bits > self.data.len() * 8 {
+            self.overflow = true;
+        }
+        !self.overflow
+    }
+
+    pub fn read(&mut self, num_bits: u32) -> u32 {
+        if !self.check(num_bits as usize) {
+            return 0;
+        }
+        let mut value = 0;
+        for _ in 0..num_bits {
+            let bit = (self.data[self.pos / 8] >> (7 - self.pos % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.pos += 1;
+        }
+        value
+    }
+
+    pub fn peek(&mut self) -> u32 {
+        if !self.check(1) {
+            return 0;
+        }
+        u32::from((self.data[self.pos / 8] >> (7 - self.pos % 8)) & 1)
+    }
+
+    pub fn skip(&mut self, num_bits: usize) {
+        if self.check(num_bits) {
+            self.pos += num_bits;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read() {
+        let mut bits = BitReader::new(&[0b1010_0110, 0b1100_0000]);
+        assert_eq!(bits.read(3), 0b101);
+        assert_eq!(bits.read(7), 0b001_1011);
+        assert_eq!(bits.peek(), 0);
+        assert_eq!(bits.remaining(), 6);
+        assert_eq!(bits.read(7), 0);
+        assert!(bits.has_overflowed());
+        assert_eq!(bits.remaining(), 0);
+    }
+}
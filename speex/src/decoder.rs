@@ -0,0 +1,258 @@
+use crate::bits::BitReader;
+use crate::filters::Highpass;
+use crate::nb::{self, FrameError, NbDecoder};
+use crate::wb::{self, WbDecoder};
+
+/// The bandwidth of a Speex stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// 8 kHz audio in frames of 160 samples.
+    Narrowband,
+
+    /// 16 kHz audio in frames of 320 samples. This is the mode used by
+    /// Flash Player.
+    Wideband,
+}
+
+impl Mode {
+    pub fn sample_rate(self) -> u32 {
+        match self {
+            Mode::Narrowband => 8000,
+            Mode::Wideband => 16000,
+        }
+    }
+
+    pub fn frame_size(self) -> usize {
+        match self {
+            Mode::Narrowband => nb::FRAME_SIZE,
+            Mode::Wideband => wb::FRAME_SIZE,
+        }
+    }
+}
+
+enum FrameDecoder {
+    Narrowband(Box<NbDecoder>),
+    Wideband(Box<WbDecoder>),
+}
+
+impl FrameDecoder {
+    fn new(mode: Mode) -> Self {
+        match mode {
+            Mode::Narrowband => Self::Narrowband(Box::new(NbDecoder::new(Highpass::Narrowband))),
+            Mode::Wideband => Self::Wideband(Box::new(WbDecoder::new())),
+        }
+    }
+}
+
+/// Decodes a Speex stream, yielding mono 16-bit samples.
+///
+/// The stream is made of packets, such as the audio tags of an FLV file,
+/// which each hold one or more frames. Like libspeex, the bits left over at
+/// the end of a packet are treated as padding.
+pub struct Decoder<P: Iterator> {
+    packets: P,
+    mode: Mode,
+    decoder: FrameDecoder,
+
+    /// The packet that frames are being decoded from.
+    packet: Option<P::Item>,
+    bit_pos: usize,
+    is_corrupt: bool,
+
+    samples: [f32; wb::FRAME_SIZE],
+    cur_sample: usize,
+    num_samples: usize,
+}
+
+impl<P: Iterator> Decoder<P>
+where
+    P::Item: AsRef<[u8]>,
+{
+    pub fn new(packets: P, mode: Mode) -> Self {
+        Self {
+            packets,
+            mode,
+            decoder: FrameDecoder::new(mode),
+            packet: None,
+            bit_pos: 0,
+            is_corrupt: false,
+            samples: [0.0; wb::FRAME_SIZE],
+            cur_sample: 0,
+            num_samples: 0,
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.mode.sample_rate()
+    }
+
+    /// Restarts decoding from the first of `packets`.
+    pub fn reset(&mut self, packets: P) {
+        *self = Self::new(packets, self.mode);
+    }
+
+    /// Decodes the next frame into `self.samples`, returning `false` at the
+    /// end of the stream.
+    fn decode_frame(&mut self) -> bool {
+        while !self.is_corrupt {
+            if self.packet.is_none() {
+                self.packet = self.packets.next();
+                self.bit_pos = 0;
+            }
+            let Some(packet) = &self.packet else {
+                return false;
+            };
+
+            let mut bits = BitReader::new(packet.as_ref());
+            bits.skip(self.bit_pos);
+            let result = match &mut self.decoder {
+                FrameDecoder::Narrowband(decoder) => decoder.decode(&mut bits, &mut self.samples),
+                FrameDecoder::Wideband(decoder) => decoder.decode(&mut bits, &mut self.samples),
+            };
+            match result {
+                Ok(()) if !bits.has_overflowed() => {
+                    self.bit_pos = bits.position();
+                    self.cur_sample = 0;
+                    self.num_samples = self.mode.frame_size();
+                    return true;
+                }
+                // The rest of this packet is padding.
+                Err(FrameError::EndOfStream) => self.packet = None,
+                // Stop here rather than trying to resynchronize.
+                _ => self.is_corrupt = true,
+            }
+        }
+        false
+    }
+}
+
+impl<P: Iterator> Iterator for Decoder<P>
+where
+    P::Item: AsRef<[u8]>,
+{
+    type Item = i16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur_sample >= self.num_samples && !self.decode_frame() {
+            return None;
+        }
+
+        let sample = self.samples[self.cur_sample];
+        self.cur_sample += 1;
+        Some(if sample > 32767.0 {
+            i16::MAX
+        } else if sample < -32768.0 {
+            i16::MIN
+        } else {
+            (0.5 + f64::from(sample)).floor() as i16
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four wideband frames at quality 3, one per packet.
+    #[rustfmt::skip]
+    const WIDEBAND: [u8; 100] = [
+        0x1b, 0x9d, 0x3f, 0x90, 0x00, 0x39, 0xce, 0x70, 0x40, 0x5c, 0x73, 0x74,
+        0x27, 0xfe, 0x44, 0xd4, 0x4b, 0x93, 0x8a, 0xfe, 0x93, 0xb6, 0x13, 0xa2,
+        0xf7, 0x1d, 0xdd, 0xfd, 0x9f, 0x90, 0xa9, 0x4a, 0x3b, 0x33, 0x8e, 0xb7,
+        0x39, 0xa5, 0xae, 0x74, 0x1c, 0xd4, 0xed, 0xb9, 0x48, 0x93, 0x9e, 0x9e,
+        0x4c, 0x97, 0x1d, 0xce, 0x5d, 0xa6, 0x78, 0x2b, 0xce, 0xd3, 0x67, 0x94,
+        0xe7, 0x39, 0x92, 0x0d, 0x1e, 0xdc, 0xc1, 0x47, 0x38, 0xaf, 0x93, 0xa8,
+        0x22, 0x80, 0x47, 0x1d, 0xdd, 0xd1, 0xa6, 0x1b, 0x3b, 0x2e, 0x72, 0xfb,
+        0x26, 0x67, 0x39, 0x75, 0x8e, 0x73, 0xbb, 0xbb, 0x6c, 0xb9, 0xca, 0x9b,
+        0xb6, 0x78, 0x94, 0xe7,
+    ];
+
+    /// Four narrowband frames at quality 4, one per packet.
+    #[rustfmt::skip]
+    const NARROWBAND: [u8; 80] = [
+        0x1b, 0x98, 0xaf, 0xb0, 0x00, 0x39, 0xce, 0x70, 0x30, 0x57, 0xd5, 0x35,
+        0xbc, 0x2a, 0xd5, 0x00, 0xcf, 0x0e, 0x4b, 0x5a, 0x1d, 0xdd, 0xdd, 0xa6,
+        0xab, 0x95, 0xad, 0xed, 0xf8, 0x92, 0xa1, 0xa9, 0xa3, 0x8a, 0x48, 0x14,
+        0xcc, 0x87, 0x64, 0x33, 0x1d, 0xdd, 0xdd, 0xa6, 0x6f, 0x74, 0x0e, 0x53,
+        0x1e, 0xba, 0xce, 0x11, 0x8e, 0xce, 0xcb, 0x82, 0xc2, 0xc3, 0x19, 0xd9,
+        0x1d, 0xdd, 0xd1, 0xae, 0x24, 0x38, 0x58, 0xf2, 0xfc, 0x1c, 0xe7, 0x39,
+        0x72, 0x8e, 0x9b, 0x9c, 0xbb, 0x07, 0x39, 0xd9,
+    ];
+
+    /// Every 40th sample decoded by libspeex, which we should match exactly.
+    #[rustfmt::skip]
+    const WIDEBAND_SAMPLES: [i16; 32] = [
+        0, 0, 0, 0, 0, 7, 161, -125, -476, 712,
+        -1100, -1112, -180, 5741, -2612, -2280, 1501, -270, -3460, -1158,
+        5269, -4637, -2374, 14266, -3643, -3271, 485, 269, -4686, -1677,
+        1501, -4593,
+    ];
+
+    #[rustfmt::skip]
+    const NARROWBAND_SAMPLES: [i16; 16] = [
+        0, 0, 2111, -1088, 1614, 564, -3731, 1067, -3737, 12954,
+        565, -4026, 2654, -4520, 8006, 598,
+    ];
+
+    /// The length of each packet of `WIDEBAND`.
+    const WIDEBAND_PACKET: usize = 25;
+
+    /// The length of each packet of `NARROWBAND`.
+    const NARROWBAND_PACKET: usize = 20;
+
+    fn decode(data: &[u8], packet_len: usize, mode: Mode) -> Vec<i16> {
+        Decoder::new(data.chunks(packet_len), mode).collect()
+    }
+
+    #[test]
+    fn decode_wideband() {
+        let samples = decode(&WIDEBAND, WIDEBAND_PACKET, Mode::Wideband);
+        assert_eq!(samples.len(), 4 * 320);
+        let sampled: Vec<i16> = samples.iter().step_by(40).copied().collect();
+        assert_eq!(sampled, WIDEBAND_SAMPLES);
+    }
+
+    #[test]
+    fn decode_narrowband() {
+        let samples = decode(&NARROWBAND, NARROWBAND_PACKET, Mode::Narrowband);
+        assert_eq!(samples.len(), 4 * 160);
+        let sampled: Vec<i16> = samples.iter().step_by(40).copied().collect();
+        assert_eq!(sampled, NARROWBAND_SAMPLES);
+    }
+
+    #[test]
+    fn decode_wideband_as_narrowband() {
+        // The high band is skipped, leaving the low band at 8 kHz.
+        let samples = decode(&WIDEBAND, WIDEBAND_PACKET, Mode::Narrowband);
+        assert_eq!(samples.len(), 4 * 160);
+    }
+
+    #[test]
+    fn decode_truncated() {
+        let samples = decode(
+            &WIDEBAND[..WIDEBAND.len() - 10],
+            WIDEBAND_PACKET,
+            Mode::Wideband,
+        );
+        assert_eq!(samples.len(), 3 * 320);
+    }
+
+    #[test]
+    fn terminator_ends_packet() {
+        // A terminator followed by bits that aren't a valid frame, then
+        // an empty packet.
+        let mut packets: Vec<&[u8]> = vec![&[0x7F, 0xFF, 0xFF], &[]];
+        packets.extend(WIDEBAND.chunks(WIDEBAND_PACKET));
+        let samples: Vec<i16> = Decoder::new(packets.into_iter(), Mode::Wideband).collect();
+        assert_eq!(samples, decode(&WIDEBAND, WIDEBAND_PACKET, Mode::Wideband));
+    }
+
+    #[test]
+    fn reset() {
+        let mut decoder = Decoder::new(WIDEBAND.chunks(WIDEBAND_PACKET), Mode::Wideband);
+        let first: Vec<i16> = decoder.by_ref().collect();
+        decoder.reset(WIDEBAND.chunks(WIDEBAND_PACKET));
+        let second: Vec<i16> = decoder.collect();
+        assert_eq!(first, second);
+    }
+}
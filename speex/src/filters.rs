@@ -0,0 +1,285 @@
+//! Signal processing primitives shared by the narrowband and wideband
+//! decoders.
+
+/// Apply bandwidth expansion to LPC coefficients, i.e. compute A(z/gamma).
+pub fn bw_lpc(gamma: f32, lpc_in: &[f32], lpc_out: &mut [f32]) {
+    let mut tmp = gamma;
+    for (out, &lpc) in lpc_out.iter_mut().zip(lpc_in) {
+        *out = tmp * lpc;
+        tmp *= gamma;
+    }
+}
+
+/// Clamp values to the given range, replacing NaNs with zero.
+pub fn sanitize_values(vec: &mut [f32], min: f32, max: f32) {
+    for value in vec {
+        if value.is_nan() {
+            *value = 0.0;
+        } else {
+            *value = value.clamp(min, max);
+        }
+    }
+}
+
+/// Generate uniformly distributed noise with the given standard deviation.
+pub fn rand(std: f32, seed: &mut u32) -> f32 {
+    *seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+    let ran = f32::from_bits(0x3f80_0000 | (*seed & 0x007f_ffff)) - 1.5;
+    (3.4642 * f64::from(std) * f64::from(ran)) as f32
+}
+
+#[derive(Clone, Copy)]
+pub enum Highpass {
+    Narrowband,
+    Wideband,
+}
+
+/// The output high-pass filter, removing DC and very low frequencies.
+pub fn highpass(x: &mut [f32], filter: Highpass, mem: &mut [f32; 2]) {
+    let (den, num) = match filter {
+        Highpass::Narrowband => ([1.0, -1.92683, 0.93071], [0.96446, -1.92879, 0.96446]),
+        Highpass::Wideband => ([1.0, -1.97226, 0.97332], [0.98645, -1.97277, 0.98645]),
+    };
+
+    for x in x {
+        let xi = *x;
+        let vout = num[0] * xi + mem[0];
+        mem[0] = mem[1] + num[1] * xi + -den[1] * vout;
+        mem[1] = num[2] * xi + -den[2] * vout;
+        *x = vout;
+    }
+}
+
+pub fn compute_rms(x: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for &x in x {
+        sum += x * x;
+    }
+    (0.1 + f64::from(sum / x.len() as f32)).sqrt() as f32
+}
+
+pub fn inner_prod(x: &[f32], y: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for (x, y) in x.chunks_exact(4).zip(y.chunks_exact(4)) {
+        let mut part = 0.0f32;
+        part += x[0] * y[0];
+        part += x[1] * y[1];
+        part += x[2] * y[2];
+        part += x[3] * y[3];
+        sum += part;
+    }
+    sum
+}
+
+/// All-pole synthesis filter 1/A(z), filtering `x` in place.
+pub fn iir_mem(x: &mut [f32], den: &[f32], mem: &mut [f32]) {
+    let order = den.len();
+    for x in x {
+        let yi = *x + mem[0];
+        let nyi = -yi;
+        for j in 0..order - 1 {
+            mem[j] = mem[j + 1] + den[j] * nyi;
+        }
+        mem[order - 1] = den[order - 1] * nyi;
+        *x = yi;
+    }
+}
+
+/// Quadrature mirror filter synthesis, merging the low band (the first half
+/// of `y`) and the high band (the second half) into a signal with twice the
+/// sample rate.
+pub fn qmf_synth(y: &mut [f32], a: &[f32], mem1: &mut [f32], mem2: &mut [f32]) {
+    let m2 = a.len() / 2;
+    let n2 = y.len() / 2;
+    let mut xx1 = vec![0.0f32; m2 + n2];
+    let mut xx2 = vec![0.0f32; m2 + n2];
+
+    for i in 0..n2 {
+        xx1[i] = y[n2 - 1 - i];
+        xx2[i] = y[2 * n2 - 1 - i];
+    }
+    for i in 0..m2 {
+        xx1[n2 + i] = mem1[2 * i + 1];
+        xx2[n2 + i] = mem2[2 * i + 1];
+    }
+
+    for i in (0..n2).step_by(2) {
+        let (mut y0, mut y1, mut y2, mut y3) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+        let mut x10 = xx1[n2 - 2 - i];
+        let mut x20 = xx2[n2 - 2 - i];
+
+        for j in (0..m2).step_by(2) {
+            let a0 = a[2 * j];
+            let a1 = a[2 * j + 1];
+            let x11 = xx1[n2 - 1 + j - i];
+            let x21 = xx2[n2 - 1 + j - i];
+
+            y0 += a0 * (x11 - x21);
+            y1 += a1 * (x11 + x21);
+            y2 += a0 * (x10 - x20);
+            y3 += a1 * (x10 + x20);
+
+            let a0 = a[2 * j + 2];
+            let a1 = a[2 * j + 3];
+            x10 = xx1[n2 + j - i];
+            x20 = xx2[n2 + j - i];
+
+            y0 += a0 * (x10 - x20);
+            y1 += a1 * (x10 + x20);
+            y2 += a0 * (x11 - x21);
+            y3 += a1 * (x11 + x21);
+        }
+
+        y[2 * i] = 2.0 * y0;
+        y[2 * i + 1] = 2.0 * y1;
+        y[2 * i + 2] = 2.0 * y2;
+        y[2 * i + 3] = 2.0 * y3;
+    }
+
+    for i in 0..m2 {
+        mem1[2 * i + 1] = xx1[i];
+        mem2[2 * i + 1] = xx2[i];
+    }
+}
+
+const SHIFT_FILT: [[f32; 7]; 3] = [
+    [
+        -0.011915, 0.046995, -0.152373, 0.614108, 0.614108, -0.152373, 0.046995,
+    ],
+    [
+        -0.0324855, 0.0859768, -0.2042986, 0.9640297, 0.208642, -0.0302054, -0.0063646,
+    ],
+    [
+        -0.0063646, -0.0302054, 0.208642, 0.9640297, -0.2042986, 0.0859768, -0.0324855,
+    ],
+];
+
+/// Find the fractional pitch that best matches the excitation starting at
+/// `exc[start]`, writing the interpolated past excitation into `interp`.
+fn interp_pitch(exc: &[f32], start: usize, interp: &mut [f32], pitch: i32, len: usize) {
+    let at = |offset: i32| exc[(start as i32 + offset) as usize];
+
+    let mut corr = [[0.0f32; 7]; 4];
+    for (i, corr) in corr[0].iter_mut().enumerate() {
+        let lag = start as i32 - pitch - 3 + i as i32;
+        *corr = inner_prod(
+            &exc[start..start + len],
+            &exc[lag as usize..lag as usize + len],
+        );
+    }
+    let lags = corr[0];
+    for (filt, corr) in SHIFT_FILT.iter().zip(&mut corr[1..]) {
+        for (j, corr) in corr.iter_mut().enumerate() {
+            let i1 = 3usize.saturating_sub(j);
+            let i2 = (10 - j).min(7);
+            let mut tmp = 0.0f32;
+            for k in i1..i2 {
+                tmp += filt[k] * lags[j + k - 3];
+            }
+            *corr = tmp;
+        }
+    }
+
+    let (mut maxi, mut maxj) = (0, 0);
+    let mut maxcorr = corr[0][0];
+    for (i, corr) in corr.iter().enumerate() {
+        for (j, &corr) in corr.iter().enumerate() {
+            if corr > maxcorr {
+                maxcorr = corr;
+                maxi = i;
+                maxj = j;
+            }
+        }
+    }
+
+    let lag = pitch - maxj as i32 + 3;
+    for (i, interp) in interp[..len].iter_mut().enumerate() {
+        let i = i as i32;
+        *interp = if maxi > 0 {
+            let mut tmp = 0.0f32;
+            for (k, &filt) in SHIFT_FILT[maxi - 1].iter().enumerate() {
+                tmp += at(i - lag + k as i32 - 3) * filt;
+            }
+            tmp
+        } else {
+            at(i - lag)
+        };
+    }
+}
+
+/// The decoder's perceptual enhancer: a comb filter around the pitch period
+/// which reduces the noise between pitch harmonics.
+///
+/// `exc` is the excitation history, the subframe to enhance starting at
+/// `exc[start]`.
+pub fn multicomb(
+    exc: &[f32],
+    start: usize,
+    new_exc: &mut [f32],
+    nsf: usize,
+    pitch: i32,
+    max_pitch: i32,
+    comb_gain: f32,
+) {
+    let mut iexc = vec![0.0f32; 2 * nsf];
+    let corr_pitch = pitch;
+
+    interp_pitch(exc, start, &mut iexc, corr_pitch, 80);
+    if corr_pitch > max_pitch {
+        interp_pitch(exc, start, &mut iexc[nsf..], 2 * corr_pitch, 80);
+    } else {
+        interp_pitch(exc, start, &mut iexc[nsf..], -corr_pitch, 80);
+    }
+
+    let cur = &exc[start..start + nsf];
+    let iexc0_mag = (1000.0 + inner_prod(&iexc[..nsf], &iexc[..nsf])).sqrt();
+    let iexc1_mag = (1000.0 + inner_prod(&iexc[nsf..], &iexc[nsf..])).sqrt();
+    let exc_mag = (1.0 + inner_prod(cur, cur)).sqrt();
+    let corr0 = inner_prod(&iexc[..nsf], cur).max(0.0);
+    let corr1 = inner_prod(&iexc[nsf..], cur).max(0.0);
+
+    let pgain1 = if corr0 > iexc0_mag * exc_mag {
+        1.0
+    } else {
+        (corr0 / exc_mag) / iexc0_mag
+    };
+    let pgain2 = if corr1 > iexc1_mag * exc_mag {
+        1.0
+    } else {
+        (corr1 / exc_mag) / iexc1_mag
+    };
+    let gg1 = exc_mag / iexc0_mag;
+    let gg2 = exc_mag / iexc1_mag;
+
+    let (c1, c2) = if comb_gain > 0.0 {
+        let c1 = (0.4 * f64::from(comb_gain) + 0.07) as f32;
+        let c2 = (0.5 + 1.72 * (f64::from(c1) - 0.07)) as f32;
+        (c1, c2)
+    } else {
+        (0.0, 0.0)
+    };
+
+    let g1 = (1.0 - c2 * pgain1 * pgain1).max(c1);
+    let g2 = (1.0 - c2 * pgain2 * pgain2).max(c1);
+    let g1 = c1 / g1;
+    let g2 = c1 / g2;
+
+    let (gain0, gain1) = if corr_pitch > max_pitch {
+        (0.7, 0.3)
+    } else {
+        (0.6, 0.6)
+    };
+    let gain0 = (gain0 * f64::from(g1 * gg1)) as f32;
+    let gain1 = (gain1 * f64::from(g2 * gg2)) as f32;
+    for i in 0..nsf {
+        new_exc[i] = cur[i] + (gain0 * iexc[i] + gain1 * iexc[i + nsf]);
+    }
+
+    let new_ener = compute_rms(&new_exc[..nsf]).max(1.0);
+    let old_ener = compute_rms(cur).max(1.0).min(new_ener);
+    let ngain = old_ener / new_ener;
+
+    for new_exc in &mut new_exc[..nsf] {
+        *new_exc *= ngain;
+    }
+}
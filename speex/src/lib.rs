@@ -0,0 +1,31 @@
+//! # speex-rs
+//!
+//! A pure Rust decoder for the Speex speech codec, as used by Flash Player
+//! for microphone audio and in SWF and FLV files.
+//!
+//! This is a port of the floating-point decoder of the reference
+//! implementation, libspeex, supporting the narrowband and wideband modes.
+//! Packet loss concealment and the ultra-wideband mode are not supported.
+//!
+//! As a derivative of libspeex, this crate is distributed under the
+//! three-clause BSD licence of libspeex, rather than the licence of the rest
+//! of Ruffle. See `COPYING` for the licence text. libspeex is Copyright:
+//!
+//! * 2002-2008 Xiph.org Foundation
+//! * 2002-2008 Jean-Marc Valin
+//! * 2005-2007 Analog Devices Inc.
+//! * 2005-2008 Commonwealth Scientific and Industrial Research Organisation (CSIRO)
+//! * 1993, 2002, 2006 David Rowe
+//! * 2003 EpicGames
+//! * 1992-1994 Jutta Degener, Carsten Bormann
+
+mod bits;
+mod decoder;
+mod filters;
+mod lsp;
+mod modes;
+mod nb;
+mod tables;
+mod wb;
+
+pub use decoder::{Decoder, Mode};
@@ -0,0 +1,136 @@
+//! Line spectral pair decoding and conversion to LPC coefficients.
+
+use crate::bits::BitReader;
+use crate::modes::LspQuant;
+use crate::tables::*;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+fn add_codebook(lsp: &mut [f32], codebook: &[i8], id: u32, scale: f64) {
+    let entry = &codebook[id as usize * lsp.len()..][..lsp.len()];
+    for (lsp, &value) in lsp.iter_mut().zip(entry) {
+        *lsp = (f64::from(*lsp) + scale * f64::from(value)) as f32;
+    }
+}
+
+/// Decode the LSPs of a narrowband frame.
+pub fn unquant_nb(lsp: &mut [f32; 10], quant: LspQuant, bits: &mut BitReader) {
+    for (i, lsp) in lsp.iter_mut().enumerate() {
+        *lsp = (0.25 * i as f64 + 0.25) as f32;
+    }
+
+    add_codebook(lsp, &CDBK_NB, bits.read(6), 0.0039062);
+    match quant {
+        LspQuant::Nb => {
+            add_codebook(&mut lsp[..5], &CDBK_NB_LOW1, bits.read(6), 0.0019531);
+            add_codebook(&mut lsp[..5], &CDBK_NB_LOW2, bits.read(6), 0.00097656);
+            add_codebook(&mut lsp[5..], &CDBK_NB_HIGH1, bits.read(6), 0.0019531);
+            add_codebook(&mut lsp[5..], &CDBK_NB_HIGH2, bits.read(6), 0.00097656);
+        }
+        LspQuant::Lbr => {
+            add_codebook(&mut lsp[..5], &CDBK_NB_LOW1, bits.read(6), 0.0019531);
+            add_codebook(&mut lsp[5..], &CDBK_NB_HIGH1, bits.read(6), 0.0019531);
+        }
+    }
+}
+
+/// Decode the LSPs of the high band of a wideband frame.
+pub fn unquant_high(lsp: &mut [f32; 8], bits: &mut BitReader) {
+    for (i, lsp) in lsp.iter_mut().enumerate() {
+        *lsp = (0.3125 * i as f64 + 0.75) as f32;
+    }
+
+    add_codebook(lsp, &HIGH_LSP_CDBK, bits.read(6), 0.0039062);
+    add_codebook(lsp, &HIGH_LSP_CDBK2, bits.read(6), 0.0019531);
+}
+
+/// Interpolate between the LSPs of the previous and current frames for the
+/// given subframe, keeping them at least `margin` apart so that the
+/// resulting filter is stable.
+pub fn interpolate(
+    old_lsp: &[f32],
+    new_lsp: &[f32],
+    lsp: &mut [f32],
+    subframe: usize,
+    num_subframes: usize,
+    margin: f32,
+) {
+    let len = lsp.len();
+    let tmp = (1.0 + subframe as f32) / num_subframes as f32;
+    for i in 0..len {
+        lsp[i] = (1.0 - tmp) * old_lsp[i] + tmp * new_lsp[i];
+    }
+
+    if lsp[0] < margin {
+        lsp[0] = margin;
+    }
+    let max = PI - f64::from(margin);
+    if f64::from(lsp[len - 1]) > max {
+        lsp[len - 1] = max as f32;
+    }
+    for i in 1..len - 1 {
+        if lsp[i] < lsp[i - 1] + margin {
+            lsp[i] = lsp[i - 1] + margin;
+        }
+        if lsp[i] > lsp[i + 1] - margin {
+            lsp[i] = 0.5 * (lsp[i] + lsp[i + 1] - margin);
+        }
+    }
+}
+
+/// Polynomial approximation of the cosine, matching the reference decoder.
+fn cos(x: f32) -> f32 {
+    const C1: f32 = 0.999_993_3;
+    const C2: f32 = -0.499_912_44;
+    const C3: f32 = 0.041_487_746;
+    const C4: f32 = -0.0012712095;
+
+    if f64::from(x) < FRAC_PI_2 {
+        let x = x * x;
+        C1 + x * (C2 + x * (C3 + C4 * x))
+    } else {
+        let x = (PI - f64::from(x)) as f32;
+        let x = x * x;
+        -(C1 + x * (C2 + x * (C3 + C4 * x)))
+    }
+}
+
+/// Convert LSPs (as angular frequencies) into LPC coefficients.
+pub fn to_lpc(freq: &[f32], ak: &mut [f32]) {
+    let order = freq.len();
+    let m = order / 2;
+    let mut wp = [0.0f32; 4 * 5 + 2];
+    let mut x_freq = [0.0f32; 10];
+    for (x, &freq) in x_freq.iter_mut().zip(freq) {
+        *x = cos(freq);
+    }
+
+    let mut xin1 = 1.0f32;
+    let mut xin2 = 1.0f32;
+
+    // Reconstruct P(z) and Q(z) by cascading second order polynomials in
+    // the form 1 - 2xz^-1 + z^-2, where x is the LSP coefficient.
+    for j in 0..=order {
+        for i in 0..m {
+            let n = i * 4;
+            let xout1 = xin1 - 2.0 * x_freq[2 * i] * wp[n] + wp[n + 1];
+            let xout2 = xin2 - 2.0 * x_freq[2 * i + 1] * wp[n + 2] + wp[n + 3];
+            wp[n + 1] = wp[n];
+            wp[n + 3] = wp[n + 2];
+            wp[n] = xin1;
+            wp[n + 2] = xin2;
+            xin1 = xout1;
+            xin2 = xout2;
+        }
+        let n = m * 4;
+        let xout1 = xin1 + wp[n];
+        let xout2 = xin2 - wp[n + 1];
+        if j > 0 {
+            ak[j - 1] = (xout1 + xout2) * 0.5;
+        }
+        wp[n] = xin1;
+        wp[n + 1] = xin2;
+
+        xin1 = 0.0;
+        xin2 = 0.0;
+    }
+}
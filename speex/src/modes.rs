@@ -0,0 +1,305 @@
+//! The parameters of each narrowband and wideband submode.
+
+use crate::bits::BitReader;
+use crate::tables::*;
+
+/// How the LSPs of a frame are quantized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LspQuant {
+    /// Five stages, used by the higher bit-rate narrowband submodes.
+    Nb,
+
+    /// Three stages, used by the lower bit-rate narrowband submodes.
+    Lbr,
+}
+
+/// Parameters of the 3-tap long-term predictor.
+pub struct LtpParams {
+    pub gain_cdbk: &'static [i8],
+    pub gain_bits: u32,
+    pub pitch_bits: u32,
+}
+
+/// How the adaptive codebook (pitch) contribution is coded.
+pub enum Ltp {
+    /// A single pitch gain for the whole frame.
+    Forced,
+
+    /// A pitch and 3-tap gain for each subframe.
+    ThreeTap(LtpParams),
+}
+
+/// Parameters of a split vector quantization codebook.
+pub struct SplitCbParams {
+    pub subvect_size: usize,
+    pub nb_subvect: usize,
+    pub shape_cb: &'static [i8],
+    pub shape_bits: u32,
+    pub have_sign: bool,
+}
+
+impl SplitCbParams {
+    /// Decode a codebook entry for each subvector, adding them to `exc`.
+    pub fn unquant(&self, exc: &mut [f32], bits: &mut BitReader) {
+        let mut entries = [(false, 0); 8];
+        let entries = &mut entries[..self.nb_subvect];
+        for (sign, index) in entries.iter_mut() {
+            *sign = self.have_sign && bits.read(1) != 0;
+            *index = bits.read(self.shape_bits) as usize;
+        }
+
+        let size = self.subvect_size;
+        for (i, &(sign, index)) in entries.iter().enumerate() {
+            let s = if sign { -1.0 } else { 1.0 };
+            let entry = &self.shape_cb[index * size..][..size];
+            for (exc, &value) in exc[i * size..][..size].iter_mut().zip(entry) {
+                *exc = (f64::from(*exc) + s * 0.03125 * f64::from(value)) as f32;
+            }
+        }
+    }
+}
+
+/// How the fixed codebook (innovation) contribution is coded.
+pub enum Innovation {
+    /// Random noise, without any bits in the stream.
+    Noise,
+
+    SplitCb(SplitCbParams),
+}
+
+pub struct NbSubmode {
+    /// The allowed pitch variation around the open-loop pitch, or `None` if
+    /// the pitch is coded independently for each subframe.
+    pub lbr_pitch: Option<i32>,
+    pub forced_pitch_gain: bool,
+    /// The number of bits of the subframe gain correction: 0, 1 or 3.
+    pub have_subframe_gain: u32,
+    pub double_codebook: bool,
+    pub lsp: LspQuant,
+    pub ltp: Ltp,
+    pub innovation: Innovation,
+    pub comb_gain: f32,
+}
+
+pub struct SbSubmode {
+    pub double_codebook: bool,
+    /// The high-band innovation codebook, or `None` if the high band is
+    /// folded from the low-band excitation.
+    pub innovation: Option<SplitCbParams>,
+}
+
+const LTP_PARAMS_NB: LtpParams = LtpParams {
+    gain_cdbk: &GAIN_CDBK_NB,
+    gain_bits: 7,
+    pitch_bits: 7,
+};
+
+const LTP_PARAMS_VLBR: LtpParams = LtpParams {
+    gain_cdbk: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 0,
+};
+
+const LTP_PARAMS_LBR: LtpParams = LtpParams {
+    gain_cdbk: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 7,
+};
+
+const SPLIT_CB_NB_VLBR: SplitCbParams = SplitCbParams {
+    subvect_size: 10,
+    nb_subvect: 4,
+    shape_cb: &EXC_10_16,
+    shape_bits: 4,
+    have_sign: false,
+};
+
+const SPLIT_CB_NB_ULBR: SplitCbParams = SplitCbParams {
+    subvect_size: 20,
+    nb_subvect: 2,
+    shape_cb: &EXC_20_32,
+    shape_bits: 5,
+    have_sign: false,
+};
+
+const SPLIT_CB_NB_LBR: SplitCbParams = SplitCbParams {
+    subvect_size: 10,
+    nb_subvect: 4,
+    shape_cb: &EXC_10_32,
+    shape_bits: 5,
+    have_sign: false,
+};
+
+const SPLIT_CB_NB: SplitCbParams = SplitCbParams {
+    subvect_size: 5,
+    nb_subvect: 8,
+    shape_cb: &EXC_5_64,
+    shape_bits: 6,
+    have_sign: false,
+};
+
+const SPLIT_CB_NB_MED: SplitCbParams = SplitCbParams {
+    subvect_size: 8,
+    nb_subvect: 5,
+    shape_cb: &EXC_8_128,
+    shape_bits: 7,
+    have_sign: false,
+};
+
+const SPLIT_CB_SB: SplitCbParams = SplitCbParams {
+    subvect_size: 5,
+    nb_subvect: 8,
+    shape_cb: &EXC_5_256,
+    shape_bits: 8,
+    have_sign: false,
+};
+
+const SPLIT_CB_HIGH: SplitCbParams = SplitCbParams {
+    subvect_size: 8,
+    nb_subvect: 5,
+    shape_cb: &HEXC,
+    shape_bits: 7,
+    have_sign: true,
+};
+
+const SPLIT_CB_HIGH_LBR: SplitCbParams = SplitCbParams {
+    subvect_size: 10,
+    nb_subvect: 4,
+    shape_cb: &HEXC_10_32,
+    shape_bits: 5,
+    have_sign: false,
+};
+
+/// The narrowband submodes, indexed by their 4-bit ID.
+///
+/// Submode 0 transmits nothing and isn't listed here.
+pub static NB_SUBMODES: [NbSubmode; 8] = [
+    // 1: 2.15 kbps "vocoder-like" mode for comfort noise
+    NbSubmode {
+        lbr_pitch: Some(0),
+        forced_pitch_gain: true,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Ltp::Forced,
+        innovation: Innovation::Noise,
+        comb_gain: -1.0,
+    },
+    // 2: 5.95 kbps very low bit-rate mode
+    NbSubmode {
+        lbr_pitch: Some(0),
+        forced_pitch_gain: false,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Ltp::ThreeTap(LTP_PARAMS_VLBR),
+        innovation: Innovation::SplitCb(SPLIT_CB_NB_VLBR),
+        comb_gain: 0.6,
+    },
+    // 3: 8 kbps low bit-rate mode
+    NbSubmode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Ltp::ThreeTap(LTP_PARAMS_LBR),
+        innovation: Innovation::SplitCb(SPLIT_CB_NB_LBR),
+        comb_gain: 0.55,
+    },
+    // 4: 11 kbps medium bit-rate mode
+    NbSubmode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Ltp::ThreeTap(LTP_PARAMS_LBR),
+        innovation: Innovation::SplitCb(SPLIT_CB_NB_MED),
+        comb_gain: 0.45,
+    },
+    // 5: 15 kbps high bit-rate mode
+    NbSubmode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: false,
+        lsp: LspQuant::Nb,
+        ltp: Ltp::ThreeTap(LTP_PARAMS_NB),
+        innovation: Innovation::SplitCb(SPLIT_CB_NB),
+        comb_gain: 0.25,
+    },
+    // 6: 18.2 kbps high bit-rate mode
+    NbSubmode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: false,
+        lsp: LspQuant::Nb,
+        ltp: Ltp::ThreeTap(LTP_PARAMS_NB),
+        innovation: Innovation::SplitCb(SPLIT_CB_SB),
+        comb_gain: 0.15,
+    },
+    // 7: 24.6 kbps high bit-rate mode
+    NbSubmode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: true,
+        lsp: LspQuant::Nb,
+        ltp: Ltp::ThreeTap(LTP_PARAMS_NB),
+        innovation: Innovation::SplitCb(SPLIT_CB_NB),
+        comb_gain: 0.05,
+    },
+    // 8: 3.95 kbps very low bit-rate mode
+    NbSubmode {
+        lbr_pitch: Some(0),
+        forced_pitch_gain: true,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Ltp::Forced,
+        innovation: Innovation::SplitCb(SPLIT_CB_NB_ULBR),
+        comb_gain: 0.5,
+    },
+];
+
+/// The wideband (high-band) submodes, indexed by their 3-bit ID.
+///
+/// Submode 0 transmits nothing and isn't listed here.
+pub static SB_SUBMODES: [SbSubmode; 4] = [
+    // 1: 1.8 kbps, folded excitation
+    SbSubmode {
+        double_codebook: false,
+        innovation: None,
+    },
+    // 2: 5.6 kbps
+    SbSubmode {
+        double_codebook: false,
+        innovation: Some(SPLIT_CB_HIGH_LBR),
+    },
+    // 3: 9.6 kbps
+    SbSubmode {
+        double_codebook: false,
+        innovation: Some(SPLIT_CB_HIGH),
+    },
+    // 4: 17.6 kbps
+    SbSubmode {
+        double_codebook: true,
+        innovation: Some(SPLIT_CB_HIGH),
+    },
+];
+
+/// The size in bits of each wideband submode, including its 4-bit header.
+///
+/// Used by the narrowband decoder to skip over high-band layers.
+pub const SB_SUBMODE_BITS_PER_FRAME: [Option<usize>; 8] = [
+    Some(4),
+    Some(36),
+    Some(112),
+    Some(192),
+    Some(352),
+    None,
+    None,
+    None,
+];
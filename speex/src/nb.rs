@@ -0,0 +1,448 @@
+//! The narrowband decoder, producing 160 samples at 8 kHz per frame.
+
+use crate::bits::BitReader;
+use crate::filters::{self, Highpass};
+use crate::lsp;
+use crate::modes::{Innovation, Ltp, LtpParams, NB_SUBMODES, SB_SUBMODE_BITS_PER_FRAME};
+
+pub const FRAME_SIZE: usize = 160;
+pub const SUBFRAME_SIZE: usize = 40;
+pub const NUM_SUBFRAMES: usize = 4;
+const ORDER: usize = 10;
+const PITCH_START: i32 = 17;
+const PITCH_END: usize = 144;
+
+/// The excitation history needs to reach two pitch periods back for the
+/// enhancer, plus some room for its fractional pitch interpolation.
+const EXC_BUF_SIZE: usize = FRAME_SIZE + 2 * PITCH_END + SUBFRAME_SIZE + 12;
+const EXC_OFFSET: usize = 2 * PITCH_END + SUBFRAME_SIZE + 6;
+
+const EXC_GAIN_QUANT_SCAL3: [f32; 8] = [
+    0.061130, 0.163546, 0.310413, 0.428220, 0.555887, 0.719055, 0.938694, 1.326874,
+];
+const EXC_GAIN_QUANT_SCAL1: [f32; 2] = [0.70469, 1.05127];
+
+/// Why no frame could be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameError {
+    /// There are no frames left in the packet.
+    EndOfStream,
+
+    /// The data isn't a valid Speex stream.
+    Corrupt,
+}
+
+pub struct NbDecoder {
+    exc_buf: [f32; EXC_BUF_SIZE],
+    old_qlsp: [f32; ORDER],
+    interp_qlpc: [f32; ORDER],
+    mem_sp: [f32; ORDER],
+    mem_hp: [f32; 2],
+    pi_gain: [f32; NUM_SUBFRAMES],
+    innov: [f32; FRAME_SIZE],
+    first: bool,
+    seed: u32,
+    voc_m1: f32,
+    voc_m2: f32,
+    voc_mean: f32,
+    voc_offset: i32,
+    dtx_enabled: bool,
+    highpass: Highpass,
+}
+
+impl NbDecoder {
+    /// Creates a decoder. `highpass` selects the output filter, which
+    /// depends on whether this decodes the low band of a wideband stream.
+    pub fn new(highpass: Highpass) -> Self {
+        Self {
+            exc_buf: [0.0; EXC_BUF_SIZE],
+            old_qlsp: [0.0; ORDER],
+            interp_qlpc: [0.0; ORDER],
+            mem_sp: [0.0; ORDER],
+            mem_hp: [0.0; 2],
+            pi_gain: [0.0; NUM_SUBFRAMES],
+            innov: [0.0; FRAME_SIZE],
+            first: true,
+            seed: 1000,
+            voc_m1: 0.0,
+            voc_m2: 0.0,
+            voc_mean: 0.0,
+            voc_offset: 0,
+            dtx_enabled: false,
+            highpass,
+        }
+    }
+
+    /// The gain of the synthesis filter at 4 kHz for each subframe of the
+    /// last frame.
+    pub fn pi_gain(&self) -> &[f32; NUM_SUBFRAMES] {
+        &self.pi_gain
+    }
+
+    /// The RMS of the excitation of the given subframe of the last frame.
+    pub fn exc_rms(&self, subframe: usize) -> f32 {
+        let start = EXC_OFFSET + subframe * SUBFRAME_SIZE;
+        filters::compute_rms(&self.exc_buf[start..start + SUBFRAME_SIZE])
+    }
+
+    /// The innovation (fixed codebook excitation) of the last frame.
+    pub fn innovation(&self) -> &[f32; FRAME_SIZE] {
+        &self.innov
+    }
+
+    /// Whether the encoder is in discontinuous transmission mode, sending
+    /// only comfort noise.
+    pub fn dtx_enabled(&self) -> bool {
+        self.dtx_enabled
+    }
+
+    /// Reads the ID of the next narrowband frame, skipping over any
+    /// wideband layers and in-band requests in front of it.
+    fn read_submode(bits: &mut BitReader) -> Result<usize, FrameError> {
+        loop {
+            if bits.remaining() < 5 {
+                return Err(FrameError::EndOfStream);
+            }
+
+            // A narrowband decoder ignores up to two wideband layers.
+            for layer in 0..3 {
+                if bits.read(1) == 0 {
+                    break;
+                }
+                if layer == 2 {
+                    return Err(FrameError::Corrupt);
+                }
+                match SB_SUBMODE_BITS_PER_FRAME[bits.read(3) as usize] {
+                    Some(size) => bits.skip(size - 4),
+                    None => return Err(FrameError::Corrupt),
+                }
+                if bits.remaining() < 5 {
+                    return Err(FrameError::EndOfStream);
+                }
+            }
+
+            match bits.read(4) {
+                // A terminator, which pads the rest of the packet.
+                15 => return Err(FrameError::EndOfStream),
+                // A Speex in-band request, which we ignore.
+                14 => {
+                    let size = match bits.read(4) {
+                        0..=1 => 1,
+                        2..=7 => 4,
+                        8..=9 => 8,
+                        10..=11 => 16,
+                        12..=13 => 32,
+                        _ => 64,
+                    };
+                    bits.skip(size);
+                }
+                // A user in-band request, which we ignore.
+                13 => {
+                    let size = bits.read(4) as usize;
+                    bits.skip(5 + 8 * size);
+                }
+                9..=12 => return Err(FrameError::Corrupt),
+                id => return Ok(id as usize),
+            }
+        }
+    }
+
+    /// Decodes a frame into the first `FRAME_SIZE` samples of `out`.
+    pub fn decode(&mut self, bits: &mut BitReader, out: &mut [f32]) -> Result<(), FrameError> {
+        let submode_id = Self::read_submode(bits)?;
+        let out = &mut out[..FRAME_SIZE];
+
+        self.exc_buf.copy_within(FRAME_SIZE.., 0);
+
+        let submode = match submode_id.checked_sub(1) {
+            Some(index) => &NB_SUBMODES[index],
+            None => {
+                self.decode_null(out);
+                return Ok(());
+            }
+        };
+
+        let mut qlsp = [0.0; ORDER];
+        lsp::unquant_nb(&mut qlsp, submode.lsp, bits);
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        let ol_pitch = match submode.lbr_pitch {
+            Some(_) => PITCH_START + bits.read(7) as i32,
+            None => 0,
+        };
+        let ol_pitch_coef = if submode.forced_pitch_gain {
+            (0.066667 * f64::from(bits.read(4))) as f32
+        } else {
+            0.0
+        };
+        let ol_gain = (f64::from(bits.read(5)) / 3.5).exp() as f32;
+
+        if submode_id == 1 {
+            self.dtx_enabled = bits.read(4) == 15;
+        } else {
+            self.dtx_enabled = false;
+        }
+
+        let mut best_pitch = 40;
+        let mut best_pitch_gain = 0.0f32;
+
+        for sub in 0..NUM_SUBFRAMES {
+            let offset = sub * SUBFRAME_SIZE;
+            let start = EXC_OFFSET + offset;
+            self.exc_buf[start..start + SUBFRAME_SIZE].fill(0.0);
+
+            // Adaptive codebook contribution.
+            let pit_min = match submode.lbr_pitch {
+                Some(0) => ol_pitch,
+                Some(margin) => (ol_pitch - margin + 1).max(PITCH_START),
+                None => PITCH_START,
+            };
+            let mut exc32 = [0.0; SUBFRAME_SIZE];
+            let (pitch, pitch_gain) = match &submode.ltp {
+                Ltp::Forced => forced_pitch_unquant(
+                    &mut self.exc_buf,
+                    start,
+                    &mut exc32,
+                    pit_min,
+                    ol_pitch_coef,
+                ),
+                Ltp::ThreeTap(params) => {
+                    pitch_unquant_3tap(&self.exc_buf, start, &mut exc32, pit_min, params, bits)
+                }
+            };
+            filters::sanitize_values(&mut exc32, -32000.0, 32000.0);
+
+            let tmp = gain_3tap_to_1tap(pitch_gain);
+            let is_multiple_of_best = (2 * best_pitch - pitch).abs() < 3
+                || (3 * best_pitch - pitch).abs() < 4
+                || (4 * best_pitch - pitch).abs() < 5;
+            let best_is_multiple = (best_pitch - 2 * pitch).abs() < 3
+                || (best_pitch - 3 * pitch).abs() < 4
+                || (best_pitch - 4 * pitch).abs() < 5;
+            if (tmp > best_pitch_gain && !is_multiple_of_best)
+                || (f64::from(tmp) > 0.6 * f64::from(best_pitch_gain) && best_is_multiple)
+                || (0.67 * f64::from(tmp) > f64::from(best_pitch_gain) && is_multiple_of_best)
+            {
+                best_pitch = pitch;
+                if tmp > best_pitch_gain {
+                    best_pitch_gain = tmp;
+                }
+            }
+
+            // Fixed codebook contribution.
+            let ener = match submode.have_subframe_gain {
+                3 => EXC_GAIN_QUANT_SCAL3[bits.read(3) as usize] * ol_gain,
+                1 => EXC_GAIN_QUANT_SCAL1[bits.read(1) as usize] * ol_gain,
+                _ => ol_gain,
+            };
+
+            let mut innov = [0.0; SUBFRAME_SIZE];
+            unquant_innovation(&submode.innovation, &mut innov, bits, &mut self.seed);
+            for innov in &mut innov {
+                *innov *= ener;
+            }
+
+            if submode.double_codebook {
+                let mut innov2 = [0.0; SUBFRAME_SIZE];
+                unquant_innovation(&submode.innovation, &mut innov2, bits, &mut self.seed);
+                let scale = 0.454545 * ener;
+                for (innov, innov2) in innov.iter_mut().zip(&innov2) {
+                    *innov += scale * innov2;
+                }
+            }
+
+            let exc = &mut self.exc_buf[start..start + SUBFRAME_SIZE];
+            for ((exc, exc32), innov) in exc.iter_mut().zip(&exc32).zip(&innov) {
+                *exc = exc32 + innov;
+            }
+            self.innov[offset..offset + SUBFRAME_SIZE].copy_from_slice(&innov);
+
+            if submode_id == 1 {
+                // Vocoder mode: a pulse train at the pitch period mixed with
+                // noise, depending on how voiced the frame is.
+                let g = (1.5 * (ol_pitch_coef - 0.2)).clamp(0.0, 1.0);
+
+                exc.fill(0.0);
+                while self.voc_offset < SUBFRAME_SIZE as i32 {
+                    if self.voc_offset >= 0 {
+                        let pulse = f64::from(2 * ol_pitch).sqrt() as f32;
+                        exc[self.voc_offset as usize] = pulse * (g * ol_gain);
+                    }
+                    self.voc_offset += ol_pitch;
+                }
+                self.voc_offset -= SUBFRAME_SIZE as i32;
+
+                for (exc, &innov) in exc.iter_mut().zip(&innov) {
+                    let exci = *exc;
+                    *exc = (0.7 * exci + 0.3 * self.voc_m1)
+                        + ((1.0 - 0.85 * g) * innov - 0.15 * g * self.voc_m2);
+                    self.voc_m1 = exci;
+                    self.voc_m2 = innov;
+                    self.voc_mean = 0.8 * self.voc_mean + 0.2 * *exc;
+                    *exc -= self.voc_mean;
+                }
+            }
+        }
+
+        if submode.comb_gain > 0.0 {
+            let half = 2 * SUBFRAME_SIZE;
+            filters::multicomb(
+                &self.exc_buf,
+                EXC_OFFSET - SUBFRAME_SIZE,
+                &mut out[..half],
+                half,
+                best_pitch,
+                40,
+                submode.comb_gain,
+            );
+            filters::multicomb(
+                &self.exc_buf,
+                EXC_OFFSET + SUBFRAME_SIZE,
+                &mut out[half..],
+                half,
+                best_pitch,
+                40,
+                submode.comb_gain,
+            );
+        } else {
+            let start = EXC_OFFSET - SUBFRAME_SIZE;
+            out.copy_from_slice(&self.exc_buf[start..start + FRAME_SIZE]);
+        }
+
+        let mut interp_qlsp = [0.0; ORDER];
+        let mut ak = [0.0; ORDER];
+        for (sub, sp) in out.chunks_exact_mut(SUBFRAME_SIZE).enumerate() {
+            lsp::interpolate(
+                &self.old_qlsp,
+                &qlsp,
+                &mut interp_qlsp,
+                sub,
+                NUM_SUBFRAMES,
+                0.002,
+            );
+            lsp::to_lpc(&interp_qlsp, &mut ak);
+
+            let mut pi_gain = 1.0;
+            for ak in ak.chunks_exact(2) {
+                pi_gain += ak[1] - ak[0];
+            }
+            self.pi_gain[sub] = pi_gain;
+
+            // The filter lags behind by a subframe, matching the enhancer's
+            // output.
+            filters::iir_mem(sp, &self.interp_qlpc, &mut self.mem_sp);
+            self.interp_qlpc = ak;
+        }
+
+        filters::highpass(out, self.highpass, &mut self.mem_hp);
+
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Ok(())
+    }
+
+    /// Decodes a frame without any data, continuing the previous signal
+    /// with noise.
+    fn decode_null(&mut self, out: &mut [f32]) {
+        let mut lpc = [0.0; ORDER];
+        filters::bw_lpc(0.93, &self.interp_qlpc, &mut lpc);
+
+        let exc = &mut self.exc_buf[EXC_OFFSET..EXC_OFFSET + FRAME_SIZE];
+        let innov_gain = filters::compute_rms(exc);
+        for exc in exc.iter_mut() {
+            *exc = filters::rand(innov_gain, &mut self.seed);
+        }
+        self.innov.fill(0.0);
+        self.first = true;
+
+        out.copy_from_slice(exc);
+        filters::iir_mem(out, &lpc, &mut self.mem_sp);
+    }
+}
+
+fn gain_3tap_to_1tap(g: [f32; 3]) -> f32 {
+    let side = |g: f32| {
+        if g > 0.0 {
+            f64::from(g)
+        } else {
+            -0.5 * f64::from(g)
+        }
+    };
+    (f64::from(g[1].abs()) + side(g[0]) + side(g[2])) as f32
+}
+
+/// Decodes the pitch and 3-tap gain of a subframe, writing the resulting
+/// adaptive codebook excitation into `exc_out`.
+fn pitch_unquant_3tap(
+    exc: &[f32],
+    start: usize,
+    exc_out: &mut [f32],
+    pit_min: i32,
+    params: &LtpParams,
+    bits: &mut BitReader,
+) -> (i32, [f32; 3]) {
+    let pitch = bits.read(params.pitch_bits) as i32 + pit_min;
+    let gain_index = bits.read(params.gain_bits) as usize;
+    let entry = &params.gain_cdbk[gain_index * 4..][..3];
+    let mut gain = [0.0; 3];
+    for (gain, &value) in gain.iter_mut().zip(entry) {
+        *gain = (0.015625 * f64::from(value) + 0.5) as f32;
+    }
+
+    let nsf = exc_out.len() as i32;
+    let at = |offset: i32| exc[(start as i32 + offset) as usize];
+    exc_out.fill(0.0);
+    for i in 0..3 {
+        let pp = pitch + 1 - i;
+        let tmp1 = nsf.min(pp);
+        for j in 0..tmp1 {
+            exc_out[j as usize] += gain[2 - i as usize] * at(j - pp);
+        }
+        let tmp3 = nsf.min(pp + pitch);
+        for j in tmp1..tmp3 {
+            exc_out[j as usize] += gain[2 - i as usize] * at(j - pp - pitch);
+        }
+    }
+
+    (pitch, gain)
+}
+
+/// Repeats the excitation from one pitch period ago with a single gain,
+/// which is applied to the excitation history as well.
+fn forced_pitch_unquant(
+    exc: &mut [f32],
+    start: usize,
+    exc_out: &mut [f32],
+    pitch: i32,
+    pitch_coef: f32,
+) -> (i32, [f32; 3]) {
+    let pitch_coef = if f64::from(pitch_coef) > 0.99 {
+        0.99
+    } else {
+        pitch_coef
+    };
+    for (i, exc_out) in exc_out.iter_mut().enumerate() {
+        let i = start + i;
+        *exc_out = exc[i - pitch as usize] * pitch_coef;
+        exc[i] = *exc_out;
+    }
+    (pitch, [0.0, pitch_coef, 0.0])
+}
+
+fn unquant_innovation(
+    innovation: &Innovation,
+    exc: &mut [f32],
+    bits: &mut BitReader,
+    seed: &mut u32,
+) {
+    match innovation {
+        Innovation::Noise => {
+            for exc in exc {
+                *exc = filters::rand(1.0, seed);
+            }
+        }
+        Innovation::SplitCb(params) => params.unquant(exc, bits),
+    }
+}
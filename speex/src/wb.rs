@@ -0,0 +1,209 @@
+//! The wideband decoder, producing 320 samples at 16 kHz per frame.
+//!
+//! A wideband frame is a narrowband frame for the 0-4 kHz band followed by
+//! a layer describing the 4-8 kHz band, the two being merged by a QMF
+//! filter bank.
+
+use crate::bits::BitReader;
+use crate::filters::{self, Highpass};
+use crate::lsp;
+use crate::modes::SB_SUBMODES;
+use crate::nb::{self, FrameError, NbDecoder};
+
+pub const FRAME_SIZE: usize = 2 * nb::FRAME_SIZE;
+const SUBFRAME_SIZE: usize = nb::SUBFRAME_SIZE;
+const NUM_SUBFRAMES: usize = nb::NUM_SUBFRAMES;
+const ORDER: usize = 8;
+const QMF_ORDER: usize = 64;
+
+/// The gain applied to the low-band innovation when it's folded into the
+/// high band.
+const FOLDING_GAIN: f32 = 0.9;
+
+#[rustfmt::skip]
+const H0: [f32; QMF_ORDER] = [
+    3.596189e-05, -0.0001123515, -0.0001104587, 0.0002790277,
+    0.0002298438, -0.0005953563, -0.0003823631, 0.00113826,
+    0.0005308539, -0.001986177, -0.0006243724, 0.003235877,
+    0.0005743159, -0.004989147, -0.0002584767, 0.007367171,
+    -0.0004857935, -0.01050689, 0.001894714, 0.01459396,
+    -0.004313674, -0.01994365, 0.00828756, 0.02716055,
+    -0.01485397, -0.03764973, 0.026447, 0.05543245,
+    -0.05095487, -0.09779096, 0.1382363, 0.4600981,
+    0.4600981, 0.1382363, -0.09779096, -0.05095487,
+    0.05543245, 0.026447, -0.03764973, -0.01485397,
+    0.02716055, 0.00828756, -0.01994365, -0.004313674,
+    0.01459396, 0.001894714, -0.01050689, -0.0004857935,
+    0.007367171, -0.0002584767, -0.004989147, 0.0005743159,
+    0.003235877, -0.0006243724, -0.001986177, 0.0005308539,
+    0.00113826, -0.0003823631, -0.0005953563, 0.0002298438,
+    0.0002790277, -0.0001104587, -0.0001123515, 3.596189e-05,
+];
+
+#[rustfmt::skip]
+const GC_QUANT_BOUND: [f32; 16] = [
+    0.97979, 1.28384, 1.68223, 2.20426, 2.88829, 3.78458, 4.95900, 6.49787,
+    8.51428, 11.15642, 14.61846, 19.15484, 25.09895, 32.88761, 43.09325, 56.46588,
+];
+
+pub struct WbDecoder {
+    low: NbDecoder,
+    exc_buf: [f32; SUBFRAME_SIZE],
+    old_qlsp: [f32; ORDER],
+    interp_qlpc: [f32; ORDER],
+    mem_sp: [f32; ORDER],
+    g0_mem: [f32; QMF_ORDER],
+    g1_mem: [f32; QMF_ORDER],
+    last_ener: f32,
+    first: bool,
+    seed: u32,
+}
+
+impl WbDecoder {
+    pub fn new() -> Self {
+        Self {
+            low: NbDecoder::new(Highpass::Wideband),
+            exc_buf: [0.0; SUBFRAME_SIZE],
+            old_qlsp: [0.0; ORDER],
+            interp_qlpc: [0.0; ORDER],
+            mem_sp: [0.0; ORDER],
+            g0_mem: [0.0; QMF_ORDER],
+            g1_mem: [0.0; QMF_ORDER],
+            last_ener: 0.0,
+            first: true,
+            seed: 1000,
+        }
+    }
+
+    /// Decodes a frame into the first `FRAME_SIZE` samples of `out`.
+    pub fn decode(&mut self, bits: &mut BitReader, out: &mut [f32]) -> Result<(), FrameError> {
+        let out = &mut out[..FRAME_SIZE];
+        self.low.decode(bits, out)?;
+
+        let submode_id = if bits.remaining() > 0 && bits.peek() == 1 {
+            bits.skip(1);
+            bits.read(3) as usize
+        } else {
+            // A narrowband frame, without any high band.
+            0
+        };
+
+        let submode = match submode_id.checked_sub(1) {
+            Some(index) => match SB_SUBMODES.get(index) {
+                Some(submode) => submode,
+                None => return Err(FrameError::Corrupt),
+            },
+            None => {
+                if self.low.dtx_enabled() {
+                    self.decode_noise(out);
+                } else {
+                    let high = &mut out[nb::FRAME_SIZE..];
+                    high.fill(1e-15);
+                    self.first = true;
+                    filters::iir_mem(high, &self.interp_qlpc, &mut self.mem_sp);
+                    filters::qmf_synth(out, &H0, &mut self.g0_mem, &mut self.g1_mem);
+                }
+                return Ok(());
+            }
+        };
+
+        let low_pi_gain = *self.low.pi_gain();
+        let low_innov = self.low.innovation();
+
+        let mut qlsp = [0.0; ORDER];
+        lsp::unquant_high(&mut qlsp, bits);
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        let mut interp_qlsp = [0.0; ORDER];
+        let mut ak = [0.0; ORDER];
+        let mut exc_ener_sum = 0.0f32;
+        for (sub, &rl) in low_pi_gain.iter().enumerate() {
+            let offset = sub * SUBFRAME_SIZE;
+
+            lsp::interpolate(
+                &self.old_qlsp,
+                &qlsp,
+                &mut interp_qlsp,
+                sub,
+                NUM_SUBFRAMES,
+                0.05,
+            );
+            lsp::to_lpc(&interp_qlsp, &mut ak);
+
+            // The ratio between the responses of the low and high band
+            // filters at 4 kHz, where the bands meet.
+            let mut rh = 1.0f32;
+            for ak in ak.chunks_exact(2) {
+                rh += ak[1] - ak[0];
+            }
+            let filter_ratio = ((f64::from(rl) + 0.01) / (f64::from(rh) + 0.01)) as f32;
+
+            let mut exc = [0.0; SUBFRAME_SIZE];
+            match &submode.innovation {
+                None => {
+                    // Fold the low band's innovation into the high band.
+                    let quant = bits.read(5) as i32;
+                    let g = f64::from(0.125 * (quant - 10) as f32).exp() as f32;
+                    let g = g / filter_ratio;
+                    let low_innov = &low_innov[offset..offset + SUBFRAME_SIZE];
+                    for (i, (exc, &innov)) in exc.iter_mut().zip(low_innov).enumerate() {
+                        *exc = FOLDING_GAIN * innov * g;
+                        if i % 2 == 1 {
+                            *exc = -*exc;
+                        }
+                    }
+                }
+                Some(params) => {
+                    let qgc = bits.read(4) as usize;
+                    let el = self.low.exc_rms(sub);
+                    let gc = (0.87360 * f64::from(GC_QUANT_BOUND[qgc])) as f32;
+                    let scale = (gc * el) / filter_ratio;
+
+                    params.unquant(&mut exc, bits);
+                    for exc in &mut exc {
+                        *exc *= scale;
+                    }
+
+                    if submode.double_codebook {
+                        let mut innov2 = [0.0; SUBFRAME_SIZE];
+                        params.unquant(&mut innov2, bits);
+                        let scale = 0.4 * scale;
+                        for (exc, innov2) in exc.iter_mut().zip(&innov2) {
+                            *exc += scale * innov2;
+                        }
+                    }
+                }
+            }
+
+            // The filter lags behind by a subframe, like in the low band.
+            let sp = &mut out[nb::FRAME_SIZE + offset..][..SUBFRAME_SIZE];
+            sp.copy_from_slice(&self.exc_buf);
+            filters::iir_mem(sp, &self.interp_qlpc, &mut self.mem_sp);
+            self.exc_buf = exc;
+            self.interp_qlpc = ak;
+
+            let exc_rms = filters::compute_rms(&self.exc_buf);
+            exc_ener_sum += (exc_rms * exc_rms) / NUM_SUBFRAMES as f32;
+        }
+        self.last_ener = exc_ener_sum.sqrt();
+
+        filters::qmf_synth(out, &H0, &mut self.g0_mem, &mut self.g1_mem);
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Ok(())
+    }
+
+    /// Fills the high band with comfort noise, as sent by the encoder while
+    /// in discontinuous transmission mode.
+    fn decode_noise(&mut self, out: &mut [f32]) {
+        self.first = true;
+        let high = &mut out[nb::FRAME_SIZE..];
+        for sample in high.iter_mut() {
+            *sample = filters::rand(self.last_ener, &mut self.seed);
+        }
+        filters::iir_mem(high, &self.interp_qlpc, &mut self.mem_sp);
+        filters::qmf_synth(out, &H0, &mut self.g0_mem, &mut self.g1_mem);
+    }
+}
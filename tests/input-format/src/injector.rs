@@ -80,7 +80,11 @@ impl InputInjector {
 
                 match event {
                     AutomatedEvent::Wait => break,
-                    AutomatedEvent::MouseMove { .. } | AutomatedEvent::KeyDown { .. } => {}
+                    AutomatedEvent::MouseMove { .. }
+                    | AutomatedEvent::KeyDown { .. }
+                    | AutomatedEvent::KeyUp { .. }
+                    | AutomatedEvent::TextInput { .. }
+                    | AutomatedEvent::SetClipboardText { .. } => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
                         self.buttons |= (*btn).into();
                     }
@@ -0,0 +1,289 @@
+use crate::set_logger;
+use crate::util::environment::WGPU;
+use ruffle_core::swf::{
+    BevelFilter, BevelFilterFlags, BlurFilter, BlurFilterFlags, Color, ColorMatrixFilter,
+    ConvolutionFilter, ConvolutionFilterFlags, DropShadowFilter, DropShadowFilterFlags, Fixed16,
+    Fixed8, GlowFilter, GlowFilterFlags, GradientFilter, GradientFilterFlags, GradientRecord,
+};
+use ruffle_render::backend::null::NullRenderer;
+use ruffle_render::backend::{RenderBackend, ViewportDimensions};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
+use ruffle_render_wgpu::target::TextureTarget;
+
+const SIZE: u32 = 64;
+
+/// The largest difference allowed between the channels of the CPU and GPU results.
+/// The GPU blurs with linear sampling, so blurred edges can be off by a few steps.
+const TOLERANCE: u8 = 3;
+
+/// Applies every filter with both the CPU implementation (used by the null renderer)
+/// and the wgpu backend, and checks that both agree.
+/// This is ignored when no GPU is available.
+pub fn filters_cpu_matches_gpu() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    let descriptors = WGPU.clone().ok_or("No GPU is available")?;
+    let target = TextureTarget::new(&descriptors.device, (SIZE, SIZE))?;
+    let mut gpu = WgpuRenderBackend::new(descriptors, target)?;
+    let mut cpu = NullRenderer::new(ViewportDimensions {
+        width: SIZE,
+        height: SIZE,
+        scale_factor: 1.0,
+    });
+
+    let cpu_map = cpu.register_bitmap(map_bitmap())?;
+    let gpu_map = gpu.register_bitmap(map_bitmap())?;
+    for (name, filter) in filters() {
+        let expected = apply_filter(&mut cpu, with_map(filter.clone(), &cpu_map))?;
+        let actual = apply_filter(&mut gpu, with_map(filter, &gpu_map))?;
+        let difference = expected
+            .data()
+            .iter()
+            .zip(actual.data())
+            .map(|(expected, actual)| expected.abs_diff(*actual))
+            .max()
+            .unwrap_or_default();
+        if difference > TOLERANCE {
+            return Err(format!(
+                "{name}: the GPU result differs from the CPU result by up to {difference}"
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Applies `filter` to the middle of a translucent shape, and reads back the whole destination.
+fn apply_filter(
+    renderer: &mut dyn RenderBackend,
+    filter: Filter,
+) -> Result<Bitmap, libtest_mimic::Failed> {
+    let source = renderer.register_bitmap(source_bitmap())?;
+    let destination = renderer.register_bitmap(Bitmap::new(
+        SIZE,
+        SIZE,
+        BitmapFormat::Rgba,
+        vec![0; (SIZE * SIZE * 4) as usize],
+    ))?;
+    let sync = renderer
+        .apply_filter(source, (8, 8), (48, 48), destination, (8, 8), filter)
+        .ok_or("The filter wasn't applied")?;
+    Ok(sync.retrieve_offscreen_texture()?)
+}
+
+/// A blue square with a translucent orange bar through it, inside a magenta border
+/// that's outside of the filtered area.
+fn source_bitmap() -> Bitmap {
+    let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let pixel = if !(8..56).contains(&x) || !(8..56).contains(&y) {
+                [0xFF, 0x00, 0xFF, 0xFF]
+            } else if (28..36).contains(&x) {
+                [0x66, 0x33, 0x00, 0x80]
+            } else if (20..44).contains(&x) && (20..44).contains(&y) {
+                [0x33, 0x66, 0xCC, 0xFF]
+            } else {
+                [0; 4]
+            };
+            data.extend_from_slice(&pixel);
+        }
+    }
+    Bitmap::new(SIZE, SIZE, BitmapFormat::Rgba, data)
+}
+
+/// A map that moves the left half of the filtered area 4 pixels to the right,
+/// and the right half 4 pixels to the left, with a [`displacement_map`] filter.
+fn map_bitmap() -> Bitmap {
+    let mut data = Vec::with_capacity(48 * 48 * 4);
+    for _ in 0..48 {
+        for x in 0..48 {
+            let red = if x < 24 { 0xC0 } else { 0x40 };
+            data.extend_from_slice(&[red, 0x80, 0x80, 0xFF]);
+        }
+    }
+    Bitmap::new(48, 48, BitmapFormat::Rgba, data)
+}
+
+/// Gives a displacement map filter the map registered with a renderer.
+fn with_map(filter: Filter, map: &BitmapHandle) -> Filter {
+    match filter {
+        Filter::DisplacementMapFilter(filter) => {
+            Filter::DisplacementMapFilter(DisplacementMapFilter {
+                map_bitmap: Some(map.clone()),
+                ..filter
+            })
+        }
+        filter => filter,
+    }
+}
+
+fn displacement_map(mode: DisplacementMapFilterMode) -> Filter {
+    Filter::DisplacementMapFilter(DisplacementMapFilter {
+        color: Color::from_rgba(0xFF00FF00),
+        component_x: 1,
+        component_y: 2,
+        map_bitmap: None,
+        map_point: (0, 0),
+        mode,
+        scale_x: 16.0,
+        scale_y: 16.0,
+    })
+}
+
+fn filters() -> Vec<(&'static str, Filter)> {
+    let gradient = GradientFilter {
+        colors: vec![
+            GradientRecord {
+                ratio: 0,
+                color: Color::from_rgba(0x00FFFFFF),
+            },
+            GradientRecord {
+                ratio: 128,
+                color: Color::from_rgba(0xFFFF0000),
+            },
+            GradientRecord {
+                ratio: 255,
+                color: Color::from_rgba(0xFF000000),
+            },
+        ],
+        blur_x: Fixed16::from_f32(6.0),
+        blur_y: Fixed16::from_f32(6.0),
+        angle: Fixed16::from_f32(std::f32::consts::FRAC_PI_4),
+        distance: Fixed16::from_f32(4.0),
+        strength: Fixed8::from_f32(2.0),
+        flags: GradientFilterFlags::COMPOSITE_SOURCE | GradientFilterFlags::from_passes(1),
+    };
+    vec![
+        (
+            "color_matrix",
+            Filter::ColorMatrixFilter(ColorMatrixFilter {
+                #[rustfmt::skip]
+                matrix: [
+                    0.0, 0.0, 1.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0, 0.0, 32.0,
+                    1.0, 0.0, 0.0, 0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.5, 0.0,
+                ],
+            }),
+        ),
+        (
+            "convolution",
+            Filter::ConvolutionFilter(ConvolutionFilter {
+                num_matrix_rows: 3,
+                num_matrix_cols: 3,
+                matrix: [-1.0, -1.0, -1.0, -1.0, 8.0, -1.0, -1.0, -1.0, -1.0]
+                    .into_iter()
+                    .map(Fixed16::from_f32)
+                    .collect(),
+                divisor: Fixed16::ONE,
+                bias: Fixed16::from_f32(64.0),
+                default_color: Color::from_rgba(0xFF00FF00),
+                flags: ConvolutionFilterFlags::empty(),
+            }),
+        ),
+        (
+            "convolution_past_15x15",
+            Filter::ConvolutionFilter(ConvolutionFilter {
+                num_matrix_rows: 16,
+                num_matrix_cols: 16,
+                // Only the first 15 rows and columns are used: an edge
+                // detection kernel, without the 100s in the last row and column.
+                matrix: (0..16 * 16)
+                    .map(|index| match (index / 16, index % 16) {
+                        (15, _) | (_, 15) => 100.0,
+                        (7, 7) => 8.0,
+                        (6..=8, 6..=8) => -1.0,
+                        _ => 0.0,
+                    })
+                    .map(Fixed16::from_f32)
+                    .collect(),
+                divisor: Fixed16::ONE,
+                bias: Fixed16::from_f32(64.0),
+                default_color: Color::from_rgba(0xFF00FF00),
+                flags: ConvolutionFilterFlags::empty(),
+            }),
+        ),
+        (
+            "blur",
+            Filter::BlurFilter(BlurFilter {
+                blur_x: Fixed16::from_f32(8.0),
+                blur_y: Fixed16::from_f32(4.0),
+                flags: BlurFilterFlags::from_passes(2),
+            }),
+        ),
+        (
+            "glow",
+            Filter::GlowFilter(GlowFilter {
+                color: Color::from_rgba(0xFF00FF00),
+                blur_x: Fixed16::from_f32(6.0),
+                blur_y: Fixed16::from_f32(6.0),
+                strength: Fixed8::from_f32(2.0),
+                flags: GlowFilterFlags::COMPOSITE_SOURCE | GlowFilterFlags::from_passes(1),
+            }),
+        ),
+        (
+            "inner_glow",
+            Filter::GlowFilter(GlowFilter {
+                color: Color::from_rgba(0xFFFFFF00),
+                blur_x: Fixed16::from_f32(6.0),
+                blur_y: Fixed16::from_f32(6.0),
+                strength: Fixed8::from_f32(1.0),
+                flags: GlowFilterFlags::INNER_GLOW
+                    | GlowFilterFlags::COMPOSITE_SOURCE
+                    | GlowFilterFlags::from_passes(1),
+            }),
+        ),
+        (
+            "drop_shadow",
+            Filter::DropShadowFilter(DropShadowFilter {
+                color: Color::from_rgba(0xFF000000),
+                blur_x: Fixed16::from_f32(4.0),
+                blur_y: Fixed16::from_f32(4.0),
+                angle: Fixed16::from_f32(std::f32::consts::FRAC_PI_4),
+                distance: Fixed16::from_f32(5.0),
+                strength: Fixed8::from_f32(1.0),
+                flags: DropShadowFilterFlags::COMPOSITE_SOURCE
+                    | DropShadowFilterFlags::KNOCKOUT
+                    | DropShadowFilterFlags::from_passes(1),
+            }),
+        ),
+        (
+            "bevel",
+            Filter::BevelFilter(BevelFilter {
+                shadow_color: Color::from_rgba(0xFF000000),
+                highlight_color: Color::from_rgba(0xFFFFFFFF),
+                blur_x: Fixed16::from_f32(4.0),
+                blur_y: Fixed16::from_f32(4.0),
+                angle: Fixed16::from_f32(std::f32::consts::FRAC_PI_4),
+                distance: Fixed16::from_f32(3.0),
+                strength: Fixed8::from_f32(1.0),
+                flags: BevelFilterFlags::ON_TOP
+                    | BevelFilterFlags::COMPOSITE_SOURCE
+                    | BevelFilterFlags::from_passes(1),
+            }),
+        ),
+        (
+            "gradient_glow",
+            Filter::GradientGlowFilter(gradient.clone()),
+        ),
+        ("gradient_bevel", Filter::GradientBevelFilter(gradient)),
+        (
+            "displacement_map_wrap",
+            displacement_map(DisplacementMapFilterMode::Wrap),
+        ),
+        (
+            "displacement_map_clamp",
+            displacement_map(DisplacementMapFilterMode::Clamp),
+        ),
+        (
+            "displacement_map_color",
+            displacement_map(DisplacementMapFilterMode::Color),
+        ),
+        (
+            "displacement_map_ignore",
+            displacement_map(DisplacementMapFilterMode::Ignore),
+        ),
+    ]
+}
@@ -3,14 +3,19 @@
 //! Trace output can be compared with correct output from the official Flash Player.
 
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
-use crate::shared_object::{shared_object_avm1, shared_object_avm2};
+use crate::filters::filters_cpu_matches_gpu;
+use crate::shared_object::{
+    shared_object_avm1, shared_object_avm2, shared_object_typed_avm1, shared_object_typed_avm2,
+};
 use anyhow::Context;
 use anyhow::Result;
 use libtest_mimic::{Arguments, Trial};
 use std::path::Path;
+use util::environment::WGPU;
 use util::test::Test;
 
 mod external_interface;
+mod filters;
 mod shared_object;
 mod util;
 
@@ -80,6 +85,14 @@ fn main() {
     // Manual tests here, since #[test] doesn't work once we use our own test harness
     tests.push(Trial::test("shared_object_avm1", shared_object_avm1));
     tests.push(Trial::test("shared_object_avm2", shared_object_avm2));
+    tests.push(Trial::test(
+        "shared_object_typed_avm1",
+        shared_object_typed_avm1,
+    ));
+    tests.push(Trial::test(
+        "shared_object_typed_avm2",
+        shared_object_typed_avm2,
+    ));
     tests.push(Trial::test(
         "external_interface_avm1",
         external_interface_avm1,
@@ -88,6 +101,11 @@ fn main() {
         "external_interface_avm2",
         external_interface_avm2,
     ));
+    tests.push(
+        Trial::test("filters_cpu_matches_gpu", filters_cpu_matches_gpu)
+            // Listing the tests shouldn't spend the cost of creating a GPU device.
+            .with_ignored_flag(!args.list && WGPU.is_none()),
+    );
 
     tests.sort_unstable_by(|a, b| a.name().cmp(b.name()));
 
@@ -121,3 +121,69 @@ pub fn shared_object_avm2() -> Result<(), libtest_mimic::Failed> {
 
     Ok(())
 }
+
+pub fn shared_object_typed_avm1() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    // Test that objects of classes registered with `Object.registerClass`
+    // keep their class when they're saved to a shared object and read again.
+    run_twice(
+        "tests/swfs/avm1/shared_object_typed/",
+        "shared_object_typed_avm1",
+    )
+}
+
+pub fn shared_object_typed_avm2() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    // Test that objects of classes registered with `registerClassAlias`,
+    // including externalizable ones, keep their class when they're saved to a
+    // shared object and read again.
+    run_twice(
+        "tests/swfs/avm2/shared_object_typed/",
+        "shared_object_typed_avm2",
+    )
+}
+
+/// Run an SWF twice, with the shared objects saved by the first run, comparing
+/// the output of the runs with `output1.txt` and `output2.txt`.
+fn run_twice(path: &str, name: &str) -> Result<(), libtest_mimic::Failed> {
+    let mut memory_storage_backend: Box<dyn StorageBackend> =
+        Box::<MemoryStorageBackend>::default();
+
+    Test::from_options(
+        TestOptions {
+            num_frames: 1,
+            output_path: "output1.txt".into(),
+            ..Default::default()
+        },
+        Path::new(path),
+        name.to_string(),
+    )?
+    .run(
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            std::mem::swap(player.storage_mut(), &mut memory_storage_backend);
+            Ok(())
+        },
+    )?;
+
+    Test::from_options(
+        TestOptions {
+            num_frames: 1,
+            output_path: "output2.txt".into(),
+            ..Default::default()
+        },
+        Path::new(path),
+        name.to_string(),
+    )?
+    .run(
+        |player| {
+            let mut player = player.lock().unwrap();
+            std::mem::swap(player.storage_mut(), &mut memory_storage_backend);
+            Ok(())
+        },
+        |_| Ok(()),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+var nc = new NetConnection();
+nc.onStatus = function(info) {
+	trace("NetConnection: " + info.code + " (" + info.level + ")");
+};
+trace("connect: " + nc.connect("rtmp://localhost/app"));
+trace("connect: " + nc.connect(null));
+
+var ns = new NetStream(nc);
+ns.onStatus = function(info) {
+	trace(info.code + " (" + info.level + "), " + ns.bytesLoaded + "/" + ns.bytesTotal);
+};
+ns.onMetaData = function(info) {
+	trace("onMetaData: duration " + info.duration + ", " + info.width + "x" + info.height);
+};
+ns.onCuePoint = function(info) {
+	trace("onCuePoint: " + info.name + " at " + info.time);
+};
+trace("bufferTime: " + ns.bufferTime);
+ns.setBufferTime(2);
+trace("bufferTime: " + ns.bufferTime);
+ns.play("video.flv");
@@ -0,0 +1,20 @@
+// Frame 1
+function Point3() {
+    trace("Point3 constructor");
+}
+Point3.prototype.toString = function() {
+    return "(" + this.x + ", " + this.y + ", " + this.z + ")";
+};
+Object.registerClass("Point3", Point3);
+
+var so = SharedObject.getLocal("RuffleTypedTest", "/");
+trace("point: " + so.data.point);
+trace("point instanceof Point3: " + (so.data.point instanceof Point3));
+if (so.data.point == undefined) {
+    var p = new Point3();
+    p.x = 1;
+    p.y = 2;
+    p.z = 3;
+    so.data.point = p;
+    trace("flush: " + so.flush());
+}
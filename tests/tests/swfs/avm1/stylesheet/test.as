@@ -0,0 +1,44 @@
+var css = new TextField.StyleSheet();
+trace("///css.parseCSS(...)");
+trace(css.parseCSS("p { color: #00FF00; } .Head { font-size: 20px; font-weight: bold } /* Hidden */ .hide { display: none }"));
+var names = css.getStyleNames();
+names.sort();
+trace(names);
+trace(css.getStyle(".HEAD").fontSize);
+trace(css.getStyle("missing"));
+
+trace("///css.parseCSS(malformed)");
+trace(css.parseCSS("p { color"));
+
+trace("///css.setStyle(...)");
+css.setStyle("Span", {fontStyle: "italic"});
+trace(css.getStyle("span").fontStyle);
+css.setStyle("span", null);
+trace(css.getStyle("span"));
+
+trace("///css.transform(...)");
+var fmt = css.transform({color: "#FF0000", fontSize: "14px", textAlign: "center"});
+trace(fmt.color + " " + fmt.size + " " + fmt.align);
+
+trace("///field.styleSheet");
+_root.createTextField("field", 1, 0, 0, 100, 100);
+field.multiline = true;
+field.html = true;
+trace(field.styleSheet);
+field.styleSheet = css;
+var html = "<p class=\"head\">Title</p><p>Body <span class=\"hide\">secret</span>text</p>";
+field.htmlText = html;
+trace(field.text.split("\r").join("|").split("\n").join("|"));
+trace(field.htmlText.split("\r").join("|").split("\n").join("|"));
+fmt = field.getTextFormat(0, 1);
+trace(fmt.color + " " + fmt.size + " " + fmt.bold);
+fmt = field.getTextFormat(6, 7);
+trace(fmt.color + " " + fmt.size + " " + fmt.bold);
+
+trace("///field.styleSheet = null");
+field.styleSheet = null;
+trace(field.styleSheet);
+field.htmlText = html;
+trace(field.text.split("\r").join("|").split("\n").join("|"));
+fmt = field.getTextFormat(0, 1);
+trace(fmt.color + " " + fmt.size + " " + fmt.bold);
@@ -0,0 +1,24 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.net.getClassByAlias;
+	import flash.net.registerClassAlias;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			registerClassAlias("point", Point);
+
+			var bytes: ByteArray = new ByteArray();
+			bytes.writeObject(new Point(1, 2));
+			bytes.position = 0;
+
+			var read: * = bytes.readObject();
+			trace("is Point: " + (read is Point));
+			trace("x: " + read.x);
+			trace("y: " + read.y);
+			trace("bytesAvailable: " + bytes.bytesAvailable);
+			trace("getClassByAlias: " + (getClassByAlias("point") == Point));
+		}
+	}
+}
@@ -0,0 +1,137 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.registerClassAlias;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			registerClassAlias("Save", Save);
+			registerClassAlias("Item", Item);
+			registerClassAlias("Sealed", Sealed);
+
+			var save: Save = new Save();
+			save.name = "slot";
+			save.item = new Item("sword", 3);
+
+			trace("// Top-level externalizable object");
+			var bytes: ByteArray = new ByteArray();
+			bytes.writeObject(save);
+			bytes.position = 0;
+			var read: * = bytes.readObject();
+			trace("is Save: " + (read is Save));
+			trace("name: " + read.name);
+			trace("item is Item: " + (read.item is Item));
+			trace("item: " + read.item);
+			trace("bytesAvailable: " + bytes.bytesAvailable);
+
+			trace("// Externalizable objects nested in an object and an array");
+			bytes = new ByteArray();
+			bytes.writeObject({save: save, list: [new Item("shield", 1), new Item("potion", 5)]});
+			bytes.writeUTF("after");
+			bytes.position = 0;
+			read = bytes.readObject();
+			trace("save is Save: " + (read.save is Save));
+			trace("save.name: " + read.save.name);
+			trace("save.item: " + read.save.item);
+			trace("list: " + read.list);
+			trace("after: " + bytes.readUTF());
+			trace("bytesAvailable: " + bytes.bytesAvailable);
+
+			trace("// Sealed members are written in declaration order");
+			bytes = new ByteArray();
+			bytes.writeObject(new Sealed());
+			bytes.position = 0;
+			read = bytes.readObject();
+			trace("is Sealed: " + (read is Sealed));
+		}
+	}
+}
+
+import flash.utils.IDataInput;
+import flash.utils.IDataOutput;
+import flash.utils.IExternalizable;
+
+class Item implements IExternalizable {
+	public var name: String;
+	public var count: int;
+
+	public function Item(name: String = "", count: int = 0) {
+		this.name = name;
+		this.count = count;
+	}
+
+	public function writeExternal(output: IDataOutput): void {
+		output.writeUTF(name);
+		output.writeByte(count);
+	}
+
+	public function readExternal(input: IDataInput): void {
+		name = input.readUTF();
+		count = input.readByte();
+	}
+
+	public function toString(): String {
+		return name + " x" + count;
+	}
+}
+
+class Save implements IExternalizable {
+	public var name: String;
+	public var item: Item;
+
+	public function writeExternal(output: IDataOutput): void {
+		output.writeUTF(name);
+		output.writeObject(item);
+	}
+
+	public function readExternal(input: IDataInput): void {
+		name = input.readUTF();
+		item = input.readObject();
+	}
+}
+
+class SealedBase {
+	private var _base: int = 1;
+
+	public function get base(): int {
+		return _base;
+	}
+
+	public function set base(value: int): void {
+		trace("set base");
+		_base = value;
+	}
+}
+
+class Sealed extends SealedBase {
+	private var _zeta: int = 2;
+	private var _alpha: int = 3;
+	private var _middle: int = 4;
+
+	public function get zeta(): int {
+		return _zeta;
+	}
+
+	public function set zeta(value: int): void {
+		trace("set zeta");
+		_zeta = value;
+	}
+
+	public function get alpha(): int {
+		return _alpha;
+	}
+
+	public function set alpha(value: int): void {
+		trace("set alpha");
+		_alpha = value;
+	}
+
+	public function get middle(): int {
+		return _middle;
+	}
+
+	public function set middle(value: int): void {
+		trace("set middle");
+		_middle = value;
+	}
+}
@@ -0,0 +1,41 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.MovieClip;
+	import flash.display.PNGEncoderOptions;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var bmd: BitmapData = new BitmapData(4, 4, true, 0x80FF0000);
+
+			var bytes: ByteArray = bmd.encode(bmd.rect, new PNGEncoderOptions());
+			trace(bytes[0]);
+			trace(bytes[1]);
+			trace(bytes[2]);
+			trace(bytes[3]);
+
+			bytes = bmd.encode(bmd.rect, new JPEGEncoderOptions(50));
+			trace(bytes[0]);
+			trace(bytes[1]);
+
+			var out: ByteArray = new ByteArray();
+			out.writeByte(42);
+			bmd.encode(bmd.rect, new PNGEncoderOptions(true), out);
+			trace(out[0]);
+			trace(out[1]);
+
+			// Only the real encoder option classes are accepted, not objects that look like them
+			try {
+				bmd.encode(bmd.rect, {fastCompression: true});
+			} catch (e: ArgumentError) {
+				trace("ArgumentError " + e.errorID);
+			}
+			try {
+				bmd.encode(bmd.rect, {quality: 80});
+			} catch (e: ArgumentError) {
+				trace("ArgumentError " + e.errorID);
+			}
+		}
+	}
+}
@@ -0,0 +1,27 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var bmd: BitmapData = new BitmapData(20, 20, true, 0);
+			bmd.fillRect(new Rectangle(5, 5, 10, 10), 0xFF00FF00);
+			bmd.fillRect(new Rectangle(0, 0, 2, 2), 0x40FF0000);
+			var other: BitmapData = new BitmapData(4, 4, true, 0xFFFFFFFF);
+
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, new Point(10, 10)));
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, new Point(2, 2)));
+			trace(bmd.hitTest(new Point(0, 0), 0x40, new Point(1, 1)));
+			trace(bmd.hitTest(new Point(0, 0), 0x41, new Point(1, 1)));
+			trace(bmd.hitTest(new Point(100, 100), 0xFF, new Point(110, 110)));
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, new Rectangle(14, 14, 5, 5)));
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, new Rectangle(15, 15, 5, 5)));
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, other, new Point(12, 12)));
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, other, new Point(15, 15)));
+			trace(bmd.hitTest(new Point(0, 0), 0xFF, new Bitmap(other), new Point(12, 12)));
+		}
+	}
+}
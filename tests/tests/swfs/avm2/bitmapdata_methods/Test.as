@@ -0,0 +1,172 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.filters.BlurFilter;
+	import flash.filters.ColorMatrixFilter;
+	import flash.filters.DropShadowFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			testSetVector();
+			testHistogram();
+			testGenerateFilterRect();
+			testThreshold();
+			testPaletteMap();
+			testMerge();
+			testCompare();
+			testPixelDissolve();
+		}
+
+		private function dump(bitmap: BitmapData): void {
+			var pixels: Array = [];
+			for (var y: int = 0; y < bitmap.height; y++) {
+				for (var x: int = 0; x < bitmap.width; x++) {
+					pixels.push(bitmap.getPixel32(x, y).toString(16));
+				}
+			}
+			trace(pixels.join(" "));
+		}
+
+		private function testSetVector(): void {
+			trace("// setVector");
+			var bitmap: BitmapData = new BitmapData(3, 2, false, 0);
+			bitmap.setVector(bitmap.rect, Vector.<uint>([0xFF112233, 0xFF445566, 0xFF778899, 0xFFAABBCC, 0xFFDDEEFF, 0xFF000000]));
+			dump(bitmap);
+
+			bitmap.setVector(new Rectangle(1, 1, 2, 1), Vector.<uint>([0xFF0000FF, 0xFF00FF00, 0xFFFF0000]));
+			dump(bitmap);
+
+			// The pixels before the vector runs out are still set
+			try {
+				bitmap.setVector(bitmap.rect, Vector.<uint>([0xFFFFFFFF, 0xFFFFFFFF]));
+			} catch (e: RangeError) {
+				trace("RangeError " + e.errorID);
+			}
+			dump(bitmap);
+		}
+
+		private function testHistogram(): void {
+			trace("// histogram");
+			var bitmap: BitmapData = new BitmapData(2, 2, true, 0);
+			bitmap.setPixel32(0, 0, 0xFF000000);
+			bitmap.setPixel32(1, 0, 0xFFFF0000);
+			bitmap.setPixel32(0, 1, 0xFF00FF00);
+
+			var histogram: Vector.<Vector.<Number>> = bitmap.histogram();
+			trace(histogram.length, histogram[0].length);
+			trace("red", histogram[0][0], histogram[0][255]);
+			trace("green", histogram[1][0], histogram[1][255]);
+			trace("alpha", histogram[3][0], histogram[3][255]);
+
+			histogram = bitmap.histogram(new Rectangle(1, 0, 5, 5));
+			trace("right column red", histogram[0][0], histogram[0][255]);
+		}
+
+		private function testGenerateFilterRect(): void {
+			trace("// generateFilterRect");
+			var bitmap: BitmapData = new BitmapData(100, 100, true, 0);
+			var rect: Rectangle = new Rectangle(10, 10, 20, 20);
+			trace(bitmap.generateFilterRect(rect, new ColorMatrixFilter()));
+			trace(bitmap.generateFilterRect(rect, new BlurFilter(4, 4, 1)));
+			trace(bitmap.generateFilterRect(rect, new DropShadowFilter(4, 0, 0, 1, 2, 2, 1, 1, true)));
+		}
+
+		private function testThreshold(): void {
+			trace("// threshold");
+			var source: BitmapData = new BitmapData(4, 1, false, 0);
+			source.setVector(source.rect, Vector.<uint>([0xFF000010, 0xFF000020, 0xFF000030, 0xFF000040]));
+
+			var dest: BitmapData = new BitmapData(4, 1, false, 0x00FF00);
+			trace(dest.threshold(source, source.rect, new Point(0, 0), ">", 0xFF000020, 0xFFFF0000));
+			dump(dest);
+
+			dest = new BitmapData(4, 1, false, 0x00FF00);
+			trace(dest.threshold(source, source.rect, new Point(0, 0), "==", 0x10, 0xFFFF0000, 0xFF, true));
+			dump(dest);
+
+			// Unknown operations don't do anything
+			trace(dest.threshold(source, source.rect, new Point(0, 0), "=>", 0x10, 0xFFFFFFFF, 0xFF, true));
+			dump(dest);
+		}
+
+		private function testPaletteMap(): void {
+			trace("// paletteMap");
+			var source: BitmapData = new BitmapData(2, 1, false, 0);
+			source.setVector(source.rect, Vector.<uint>([0xFF102030, 0xFF405060]));
+
+			// Red is moved into blue, the other channels stay as they are
+			var red: Array = [];
+			for (var i: int = 0; i < 256; i++) {
+				red.push(i);
+			}
+			var dest: BitmapData = new BitmapData(2, 1, false, 0);
+			dest.paletteMap(source, source.rect, new Point(0, 0), red);
+			dump(dest);
+
+			// Unlisted channels are kept; listed channels are summed up
+			var green: Array = [];
+			for (i = 0; i < 256; i++) {
+				green.push(0x010000);
+			}
+			dest.paletteMap(source, new Rectangle(1, 0, 1, 1), new Point(0, 0), null, green);
+			dump(dest);
+		}
+
+		private function testMerge(): void {
+			trace("// merge");
+			var source: BitmapData = new BitmapData(2, 1, false, 0xFFFFFF);
+			var dest: BitmapData = new BitmapData(2, 1, false, 0x000000);
+			dest.merge(source, new Rectangle(0, 0, 1, 1), new Point(0, 0), 128, 256, 0, 256);
+			dump(dest);
+
+			// Multipliers are clamped to 0..256
+			dest.merge(source, new Rectangle(0, 0, 1, 1), new Point(1, 0), 1000, -5, 64, 256);
+			dump(dest);
+		}
+
+		private function testCompare(): void {
+			trace("// compare");
+			var a: BitmapData = new BitmapData(2, 1, false, 0x112233);
+			var b: BitmapData = new BitmapData(2, 1, false, 0x112233);
+			trace(a.compare(b));
+			trace(a.compare(a));
+			trace(a.compare(new BitmapData(3, 1, false, 0)));
+			trace(a.compare(new BitmapData(2, 2, false, 0)));
+
+			b.setPixel(1, 0, 0x102030);
+			var result: Object = a.compare(b);
+			trace(result is BitmapData);
+			dump(BitmapData(result));
+		}
+
+		private function testPixelDissolve(): void {
+			trace("// pixelDissolve");
+			var bitmap: BitmapData = new BitmapData(10, 10, false, 0);
+			var seed: int = bitmap.pixelDissolve(bitmap, bitmap.rect, new Point(0, 0), 0, 30, 0xFFFF0000);
+			trace(countRed(bitmap));
+
+			// Continuing with the returned seed never picks a pixel twice
+			bitmap.pixelDissolve(bitmap, bitmap.rect, new Point(0, 0), seed, 70, 0xFFFF0000);
+			trace(countRed(bitmap));
+
+			// Other sources are copied from
+			var source: BitmapData = new BitmapData(10, 10, false, 0x0000FF);
+			bitmap.pixelDissolve(source, new Rectangle(0, 0, 5, 10), new Point(5, 0), 123, 1000);
+			trace(countRed(bitmap));
+		}
+
+		private function countRed(bitmap: BitmapData): int {
+			var count: int = 0;
+			for (var y: int = 0; y < bitmap.height; y++) {
+				for (var x: int = 0; x < bitmap.width; x++) {
+					if (bitmap.getPixel(x, y) == 0xFF0000) {
+						count++;
+					}
+				}
+			}
+			return count;
+		}
+	}
+}
@@ -0,0 +1,92 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.TextField;
+	import flash.text.TextFormat;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var format:TextFormat = new TextFormat("TestFont", 20);
+			format.leading = 2;
+
+			var tf:TextField = new TextField();
+			tf.width = 200;
+			tf.height = 100;
+			tf.multiline = true;
+			tf.embedFonts = true;
+			tf.defaultTextFormat = format;
+			tf.text = "ab cd\refg";
+			addChild(tf);
+
+			trace("/// numLines");
+			trace(tf.numLines);
+
+			trace("/// getLineOffset, getLineLength, getLineText");
+			for (var line:int = 0; line < 2; line++) {
+				trace(line + ": " + tf.getLineOffset(line) + " " + tf.getLineLength(line) + " " + escape(tf.getLineText(line)));
+			}
+			expectRangeError(function():void { tf.getLineOffset(2); });
+			expectRangeError(function():void { tf.getLineLength(2); });
+			expectRangeError(function():void { tf.getLineText(-1); });
+
+			trace("/// getLineIndexOfChar");
+			trace(tf.getLineIndexOfChar(0));
+			trace(tf.getLineIndexOfChar(5));
+			trace(tf.getLineIndexOfChar(7));
+			trace(tf.getLineIndexOfChar(9));
+			trace(tf.getLineIndexOfChar(-1));
+
+			trace("/// getCharBoundaries");
+			trace(tf.getCharBoundaries(1));
+			trace(tf.getCharBoundaries(7));
+			trace(tf.getCharBoundaries(5));
+			trace(tf.getCharBoundaries(20));
+
+			trace("/// getCharIndexAtPoint");
+			trace(tf.getCharIndexAtPoint(25, 5));
+			trace(tf.getCharIndexAtPoint(15, 30));
+			trace(tf.getCharIndexAtPoint(100, 5));
+			trace(tf.getCharIndexAtPoint(5, 23));
+
+			trace("/// getLineIndexAtPoint");
+			trace(tf.getLineIndexAtPoint(5, 5));
+			trace(tf.getLineIndexAtPoint(5, 30));
+			trace(tf.getLineIndexAtPoint(5, 23));
+
+			trace("/// getFirstCharInParagraph, getParagraphLength");
+			trace(tf.getFirstCharInParagraph(2) + " " + tf.getParagraphLength(2));
+			trace(tf.getFirstCharInParagraph(7) + " " + tf.getParagraphLength(7));
+			trace(tf.getFirstCharInParagraph(9) + " " + tf.getParagraphLength(9));
+
+			trace("/// getImageReference");
+			trace(tf.getImageReference("image"));
+
+			trace("/// selectionBeginIndex, selectionEndIndex, caretIndex");
+			tf.setSelection(1, 4);
+			trace(tf.selectionBeginIndex + " " + tf.selectionEndIndex + " " + tf.caretIndex);
+			tf.setSelection(5, 2);
+			trace(tf.selectionBeginIndex + " " + tf.selectionEndIndex + " " + tf.caretIndex);
+
+			trace("/// Empty field");
+			var empty:TextField = new TextField();
+			empty.embedFonts = true;
+			empty.defaultTextFormat = format;
+			trace(empty.getLineOffset(0) + " " + empty.getLineLength(0) + " " + escape(empty.getLineText(0)));
+			expectRangeError(function():void { empty.getLineOffset(1); });
+			trace(empty.getLineIndexOfChar(0));
+			trace(empty.getCharBoundaries(0));
+		}
+
+		private function expectRangeError(f:Function):void {
+			try {
+				f();
+				trace("No error");
+			} catch (e:RangeError) {
+				trace(e.errorID);
+			}
+		}
+
+		private function escape(text:String):String {
+			return "\"" + text.split("\r").join("\\r") + "\"";
+		}
+	}
+}
@@ -0,0 +1,52 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.KeyboardEvent;
+	import flash.events.MouseEvent;
+	import flash.events.TextEvent;
+	import flash.text.TextField;
+	import flash.text.TextFieldType;
+	import flash.text.TextFormat;
+
+	public class Test extends MovieClip {
+		private var tf:TextField;
+
+		public function Test() {
+			tf = new TextField();
+			tf.x = 10;
+			tf.y = 10;
+			tf.width = 200;
+			tf.height = 30;
+			tf.type = TextFieldType.INPUT;
+			tf.embedFonts = true;
+			tf.defaultTextFormat = new TextFormat("TestFont", 20);
+			tf.text = "hello big world";
+			addChild(tf);
+
+			tf.addEventListener(MouseEvent.CLICK, function(e:MouseEvent):void {
+				traceState("click");
+			});
+			tf.addEventListener(TextEvent.TEXT_INPUT, function(e:TextEvent):void {
+				trace("textInput: " + e.text);
+			});
+			tf.addEventListener(Event.CHANGE, function(e:Event):void {
+				trace("change");
+			});
+			stage.addEventListener(KeyboardEvent.KEY_UP, function(e:KeyboardEvent):void {
+				// Ignore Command, Shift and Control.
+				if (e.keyCode == 15 || e.keyCode == 16 || e.keyCode == 17) {
+					return;
+				}
+				// M limits the field to 5 characters.
+				if (e.keyCode == 77) {
+					tf.maxChars = 5;
+				}
+				traceState("keyUp " + e.keyCode);
+			});
+		}
+
+		private function traceState(label:String):void {
+			trace(label + ": " + tf.selectionBeginIndex + " " + tf.selectionEndIndex + " " + tf.caretIndex + " " + tf.text);
+		}
+	}
+}
@@ -0,0 +1,54 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.Font;
+	import flash.text.TextField;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("///Font.enumerateFonts().length");
+			var fonts:Array = Font.enumerateFonts();
+			trace(fonts.length);
+			for each (var font:Font in fonts) {
+				trace(font.fontName + " " + font.fontStyle + " " + font.fontType);
+			}
+
+			var cff:Font = new CffSans();
+			trace("///cff.hasGlyphs(\"AB\")");
+			trace(cff.hasGlyphs("AB"));
+			trace("///cff.hasGlyphs(\"ABC\")");
+			trace(cff.hasGlyphs("ABC"));
+
+			trace("///Font.registerFont(CffSans)");
+			Font.registerFont(CffSans);
+			trace(Font.enumerateFonts().length);
+
+			trace("///Font.registerFont(Test)");
+			try {
+				Font.registerFont(Test);
+			} catch (e:ArgumentError) {
+				trace(e.errorID);
+			}
+
+			trace("///Font.registerFont(null)");
+			try {
+				Font.registerFont(null);
+			} catch (e:ArgumentError) {
+				trace(e.errorID);
+			}
+
+			// Text fields can't use DefineFont4 fonts, so nothing is rendered.
+			trace("///textWidth with embedFonts");
+			var field:TextField = new TextField();
+			field.embedFonts = true;
+			field.htmlText = "<font face=\"Cff Sans\">AB</font>";
+			trace(field.textWidth);
+		}
+	}
+}
+
+package {
+	import flash.text.Font;
+
+	public class CffSans extends Font {
+	}
+}
@@ -0,0 +1,65 @@
+package {
+	import flash.display.GraphicsPath;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// Read back a simple filled rectangle.
+			var rect: Shape = new Shape();
+			rect.graphics.beginFill(0xFF0000);
+			rect.graphics.drawRect(0, 0, 10, 20);
+			rect.graphics.endFill();
+			var data: Vector.<IGraphicsData> = rect.graphics.readGraphicsData();
+			trace(data);
+			var items: Vector.<IGraphicsData> = data.concat();
+			var item: * = items.shift();
+			trace(item.color);
+			trace(item.alpha);
+			item = items.shift();
+			trace(item.commands);
+			trace(item.data);
+
+			// Build a path with the GraphicsPath helpers.
+			var path: GraphicsPath = new GraphicsPath();
+			path.moveTo(1, 2);
+			path.lineTo(3, 4);
+			path.wideLineTo(5, 6);
+			path.cubicCurveTo(1, 2, 3, 4, 5, 6);
+			trace(path.commands);
+			trace(path.data);
+
+			// Draw the data that was read back.
+			var redrawn: Shape = new Shape();
+			redrawn.graphics.drawGraphicsData(data);
+			trace(redrawn.width);
+			trace(redrawn.height);
+
+			// Copy the graphics of another shape.
+			var copy: Shape = new Shape();
+			copy.graphics.copyFrom(rect.graphics);
+			trace(copy.graphics.readGraphicsData().length);
+
+			// Rounded rectangles stay inside their bounds.
+			var rounded: Shape = new Shape();
+			rounded.graphics.beginFill(0x00FF00);
+			rounded.graphics.drawRoundRectComplex(0, 0, 30, 40, 5, 10, 0, 20);
+			rounded.graphics.endFill();
+			trace(rounded.width);
+			trace(rounded.height);
+
+			// Triangles without indices.
+			var vertices: GraphicsPath = new GraphicsPath();
+			vertices.lineTo(0, 0);
+			vertices.lineTo(20, 0);
+			vertices.lineTo(0, 30);
+			var triangles: Shape = new Shape();
+			triangles.graphics.beginFill(0x0000FF);
+			triangles.graphics.drawTriangles(vertices.data);
+			triangles.graphics.endFill();
+			trace(triangles.width);
+			trace(triangles.height);
+		}
+	}
+}
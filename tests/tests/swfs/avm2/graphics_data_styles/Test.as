@@ -0,0 +1,111 @@
+package {
+	import flash.display.GraphicsGradientFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsSolidFill;
+	import flash.display.GraphicsStroke;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.events.Event;
+	import flash.geom.Matrix;
+
+	public class Test extends MovieClip {
+		private var nonZero: Shape;
+		private var evenOdd: Shape;
+
+		public function Test() {
+			// Gradient fills.
+			var matrix: Matrix = new Matrix();
+			matrix.createGradientBox(100, 50, 0, 10, 20);
+			var gradient: Shape = new Shape();
+			gradient.graphics.beginGradientFill("linear", [0xFF0000, 0x0000FF], [1, 0], [0, 255], matrix, "reflect", "linearRGB");
+			gradient.graphics.drawRect(10, 20, 100, 50);
+			gradient.graphics.endFill();
+			roundTrip("gradient fill", gradient);
+
+			// Solid line styles.
+			var line: Shape = new Shape();
+			line.graphics.lineStyle(4, 0x00FF00, 1, true, "horizontal", "square", "miter", 5);
+			line.graphics.moveTo(0, 0);
+			line.graphics.lineTo(30, 0);
+			roundTrip("solid line", line);
+
+			// Gradient line styles.
+			var gradientLine: Shape = new Shape();
+			gradientLine.graphics.lineStyle(2, 0, 1, false, "normal", "round", "round", 3);
+			gradientLine.graphics.lineGradientStyle("radial", [0x0000FF, 0x00FF00], [1, 1], [0, 128], null, "repeat", "rgb", 0.5);
+			gradientLine.graphics.lineTo(10, 10);
+			roundTrip("gradient line", gradientLine);
+
+			// Two overlapping squares drawn in the same direction.
+			var commands: Vector.<int> = Vector.<int>([1, 2, 2, 2, 2, 1, 2, 2, 2, 2]);
+			var data: Vector.<Number> = Vector.<Number>([0, 0, 20, 0, 20, 20, 0, 20, 0, 0, 10, 10, 30, 10, 30, 30, 10, 30, 10, 10]);
+			nonZero = new Shape();
+			nonZero.graphics.beginFill(0xFF0000);
+			nonZero.graphics.drawPath(commands, data, "nonZero");
+			nonZero.graphics.endFill();
+			roundTrip("nonZero path", nonZero);
+			evenOdd = new Shape();
+			evenOdd.graphics.beginFill(0xFF0000);
+			evenOdd.graphics.drawPath(commands, data, "evenOdd");
+			evenOdd.graphics.endFill();
+			roundTrip("evenOdd path", evenOdd);
+
+			try {
+				evenOdd.graphics.drawPath(commands, data, "bogus");
+			} catch (e: ArgumentError) {
+				trace("drawPath with a bogus winding: " + e.errorID);
+			}
+
+			addChild(nonZero);
+			addChild(evenOdd);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event: Event): void {
+			removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+			trace("/// Hit tests");
+			// The squares only overlap between 10 and 20.
+			trace("nonZero at (5, 5): " + nonZero.hitTestPoint(5, 5, true));
+			trace("nonZero at (15, 15): " + nonZero.hitTestPoint(15, 15, true));
+			trace("evenOdd at (5, 5): " + evenOdd.hitTestPoint(5, 5, true));
+			trace("evenOdd at (15, 15): " + evenOdd.hitTestPoint(15, 15, true));
+		}
+
+		private function roundTrip(name: String, shape: Shape): void {
+			trace("/// " + name);
+			var data: Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+			var description: String = describeAll(data);
+			trace(description);
+
+			var redrawn: Shape = new Shape();
+			redrawn.graphics.drawGraphicsData(data);
+			trace("Redrawn data matches: " + (describeAll(redrawn.graphics.readGraphicsData()) == description));
+		}
+
+		private function describeAll(data: Vector.<IGraphicsData>): String {
+			var lines: Array = [];
+			for each (var item: IGraphicsData in data) {
+				lines.push(describe(item));
+			}
+			return lines.join("\n");
+		}
+
+		private function describe(item: Object): String {
+			if (item is GraphicsSolidFill) {
+				return "GraphicsSolidFill color=" + item.color + " alpha=" + item.alpha;
+			} else if (item is GraphicsGradientFill) {
+				return "GraphicsGradientFill type=" + item.type + " colors=" + item.colors + " alphas=" + item.alphas +
+					" ratios=" + item.ratios + " matrix=" + item.matrix + " spreadMethod=" + item.spreadMethod +
+					" interpolationMethod=" + item.interpolationMethod + " focalPointRatio=" + item.focalPointRatio;
+			} else if (item is GraphicsStroke) {
+				return "GraphicsStroke thickness=" + item.thickness + " pixelHinting=" + item.pixelHinting +
+					" scaleMode=" + item.scaleMode + " caps=" + item.caps + " joints=" + item.joints +
+					" miterLimit=" + item.miterLimit + " fill=" + (item.fill == null ? "null" : describe(item.fill));
+			} else if (item is GraphicsPath) {
+				return "GraphicsPath commands=" + item.commands + " data=" + item.data + " winding=" + item.winding;
+			}
+			return String(item);
+		}
+	}
+}
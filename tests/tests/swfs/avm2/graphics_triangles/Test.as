@@ -0,0 +1,61 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		private var uv: Shape;
+		private var uvt: Shape;
+
+		public function Test() {
+			var bitmap: BitmapData = new BitmapData(4, 4, false, 0xFF0000);
+			var square: Vector.<Number> = Vector.<Number>([0, 0, 20, 0, 20, 20, 0, 20]);
+			var indices: Vector.<int> = Vector.<int>([0, 1, 2, 0, 2, 3]);
+
+			// A square mapped with (u, v) coordinates.
+			uv = new Shape();
+			uv.graphics.beginBitmapFill(bitmap);
+			uv.graphics.drawTriangles(square, indices, Vector.<Number>([0, 0, 1, 0, 1, 1, 0, 1]));
+			uv.graphics.endFill();
+			trace("uv: " + uv.width + " x " + uv.height);
+
+			// The same square mapped with (u, v, t) coordinates.
+			uvt = new Shape();
+			uvt.x = 100;
+			uvt.graphics.beginBitmapFill(bitmap);
+			uvt.graphics.drawTriangles(square, indices, Vector.<Number>([0, 0, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1]));
+			uvt.graphics.endFill();
+			trace("uvt: " + uvt.width + " x " + uvt.height);
+
+			// The first triangle is clockwise on screen, the second one is counter-clockwise.
+			var triangles: Vector.<Number> = Vector.<Number>([0, 0, 10, 0, 0, 10, 20, 0, 20, 30, 30, 0]);
+			for each (var culling: String in ["none", "positive", "negative"]) {
+				var culled: Shape = new Shape();
+				culled.graphics.beginFill(0x0000FF);
+				culled.graphics.drawTriangles(triangles, null, null, culling);
+				culled.graphics.endFill();
+				trace(culling + ": " + culled.width + " x " + culled.height);
+			}
+
+			try {
+				culled.graphics.drawTriangles(triangles, null, null, "bogus");
+			} catch (e: ArgumentError) {
+				trace("drawTriangles with a bogus culling: " + e.errorID);
+			}
+
+			addChild(uv);
+			addChild(uvt);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event: Event): void {
+			removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+			// Both triangles of the squares are filled.
+			trace("uv at (15, 5): " + uv.hitTestPoint(15, 5, true));
+			trace("uv at (5, 15): " + uv.hitTestPoint(5, 15, true));
+			trace("uvt at (115, 5): " + uvt.hitTestPoint(115, 5, true));
+			trace("uvt at (105, 15): " + uvt.hitTestPoint(105, 15, true));
+		}
+	}
+}
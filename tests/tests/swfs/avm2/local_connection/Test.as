@@ -0,0 +1,33 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.AsyncErrorEvent;
+	import flash.events.StatusEvent;
+	import flash.net.LocalConnection;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var receiver: LocalConnection = new LocalConnection();
+			receiver.client = {
+				hello: function(a: *, b: *): void {
+					trace("hello: " + a + " " + b);
+				}
+			};
+			receiver.addEventListener("asyncError", function(e: AsyncErrorEvent): void {
+				trace("asyncError: " + e.text);
+				trace("error: " + (e.error is Error));
+			});
+			receiver.allowDomain("example.com");
+			receiver.allowInsecureDomain("*");
+			receiver.connect("_test");
+
+			var sender: LocalConnection = new LocalConnection();
+			sender.addEventListener("status", function(e: StatusEvent): void {
+				trace("status: " + e.level);
+			});
+			sender.send("_test", "hello", 1, "two");
+			sender.send("_test", "missing");
+			sender.send("_missing", "hello");
+			trace("sent");
+		}
+	}
+}
@@ -0,0 +1,31 @@
+package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.StatusEvent;
+	import flash.net.LocalConnection;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		private var receiver: LocalConnection = new LocalConnection();
+		private var sender: LocalConnection = new LocalConnection();
+
+		public function Test() {
+			var sender: LocalConnection = this.sender;
+			receiver.client = {
+				fromAvm1: function(a: *, b: *): void {
+					trace("avm2 received: " + a + " " + b);
+					sender.send("_avm1", "fromAvm2", 3, "four");
+				}
+			};
+			receiver.connect("_avm2");
+			sender.addEventListener("status", function(e: StatusEvent): void {
+				trace("avm2 status: " + e.level);
+			});
+
+			// The AVM1 movie starts the conversation once it's loaded.
+			var loader: Loader = new Loader();
+			loader.load(new URLRequest("child.swf"));
+			addChild(loader);
+		}
+	}
+}
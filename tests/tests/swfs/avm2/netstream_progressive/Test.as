@@ -0,0 +1,104 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+
+	// Plays an FLV that arrives in small chunks: events, metadata and cue
+	// points, seeking inside and outside of the loaded data, pausing, and
+	// files that are missing or aren't videos.
+	public class Test extends MovieClip {
+		private var connection: NetConnection;
+		private var stream: NetStream;
+		private var missingStream: NetStream;
+		private var invalidStream: NetStream;
+		private var pausedOnce: Boolean = false;
+		private var framesUntilResume: int = -1;
+		private var finished: Boolean = false;
+
+		public function Test() {
+			connection = new NetConnection();
+			connection.addEventListener(NetStatusEvent.NET_STATUS, function(e: NetStatusEvent): void {
+				trace("NetConnection: " + e.info.code + " (" + e.info.level + ")");
+			});
+			connection.connect("rtmp://localhost/app");
+			trace("connected: " + connection.connected);
+			connection.connect(null);
+			trace("connected: " + connection.connected);
+
+			stream = new NetStream(connection);
+			stream.client = {
+				onMetaData: function(info: Object): void {
+					trace("onMetaData: duration " + info.duration + ", " + info.width + "x" + info.height);
+				},
+				onCuePoint: function(info: Object): void {
+					trace("onCuePoint: " + info.name + " at " + info.time);
+					if (!pausedOnce) {
+						pausedOnce = true;
+						stream.pause();
+						framesUntilResume = 5;
+					}
+				}
+			};
+			stream.addEventListener(NetStatusEvent.NET_STATUS, onStatus);
+			trace("bufferTime: " + stream.bufferTime);
+			stream.bufferTime = 0.5;
+			trace("bufferTime: " + stream.bufferTime);
+			stream.play("video.flv");
+
+			missingStream = new NetStream(connection);
+			missingStream.addEventListener(NetStatusEvent.NET_STATUS, function(e: NetStatusEvent): void {
+				trace("missing: " + e.info.code + " (" + e.info.level + ")");
+				invalidStream.play("invalid.flv");
+			});
+
+			invalidStream = new NetStream(connection);
+			invalidStream.addEventListener(NetStatusEvent.NET_STATUS, function(e: NetStatusEvent): void {
+				trace("invalid: " + e.info.code + " (" + e.info.level + ")");
+			});
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onStatus(e: NetStatusEvent): void {
+			trace(e.info.code + " (" + e.info.level + ")");
+			switch (e.info.code) {
+				case "NetStream.Play.Start":
+					// Only the first chunk has arrived.
+					trace("bytesLoaded: " + stream.bytesLoaded + ", bytesTotal: " + stream.bytesTotal);
+					stream.seek(1.5);
+					break;
+				case "NetStream.Buffer.Full":
+					trace("bytesLoaded: " + stream.bytesLoaded + ", bytesTotal: " + stream.bytesTotal);
+					trace("time: " + stream.time + ", bufferLength: " + stream.bufferLength);
+					break;
+				case "NetStream.Unpause.Notify":
+					// Snaps to the keyframe at 0.5s.
+					stream.seek(0.6);
+					break;
+				case "NetStream.Seek.Notify":
+					trace("time: " + stream.time);
+					break;
+				case "NetStream.Play.Stop":
+					trace("time >= 1.9: " + (stream.time >= 1.9));
+					finished = true;
+					break;
+				case "NetStream.Buffer.Empty":
+					if (finished) {
+						missingStream.play("missing.flv");
+					}
+					break;
+			}
+		}
+
+		private function onEnterFrame(e: Event): void {
+			if (framesUntilResume > 0) {
+				framesUntilResume--;
+				if (framesUntilResume == 0) {
+					stream.resume();
+				}
+			}
+		}
+	}
+}
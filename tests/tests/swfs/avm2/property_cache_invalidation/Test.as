@@ -0,0 +1,102 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// Each access in the loop is a single instruction that sees
+			// objects of different classes.
+			var objects:Array = [
+				new Base(),
+				new Derived(),
+				new Other(),
+				{name: "dynamic", count: 4, describe: function():String {
+					return "closure";
+				}},
+				new Base()
+			];
+			for each (var o:* in objects) {
+				trace(o.name + ": " + o.describe() + " count " + o.count + " id " + o.id);
+				o.count = 7;
+				trace(o.name + " count " + o.count);
+			}
+
+			// Properties that aren't traits are looked up on the prototype
+			// every time.
+			var p:* = new Plain();
+			Plain.prototype.extra = "first";
+			for (var i:int = 0; i < 3; i++) {
+				if (i == 1) {
+					Plain.prototype.extra = "second";
+				}
+				trace("extra: " + p.extra);
+			}
+
+			// Dynamic properties shadow the prototype.
+			var d:* = new Dyn();
+			Dyn.prototype.shade = "prototype";
+			for (i = 0; i < 3; i++) {
+				if (i == 1) {
+					d.shade = "own";
+				}
+				if (i == 2) {
+					delete d.shade;
+				}
+				trace("shade: " + d.shade);
+			}
+		}
+	}
+}
+
+class Base {
+	private static var counter:int = 0;
+
+	private static function next():int {
+		return ++counter;
+	}
+
+	// Initialized with `initproperty` in the constructor, which also runs
+	// for `Derived`.
+	public const id:int = next();
+	public var name:String = "base";
+	public var count:int = 1;
+
+	public function describe():String {
+		return "Base.describe";
+	}
+}
+
+class Derived extends Base {
+	public function Derived() {
+		name = "derived";
+	}
+
+	override public function describe():String {
+		return "Derived.describe";
+	}
+}
+
+dynamic class Other {
+	private var _count:int = 3;
+
+	public function get name():String {
+		return "other";
+	}
+
+	public function get count():int {
+		return _count;
+	}
+
+	public function set count(value:int):void {
+		_count = value * 2;
+	}
+
+	public function describe():String {
+		return "Other.describe";
+	}
+}
+
+class Plain {
+}
+
+dynamic class Dyn {
+}
@@ -0,0 +1,70 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.SharedObject;
+	import flash.net.registerClassAlias;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// Aliases must be registered before the data is read.
+			registerClassAlias("Point3", Point3);
+			registerClassAlias("Inventory", Inventory);
+
+			var so: SharedObject = SharedObject.getLocal("RuffleTypedTest", "/");
+			if (so.data.point === undefined) {
+				trace("No data found. Initializing...");
+				so.data.point = new Point3(1, 2, 3);
+				so.data.inventory = new Inventory(["sword", "shield"]);
+				so.data.list = [new Point3(4, 5, 6), new Inventory(["potion"])];
+				so.flush();
+			} else {
+				trace("point is Point3: " + (so.data.point is Point3));
+				trace("point: " + so.data.point);
+				trace("inventory is Inventory: " + (so.data.inventory is Inventory));
+				trace("inventory: " + so.data.inventory);
+				trace("list[0] is Point3: " + (so.data.list[0] is Point3));
+				trace("list[1] is Inventory: " + (so.data.list[1] is Inventory));
+				trace("list: " + so.data.list);
+			}
+		}
+	}
+}
+
+import flash.utils.IDataInput;
+import flash.utils.IDataOutput;
+import flash.utils.IExternalizable;
+
+class Point3 {
+	public var x: int;
+	public var y: int;
+	public var z: int;
+
+	public function Point3(x: int = 0, y: int = 0, z: int = 0) {
+		this.x = x;
+		this.y = y;
+		this.z = z;
+	}
+
+	public function toString(): String {
+		return "(" + x + ", " + y + ", " + z + ")";
+	}
+}
+
+class Inventory implements IExternalizable {
+	public var items: Array;
+
+	public function Inventory(items: Array = null) {
+		this.items = items;
+	}
+
+	public function writeExternal(output: IDataOutput): void {
+		output.writeObject(items);
+	}
+
+	public function readExternal(input: IDataInput): void {
+		items = input.readObject();
+	}
+
+	public function toString(): String {
+		return "[" + items.join(" ") + "]";
+	}
+}
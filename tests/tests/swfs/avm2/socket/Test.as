@@ -0,0 +1,33 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.ProgressEvent;
+	import flash.net.Socket;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var failing: Socket = new Socket();
+			failing.addEventListener("ioError", function(e: IOErrorEvent): void {
+				trace("ioError: " + e.text);
+			});
+			failing.connect("localhost", 8002);
+
+			var socket: Socket = new Socket();
+			socket.addEventListener("connect", function(e: Event): void {
+				trace("connect: " + e.target.connected);
+				e.target.writeUTFBytes("Hello");
+				e.target.flush();
+			});
+			socket.addEventListener("socketData", function(e: ProgressEvent): void {
+				trace("data: " + e.target.bytesAvailable);
+				trace(e.target.readUTFBytes(e.target.bytesAvailable));
+			});
+			socket.addEventListener("close", function(e: Event): void {
+				trace("close: " + e.target.connected);
+			});
+			socket.connect("localhost", 8001);
+			trace("connecting: " + socket.connected);
+		}
+	}
+}
@@ -0,0 +1,67 @@
+package {
+	import flash.display.MovieClip;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// 8 stereo frames of 16-bit samples, with the left channel
+			// counting up and the right channel counting down.
+			var pcm:ByteArray = new ByteArray();
+			for (var i:int = 0; i < 8; i++) {
+				pcm.writeShort(i * 4096);
+				pcm.writeShort(-i * 4096);
+			}
+			pcm.position = 0;
+
+			var sound:Sound = new Sound();
+			sound.loadPCMFromByteArray(pcm, 8, "short", true, 44100);
+			trace("length: " + Math.round(sound.length * 1000) / 1000);
+
+			// Consecutive extractions continue where the last one stopped.
+			extract(sound, 3);
+			extract(sound, 3);
+			// A start position seeks, and following extractions continue
+			// from there.
+			extract(sound, 2, 1);
+			extract(sound, 2);
+			extract(sound, 1, 7);
+
+			try {
+				sound.loadPCMFromByteArray(pcm, 1, "short");
+			} catch (e:Error) {
+				trace("reload: " + e.errorID);
+			}
+
+			var invalid:Sound = new Sound();
+			try {
+				invalid.loadPCMFromByteArray(pcm, 1, "double");
+			} catch (e:ArgumentError) {
+				trace("invalid format: " + e.errorID);
+			}
+
+			// Float samples are read as 32-bit floats, and mono samples are
+			// played on both channels.
+			var floats:ByteArray = new ByteArray();
+			floats.writeFloat(0.5);
+			floats.writeFloat(-0.25);
+			floats.position = 0;
+			var mono:Sound = new Sound();
+			mono.loadPCMFromByteArray(floats, 2, "float", false);
+			extract(mono, 2);
+		}
+
+		private function extract(sound:Sound, length:Number, startPosition:Number = -1):void {
+			var target:ByteArray = new ByteArray();
+			var extracted:Number = sound.extract(target, length, startPosition);
+			var frames:Array = [];
+			target.position = 0;
+			while (target.bytesAvailable >= 8) {
+				var left:Number = target.readFloat();
+				var right:Number = target.readFloat();
+				frames.push(left.toFixed(3) + "/" + right.toFixed(3));
+			}
+			trace("extract(" + length + ", " + startPosition + "): " + extracted + " [" + frames.join(" ") + "]");
+		}
+	}
+}
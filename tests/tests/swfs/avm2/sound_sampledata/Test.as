@@ -0,0 +1,35 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	public class Test extends MovieClip {
+		private var requests:int = 0;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			var channel:SoundChannel = sound.play();
+			trace("channel: " + (channel != null));
+			channel.addEventListener(Event.SOUND_COMPLETE, function(e:Event):void {
+				trace("soundComplete after " + requests + " requests");
+			});
+		}
+
+		private function onSampleData(e:SampleDataEvent):void {
+			requests++;
+			trace("sampleData: position " + e.position + " data length " + e.data.length);
+
+			// Only the first 8192 of the 9000 frames are played, then a full
+			// block, then fewer than 2048 frames, which ends the sound.
+			var frames:int = [9000, 2048, 100][requests - 1];
+			for (var i:int = 0; i < frames; i++) {
+				var sample:Number = Math.sin(i / 10) * 0.25;
+				e.data.writeFloat(sample);
+				e.data.writeFloat(sample);
+			}
+		}
+	}
+}
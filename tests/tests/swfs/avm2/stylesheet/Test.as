@@ -0,0 +1,110 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.StyleSheet;
+	import flash.text.TextField;
+	import flash.text.TextFormat;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var sheet:StyleSheet = new StyleSheet();
+
+			trace("///sheet.parseCSS(...)");
+			sheet.parseCSS("/* Headings */ P { color: #00FF00; } .Head, .title { font-size: 20px; font-weight: bold }\n" +
+				".head { color: #0000FF; font-family: sans-serif, Arial } .hide { display: none; }");
+			trace(sorted(sheet.styleNames));
+			traceStyle(sheet.getStyle("p"));
+			traceStyle(sheet.getStyle(".HEAD"));
+			traceStyle(sheet.getStyle(".title"));
+			traceStyle(sheet.getStyle(".hide"));
+			trace(sheet.getStyle("missing"));
+
+			trace("///sheet.parseCSS(malformed)");
+			sheet.parseCSS("span { color: #FF0000");
+			trace(sorted(sheet.styleNames));
+
+			trace("///getStyle returns a copy");
+			sheet.getStyle("p").color = "#FFFFFF";
+			traceStyle(sheet.getStyle("p"));
+
+			trace("///sheet.setStyle(...)");
+			var style:Object = {fontStyle: "italic"};
+			sheet.setStyle("Span", style);
+			style.fontStyle = "normal";
+			traceStyle(sheet.getStyle("span"));
+			sheet.setStyle("span", null);
+			trace(sorted(sheet.styleNames));
+
+			trace("///sheet.transform(...)");
+			var format:TextFormat = sheet.transform({
+				color: "#FF0000",
+				fontSize: "14px",
+				fontWeight: "bold",
+				fontStyle: "italic",
+				fontFamily: "'Courier New', serif",
+				textAlign: "center",
+				textDecoration: "underline",
+				marginLeft: "5",
+				marginRight: "6px",
+				textIndent: "7",
+				leading: "2",
+				letterSpacing: "1",
+				kerning: "true",
+				unknown: "value"
+			});
+			trace(format.color + " " + format.size + " " + format.bold + " " + format.italic + " " + format.font);
+			trace(format.align + " " + format.underline + " " + format.leftMargin + " " + format.rightMargin);
+			trace(format.indent + " " + format.leading + " " + format.letterSpacing + " " + format.kerning);
+			format = sheet.transform({color: "red", fontSize: "big"});
+			trace(format.color + " " + format.size);
+
+			trace("///TextField.styleSheet");
+			var field:TextField = new TextField();
+			field.multiline = true;
+			field.styleSheet = sheet;
+			trace(field.styleSheet == sheet);
+			var html:String = "<p class=\"head\">Title</p><p>Body <span class=\"hide\">secret</span>text</p>";
+			field.htmlText = html;
+			trace(escape(field.text));
+			trace(field.htmlText == html);
+			traceFormat(field.getTextFormat(0, 1));
+			traceFormat(field.getTextFormat(6, 7));
+
+			trace("///Changes to the style sheet apply once it is set again");
+			sheet.setStyle(".head", {color: "#FF0000"});
+			traceFormat(field.getTextFormat(0, 1));
+			field.styleSheet = sheet;
+			traceFormat(field.getTextFormat(0, 1));
+
+			trace("///field.styleSheet = null");
+			field.styleSheet = null;
+			trace(field.styleSheet);
+			field.htmlText = html;
+			trace(escape(field.text));
+			traceFormat(field.getTextFormat(0, 1));
+
+			trace("///sheet.clear()");
+			sheet.clear();
+			trace(sheet.styleNames.length);
+		}
+
+		private static function sorted(names:Array):String {
+			return names.sort().join(",");
+		}
+
+		private static function traceStyle(style:Object):void {
+			var keys:Array = [];
+			for (var key:String in style) {
+				keys.push(key + "=" + style[key]);
+			}
+			trace(keys.sort().join(", "));
+		}
+
+		private static function traceFormat(format:TextFormat):void {
+			trace(format.color + " " + format.size + " " + format.bold);
+		}
+
+		private static function escape(text:String):String {
+			return text.split("\r").join("|").split("\n").join("|");
+		}
+	}
+}
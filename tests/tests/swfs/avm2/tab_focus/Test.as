@@ -0,0 +1,98 @@
+package {
+	import flash.display.DisplayObject;
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.FocusEvent;
+	import flash.events.KeyboardEvent;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		private var cancelNextChange:Boolean = false;
+
+		public function Test() {
+			var a:Sprite = makeButton("a", 10, 10);
+			var b:Sprite = makeButton("b", 100, 10);
+			var c:Sprite = makeButton("c", 10, 100);
+			var d:Sprite = new Sprite();
+			d.name = "d";
+			d.x = 100;
+			d.y = 100;
+			addChild(d);
+			var e:Sprite = makeButton("e", 0, 0);
+			d.addChild(e);
+
+			trace("/// tabEnabled");
+			trace(a.tabEnabled + " " + d.tabEnabled);
+
+			trace("/// tabIndex");
+			trace(a.tabIndex);
+			try {
+				a.tabIndex = -1;
+			} catch (err:RangeError) {
+				trace(err.errorID);
+			}
+
+			trace("/// tabChildren");
+			trace(d.tabChildren);
+
+			trace("/// focusRect");
+			trace(a.focusRect);
+			a.focusRect = false;
+			trace(a.focusRect);
+			a.focusRect = true;
+			trace(a.focusRect);
+			a.focusRect = "yes";
+			trace(a.focusRect);
+			a.focusRect = null;
+			trace(a.focusRect);
+			trace(stage.stageFocusRect);
+
+			trace("/// Input");
+			stage.addEventListener(FocusEvent.KEY_FOCUS_CHANGE, function(event:FocusEvent):void {
+				trace("keyFocusChange: " + event.relatedObject.name + " " + event.shiftKey);
+				if (cancelNextChange) {
+					event.preventDefault();
+					cancelNextChange = false;
+				}
+			});
+			stage.addEventListener(KeyboardEvent.KEY_UP, function(event:KeyboardEvent):void {
+				// Ignore Shift.
+				if (event.keyCode == 16) {
+					return;
+				}
+				if (event.keyCode == 84) {
+					// T stops the children of d from being focused.
+					d.tabChildren = false;
+				} else if (event.keyCode == 80) {
+					// P prevents the next focus change.
+					cancelNextChange = true;
+				} else if (event.keyCode == 73) {
+					// I orders the buttons with tabIndex.
+					c.tabIndex = 1;
+					b.tabIndex = 2;
+					a.tabIndex = 3;
+				}
+				var focus:DisplayObject = stage.focus;
+				trace("keyUp " + event.keyCode + ": " + (focus ? focus.name : null));
+			});
+		}
+
+		private function makeButton(name:String, x:Number, y:Number):Sprite {
+			var button:Sprite = new Sprite();
+			button.name = name;
+			button.x = x;
+			button.y = y;
+			button.buttonMode = true;
+			button.graphics.beginFill(0x0000FF);
+			button.graphics.drawRect(0, 0, 50, 50);
+			button.graphics.endFill();
+			button.addEventListener(MouseEvent.CLICK, function(event:MouseEvent):void {
+				trace("click: " + name);
+			});
+			if (name != "e") {
+				addChild(button);
+			}
+			return button;
+		}
+	}
+}
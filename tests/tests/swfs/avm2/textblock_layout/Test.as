@@ -0,0 +1,188 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.EventDispatcher;
+	import flash.geom.Rectangle;
+	import flash.text.engine.ContentElement;
+	import flash.text.engine.ElementFormat;
+	import flash.text.engine.FontDescription;
+	import flash.text.engine.FontLookup;
+	import flash.text.engine.GroupElement;
+	import flash.text.engine.TabAlignment;
+	import flash.text.engine.TabStop;
+	import flash.text.engine.TextBlock;
+	import flash.text.engine.TextElement;
+	import flash.text.engine.TextLine;
+	import flash.text.engine.TextLineMirrorRegion;
+
+	// "Cff Sans" is a DefineFont4 font where every glyph is half an em wide,
+	// so each character is 10 pixels wide at a size of 20.
+	public class Test extends MovieClip {
+		private var format:ElementFormat = new ElementFormat(
+			new FontDescription("Cff Sans", "normal", "normal", FontLookup.EMBEDDED_CFF), 20);
+
+		public function Test() {
+			testLineBreaking();
+			testTabs();
+			testAtoms();
+			testBidi();
+			testMirrorRegions();
+			testInvalidation();
+		}
+
+		private function traceLines(block:TextBlock, width:Number, fitSomething:Boolean = false):void {
+			var line:TextLine = null;
+			while (true) {
+				line = block.createTextLine(line, width, 0, fitSomething);
+				trace(block.textLineCreationResult);
+				if (line == null) {
+					break;
+				}
+				trace(line.textBlockBeginIndex + " " + line.rawTextLength + " " + line.atomCount + " " +
+					line.ascent + " " + line.descent + " " + line.textWidth);
+			}
+		}
+
+		private function testLineBreaking():void {
+			trace("///Breaking at spaces");
+			traceLines(new TextBlock(new TextElement("aaa bbb ccc", format)), 75);
+
+			trace("///Forced breaks");
+			traceLines(new TextBlock(new TextElement("aa\nbb", format)), 1000);
+
+			trace("///A word longer than the line");
+			traceLines(new TextBlock(new TextElement("abcdefgh", format)), 35);
+
+			trace("///Not even one atom fits");
+			traceLines(new TextBlock(new TextElement("abc", format)), 5);
+
+			trace("///Not even one atom fits, with fitSomething");
+			traceLines(new TextBlock(new TextElement("abc", format)), 5, true);
+		}
+
+		private function tabPositions(text:String, tabStops:Vector.<TabStop>):void {
+			var block:TextBlock = new TextBlock(new TextElement(text, format), tabStops);
+			var line:TextLine = block.createTextLine(null, 1000);
+			var positions:Array = [];
+			for (var i:int = 0; i < line.atomCount; i++) {
+				positions.push(line.getAtomBounds(i).x);
+			}
+			trace(line.hasTabs + " " + positions);
+		}
+
+		private function testTabs():void {
+			trace("///Default tab stops");
+			tabPositions("a\tb\tc", null);
+
+			trace("///Start tab stop");
+			tabPositions("a\tbb", new <TabStop>[new TabStop(TabAlignment.START, 100)]);
+
+			trace("///End tab stop");
+			tabPositions("a\tbb", new <TabStop>[new TabStop(TabAlignment.END, 100)]);
+
+			trace("///Center tab stop");
+			tabPositions("a\tbb", new <TabStop>[new TabStop(TabAlignment.CENTER, 100)]);
+
+			trace("///Decimal tab stop");
+			tabPositions("a\t12.5", new <TabStop>[new TabStop(TabAlignment.DECIMAL, 100, ".")]);
+
+			trace("///Tab past the last tab stop");
+			tabPositions("abcdefghijk\tb", new <TabStop>[new TabStop(TabAlignment.START, 100)]);
+
+			trace("///No tabs");
+			tabPositions("ab", null);
+		}
+
+		private function testAtoms():void {
+			var block:TextBlock = new TextBlock(new TextElement("ab c", format));
+			var line:TextLine = block.createTextLine(null, 1000);
+			trace("///line.atomCount");
+			trace(line.atomCount);
+			for (var i:int = 0; i < line.atomCount; i++) {
+				trace("///Atom " + i);
+				trace(line.getAtomBounds(i));
+				trace(line.getAtomCenter(i));
+				trace(line.getAtomTextBlockBeginIndex(i) + " " + line.getAtomTextBlockEndIndex(i));
+				trace(line.getAtomBidiLevel(i));
+				trace(line.getAtomWordBoundaryOnLeft(i));
+			}
+
+			trace("///line.getAtomIndexAtCharIndex(3)");
+			trace(line.getAtomIndexAtCharIndex(3));
+
+			trace("///line.getAtomBounds(4)");
+			try {
+				line.getAtomBounds(4);
+			} catch (e:RangeError) {
+				trace(e.errorID);
+			}
+		}
+
+		private function testBidi():void {
+			trace("///Right-to-left text in a left-to-right block");
+			var block:TextBlock = new TextBlock(new TextElement("ab \u05D0\u05D1(", format));
+			var line:TextLine = block.createTextLine(null, 1000);
+			for (var i:int = 0; i < line.atomCount; i++) {
+				trace(line.getAtomBounds(i).x + " " + line.getAtomBidiLevel(i));
+			}
+
+			trace("///Left-to-right text in a right-to-left block");
+			block = new TextBlock(new TextElement("ab \u05D0", format));
+			block.bidiLevel = 1;
+			line = block.createTextLine(null, 1000);
+			for (i = 0; i < line.atomCount; i++) {
+				trace(line.getAtomBounds(i).x + " " + line.getAtomBidiLevel(i));
+			}
+		}
+
+		private function testMirrorRegions():void {
+			var mirror:EventDispatcher = new EventDispatcher();
+			var plain:TextElement = new TextElement("ab ", format);
+			var mirrored:TextElement = new TextElement("cd ef", format, mirror);
+			var elements:Vector.<ContentElement> = new <ContentElement>[plain, mirrored];
+			var block:TextBlock = new TextBlock(new GroupElement(elements));
+
+			trace("///Mirror regions of a line with no mirrored elements");
+			var line:TextLine = block.createTextLine(null, 25);
+			trace(line.mirrorRegions);
+
+			trace("///Mirror regions split across lines");
+			line = block.createTextLine(line, 25);
+			trace(line.mirrorRegions.length);
+			var region:TextLineMirrorRegion = line.mirrorRegions[0];
+			trace(region.bounds);
+			trace(region.element == mirrored);
+			trace(region.mirror == mirror);
+			trace(region.textLine == line);
+			trace(region.previousRegion + " " + region.nextRegion);
+			trace(line.getMirrorRegion(mirror) == region);
+
+			line = block.createTextLine(line, 25);
+			trace(line.mirrorRegions[0].bounds);
+
+			trace("///line.getMirrorRegion(new EventDispatcher())");
+			trace(line.getMirrorRegion(new EventDispatcher()));
+		}
+
+		private function testInvalidation():void {
+			var element:TextElement = new TextElement("aaa bbb", format);
+			var block:TextBlock = new TextBlock(element);
+			var first:TextLine = block.createTextLine(null, 45);
+
+			trace("///Lines after changing the text");
+			element.text = "cccccccc dd";
+			trace(first.validity);
+			trace(block.firstInvalidLine == first);
+			var second:TextLine = block.createTextLine(first, 45);
+			trace(second.textBlockBeginIndex + " " + second.rawTextLength);
+
+			trace("///Lines after releaseLineCreationData");
+			block.releaseLineCreationData();
+			first = block.createTextLine(null, 45);
+			second = block.createTextLine(first, 45);
+			block.releaseLineCreationData();
+			var third:TextLine = block.createTextLine(second, 45);
+			trace(first.rawTextLength + " " + second.rawTextLength + " " + third);
+			trace(block.textLineCreationResult);
+		}
+	}
+}
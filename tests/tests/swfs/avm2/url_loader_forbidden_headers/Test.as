@@ -0,0 +1,28 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+	import flash.net.URLRequestHeader;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var request:URLRequest = new URLRequest("data.txt");
+			// Headers that the browser controls are never sent.
+			request.requestHeaders = [
+				new URLRequestHeader("Host", "example.com"),
+				new URLRequestHeader("X-First", "1"),
+				new URLRequestHeader("content-length", "5"),
+				new URLRequestHeader("REFERER", "http://example.com/"),
+				new URLRequestHeader("X-Second", "two"),
+				new URLRequestHeader("Cookie", "a=b")
+			];
+
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: " + loader.data);
+			});
+			loader.load(request);
+		}
+	}
+}
@@ -0,0 +1,35 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.HTTPStatusEvent;
+	import flash.events.IOErrorEvent;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+	import flash.net.URLRequestHeader;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var request:URLRequest = new URLRequest("missing.txt");
+			request.requestHeaders = [new URLRequestHeader("X-Attempt", "1")];
+
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(Event.OPEN, function(e:Event):void {
+				trace("open");
+			});
+			loader.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+				trace("httpStatus: " + e.status + " redirected " + e.redirected);
+				trace("responseURL: " + e.responseURL);
+				for each (var header:URLRequestHeader in e.responseHeaders) {
+					trace("header " + header.name + ": " + header.value);
+				}
+			});
+			loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+				trace("ioError: " + e.text);
+			});
+			loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete");
+			});
+			loader.load(request);
+		}
+	}
+}
@@ -0,0 +1,33 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.HTTPStatusEvent;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+	import flash.net.URLRequestHeader;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var request:URLRequest = new URLRequest("data.txt");
+			trace("default requestHeaders: " + request.requestHeaders.length);
+			request.requestHeaders = [new URLRequestHeader("X-First", "1")];
+			request.requestHeaders.push(new URLRequestHeader("X-Second", "two"));
+
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(Event.OPEN, function(e:Event):void {
+				trace("open");
+			});
+			loader.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+				trace("httpStatus: " + e.status + " redirected " + e.redirected);
+				trace("responseURL: " + e.responseURL.substr(e.responseURL.lastIndexOf("/") + 1));
+				for each (var header:URLRequestHeader in e.responseHeaders) {
+					trace("header " + header.name + ": " + header.value);
+				}
+			});
+			loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: " + loader.data);
+			});
+			loader.load(request);
+		}
+	}
+}
@@ -0,0 +1,43 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// The bodies of `badBranch` and `badStack` are patched after
+			// compiling so that they fail verification.
+			callTwice("badBranch", badBranch);
+			callTwice("badStack", badStack);
+			trace("good: " + good());
+		}
+
+		private function callTwice(name:String, method:Function):void {
+			for (var i:int = 0; i < 2; i++) {
+				try {
+					method();
+					trace(name + " returned");
+				} catch (e:VerifyError) {
+					trace(name + " threw VerifyError " + e.errorID);
+					trace(e.message);
+				}
+			}
+		}
+
+		// Patched to: jump +0x7fffff; nop; nop
+		private function badBranch():void {
+			var x:int = 90;
+			x = 91;
+		}
+
+		// Patched to: pushtrue; iftrue +2; pushbyte 1
+		// so that the branch and the fallthrough meet with different stack
+		// depths.
+		private function badStack():void {
+			var x:int = 92;
+			x = 93;
+		}
+
+		private function good():int {
+			return 5;
+		}
+	}
+}
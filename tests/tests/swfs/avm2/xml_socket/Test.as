@@ -0,0 +1,44 @@
+package {
+	import flash.display.MovieClip;
+	import flash.errors.IOError;
+	import flash.events.DataEvent;
+	import flash.events.Event;
+	import flash.events.SecurityErrorEvent;
+	import flash.net.XMLSocket;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var unconnected: XMLSocket = new XMLSocket();
+			try {
+				unconnected.send("<hello/>");
+			} catch (e: IOError) {
+				trace("send: " + e.errorID);
+			}
+			try {
+				unconnected.connect("localhost", 70000);
+			} catch (e: SecurityError) {
+				trace("connect: " + e.errorID);
+			}
+
+			var denied: XMLSocket = new XMLSocket();
+			denied.addEventListener("securityError", function(e: SecurityErrorEvent): void {
+				trace("securityError: " + e.text);
+			});
+			denied.connect("example.com", 8001);
+
+			var socket: XMLSocket = new XMLSocket();
+			socket.addEventListener("connect", function(e: Event): void {
+				trace("connect: " + socket.connected);
+				socket.send("<hello/>");
+			});
+			socket.addEventListener("data", function(e: DataEvent): void {
+				trace("data: " + e.data);
+			});
+			socket.addEventListener("close", function(e: Event): void {
+				trace("close: " + socket.connected);
+			});
+			socket.connect("localhost", 8001);
+			trace("connecting: " + socket.connected);
+		}
+	}
+}
@@ -0,0 +1,344 @@
+use crate::util::options::{HttpHeader, HttpOptions, HttpRequest, SocketEvent, SocketOptions};
+use anyhow::{anyhow, Result};
+use ruffle_core::backend::navigator::{
+    NavigationMethod, NavigatorBackend, NullExecutor, NullNavigatorBackend, OwnedFuture, Request,
+    Response, ResponseBody, StreamingResponse,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::time::Duration;
+use url::Url;
+
+/// A navigator that loads files relative to the test directory, answering
+/// requests through a `FakeHttpServer` and connecting sockets to a
+/// `FakeServer`.
+pub struct TestNavigatorBackend {
+    inner: NullNavigatorBackend,
+    http_server: FakeHttpServer,
+    server: FakeServer,
+}
+
+impl TestNavigatorBackend {
+    pub fn new(
+        path: &Path,
+        executor: &NullExecutor,
+        http_server: FakeHttpServer,
+        server: FakeServer,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: NullNavigatorBackend::with_base_path(path, executor)?,
+            http_server,
+            server,
+        })
+    }
+}
+
+impl NavigatorBackend for TestNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        target: String,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, target, vars_method)
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
+        let answer = self.http_server.receive(&request);
+        let url = request.url().to_string();
+        let fetch = self.inner.fetch(request);
+        let (status, headers) = match answer {
+            Some(answer) => answer,
+            None => return fetch,
+        };
+
+        Box::pin(async move {
+            if !(200..300).contains(&status) {
+                return Err(Error::HttpNotOk {
+                    url,
+                    status,
+                    redirected: false,
+                    headers,
+                });
+            }
+
+            let mut response = fetch.await?;
+            response.status = status;
+            response.headers = headers;
+            Ok(response)
+        })
+    }
+
+    fn fetch_streaming(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
+        let fetch = self.fetch(request);
+        Box::pin(async move {
+            let response = fetch.await?;
+            // Announce the size of the file, like an HTTP server would.
+            let mut headers = response.headers;
+            headers.push((
+                "Content-Length".to_string(),
+                response.body.len().to_string(),
+            ));
+            let chunks = response
+                .body
+                .chunks(STREAMING_CHUNK_SIZE)
+                .map(|chunk| chunk.to_vec())
+                .collect();
+            Ok(StreamingResponse {
+                url: response.url,
+                status: response.status,
+                redirected: response.redirected,
+                headers,
+                body: Box::new(ChunkedBody(chunks)),
+            })
+        })
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.server.accept(host, port, handle, receiver, sender);
+    }
+}
+
+/// The size of the chunks that streamed responses are handed over in.
+///
+/// This is small enough to split the tags of media files, so that every
+/// streaming test also covers data that arrives piece by piece.
+const STREAMING_CHUNK_SIZE: usize = 1000;
+
+/// A response body that was loaded in full, handed over in chunks.
+struct ChunkedBody(VecDeque<Vec<u8>>);
+
+impl ResponseBody for ChunkedBody {
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let chunk = self.0.pop_front();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
+/// An in-process HTTP server that answers requests as configured by the
+/// `http` test option, and records the requests it receives.
+#[derive(Clone)]
+pub struct FakeHttpServer(Rc<RefCell<FakeHttpServerState>>);
+
+struct FakeHttpServerState {
+    options: Option<HttpOptions>,
+    received: Vec<HttpRequest>,
+}
+
+impl FakeHttpServer {
+    pub fn new(options: Option<HttpOptions>) -> Self {
+        Self(Rc::new(RefCell::new(FakeHttpServerState {
+            options,
+            received: vec![],
+        })))
+    }
+
+    /// Record a request, returning the status and headers to answer it with,
+    /// or `None` if the test doesn't use the server.
+    fn receive(&self, request: &Request) -> Option<(u16, Vec<(String, String)>)> {
+        let mut state = self.0.borrow_mut();
+        let options = state.options.as_ref()?;
+        let answer = (
+            options.status,
+            options
+                .response_headers
+                .iter()
+                .map(|header| (header.name.clone(), header.value.clone()))
+                .collect(),
+        );
+
+        state.received.push(HttpRequest {
+            url: request.url().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| HttpHeader {
+                    name: name.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        });
+
+        Some(answer)
+    }
+
+    /// Check that the movie made the expected requests.
+    pub fn finish(&self) -> Result<()> {
+        let state = self.0.borrow();
+        match &state.options {
+            Some(options) if options.requests != state.received => Err(anyhow!(
+                "Expected the requests {:?}, but got {:?}",
+                options.requests,
+                state.received
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An in-process server that plays the conversation of the `socket` test
+/// option with the first connection made to its address.
+///
+/// Only the host of the server may be connected to: connections to other
+/// hosts are refused by the socket policy, and connections to other ports of
+/// the host fail.
+///
+/// The server doesn't run on its own: `poll` must be called every frame, so
+/// that the events of a test always happen on the same frames.
+#[derive(Clone)]
+pub struct FakeServer(Rc<RefCell<FakeServerState>>);
+
+struct FakeServerState {
+    options: Option<SocketOptions>,
+    connection: Option<Connection>,
+}
+
+struct Connection {
+    handle: SocketHandle,
+    events: VecDeque<SocketEvent>,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+
+    /// Data received from the movie that wasn't matched yet.
+    received: Vec<u8>,
+}
+
+impl FakeServer {
+    pub fn new(options: Option<SocketOptions>) -> Self {
+        Self(Rc::new(RefCell::new(FakeServerState {
+            options,
+            connection: None,
+        })))
+    }
+
+    fn accept(
+        &self,
+        host: String,
+        port: u16,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let mut state = self.0.borrow_mut();
+        let events = match state.options.take() {
+            Some(options) if options.host == host && options.port == port => options.events,
+            options => {
+                let allowed = options
+                    .as_ref()
+                    .map_or(false, |options| options.host == host);
+                state.options = options;
+                let state = if allowed {
+                    ConnectionState::Failed
+                } else {
+                    ConnectionState::NotAllowed
+                };
+                let _ = sender.send(SocketAction::Connect(handle, state));
+                return;
+            }
+        };
+
+        let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Connected));
+        state.connection = Some(Connection {
+            handle,
+            events: events.into(),
+            receiver,
+            sender,
+            received: vec![],
+        });
+    }
+
+    /// Play the events of the conversation until one has to wait for the
+    /// movie.
+    pub fn poll(&self) -> Result<()> {
+        let mut state = self.0.borrow_mut();
+        let connection = match &mut state.connection {
+            Some(connection) => connection,
+            None => return Ok(()),
+        };
+
+        while let Some(event) = connection.events.front() {
+            match event {
+                SocketEvent::Receive { expected } => {
+                    while let Ok(data) = connection.receiver.try_recv() {
+                        connection.received.extend(data);
+                    }
+                    if connection.received.len() < expected.len() {
+                        return Ok(());
+                    }
+
+                    let received: Vec<u8> = connection.received.drain(..expected.len()).collect();
+                    if received != expected.as_bytes() {
+                        return Err(anyhow!(
+                            "Socket expected to receive {:?}, but got {:?}",
+                            expected,
+                            String::from_utf8_lossy(&received)
+                        ));
+                    }
+                }
+                SocketEvent::Send { payload } => {
+                    let data = payload.as_bytes().to_vec();
+                    let _ = connection
+                        .sender
+                        .send(SocketAction::Data(connection.handle, data));
+                }
+                SocketEvent::WaitForDisconnect => match connection.receiver.try_recv() {
+                    Err(TryRecvError::Disconnected) => {}
+                    Err(TryRecvError::Empty) => return Ok(()),
+                    Ok(data) => {
+                        return Err(anyhow!(
+                            "Socket expected to be closed, but got {:?}",
+                            String::from_utf8_lossy(&data)
+                        ))
+                    }
+                },
+                SocketEvent::Disconnect => {
+                    let _ = connection
+                        .sender
+                        .send(SocketAction::Close(connection.handle));
+                }
+            }
+
+            connection.events.pop_front();
+        }
+
+        Ok(())
+    }
+
+    /// Check that the whole conversation took place.
+    pub fn finish(&self) -> Result<()> {
+        let state = self.0.borrow();
+        if state.options.is_some() {
+            return Err(anyhow!("Socket was never connected to"));
+        }
+
+        match &state.connection {
+            Some(connection) if !connection.events.is_empty() => Err(anyhow!(
+                "Socket conversation stopped before {:?}",
+                connection.events.front()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -22,6 +22,8 @@ pub struct TestOptions {
     pub ignore: bool,
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
+    pub socket: Option<SocketOptions>,
+    pub http: Option<HttpOptions>,
 }
 
 impl Default for TestOptions {
@@ -34,6 +36,8 @@ impl Default for TestOptions {
             ignore: false,
             approximations: None,
             player_options: PlayerOptions::default(),
+            socket: None,
+            http: None,
         }
     }
 }
@@ -81,6 +85,75 @@ impl Approximations {
     }
 }
 
+/// The conversation that the fake socket server of a test has with the
+/// movie.
+#[derive(Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct SocketOptions {
+    pub host: String,
+    pub port: u16,
+    pub events: Vec<SocketEvent>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum SocketEvent {
+    /// Wait for the movie to send this data.
+    Receive { expected: String },
+
+    /// Send this data to the movie.
+    Send { payload: String },
+
+    /// Wait for the movie to close the connection.
+    WaitForDisconnect,
+
+    /// Close the connection.
+    Disconnect,
+}
+
+/// How the fake HTTP server of a test answers the requests of the movie.
+///
+/// The body of every response is still the file at the requested path in the
+/// test directory.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpOptions {
+    /// The status code of every response.
+    pub status: u16,
+
+    /// The headers of every response.
+    pub response_headers: Vec<HttpHeader>,
+
+    /// The requests that the movie is expected to make, in order.
+    pub requests: Vec<HttpRequest>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            status: 200,
+            response_headers: vec![],
+            requests: vec![],
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpRequest {
+    pub url: String,
+
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct PlayerOptions {
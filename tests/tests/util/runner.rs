@@ -1,3 +1,4 @@
+use crate::util::navigator::{FakeHttpServer, FakeServer, TestNavigatorBackend};
 use crate::util::test::Test;
 use anyhow::{anyhow, Result};
 use ruffle_core::backend::audio::{
@@ -5,7 +6,8 @@ use ruffle_core::backend::audio::{
     SoundTransform,
 };
 use ruffle_core::backend::log::LogBackend;
-use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::navigator::NullExecutor;
+use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
 use ruffle_core::events::KeyCode;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
 use ruffle_core::impl_audio_mixer_backend;
@@ -67,6 +69,49 @@ impl LogBackend for TestLogBackend {
     }
 }
 
+/// A `UiBackend` with a clipboard that only lives as long as the test.
+struct TestUiBackend {
+    clipboard: String,
+}
+
+impl TestUiBackend {
+    pub fn new() -> Self {
+        Self {
+            clipboard: String::new(),
+        }
+    }
+}
+
+impl UiBackend for TestUiBackend {
+    fn mouse_visible(&self) -> bool {
+        true
+    }
+
+    fn set_mouse_visible(&mut self, _visible: bool) {}
+
+    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = content;
+    }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.clone()
+    }
+
+    fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
+        Ok(())
+    }
+
+    fn display_unsupported_message(&self) {}
+
+    fn display_root_movie_download_failed_message(&self) {}
+
+    fn message(&self, _message: &str) {}
+
+    fn open_virtual_keyboard(&self) {}
+}
+
 /// Loads an SWF and runs it through the Ruffle core for a number of frames.
 /// Tests that the trace output matches the given expected output.
 pub fn run_swf(
@@ -81,10 +126,18 @@ pub fn run_swf(
     let frame_time = 1000.0 / movie.frame_rate().to_f64();
     let frame_time_duration = Duration::from_millis(frame_time as u64);
     let trace_output = Rc::new(RefCell::new(String::new()));
+    let http_server = FakeHttpServer::new(test.options.http.clone());
+    let socket_server = FakeServer::new(test.options.socket.clone());
 
     let builder = PlayerBuilder::new()
         .with_log(TestLogBackend::new(trace_output.clone()))
-        .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor)?)
+        .with_ui(TestUiBackend::new())
+        .with_navigator(TestNavigatorBackend::new(
+            base_path,
+            &executor,
+            http_server.clone(),
+            socket_server.clone(),
+        )?)
         .with_max_execution_duration(Duration::from_secs(300))
         .with_viewport_dimensions(
             movie.width().to_pixels() as u32,
@@ -128,9 +181,23 @@ pub fn run_swf(
 
         player.lock().unwrap().run_frame();
         player.lock().unwrap().update_timers(frame_time);
+        player.lock().unwrap().update_streams(frame_time);
+        player.lock().unwrap().update_dynamic_sounds();
+        socket_server.poll()?;
+        player.lock().unwrap().update_sockets();
+        player.lock().unwrap().update_local_connections();
         executor.run();
 
         injector.next(|evt, _btns_down| {
+            if let AutomatedEvent::SetClipboardText { text } = evt {
+                player
+                    .lock()
+                    .unwrap()
+                    .ui_mut()
+                    .set_clipboard_content(text.to_owned());
+                return;
+            }
+
             player.lock().unwrap().handle_event(match evt {
                 AutomatedEvent::MouseDown { pos, btn } => PlayerEvent::MouseDown {
                     x: pos.0,
@@ -155,7 +222,14 @@ pub fn run_swf(
                     key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
                     key_char: None,
                 },
-                AutomatedEvent::Wait => unreachable!(),
+                AutomatedEvent::KeyUp { key_code } => PlayerEvent::KeyUp {
+                    key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
+                    key_char: None,
+                },
+                AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
+                    codepoint: *codepoint,
+                },
+                AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => unreachable!(),
             });
         });
         // Rendering has side-effects (such as processing 'DisplayObject.scrollRect' updates)
@@ -204,6 +278,8 @@ pub fn run_swf(
     before_end(player)?;
 
     executor.run();
+    http_server.finish()?;
+    socket_server.finish()?;
 
     let trace = trace_output.borrow().clone();
     Ok(trace)
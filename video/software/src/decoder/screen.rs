@@ -12,6 +12,12 @@ pub enum ScreenError {
     #[error("Unexpected end of file")]
     UnexpectedEOF,
 
+    #[error("Empty video frame")]
+    EmptyFrame,
+
+    #[error("Invalid image size: {0}x{1}")]
+    InvalidDimensions(usize, usize),
+
     #[error("Decompression error")]
     DecompressionError(#[from] flate2::DecompressError),
 
@@ -23,6 +29,18 @@ pub enum ScreenError {
 
     #[error("Not all blocks were updated by a supposed keyframe")]
     KeyframeInvalid,
+
+    #[error("Invalid image block format: {0}")]
+    InvalidBlockFormat(u8),
+
+    #[error("Diff block rows {0}..{1} don't fit in the block")]
+    InvalidDiffBlock(usize, usize),
+
+    #[error("Invalid palette of {0} bytes")]
+    InvalidPalette(usize),
+
+    #[error("Unsupported Screen Video V2 feature: {0}")]
+    UnsupportedFeature(&'static str),
 }
 
 impl From<ScreenError> for Error {
@@ -43,17 +61,17 @@ pub struct ScreenVideoDecoder {
     last_frame: Option<Vec<u8>>,
 }
 
-struct ByteReader<'a> {
+pub(crate) struct ByteReader<'a> {
     data: &'a [u8],
     pos: usize,
 }
 
 impl<'a> ByteReader<'a> {
-    fn new(data: &'a [u8]) -> Self {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
         Self { data, pos: 0 }
     }
 
-    fn read_byte(&mut self) -> Result<u8, ScreenError> {
+    pub(crate) fn read_byte(&mut self) -> Result<u8, ScreenError> {
         if self.pos >= self.data.len() {
             return Err(ScreenError::UnexpectedEOF);
         }
@@ -62,13 +80,13 @@ impl<'a> ByteReader<'a> {
         Ok(byte)
     }
 
-    fn read_u16be(&mut self) -> Result<u16, ScreenError> {
+    pub(crate) fn read_u16be(&mut self) -> Result<u16, ScreenError> {
         let byte1 = self.read_byte()?;
         let byte2 = self.read_byte()?;
         Ok((byte1 as u16) << 8 | (byte2 as u16))
     }
 
-    fn read_buf_ref(&mut self, length: usize) -> Result<&[u8], ScreenError> {
+    pub(crate) fn read_buf_ref(&mut self, length: usize) -> Result<&'a [u8], ScreenError> {
         if self.pos + length > self.data.len() {
             return Err(ScreenError::UnexpectedEOF);
         }
@@ -76,6 +94,30 @@ impl<'a> ByteReader<'a> {
         self.pos += length;
         Ok(result)
     }
+
+    pub(crate) fn read_to_end(&mut self) -> &'a [u8] {
+        let result = &self.data[self.pos..];
+        self.pos = self.data.len();
+        result
+    }
+}
+
+/// Convert a bottom-up BGR image, as stored by both Screen Video versions,
+/// into a top-down RGBA one.
+pub(crate) fn bgr_to_rgba(data: &[u8], w: usize, h: usize) -> Vec<u8> {
+    let mut rgba = vec![0u8; w * h * 4];
+
+    // convert from BGR to RGBA and flip Y
+    for y in 0..h {
+        let data_row = &data[y * w * 3..(y + 1) * w * 3];
+        let rgba_row = &mut rgba[(h - y - 1) * w * 4..(h - y) * w * 4];
+
+        for (bgr, rgba) in data_row.chunks(3).zip(rgba_row.chunks_mut(4)) {
+            rgba.copy_from_slice(&[bgr[2], bgr[1], bgr[0], 255]);
+        }
+    }
+
+    rgba
 }
 
 impl ScreenVideoDecoder {
@@ -189,17 +231,7 @@ impl VideoDecoder for ScreenVideoDecoder {
             return Err(ScreenError::KeyframeInvalid.into());
         }
 
-        let mut rgba = vec![0u8; w * h * 4];
-
-        // convert from BGR to RGBA and flip Y
-        for y in 0..h {
-            let data_row = &data[y * w * 3..(y + 1) * w * 3];
-            let rgba_row = &mut rgba[(h - y - 1) * w * 4..(h - y) * w * 4];
-
-            for (bgr, rgba) in data_row.chunks(3).zip(rgba_row.chunks_mut(4)) {
-                rgba.copy_from_slice(&[bgr[2], bgr[1], bgr[0], 255]);
-            }
-        }
+        let rgba = bgr_to_rgba(&data, w, h);
 
         self.last_frame = Some(data);
 
@@ -0,0 +1,740 @@
+// The bitstream layout follows the "Screen Video V2" chapter of the SWF
+// specification; the interpretation of diff blocks and zlib priming matches
+// the flashsv2 decoder in FFmpeg.
+
+use crate::decoder::screen::{bgr_to_rgba, ByteReader, ScreenError};
+use crate::decoder::VideoDecoder;
+use ruffle_video::error::Error;
+
+use flate2::Decompress;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+
+/// The palette used by hybrid palette blocks until the stream defines its
+/// own, as `0xRRGGBB`.
+///
+/// The encoder in Flash Player always uses this one.
+#[rustfmt::skip]
+const DEFAULT_PALETTE: [u32; 128] = [
+    0x000000, 0x333333, 0x666666, 0x999999, 0xCCCCCC, 0xFFFFFF,
+    0x330000, 0x660000, 0x990000, 0xCC0000, 0xFF0000, 0x003300,
+    0x006600, 0x009900, 0x00CC00, 0x00FF00, 0x000033, 0x000066,
+    0x000099, 0x0000CC, 0x0000FF, 0x333300, 0x666600, 0x999900,
+    0xCCCC00, 0xFFFF00, 0x003333, 0x006666, 0x009999, 0x00CCCC,
+    0x00FFFF, 0x330033, 0x660066, 0x990099, 0xCC00CC, 0xFF00FF,
+    0xFFFF33, 0xFFFF66, 0xFFFF99, 0xFFFFCC, 0xFF33FF, 0xFF66FF,
+    0xFF99FF, 0xFFCCFF, 0x33FFFF, 0x66FFFF, 0x99FFFF, 0xCCFFFF,
+    0xCCCC33, 0xCCCC66, 0xCCCC99, 0xCCCCFF, 0xCC33CC, 0xCC66CC,
+    0xCC99CC, 0xCCFFCC, 0x33CCCC, 0x66CCCC, 0x99CCCC, 0xFFCCCC,
+    0x999933, 0x999966, 0x9999CC, 0x9999FF, 0x993399, 0x996699,
+    0x99CC99, 0x99FF99, 0x339999, 0x669999, 0xCC9999, 0xFF9999,
+    0x666633, 0x666699, 0x6666CC, 0x6666FF, 0x663366, 0x669966,
+    0x66CC66, 0x66FF66, 0x336666, 0x996666, 0xCC6666, 0xFF6666,
+    0x333366, 0x333399, 0x3333CC, 0x3333FF, 0x336633, 0x339933,
+    0x33CC33, 0x33FF33, 0x663333, 0x993333, 0xCC3333, 0xFF3333,
+    0x003366, 0x336600, 0x660033, 0x006633, 0x330066, 0x663300,
+    0x336699, 0x669933, 0x993366, 0x339966, 0x663399, 0x996633,
+    0x6699CC, 0x99CC66, 0xCC6699, 0x66CC99, 0x9966CC, 0xCC9966,
+    0x99CCFF, 0xCCFF99, 0xFF99CC, 0x99FFCC, 0xCC99FF, 0xFFCC99,
+    0x111111, 0x222222, 0x444444, 0x555555, 0xAAAAAA, 0xBBBBBB,
+    0xDDDDDD, 0xEEEEEE,
+];
+
+/// The pixel format of a single image block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorDepth {
+    /// 24-bit BGR, like Screen Video V1.
+    Bgr,
+
+    /// A mix of 7-bit palette indices and 15-bit RGB colors.
+    HybridPalette,
+}
+
+/// The `IMAGEFORMAT` byte at the start of every non-empty V2 image block.
+struct BlockFormat {
+    color_depth: ColorDepth,
+    has_diff_blocks: bool,
+    zlib_prime_compress_current: bool,
+    zlib_prime_compress_previous: bool,
+}
+
+impl BlockFormat {
+    fn parse(flags: u8) -> Result<Self, ScreenError> {
+        if flags & 0xE0 != 0 {
+            return Err(ScreenError::InvalidBlockFormat(flags));
+        }
+
+        let color_depth = match (flags >> 3) & 0x3 {
+            0 => ColorDepth::Bgr,
+            2 => ColorDepth::HybridPalette,
+            _ => return Err(ScreenError::InvalidBlockFormat(flags)),
+        };
+
+        Ok(Self {
+            color_depth,
+            has_diff_blocks: flags & 0x4 != 0,
+            zlib_prime_compress_current: flags & 0x2 != 0,
+            zlib_prime_compress_previous: flags & 0x1 != 0,
+        })
+    }
+}
+
+/// Screen Video V2 decoder.
+pub struct ScreenVideoV2Decoder {
+    w: usize,
+    h: usize,
+    block_w: usize,
+    block_h: usize,
+
+    tile: Vec<u8>, // acts as a scratch buffer
+
+    last_frame: Option<Vec<u8>>,
+
+    /// The image of the last keyframe, which diff blocks are applied to.
+    keyframe: Option<Vec<u8>>,
+
+    /// The decompressed data of each block of the last keyframe, used as
+    /// the zlib dictionary of blocks that are primed with it.
+    keyframe_blocks: Vec<Vec<u8>>,
+
+    /// The palette of hybrid palette blocks, as `0xRRGGBB`.
+    palette: [u32; 128],
+}
+
+impl ScreenVideoV2Decoder {
+    pub fn new() -> Self {
+        Self {
+            w: 0,
+            h: 0,
+            block_w: 0,
+            block_h: 0,
+            tile: vec![],
+            last_frame: None,
+            keyframe: None,
+            keyframe_blocks: vec![],
+            palette: DEFAULT_PALETTE,
+        }
+    }
+
+    /// Replace the start of the palette with the colors of a `PaletteInfo`
+    /// block.
+    ///
+    /// The block is laid out like an image block, with its zlib data holding
+    /// up to 128 BGR colors.
+    fn read_palette(&mut self, src: &mut ByteReader) -> Result<(), ScreenError> {
+        let data_size = src.read_u16be()? as usize;
+        if data_size == 0 {
+            return Ok(());
+        }
+
+        let mut block = ByteReader::new(src.read_buf_ref(data_size)?);
+        let flags = block.read_byte()?;
+        let format = BlockFormat::parse(flags)?;
+        if format.color_depth != ColorDepth::Bgr
+            || format.has_diff_blocks
+            || format.zlib_prime_compress_current
+            || format.zlib_prime_compress_previous
+        {
+            return Err(ScreenError::InvalidBlockFormat(flags));
+        }
+
+        // One spare byte tells a palette that's too long apart from a full one.
+        let mut colors = [0; 128 * 3 + 1];
+        let len = inflate(block.read_to_end(), None, &mut colors)?;
+        if len % 3 != 0 || len > 128 * 3 {
+            return Err(ScreenError::InvalidPalette(len));
+        }
+
+        for (entry, bgr) in self.palette.iter_mut().zip(colors[..len].chunks_exact(3)) {
+            *entry = u32::from_le_bytes([bgr[0], bgr[1], bgr[2], 0]);
+        }
+        Ok(())
+    }
+
+    fn decode_v2(
+        &mut self,
+        src: &mut ByteReader,
+        data: &mut [u8],
+        stride: usize,
+        updates_reference: bool,
+    ) -> Result<bool, Error> {
+        let blocks_per_row = (0..self.w).step_by(self.block_w).len();
+        let mut is_intra = true;
+        for (yy, row) in data.chunks_mut(stride * self.block_h).enumerate() {
+            let cur_h = (self.h - yy * self.block_h).min(self.block_h);
+            for (xx, x) in (0..self.w).step_by(self.block_w).enumerate() {
+                let cur_w = (self.w - x).min(self.block_w);
+                let block_index = yy * blocks_per_row + xx;
+
+                let data_size = src.read_u16be()? as usize;
+                if data_size == 0 {
+                    is_intra = false;
+                    continue;
+                }
+
+                let mut block = ByteReader::new(src.read_buf_ref(data_size)?);
+                let format = BlockFormat::parse(block.read_byte()?)?;
+
+                let (diff_start, diff_height) = if format.has_diff_blocks {
+                    let start = block.read_byte()? as usize;
+                    let height = block.read_byte()? as usize;
+                    if start + height > cur_h {
+                        return Err(ScreenError::InvalidDiffBlock(start, start + height).into());
+                    }
+
+                    // Rows outside of the diff range are taken from the
+                    // last keyframe.
+                    let keyframe = self
+                        .keyframe
+                        .as_ref()
+                        .ok_or(ScreenError::MissingReferenceFrame)?;
+                    let block_start = yy * self.block_h * stride + x * 3;
+                    for line in 0..cur_h {
+                        let offset = line * stride + x * 3;
+                        row[offset..offset + cur_w * 3]
+                            .copy_from_slice(&keyframe[block_start + line * stride..][..cur_w * 3]);
+                    }
+
+                    is_intra = false;
+                    (start, height)
+                } else {
+                    (0, cur_h)
+                };
+
+                if format.zlib_prime_compress_current {
+                    return Err(ScreenError::UnsupportedFeature(
+                        "zlib priming from the current frame",
+                    )
+                    .into());
+                }
+
+                let dictionary = if format.zlib_prime_compress_previous {
+                    match self.keyframe_blocks.get(block_index) {
+                        Some(dictionary) if !dictionary.is_empty() => Some(dictionary.as_slice()),
+                        _ => return Err(ScreenError::MissingReferenceFrame.into()),
+                    }
+                } else {
+                    None
+                };
+
+                let tile_len = inflate(
+                    block.read_to_end(),
+                    dictionary,
+                    &mut self.tile[..cur_w * cur_h * 3],
+                )?;
+                let tile = &self.tile[..tile_len];
+
+                match format.color_depth {
+                    ColorDepth::Bgr => {
+                        for (line, src) in tile.chunks(cur_w * 3).take(diff_height).enumerate() {
+                            let offset = (diff_start + line) * stride + x * 3;
+                            row[offset..offset + src.len()].copy_from_slice(src);
+                        }
+                    }
+                    ColorDepth::HybridPalette => {
+                        let mut pixels = ByteReader::new(tile);
+                        for line in diff_start..diff_start + diff_height {
+                            let offset = line * stride + x * 3;
+                            for dst in row[offset..offset + cur_w * 3].chunks_mut(3) {
+                                dst.copy_from_slice(&read_hybrid_pixel(
+                                    &mut pixels,
+                                    &self.palette,
+                                )?);
+                            }
+                        }
+                    }
+                }
+
+                if updates_reference {
+                    if let Some(stored) = self.keyframe_blocks.get_mut(block_index) {
+                        stored.clear();
+                        stored.extend_from_slice(tile);
+                    }
+                }
+            }
+        }
+        Ok(is_intra)
+    }
+
+    fn flush(&mut self) {
+        self.last_frame = None;
+        self.keyframe = None;
+        self.keyframe_blocks.clear();
+        self.palette = DEFAULT_PALETTE;
+    }
+}
+
+/// Read one pixel of a hybrid palette block, returning it as BGR.
+fn read_hybrid_pixel(src: &mut ByteReader, palette: &[u32; 128]) -> Result<[u8; 3], ScreenError> {
+    let first = src.read_byte()?;
+    if first & 0x80 != 0 {
+        // 15-bit RGB color
+        let color = (((first & 0x7F) as u16) << 8) | src.read_byte()? as u16;
+        let expand = |c: u16| ((c << 3) | (c >> 2)) as u8;
+        Ok([
+            expand(color & 0x1F),
+            expand((color >> 5) & 0x1F),
+            expand(color >> 10),
+        ])
+    } else {
+        // palette index
+        let color = palette[first as usize];
+        Ok([color as u8, (color >> 8) as u8, (color >> 16) as u8])
+    }
+}
+
+/// Decompress the zlib data of an image block into `out`, returning the
+/// decompressed length.
+///
+/// Blocks which are primed with a dictionary don't carry a zlib header of
+/// their own: they continue a stream that has already produced the
+/// dictionary. That state is rebuilt by feeding the dictionary through
+/// stored deflate blocks before the block's data.
+fn inflate(data: &[u8], dictionary: Option<&[u8]>, out: &mut [u8]) -> Result<usize, ScreenError> {
+    let dictionary = match dictionary {
+        Some(dictionary) => dictionary,
+        None => {
+            let mut decompress = Decompress::new(true);
+            decompress.decompress(data, out, flate2::FlushDecompress::Finish)?;
+            return Ok(decompress.total_out() as usize);
+        }
+    };
+
+    let mut stream =
+        Vec::with_capacity(dictionary.len() + dictionary.len() / 0xFFFF * 5 + data.len() + 5);
+    for chunk in dictionary.chunks(0xFFFF) {
+        // Non-final stored block
+        stream.push(0);
+        stream.extend_from_slice(&(chunk.len() as u16).to_le_bytes());
+        stream.extend_from_slice(&(!(chunk.len() as u16)).to_le_bytes());
+        stream.extend_from_slice(chunk);
+    }
+    stream.extend_from_slice(data);
+
+    let mut primed = vec![0; dictionary.len() + out.len()];
+    let mut decompress = Decompress::new(false);
+    decompress.decompress(&stream, &mut primed, flate2::FlushDecompress::Finish)?;
+    let len = decompress.total_out() as usize - dictionary.len();
+    out[..len].copy_from_slice(&primed[dictionary.len()..dictionary.len() + len]);
+    Ok(len)
+}
+
+impl VideoDecoder for ScreenVideoV2Decoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        // As with V1, frames start with the FrameType + CodecID byte of the
+        // FLV VIDEODATA tag.
+        let frame_type = *encoded_frame.data.first().ok_or(ScreenError::EmptyFrame)?;
+        debug_assert!(frame_type & 0xF == 6);
+
+        match frame_type >> 4 {
+            1 => Ok(FrameDependency::None),
+            2 => Ok(FrameDependency::Past),
+            x => Err(ScreenError::InvalidFrameType(x).into()),
+        }
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let frame_type = *encoded_frame.data.first().ok_or(ScreenError::EmptyFrame)?;
+        let is_keyframe = frame_type >> 4 == 1;
+
+        if !is_keyframe && self.last_frame.is_none() {
+            return Err(ScreenError::MissingReferenceFrame.into());
+        }
+
+        // Need to drop the extra preceding byte
+        let mut br = ByteReader::new(&encoded_frame.data[1..]);
+
+        let hdr0 = br.read_u16be()? as usize;
+        let blk_w = (hdr0 >> 12) * 16 + 16;
+        let w = hdr0 & 0xFFF;
+
+        let hdr1 = br.read_u16be()? as usize;
+        let blk_h = (hdr1 >> 12) * 16 + 16;
+        let h = hdr1 & 0xFFF;
+
+        if w == 0 || h == 0 {
+            return Err(ScreenError::InvalidDimensions(w, h).into());
+        }
+
+        let flags = br.read_byte()?;
+        // An interframe with `IFrameImage` set becomes the reference that
+        // later diff blocks and zlib priming use, just like a keyframe.
+        let is_iframe_image = flags & 0x2 != 0;
+        let has_palette_info = flags & 0x1 != 0;
+
+        if self.w != w || self.h != h || self.block_w != blk_w || self.block_h != blk_h {
+            self.flush();
+            self.tile.resize(blk_w * blk_h * 3, 0);
+            self.w = w;
+            self.h = h;
+            self.block_w = blk_w;
+            self.block_h = blk_h;
+        }
+
+        if has_palette_info {
+            self.read_palette(&mut br)?;
+        }
+
+        let updates_reference = is_keyframe || is_iframe_image;
+        if updates_reference {
+            let num_blocks = (0..w).step_by(blk_w).len() * (0..h).step_by(blk_h).len();
+            self.keyframe_blocks.resize(num_blocks, vec![]);
+        }
+
+        let mut data = self
+            .last_frame
+            .clone()
+            .unwrap_or_else(|| vec![0; w * h * 3]);
+
+        let stride = w * 3;
+
+        let is_intra = self.decode_v2(&mut br, data.as_mut_slice(), stride, updates_reference)?;
+
+        if is_keyframe && !is_intra {
+            return Err(ScreenError::KeyframeInvalid.into());
+        }
+
+        let rgba = bgr_to_rgba(&data, w, h);
+
+        if updates_reference {
+            self.keyframe = Some(data.clone());
+        }
+        self.last_frame = Some(data);
+
+        Ok(DecodedFrame {
+            width: w as u16,
+            height: h as u16,
+            rgba,
+        })
+    }
+}
+
+impl Default for ScreenVideoV2Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use swf::VideoCodec;
+
+    const KEYFRAME: u8 = 0x16;
+    const INTERFRAME: u8 = 0x26;
+
+    fn zlib(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Build an `IMAGEBLOCKV2` with the given format byte, diff range and
+    /// (already compressed) data.
+    fn block(format: u8, diff: Option<(u8, u8)>, data: &[u8]) -> Vec<u8> {
+        let mut body = vec![format];
+        if let Some((start, height)) = diff {
+            body.extend_from_slice(&[start, height]);
+        }
+        body.extend_from_slice(data);
+
+        let mut block = (body.len() as u16).to_be_bytes().to_vec();
+        block.extend(body);
+        block
+    }
+
+    /// Build a `SCREENV2VIDEOPACKET` with 16x16 blocks, prefixed with the FLV
+    /// frame type and codec byte.
+    fn packet(frame_type: u8, width: u16, height: u16, blocks: &[Vec<u8>]) -> Vec<u8> {
+        packet_with_flags(frame_type, width, height, 0, blocks)
+    }
+
+    /// Like `packet`, with the `IFrameImage` and `HasPaletteInfo` flags.
+    /// The palette block, if any, is the first of `blocks`.
+    fn packet_with_flags(
+        frame_type: u8,
+        width: u16,
+        height: u16,
+        flags: u8,
+        blocks: &[Vec<u8>],
+    ) -> Vec<u8> {
+        let mut packet = vec![frame_type];
+        packet.extend_from_slice(&width.to_be_bytes());
+        packet.extend_from_slice(&height.to_be_bytes());
+        packet.push(flags);
+        for block in blocks {
+            packet.extend_from_slice(block);
+        }
+        packet
+    }
+
+    fn decode(decoder: &mut ScreenVideoV2Decoder, data: &[u8]) -> Result<DecodedFrame, Error> {
+        decoder.decode_frame(EncodedFrame {
+            codec: VideoCodec::ScreenVideoV2,
+            data,
+            frame_id: 0,
+        })
+    }
+
+    /// Read a pixel of a decoded frame, as RGBA, in top-down coordinates.
+    fn pixel(frame: &DecodedFrame, x: usize, y: usize) -> [u8; 4] {
+        let offset = (y * frame.width as usize + x) * 4;
+        frame.rgba[offset..offset + 4].try_into().unwrap()
+    }
+
+    fn solid_bgr(width: usize, height: usize, bgr: [u8; 3]) -> Vec<u8> {
+        bgr.repeat(width * height)
+    }
+
+    #[test]
+    fn bgr_keyframe() {
+        // A 20x18 image splits into 16x16, 4x16, 16x2 and 4x2 blocks,
+        // starting from the bottom left.
+        let frame = packet(
+            KEYFRAME,
+            20,
+            18,
+            &[
+                block(0, None, &zlib(&solid_bgr(16, 16, [0, 0, 255]))),
+                block(0, None, &zlib(&solid_bgr(4, 16, [0, 255, 0]))),
+                block(0, None, &zlib(&solid_bgr(16, 2, [255, 0, 0]))),
+                block(0, None, &zlib(&solid_bgr(4, 2, [255, 255, 255]))),
+            ],
+        );
+
+        let mut decoder = ScreenVideoV2Decoder::new();
+        assert!(decoder
+            .preload_frame(EncodedFrame {
+                codec: VideoCodec::ScreenVideoV2,
+                data: &frame,
+                frame_id: 0,
+            })
+            .unwrap()
+            .is_keyframe());
+
+        let decoded = decode(&mut decoder, &frame).unwrap();
+        assert_eq!((decoded.width, decoded.height), (20, 18));
+        assert_eq!(pixel(&decoded, 0, 17), [255, 0, 0, 255]);
+        assert_eq!(pixel(&decoded, 19, 2), [0, 255, 0, 255]);
+        assert_eq!(pixel(&decoded, 0, 0), [0, 0, 255, 255]);
+        assert_eq!(pixel(&decoded, 19, 1), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn hybrid_palette() {
+        // The first row (the bottom one) alternates between palette entry 10
+        // (pure red) and the 15-bit color 0x03E0 (pure green); the rest of the
+        // block uses palette entry 20 (pure blue).
+        let mut data = vec![];
+        for _ in 0..8 {
+            data.extend_from_slice(&[10, 0x83, 0xE0]);
+        }
+        data.extend_from_slice(&[20; 16 * 15]);
+
+        let frame = packet(KEYFRAME, 16, 16, &[block(0x10, None, &zlib(&data))]);
+        let decoded = decode(&mut ScreenVideoV2Decoder::new(), &frame).unwrap();
+
+        assert_eq!(pixel(&decoded, 0, 15), [255, 0, 0, 255]);
+        assert_eq!(pixel(&decoded, 1, 15), [0, 255, 0, 255]);
+        assert_eq!(pixel(&decoded, 0, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn custom_palette() {
+        // Entry 0 becomes pure green and entry 1 pure blue (both as BGR);
+        // entry 2 keeps the default mid grey.
+        let palette = block(0, None, &zlib(&[0, 255, 0, 255, 0, 0]));
+        let mut data = vec![0; 16];
+        data.extend_from_slice(&[1; 16 * 14]);
+        data.extend_from_slice(&[2; 16]);
+
+        let frame = packet_with_flags(
+            KEYFRAME,
+            16,
+            16,
+            0x01,
+            &[palette, block(0x10, None, &zlib(&data))],
+        );
+        let mut decoder = ScreenVideoV2Decoder::new();
+        let decoded = decode(&mut decoder, &frame).unwrap();
+
+        assert_eq!(pixel(&decoded, 0, 15), [0, 255, 0, 255]);
+        assert_eq!(pixel(&decoded, 0, 8), [0, 0, 255, 255]);
+        assert_eq!(pixel(&decoded, 0, 0), [0x66, 0x66, 0x66, 255]);
+
+        // The palette carries over to later frames.
+        let interframe = packet(
+            INTERFRAME,
+            16,
+            16,
+            &[block(0x10, None, &zlib(&[1; 16 * 16]))],
+        );
+        let decoded = decode(&mut decoder, &interframe).unwrap();
+        assert_eq!(pixel(&decoded, 8, 8), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn invalid_palette() {
+        let palette = block(0, None, &zlib(&[0, 255]));
+        let frame = packet_with_flags(KEYFRAME, 16, 16, 0x01, &[palette]);
+        assert!(decode(&mut ScreenVideoV2Decoder::new(), &frame).is_err());
+    }
+
+    #[test]
+    fn iframe_image_replaces_reference() {
+        let mut decoder = ScreenVideoV2Decoder::new();
+        let keyframe = packet(
+            KEYFRAME,
+            16,
+            16,
+            &[block(0, None, &zlib(&solid_bgr(16, 16, [0, 0, 255])))],
+        );
+        decode(&mut decoder, &keyframe).unwrap();
+
+        let iframe_image = packet_with_flags(
+            INTERFRAME,
+            16,
+            16,
+            0x02,
+            &[block(0, None, &zlib(&solid_bgr(16, 16, [255, 0, 0])))],
+        );
+        decode(&mut decoder, &iframe_image).unwrap();
+
+        let interframe = packet(
+            INTERFRAME,
+            16,
+            16,
+            &[block(0, None, &zlib(&solid_bgr(16, 16, [255, 255, 255])))],
+        );
+        decode(&mut decoder, &interframe).unwrap();
+
+        // The rows outside of this diff block come from the IFrameImage
+        // rather than from the keyframe.
+        let interframe = packet(
+            INTERFRAME,
+            16,
+            16,
+            &[block(
+                0x04,
+                Some((0, 1)),
+                &zlib(&solid_bgr(16, 1, [0, 255, 0])),
+            )],
+        );
+        let decoded = decode(&mut decoder, &interframe).unwrap();
+
+        assert_eq!(pixel(&decoded, 0, 15), [0, 255, 0, 255]);
+        assert_eq!(pixel(&decoded, 0, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn empty_frame() {
+        let mut decoder = ScreenVideoV2Decoder::new();
+        let empty = EncodedFrame {
+            codec: VideoCodec::ScreenVideoV2,
+            data: &[],
+            frame_id: 0,
+        };
+        assert!(decoder.preload_frame(empty).is_err());
+        assert!(decode(&mut decoder, &[]).is_err());
+        assert!(decode(&mut decoder, &[KEYFRAME]).is_err());
+        assert!(decode(&mut decoder, &packet(KEYFRAME, 0, 0, &[])).is_err());
+    }
+
+    #[test]
+    fn interframe_diff_block() {
+        let mut decoder = ScreenVideoV2Decoder::new();
+        let keyframe = packet(
+            KEYFRAME,
+            16,
+            16,
+            &[block(0, None, &zlib(&solid_bgr(16, 16, [0, 0, 255])))],
+        );
+        decode(&mut decoder, &keyframe).unwrap();
+
+        // Replace rows 2 and 3 (counting from the bottom) with green.
+        let interframe = packet(
+            INTERFRAME,
+            16,
+            16,
+            &[block(
+                0x04,
+                Some((2, 2)),
+                &zlib(&solid_bgr(16, 2, [0, 255, 0])),
+            )],
+        );
+        let decoded = decode(&mut decoder, &interframe).unwrap();
+
+        assert_eq!(pixel(&decoded, 0, 15), [255, 0, 0, 255]);
+        assert_eq!(pixel(&decoded, 0, 13), [0, 255, 0, 255]);
+        assert_eq!(pixel(&decoded, 15, 12), [0, 255, 0, 255]);
+        assert_eq!(pixel(&decoded, 0, 11), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn interframe_keeps_unchanged_blocks() {
+        let mut decoder = ScreenVideoV2Decoder::new();
+        let keyframe = packet(
+            KEYFRAME,
+            32,
+            16,
+            &[
+                block(0, None, &zlib(&solid_bgr(16, 16, [0, 0, 255]))),
+                block(0, None, &zlib(&solid_bgr(16, 16, [0, 0, 255]))),
+            ],
+        );
+        decode(&mut decoder, &keyframe).unwrap();
+
+        let interframe = packet(
+            INTERFRAME,
+            32,
+            16,
+            &[
+                vec![0, 0],
+                block(0, None, &zlib(&solid_bgr(16, 16, [255, 0, 0]))),
+            ],
+        );
+        let decoded = decode(&mut decoder, &interframe).unwrap();
+
+        assert_eq!(pixel(&decoded, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&decoded, 16, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn zlib_priming_from_keyframe() {
+        let mut decoder = ScreenVideoV2Decoder::new();
+        let keyframe = packet(
+            KEYFRAME,
+            16,
+            16,
+            &[block(0, None, &zlib(&solid_bgr(16, 16, [0, 0, 255])))],
+        );
+        decode(&mut decoder, &keyframe).unwrap();
+
+        // A headerless deflate stream continuing after the keyframe block's
+        // data, made of a single final stored block.
+        let pixels = solid_bgr(16, 16, [0, 255, 0]);
+        let mut data = vec![1];
+        data.extend_from_slice(&(pixels.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(!(pixels.len() as u16)).to_le_bytes());
+        data.extend_from_slice(&pixels);
+
+        let interframe = packet(INTERFRAME, 16, 16, &[block(0x01, None, &data)]);
+        let decoded = decode(&mut decoder, &interframe).unwrap();
+
+        assert_eq!(pixel(&decoded, 8, 8), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn interframe_without_keyframe() {
+        let interframe = packet(INTERFRAME, 16, 16, &[vec![0, 0]]);
+        assert!(decode(&mut ScreenVideoV2Decoder::new(), &interframe).is_err());
+    }
+
+    #[test]
+    fn keyframe_with_missing_blocks() {
+        let keyframe = packet(KEYFRAME, 16, 16, &[vec![0, 0]]);
+        assert!(decode(&mut ScreenVideoV2Decoder::new(), &keyframe).is_err());
+    }
+}
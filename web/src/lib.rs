@@ -9,6 +9,7 @@ mod ui;
 
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Error as JsError, Function, Object, Promise, Uint8Array};
+use ruffle_core::backend::local_connection::LocalConnectionHub;
 use ruffle_core::compatibility_rules::CompatibilityRules;
 use ruffle_core::config::Letterbox;
 use ruffle_core::context::UpdateContext;
@@ -50,6 +51,9 @@ thread_local! {
     static INSTANCES: RefCell<Arena<RefCell<RuffleInstance>>> = RefCell::new(Arena::new());
 
     static CURRENT_CONTEXT: RefCell<Option<*mut UpdateContext<'static, 'static>>> = RefCell::new(None);
+
+    /// Lets the players of the page talk to each other over `LocalConnection`.
+    static LOCAL_CONNECTION_HUB: LocalConnectionHub = LocalConnectionHub::new();
 }
 
 type AnimationHandler = Closure<dyn FnMut(f64)>;
@@ -542,6 +546,8 @@ impl Ruffle {
             }
         };
 
+        builder = builder.with_local_connection(LOCAL_CONNECTION_HUB.with(|hub| hub.backend()));
+
         let default_quality = if ruffle_web_common::is_mobile_or_tablet() {
             tracing::info!("Running on a mobile device; defaulting to low quality");
             StageQuality::Low
@@ -1463,6 +1469,7 @@ fn web_to_ruffle_key_code(key_code: &str) -> KeyCode {
         "Enter" => KeyCode::Return,
         "ShiftLeft" | "ShiftRight" => KeyCode::Shift,
         "ControlLeft" | "ControlRight" => KeyCode::Control,
+        "MetaLeft" | "MetaRight" => KeyCode::Command,
         "AltLeft" | "AltRight" => KeyCode::Alt,
         "CapsLock" => KeyCode::CapsLock,
         "Escape" => KeyCode::Escape,
@@ -1,7 +1,8 @@
 //! Navigator backend for web
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use js_sys::{Array, ArrayBuffer, Function, Promise, Reflect, Uint8Array};
 use ruffle_core::backend::navigator::{
-    NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response,
+    NavigationMethod, NavigatorBackend, OwnedFuture, Request, Response, ResponseBody,
+    StreamingResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
@@ -11,10 +12,11 @@ use tracing_subscriber::layer::Layered;
 use tracing_subscriber::Registry;
 use tracing_wasm::WASMLayer;
 use url::Url;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
-    window, Blob, BlobPropertyBag, Request as WebRequest, RequestInit, Response as WebResponse,
+    window, Blob, BlobPropertyBag, Headers, Request as WebRequest, RequestInit,
+    Response as WebResponse,
 };
 
 pub struct WebNavigatorBackend {
@@ -161,6 +163,11 @@ impl NavigatorBackend for WebNavigatorBackend {
     }
 
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
+        let fetch = self.fetch_streaming(request);
+        Box::pin(async move { fetch.await?.into_response().await })
+    }
+
+    fn fetch_streaming(&self, request: Request) -> OwnedFuture<StreamingResponse, Error> {
         let url = self.resolve_url(request.url()).into_owned();
 
         Box::pin(async move {
@@ -194,6 +201,17 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
+            if !request.headers().is_empty() {
+                let headers =
+                    Headers::new().map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+                for (name, value) in request.headers() {
+                    headers
+                        .append(name, value)
+                        .map_err(|_| Error::FetchError(format!("Invalid header {name}")))?;
+                }
+                init.headers(&headers);
+            }
+
             let request = WebRequest::new_with_str_and_init(&url, &init)
                 .map_err(|_| Error::FetchError(format!("Unable to create request for {url}")))?;
 
@@ -205,31 +223,54 @@ impl NavigatorBackend for WebNavigatorBackend {
             let response: WebResponse = fetchval
                 .dyn_into()
                 .map_err(|_| Error::FetchError("Fetch result wasn't a WebResponse".to_string()))?;
+            let url = response.url();
+            let status = response.status();
+            let redirected = response.redirected();
+            let headers = js_sys::try_iter(&response.headers())
+                .ok()
+                .flatten()
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| {
+                            let entry: Array = entry.ok()?.dyn_into().ok()?;
+                            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
             if !response.ok() {
-                return Err(Error::FetchError(format!(
-                    "HTTP status is not ok, got {}",
-                    response.status_text()
-                )));
+                return Err(Error::HttpNotOk {
+                    url,
+                    status,
+                    redirected,
+                    headers,
+                });
             }
 
-            let url = response.url();
-
-            let body: ArrayBuffer = JsFuture::from(
-                response
-                    .array_buffer()
-                    .map_err(|_| Error::FetchError("Got JS error".to_string()))?,
-            )
-            .await
-            .map_err(|_| {
-                Error::FetchError("Could not allocate array buffer for response".to_string())
-            })?
-            .dyn_into()
-            .map_err(|_| {
-                Error::FetchError("array_buffer result wasn't an ArrayBuffer".to_string())
-            })?;
-            let body = Uint8Array::new(&body).to_vec();
-
-            Ok(Response { url, body })
+            // Read the body through its stream, so that it can be used before it
+            // has been downloaded in full.
+            let body = Reflect::get(&response, &"body".into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            let body: Box<dyn ResponseBody> = if body.is_null() {
+                Box::new(WebResponseBody(None))
+            } else {
+                let get_reader: Function = Reflect::get(&body, &"getReader".into())
+                    .and_then(|get_reader| get_reader.dyn_into())
+                    .map_err(|_| Error::FetchError("Body isn't a ReadableStream".to_string()))?;
+                let reader = get_reader
+                    .call0(&body)
+                    .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+                Box::new(WebResponseBody(Some(reader)))
+            };
+
+            Ok(StreamingResponse {
+                url,
+                status,
+                redirected,
+                headers,
+                body,
+            })
         })
     }
 
@@ -250,3 +291,41 @@ impl NavigatorBackend for WebNavigatorBackend {
         url
     }
 }
+
+/// The body of a fetch response, read from its `ReadableStream` in chunks as they arrive.
+struct WebResponseBody(Option<JsValue>);
+
+impl ResponseBody for WebResponseBody {
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let reader = self.0.clone();
+        Box::pin(async move {
+            let Some(reader) = reader else {
+                return Ok(None);
+            };
+
+            let read: Function = Reflect::get(&reader, &"read".into())
+                .and_then(|read| read.dyn_into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            let promise: Promise = read
+                .call0(&reader)
+                .and_then(|promise| promise.dyn_into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            let result = JsFuture::from(promise)
+                .await
+                .map_err(|_| Error::FetchError("Could not read response body".to_string()))?;
+
+            let is_done = Reflect::get(&result, &"done".into())
+                .ok()
+                .and_then(|done| done.as_bool())
+                .unwrap_or(true);
+            if is_done {
+                return Ok(None);
+            }
+
+            let chunk: Uint8Array = Reflect::get(&result, &"value".into())
+                .and_then(|value| value.dyn_into())
+                .map_err(|_| Error::FetchError("Body chunk wasn't a Uint8Array".to_string()))?;
+            Ok(Some(chunk.to_vec()))
+        })
+    }
+}
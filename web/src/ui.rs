@@ -61,6 +61,12 @@ impl UiBackend for WebUiBackend {
         tracing::warn!("set clipboard not implemented");
     }
 
+    fn clipboard_content(&mut self) -> String {
+        // TODO: the async Clipboard API can only be read from a user-initiated paste event
+        tracing::warn!("get clipboard not implemented");
+        String::new()
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         match self.js_player.set_fullscreen(is_full) {
             Ok(_) => Ok(()),